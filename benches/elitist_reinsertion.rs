@@ -0,0 +1,83 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::{BatchSize, BenchmarkId, Criterion};
+use genevo::{
+    algorithm::EvaluatedPopulation,
+    operator::{prelude::*, ReinsertionOp},
+    prelude::*,
+    random::{get_rng, Rng},
+};
+use std::sync::Arc;
+
+const POPULATION_SIZE: usize = 100_000;
+
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessFunction<Vec<u32>, u32> for FitnessCalc {
+    fn fitness_of(&self, genome: &Vec<u32>) -> u32 {
+        genome[0]
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().map(|x| *x as u64).sum::<u64>() / fitness_values.len() as u64) as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        u32::MAX
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+fn random_genomes<R>(rng: &mut R, size: usize) -> Vec<Vec<u32>>
+where
+    R: Rng,
+{
+    (0..size)
+        .map(|_| vec![rng.gen_range(0..1_000_000)])
+        .collect()
+}
+
+fn bench_elitist_reinsertion(c: &mut Criterion) {
+    let mut rng = get_rng([42; 32]);
+    let individuals = random_genomes(&mut rng, POPULATION_SIZE);
+    let fitness_values: Vec<u32> = individuals.iter().map(|x| x[0]).collect();
+    let evaluated = EvaluatedPopulation::new(Arc::new(individuals), fitness_values, 0, 0, 0);
+    let offspring = random_genomes(&mut rng, POPULATION_SIZE);
+
+    let mut group = c.benchmark_group("elitist reinsertion into a population of 100k");
+    let reinserters = [
+        (
+            "elite count 10",
+            ElitistReinserter::with_elite_count(FitnessCalc, 10),
+        ),
+        (
+            "replace ratio 0.7",
+            ElitistReinserter::new(FitnessCalc, true, 0.7),
+        ),
+        (
+            "best of both",
+            ElitistReinserter::new(FitnessCalc, false, 1.0),
+        ),
+    ];
+    for (name, reinserter) in reinserters.iter() {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            reinserter,
+            |b, reinserter| {
+                b.iter_batched(
+                    || offspring.clone(),
+                    |mut offspring| reinserter.combine(&mut offspring, &evaluated, &mut rng),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+}
+
+criterion_group!(benches, bench_elitist_reinsertion);
+criterion_main!(benches);
@@ -0,0 +1,130 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::{BenchmarkId, Criterion};
+use genevo::{
+    operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*, random::get_rng,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Counts the number of heap allocations, so that the allocations per
+/// generation can be reported.
+struct CountingAllocator;
+
+static NUM_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const GENOME_LENGTH: usize = 100;
+
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessFunction<Vec<u8>, usize> for FitnessCalc {
+    fn fitness_of(&self, genome: &Vec<u8>) -> usize {
+        genome.iter().filter(|value| **value == b'a').count()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        GENOME_LENGTH
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+type TextAlgorithm = GeneticAlgorithm<
+    Vec<u8>,
+    usize,
+    FitnessCalc,
+    MaximizeSelector,
+    MultiPointCrossBreeder,
+    RandomValueMutator<Vec<u8>>,
+    ElitistReinserter<Vec<u8>, usize, FitnessCalc>,
+>;
+
+fn text_algorithm(population_size: usize, genome_reuse: bool) -> TextAlgorithm {
+    let initial_population: Population<Vec<u8>> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(GENOME_LENGTH, 32, 126))
+        .of_size(population_size)
+        .using_seed([42; 32]);
+    genetic_algorithm()
+        .with_evaluation(FitnessCalc)
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(MultiPointCrossBreeder::new(3))
+        .with_mutation(RandomValueMutator::new(0.02, 32, 126))
+        .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+        .with_initial_population(initial_population)
+        .with_genome_reuse(genome_reuse)
+        .build()
+}
+
+/// Returns the number of heap allocations per generation. Without reusing
+/// genomes every selected parent and every individual that is taken over
+/// from the old population is cloned into a fresh allocation.
+fn allocations_per_generation(population_size: usize, genome_reuse: bool) -> usize {
+    let mut algorithm = text_algorithm(population_size, genome_reuse);
+    let mut rng = get_rng([7; 32]);
+    let num_generations = 100;
+    let before = NUM_ALLOCATIONS.load(Ordering::Relaxed);
+    for iteration in 1..=num_generations {
+        algorithm.next(iteration, &mut rng).unwrap();
+    }
+    let allocations = NUM_ALLOCATIONS.load(Ordering::Relaxed) - before;
+    allocations / num_generations as usize
+}
+
+fn bench_generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("process generation of Vec<u8> genomes");
+    for population_size in [100, 1000] {
+        let cloning = allocations_per_generation(population_size, false);
+        let reusing = allocations_per_generation(population_size, true);
+        println!(
+            "population size {}: {} allocations per generation, {} without reusing genomes",
+            population_size, reusing, cloning
+        );
+        assert!(reusing < cloning, "reusing genomes must save allocations");
+
+        let mut algorithm = text_algorithm(population_size, true);
+        let mut rng = get_rng([7; 32]);
+        let mut iteration = 0;
+        group.bench_with_input(
+            BenchmarkId::from_parameter(population_size),
+            &population_size,
+            |b, _| {
+                b.iter(|| {
+                    iteration += 1;
+                    algorithm.next(iteration, &mut rng).unwrap()
+                })
+            },
+        );
+    }
+}
+
+criterion_group!(benches, bench_generation);
+criterion_main!(benches);
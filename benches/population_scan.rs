@@ -0,0 +1,100 @@
+#[macro_use]
+extern crate criterion;
+
+use criterion::{BenchmarkId, Criterion};
+use genevo::{
+    algorithm::EvaluatedPopulation,
+    operator::{prelude::*, SelectionOp},
+    prelude::*,
+    random::{get_rng, Rng},
+    statistic::{fitness_histogram, GenerationStats},
+};
+use std::sync::Arc;
+
+const POPULATION_SIZE: usize = 50_000;
+
+const GENOME_LENGTH: usize = 16;
+
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessFunction<Vec<u8>, usize> for FitnessCalc {
+    fn fitness_of(&self, genome: &Vec<u8>) -> usize {
+        genome.iter().map(|value| *value as usize).sum()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        GENOME_LENGTH * u8::MAX as usize
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+fn evaluated_population() -> EvaluatedPopulation<Vec<u8>, usize> {
+    let mut rng = get_rng([42; 32]);
+    let individuals: Vec<Vec<u8>> = (0..POPULATION_SIZE)
+        .map(|_| (0..GENOME_LENGTH).map(|_| rng.gen()).collect())
+        .collect();
+    let fitness_values: Vec<usize> = individuals
+        .iter()
+        .map(|genome| FitnessCalc.fitness_of(genome))
+        .collect();
+    let highest = *fitness_values
+        .iter()
+        .max()
+        .expect("population is not empty");
+    let lowest = *fitness_values
+        .iter()
+        .min()
+        .expect("population is not empty");
+    let average = FitnessCalc.average(&fitness_values);
+    EvaluatedPopulation::new(
+        Arc::new(individuals),
+        fitness_values,
+        highest,
+        lowest,
+        average,
+    )
+}
+
+fn bench_selection(c: &mut Criterion) {
+    let evaluated = evaluated_population();
+    let mut rng = get_rng([42; 32]);
+
+    let mut group = c.benchmark_group("selection from a population of 50k");
+    group.bench_function(BenchmarkId::from_parameter("maximize"), |b| {
+        let selector = MaximizeSelector::new(0.7, 2);
+        b.iter(|| selector.select_from(&evaluated, &mut rng))
+    });
+    group.bench_function(BenchmarkId::from_parameter("tournament"), |b| {
+        let selector = TournamentSelector::new(0.7, 2, 4, 1.0, false);
+        b.iter(|| selector.select_from(&evaluated, &mut rng))
+    });
+    group.bench_function(BenchmarkId::from_parameter("roulette wheel"), |b| {
+        let selector = RouletteWheelSelector::new(0.7, 2);
+        b.iter(|| selector.select_from(&evaluated, &mut rng))
+    });
+    group.finish();
+}
+
+fn bench_statistics(c: &mut Criterion) {
+    let evaluated = evaluated_population();
+
+    let mut group = c.benchmark_group("statistics of a population of 50k");
+    group.bench_function(BenchmarkId::from_parameter("generation stats"), |b| {
+        b.iter(|| GenerationStats::from_scalar_evaluated(&evaluated))
+    });
+    group.bench_function(BenchmarkId::from_parameter("fitness histogram"), |b| {
+        b.iter(|| fitness_histogram(&evaluated, &FitnessCalc, 20))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_selection, bench_statistics);
+criterion_main!(benches);
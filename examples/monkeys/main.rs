@@ -2,9 +2,7 @@
 //! known as the
 //! [infinite monkey theorem](https://en.wikipedia.org/wiki/Infinite_monkey_theorem).
 
-use genevo::{
-    operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*, types::fmt::Display,
-};
+use genevo::{operator::prelude::*, population::IntGenomeSpec, prelude::*, types::fmt::Display};
 
 // const TARGET_TEXT: &str = "See how a genius creates a legend";
 const TARGET_TEXT: &str = "Be not afraid of greatness! Some are great, some achieve greatness, \
@@ -42,16 +40,18 @@ impl Default for Parameter {
 type Text = String;
 
 /// The genotype
-type TextGenome = Vec<u8>;
+///
+/// The length of the text is known at compile time, so the genome is an
+/// array, which is cloned without allocating memory on the heap.
+type TextGenome = [u8; TARGET_TEXT.len()];
 
 /// How do the genes of the genotype show up in the phenotype
-trait AsPhenotype {
-    fn as_text(&self) -> Text;
-}
+#[derive(Clone, Debug)]
+struct TextDecoder;
 
-impl AsPhenotype for TextGenome {
-    fn as_text(&self) -> Text {
-        String::from_utf8(self.to_vec()).unwrap()
+impl GenotypeToPhenotype<TextGenome, Text> for TextDecoder {
+    fn decode(&self, genome: &TextGenome) -> Text {
+        String::from_utf8(genome.to_vec()).unwrap()
     }
 }
 
@@ -69,7 +69,7 @@ impl FitnessFunction<TextGenome, usize> for FitnessCalc {
             }
         }
         let fraction = score as f32 / TARGET_TEXT.len() as f32;
-        (fraction * fraction * 100_00. + 0.5).floor() as usize
+        (fraction * fraction * 10_000. + 0.5).floor() as usize
     }
 
     fn average(&self, fitness_values: &[usize]) -> usize {
@@ -77,7 +77,7 @@ impl FitnessFunction<TextGenome, usize> for FitnessCalc {
     }
 
     fn highest_possible_fitness(&self) -> usize {
-        100_00
+        10_000
     }
 
     fn lowest_possible_fitness(&self) -> usize {
@@ -88,8 +88,11 @@ impl FitnessFunction<TextGenome, usize> for FitnessCalc {
 fn main() {
     let params = Parameter::default();
 
+    // the printable ASCII characters from ' ' (32) to '~' (126)
+    let genome_spec = IntGenomeSpec::new(TARGET_TEXT.len(), 32, 126);
+
     let initial_population: Population<TextGenome> = build_population()
-        .with_genome_builder(ValueEncodedGenomeBuilder::new(TARGET_TEXT.len(), 32, 126))
+        .with_genome_builder(genome_spec.clone())
         .of_size(params.population_size)
         .uniform_at_random();
 
@@ -101,19 +104,21 @@ fn main() {
                 params.num_individuals_per_parents,
             ))
             .with_crossover(MultiPointCrossBreeder::new(params.num_crossover_points))
-            .with_mutation(RandomValueMutator::new(params.mutation_rate, 32, 126))
+            .with_mutation(genome_spec.mutator(params.mutation_rate))
             .with_reinsertion(ElitistReinserter::new(
                 FitnessCalc,
                 true,
                 params.reinsertion_ratio,
             ))
             .with_initial_population(initial_population)
+            .with_decoder(TextDecoder)
             .build(),
     )
     .until(or(
         FitnessLimit::new(FitnessCalc.highest_possible_fitness()),
         GenerationLimit::new(params.generation_limit),
     ))
+    .report_every(10)
     .build();
 
     println!("Starting Shakespeare's Monkeys with: {:?}", params);
@@ -133,9 +138,7 @@ fn main() {
                     step.duration.fmt(),
                     step.processing_time.fmt()
                 );
-                println!("      {}", best_solution.solution.genome.as_text());
-                //                println!("| population: [{}]", result.population.iter().map(|g| g.as_text())
-                //                    .collect::<Vec<String>>().join("], ["));
+                println!("      {}", best_solution.phenotype);
             },
             Ok(SimResult::Final(step, processing_time, duration, stop_reason)) => {
                 let best_solution = step.result.best_solution;
@@ -149,7 +152,7 @@ fn main() {
                     best_solution.generation,
                     processing_time.fmt()
                 );
-                println!("      {}", best_solution.solution.genome.as_text());
+                println!("      {}", best_solution.phenotype);
                 break;
             },
             Err(error) => {
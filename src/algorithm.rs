@@ -4,10 +4,14 @@
 
 use crate::{
     genetic::{Fitness, Genotype},
-    random::Prng,
+    random::SplittableRng,
+};
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Debug},
+    sync::{Arc, Mutex},
 };
-use chrono::{DateTime, Local, Utc};
-use std::{error::Error, fmt::Debug, rc::Rc};
 
 /// An `Algorithm` defines the steps to be processed in a
 /// `simulation::Simulation`. The `Simulation` uses an implementation of an
@@ -16,9 +20,87 @@ pub trait Algorithm {
     type Output: Clone + Debug + PartialEq;
     type Error: Error + Clone + Debug + PartialEq;
 
-    fn next(&mut self, iteration: u64, rng: &mut Prng) -> Result<Self::Output, Self::Error>;
+    /// Processes the given iteration using the given random number
+    /// generator.
+    fn next<R>(&mut self, iteration: u64, rng: &mut R) -> Result<Self::Output, Self::Error>
+    where
+        R: SplittableRng;
 
     fn reset(&mut self) -> Result<bool, Self::Error>;
+
+    /// Completes the output of the last iteration when the simulation is
+    /// finished.
+    ///
+    /// The `simulation::Simulator` calls this function once with the output
+    /// of the last iteration before it returns the `SimResult::Final`. It can
+    /// be used to add results to the final output that are too expensive to
+    /// be collected in every iteration. The default implementation does
+    /// nothing.
+    fn finish(&mut self, _output: &mut Self::Output) {}
+}
+
+/// An `Algorithm` whose state can be saved and restored later on in order to
+/// resume a `simulation::Simulation`.
+///
+/// Restoring a saved state and continuing the algorithm with the same random
+/// number generator must produce exactly the same results as if the
+/// algorithm has never been interrupted. State that is not part of the saved
+/// state, e.g. the state of the operators of a `ga::GeneticAlgorithm`, must
+/// be documented by the implementation and restored by the user.
+pub trait Resumable: Algorithm {
+    /// The saved state of the algorithm.
+    type SavedState;
+
+    /// Saves the current state of this algorithm.
+    fn save_state(&self) -> Self::SavedState;
+
+    /// Restores the state of this algorithm from the given saved state.
+    fn restore_state(&mut self, state: Self::SavedState);
+}
+
+/// An `Algorithm` that can be restarted from a fresh population while
+/// keeping the best solutions found so far, e.g. when the population has
+/// converged prematurely.
+pub trait Restartable: Algorithm {
+    /// Replaces the current population with a freshly generated population
+    /// into which up to `num_best` of the best solutions found so far are
+    /// injected. The random number generator is used to generate the fresh
+    /// population.
+    ///
+    /// Returns whether the population has been replaced.
+    fn restart_preserving_best<R>(
+        &mut self,
+        num_best: usize,
+        rng: &mut R,
+    ) -> Result<bool, Self::Error>
+    where
+        R: SplittableRng;
+}
+
+/// An `Algorithm` whose output of an iteration contains the fitness of the
+/// best solution of that iteration. It is used to compare the outputs of
+/// several simulations of the same algorithm.
+pub trait BestFitness: Algorithm {
+    /// The type of the fitness values of the solutions.
+    type Fitness: Fitness;
+
+    /// Returns the fitness of the best solution contained in the given output
+    /// of one iteration of the algorithm.
+    fn best_fitness_of(output: &<Self as Algorithm>::Output) -> Self::Fitness;
+
+    /// Compares the best solutions contained in the given outputs. Returns
+    /// `Ordering::Greater` if the best solution of `output1` is better than
+    /// the one of `output2`.
+    ///
+    /// The default implementation compares the fitness values returned by
+    /// `best_fitness_of`, i.e. the higher fitness is better. Algorithms that
+    /// can minimize override it.
+    fn compare_best_fitness(
+        output1: &<Self as Algorithm>::Output,
+        output2: &<Self as Algorithm>::Output,
+    ) -> Ordering {
+        Self::best_fitness_of(output1).cmp(&Self::best_fitness_of(output2))
+    }
 }
 
 pub trait OptimizationResult<G, F>
@@ -37,6 +119,11 @@ where
 /// simulation with more sophisticated fitness calculations this can improve
 /// performance.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
 pub struct Evaluated<G, F>
 where
     G: Genotype,
@@ -53,7 +140,12 @@ where
 /// If the solution is finished this is the overall best solution found by the
 /// simulation.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct BestSolution<G, F>
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct BestSolution<G, F, P = G>
 where
     G: Genotype,
     F: Fitness,
@@ -62,8 +154,142 @@ where
     pub generation: u64,
     /// The evaluated `genetic::Genotype` that is considered to be best.
     pub solution: Evaluated<G, F>,
+    /// The phenotype of the solution as decoded by the
+    /// `genetic::GenotypeToPhenotype` decoder of the algorithm.
+    pub phenotype: P,
+}
+
+/// The `GenomePool` holds spare genomes whose allocations are reused when
+/// the individuals of an `EvaluatedPopulation` are cloned, see
+/// `EvaluatedPopulation::clone_individual`.
+///
+/// The population of a generation is retired into the pool when it is
+/// replaced by the next generation. As soon as the output of the generation
+/// that refers to the retired population has been dropped its genomes become
+/// spare genomes. Thus the pool double buffers the population: the genomes of
+/// the previous generation are overwritten by the clones of the next one.
+/// The genomes that are discarded in a generation, e.g. the offspring that
+/// has not been reinserted, are spare genomes as well. The spare genomes
+/// that are left over at the end of a generation are dropped, so that the
+/// pool does not grow beyond the size of a population and its offspring.
+///
+/// Clones of a `GenomePool` share the spare genomes, but not the retired
+/// population. The spare genomes do not contribute to the value of a pool, so
+/// all pools are equal.
+pub struct GenomePool<G>
+where
+    G: Genotype,
+{
+    spare: Arc<Mutex<Vec<G>>>,
+    retired: Option<Arc<Vec<G>>>,
+}
+
+impl<G> GenomePool<G>
+where
+    G: Genotype,
+{
+    /// Creates a new `GenomePool` without any spare genomes.
+    pub fn new() -> Self {
+        GenomePool {
+            spare: Arc::new(Mutex::new(Vec::new())),
+            retired: None,
+        }
+    }
+
+    /// Retires the given population and replaces the spare genomes by the
+    /// given discarded genomes, e.g. the offspring that has not been
+    /// reinserted. The genomes of the retired population become spare genomes
+    /// on the next call of `reuse_retired` if the population is not
+    /// referenced anywhere else by then.
+    pub fn retire(&mut self, population: Arc<Vec<G>>, discarded: Vec<G>) {
+        if let Ok(mut spare) = self.spare.lock() {
+            *spare = discarded;
+        }
+        self.retired = Some(population);
+    }
+
+    /// Adds the genomes of the retired population to the spare genomes, if
+    /// the retired population is not referenced anywhere else. Otherwise the
+    /// retired population is released.
+    ///
+    /// Returns whether the genomes of the retired population are reused.
+    pub fn reuse_retired(&mut self) -> bool {
+        let mut genomes = match self.retired.take().map(Arc::try_unwrap) {
+            Some(Ok(genomes)) => genomes,
+            _ => return false,
+        };
+        match self.spare.lock() {
+            Ok(mut spare) => {
+                spare.append(&mut genomes);
+                true
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the number of spare genomes.
+    pub fn num_spare(&self) -> usize {
+        self.spare.lock().map(|spare| spare.len()).unwrap_or(0)
+    }
+
+    /// Returns a clone of the given genome that reuses the allocation of a
+    /// spare genome if there is any left.
+    pub fn clone_of(&self, genome: &G) -> G {
+        let spare = self.spare.lock().ok().and_then(|mut spare| spare.pop());
+        match spare {
+            Some(mut clone) => {
+                clone.clone_from(genome);
+                clone
+            },
+            None => genome.clone(),
+        }
+    }
+}
+
+impl<G> Clone for GenomePool<G>
+where
+    G: Genotype,
+{
+    fn clone(&self) -> Self {
+        GenomePool {
+            spare: self.spare.clone(),
+            retired: None,
+        }
+    }
+}
+
+impl<G> Default for GenomePool<G>
+where
+    G: Genotype,
+{
+    fn default() -> Self {
+        GenomePool::new()
+    }
+}
+
+impl<G> Debug for GenomePool<G>
+where
+    G: Genotype,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GenomePool")
+            .field("num_spare", &self.num_spare())
+            .field("retired", &self.retired.is_some())
+            .finish()
+    }
+}
+
+impl<G> PartialEq for GenomePool<G>
+where
+    G: Genotype,
+{
+    fn eq(&self, _: &GenomePool<G>) -> bool {
+        true
+    }
 }
 
+impl<G> Eq for GenomePool<G> where G: Genotype {}
+
 /// The `EvaluatedPopulation` holds the results of the evaluation stage of
 /// the genetic algorithm. It is used to pass these values to the
 /// `operator::SelectionOp` to enable this operator to do its job.
@@ -72,10 +298,18 @@ where
 /// their normalized fitness values and highest and average fitness of the
 /// population.
 ///
+/// It also holds the direction of the optimization. By default higher fitness
+/// values are better. If the population is minimized lower fitness values are
+/// better, see `with_minimize`. The `ga::GeneticAlgorithm` minimizes the
+/// population if its `operator::SelectionOp` minimizes, so that the
+/// operators that compare the fitness values of individuals, e.g. by
+/// `compare_fitness`, agree on which individuals are the best ones.
+///
 /// As the information in this struct is only used to pass the output of the
 /// evaluation stage to the selection operator and this happens once for every
 /// population the types of the fields are designed to avoid cloning of whole
-/// data structures. To be able to change the fields internally later when
+/// data structures. The operators clone single individuals by
+/// `clone_individual`, which reuses the spare genomes of a `GenomePool`. To be able to change the fields internally later when
 /// new optimization are found the fields are kept private.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EvaluatedPopulation<G, F>
@@ -83,11 +317,13 @@ where
     G: Genotype,
     F: Fitness,
 {
-    individuals: Rc<Vec<G>>,
+    individuals: Arc<Vec<G>>,
     fitness_values: Vec<F>,
     highest_fitness: F,
     lowest_fitness: F,
     average_fitness: F,
+    minimize: bool,
+    genome_pool: Option<GenomePool<G>>,
 }
 
 impl<G, F> EvaluatedPopulation<G, F>
@@ -97,7 +333,7 @@ where
 {
     /// Construct a new instance of the `EvaluatedPopulation` struct.
     pub fn new(
-        individuals: Rc<Vec<G>>,
+        individuals: Arc<Vec<G>>,
         fitness_values: Vec<F>,
         highest_fitness: F,
         lowest_fitness: F,
@@ -109,11 +345,31 @@ where
             highest_fitness,
             lowest_fitness,
             average_fitness,
+            minimize: false,
+            genome_pool: None,
         }
     }
 
+    /// Sets the `GenomePool` whose spare genomes are reused by
+    /// `clone_individual`.
+    pub fn with_genome_pool(mut self, genome_pool: GenomePool<G>) -> Self {
+        self.genome_pool = Some(genome_pool);
+        self
+    }
+
+    /// Sets whether lower fitness values are better than higher ones.
+    pub fn with_minimize(mut self, minimize: bool) -> Self {
+        self.minimize = minimize;
+        self
+    }
+
+    /// Returns whether lower fitness values are better than higher ones.
+    pub fn is_minimize(&self) -> bool {
+        self.minimize
+    }
+
     /// Returns the individuals of the population that has been evaluated.
-    pub fn individuals(&self) -> Rc<Vec<G>> {
+    pub fn individuals(&self) -> Arc<Vec<G>> {
         self.individuals.clone()
     }
 
@@ -147,11 +403,61 @@ where
         &self.average_fitness
     }
 
+    /// Returns the best `genetic::Fitness` value found in the evaluated
+    /// population, which is the lowest value if the population is minimized
+    /// and the highest value otherwise.
+    pub fn best_fitness(&self) -> &F {
+        if self.minimize {
+            &self.lowest_fitness
+        } else {
+            &self.highest_fitness
+        }
+    }
+
+    /// Compares two `genetic::Fitness` values in the direction of the
+    /// optimization. The result is `Ordering::Greater` if the first value is
+    /// better than the second one.
+    pub fn compare_fitness(&self, fitness1: &F, fitness2: &F) -> Ordering {
+        if self.minimize {
+            fitness2.cmp(fitness1)
+        } else {
+            fitness1.cmp(fitness2)
+        }
+    }
+
     /// Returns the individual at the given index.
     pub fn individual(&self, index: usize) -> Option<&G> {
         self.individuals.get(index)
     }
 
+    /// Returns clones of all individuals of the population in the same order
+    /// as `individuals`, see `clone_individual`.
+    pub fn clone_individuals(&self) -> Vec<G> {
+        (0..self.individuals.len())
+            .map(|index| self.clone_individual(index))
+            .collect()
+    }
+
+    /// Returns the `GenomePool` whose spare genomes are reused by
+    /// `clone_individual`, if any.
+    pub fn genome_pool(&self) -> Option<&GenomePool<G>> {
+        self.genome_pool.as_ref()
+    }
+
+    /// Returns a clone of the individual at the given index. The clone reuses
+    /// the allocation of a spare genome of the `GenomePool` of the population
+    /// if there is one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn clone_individual(&self, index: usize) -> G {
+        match self.genome_pool {
+            Some(ref genome_pool) => genome_pool.clone_of(&self.individuals[index]),
+            None => self.individuals[index].clone(),
+        }
+    }
+
     /// Returns the `genetic::Fitness` value of the given individual.
     ///
     /// Note: This function might be more expensive due to the data structure
@@ -177,7 +483,7 @@ where
     /// Note: This function might be more expensive due to the data structure
     /// chosen for this struct. So use it sparingly.
     pub fn evaluated_individual_with_fitness(&self, fitness: &F) -> Option<Evaluated<G, F>> {
-        self.index_of_fitness(&fitness).map(|index| Evaluated {
+        self.index_of_fitness(fitness).map(|index| Evaluated {
             genome: self.individuals[index].clone(),
             fitness: self.fitness_values[index].clone(),
         })
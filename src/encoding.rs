@@ -56,6 +56,29 @@ impl<V> ValueEncoded for Vec<V> {}
 /// using `Vec`.
 impl<V> PermutationEncoded for Vec<V> {}
 
+/// Implementation of a genotype using fixed-size arrays.
+///
+/// Arrays are a good fit for genomes of a length that is known at compile
+/// time, as cloning them does not allocate memory on the heap.
+impl<V, const N: usize> Genotype for [V; N]
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    type Dna = V;
+}
+
+/// Implementation of binary encoded `genetic::Genotype`
+/// using `[bool; N]`.
+impl<const N: usize> BinaryEncoded for [bool; N] {}
+
+/// Implementation of a value encoded `genetic::Genotype`.
+/// using fixed-size arrays.
+impl<V, const N: usize> ValueEncoded for [V; N] {}
+
+/// Implementation of a permutation encoded `genetic::Genotype`
+/// using fixed-size arrays.
+impl<V, const N: usize> PermutationEncoded for [V; N] {}
+
 #[cfg(feature = "fixedbitset")]
 mod fixedbitset_genotype {
     use super::{BinaryEncoded, Genotype};
@@ -0,0 +1,68 @@
+//! The `fixtures` module provides the evaluated populations shared by the
+//! unit tests of the operators and statistics.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    genetic::{Fitness, Genotype},
+};
+use std::sync::Arc;
+
+/// Returns the `EvaluatedPopulation` of the given individuals with the given
+/// fitness values. The highest and lowest fitness are taken from the fitness
+/// values.
+pub(crate) fn evaluated_population<G, F>(
+    individuals: Vec<G>,
+    fitness_values: Vec<F>,
+    average_fitness: F,
+) -> EvaluatedPopulation<G, F>
+where
+    G: Genotype,
+    F: Fitness,
+{
+    let highest = fitness_values
+        .iter()
+        .max()
+        .cloned()
+        .expect("a population of at least one individual");
+    let lowest = fitness_values
+        .iter()
+        .min()
+        .cloned()
+        .expect("a population of at least one individual");
+    EvaluatedPopulation::new(
+        Arc::new(individuals),
+        fitness_values,
+        highest,
+        lowest,
+        average_fitness,
+    )
+}
+
+/// Returns an `EvaluatedPopulation` of genomes with a single gene, which is
+/// the fitness value of the genome.
+pub(crate) fn population_of_fitness<F>(
+    fitness_values: Vec<F>,
+    average_fitness: F,
+) -> EvaluatedPopulation<Vec<F>, F>
+where
+    F: Fitness + Send + Sync,
+{
+    let individuals = fitness_values
+        .iter()
+        .map(|fitness| vec![fitness.clone()])
+        .collect();
+    evaluated_population(individuals, fitness_values, average_fitness)
+}
+
+/// Returns an `EvaluatedPopulation` of genomes with a single gene, which is
+/// the index of the genome in the population.
+pub(crate) fn indexed_population<F>(
+    fitness_values: Vec<F>,
+    average_fitness: F,
+) -> EvaluatedPopulation<Vec<usize>, F>
+where
+    F: Fitness,
+{
+    let individuals = (0..fitness_values.len()).map(|index| vec![index]).collect();
+    evaluated_population(individuals, fitness_values, average_fitness)
+}
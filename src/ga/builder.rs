@@ -1,16 +1,20 @@
-use super::GeneticAlgorithm;
+use super::{GeneticAlgorithm, GeneticAlgorithmError, SameGenome, ScalarConversion, TieBreak};
 use crate::{
-    genetic::{Fitness, FitnessFunction, Genotype},
+    algorithm::GenomePool,
+    genetic::{
+        AsScalar, Fitness, FitnessFunction, GenomeIdentity, Genotype, GenotypeToPhenotype,
+        IdentityDecoder,
+    },
     operator::{CrossoverOp, MutationOp, ReinsertionOp, SelectionOp},
-    population::Population,
+    population::{GenomeBuilder, Population, PopulationGenerator},
     statistic::ProcessingTime,
 };
-use std::{marker::PhantomData, rc::Rc};
+use std::{marker::PhantomData, sync::Arc};
 
 const DEFAULT_MIN_POPULATION_SIZE: usize = 6;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct GeneticAlgorithmBuilder<G, F, E, S, C, M, R>
+pub struct GeneticAlgorithmBuilder<G, F, E, S, C, M, R, P = G, D = IdentityDecoder>
 where
     G: Genotype,
     F: Fitness,
@@ -19,18 +23,35 @@ where
     C: CrossoverOp<G>,
     M: MutationOp<G>,
     R: ReinsertionOp<G, F>,
+    D: GenotypeToPhenotype<G, P>,
 {
     _f: PhantomData<F>,
+    _p: PhantomData<P>,
     evaluator: E,
     selector: S,
     breeder: C,
     mutator: M,
     reinserter: R,
+    decoder: D,
     min_population_size: usize,
+    full_final_population: bool,
+    timings: bool,
+    inspection: bool,
+    fitness_histogram: Option<usize>,
+    scalar_conversion: ScalarConversion<F>,
+    scalar_fitness: bool,
+    num_top_solutions: Option<usize>,
+    phase_rngs: bool,
+    genome_reuse: bool,
+    fitness_validation: bool,
+    offspring_count: Option<usize>,
+    tie_break: TieBreak<G>,
+    same_genome: SameGenome<G>,
     initial_population: Population<G>,
+    population_generator: Option<PopulationGenerator<G>>,
 }
 
-impl<G, F, E, S, C, M, R> GeneticAlgorithmBuilder<G, F, E, S, C, M, R>
+impl<G, F, E, S, C, M, R, P, D> GeneticAlgorithmBuilder<G, F, E, S, C, M, R, P, D>
 where
     G: Genotype,
     F: Fitness,
@@ -39,26 +60,356 @@ where
     C: CrossoverOp<G>,
     M: MutationOp<G>,
     R: ReinsertionOp<G, F>,
+    D: GenotypeToPhenotype<G, P>,
 {
-    pub fn build(self) -> GeneticAlgorithm<G, F, E, S, C, M, R> {
-        GeneticAlgorithm {
+    /// Builds the `GeneticAlgorithm`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operators can not be combined, see `try_build`.
+    pub fn build(self) -> GeneticAlgorithm<G, F, E, S, C, M, R, P, D> {
+        match self.try_build() {
+            Ok(algorithm) => algorithm,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Builds the `GeneticAlgorithm` after validating the parameters of the
+    /// operators and that the operators can be combined.
+    ///
+    /// Returns a `GeneticAlgorithmError::InvalidRatio`, a
+    /// `GeneticAlgorithmError::InvalidCount` or a
+    /// `GeneticAlgorithmError::InvalidParameter` if a parameter of an operator
+    /// is out of its valid range as reported by `GeneticOperator::validate`, a
+    /// `GeneticAlgorithmError::IncompatibleOperators` if the `SelectionOp`
+    /// selects parents of a different size than the `CrossoverOp` requires,
+    /// and a `GeneticAlgorithmError::PopulationTooSmall` if the initial
+    /// population, or the populations generated by the `PopulationGenerator`,
+    /// is smaller than the `SelectionOp` requires.
+    #[allow(clippy::type_complexity)]
+    pub fn try_build(
+        self,
+    ) -> Result<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>, GeneticAlgorithmError> {
+        self.selector.validate()?;
+        self.breeder.validate()?;
+        self.mutator.validate()?;
+        self.reinserter.validate()?;
+        if let (Some(parents_size), Some(required_parents_size)) = (
+            self.selector.parents_size(),
+            self.breeder.required_parents_size(),
+        ) {
+            if parents_size != required_parents_size {
+                return Err(GeneticAlgorithmError::IncompatibleOperators(format!(
+                    "The selection operator {} selects parents of {} individuals, but the \
+                     crossover operator {} requires parents of {} individuals.",
+                    S::name(),
+                    parents_size,
+                    C::name(),
+                    required_parents_size
+                )));
+            }
+        }
+        if let Some(required_population_size) = self.selector.required_population_size() {
+            let initial_population_size = match self.population_generator {
+                Some(ref generator) => generator.population_size(),
+                None => self.initial_population.size(),
+            };
+            if initial_population_size < required_population_size {
+                return Err(GeneticAlgorithmError::PopulationTooSmall(format!(
+                    "The selection operator {} requires a population of at least {} \
+                     individuals, but the initial population has a size of {}.",
+                    S::name(),
+                    required_population_size,
+                    initial_population_size
+                )));
+            }
+        }
+        Ok(GeneticAlgorithm {
             _f: self._f,
             evaluator: self.evaluator,
             selector: self.selector,
             breeder: self.breeder,
             mutator: self.mutator,
             reinserter: self.reinserter,
+            decoder: self.decoder,
             min_population_size: self.min_population_size,
-            population: Rc::new(self.initial_population.individuals().to_vec()),
+            full_final_population: self.full_final_population,
+            timings: self.timings,
+            inspection: self.inspection,
+            fitness_histogram: self.fitness_histogram,
+            scalar_conversion: self.scalar_conversion,
+            scalar_fitness: self.scalar_fitness,
+            num_top_solutions: self.num_top_solutions,
+            phase_rngs: self.phase_rngs,
+            fitness_validation: self.fitness_validation,
+            offspring_count: self.offspring_count,
+            tie_break: self.tie_break,
+            same_genome: self.same_genome,
+            population: Arc::new(self.initial_population.individuals().to_vec()),
+            ranking: None,
+            genome_pool: if self.genome_reuse {
+                Some(GenomePool::new())
+            } else {
+                None
+            },
             initial_population: self.initial_population,
+            population_generator: self.population_generator,
+            best_solution: None,
+            top_solutions: Vec::new(),
+            generations_since_improvement: 0,
             processing_time: ProcessingTime::zero(),
-        }
+        })
     }
 
     pub fn with_min_population_size(mut self, min_population_size: usize) -> Self {
         self.min_population_size = min_population_size;
         self
     }
+
+    /// Sets whether the final result of a simulation contains all
+    /// individuals of the last generation together with their fitness
+    /// values in `State::final_population`.
+    ///
+    /// The fitness values are the ones calculated by the simulation, the
+    /// individuals are not evaluated again. As the individuals are cloned
+    /// this is disabled by default.
+    pub fn with_full_final_population(mut self, full_final_population: bool) -> Self {
+        self.full_final_population = full_final_population;
+        self
+    }
+
+    /// Sets whether the processing time of each phase of the genetic
+    /// algorithm is reported in the `PhaseTimings` of each generation.
+    ///
+    /// Measuring the crossover and the mutation separately takes the time of
+    /// each call of the operators. To avoid this overhead the timings are
+    /// disabled by default.
+    pub fn with_timings(mut self, timings: bool) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// Sets whether the intermediate stages of breeding each generation are
+    /// reported in the `Inspection` of each generation, e.g. to debug a
+    /// custom crossover operator.
+    ///
+    /// Reporting the stages clones the selected parents and each bred child,
+    /// so the inspection is meant for debugging only and is disabled by
+    /// default. If it is disabled no individual is cloned at all.
+    pub fn with_inspection(mut self, inspection: bool) -> Self {
+        self.inspection = inspection;
+        self
+    }
+
+    /// Enables the histogram of the fitness values of each generation, which
+    /// is reported in `State::fitness_histogram`. The histogram counts the
+    /// individuals in the given number of buckets of equal width between the
+    /// lowest and the highest possible fitness, as calculated by
+    /// `statistic::fitness_histogram`.
+    ///
+    /// The histogram shows how the population is distributed, e.g. whether
+    /// it has converged or is spread over several peaks. The buckets are
+    /// calculated from the scalar representation of the fitness values as
+    /// given by `genetic::AsScalar`. As it takes another pass over the fitness
+    /// values it is disabled by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buckets` is 0.
+    pub fn with_fitness_histogram(mut self, buckets: usize) -> Self
+    where
+        F: AsScalar,
+    {
+        assert!(buckets > 0, "the number of buckets must be greater than 0");
+        self.fitness_histogram = Some(buckets);
+        self.scalar_conversion = ScalarConversion::by_as_scalar();
+        self
+    }
+
+    /// Enables the statistics that need the scalar representation of the
+    /// fitness values as given by `genetic::AsScalar`: the standard deviation
+    /// in `statistic::GenerationStats::std_dev` and, if the fitness values
+    /// are validated by `with_fitness_validation`, the check that each scalar
+    /// fitness value is finite.
+    ///
+    /// Without it the standard deviation is `None`, so that fitness types
+    /// without a scalar representation, e.g. multi-objective fitness values,
+    /// can be used. As it takes another pass over the fitness values it is
+    /// disabled by default.
+    pub fn with_scalar_fitness(mut self) -> Self
+    where
+        F: AsScalar,
+    {
+        self.scalar_conversion = ScalarConversion::by_as_scalar();
+        self.scalar_fitness = true;
+        self
+    }
+
+    /// Enables tracking the given number of the best distinct solutions over
+    /// all generations, which are reported in `State::top_solutions`.
+    ///
+    /// In contrast to the best solution of each generation a solution stays
+    /// in the top solutions even if it is lost from the population in later
+    /// generations, until it is displaced by fitter solutions. Thus for
+    /// multimodal problems the top solutions can contain several optima.
+    /// Genomes are compared by `PartialEq` or by the `genetic::GenomeIdentity`
+    /// set by `with_genome_identity`, so that each genome is reported only
+    /// once. As the genomes are cloned this is disabled by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is 0.
+    pub fn with_top_solutions(mut self, count: usize) -> Self {
+        assert!(
+            count > 0,
+            "the number of top solutions must be greater than 0"
+        );
+        self.num_top_solutions = Some(count);
+        self
+    }
+
+    /// Sets whether each phase of a generation draws its random numbers from
+    /// its own random number generator.
+    ///
+    /// If enabled the random number generator of the simulation is split into
+    /// independent generators for the selection, the crossover, the mutation
+    /// and the reinsertion at the start of each generation. The generators
+    /// only depend on the seed of the simulation and the generation. Thus,
+    /// e.g., replacing the mutation operator does not change the random
+    /// numbers drawn by the selection and the crossover operator, which makes
+    /// ablation studies cleaner. The simulation is still deterministic for a
+    /// given seed, but produces other results than with a shared generator.
+    ///
+    /// It is disabled by default, so that all operators draw from the random
+    /// number generator of the simulation one after the other.
+    pub fn with_phase_rngs(mut self, phase_rngs: bool) -> Self {
+        self.phase_rngs = phase_rngs;
+        self
+    }
+
+    /// Sets whether the genomes of the previous generation are reused.
+    ///
+    /// If enabled the selected parents and the individuals that are taken
+    /// over from the old population are cloned into the allocations of the
+    /// genomes of the previous generation and of the discarded offspring,
+    /// see `algorithm::GenomePool`. The genomes of the previous generation can
+    /// only be reused if the output of that generation has been dropped
+    /// before the next generation is processed. The results are the same
+    /// whether the genomes are reused or not.
+    ///
+    /// It is enabled by default.
+    pub fn with_genome_reuse(mut self, genome_reuse: bool) -> Self {
+        self.genome_reuse = genome_reuse;
+        self
+    }
+
+    /// Sets whether the fitness value of each individual is validated after
+    /// the population has been evaluated.
+    ///
+    /// If enabled each fitness value must lie between the
+    /// `lowest_possible_fitness` and the `highest_possible_fitness` of the
+    /// `genetic::FitnessFunction` and its scalar representation, if enabled by
+    /// `with_scalar_fitness`, must be finite. Otherwise the generation fails with a
+    /// `GeneticAlgorithmError::InvalidFitness` that names the offending
+    /// genome. This catches bugs in the fitness function, e.g. a NaN, before
+    /// they silently corrupt the selection.
+    ///
+    /// The check takes one pass over the fitness values. It is disabled by
+    /// default, so that fitness functions which deliberately return values
+    /// outside of their declared range, e.g. NaN as the worst fitness, keep
+    /// working. Enabling it for debug builds only is as simple as
+    /// `with_fitness_validation(cfg!(debug_assertions))`.
+    pub fn with_fitness_validation(mut self, fitness_validation: bool) -> Self {
+        self.fitness_validation = fitness_validation;
+        self
+    }
+
+    /// Sets the number of children `lambda` that are bred in each generation
+    /// before the reinsertion operator combines them with the population.
+    ///
+    /// By default the number of children is determined by the selection
+    /// operator: its selection ratio decides how many parents are selected
+    /// and the crossover operator breeds its children from each of them. With
+    /// an offspring count the selected parents are bred again and again, each
+    /// time with fresh random numbers, until `lambda` children are born,
+    /// surplus children of the last round are dropped. Thus the selection
+    /// ratio only decides how many distinct parents, `mu` in the notation of
+    /// evolution strategies, take part in the breeding, and `lambda` may be
+    /// much greater than the population size, as in a `(mu, lambda)`
+    /// evolution strategy.
+    ///
+    /// The reinsertion operator reduces the offspring to the size of the
+    /// population, e.g. the `reinsertion::elitist::ElitistReinserter` picks
+    /// the best individuals. Reinsertion operators that require a specific
+    /// size of the offspring, like the
+    /// `reinsertion::generational::GenerationalReinserter`, only work if
+    /// `lambda` equals that size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lambda` is 0.
+    pub fn with_offspring_count(mut self, lambda: usize) -> Self {
+        assert!(lambda > 0, "the offspring count must be greater than 0");
+        self.offspring_count = Some(lambda);
+        self
+    }
+
+    /// Sets the `TieBreak` policy that decides which of several equally fit
+    /// solutions is reported as the best solution. The default policy is
+    /// `TieBreak::FirstSeen`.
+    pub fn with_tie_break(mut self, tie_break: TieBreak<G>) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Sets the `genetic::GenomeIdentity` that decides whether two genomes are
+    /// the same solution when the best distinct solutions are tracked, see
+    /// `with_top_solutions`. Genomes with the same canonical key are reported
+    /// only once. By default genomes are compared by `PartialEq`.
+    pub fn with_genome_identity<I>(mut self, identity: I) -> Self
+    where
+        I: GenomeIdentity<G> + Send + Sync + 'static,
+    {
+        self.same_genome = SameGenome::by_identity(identity);
+        self
+    }
+
+    /// Registers the `genetic::GenotypeToPhenotype` decoder that decodes the
+    /// best solution of each generation into its phenotype.
+    pub fn with_decoder<Q, X>(
+        self,
+        decoder: X,
+    ) -> GeneticAlgorithmBuilder<G, F, E, S, C, M, R, Q, X>
+    where
+        X: GenotypeToPhenotype<G, Q>,
+    {
+        GeneticAlgorithmBuilder {
+            _f: self._f,
+            _p: PhantomData,
+            evaluator: self.evaluator,
+            selector: self.selector,
+            breeder: self.breeder,
+            mutator: self.mutator,
+            reinserter: self.reinserter,
+            decoder,
+            min_population_size: self.min_population_size,
+            full_final_population: self.full_final_population,
+            timings: self.timings,
+            inspection: self.inspection,
+            fitness_histogram: self.fitness_histogram,
+            scalar_conversion: self.scalar_conversion,
+            scalar_fitness: self.scalar_fitness,
+            num_top_solutions: self.num_top_solutions,
+            phase_rngs: self.phase_rngs,
+            genome_reuse: self.genome_reuse,
+            fitness_validation: self.fitness_validation,
+            offspring_count: self.offspring_count,
+            tie_break: self.tie_break,
+            same_genome: self.same_genome,
+            initial_population: self.initial_population,
+            population_generator: self.population_generator,
+        }
+    }
 }
 
 #[derive(Default, Clone, Debug, PartialEq)]
@@ -293,15 +644,109 @@ where
     where
         R: ReinsertionOp<G, F>,
     {
+        self.into_builder(initial_population, None)
+    }
+
+    /// Registers the `population::GenomeBuilder` that builds the genomes of
+    /// the initial population, as an alternative to passing a pre-built
+    /// population to `with_initial_population`. The size of the population
+    /// is set by `with_population_size` next.
+    pub fn with_population_generator<B>(
+        self,
+        genome_builder: B,
+    ) -> GeneticAlgorithmWithEvalSeleBreeMutaReinAndGeneBuilder<G, F, E, S, C, M, R, B>
+    where
+        B: GenomeBuilder<G> + Send + 'static,
+    {
+        GeneticAlgorithmWithEvalSeleBreeMutaReinAndGeneBuilder {
+            inner: self,
+            genome_builder,
+        }
+    }
+
+    fn into_builder(
+        self,
+        initial_population: Population<G>,
+        population_generator: Option<PopulationGenerator<G>>,
+    ) -> GeneticAlgorithmBuilder<G, F, E, S, C, M, R> {
         GeneticAlgorithmBuilder {
             _f: self._f,
+            _p: PhantomData,
             evaluator: self.evaluator,
             selector: self.selector,
             breeder: self.breeder,
             mutator: self.mutator,
             reinserter: self.reinserter,
+            decoder: IdentityDecoder,
             min_population_size: DEFAULT_MIN_POPULATION_SIZE,
+            full_final_population: false,
+            timings: false,
+            inspection: false,
+            fitness_histogram: None,
+            scalar_conversion: ScalarConversion::default(),
+            scalar_fitness: false,
+            num_top_solutions: None,
+            phase_rngs: false,
+            genome_reuse: true,
+            fitness_validation: false,
+            offspring_count: None,
+            tie_break: TieBreak::default(),
+            same_genome: SameGenome::default(),
             initial_population,
+            population_generator,
         }
     }
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneticAlgorithmWithEvalSeleBreeMutaReinAndGeneBuilder<G, F, E, S, C, M, R, B>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G>,
+    M: MutationOp<G>,
+    R: ReinsertionOp<G, F>,
+    B: GenomeBuilder<G>,
+{
+    inner: GeneticAlgorithmWithEvalSeleBreeMutaAndReinBuilder<G, F, E, S, C, M, R>,
+    genome_builder: B,
+}
+
+impl<G, F, E, S, C, M, R, B>
+    GeneticAlgorithmWithEvalSeleBreeMutaReinAndGeneBuilder<G, F, E, S, C, M, R, B>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G>,
+    M: MutationOp<G>,
+    R: ReinsertionOp<G, F>,
+    B: GenomeBuilder<G> + Send + 'static,
+{
+    /// Sets the number of individuals of the initial population.
+    ///
+    /// The `GeneticAlgorithm` keeps the `population::PopulationGenerator`
+    /// made of the `GenomeBuilder` and this size. It generates the initial
+    /// population from the random number generator of the simulation in its
+    /// first generation and a fresh one each time it is reset, e.g. by a
+    /// restart of the simulation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `population_size` is 0.
+    pub fn with_population_size(
+        self,
+        population_size: usize,
+    ) -> GeneticAlgorithmBuilder<G, F, E, S, C, M, R> {
+        assert!(
+            population_size > 0,
+            "the size of the population must be greater than 0"
+        );
+        let generator = PopulationGenerator::new(self.genome_builder, population_size);
+        self.inner
+            .into_builder(Population::with_individuals(Vec::new()), Some(generator))
+    }
+}
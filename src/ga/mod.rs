@@ -7,16 +7,16 @@
 //! 2. **Fitness**: Evaluate the fitness of each genotype in the population
 //! 3. **New Population**: Create a new population by repeating following steps
 //!    until the new population is complete:
-//! 3.1. **Selection**: Select a tuple of parent genotypes from a population
-//!      according to their fitness and the selection strategy of the
-//!      configured `operator::SelectionOp`
-//! 3.2. **Crossover**: With a crossover probability cross over the parents to
-//!      form a new offspring (child) by means of the configured
-//!      `operator::CrossoverOp`.
-//! 3.3. **Mutation**: With a mutation probability mutate new offspring at each
-//!      locus (position in genotype) by means of the configured
-//!      `operator::MutationOp`.
-//! 3.4. **Accepting**: Place new offspring in the new population.
+//!    1. **Selection**: Select a tuple of parent genotypes from a population
+//!       according to their fitness and the selection strategy of the
+//!       configured `operator::SelectionOp`
+//!    2. **Crossover**: With a crossover probability cross over the parents to
+//!       form a new offspring (child) by means of the configured
+//!       `operator::CrossoverOp`.
+//!    3. **Mutation**: With a mutation probability mutate new offspring at each
+//!       locus (position in genotype) by means of the configured
+//!       `operator::MutationOp`.
+//!    4. **Accepting**: Place new offspring in the new population.
 //! 4. **Replace**: Use new generated population for a further run of the
 //!    algorithm.
 //! 5. **Termination**: If the end condition is satisfied, stop, and return the
@@ -27,40 +27,322 @@ pub mod builder;
 
 use self::builder::EmptyGeneticAlgorithmBuilder;
 use crate::{
-    algorithm::{Algorithm, BestSolution, EvaluatedPopulation},
-    genetic::{Fitness, FitnessFunction, Genotype, Offspring, Parents},
-    operator::{CrossoverOp, MutationOp, ReinsertionOp, SelectionOp},
-    population::Population,
-    random::Prng,
-    statistic::{timed, ProcessingTime, TimedResult, TrackProcessingTime},
+    algorithm::{
+        Algorithm, BestFitness, BestSolution, Evaluated, EvaluatedPopulation, GenomePool,
+        Restartable, Resumable,
+    },
+    genetic::{
+        AsScalar, Fitness, FitnessFunction, GenomeIdentity, Genotype, GenotypeToPhenotype,
+        IdentityDecoder, Offspring, Parents,
+    },
+    operator::{CrossoverOp, GenerationSignal, MutationOp, ReinsertionOp, SelectionOp},
+    population::{Population, PopulationGenerator},
+    random::SplittableRng,
+    statistic::{
+        scalar_fitness_histogram, timed, GenerationStats, PhaseTimings, ProcessingTime,
+        TimedResult, TrackProcessingTime,
+    },
 };
-use chrono::Local;
-#[cfg(not(target_arch = "wasm32"))]
-use rayon;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use rayon::{self, iter::ParallelIterator, prelude::IntoParallelIterator};
 use std::{
-    fmt::{self, Display},
+    cmp::Ordering,
+    fmt::{self, Debug, Display},
     marker::PhantomData,
-    rc::Rc,
+    sync::Arc,
 };
-use rayon::iter::ParallelIterator;
-use rayon::prelude::IntoParallelIterator;
 
 /// The `State` struct holds the results of one pass of the genetic algorithm
 /// loop, i.e. the processing of the evolution from one generation to the next
 /// generation.
 #[derive(Clone, Debug, PartialEq)]
-pub struct State<G, F>
+pub struct State<G, F, P = G>
 where
     G: Genotype,
     F: Fitness,
 {
     /// The evaluated population of the current generation.
     pub evaluated_population: EvaluatedPopulation<G, F>,
-    /// Best solution of this generation.
-    pub best_solution: BestSolution<G, F>,
+    /// Best solution of this generation, which is the individual with the
+    /// best `genetic::Fitness` value. The best value is the highest one, or
+    /// the lowest one if the `operator::SelectionOp` of the `GeneticAlgorithm`
+    /// minimizes, see `operator::SelectionOp::is_minimize`. If several
+    /// individuals share the best fitness value the `TieBreak` policy of the
+    /// `GeneticAlgorithm` decides which one is reported.
+    pub best_solution: BestSolution<G, F, P>,
+    /// Statistics about the fitness values of this generation.
+    pub stats: GenerationStats<F>,
     /// Processing time for this generation. In case of parallel processing it
     /// is the accumulated time spent by each thread.
     pub processing_time: ProcessingTime,
+    /// The individuals of the evaluated population together with their
+    /// fitness values as calculated in this generation.
+    ///
+    /// It is only set in the final result of a simulation and only if the
+    /// `GeneticAlgorithm` has been built with
+    /// `with_full_final_population(true)`. Otherwise it is `None`.
+    pub final_population: Option<Vec<(G, F)>>,
+    /// The processing time of each phase of this generation.
+    ///
+    /// It is only set if the `GeneticAlgorithm` has been built with
+    /// `with_timings(true)`. Otherwise it is `None`.
+    pub phase_timings: Option<PhaseTimings>,
+    /// The amount by which the best fitness of this generation exceeds the
+    /// best fitness of all previous generations, as calculated by
+    /// `genetic::Fitness::abs_diff`. It is zero if the best fitness has not
+    /// improved and in the first generation, which sets the initial best
+    /// fitness.
+    ///
+    /// An improvement means a greater `genetic::Fitness` value, or a lower one
+    /// if the `operator::SelectionOp` of the `GeneticAlgorithm` minimizes.
+    pub best_fitness_delta: F,
+    /// The number of generations since the best fitness has improved the
+    /// last time. It is 0 if the best fitness has improved in this generation
+    /// and in the first generation.
+    pub generations_since_improvement: u64,
+    /// The intermediate stages of breeding the offspring of this generation.
+    ///
+    /// It is only set if the `GeneticAlgorithm` has been built with
+    /// `with_inspection(true)`. Otherwise it is `None`.
+    pub inspection: Option<Inspection<G>>,
+    /// The number of individuals of the evaluated population per bucket of
+    /// fitness values, from the bucket of the lowest to the bucket of the
+    /// highest fitness values.
+    ///
+    /// It is only set if the `GeneticAlgorithm` has been built with
+    /// `with_fitness_histogram`. Otherwise it is `None`.
+    pub fitness_histogram: Option<Vec<usize>>,
+    /// The best distinct solutions of all generations processed so far,
+    /// ordered from the best to the worst fitness.
+    ///
+    /// It is only set if the `GeneticAlgorithm` has been built with
+    /// `with_top_solutions`. Otherwise it is `None`.
+    pub top_solutions: Option<Vec<BestSolution<G, F, P>>>,
+}
+
+/// The `Inspection` holds the intermediate stages of breeding the offspring
+/// of one generation. It is reported in the `State` of each generation if
+/// the `GeneticAlgorithm` has been built with `with_inspection(true)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inspection<G>
+where
+    G: Genotype,
+{
+    /// The parents selected by the `operator::SelectionOp`, also called the
+    /// mating pool.
+    pub parents: Vec<Parents<G>>,
+    /// The children bred by the `operator::CrossoverOp` before they have been
+    /// mutated.
+    pub children: Vec<G>,
+    /// The offspring, which are the children after they have been mutated by
+    /// the `operator::MutationOp`. The offspring is then combined with the
+    /// current population by the `operator::ReinsertionOp`.
+    pub offspring: Vec<G>,
+}
+
+/// The `GeneticAlgorithmState` holds the state of a `GeneticAlgorithm` that
+/// is needed to resume the algorithm later on.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct GeneticAlgorithmState<G, F, P = G>
+where
+    G: Genotype,
+    F: Fitness,
+{
+    /// The population that is going to be processed in the next generation.
+    pub population: Vec<G>,
+    /// The best solution found so far.
+    pub best_solution: Option<BestSolution<G, F, P>>,
+    /// The number of generations since the best fitness has improved the
+    /// last time.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub generations_since_improvement: u64,
+    /// The best distinct solutions found so far, if they are tracked.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub top_solutions: Vec<BestSolution<G, F, P>>,
+}
+
+/// Decides whether two genomes are the same solution, either by the canonical
+/// keys of a `genetic::GenomeIdentity` or by `PartialEq` if none is set.
+#[derive(Clone)]
+pub(crate) struct SameGenome<G> {
+    compare: Option<GenomeComparison<G>>,
+}
+
+/// Returns whether two genomes have the same canonical key.
+type GenomeComparison<G> = Arc<dyn Fn(&G, &G) -> bool + Send + Sync>;
+
+impl<G> SameGenome<G>
+where
+    G: Genotype,
+{
+    /// Compares genomes by the canonical keys of the given `GenomeIdentity`.
+    pub(crate) fn by_identity<I>(identity: I) -> Self
+    where
+        I: GenomeIdentity<G> + Send + Sync + 'static,
+    {
+        SameGenome {
+            compare: Some(Arc::new(move |genome1, genome2| {
+                identity.canonical_key(genome1) == identity.canonical_key(genome2)
+            })),
+        }
+    }
+
+    /// Returns whether the given genomes are the same solution.
+    fn same(&self, genome1: &G, genome2: &G) -> bool {
+        match self.compare {
+            Some(ref compare) => compare(genome1, genome2),
+            None => genome1 == genome2,
+        }
+    }
+}
+
+impl<G> Default for SameGenome<G> {
+    fn default() -> Self {
+        SameGenome { compare: None }
+    }
+}
+
+impl<G> Debug for SameGenome<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SameGenome")
+            .field("by_identity", &self.compare.is_some())
+            .finish()
+    }
+}
+
+impl<G> PartialEq for SameGenome<G> {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.compare, &other.compare) {
+            (Some(compare), Some(other_compare)) => Arc::ptr_eq(compare, other_compare),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Converts `genetic::Fitness` values into their scalar representation by
+/// `genetic::AsScalar`, if the statistics that need it are enabled.
+pub(crate) struct ScalarConversion<F> {
+    as_scalar: Option<fn(&F) -> f64>,
+}
+
+impl<F> ScalarConversion<F> {
+    /// Converts the fitness values by their implementation of `AsScalar`.
+    pub(crate) fn by_as_scalar() -> Self
+    where
+        F: AsScalar,
+    {
+        ScalarConversion {
+            as_scalar: Some(F::as_scalar),
+        }
+    }
+
+    /// Returns the function that converts a fitness value, if any.
+    fn get(&self) -> Option<fn(&F) -> f64> {
+        self.as_scalar
+    }
+}
+
+impl<F> Clone for ScalarConversion<F> {
+    fn clone(&self) -> Self {
+        ScalarConversion {
+            as_scalar: self.as_scalar,
+        }
+    }
+}
+
+impl<F> Default for ScalarConversion<F> {
+    fn default() -> Self {
+        ScalarConversion { as_scalar: None }
+    }
+}
+
+impl<F> Debug for ScalarConversion<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScalarConversion")
+            .field("by_as_scalar", &self.as_scalar.is_some())
+            .finish()
+    }
+}
+
+/// There is only one implementation of `AsScalar` for each fitness type.
+impl<F> PartialEq for ScalarConversion<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_scalar.is_some() == other.as_scalar.is_some()
+    }
+}
+
+/// The `TieBreak` policy decides which solution is reported as the best
+/// solution when several solutions share the best `genetic::Fitness`
+/// value.
+///
+/// The policy is applied to the best solution of each generation as reported
+/// in the intermediate results as well as in the final result and to the
+/// best solution of all generations as returned by
+/// `GeneticAlgorithm::best_solution`.
+#[derive(Clone, Debug, Default)]
+pub enum TieBreak<G> {
+    /// The individual that comes first in the population of a generation is
+    /// the best solution of this generation. The best solution of all
+    /// generations is only replaced by a solution with a better fitness
+    /// value.
+    ///
+    /// This is the default policy.
+    #[default]
+    FirstSeen,
+    /// Like `FirstSeen`, but if the best individual of a generation has the
+    /// same fitness value as the best solution of all previous generations,
+    /// the solution of the lowest generation is reported as the best
+    /// solution of this generation.
+    LowestGeneration,
+    /// Among the individuals that share the best fitness value the least
+    /// one according to the given comparator is the best solution, e.g. the
+    /// shortest genome. The best solution of all generations is replaced by
+    /// a solution with the same fitness value that is less according to the
+    /// comparator. Individuals that are equal according to the comparator
+    /// are handled like `FirstSeen`.
+    Comparator(fn(&G, &G) -> Ordering),
+}
+
+/// Two `TieBreak` policies are equal if they are the same kind of policy.
+/// The functions of two `Comparator` policies are not compared.
+impl<G> PartialEq for TieBreak<G> {
+    fn eq(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl<G> TieBreak<G>
+where
+    G: Genotype,
+{
+    /// Returns whether the solution `challenger` replaces the solution
+    /// `incumbent` as the best solution. The fitness values are compared in
+    /// the direction of the optimization of the given population.
+    fn replaces<F>(
+        &self,
+        challenger: &Evaluated<G, F>,
+        incumbent: &Evaluated<G, F>,
+        population: &EvaluatedPopulation<G, F>,
+    ) -> bool
+    where
+        F: Fitness,
+    {
+        let comparison = population.compare_fitness(&challenger.fitness, &incumbent.fitness);
+        match self {
+            TieBreak::FirstSeen | TieBreak::LowestGeneration => comparison == Ordering::Greater,
+            TieBreak::Comparator(compare) => match comparison {
+                Ordering::Greater => true,
+                Ordering::Equal => compare(&challenger.genome, &incumbent.genome) == Ordering::Less,
+                Ordering::Less => false,
+            },
+        }
+    }
 }
 
 /// An error that can occur during execution of a `GeneticAlgorithm`.
@@ -69,8 +351,29 @@ pub enum GeneticAlgorithmError {
     /// The algorithm is run with an empty population.
     EmptyPopulation(String),
     /// The algorithm is run with an population size that is smaller than the
-    /// required minimum.
+    /// required minimum or smaller than the number of individuals required by
+    /// the selection or the crossover operator, or the selection operator
+    /// selects no parents from the population.
     PopulationTooSmall(String),
+    /// The algorithm is built with operators that can not be combined, e.g.
+    /// a selection operator that selects parents of a different size than
+    /// the crossover operator requires.
+    IncompatibleOperators(String),
+    /// The algorithm is built with an operator whose ratio or probability
+    /// parameter is not between 0 and 1, e.g. a mutation rate of 1.5.
+    InvalidRatio(String),
+    /// The algorithm is built with an operator whose count parameter is not
+    /// positive, e.g. a crossover operator with 0 cut points.
+    InvalidCount(String),
+    /// The algorithm is built with an operator whose parameter is out of its
+    /// valid range and is neither a ratio nor a count, e.g. a temperature of
+    /// the `selection::boltzmann::BoltzmannSelector` that is not positive.
+    InvalidParameter(String),
+    /// The fitness function returned a fitness value that is outside of the
+    /// range of its lowest and highest possible fitness or is not finite.
+    /// It is only checked if the algorithm is built with
+    /// `with_fitness_validation(true)`.
+    InvalidFitness(String),
 }
 
 impl Display for GeneticAlgorithmError {
@@ -78,6 +381,11 @@ impl Display for GeneticAlgorithmError {
         match self {
             GeneticAlgorithmError::EmptyPopulation(details) => write!(f, "{}", details),
             GeneticAlgorithmError::PopulationTooSmall(details) => write!(f, "{}", details),
+            GeneticAlgorithmError::IncompatibleOperators(details) => write!(f, "{}", details),
+            GeneticAlgorithmError::InvalidRatio(details) => write!(f, "{}", details),
+            GeneticAlgorithmError::InvalidCount(details) => write!(f, "{}", details),
+            GeneticAlgorithmError::InvalidParameter(details) => write!(f, "{}", details),
+            GeneticAlgorithmError::InvalidFitness(details) => write!(f, "{}", details),
         }
     }
 }
@@ -94,8 +402,12 @@ where
 
 /// A `GeneticAlgorithm` declares the building blocks that make up the actual
 /// algorithm for a specific optimization problem.
+///
+/// The best solution of each generation is decoded into its phenotype of
+/// type `P` by the `genetic::GenotypeToPhenotype` decoder `D`. If no decoder
+/// is registered the phenotype is the genome itself.
 #[derive(Clone, Debug, PartialEq)]
-pub struct GeneticAlgorithm<G, F, E, S, C, M, R>
+pub struct GeneticAlgorithm<G, F, E, S, C, M, R, P = G, D = IdentityDecoder>
 where
     G: Genotype,
     F: Fitness,
@@ -104,6 +416,7 @@ where
     C: CrossoverOp<G>,
     M: MutationOp<G>,
     R: ReinsertionOp<G, F>,
+    D: GenotypeToPhenotype<G, P>,
 {
     _f: PhantomData<F>,
     evaluator: E,
@@ -111,13 +424,32 @@ where
     breeder: C,
     mutator: M,
     reinserter: R,
+    decoder: D,
     min_population_size: usize,
+    full_final_population: bool,
+    timings: bool,
+    inspection: bool,
+    fitness_histogram: Option<usize>,
+    scalar_conversion: ScalarConversion<F>,
+    scalar_fitness: bool,
+    num_top_solutions: Option<usize>,
+    phase_rngs: bool,
+    fitness_validation: bool,
+    offspring_count: Option<usize>,
+    tie_break: TieBreak<G>,
+    same_genome: SameGenome<G>,
     initial_population: Population<G>,
-    population: Rc<Vec<G>>,
+    population_generator: Option<PopulationGenerator<G>>,
+    population: Arc<Vec<G>>,
+    ranking: Option<Vec<usize>>,
+    genome_pool: Option<GenomePool<G>>,
+    best_solution: Option<BestSolution<G, F, P>>,
+    top_solutions: Vec<BestSolution<G, F, P>>,
+    generations_since_improvement: u64,
     processing_time: ProcessingTime,
 }
 
-impl<G, F, E, S, C, M, R> GeneticAlgorithm<G, F, E, S, C, M, R>
+impl<G, F, E, S, C, M, R, P, D> GeneticAlgorithm<G, F, E, S, C, M, R, P, D>
 where
     G: Genotype,
     F: Fitness,
@@ -126,6 +458,7 @@ where
     C: CrossoverOp<G>,
     M: MutationOp<G>,
     R: ReinsertionOp<G, F>,
+    D: GenotypeToPhenotype<G, P>,
 {
     pub fn evaluator(&self) -> &E {
         &self.evaluator
@@ -147,12 +480,154 @@ where
         &self.reinserter
     }
 
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
     pub fn min_population_size(&self) -> usize {
         self.min_population_size
     }
+
+    /// Returns whether the final result contains all individuals of the last
+    /// generation together with their fitness values.
+    pub fn full_final_population(&self) -> bool {
+        self.full_final_population
+    }
+
+    /// Returns whether the processing time of each phase is reported in the
+    /// `PhaseTimings` of each generation.
+    pub fn timings(&self) -> bool {
+        self.timings
+    }
+
+    /// Returns whether the intermediate stages of breeding are reported in
+    /// the `Inspection` of each generation.
+    pub fn inspection(&self) -> bool {
+        self.inspection
+    }
+
+    /// Returns the number of buckets of the histogram of the fitness values
+    /// of each generation, or `None` if no histogram is reported.
+    pub fn fitness_histogram(&self) -> Option<usize> {
+        self.fitness_histogram
+    }
+
+    /// Returns whether the statistics that need the scalar representation of
+    /// the fitness values are calculated.
+    pub fn scalar_fitness(&self) -> bool {
+        self.scalar_fitness
+    }
+
+    /// Returns the function that converts the fitness values into their
+    /// scalar representation for the statistics of `scalar_fitness`.
+    fn scalar_statistics(&self) -> Option<fn(&F) -> f64> {
+        self.scalar_conversion.get().filter(|_| self.scalar_fitness)
+    }
+
+    /// Returns the number of the best distinct solutions that are tracked
+    /// over all generations, or `None` if they are not tracked.
+    pub fn num_top_solutions(&self) -> Option<usize> {
+        self.num_top_solutions
+    }
+
+    /// Returns whether each phase of a generation draws its random numbers
+    /// from its own random number generator.
+    pub fn phase_rngs(&self) -> bool {
+        self.phase_rngs
+    }
+
+    /// Returns whether the genomes of the previous generation are reused.
+    pub fn genome_reuse(&self) -> bool {
+        self.genome_pool.is_some()
+    }
+
+    /// Returns whether the fitness value of each individual is validated
+    /// after the population has been evaluated.
+    pub fn fitness_validation(&self) -> bool {
+        self.fitness_validation
+    }
+
+    /// Returns the number of children that are bred in each generation, or
+    /// `None` if it is determined by the selection operator.
+    pub fn offspring_count(&self) -> Option<usize> {
+        self.offspring_count
+    }
+
+    /// Returns the `TieBreak` policy that decides which of several equally
+    /// fit solutions is reported as the best solution.
+    pub fn tie_break(&self) -> &TieBreak<G> {
+        &self.tie_break
+    }
+
+    /// Returns the `PopulationGenerator` that generates the initial
+    /// population, or `None` if the algorithm was built with a pre-built
+    /// initial population.
+    pub fn population_generator(&self) -> Option<&PopulationGenerator<G>> {
+        self.population_generator.as_ref()
+    }
+
+    /// Returns the individuals of the population that is going to be
+    /// processed by the next call of `next`.
+    ///
+    /// If the algorithm was built with a `PopulationGenerator` the population
+    /// is empty until it is generated in the first generation.
+    pub fn population(&self) -> &[G] {
+        &self.population
+    }
+
+    /// Replaces the population that is going to be processed by the next call
+    /// of `next` with the given individuals.
+    pub fn set_population(&mut self, population: Vec<G>) {
+        self.replace_population(population);
+    }
+
+    /// Returns the indices of the individuals of the population that is going
+    /// to be processed by the next call of `next` sorted from the best to the
+    /// worst performing individual.
+    ///
+    /// The population is evaluated only when it is ranked for the first time.
+    /// The ranking is kept until the population is replaced, so that ranking
+    /// the same population again does not evaluate its fitness again.
+    pub(crate) fn ranking(&mut self) -> Vec<usize> {
+        if let Some(ref ranking) = self.ranking {
+            return ranking.clone();
+        }
+        let fitness_values: Vec<F> = self
+            .population
+            .iter()
+            .map(|individual| self.evaluator.fitness_of(individual))
+            .collect();
+        let mut ranking: Vec<usize> = (0..fitness_values.len()).collect();
+        if self.selector.is_minimize() {
+            ranking.sort_by(|x, y| fitness_values[*x].cmp(&fitness_values[*y]));
+        } else {
+            ranking.sort_by(|x, y| fitness_values[*y].cmp(&fitness_values[*x]));
+        }
+        self.ranking = Some(ranking.clone());
+        ranking
+    }
+
+    /// Replaces the population by the given individuals, discards the ranking
+    /// of the replaced population and returns the replaced population.
+    fn replace_population(&mut self, population: Vec<G>) -> Arc<Vec<G>> {
+        self.ranking = None;
+        std::mem::replace(&mut self.population, Arc::new(population))
+    }
+
+    /// Returns the best solution of all generations processed so far.
+    pub fn best_solution(&self) -> Option<&BestSolution<G, F, P>> {
+        self.best_solution.as_ref()
+    }
+
+    /// Returns the best distinct solutions of all generations processed so
+    /// far, ordered from the best to the worst fitness. It is empty if
+    /// the top solutions are not tracked.
+    pub fn top_solutions(&self) -> &[BestSolution<G, F, P>] {
+        &self.top_solutions
+    }
 }
 
-impl<G, F, E, S, C, M, R> TrackProcessingTime for GeneticAlgorithm<G, F, E, S, C, M, R>
+impl<G, F, E, S, C, M, R, P, D> TrackProcessingTime for GeneticAlgorithm<G, F, E, S, C, M, R, P, D>
 where
     G: Genotype,
     F: Fitness,
@@ -161,13 +636,14 @@ where
     C: CrossoverOp<G>,
     M: MutationOp<G>,
     R: ReinsertionOp<G, F>,
+    D: GenotypeToPhenotype<G, P>,
 {
     fn processing_time(&self) -> ProcessingTime {
         self.processing_time
     }
 }
 
-impl<G, F, E, S, C, M, R> Algorithm for GeneticAlgorithm<G, F, E, S, C, M, R>
+impl<G, F, E, S, C, M, R, P, D> Algorithm for GeneticAlgorithm<G, F, E, S, C, M, R, P, D>
 where
     G: Genotype,
     F: Fitness + Send + Sync,
@@ -176,11 +652,22 @@ where
     C: CrossoverOp<G> + Sync,
     M: MutationOp<G> + Sync,
     R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
 {
-    type Output = State<G, F>;
+    type Output = State<G, F, P>;
     type Error = GeneticAlgorithmError;
 
-    fn next(&mut self, iteration: u64, rng: &mut Prng) -> Result<Self::Output, Self::Error> {
+    fn next<Q>(&mut self, iteration: u64, rng: &mut Q) -> Result<Self::Output, Self::Error>
+    where
+        Q: SplittableRng,
+    {
+        if let Some(ref generator) = self.population_generator {
+            if self.population.is_empty() {
+                let population = generator.generate(rng);
+                self.replace_population(population.individuals().to_vec());
+            }
+        }
         if self.population.is_empty() {
             return Err(GeneticAlgorithmError::EmptyPopulation(format!(
                 "Population of generation {} is empty. The required minimum size for \
@@ -197,45 +684,507 @@ where
                 self.min_population_size
             )));
         }
+        let requirements = [
+            (
+                self.selector.parents_size(),
+                "individuals per parents selected by the selection operator",
+                S::name(),
+            ),
+            (
+                self.selector.required_population_size(),
+                "individuals required by the selection operator",
+                S::name(),
+            ),
+            (
+                self.breeder.required_parents_size(),
+                "individuals per parents required by the crossover operator",
+                C::name(),
+            ),
+        ];
+        for (required_size, requirement, operator) in requirements.iter() {
+            if let Some(required_size) = required_size {
+                if self.population.len() < *required_size {
+                    return Err(GeneticAlgorithmError::PopulationTooSmall(format!(
+                        "Population of generation {} has a size of {} which is smaller than \
+                         the {} {} {}.",
+                        iteration,
+                        self.population.len(),
+                        required_size,
+                        requirement,
+                        operator
+                    )));
+                }
+            }
+        }
 
         // Stage 2: The fitness check:
-        let evaluation = evaluate_fitness(self.population.clone(), &self.evaluator);
-        let best_solution = determine_best_solution(iteration, &evaluation.result);
+        self.evaluator.start_generation(iteration);
+        // the genomes of the previous generation are overwritten by the
+        // clones of this generation if its output has been dropped
+        if let Some(ref mut genome_pool) = self.genome_pool {
+            genome_pool.reuse_retired();
+        }
+        let evaluation = evaluate_fitness(
+            self.population.clone(),
+            &self.evaluator,
+            self.selector.is_minimize(),
+            self.genome_pool.clone(),
+        );
+        let validation = timed(|| {
+            if self.fitness_validation {
+                validate_fitness(
+                    iteration,
+                    &evaluation.result,
+                    &self.evaluator,
+                    self.scalar_statistics(),
+                )
+            } else {
+                Ok(())
+            }
+        })
+        .run();
+        let validation_time = validation.time;
+        validation.result?;
+        let mut best_solution = determine_best_solution(
+            iteration,
+            &evaluation.result,
+            &self.decoder,
+            &self.tie_break,
+        );
+        let stats = timed(|| {
+            let stats = GenerationStats::calculate(&evaluation.result, self.scalar_statistics());
+            let histogram = self
+                .fitness_histogram
+                .zip(self.scalar_conversion.get())
+                .map(|(buckets, as_scalar)| {
+                    scalar_fitness_histogram(
+                        &evaluation.result,
+                        &self.evaluator,
+                        buckets,
+                        as_scalar,
+                    )
+                });
+            (stats, histogram)
+        })
+        .run();
+        let ranking = timed(|| {
+            if let Some(count) = self.num_top_solutions {
+                update_top_solutions(
+                    &mut self.top_solutions,
+                    count,
+                    iteration,
+                    &evaluation.result,
+                    &self.decoder,
+                    &self.same_genome,
+                );
+            }
+        })
+        .run();
+
+        let is_best_so_far = match self.best_solution {
+            Some(ref best) => {
+                evaluation.result.compare_fitness(
+                    &best_solution.result.solution.fitness,
+                    &best.solution.fitness,
+                ) == Ordering::Greater
+            },
+            None => true,
+        };
+        let best_fitness_delta = match self.best_solution {
+            Some(ref best) if is_best_so_far => best_solution
+                .result
+                .solution
+                .fitness
+                .abs_diff(&best.solution.fitness),
+            _ => F::zero(),
+        };
+        if is_best_so_far {
+            self.generations_since_improvement = 0;
+        } else {
+            self.generations_since_improvement += 1;
+        }
+        let signal = GenerationSignal {
+            generation: iteration,
+            individuals: &self.population,
+            improved: is_best_so_far,
+        };
+        self.selector.adapt(&signal);
+        self.breeder.adapt(&signal);
+        self.mutator.adapt(&signal);
+        self.reinserter.adapt(&signal);
 
         // Stage 3: The making of a new population:
-        let selection = timed(|| self.selector.select_from(&evaluation.result, rng)).run();
-        let mut breeding = par_breed_offspring(selection.result, &self.breeder, &self.mutator, rng);
-        let reinsertion = timed(|| {
-            self.reinserter
-                .combine(&mut breeding.result, &evaluation.result, rng)
+        let mut phase_rngs = if self.phase_rngs {
+            Some(PhaseRngs::split_from(rng))
+        } else {
+            None
+        };
+        let selection = {
+            let rng = match phase_rngs {
+                Some(ref mut phase_rngs) => &mut phase_rngs.selection,
+                None => &mut *rng,
+            };
+            timed(|| self.selector.select_from(&evaluation.result, rng)).run()
+        };
+        if selection.result.is_empty() {
+            return Err(GeneticAlgorithmError::PopulationTooSmall(format!(
+                "The selection operator {} selected no parents from the population of \
+                 generation {} with a size of {}.",
+                S::name(),
+                iteration,
+                self.population.len()
+            )));
+        }
+        let selected_parents = if self.inspection {
+            Some(selection.result.clone())
+        } else {
+            None
+        };
+        let mut breeding_rngs = match phase_rngs {
+            Some(ref mut phase_rngs) => BreedingRngs {
+                crossover: &mut phase_rngs.crossover,
+                mutation: Some(&mut phase_rngs.mutation),
+            },
+            None => BreedingRngs {
+                crossover: &mut *rng,
+                mutation: None,
+            },
+        };
+        let rebred_parents = self.offspring_count.map(|_| selection.result.clone());
+        let mut breeding = par_breed_offspring(
+            selection.result,
+            &self.breeder,
+            &self.mutator,
+            &mut breeding_rngs,
+            self.timings,
+            self.inspection,
+            self.evaluator.tracks_lineage(),
+        );
+        if let (Some(offspring_count), Some(parents)) = (self.offspring_count, rebred_parents) {
+            // breed the selected parents again until there are enough children
+            while breeding.offspring.len() < offspring_count {
+                let more = par_breed_offspring(
+                    parents.clone(),
+                    &self.breeder,
+                    &self.mutator,
+                    &mut breeding_rngs,
+                    self.timings,
+                    self.inspection,
+                    self.evaluator.tracks_lineage(),
+                );
+                // a crossover operator that breeds no children would loop forever
+                if more.offspring.is_empty() {
+                    break;
+                }
+                breeding.append(more);
+            }
+            breeding.truncate(offspring_count);
+        }
+        let mut offspring = breeding.offspring;
+        let lineage = breeding.lineage;
+        let recording = timed(|| {
+            if let Some(ref parents) = lineage {
+                self.evaluator.record_lineage(parents, &offspring);
+            }
         })
         .run();
+        let inspection = match (selected_parents, breeding.children) {
+            (Some(parents), Some(children)) => Some(Inspection {
+                parents,
+                children,
+                offspring: offspring.clone(),
+            }),
+            _ => None,
+        };
+        if let Some(required_size) = self
+            .reinserter
+            .required_offspring_size(self.population.len())
+        {
+            if offspring.len() != required_size {
+                return Err(GeneticAlgorithmError::IncompatibleOperators(format!(
+                    "Offspring of generation {} has a size of {} but the reinsertion operator {} \
+                     requires {} individuals for a population of {}. Configure the selection \
+                     operator {} and the crossover operator {} to breed {} children.",
+                    iteration,
+                    offspring.len(),
+                    R::name(),
+                    required_size,
+                    self.population.len(),
+                    S::name(),
+                    C::name(),
+                    required_size
+                )));
+            }
+        }
+        let reinsertion = {
+            let rng = match phase_rngs {
+                Some(ref mut phase_rngs) => &mut phase_rngs.reinsertion,
+                None => &mut *rng,
+            };
+            timed(|| {
+                self.reinserter
+                    .combine(&mut offspring, &evaluation.result, rng)
+            })
+            .run()
+        };
 
         // Stage 4: On to the next generation:
         self.processing_time = evaluation.time
+            + validation_time
             + best_solution.time
+            + stats.time
+            + ranking.time
             + selection.time
             + breeding.time
+            + recording.time
             + reinsertion.time;
+        let selection_time = selection.time;
+        let phase_timings = breeding.phases.map(|(crossover, mutation)| PhaseTimings {
+            evaluation: evaluation.time + validation_time + recording.time,
+            statistics: best_solution.time + stats.time + ranking.time,
+            selection: selection_time,
+            crossover,
+            mutation,
+            reinsertion: reinsertion.time,
+        });
         let next_generation = reinsertion.result;
-        self.population = Rc::new(next_generation);
+        let retired = self.replace_population(next_generation);
+        if let Some(ref mut genome_pool) = self.genome_pool {
+            // the reinsertion operator leaves the discarded offspring behind
+            genome_pool.retire(retired, offspring);
+        }
+        match self.best_solution {
+            Some(ref best)
+                if !self.tie_break.replaces(
+                    &best_solution.result.solution,
+                    &best.solution,
+                    &evaluation.result,
+                ) =>
+            {
+                if self.tie_break == TieBreak::LowestGeneration
+                    && best_solution.result.solution.fitness == best.solution.fitness
+                {
+                    best_solution.result = best.clone();
+                }
+            },
+            _ => self.best_solution = Some(best_solution.result.clone()),
+        }
+        let (generation_stats, histogram) = stats.result;
         Ok(State {
             evaluated_population: evaluation.result,
             best_solution: best_solution.result,
+            stats: generation_stats,
             processing_time: self.processing_time,
+            final_population: None,
+            phase_timings,
+            best_fitness_delta,
+            generations_since_improvement: self.generations_since_improvement,
+            inspection,
+            fitness_histogram: histogram,
+            top_solutions: self.num_top_solutions.map(|_| self.top_solutions.clone()),
         })
     }
 
     fn reset(&mut self) -> Result<bool, Self::Error> {
         self.processing_time = ProcessingTime::zero();
-        self.population = Rc::new(self.initial_population.individuals().to_vec());
+        let population = match self.population_generator {
+            // a fresh population is generated in the next generation
+            Some(_) => Vec::new(),
+            None => self.initial_population.individuals().to_vec(),
+        };
+        self.replace_population(population);
+        self.best_solution = None;
+        self.top_solutions.clear();
+        self.generations_since_improvement = 0;
+        self.selector.reset();
+        self.breeder.reset();
+        self.mutator.reset();
+        self.reinserter.reset();
+        Ok(true)
+    }
+
+    fn finish(&mut self, output: &mut Self::Output) {
+        if self.full_final_population {
+            let evaluated = &output.evaluated_population;
+            output.final_population = Some(
+                evaluated
+                    .individuals()
+                    .iter()
+                    .cloned()
+                    .zip(evaluated.fitness_values().iter().cloned())
+                    .collect(),
+            );
+        }
+    }
+}
+
+impl<G, F, E, S, C, M, R, P, D> BestFitness for GeneticAlgorithm<G, F, E, S, C, M, R, P, D>
+where
+    G: Genotype,
+    F: Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Sync,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G> + Sync,
+    M: MutationOp<G> + Sync,
+    R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
+{
+    type Fitness = F;
+
+    fn best_fitness_of(output: &State<G, F, P>) -> F {
+        output.best_solution.solution.fitness.clone()
+    }
+
+    fn compare_best_fitness(output1: &State<G, F, P>, output2: &State<G, F, P>) -> Ordering {
+        output1.evaluated_population.compare_fitness(
+            &output1.best_solution.solution.fitness,
+            &output2.best_solution.solution.fitness,
+        )
+    }
+}
+
+/// The `GeneticAlgorithmState` does not contain the state that the operators
+/// adapt during the simulation, e.g. the mutation rate of an
+/// `mutation::adaptive::AdaptiveRateMutator` or the stagnation counter of a
+/// `reinsertion::restart::HypermutationReinserter`. The operators keep their
+/// current state when a state is restored. To resume exactly where a saved
+/// simulation was interrupted, the algorithm must be restored with operators
+/// in the same state as when the state was saved.
+impl<G, F, E, S, C, M, R, P, D> Resumable for GeneticAlgorithm<G, F, E, S, C, M, R, P, D>
+where
+    G: Genotype,
+    F: Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Sync,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G> + Sync,
+    M: MutationOp<G> + Sync,
+    R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
+{
+    type SavedState = GeneticAlgorithmState<G, F, P>;
+
+    fn save_state(&self) -> Self::SavedState {
+        GeneticAlgorithmState {
+            population: self.population.to_vec(),
+            best_solution: self.best_solution.clone(),
+            generations_since_improvement: self.generations_since_improvement,
+            top_solutions: self.top_solutions.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: Self::SavedState) {
+        self.replace_population(state.population);
+        self.best_solution = state.best_solution;
+        self.generations_since_improvement = state.generations_since_improvement;
+        self.top_solutions = state.top_solutions;
+    }
+}
+
+/// The `GeneticAlgorithm` is restarted by generating a fresh population with
+/// its `PopulationGenerator`. It can not be restarted if it was built with a
+/// pre-built initial population, then the population is left unchanged and
+/// `false` is returned.
+///
+/// The best solutions injected into the fresh population are the tracked top
+/// solutions, if the algorithm was built `with_top_solutions`, or else the
+/// best solution of all generations. They replace the first individuals of
+/// the fresh population, all other individuals are freshly generated. The
+/// best solution and the top solutions found so far are kept, while the
+/// number of generations since the last improvement starts from 0.
+impl<G, F, E, S, C, M, R, P, D> Restartable for GeneticAlgorithm<G, F, E, S, C, M, R, P, D>
+where
+    G: Genotype,
+    F: Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Sync,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G> + Sync,
+    M: MutationOp<G> + Sync,
+    R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
+{
+    fn restart_preserving_best<Q>(
+        &mut self,
+        num_best: usize,
+        rng: &mut Q,
+    ) -> Result<bool, Self::Error>
+    where
+        Q: SplittableRng,
+    {
+        let generator = match self.population_generator {
+            Some(ref generator) => generator,
+            None => return Ok(false),
+        };
+        let mut population = generator.generate(rng).individuals().to_vec();
+        let best_solutions = if self.top_solutions.is_empty() {
+            self.best_solution.iter().collect::<Vec<_>>()
+        } else {
+            self.top_solutions.iter().collect()
+        };
+        for (individual, best) in population
+            .iter_mut()
+            .zip(best_solutions.into_iter().take(num_best))
+        {
+            *individual = best.solution.genome.clone();
+        }
+        self.replace_population(population);
+        self.generations_since_improvement = 0;
         Ok(true)
     }
 }
 
+/// Checks that each fitness value of the evaluated population lies between
+/// the lowest and the highest possible fitness of the evaluator and that its
+/// scalar representation, if the conversion is given, is finite.
+fn validate_fitness<G, F, E>(
+    iteration: u64,
+    evaluated: &EvaluatedPopulation<G, F>,
+    evaluator: &E,
+    as_scalar: Option<fn(&F) -> f64>,
+) -> Result<(), GeneticAlgorithmError>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    let lowest = evaluator.lowest_possible_fitness();
+    let highest = evaluator.highest_possible_fitness();
+    for (index, fitness) in evaluated.fitness_values().iter().enumerate() {
+        let problem = if as_scalar.is_some_and(|as_scalar| !as_scalar(fitness).is_finite()) {
+            "which is not finite".to_string()
+        } else if *fitness < lowest || *fitness > highest {
+            format!(
+                "which is outside of the range from the lowest possible fitness {:?} to the \
+                 highest possible fitness {:?}",
+                lowest, highest
+            )
+        } else {
+            continue;
+        };
+        return Err(GeneticAlgorithmError::InvalidFitness(format!(
+            "The fitness function returned the fitness {:?}, {}, for the individual {} of \
+             generation {}: {:?}",
+            fitness,
+            problem,
+            index,
+            iteration,
+            evaluated.individuals()[index]
+        )));
+    }
+    Ok(())
+}
+
+/// Evaluates the fitness of the population. Lower fitness values are better
+/// in the returned `EvaluatedPopulation` if `minimize` is set. The operators
+/// clone the individuals of the returned `EvaluatedPopulation` into the spare
+/// genomes of the given `GenomePool`, if any.
 fn evaluate_fitness<G, F, E>(
-    population: Rc<Vec<G>>,
+    population: Arc<Vec<G>>,
     evaluator: &E,
+    minimize: bool,
+    genome_pool: Option<GenomePool<G>>,
 ) -> TimedResult<EvaluatedPopulation<G, F>>
 where
     G: Genotype + Sync,
@@ -243,23 +1192,39 @@ where
     E: FitnessFunction<G, F> + Sync,
 {
     let evaluation = par_evaluate_fitness(&population, evaluator);
-    let average = timed(|| evaluator.average(&evaluation.result.0)).run();
-    let evaluated = EvaluatedPopulation::new(
-        population,
-        evaluation.result.0,
-        evaluation.result.1,
-        evaluation.result.2,
-        average.result,
-    );
+    let (mut fitness_values, mut highest, mut lowest) = evaluation.result;
+    let adjustment = timed(|| {
+        if evaluator.adjust_population_fitness(&population, &mut fitness_values) {
+            if let Some(max) = fitness_values.iter().max() {
+                highest = max.clone();
+            }
+            if let Some(min) = fitness_values.iter().min() {
+                lowest = min.clone();
+            }
+        }
+    })
+    .run();
+    let average = timed(|| evaluator.average(&fitness_values)).run();
+    let mut evaluated =
+        EvaluatedPopulation::new(population, fitness_values, highest, lowest, average.result)
+            .with_minimize(minimize);
+    if let Some(genome_pool) = genome_pool {
+        evaluated = evaluated.with_genome_pool(genome_pool);
+    }
     TimedResult {
         result: evaluated,
-        time: evaluation.time + average.time,
+        time: evaluation.time + adjustment.time + average.time,
     }
 }
 
 /// Calculates the `genetic::Fitness` value of each `genetic::Genotype` and
 /// records the highest and lowest values.
-#[cfg(not(target_arch = "wasm32"))]
+///
+/// The fitness values are calculated in parallel unless the `FitnessFunction`
+/// opts out by returning `false` from `use_parallel_ga`. The order of the
+/// calculated fitness values always corresponds to the order of the
+/// individuals in the population.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
 fn par_evaluate_fitness<G, F, E>(population: &[G], evaluator: &E) -> TimedResult<(Vec<F>, F, F)>
 where
     G: Genotype + Sync,
@@ -267,60 +1232,72 @@ where
     E: FitnessFunction<G, F> + Sync,
 {
     if evaluator.use_parallel_ga() {
-        timed(|| population.into_par_iter()
-            .map(|x| evaluator.fitness_of(x))
-            .fold(|| (vec![], evaluator.lowest_possible_fitness(), evaluator.highest_possible_fitness()),
-                  |(mut fitness, mut highest, mut lowest), score| {
-                      if score > highest {
-                          highest = score.clone();
-                      }
-                      if score < lowest {
-                          lowest = score.clone();
-                      }
-                      fitness.push(score);
-
-                      (fitness, highest, lowest)
-                  })
-            .reduce_with(|(mut fitness, mut highest, mut lowest), (mut fitness2, highest2, lowest2)| {
-                fitness.append(&mut fitness2);
-                if highest2 > highest {
-                    highest = highest2;
-                }
-                if lowest2 < lowest {
-                    lowest = lowest2;
-                }
-                (fitness, highest, lowest)
-            })
-            .unwrap()
-        )
-            .run()
-    } else {
         timed(|| {
-            let mut fitness = Vec::with_capacity(population.len());
-            let mut highest = evaluator.lowest_possible_fitness();
-            let mut lowest = evaluator.highest_possible_fitness();
-            for genome in population.iter() {
-                let score = evaluator.fitness_of(genome);
-                if score > highest {
-                    highest = score.clone();
-                }
-                if score < lowest {
-                    lowest = score.clone();
-                }
-                fitness.push(score);
-            }
-            (fitness, highest, lowest)
+            population
+                .into_par_iter()
+                .map(|x| evaluator.fitness_of(x))
+                .fold(
+                    || {
+                        (
+                            vec![],
+                            evaluator.lowest_possible_fitness(),
+                            evaluator.highest_possible_fitness(),
+                        )
+                    },
+                    |(mut fitness, mut highest, mut lowest), score| {
+                        if score > highest {
+                            highest = score.clone();
+                        }
+                        if score < lowest {
+                            lowest = score.clone();
+                        }
+                        fitness.push(score);
+
+                        (fitness, highest, lowest)
+                    },
+                )
+                .reduce_with(
+                    |(mut fitness, mut highest, mut lowest), (mut fitness2, highest2, lowest2)| {
+                        fitness.append(&mut fitness2);
+                        if highest2 > highest {
+                            highest = highest2;
+                        }
+                        if lowest2 < lowest {
+                            lowest = lowest2;
+                        }
+                        (fitness, highest, lowest)
+                    },
+                )
+                .unwrap()
         })
         .run()
+    } else {
+        seq_evaluate_fitness(population, evaluator)
     }
 }
 
-#[cfg(target_arch = "wasm32")]
+/// Calculates the `genetic::Fitness` value of each `genetic::Genotype` and
+/// records the highest and lowest values.
+///
+/// Without the `parallel` feature or on wasm32 targets the fitness values are
+/// always calculated sequentially.
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
 fn par_evaluate_fitness<G, F, E>(population: &[G], evaluator: &E) -> TimedResult<(Vec<F>, F, F)>
 where
     G: Genotype + Sync,
     F: Fitness + Send + Sync,
     E: FitnessFunction<G, F> + Sync,
+{
+    seq_evaluate_fitness(population, evaluator)
+}
+
+/// Calculates the `genetic::Fitness` value of each `genetic::Genotype` one
+/// after the other and records the highest and lowest values.
+fn seq_evaluate_fitness<G, F, E>(population: &[G], evaluator: &E) -> TimedResult<(Vec<F>, F, F)>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
 {
     timed(|| {
         let mut fitness = Vec::with_capacity(population.len());
@@ -341,104 +1318,365 @@ where
     .run()
 }
 
-/// Determines the best solution of the current population
-fn determine_best_solution<G, F>(
+/// Determines the best solution of the current population and decodes its
+/// phenotype.
+fn determine_best_solution<G, F, P, D>(
     generation: u64,
     score_board: &EvaluatedPopulation<G, F>,
-) -> TimedResult<BestSolution<G, F>>
+    decoder: &D,
+    tie_break: &TieBreak<G>,
+) -> TimedResult<BestSolution<G, F, P>>
 where
     G: Genotype,
     F: Fitness,
+    D: GenotypeToPhenotype<G, P>,
 {
     timed(|| {
-        let evaluated = score_board
-            .evaluated_individual_with_fitness(&score_board.highest_fitness())
+        let best_fitness = score_board.best_fitness();
+        let individuals = score_board.individuals();
+        let fitness_values = score_board.fitness_values();
+        let mut best_indices =
+            (0..fitness_values.len()).filter(|index| fitness_values[*index] == *best_fitness);
+        let best_index = match tie_break {
+            TieBreak::Comparator(compare) => {
+                best_indices.min_by(|x, y| compare(&individuals[*x], &individuals[*y]))
+            },
+            TieBreak::FirstSeen | TieBreak::LowestGeneration => best_indices.next(),
+        };
+        let evaluated = best_index
+            .map(|index| Evaluated {
+                genome: individuals[index].clone(),
+                fitness: fitness_values[index].clone(),
+            })
             .unwrap_or_else(|| {
                 panic!(
                     "No fitness value of {:?} found in this EvaluatedPopulation",
-                    &score_board.highest_fitness()
+                    best_fitness
                 )
             });
+        let phenotype = decoder.decode(&evaluated.genome);
         BestSolution {
             generation,
             solution: evaluated,
+            phenotype,
         }
     })
     .run()
 }
 
+/// Inserts the individuals of the current population into the best distinct
+/// solutions found so far, keeping at most `count` solutions ordered from the
+/// best to the worst fitness.
+///
+/// Genomes are compared by `SameGenome`, so that each genome is contained
+/// only once. An individual only replaces a solution of the list if its fitness is
+/// better, thus of equally fit solutions the one found first is kept. Only
+/// the phenotypes of inserted solutions are decoded.
+fn update_top_solutions<G, F, P, D>(
+    top_solutions: &mut Vec<BestSolution<G, F, P>>,
+    count: usize,
+    generation: u64,
+    score_board: &EvaluatedPopulation<G, F>,
+    decoder: &D,
+    same_genome: &SameGenome<G>,
+) where
+    G: Genotype,
+    F: Fitness,
+    D: GenotypeToPhenotype<G, P>,
+{
+    let individuals = score_board.individuals();
+    for (genome, fitness) in individuals.iter().zip(score_board.fitness_values()) {
+        if top_solutions.len() >= count
+            && top_solutions.last().is_some_and(|worst| {
+                score_board.compare_fitness(fitness, &worst.solution.fitness) != Ordering::Greater
+            })
+        {
+            continue;
+        }
+        if top_solutions
+            .iter()
+            .any(|top| same_genome.same(&top.solution.genome, genome))
+        {
+            continue;
+        }
+        let index = top_solutions.partition_point(|top| {
+            score_board.compare_fitness(&top.solution.fitness, fitness) != Ordering::Less
+        });
+        top_solutions.insert(
+            index,
+            BestSolution {
+                generation,
+                solution: Evaluated {
+                    genome: genome.clone(),
+                    fitness: fitness.clone(),
+                },
+                phenotype: decoder.decode(genome),
+            },
+        );
+        top_solutions.truncate(count);
+    }
+}
+
+/// The offspring bred from the selected parents together with the time spent
+/// on breeding.
+struct Breeding<G> {
+    offspring: Offspring<G>,
+    /// The time spent on crossover and mutation.
+    time: ProcessingTime,
+    /// The time spent on crossover and the time spent on mutation, if they
+    /// have been measured separately.
+    phases: Option<(ProcessingTime, ProcessingTime)>,
+    /// The children before they have been mutated, if they are inspected.
+    children: Option<Vec<G>>,
+    /// The parent each genome of the offspring has been bred from, if the
+    /// lineage is tracked.
+    lineage: Option<Vec<G>>,
+}
+
+impl<G> Breeding<G> {
+    /// Appends the offspring of another breeding to this offspring.
+    fn append(&mut self, mut other: Breeding<G>) {
+        self.offspring.append(&mut other.offspring);
+        self.time += other.time;
+        self.phases = match (self.phases, other.phases) {
+            (Some((crossover, mutation)), Some((other_crossover, other_mutation))) => {
+                Some((crossover + other_crossover, mutation + other_mutation))
+            },
+            _ => None,
+        };
+        self.children = match (self.children.take(), other.children) {
+            (Some(mut children), Some(mut other_children)) => {
+                children.append(&mut other_children);
+                Some(children)
+            },
+            _ => None,
+        };
+        self.lineage = match (self.lineage.take(), other.lineage) {
+            (Some(mut lineage), Some(mut other_lineage)) => {
+                lineage.append(&mut other_lineage);
+                Some(lineage)
+            },
+            _ => None,
+        };
+    }
+
+    /// Drops the children of the offspring beyond the given length.
+    fn truncate(&mut self, len: usize) {
+        self.offspring.truncate(len);
+        if let Some(ref mut children) = self.children {
+            children.truncate(len);
+        }
+        if let Some(ref mut lineage) = self.lineage {
+            lineage.truncate(len);
+        }
+    }
+}
+
 /// Lets the parents breed their offspring and mutate its children. And
 /// finally combines the offspring of all parents into one big offspring.
-#[cfg(not(target_arch = "wasm32"))]
-fn par_breed_offspring<G, C, M>(
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn par_breed_offspring<G, C, M, Q>(
     parents: Vec<Parents<G>>,
     breeder: &C,
     mutator: &M,
-    rng: &mut Prng,
-) -> TimedResult<Offspring<G>>
+    rngs: &mut BreedingRngs<Q>,
+    timings: bool,
+    inspection: bool,
+    lineage: bool,
+) -> Breeding<G>
 where
     G: Genotype + Send,
     C: CrossoverOp<G> + Sync,
     M: MutationOp<G> + Sync,
+    Q: SplittableRng,
 {
     if parents.len() < 50 {
-        timed(|| {
-            let mut offspring: Offspring<G> = Vec::with_capacity(parents.len() * parents[0].len());
-            for parents in parents {
-                let children = breeder.crossover(parents, rng);
-                for child in children {
-                    let mutated = mutator.mutate(child, rng);
-                    offspring.push(mutated);
-                }
-            }
-            offspring
-        })
-        .run()
+        breed_offspring(
+            parents, breeder, mutator, rngs, timings, inspection, lineage,
+        )
     } else {
-        rng.jump();
-        let mut rng1 = rng.clone();
-        rng.jump();
-        let mut rng2 = rng.clone();
+        let mut rng1 = rngs.crossover.split();
+        let mut rng2 = rngs.crossover.split();
+        let (mut mutation_rng1, mut mutation_rng2) = match rngs.mutation {
+            Some(ref mut rng) => (Some(rng.split()), Some(rng.split())),
+            None => (None, None),
+        };
+        let mut rngs1 = BreedingRngs {
+            crossover: &mut rng1,
+            mutation: mutation_rng1.as_mut(),
+        };
+        let mut rngs2 = BreedingRngs {
+            crossover: &mut rng2,
+            mutation: mutation_rng2.as_mut(),
+        };
         let mid_point = parents.len() / 2;
-        let mut offspring = Vec::with_capacity(parents.len() * 2);
         let mut parents = parents;
         let r_slice = parents.drain(mid_point..).collect();
         let l_slice = parents;
-        let (mut left, mut right) = rayon::join(
-            || par_breed_offspring(l_slice, breeder, mutator, &mut rng1),
-            || par_breed_offspring(r_slice, breeder, mutator, &mut rng2),
+        let (mut left, right) = rayon::join(
+            || {
+                par_breed_offspring(
+                    l_slice, breeder, mutator, &mut rngs1, timings, inspection, lineage,
+                )
+            },
+            || {
+                par_breed_offspring(
+                    r_slice, breeder, mutator, &mut rngs2, timings, inspection, lineage,
+                )
+            },
         );
-        offspring.append(&mut left.result);
-        offspring.append(&mut right.result);
-        TimedResult {
-            result: offspring,
-            time: left.time + right.time,
-        }
+        left.append(right);
+        left
     }
 }
 
-#[cfg(target_arch = "wasm32")]
-fn par_breed_offspring<G, C, M>(
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+fn par_breed_offspring<G, C, M, Q>(
     parents: Vec<Parents<G>>,
     breeder: &C,
     mutator: &M,
-    rng: &mut Prng,
-) -> TimedResult<Offspring<G>>
+    rngs: &mut BreedingRngs<Q>,
+    timings: bool,
+    inspection: bool,
+    lineage: bool,
+) -> Breeding<G>
 where
     G: Genotype + Send,
     C: CrossoverOp<G> + Sync,
     M: MutationOp<G> + Sync,
+    Q: SplittableRng,
 {
-    timed(|| {
-        let mut offspring: Offspring<G> = Vec::with_capacity(parents.len() * parents[0].len());
-        for parents in parents {
-            let children = breeder.crossover(parents, rng);
-            for child in children {
-                let mutated = mutator.mutate(child, rng);
-                offspring.push(mutated);
+    breed_offspring(
+        parents, breeder, mutator, rngs, timings, inspection, lineage,
+    )
+}
+
+/// The random number generators of the phases of one generation, which are
+/// used if the `GeneticAlgorithm` is built with `with_phase_rngs(true)`.
+struct PhaseRngs<Q> {
+    selection: Q,
+    crossover: Q,
+    mutation: Q,
+    reinsertion: Q,
+}
+
+impl<Q> PhaseRngs<Q>
+where
+    Q: SplittableRng,
+{
+    /// Splits an independent generator for each phase off the given
+    /// generator. The given generator is only advanced by a fixed number of
+    /// steps and not used otherwise.
+    fn split_from(rng: &mut Q) -> Self {
+        PhaseRngs {
+            selection: rng.split(),
+            crossover: rng.split(),
+            mutation: rng.split(),
+            reinsertion: rng.split(),
+        }
+    }
+}
+
+/// The random number generators used for breeding the offspring.
+struct BreedingRngs<'a, Q> {
+    /// The generator of the crossover operator, which is also used by the
+    /// mutation operator unless it has its own generator.
+    crossover: &'a mut Q,
+    /// The own generator of the mutation operator.
+    mutation: Option<&'a mut Q>,
+}
+
+impl<'a, Q> BreedingRngs<'a, Q> {
+    /// Returns the generator of the mutation operator.
+    fn mutation(&mut self) -> &mut Q {
+        match self.mutation {
+            Some(ref mut rng) => rng,
+            None => self.crossover,
+        }
+    }
+}
+
+/// Breeds the offspring of the given parents one after the other. If
+/// `timings` is set each call of the crossover and the mutation operator is
+/// timed separately. If `inspection` is set the children are also returned
+/// before they are mutated. If `lineage` is set the parent of each genome of
+/// the offspring is returned too, which is the parent at the same position
+/// as the genome among the children of its parents.
+fn breed_offspring<G, C, M, Q>(
+    parents: Vec<Parents<G>>,
+    breeder: &C,
+    mutator: &M,
+    rngs: &mut BreedingRngs<Q>,
+    timings: bool,
+    inspection: bool,
+    lineage: bool,
+) -> Breeding<G>
+where
+    G: Genotype,
+    C: CrossoverOp<G>,
+    M: MutationOp<G>,
+    Q: SplittableRng,
+{
+    let capacity = parents.len() * parents[0].len();
+    if !timings && !inspection && !lineage {
+        let breeding = timed(|| {
+            let mut offspring: Offspring<G> = Vec::with_capacity(capacity);
+            for parents in parents {
+                let children = breeder.crossover(parents, rngs.crossover);
+                for child in children {
+                    let mutated = mutator.mutate(child, rngs.mutation());
+                    offspring.push(mutated);
+                }
             }
+            offspring
+        })
+        .run();
+        return Breeding {
+            offspring: breeding.result,
+            time: breeding.time,
+            phases: None,
+            children: None,
+            lineage: None,
+        };
+    }
+    let mut offspring: Offspring<G> = Vec::with_capacity(capacity);
+    let mut inspected: Option<Vec<G>> = if inspection {
+        Some(Vec::with_capacity(capacity))
+    } else {
+        None
+    };
+    let mut tracked: Option<Vec<G>> = if lineage {
+        Some(Vec::with_capacity(capacity))
+    } else {
+        None
+    };
+    let mut crossover = ProcessingTime::zero();
+    let mut mutation = ProcessingTime::zero();
+    for parents in parents {
+        let bred_from = if lineage { Some(parents.clone()) } else { None };
+        let children = timed(|| breeder.crossover(parents, rngs.crossover)).run();
+        crossover += children.time;
+        for (index, child) in children.result.into_iter().enumerate() {
+            if let (Some(ref mut tracked), Some(ref bred_from)) = (&mut tracked, &bred_from) {
+                tracked.push(bred_from[index % bred_from.len()].clone());
+            }
+            if let Some(ref mut inspected) = inspected {
+                inspected.push(child.clone());
+            }
+            let mutated = timed(|| mutator.mutate(child, rngs.mutation())).run();
+            mutation += mutated.time;
+            offspring.push(mutated.result);
         }
-        offspring
-    })
-    .run()
+    }
+    Breeding {
+        offspring,
+        time: crossover + mutation,
+        phases: if timings {
+            Some((crossover, mutation))
+        } else {
+            None
+        },
+        children: inspected,
+        lineage: tracked,
+    }
 }
@@ -3,7 +3,18 @@
 //! or search problem. The types are named after terms as they are found in
 //! genetic biology.
 
-use std::fmt::Debug;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use std::{collections::hash_map::RandomState, hash::BuildHasher, sync::OnceLock};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::{self, Debug},
+    hash::Hash,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
 
 /// A `Phenotype` is a candidate solution of the optimization or search problem.
 /// Phenotypes are also called individuals or creatures. A `Phenotype` is the
@@ -28,6 +39,36 @@ where
     fn derive(&self, new_genes: G) -> Self;
 }
 
+/// A `GenotypeToPhenotype` decodes a `Genotype` into the candidate solution
+/// in the problem domain, its phenotype.
+///
+/// A decoder is registered on the `ga::builder::GeneticAlgorithmBuilder` by
+/// calling `with_decoder`. The `ga::GeneticAlgorithm` decodes the best
+/// solution of each generation, so that its phenotype is available as
+/// `best_solution.phenotype` in the results of the simulation.
+pub trait GenotypeToPhenotype<G, P>: Clone + Debug
+where
+    G: Genotype,
+{
+    /// Decodes the given genome into its phenotype.
+    fn decode(&self, genome: &G) -> P;
+}
+
+/// The `IdentityDecoder` is the `GenotypeToPhenotype` decoder that is used
+/// if no decoder is registered. The phenotype is a clone of the genome, so
+/// the only cost is one clone of the best genome per generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct IdentityDecoder;
+
+impl<G> GenotypeToPhenotype<G, G> for IdentityDecoder
+where
+    G: Genotype,
+{
+    fn decode(&self, genome: &G) -> G {
+        genome.clone()
+    }
+}
+
 /// A `Genotype` defines those properties of a `Phenotype` that are relevant
 /// for the genetic algorithm. Respectively they are used to determine the
 /// `Fitness` value of the solution candidate. These properties are also called
@@ -40,6 +81,136 @@ pub trait Genotype: Clone + Debug + PartialEq + Send + Sync {
     type Dna: Clone + Debug + PartialEq;
 }
 
+/// A `GenomeDistance` measures how different two `Genotype`s are.
+///
+/// The distance of two identical genomes is 0. The more the genomes differ
+/// the greater is their distance.
+///
+/// It is the common foundation of all operators that compare genomes, e.g.
+/// the niching of `reinsertion::crowding::DeterministicCrowding`. Provided
+/// implementations are the `HammingDistance` for discrete genomes and the
+/// `EuclideanDistance` for continuous genomes.
+pub trait GenomeDistance<G>: Clone
+where
+    G: Genotype,
+{
+    /// Calculates the distance between the two given genomes.
+    fn distance(&self, genome1: &G, genome2: &G) -> f64;
+}
+
+/// The `HammingDistance` is the number of loci at which two genomes differ.
+/// If the genomes are of different length, the surplus loci of the longer
+/// genome count as differing.
+///
+/// The distance is suited for discrete genomes like `Vec<bool>`, `Vec<u8>` or
+/// `genotype::BinaryGenome`. As a `termination::convergence::DiversityMetric`
+/// it measures the average hamming distance over all pairs of individuals.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HammingDistance;
+
+impl<V> GenomeDistance<Vec<V>> for HammingDistance
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn distance(&self, genome1: &Vec<V>, genome2: &Vec<V>) -> f64 {
+        let num_differing = genome1
+            .iter()
+            .zip(genome2.iter())
+            .filter(|(value1, value2)| value1 != value2)
+            .count();
+        let length_difference = genome1.len().max(genome2.len()) - genome1.len().min(genome2.len());
+        (num_differing + length_difference) as f64
+    }
+}
+
+/// The `EuclideanDistance` is the euclidean distance between two genomes
+/// interpreted as points in space.
+///
+/// The distance is suited for continuous genomes like `Vec<f64>`, but can
+/// also be used for genomes of integers. If the genomes are of different
+/// length, only the loci of the shorter genome are taken into account. As a
+/// `termination::convergence::DiversityMetric` it measures the average
+/// euclidean distance over all pairs of individuals.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EuclideanDistance;
+
+impl<V> GenomeDistance<Vec<V>> for EuclideanDistance
+where
+    V: Clone + Copy + Debug + PartialEq + Send + Sync + Into<f64>,
+{
+    fn distance(&self, genome1: &Vec<V>, genome2: &Vec<V>) -> f64 {
+        genome1
+            .iter()
+            .zip(genome2.iter())
+            .map(|(&value1, &value2)| {
+                let delta = value1.into() - value2.into();
+                delta * delta
+            })
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+/// A `GenomeIdentity` defines when two genomes are considered the same
+/// solution by mapping each genome to a canonical key. Genomes with equal
+/// keys are the same, even if they differ in their genes, e.g. a round trip
+/// of a traveling salesman problem and its reversed round trip.
+///
+/// It is used by the features that look for duplicate genomes, e.g. the
+/// `reinsertion::unique::UniqueReinserter`, the top solutions tracked by the
+/// `ga::GeneticAlgorithm` and the `termination::convergence::UniqueKeyFraction`.
+/// Provided implementations are the `ExactIdentity`, which compares the
+/// genomes themselves, and the `ReversalIdentity` for symmetric permutations.
+pub trait GenomeIdentity<G>: Clone
+where
+    G: Genotype,
+{
+    /// The canonical key of the genomes.
+    type Key: Hash + Eq;
+
+    /// Returns the canonical key of the given genome.
+    fn canonical_key(&self, genome: &G) -> Self::Key;
+}
+
+/// The `ExactIdentity` is the `GenomeIdentity` that considers two genomes as
+/// the same only if they are equal. The key is a clone of the genome. It can
+/// be used for any genome that implements `Hash` and `Eq`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExactIdentity;
+
+impl<G> GenomeIdentity<G> for ExactIdentity
+where
+    G: Genotype + Hash + Eq,
+{
+    type Key = G;
+
+    fn canonical_key(&self, genome: &G) -> G {
+        genome.clone()
+    }
+}
+
+/// The `ReversalIdentity` is the `GenomeIdentity` that considers a genome and
+/// its reversed genome as the same, e.g. a round trip of a symmetric
+/// traveling salesman problem in both directions. The key is the lesser of
+/// both.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReversalIdentity;
+
+impl<V> GenomeIdentity<Vec<V>> for ReversalIdentity
+where
+    V: Clone + Debug + Hash + Ord + Send + Sync,
+{
+    type Key = Vec<V>;
+
+    fn canonical_key(&self, genome: &Vec<V>) -> Vec<V> {
+        if genome.iter().rev().lt(genome.iter()) {
+            genome.iter().rev().cloned().collect()
+        } else {
+            genome.clone()
+        }
+    }
+}
+
 /// The `Locus` is a position within a `Genotype`.
 pub type Locus = usize;
 
@@ -72,6 +243,14 @@ pub type Offspring<G> = Vec<G>;
 /// A `Genotype` with a `Fitness` value of `f1` performs better than another
 /// `Genotype` with a `Fitness` value of `f2` if `f1 > f2`.
 ///
+/// The best solution of a generation is the `Genotype` with the highest
+/// `Fitness` value. For minimization problems the `ga::GeneticAlgorithm` is
+/// built with a minimizing `operator::SelectionOp`, e.g. the
+/// `selection::truncation::MinimizeSelector`. Then a `Genotype` with a
+/// `Fitness` value of `f1` performs better if `f1 < f2` and the best solution
+/// is the `Genotype` with the lowest `Fitness` value, see
+/// `operator::SelectionOp::is_minimize`.
+///
 /// For multi-objective `Fitness` values either `operator::GeneticOperator`s
 /// suitable for multi-objective optimization are used or the implementation
 /// of the multi-objective `Fitness` value additionally implements the
@@ -100,6 +279,33 @@ pub trait AsScalar {
     fn as_scalar(&self) -> f64;
 }
 
+/// A `Fitness` value that can be reduced by a penalty. It is used by the
+/// `ConstrainedFitness` to penalize genomes that violate constraints.
+pub trait Penalizable: Fitness {
+    /// Returns this `Fitness` value reduced by the given penalty. The penalty
+    /// is a non-negative value. A positive penalty must result in a lower
+    /// `Fitness` value unless this value is already the lowest possible one.
+    fn penalized(&self, penalty: f64) -> Self;
+}
+
+/// A `MultiObjectiveFitness` value consists of several objective values, e.g.
+/// the cost and the weight of a construction. The objective values are used
+/// to determine the Pareto dominance between two solutions as provided by
+/// `pareto::ParetoDominance`.
+///
+/// Values implementing this trait must always return the same number of
+/// objective values in the same order.
+pub trait MultiObjectiveFitness {
+    /// Returns the objective values of this fitness value.
+    fn objectives(&self) -> Vec<f64>;
+}
+
+impl MultiObjectiveFitness for Vec<f64> {
+    fn objectives(&self) -> Vec<f64> {
+        self.clone()
+    }
+}
+
 /// Defines the evaluation function to calculate the `Fitness` value of a
 /// `Genotype` based on its properties.
 pub trait FitnessFunction<G, F>: Clone
@@ -120,5 +326,1953 @@ where
     /// This is usually a value equivalent to zero.
     fn lowest_possible_fitness(&self) -> F;
 
-    fn use_parallel_ga(&self) -> bool { true }
+    /// Returns whether the `Fitness` values of a population may be calculated
+    /// in parallel.
+    ///
+    /// Parallel evaluation requires the crate feature `parallel`, which is
+    /// enabled by default. To be used by the `ga::GeneticAlgorithm` the
+    /// `FitnessFunction` must be `Sync` and the `Fitness` must be `Send` and
+    /// `Sync`. Fitness functions that are expensive to calculate benefit most
+    /// from parallel evaluation. As the evaluation order does not affect the
+    /// calculated values, the results of a simulation are the same whether
+    /// the fitness values are calculated in parallel or not.
+    ///
+    /// The default implementation returns `true`.
+    fn use_parallel_ga(&self) -> bool {
+        true
+    }
+
+    /// Tells this `FitnessFunction` the number of the generation whose
+    /// population is about to be evaluated, e.g. to penalize infeasible
+    /// genomes more in later generations like the `ConstrainedFitness` does.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function once per generation
+    /// before any `Fitness` value of the generation is calculated.
+    ///
+    /// The default implementation does nothing.
+    fn start_generation(&self, _generation: u64) {}
+
+    /// Adjusts the `Fitness` values of a whole population after each value
+    /// has been calculated by `fitness_of`, e.g. to share the fitness among
+    /// similar individuals. The `Fitness` value at index `i` belongs to the
+    /// genome at index `i` of the population.
+    ///
+    /// Returns whether any value has been changed. The `ga::GeneticAlgorithm`
+    /// calls this function for each generation. `operator::ReinsertionOp`s
+    /// that evaluate the offspring by `fitness_of` do not see the adjusted
+    /// values.
+    ///
+    /// The default implementation does not change any value and returns
+    /// `false`.
+    fn adjust_population_fitness(&self, _population: &[G], _fitness_values: &mut [F]) -> bool {
+        false
+    }
+
+    /// Returns whether the `ga::GeneticAlgorithm` shall tell this
+    /// `FitnessFunction` the parent of each bred genome by `record_lineage`.
+    ///
+    /// The default implementation returns `false`, so that the parents are
+    /// not cloned for `record_lineage`.
+    fn tracks_lineage(&self) -> bool {
+        false
+    }
+
+    /// Records the parent of each genome of the offspring of one generation.
+    /// The genome at index `i` of `offspring` has been bred from the parent
+    /// at index `i` of `parents`, i.e. it has been recombined from this and
+    /// other parents and has been mutated afterwards.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function once per generation
+    /// after the offspring has been bred, if `tracks_lineage` returns `true`.
+    /// The `IncrementalFitnessEvaluation` uses the lineage to calculate the
+    /// `Fitness` values of the offspring from those of their parents.
+    ///
+    /// The default implementation does nothing.
+    fn record_lineage(&self, _parents: &[G], _offspring: &[G]) {}
+}
+
+/// The `CachingFitnessEvaluation` is a `FitnessFunction` that remembers the
+/// `Fitness` values calculated by another `FitnessFunction`.
+///
+/// The `Fitness` value of a genome is only calculated by the wrapped
+/// `FitnessFunction` if it is not found in the cache. This saves time for
+/// expensive fitness functions when the same genomes appear again and again,
+/// e.g. because elitist reinsertion keeps the best individuals over many
+/// generations.
+///
+/// The genomes are the keys of the cache. Therefore they must implement `Hash`
+/// and `Eq`, which e.g. `Vec<f64>` does not. The genomes are cloned into the
+/// cache, thus the cache is most effective for cheap to clone genomes and
+/// expensive fitness functions.
+///
+/// By default the cache grows without limits. A cache created by the
+/// `with_capacity` function holds at most the given number of entries. When it
+/// is full, the least recently used entry is removed to make room for a new
+/// one.
+///
+/// Clones of a `CachingFitnessEvaluation` share the same cache. The cache is
+/// safe to be used from several threads in parallel. As the lock on the cache
+/// is not held while the wrapped fitness function calculates a value, the
+/// same genome may be evaluated more than once if it is evaluated by several
+/// threads at the same time.
+#[derive(Clone, Debug)]
+pub struct CachingFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    /// The wrapped `FitnessFunction`.
+    evaluator: E,
+    /// The cache shared by all clones.
+    cache: Arc<Mutex<FitnessCache<G, F>>>,
+}
+
+impl<G, F, E> CachingFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    /// Creates a new `CachingFitnessEvaluation` wrapping the given
+    /// `FitnessFunction` with a cache of unlimited size.
+    pub fn new(evaluator: E) -> Self {
+        CachingFitnessEvaluation {
+            evaluator,
+            cache: Arc::new(Mutex::new(FitnessCache::new(None))),
+        }
+    }
+
+    /// Creates a new `CachingFitnessEvaluation` wrapping the given
+    /// `FitnessFunction` with a cache that holds at most `capacity` entries.
+    /// When the cache is full the least recently used entry is removed.
+    pub fn with_capacity(evaluator: E, capacity: usize) -> Self {
+        CachingFitnessEvaluation {
+            evaluator,
+            cache: Arc::new(Mutex::new(FitnessCache::new(Some(capacity)))),
+        }
+    }
+
+    /// Returns the wrapped `FitnessFunction`.
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// Returns the maximum number of entries of the cache or `None` if the
+    /// size of the cache is unlimited.
+    pub fn capacity(&self) -> Option<usize> {
+        self.lock_cache().capacity
+    }
+
+    /// Returns the number of genomes whose fitness is currently cached.
+    pub fn num_cached(&self) -> usize {
+        self.lock_cache().entries.len()
+    }
+
+    /// Removes all entries from the cache.
+    pub fn clear(&self) {
+        self.lock_cache().clear();
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, FitnessCache<G, F>> {
+        // the cache holds valid entries only even if a thread panicked
+        self.cache.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl<G, F, E> FitnessFunction<G, F> for CachingFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    fn fitness_of(&self, genome: &G) -> F {
+        if let Some(fitness) = self.lock_cache().get(genome) {
+            return fitness;
+        }
+        let fitness = self.evaluator.fitness_of(genome);
+        self.lock_cache().insert(genome.clone(), fitness.clone());
+        fitness
+    }
+
+    fn average(&self, fitness_values: &[F]) -> F {
+        self.evaluator.average(fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> F {
+        self.evaluator.highest_possible_fitness()
+    }
+
+    fn lowest_possible_fitness(&self) -> F {
+        self.evaluator.lowest_possible_fitness()
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        self.evaluator.use_parallel_ga()
+    }
+
+    fn start_generation(&self, generation: u64) {
+        self.evaluator.start_generation(generation)
+    }
+
+    fn adjust_population_fitness(&self, population: &[G], fitness_values: &mut [F]) -> bool {
+        self.evaluator
+            .adjust_population_fitness(population, fitness_values)
+    }
+
+    fn tracks_lineage(&self) -> bool {
+        self.evaluator.tracks_lineage()
+    }
+
+    fn record_lineage(&self, parents: &[G], offspring: &[G]) {
+        self.evaluator.record_lineage(parents, offspring)
+    }
+}
+
+/// The cache of the `CachingFitnessEvaluation`. Each entry records when it
+/// was used last, so that the least recently used entry can be found in the
+/// `usage` map when the cache is full.
+#[derive(Debug)]
+struct FitnessCache<G, F> {
+    capacity: Option<usize>,
+    entries: HashMap<G, (F, u64)>,
+    usage: BTreeMap<u64, G>,
+    clock: u64,
+}
+
+impl<G, F> FitnessCache<G, F>
+where
+    G: Clone + Hash + Eq,
+    F: Clone,
+{
+    fn new(capacity: Option<usize>) -> Self {
+        FitnessCache {
+            capacity,
+            entries: HashMap::new(),
+            usage: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, genome: &G) -> Option<F> {
+        let is_bounded = self.capacity.is_some();
+        self.clock += 1;
+        let clock = self.clock;
+        let (fitness, last_used) = self.entries.get_mut(genome)?;
+        if is_bounded {
+            let genome = self.usage.remove(last_used).expect("usage of cached entry");
+            self.usage.insert(clock, genome);
+            *last_used = clock;
+        }
+        Some(fitness.clone())
+    }
+
+    fn insert(&mut self, genome: G, fitness: F) {
+        match self.capacity {
+            Some(0) => return,
+            Some(capacity) => {
+                if let Some((_, last_used)) = self.entries.get(&genome) {
+                    self.usage.remove(last_used);
+                } else if self.entries.len() >= capacity {
+                    let (_, least_recently_used) =
+                        self.usage.pop_first().expect("a full cache has entries");
+                    self.entries.remove(&least_recently_used);
+                }
+                self.clock += 1;
+                self.usage.insert(self.clock, genome.clone());
+            },
+            None => {},
+        }
+        self.entries.insert(genome, (fitness, self.clock));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.usage.clear();
+    }
+}
+
+/// The `SharedCachingFitnessEvaluation` is a `FitnessFunction` that remembers
+/// the `Fitness` values calculated by another `FitnessFunction`, like the
+/// `CachingFitnessEvaluation`, but is designed to be shared by several
+/// simulations that run at the same time, e.g. the islands of a
+/// `simulation::islands::IslandSimulator` or the runs of a
+/// `simulation::ensemble::MultiStartRunner` started by `run_parallel`.
+///
+/// Each genome is evaluated exactly once, even if several threads request
+/// the `Fitness` value of the same genome at the same time. One of them
+/// calculates the value while the others wait for it. Threads that evaluate
+/// different genomes do not wait for each other, as the cache is split into
+/// shards that are locked independently and no lock is held while the
+/// wrapped fitness function calculates a value.
+///
+/// Clones of a `SharedCachingFitnessEvaluation` share the same cache. Thus the
+/// simulations share the cache by using clones of the same instance. The
+/// genomes must implement `Hash` and `Eq`. The size of the cache is
+/// unlimited.
+///
+/// It requires the crate feature `parallel`, which is enabled by default.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+#[derive(Clone, Debug)]
+pub struct SharedCachingFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    /// The wrapped `FitnessFunction`.
+    evaluator: E,
+    /// The shards of the cache shared by all clones.
+    shards: Arc<Vec<Mutex<CacheShard<G, F>>>>,
+    /// The hasher that distributes the genomes over the shards.
+    hasher: RandomState,
+}
+
+/// A shard of the cache of the `SharedCachingFitnessEvaluation`. The
+/// `Fitness` value of an entry is set once it has been calculated.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+type CacheShard<G, F> = HashMap<G, Arc<OnceLock<F>>>;
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+impl<G, F, E> SharedCachingFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    /// Creates a new `SharedCachingFitnessEvaluation` wrapping the given
+    /// `FitnessFunction` with a cache of 16 shards.
+    pub fn new(evaluator: E) -> Self {
+        Self::with_shards(evaluator, 16)
+    }
+
+    /// Creates a new `SharedCachingFitnessEvaluation` wrapping the given
+    /// `FitnessFunction` with a cache of the given number of shards. More
+    /// shards reduce the contention of many threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_shards` is 0.
+    pub fn with_shards(evaluator: E, num_shards: usize) -> Self {
+        assert!(
+            num_shards > 0,
+            "the number of shards must be greater than 0"
+        );
+        SharedCachingFitnessEvaluation {
+            evaluator,
+            shards: Arc::new(
+                (0..num_shards)
+                    .map(|_| Mutex::new(HashMap::new()))
+                    .collect(),
+            ),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Returns the wrapped `FitnessFunction`.
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// Returns the number of shards of the cache.
+    pub fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the number of genomes whose fitness is currently cached or
+    /// being calculated.
+    pub fn num_cached(&self) -> usize {
+        (0..self.shards.len())
+            .map(|index| self.lock_shard(index).len())
+            .sum()
+    }
+
+    /// Removes all entries from the cache.
+    pub fn clear(&self) {
+        for index in 0..self.shards.len() {
+            self.lock_shard(index).clear();
+        }
+    }
+
+    fn lock_shard(&self, index: usize) -> std::sync::MutexGuard<'_, CacheShard<G, F>> {
+        // the cache holds valid entries only even if a thread panicked
+        self.shards[index]
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+impl<G, F, E> FitnessFunction<G, F> for SharedCachingFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    fn fitness_of(&self, genome: &G) -> F {
+        let index = (self.hasher.hash_one(genome) % self.shards.len() as u64) as usize;
+        let entry = self
+            .lock_shard(index)
+            .entry(genome.clone())
+            .or_default()
+            .clone();
+        // the shard is unlocked while the value is calculated
+        entry
+            .get_or_init(|| self.evaluator.fitness_of(genome))
+            .clone()
+    }
+
+    fn average(&self, fitness_values: &[F]) -> F {
+        self.evaluator.average(fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> F {
+        self.evaluator.highest_possible_fitness()
+    }
+
+    fn lowest_possible_fitness(&self) -> F {
+        self.evaluator.lowest_possible_fitness()
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        self.evaluator.use_parallel_ga()
+    }
+
+    fn start_generation(&self, generation: u64) {
+        self.evaluator.start_generation(generation)
+    }
+
+    fn adjust_population_fitness(&self, population: &[G], fitness_values: &mut [F]) -> bool {
+        self.evaluator
+            .adjust_population_fitness(population, fitness_values)
+    }
+
+    fn tracks_lineage(&self) -> bool {
+        self.evaluator.tracks_lineage()
+    }
+
+    fn record_lineage(&self, parents: &[G], offspring: &[G]) {
+        self.evaluator.record_lineage(parents, offspring)
+    }
+}
+
+/// An `IncrementalFitness` calculates the `Fitness` value of a genome from
+/// the `Fitness` value of a similar genome, e.g. of the parent the genome has
+/// been bred from.
+///
+/// For many encodings a child differs from its parent in a few genes only.
+/// If the `Fitness` value is a sum over the genes, like the number of
+/// matching characters, only the differing genes need to be evaluated.
+///
+/// The `IncrementalFitnessEvaluation` uses this trait to calculate the
+/// `Fitness` values of the offspring bred by the `ga::GeneticAlgorithm`.
+pub trait IncrementalFitness<G, F>: FitnessFunction<G, F>
+where
+    G: Genotype,
+    F: Fitness,
+{
+    /// Calculates the `Fitness` value of the `child` from the `Fitness` value
+    /// `parent_fitness` of the `parent`.
+    ///
+    /// The returned value must be the same value as `fitness_of(child)`
+    /// returns. Otherwise the results of a simulation depend on whether the
+    /// `Fitness` values are calculated incrementally or not.
+    fn fitness_delta(&self, parent_fitness: F, parent: &G, child: &G) -> F;
+}
+
+/// The `IncrementalFitnessEvaluation` is a `FitnessFunction` that calculates
+/// the `Fitness` values of the offspring incrementally from the `Fitness`
+/// values of their parents by `IncrementalFitness::fitness_delta`.
+///
+/// The `ga::GeneticAlgorithm` records the parent of each bred genome by
+/// `FitnessFunction::record_lineage`. When such a genome is evaluated later
+/// on, its `Fitness` value is calculated from the `Fitness` value of its
+/// parent. The `Fitness` values of genomes without a recorded parent, like
+/// those of the initial population, are calculated by `fitness_of` of the
+/// wrapped `IncrementalFitness`. The individuals that are taken over from the
+/// previous generation are not evaluated again.
+///
+/// The results of a simulation are the same as with the wrapped
+/// `IncrementalFitness`, as long as it calculates the same `Fitness` values
+/// for the same genomes every time and by both of its functions.
+///
+/// Like the `CachingFitnessEvaluation` the genomes must implement `Hash` and
+/// `Eq`. Only the lineage and the `Fitness` values of the last two
+/// generations are remembered. Clones of an `IncrementalFitnessEvaluation`
+/// share the remembered values.
+#[derive(Clone, Debug)]
+pub struct IncrementalFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: IncrementalFitness<G, F>,
+{
+    /// The wrapped `IncrementalFitness`.
+    evaluator: E,
+    /// The lineage and the `Fitness` values shared by all clones.
+    lineage: Arc<Mutex<Lineage<G, F>>>,
+}
+
+impl<G, F, E> IncrementalFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: IncrementalFitness<G, F>,
+{
+    /// Creates a new `IncrementalFitnessEvaluation` wrapping the given
+    /// `IncrementalFitness`.
+    pub fn new(evaluator: E) -> Self {
+        IncrementalFitnessEvaluation {
+            evaluator,
+            lineage: Arc::new(Mutex::new(Lineage::default())),
+        }
+    }
+
+    /// Returns the wrapped `IncrementalFitness`.
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// Returns the number of `Fitness` values that have been calculated
+    /// incrementally by `IncrementalFitness::fitness_delta`.
+    pub fn num_incremental_evaluations(&self) -> u64 {
+        self.lock_lineage().num_incremental
+    }
+
+    /// Returns the number of `Fitness` values that have been calculated
+    /// from scratch by `FitnessFunction::fitness_of`.
+    pub fn num_full_evaluations(&self) -> u64 {
+        self.lock_lineage().num_full
+    }
+
+    fn lock_lineage(&self) -> std::sync::MutexGuard<'_, Lineage<G, F>> {
+        // the lineage holds valid entries only even if a thread panicked
+        self.lineage
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl<G, F, E> FitnessFunction<G, F> for IncrementalFitnessEvaluation<G, F, E>
+where
+    G: Genotype + Hash + Eq,
+    F: Fitness,
+    E: IncrementalFitness<G, F>,
+{
+    fn fitness_of(&self, genome: &G) -> F {
+        let parent = {
+            let mut lineage = self.lock_lineage();
+            if let Some(fitness) = lineage.current.get(genome) {
+                return fitness.clone();
+            }
+            if let Some(fitness) = lineage.previous.get(genome).cloned() {
+                // remember the individuals taken over from the previous
+                // generation as parents of the next offspring
+                lineage.current.insert(genome.clone(), fitness.clone());
+                return fitness;
+            }
+            lineage.parents.get(genome).cloned()
+        };
+        let fitness = match parent {
+            Some((ref parent, ref parent_fitness)) => {
+                self.evaluator
+                    .fitness_delta(parent_fitness.clone(), parent, genome)
+            },
+            None => self.evaluator.fitness_of(genome),
+        };
+        let mut lineage = self.lock_lineage();
+        if parent.is_some() {
+            lineage.num_incremental += 1;
+        } else {
+            lineage.num_full += 1;
+        }
+        lineage.current.insert(genome.clone(), fitness.clone());
+        fitness
+    }
+
+    fn average(&self, fitness_values: &[F]) -> F {
+        self.evaluator.average(fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> F {
+        self.evaluator.highest_possible_fitness()
+    }
+
+    fn lowest_possible_fitness(&self) -> F {
+        self.evaluator.lowest_possible_fitness()
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        self.evaluator.use_parallel_ga()
+    }
+
+    fn start_generation(&self, generation: u64) {
+        self.evaluator.start_generation(generation)
+    }
+
+    fn adjust_population_fitness(&self, population: &[G], fitness_values: &mut [F]) -> bool {
+        self.evaluator
+            .adjust_population_fitness(population, fitness_values)
+    }
+
+    fn tracks_lineage(&self) -> bool {
+        true
+    }
+
+    fn record_lineage(&self, parents: &[G], offspring: &[G]) {
+        let mut lineage = self.lock_lineage();
+        // the parents have been evaluated in the current generation
+        lineage.previous = std::mem::take(&mut lineage.current);
+        lineage.parents.clear();
+        for (parent, child) in parents.iter().zip(offspring) {
+            if let Some(parent_fitness) = lineage.previous.get(parent).cloned() {
+                lineage
+                    .parents
+                    .insert(child.clone(), (parent.clone(), parent_fitness));
+            }
+        }
+    }
+}
+
+/// The lineage and the `Fitness` values remembered by the
+/// `IncrementalFitnessEvaluation`.
+#[derive(Debug)]
+struct Lineage<G, F> {
+    /// The `Fitness` values calculated since the offspring has been bred.
+    current: HashMap<G, F>,
+    /// The `Fitness` values calculated before the offspring has been bred.
+    previous: HashMap<G, F>,
+    /// The parent and its `Fitness` value of each genome of the offspring.
+    parents: HashMap<G, (G, F)>,
+    num_incremental: u64,
+    num_full: u64,
+}
+
+impl<G, F> Default for Lineage<G, F> {
+    fn default() -> Self {
+        Lineage {
+            current: HashMap::new(),
+            previous: HashMap::new(),
+            parents: HashMap::new(),
+            num_incremental: 0,
+            num_full: 0,
+        }
+    }
+}
+
+/// Defines the evaluation function to calculate the `Fitness` value of a
+/// phenotype, i.e. of a genome decoded by a `GenotypeToPhenotype` decoder.
+///
+/// A `PhenotypeFitnessFunction` is turned into a `FitnessFunction` of the
+/// genomes by the `PhenotypeFitness`.
+pub trait PhenotypeFitnessFunction<P, F>: Clone
+where
+    F: Fitness,
+{
+    /// Calculates the `Fitness` value of the given phenotype.
+    fn fitness_of(&self, phenotype: &P) -> F;
+
+    /// Calculates the average `Fitness` value of the given `Fitness` values.
+    fn average(&self, a: &[F]) -> F;
+
+    /// Returns the very best of all theoretically possible `Fitness` values.
+    fn highest_possible_fitness(&self) -> F;
+
+    /// Returns the worst of all theoretically possible `Fitness` values.
+    /// This is usually a value equivalent to zero.
+    fn lowest_possible_fitness(&self) -> F;
+
+    /// Returns whether the `Fitness` values of a population may be calculated
+    /// in parallel. See `FitnessFunction::use_parallel_ga`.
+    ///
+    /// The default implementation returns `true`.
+    fn use_parallel_ga(&self) -> bool {
+        true
+    }
+}
+
+/// The `PhenotypeFitness` is a `FitnessFunction` that decodes each genome
+/// into its phenotype by a `GenotypeToPhenotype` decoder and calculates the
+/// `Fitness` value of the phenotype by a `PhenotypeFitnessFunction`.
+///
+/// The decoded phenotypes of the current population are cached. The
+/// `PhenotypeFitness` is a `GenotypeToPhenotype` decoder as well, which
+/// returns the cached phenotype of a genome instead of decoding it again.
+/// When it is registered as both the evaluation and the decoder of the
+/// `ga::GeneticAlgorithm`, each genome is decoded only once per generation,
+/// although the phenotype of the best solution is reported too.
+///
+/// The `ga::GeneticAlgorithm` calls `adjust_population_fitness` once per
+/// generation after the population has been evaluated. Then the phenotypes of
+/// genomes that are not part of the population anymore are removed from the
+/// cache.
+///
+/// Like the `CachingFitnessEvaluation` the genomes must implement `Hash` and
+/// `Eq`. Clones of a `PhenotypeFitness` share the same cache. As the lock on
+/// the cache is not held while a genome is decoded, equal genomes evaluated
+/// by several threads at the same time may be decoded more than once.
+#[derive(Clone, Debug)]
+pub struct PhenotypeFitness<G, P, D, F, E>
+where
+    G: Genotype + Hash + Eq,
+    P: Clone + Debug,
+    D: GenotypeToPhenotype<G, P>,
+    F: Fitness,
+    E: PhenotypeFitnessFunction<P, F>,
+{
+    /// The decoder of the genomes.
+    decoder: D,
+    /// The `PhenotypeFitnessFunction` for the decoded phenotypes.
+    evaluator: E,
+    /// The phenotypes of the current population shared by all clones.
+    phenotypes: Arc<Mutex<HashMap<G, P>>>,
+    // phantom types
+    _f: PhantomData<F>,
+}
+
+impl<G, P, D, F, E> PhenotypeFitness<G, P, D, F, E>
+where
+    G: Genotype + Hash + Eq,
+    P: Clone + Debug,
+    D: GenotypeToPhenotype<G, P>,
+    F: Fitness,
+    E: PhenotypeFitnessFunction<P, F>,
+{
+    /// Creates a new `PhenotypeFitness` which decodes the genomes by the
+    /// given decoder and evaluates the phenotypes by the given
+    /// `PhenotypeFitnessFunction`.
+    pub fn new(decoder: D, evaluator: E) -> Self {
+        PhenotypeFitness {
+            decoder,
+            evaluator,
+            phenotypes: Arc::new(Mutex::new(HashMap::new())),
+            _f: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped decoder.
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns the wrapped `PhenotypeFitnessFunction`.
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// Returns the cached phenotype of the given genome, if it has been
+    /// decoded for the current population.
+    pub fn phenotype_of(&self, genome: &G) -> Option<P> {
+        self.lock_phenotypes().get(genome).cloned()
+    }
+
+    /// Returns the number of genomes whose phenotype is currently cached.
+    pub fn num_cached(&self) -> usize {
+        self.lock_phenotypes().len()
+    }
+
+    fn cached_decode(&self, genome: &G) -> P {
+        if let Some(phenotype) = self.phenotype_of(genome) {
+            return phenotype;
+        }
+        let phenotype = self.decoder.decode(genome);
+        self.lock_phenotypes()
+            .insert(genome.clone(), phenotype.clone());
+        phenotype
+    }
+
+    fn lock_phenotypes(&self) -> std::sync::MutexGuard<'_, HashMap<G, P>> {
+        // the cache holds valid entries only even if a thread panicked
+        self.phenotypes
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl<G, P, D, F, E> GenotypeToPhenotype<G, P> for PhenotypeFitness<G, P, D, F, E>
+where
+    G: Genotype + Hash + Eq,
+    P: Clone + Debug,
+    D: GenotypeToPhenotype<G, P>,
+    F: Fitness,
+    E: PhenotypeFitnessFunction<P, F> + Debug,
+{
+    fn decode(&self, genome: &G) -> P {
+        self.cached_decode(genome)
+    }
+}
+
+impl<G, P, D, F, E> FitnessFunction<G, F> for PhenotypeFitness<G, P, D, F, E>
+where
+    G: Genotype + Hash + Eq,
+    P: Clone + Debug,
+    D: GenotypeToPhenotype<G, P>,
+    F: Fitness,
+    E: PhenotypeFitnessFunction<P, F>,
+{
+    fn fitness_of(&self, genome: &G) -> F {
+        self.evaluator.fitness_of(&self.cached_decode(genome))
+    }
+
+    fn average(&self, fitness_values: &[F]) -> F {
+        self.evaluator.average(fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> F {
+        self.evaluator.highest_possible_fitness()
+    }
+
+    fn lowest_possible_fitness(&self) -> F {
+        self.evaluator.lowest_possible_fitness()
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        self.evaluator.use_parallel_ga()
+    }
+
+    fn adjust_population_fitness(&self, population: &[G], _fitness_values: &mut [F]) -> bool {
+        let current: HashSet<&G> = population.iter().collect();
+        self.lock_phenotypes()
+            .retain(|genome, _| current.contains(genome));
+        false
+    }
+}
+
+/// A `Constraint` of the `ConstrainedFitness` returns the magnitude by which
+/// a genome violates the constraint. A value of 0 or less means that the
+/// constraint is satisfied.
+pub type Constraint<G> = Arc<dyn Fn(&G) -> f64 + Send + Sync>;
+
+/// The `PenaltyMethod` defines how the `ConstrainedFitness` penalizes genomes
+/// that violate at least one constraint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PenaltyMethod {
+    /// The death penalty assigns the lowest possible `Fitness` value to any
+    /// genome that violates a constraint.
+    Death,
+    /// A graded penalty of `coefficient * violation`, where `violation` is
+    /// the sum of the violations of all constraints.
+    Static {
+        /// The factor by which the violation is multiplied.
+        coefficient: f64,
+    },
+    /// A graded penalty of `(coefficient * generation)^exponent * violation`,
+    /// where `violation` is the sum of the violations of all constraints.
+    /// The penalty grows with the generation, so that infeasible genomes are
+    /// tolerated at the beginning of the simulation to explore the search
+    /// space but are increasingly penalized later on.
+    GenerationScaled {
+        /// The factor by which the generation is multiplied.
+        coefficient: f64,
+        /// The exponent of the scaled generation.
+        exponent: f64,
+    },
+}
+
+/// The `ConstrainedFitness` is a `FitnessFunction` that applies a penalty to
+/// the `Fitness` value calculated by another `FitnessFunction` if a genome
+/// violates any of the given constraints.
+///
+/// Each `Constraint` returns the magnitude of its violation by a genome, e.g.
+/// `x - 5` for the constraint `x <= 5`. The violations of all constraints are
+/// summed up, where values of 0 or less count as satisfied. Genomes that
+/// satisfy all constraints keep the `Fitness` value of the wrapped
+/// `FitnessFunction`. Infeasible genomes are penalized according to the
+/// `PenaltyMethod`. A penalized `Fitness` value is never lower than the
+/// lowest possible `Fitness` value of the wrapped `FitnessFunction`.
+///
+/// The current generation used by `PenaltyMethod::GenerationScaled` starts
+/// at 1 and is updated by the `ga::GeneticAlgorithm` through
+/// `FitnessFunction::start_generation` before each generation is evaluated.
+/// It can also be set by `set_generation`, e.g. when the fitness function is
+/// used outside of the genetic algorithm. Clones of a `ConstrainedFitness`
+/// share the current generation. As the penalty changes from generation to
+/// generation, a `ConstrainedFitness` with `PenaltyMethod::GenerationScaled`
+/// should not be wrapped in a `CachingFitnessEvaluation`, which would keep
+/// the penalized values of earlier generations.
+#[derive(Clone)]
+pub struct ConstrainedFitness<G, F, E>
+where
+    G: Genotype,
+    F: Penalizable,
+    E: FitnessFunction<G, F>,
+{
+    /// The wrapped `FitnessFunction`.
+    evaluator: E,
+    /// The constraints a genome must satisfy.
+    constraints: Vec<Constraint<G>>,
+    /// The method used to penalize infeasible genomes.
+    penalty_method: PenaltyMethod,
+    /// The current generation shared by all clones.
+    generation: Arc<AtomicU64>,
+    // phantom types
+    _f: PhantomData<F>,
+}
+
+impl<G, F, E> ConstrainedFitness<G, F, E>
+where
+    G: Genotype,
+    F: Penalizable,
+    E: FitnessFunction<G, F>,
+{
+    /// Creates a new `ConstrainedFitness` wrapping the given
+    /// `FitnessFunction` without any constraints.
+    pub fn new(evaluator: E, penalty_method: PenaltyMethod) -> Self {
+        ConstrainedFitness {
+            evaluator,
+            constraints: Vec::new(),
+            penalty_method,
+            generation: Arc::new(AtomicU64::new(1)),
+            _f: PhantomData,
+        }
+    }
+
+    /// Adds the given constraint and returns this `ConstrainedFitness`.
+    pub fn with_constraint<C>(mut self, constraint: C) -> Self
+    where
+        C: Fn(&G) -> f64 + Send + Sync + 'static,
+    {
+        self.constraints.push(Arc::new(constraint));
+        self
+    }
+
+    /// Returns the wrapped `FitnessFunction`.
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// Returns the number of constraints.
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Returns the method used to penalize infeasible genomes.
+    pub fn penalty_method(&self) -> PenaltyMethod {
+        self.penalty_method
+    }
+
+    /// Sets the method used to penalize infeasible genomes.
+    pub fn set_penalty_method(&mut self, value: PenaltyMethod) {
+        self.penalty_method = value;
+    }
+
+    /// Returns the current generation.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Sets the current generation for this `ConstrainedFitness` and all of
+    /// its clones.
+    pub fn set_generation(&self, value: u64) {
+        self.generation.store(value, Ordering::Relaxed);
+    }
+
+    /// Returns the sum of the violations of all constraints by the given
+    /// genome. A value of 0 means that the genome satisfies all constraints.
+    pub fn violation_of(&self, genome: &G) -> f64 {
+        self.constraints
+            .iter()
+            .map(|constraint| constraint(genome).max(0.))
+            .sum()
+    }
+}
+
+impl<G, F, E> Debug for ConstrainedFitness<G, F, E>
+where
+    G: Genotype,
+    F: Penalizable,
+    E: FitnessFunction<G, F> + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConstrainedFitness")
+            .field("evaluator", &self.evaluator)
+            .field("num_constraints", &self.constraints.len())
+            .field("penalty_method", &self.penalty_method)
+            .field("generation", &self.generation())
+            .finish()
+    }
+}
+
+impl<G, F, E> FitnessFunction<G, F> for ConstrainedFitness<G, F, E>
+where
+    G: Genotype,
+    F: Penalizable,
+    E: FitnessFunction<G, F>,
+{
+    fn fitness_of(&self, genome: &G) -> F {
+        let fitness = self.evaluator.fitness_of(genome);
+        let violation = self.violation_of(genome);
+        if violation <= 0. {
+            return fitness;
+        }
+        let lowest = self.evaluator.lowest_possible_fitness();
+        let penalty = match self.penalty_method {
+            PenaltyMethod::Death => return lowest,
+            PenaltyMethod::Static { coefficient } => coefficient * violation,
+            PenaltyMethod::GenerationScaled {
+                coefficient,
+                exponent,
+            } => (coefficient * self.generation() as f64).powf(exponent) * violation,
+        };
+        fitness.penalized(penalty.max(0.)).max(lowest)
+    }
+
+    fn average(&self, fitness_values: &[F]) -> F {
+        self.evaluator.average(fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> F {
+        self.evaluator.highest_possible_fitness()
+    }
+
+    fn lowest_possible_fitness(&self) -> F {
+        self.evaluator.lowest_possible_fitness()
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        self.evaluator.use_parallel_ga()
+    }
+
+    fn start_generation(&self, generation: u64) {
+        self.set_generation(generation);
+        self.evaluator.start_generation(generation)
+    }
+
+    fn adjust_population_fitness(&self, population: &[G], fitness_values: &mut [F]) -> bool {
+        self.evaluator
+            .adjust_population_fitness(population, fitness_values)
+    }
+
+    fn tracks_lineage(&self) -> bool {
+        self.evaluator.tracks_lineage()
+    }
+
+    fn record_lineage(&self, parents: &[G], offspring: &[G]) {
+        self.evaluator.record_lineage(parents, offspring)
+    }
+}
+
+/// The `ScalarFitness` is a `Fitness` value represented by a single float,
+/// e.g. the weighted sum calculated by the `WeightedSumFitness`. It is the
+/// fitness type to use for objective functions that are naturally
+/// floating-point, as the bare `f64` is not `Ord`.
+///
+/// The values are totally ordered, thus they can be used with any
+/// `operator::GeneticOperator` designed for single-objective optimization.
+/// NaN is treated as the worst possible fitness: it is less than any other
+/// value including negative infinity and all NaN values are equal. Thus an
+/// individual with a NaN fitness is never selected as the best solution and
+/// sorting a population by its fitness never panics. Apart from NaN the
+/// values are ordered as floats, so that `-0.0` equals `0.0`.
+#[derive(Clone, Copy, Debug)]
+pub struct ScalarFitness(pub f64);
+
+impl ScalarFitness {
+    /// Returns the float value of this fitness.
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl From<f64> for ScalarFitness {
+    fn from(value: f64) -> Self {
+        ScalarFitness(value)
+    }
+}
+
+impl PartialEq for ScalarFitness {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for ScalarFitness {}
+
+impl PartialOrd for ScalarFitness {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScalarFitness {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use std::cmp::Ordering::*;
+        match (self.0.is_nan(), other.0.is_nan()) {
+            (true, true) => Equal,
+            (true, false) => Less,
+            (false, true) => Greater,
+            (false, false) => self.0.partial_cmp(&other.0).unwrap_or(Equal),
+        }
+    }
+}
+
+impl Fitness for ScalarFitness {
+    fn zero() -> Self {
+        ScalarFitness(0.)
+    }
+
+    fn abs_diff(&self, other: &Self) -> Self {
+        ScalarFitness((self.0 - other.0).abs())
+    }
+}
+
+impl AsScalar for ScalarFitness {
+    fn as_scalar(&self) -> f64 {
+        self.0
+    }
+}
+
+impl Penalizable for ScalarFitness {
+    fn penalized(&self, penalty: f64) -> Self {
+        ScalarFitness(self.0 - penalty.max(0.))
+    }
+}
+
+/// An `Objective` of the `WeightedSumFitness` returns the value of one
+/// objective for a genome. Higher values are better.
+pub type Objective<G> = Arc<dyn Fn(&G) -> f64 + Send + Sync>;
+
+/// The `WeightedSumFitness` is a `FitnessFunction` that scalarizes several
+/// objectives into a single `ScalarFitness` value by calculating the
+/// weighted sum `F = Sum(w_i * f_i)` of the objective values `f_i`.
+///
+/// All objectives are maximized. An objective that should be minimized is
+/// given a negative weight.
+///
+/// Objectives usually have different scales, e.g. a cost in thousands and a
+/// weight below 1. If bounds are given by `with_bounds`, each objective value
+/// is normalized to `(f_i - min_i) / (max_i - min_i)` before it is weighted,
+/// so that the weights are comparable. Values outside of the bounds are not
+/// clamped. The weights are used as they are given unless
+/// `with_normalized_weights` scales them to sum up to 1.
+///
+/// The weighted sum can only find solutions on the convex parts of the
+/// Pareto front. Use the multi-objective operators, like the
+/// `selection::nsga2::Nsga2Selector`, to find solutions on
+/// concave parts of the Pareto front.
+#[derive(Clone)]
+pub struct WeightedSumFitness<G>
+where
+    G: Genotype,
+{
+    /// The objectives to be scalarized.
+    objectives: Vec<Objective<G>>,
+    /// The weight of each objective.
+    weights: Vec<f64>,
+    /// The lower and upper bound of each objective used for normalization.
+    bounds: Option<Vec<(f64, f64)>>,
+}
+
+impl<G> WeightedSumFitness<G>
+where
+    G: Genotype,
+{
+    /// Creates a new `WeightedSumFitness` for the given objectives, where
+    /// the objective at index `i` is weighted by the weight at index `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no objectives, if the number of weights does not
+    /// match the number of objectives or if any weight is not finite.
+    pub fn new(objectives: Vec<Objective<G>>, weights: Vec<f64>) -> Self {
+        assert!(!objectives.is_empty(), "at least one objective is required");
+        assert!(
+            objectives.len() == weights.len(),
+            "the number of weights {} does not match the number of objectives {}",
+            weights.len(),
+            objectives.len()
+        );
+        assert!(
+            weights.iter().all(|weight| weight.is_finite()),
+            "the weights must be finite"
+        );
+        WeightedSumFitness {
+            objectives,
+            weights,
+            bounds: None,
+        }
+    }
+
+    /// Normalizes each objective value by the given `(min, max)` bounds of
+    /// the objective before it is weighted and returns this
+    /// `WeightedSumFitness`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of bounds does not match the number of objectives
+    /// or if any `min` is not less than the corresponding `max`.
+    pub fn with_bounds(mut self, bounds: Vec<(f64, f64)>) -> Self {
+        assert!(
+            bounds.len() == self.objectives.len(),
+            "the number of bounds {} does not match the number of objectives {}",
+            bounds.len(),
+            self.objectives.len()
+        );
+        assert!(
+            bounds.iter().all(|(min, max)| min < max),
+            "the lower bound of each objective must be less than its upper bound"
+        );
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Scales the weights so that their sum is 1 and returns this
+    /// `WeightedSumFitness`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum of the weights is 0.
+    pub fn with_normalized_weights(mut self) -> Self {
+        let sum: f64 = self.weights.iter().sum();
+        assert!(sum != 0., "the weights must not sum up to 0");
+        for weight in &mut self.weights {
+            *weight /= sum;
+        }
+        self
+    }
+
+    /// Returns the number of objectives.
+    pub fn num_objectives(&self) -> usize {
+        self.objectives.len()
+    }
+
+    /// Returns the weights of the objectives.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the bounds of the objectives used for normalization, if any.
+    pub fn bounds(&self) -> Option<&[(f64, f64)]> {
+        self.bounds.as_deref()
+    }
+
+    /// Returns the objective values of the given genome, normalized by the
+    /// bounds if any.
+    pub fn objectives_of(&self, genome: &G) -> Vec<f64> {
+        let values = self.objectives.iter().map(|objective| objective(genome));
+        match &self.bounds {
+            Some(bounds) => values
+                .zip(bounds)
+                .map(|(value, (min, max))| (value - min) / (max - min))
+                .collect(),
+            None => values.collect(),
+        }
+    }
+}
+
+impl<G> Debug for WeightedSumFitness<G>
+where
+    G: Genotype,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WeightedSumFitness")
+            .field("num_objectives", &self.objectives.len())
+            .field("weights", &self.weights)
+            .field("bounds", &self.bounds)
+            .finish()
+    }
+}
+
+impl<G> FitnessFunction<G, ScalarFitness> for WeightedSumFitness<G>
+where
+    G: Genotype,
+{
+    fn fitness_of(&self, genome: &G) -> ScalarFitness {
+        ScalarFitness(
+            self.objectives_of(genome)
+                .iter()
+                .zip(&self.weights)
+                .map(|(value, weight)| value * weight)
+                .sum(),
+        )
+    }
+
+    fn average(&self, fitness_values: &[ScalarFitness]) -> ScalarFitness {
+        ScalarFitness(
+            fitness_values.iter().map(|fitness| fitness.0).sum::<f64>()
+                / fitness_values.len() as f64,
+        )
+    }
+
+    /// Returns the sum of the positive weights if the objectives are
+    /// normalized by bounds, otherwise positive infinity.
+    fn highest_possible_fitness(&self) -> ScalarFitness {
+        match self.bounds {
+            Some(_) => ScalarFitness(self.weights.iter().map(|weight| weight.max(0.)).sum()),
+            None => ScalarFitness(f64::INFINITY),
+        }
+    }
+
+    /// Returns the sum of the negative weights if the objectives are
+    /// normalized by bounds, otherwise negative infinity.
+    fn lowest_possible_fitness(&self) -> ScalarFitness {
+        match self.bounds {
+            Some(_) => ScalarFitness(self.weights.iter().map(|weight| weight.min(0.)).sum()),
+            None => ScalarFitness(f64::NEG_INFINITY),
+        }
+    }
+}
+
+/// A `FitnessTransform` transforms the `Fitness` values calculated by a
+/// `FitnessFunction`, e.g. by scaling or penalizing them. Transforms are
+/// chained by a `TransformPipeline`.
+///
+/// Transforms that only need the `Fitness` value of a single genome
+/// implement `transform`. Transforms that need the context of the whole
+/// population, like fitness sharing, implement `transform_population`. The
+/// default implementations of both functions leave the values unchanged.
+pub trait FitnessTransform<G, F>: Clone
+where
+    G: Genotype,
+    F: Fitness,
+{
+    /// Transforms the `Fitness` value of the given genome.
+    fn transform(&self, _genome: &G, fitness: F) -> F {
+        fitness
+    }
+
+    /// Transforms the `Fitness` values of a whole population, where the value
+    /// at index `i` belongs to the genome at index `i`. Returns whether any
+    /// value has been changed.
+    fn transform_population(&self, _population: &[G], _fitness_values: &mut [F]) -> bool {
+        false
+    }
+
+    /// Transforms the highest or lowest possible `Fitness` value of the
+    /// wrapped `FitnessFunction`.
+    fn transform_limit(&self, limit: F) -> F {
+        limit
+    }
+}
+
+/// The identity transform, which leaves all `Fitness` values unchanged. It
+/// is the start of each `TransformPipeline`.
+impl<G, F> FitnessTransform<G, F> for ()
+where
+    G: Genotype,
+    F: Fitness,
+{
+}
+
+/// Starts a `TransformPipeline` that evaluates the genomes by the given
+/// `FitnessFunction`. Transforms are appended by `TransformPipeline::then`.
+pub fn pipeline<G, F, E>(evaluator: E) -> TransformPipeline<G, F, E, ()>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    TransformPipeline {
+        evaluator,
+        transform: (),
+        _g: PhantomData,
+        _f: PhantomData,
+    }
+}
+
+/// The `TransformPipeline` is a `FitnessFunction` that applies a
+/// `FitnessTransform` to the `Fitness` values calculated by another
+/// `FitnessFunction`.
+///
+/// A pipeline is started by `pipeline` and each call of `then` wraps the
+/// pipeline built so far, so the transforms are applied in the order in which
+/// they are appended: `pipeline(raw).then(scaling).then(penalty)` first
+/// scales and then penalizes the raw `Fitness` values. The transforms of the
+/// whole population are applied in the same order after the values of all
+/// genomes have been calculated and transformed one by one.
+///
+/// As the `TransformPipeline` is a `FitnessFunction` it is used like any
+/// other evaluator, e.g. by `ga::GeneticAlgorithmBuilder::with_evaluation`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransformPipeline<G, F, E, T>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    T: FitnessTransform<G, F>,
+{
+    /// The wrapped `FitnessFunction`.
+    evaluator: E,
+    /// The transform applied to the values of the wrapped `FitnessFunction`.
+    transform: T,
+    // phantom types
+    _g: PhantomData<G>,
+    _f: PhantomData<F>,
+}
+
+impl<G, F, E, T> TransformPipeline<G, F, E, T>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    T: FitnessTransform<G, F>,
+{
+    /// Appends the given transform to this pipeline.
+    pub fn then<N>(self, transform: N) -> TransformPipeline<G, F, Self, N>
+    where
+        N: FitnessTransform<G, F>,
+    {
+        TransformPipeline {
+            evaluator: self,
+            transform,
+            _g: PhantomData,
+            _f: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped `FitnessFunction`.
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// Returns the last transform of this pipeline.
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+}
+
+impl<G, F, E, T> FitnessFunction<G, F> for TransformPipeline<G, F, E, T>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    T: FitnessTransform<G, F>,
+{
+    fn fitness_of(&self, genome: &G) -> F {
+        self.transform
+            .transform(genome, self.evaluator.fitness_of(genome))
+    }
+
+    fn average(&self, fitness_values: &[F]) -> F {
+        self.evaluator.average(fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> F {
+        self.transform
+            .transform_limit(self.evaluator.highest_possible_fitness())
+    }
+
+    fn lowest_possible_fitness(&self) -> F {
+        self.transform
+            .transform_limit(self.evaluator.lowest_possible_fitness())
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        self.evaluator.use_parallel_ga()
+    }
+
+    fn start_generation(&self, generation: u64) {
+        self.evaluator.start_generation(generation)
+    }
+
+    fn adjust_population_fitness(&self, population: &[G], fitness_values: &mut [F]) -> bool {
+        let adjusted = self
+            .evaluator
+            .adjust_population_fitness(population, fitness_values);
+        self.transform
+            .transform_population(population, fitness_values)
+            || adjusted
+    }
+
+    fn tracks_lineage(&self) -> bool {
+        self.evaluator.tracks_lineage()
+    }
+
+    fn record_lineage(&self, parents: &[G], offspring: &[G]) {
+        self.evaluator.record_lineage(parents, offspring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use galvanic_assert::matchers::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone, Debug, Default)]
+    struct CountingEvaluator {
+        num_evaluations: Arc<AtomicUsize>,
+    }
+
+    impl CountingEvaluator {
+        fn num_evaluations(&self) -> usize {
+            self.num_evaluations.load(Ordering::SeqCst)
+        }
+    }
+
+    impl FitnessFunction<Vec<u8>, u32> for CountingEvaluator {
+        fn fitness_of(&self, genome: &Vec<u8>) -> u32 {
+            self.num_evaluations.fetch_add(1, Ordering::SeqCst);
+            genome.iter().map(|value| *value as u32).sum()
+        }
+
+        fn average(&self, fitness_values: &[u32]) -> u32 {
+            fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+        }
+
+        fn highest_possible_fitness(&self) -> u32 {
+            u32::MAX
+        }
+
+        fn lowest_possible_fitness(&self) -> u32 {
+            0
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct CountingDecoder {
+        num_decodes: Arc<AtomicUsize>,
+    }
+
+    impl GenotypeToPhenotype<Vec<u8>, Vec<u32>> for CountingDecoder {
+        fn decode(&self, genome: &Vec<u8>) -> Vec<u32> {
+            self.num_decodes.fetch_add(1, Ordering::SeqCst);
+            genome.iter().map(|value| *value as u32).collect()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct SumOfPhenotype;
+
+    impl PhenotypeFitnessFunction<Vec<u32>, u32> for SumOfPhenotype {
+        fn fitness_of(&self, phenotype: &Vec<u32>) -> u32 {
+            phenotype.iter().sum()
+        }
+
+        fn average(&self, fitness_values: &[u32]) -> u32 {
+            fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+        }
+
+        fn highest_possible_fitness(&self) -> u32 {
+            u32::MAX
+        }
+
+        fn lowest_possible_fitness(&self) -> u32 {
+            0
+        }
+    }
+
+    #[test]
+    fn phenotype_fitness_decodes_each_genome_once_for_evaluation_and_decoding() {
+        let decoder = CountingDecoder::default();
+        let evaluator = PhenotypeFitness::new(decoder.clone(), SumOfPhenotype);
+        let population = vec![vec![1, 2], vec![3, 4], vec![1, 2]];
+
+        let mut fitness_values: Vec<u32> = population
+            .iter()
+            .map(|genome| evaluator.fitness_of(genome))
+            .collect();
+        evaluator.adjust_population_fitness(&population, &mut fitness_values);
+        let phenotype = evaluator.decode(&population[1]);
+
+        expect_that!(&fitness_values, eq(vec![3, 7, 3]));
+        expect_that!(&phenotype, eq(vec![3, 4]));
+        expect_that!(&decoder.num_decodes.load(Ordering::SeqCst), eq(2));
+    }
+
+    #[test]
+    fn phenotype_fitness_removes_phenotypes_of_genomes_not_in_the_population() {
+        let evaluator = PhenotypeFitness::new(CountingDecoder::default(), SumOfPhenotype);
+        evaluator.fitness_of(&vec![1, 2]);
+        evaluator.fitness_of(&vec![3, 4]);
+
+        evaluator.adjust_population_fitness(&[vec![3, 4]], &mut [7]);
+
+        expect_that!(&evaluator.num_cached(), eq(1));
+        expect_that!(&evaluator.phenotype_of(&vec![1, 2]), eq(None));
+        expect_that!(&evaluator.phenotype_of(&vec![3, 4]), eq(Some(vec![3, 4])));
+    }
+
+    #[test]
+    fn caching_fitness_evaluation_evaluates_repeated_genomes_once() {
+        let counter = CountingEvaluator::default();
+        let evaluator = CachingFitnessEvaluation::new(counter.clone());
+        let genomes = [vec![1, 2], vec![3, 4], vec![1, 2], vec![3, 4], vec![1, 2]];
+
+        let fitness_values: Vec<u32> = genomes.iter().map(|g| evaluator.fitness_of(g)).collect();
+
+        expect_that!(&fitness_values, eq(vec![3, 7, 3, 7, 3]));
+        expect_that!(&counter.num_evaluations(), eq(2));
+        expect_that!(&evaluator.num_cached(), eq(2));
+    }
+
+    #[test]
+    fn caching_fitness_evaluation_clones_share_the_cache() {
+        let counter = CountingEvaluator::default();
+        let evaluator = CachingFitnessEvaluation::new(counter.clone());
+        let cloned = evaluator.clone();
+
+        evaluator.fitness_of(&vec![5]);
+        cloned.fitness_of(&vec![5]);
+
+        expect_that!(&counter.num_evaluations(), eq(1));
+    }
+
+    #[test]
+    fn caching_fitness_evaluation_with_capacity_removes_least_recently_used() {
+        let counter = CountingEvaluator::default();
+        let evaluator = CachingFitnessEvaluation::with_capacity(counter.clone(), 2);
+
+        evaluator.fitness_of(&vec![1]);
+        evaluator.fitness_of(&vec![2]);
+        // use [1] again, so that [2] is the least recently used
+        evaluator.fitness_of(&vec![1]);
+        evaluator.fitness_of(&vec![3]);
+        expect_that!(&counter.num_evaluations(), eq(3));
+        expect_that!(&evaluator.num_cached(), eq(2));
+
+        // [1] and [3] are cached, [2] has been removed
+        evaluator.fitness_of(&vec![1]);
+        evaluator.fitness_of(&vec![3]);
+        expect_that!(&counter.num_evaluations(), eq(3));
+        evaluator.fitness_of(&vec![2]);
+        expect_that!(&counter.num_evaluations(), eq(4));
+        expect_that!(&evaluator.num_cached(), eq(2));
+    }
+
+    #[test]
+    fn caching_fitness_evaluation_clear_removes_all_entries() {
+        let counter = CountingEvaluator::default();
+        let evaluator = CachingFitnessEvaluation::with_capacity(counter.clone(), 4);
+        evaluator.fitness_of(&vec![1]);
+
+        evaluator.clear();
+        evaluator.fitness_of(&vec![1]);
+
+        expect_that!(&counter.num_evaluations(), eq(2));
+        expect_that!(&evaluator.capacity(), eq(Some(4)));
+    }
+
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    #[test]
+    fn shared_caching_fitness_evaluation_evaluates_each_genome_once_across_threads() {
+        let counter = CountingEvaluator::default();
+        let evaluator = SharedCachingFitnessEvaluation::with_shards(counter.clone(), 4);
+        let genomes: Vec<Vec<u8>> = (0..200).map(|value| vec![value as u8, 1]).collect();
+
+        let handles: Vec<_> = (0..2)
+            .map(|thread| {
+                let evaluator = evaluator.clone();
+                let mut genomes = genomes.clone();
+                if thread == 1 {
+                    genomes.reverse();
+                }
+                std::thread::spawn(move || {
+                    for _ in 0..5 {
+                        for genome in &genomes {
+                            assert_eq!(
+                                evaluator.fitness_of(genome),
+                                genome.iter().map(|value| *value as u32).sum::<u32>()
+                            );
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("evaluating thread panicked");
+        }
+
+        expect_that!(&counter.num_evaluations(), eq(200));
+        expect_that!(&evaluator.num_cached(), eq(200));
+    }
+
+    /// Maximizes the first gene.
+    #[derive(Clone, Debug)]
+    struct FirstGene;
+
+    impl FitnessFunction<Vec<i64>, i64> for FirstGene {
+        fn fitness_of(&self, genome: &Vec<i64>) -> i64 {
+            genome[0]
+        }
+
+        fn average(&self, fitness_values: &[i64]) -> i64 {
+            fitness_values.iter().sum::<i64>() / fitness_values.len() as i64
+        }
+
+        fn highest_possible_fitness(&self) -> i64 {
+            100
+        }
+
+        fn lowest_possible_fitness(&self) -> i64 {
+            -100
+        }
+    }
+
+    /// Maximize x subject to x <= 5.
+    fn constrained(penalty_method: PenaltyMethod) -> ConstrainedFitness<Vec<i64>, i64, FirstGene> {
+        ConstrainedFitness::new(FirstGene, penalty_method)
+            .with_constraint(|x: &Vec<i64>| x[0] as f64 - 5.)
+    }
+
+    #[test]
+    fn constrained_fitness_keeps_fitness_of_feasible_genomes() {
+        let evaluator = constrained(PenaltyMethod::Death);
+
+        expect_that!(&evaluator.fitness_of(&vec![-3]), eq(-3));
+        expect_that!(&evaluator.fitness_of(&vec![5]), eq(5));
+        expect_that!(&evaluator.violation_of(&vec![5]), eq(0.));
+    }
+
+    #[test]
+    fn constrained_fitness_with_death_penalty_assigns_lowest_fitness() {
+        let evaluator = constrained(PenaltyMethod::Death);
+
+        expect_that!(&evaluator.fitness_of(&vec![6]), eq(-100));
+        expect_that!(&evaluator.fitness_of(&vec![50]), eq(-100));
+    }
+
+    #[test]
+    fn constrained_fitness_with_static_penalty_grades_infeasible_genomes() {
+        let evaluator = constrained(PenaltyMethod::Static { coefficient: 3. });
+
+        // 7 - 3 * 2 and 6 - 3 * 1
+        expect_that!(&evaluator.fitness_of(&vec![7]), eq(1));
+        expect_that!(&evaluator.fitness_of(&vec![6]), eq(3));
+        // the best feasible genome is better than any infeasible one
+        let best_infeasible = (6..100).map(|x| evaluator.fitness_of(&vec![x])).max();
+        expect_that!(
+            &(best_infeasible < Some(evaluator.fitness_of(&vec![5]))),
+            is(eq(true))
+        );
+        // the penalty does not go below the lowest possible fitness
+        expect_that!(&evaluator.fitness_of(&vec![90]), eq(-100));
+    }
+
+    #[test]
+    fn constrained_fitness_with_generation_scaled_penalty_grows_with_generation() {
+        let evaluator = constrained(PenaltyMethod::GenerationScaled {
+            coefficient: 1.,
+            exponent: 2.,
+        });
+        let cloned = evaluator.clone();
+
+        // 7 - (1 * 1)^2 * 2
+        expect_that!(&evaluator.fitness_of(&vec![7]), eq(5));
+        cloned.set_generation(3);
+        // 7 - (1 * 3)^2 * 2
+        expect_that!(&evaluator.generation(), eq(3));
+        expect_that!(&evaluator.fitness_of(&vec![7]), eq(-11));
+    }
+
+    #[test]
+    fn constrained_fitness_sums_violations_of_all_constraints() {
+        let evaluator = constrained(PenaltyMethod::Static { coefficient: 1. })
+            .with_constraint(|x: &Vec<i64>| x[1] as f64 - 10.)
+            .with_constraint(|x: &Vec<i64>| 0. - x[1] as f64);
+
+        expect_that!(&evaluator.num_constraints(), eq(3));
+        // violates x[0] <= 5 by 3 and x[1] <= 10 by 2
+        expect_that!(&evaluator.violation_of(&vec![8, 12]), eq(5.));
+        expect_that!(&evaluator.fitness_of(&vec![8, 12]), eq(3));
+        // violates x[1] >= 0 by 4
+        expect_that!(&evaluator.fitness_of(&vec![2, -4]), eq(-2));
+    }
+
+    #[test]
+    fn reversal_identity_maps_a_genome_and_its_reversal_to_the_same_key() {
+        let key = ReversalIdentity.canonical_key(&vec![3, 1, 2]);
+
+        expect_that!(&key, eq(vec![2, 1, 3]));
+        expect_that!(&ReversalIdentity.canonical_key(&vec![2, 1, 3]), eq(key));
+        expect_that!(
+            &ReversalIdentity.canonical_key(&vec![1, 3, 2]),
+            eq(vec![1, 3, 2])
+        );
+    }
+
+    #[test]
+    fn hamming_distance_of_identical_genomes_is_zero() {
+        let genome = vec![1u8, 2, 3, 4];
+
+        expect_that!(&HammingDistance.distance(&genome, &genome.clone()), eq(0.));
+    }
+
+    #[test]
+    fn hamming_distance_of_fully_different_genomes_is_their_length() {
+        let genome1 = vec![true, false, true, false, true];
+        let genome2 = vec![false, true, false, true, false];
+
+        expect_that!(&HammingDistance.distance(&genome1, &genome2), eq(5.));
+    }
+
+    #[test]
+    fn hamming_distance_counts_the_surplus_loci_of_the_longer_genome_as_differing() {
+        let genome1 = vec![1u8, 2, 3];
+        let genome2 = vec![1u8, 5, 3, 4, 5];
+
+        expect_that!(&HammingDistance.distance(&genome1, &genome2), eq(3.));
+        expect_that!(&HammingDistance.distance(&genome2, &genome1), eq(3.));
+        expect_that!(&HammingDistance.distance(&genome1, &Vec::new()), eq(3.));
+    }
+
+    #[test]
+    fn euclidean_distance_of_identical_genomes_is_zero() {
+        let genome = vec![0.5, -1.5, 2.];
+
+        expect_that!(
+            &EuclideanDistance.distance(&genome, &genome.clone()),
+            eq(0.)
+        );
+    }
+
+    #[test]
+    fn euclidean_distance_of_different_genomes_is_the_length_of_their_difference() {
+        let genome1 = vec![1., 2., 3., 4.];
+        let genome2 = vec![2., 4., 0., 4.];
+
+        expect_that!(
+            &EuclideanDistance.distance(&genome1, &genome2),
+            is(close_to(14f64.sqrt(), 1e-12))
+        );
+        expect_that!(
+            &EuclideanDistance.distance(&vec![0i32, 0], &vec![3i32, 4]),
+            eq(5.)
+        );
+    }
+
+    /// A toy problem with the objectives `f1(x) = x` and `f2(x) = 10 - x^2`.
+    fn two_objectives() -> Vec<Objective<Vec<f64>>> {
+        vec![
+            Arc::new(|genome: &Vec<f64>| genome[0]),
+            Arc::new(|genome: &Vec<f64>| 10. - genome[0] * genome[0]),
+        ]
+    }
+
+    #[test]
+    fn weighted_sum_fitness_is_the_weighted_sum_of_the_objectives() {
+        let fitness = WeightedSumFitness::new(two_objectives(), vec![0.25, 0.5]);
+
+        expect_that!(
+            &fitness.fitness_of(&vec![2.]).value(),
+            is(close_to(0.25 * 2. + 0.5 * 6., 1e-12))
+        );
+        expect_that!(
+            &fitness.fitness_of(&vec![-1.]).value(),
+            is(close_to(-0.25 + 0.5 * 9., 1e-12))
+        );
+        expect_that!(
+            &fitness.fitness_of(&vec![1.]),
+            is(greater_than(fitness.fitness_of(&vec![-1.])))
+        );
+    }
+
+    #[test]
+    fn weighted_sum_fitness_normalizes_the_objectives_by_their_bounds() {
+        let fitness = WeightedSumFitness::new(two_objectives(), vec![1., 3.])
+            .with_bounds(vec![(-2., 2.), (6., 10.)])
+            .with_normalized_weights();
+
+        expect_that!(&fitness.weights(), eq(&[0.25, 0.75][..]));
+        // f1 = (1 + 2) / 4 = 0.75, f2 = (9 - 6) / 4 = 0.75
+        expect_that!(
+            &fitness.fitness_of(&vec![1.]).value(),
+            is(close_to(0.25 * 0.75 + 0.75 * 0.75, 1e-12))
+        );
+        expect_that!(&fitness.highest_possible_fitness().value(), eq(1.));
+        expect_that!(&fitness.lowest_possible_fitness().value(), eq(0.));
+    }
+
+    #[test]
+    #[should_panic(expected = "the number of weights 3 does not match the number of objectives 2")]
+    fn weighted_sum_fitness_rejects_a_weight_per_objective_mismatch() {
+        WeightedSumFitness::new(two_objectives(), vec![0.2, 0.3, 0.5]);
+    }
+
+    #[test]
+    fn scalar_fitness_treats_nan_as_the_worst_fitness() {
+        let nan = ScalarFitness(f64::NAN);
+
+        expect_that!(&nan, is(less_than(ScalarFitness(f64::NEG_INFINITY))));
+        expect_that!(&nan, eq(ScalarFitness(-f64::NAN)));
+        expect_that!(&ScalarFitness(-0.), eq(ScalarFitness(0.)));
+        expect_that!(&ScalarFitness(1.5), is(greater_than(ScalarFitness(-2.))));
+    }
+
+    #[test]
+    fn scalar_fitness_with_nan_values_is_sorted_deterministically() {
+        let mut values: Vec<ScalarFitness> = vec![3., f64::NAN, -1., f64::INFINITY, f64::NAN, 0.]
+            .into_iter()
+            .map(ScalarFitness::from)
+            .collect();
+
+        values.sort();
+
+        let values: Vec<f64> = values.iter().map(ScalarFitness::value).collect();
+        expect_that!(&values[0].is_nan(), eq(true));
+        expect_that!(&values[1].is_nan(), eq(true));
+        expect_that!(&values[2..].to_vec(), eq(vec![-1., 0., 3., f64::INFINITY]));
+        let best = vec![ScalarFitness(f64::NAN), ScalarFitness(-5.)]
+            .into_iter()
+            .max();
+        expect_that!(&best, eq(Some(ScalarFitness(-5.))));
+    }
+
+    /// Adds a bonus of 10 to the fitness of genomes starting with 0.
+    #[derive(Clone, Debug, PartialEq)]
+    struct ZeroStartBonus;
+
+    impl FitnessTransform<Vec<u8>, u32> for ZeroStartBonus {
+        fn transform(&self, genome: &Vec<u8>, fitness: u32) -> u32 {
+            if genome[0] == 0 {
+                fitness + 10
+            } else {
+                fitness
+            }
+        }
+
+        fn transform_limit(&self, limit: u32) -> u32 {
+            limit.saturating_add(10)
+        }
+    }
+
+    /// Subtracts the lowest fitness of the population from all values.
+    #[derive(Clone, Debug, PartialEq)]
+    struct SubtractLowest;
+
+    impl FitnessTransform<Vec<u8>, u32> for SubtractLowest {
+        fn transform_population(&self, _: &[Vec<u8>], fitness_values: &mut [u32]) -> bool {
+            let lowest = fitness_values.iter().min().cloned().unwrap_or(0);
+            for fitness in fitness_values.iter_mut() {
+                *fitness -= lowest;
+            }
+            lowest > 0
+        }
+    }
+
+    #[test]
+    fn transform_pipeline_applies_the_transforms_in_order() {
+        let evaluator = pipeline(CountingEvaluator::default())
+            .then(ZeroStartBonus)
+            .then(SubtractLowest);
+        let population = vec![vec![0u8, 4], vec![3, 3], vec![5, 9], vec![0, 0]];
+
+        let mut fitness_values: Vec<u32> = population
+            .iter()
+            .map(|genome| evaluator.fitness_of(genome))
+            .collect();
+        expect_that!(&fitness_values, eq(vec![14, 6, 14, 10]));
+
+        let adjusted = evaluator.adjust_population_fitness(&population, &mut fitness_values);
+
+        expect_that!(&adjusted, is(eq(true)));
+        expect_that!(&fitness_values, eq(vec![8, 0, 8, 4]));
+        expect_that!(&evaluator.lowest_possible_fitness(), eq(10));
+    }
+
+    #[test]
+    fn empty_transform_pipeline_evaluates_like_the_wrapped_evaluator() {
+        let evaluator = pipeline(CountingEvaluator::default());
+        let population = vec![vec![1u8, 2], vec![3, 4]];
+        let mut fitness_values: Vec<u32> = population
+            .iter()
+            .map(|genome| evaluator.fitness_of(genome))
+            .collect();
+
+        let adjusted = evaluator.adjust_population_fitness(&population, &mut fitness_values);
+
+        expect_that!(&adjusted, is(eq(false)));
+        expect_that!(&fitness_values, eq(vec![3, 7]));
+        expect_that!(&evaluator.evaluator().num_evaluations(), eq(2));
+    }
 }
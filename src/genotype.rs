@@ -0,0 +1,632 @@
+//! The `genotype` module provides implementations of `genetic::Genotype`s
+//! that are more efficient than the standard collections for special kinds of
+//! problems.
+//!
+//! The provided `genetic::Genotype`s are:
+//! * `BinaryGenome` - a binary encoded genome that stores 64 bits per word
+//! * `Diploid` - a pair of homologous genomes that is expressed into one
+//!   effective genome by a user supplied `Dominance` map
+//! * `SelfAdaptive` - a genome with its own number of crossover points that
+//!   evolves alongside the solution
+//! * `tree::ExpressionTree` - a tree of functions and terminals as evolved by
+//!   genetic programming
+
+use crate::{
+    encoding::BinaryEncoded,
+    genetic::{
+        Fitness, FitnessFunction, GenomeDistance, Genotype, GenotypeToPhenotype, HammingDistance,
+    },
+};
+use std::{fmt::Debug, iter::FromIterator};
+
+pub mod tree;
+
+/// The number of bits stored in one word of a `BinaryGenome`.
+const BITS_PER_WORD: usize = 64;
+
+/// The `BinaryGenome` is a binary encoded `genetic::Genotype` that packs its
+/// bits into a `Vec` of `u64` words. It needs only one bit of memory per
+/// gene while a `Vec<bool>` needs one byte per gene.
+///
+/// The `BinaryEncodedGenomeBuilder`, the `BitFlipMutator`, the
+/// `UniformCrossBreeder`, the `SinglePointCrossBreeder` and the
+/// `MultiPointCrossBreeder` can be used with the `BinaryGenome`. They
+/// consume random numbers in the same way as for `Vec<bool>` genomes, so that
+/// a simulation gives the same results for both kinds of genomes.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct BinaryGenome {
+    /// The bits of the genome. The unused bits of the last word are
+    /// always 0.
+    words: Vec<u64>,
+    /// The number of bits of the genome.
+    len: usize,
+}
+
+impl BinaryGenome {
+    /// Constructs a new `BinaryGenome` of the given length with all bits set
+    /// to `false`.
+    pub fn new(len: usize) -> Self {
+        BinaryGenome {
+            words: vec![0; num_words(len)],
+            len,
+        }
+    }
+
+    /// Returns the number of bits of this genome.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether this genome has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn get(&self, index: usize) -> bool {
+        self.check_index(index);
+        self.words[index / BITS_PER_WORD] & bit_mask(index) != 0
+    }
+
+    /// Sets the bit at the given index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: bool) {
+        self.check_index(index);
+        if value {
+            self.words[index / BITS_PER_WORD] |= bit_mask(index);
+        } else {
+            self.words[index / BITS_PER_WORD] &= !bit_mask(index);
+        }
+    }
+
+    /// Flips the bit at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn flip(&mut self, index: usize) {
+        self.check_index(index);
+        self.words[index / BITS_PER_WORD] ^= bit_mask(index);
+    }
+
+    /// Returns the number of bits that are set to `true`.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns an iterator over the bits of this genome.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            genome: self,
+            index: 0,
+        }
+    }
+
+    /// Returns the bits of this genome as `Vec<bool>`.
+    pub fn to_vec(&self) -> Vec<bool> {
+        self.iter().collect()
+    }
+
+    /// Returns the number of words in which the bits are stored.
+    pub(crate) fn num_words(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns the number of bits stored in the word at the given index.
+    pub(crate) fn bits_in_word(&self, word: usize) -> usize {
+        (self.len - word * BITS_PER_WORD).min(BITS_PER_WORD)
+    }
+
+    /// Returns the word at the given index.
+    pub(crate) fn word(&self, word: usize) -> u64 {
+        self.words[word]
+    }
+
+    /// Sets the word at the given index. The bits of the word beyond the
+    /// length of the genome must be 0.
+    pub(crate) fn set_word(&mut self, word: usize, value: u64) {
+        debug_assert!(
+            self.bits_in_word(word) == BITS_PER_WORD || value >> self.bits_in_word(word) == 0,
+            "bits beyond the length of the genome must be 0"
+        );
+        self.words[word] = value;
+    }
+
+    /// Copies the bits from `start` to `end` (exclusive) of the other genome
+    /// into this genome. Whole words are copied at once, only the words at the
+    /// boundaries of the range are masked.
+    pub(crate) fn copy_bits_from(&mut self, other: &BinaryGenome, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+        let first_word = start / BITS_PER_WORD;
+        let last_word = (end - 1) / BITS_PER_WORD;
+        for word in first_word..=last_word {
+            let low = if word == first_word {
+                start % BITS_PER_WORD
+            } else {
+                0
+            };
+            let high = if word == last_word {
+                (end - 1) % BITS_PER_WORD + 1
+            } else {
+                BITS_PER_WORD
+            };
+            let mask = range_mask(low, high);
+            self.words[word] = (self.words[word] & !mask) | (other.words[word] & mask);
+        }
+    }
+
+    fn check_index(&self, index: usize) {
+        assert!(
+            index < self.len,
+            "index {} is out of bounds for a genome of length {}",
+            index,
+            self.len
+        );
+    }
+}
+
+impl Genotype for BinaryGenome {
+    type Dna = bool;
+}
+
+impl BinaryEncoded for BinaryGenome {}
+
+/// The hamming distance of two `BinaryGenome`s of the same length is
+/// calculated word by word.
+impl GenomeDistance<BinaryGenome> for HammingDistance {
+    fn distance(&self, genome1: &BinaryGenome, genome2: &BinaryGenome) -> f64 {
+        if genome1.len == genome2.len {
+            return genome1
+                .words
+                .iter()
+                .zip(genome2.words.iter())
+                .map(|(word1, word2)| (word1 ^ word2).count_ones() as usize)
+                .sum::<usize>() as f64;
+        }
+        let num_differing = genome1
+            .iter()
+            .zip(genome2.iter())
+            .filter(|(bit1, bit2)| bit1 != bit2)
+            .count();
+        let length_difference = genome1.len.max(genome2.len) - genome1.len.min(genome2.len);
+        (num_differing + length_difference) as f64
+    }
+}
+
+impl FromIterator<bool> for BinaryGenome {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = bool>,
+    {
+        let mut words = Vec::new();
+        let mut len = 0;
+        for bit in iter {
+            if len % BITS_PER_WORD == 0 {
+                words.push(0);
+            }
+            if bit {
+                *words.last_mut().expect("a word has been pushed") |= bit_mask(len);
+            }
+            len += 1;
+        }
+        BinaryGenome { words, len }
+    }
+}
+
+impl From<&[bool]> for BinaryGenome {
+    fn from(bits: &[bool]) -> Self {
+        bits.iter().cloned().collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a BinaryGenome {
+    type Item = bool;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the bits of a `BinaryGenome`.
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    genome: &'a BinaryGenome,
+    index: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.index < self.genome.len {
+            let bit = self.genome.get(self.index);
+            self.index += 1;
+            Some(bit)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.genome.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+/// Returns the number of words that are needed to store the given number of
+/// bits.
+fn num_words(len: usize) -> usize {
+    len.div_ceil(BITS_PER_WORD)
+}
+
+/// Returns the mask of the bit at the given index within its word.
+fn bit_mask(index: usize) -> u64 {
+    1 << (index % BITS_PER_WORD)
+}
+
+/// Returns the mask of the bits from `low` to `high` (exclusive) of a word.
+fn range_mask(low: usize, high: usize) -> u64 {
+    if high - low == BITS_PER_WORD {
+        !0
+    } else {
+        ((1 << (high - low)) - 1) << low
+    }
+}
+
+/// The `Diploid` genome holds two homologous genomes, like the pairs of
+/// chromosomes of diploid organisms.
+///
+/// The two homologous genomes are expressed into one effective genome by a
+/// `Dominance` map. Only the expressed genome determines the fitness of a
+/// `Diploid` genome, so that an allele that is recessive can persist in the
+/// population without lowering the fitness of its carriers.
+///
+/// The `ExpressedFitness` evaluates a `Diploid` genome with the
+/// `FitnessFunction` of the expressed genome, and the `DiploidDecoder`
+/// decodes a `Diploid` genome into its expressed genome. The
+/// `recombination::diploid::DiploidCrossBreeder` and the
+/// `mutation::diploid::DiploidMutator` apply the operators of the homologous
+/// genomes to each homologous genome.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct Diploid<G> {
+    /// The first homologous genome.
+    first: G,
+    /// The second homologous genome.
+    second: G,
+}
+
+impl<G> Diploid<G> {
+    /// Constructs a new `Diploid` genome of the two given homologous genomes.
+    pub fn new(first: G, second: G) -> Self {
+        Diploid { first, second }
+    }
+
+    /// Returns the first homologous genome.
+    pub fn first(&self) -> &G {
+        &self.first
+    }
+
+    /// Returns the second homologous genome.
+    pub fn second(&self) -> &G {
+        &self.second
+    }
+
+    /// Returns the two homologous genomes.
+    pub fn into_inner(self) -> (G, G) {
+        (self.first, self.second)
+    }
+
+    /// Expresses the two homologous genomes into the effective genome using
+    /// the given `Dominance` map.
+    pub fn express<D>(&self, dominance: &D) -> G
+    where
+        D: Dominance<G>,
+    {
+        dominance.express(&self.first, &self.second)
+    }
+}
+
+impl<G> Genotype for Diploid<G>
+where
+    G: Genotype,
+{
+    type Dna = G::Dna;
+}
+
+/// The `Dominance` map defines how the two homologous genomes of a `Diploid`
+/// genome are expressed into the effective genome, e.g. which allele of
+/// each locus is dominant.
+pub trait Dominance<G>: Clone + Debug {
+    /// Expresses the two given homologous genomes into the effective genome.
+    fn express(&self, first: &G, second: &G) -> G;
+}
+
+/// The `ExpressedFitness` is a `FitnessFunction` for `Diploid` genomes. It
+/// expresses the homologous genomes with the `Dominance` map and evaluates
+/// the expressed genome with the wrapped `FitnessFunction`.
+///
+/// This way a `FitnessFunction` that has been written for haploid genomes
+/// can be used for `Diploid` genomes without changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpressedFitness<E, D> {
+    /// The `FitnessFunction` of the expressed genome.
+    fitness_function: E,
+    /// The `Dominance` map that expresses the homologous genomes.
+    dominance: D,
+}
+
+impl<E, D> ExpressedFitness<E, D> {
+    /// Constructs a new `ExpressedFitness` which evaluates the genomes that
+    /// are expressed by the given `Dominance` map with the given
+    /// `FitnessFunction`.
+    pub fn new(fitness_function: E, dominance: D) -> Self {
+        ExpressedFitness {
+            fitness_function,
+            dominance,
+        }
+    }
+
+    /// Returns the `FitnessFunction` of the expressed genome.
+    pub fn fitness_function(&self) -> &E {
+        &self.fitness_function
+    }
+
+    /// Returns the `Dominance` map.
+    pub fn dominance(&self) -> &D {
+        &self.dominance
+    }
+}
+
+impl<G, F, E, D> FitnessFunction<Diploid<G>, F> for ExpressedFitness<E, D>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    D: Dominance<G>,
+{
+    fn fitness_of(&self, diploid: &Diploid<G>) -> F {
+        self.fitness_function
+            .fitness_of(&diploid.express(&self.dominance))
+    }
+
+    fn average(&self, fitness_values: &[F]) -> F {
+        self.fitness_function.average(fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> F {
+        self.fitness_function.highest_possible_fitness()
+    }
+
+    fn lowest_possible_fitness(&self) -> F {
+        self.fitness_function.lowest_possible_fitness()
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        self.fitness_function.use_parallel_ga()
+    }
+
+    fn start_generation(&self, generation: u64) {
+        self.fitness_function.start_generation(generation)
+    }
+}
+
+/// The `DiploidDecoder` is a `GenotypeToPhenotype` decoder that decodes a
+/// `Diploid` genome into the genome that is expressed by the `Dominance`
+/// map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiploidDecoder<D> {
+    /// The `Dominance` map that expresses the homologous genomes.
+    dominance: D,
+}
+
+impl<D> DiploidDecoder<D> {
+    /// Constructs a new `DiploidDecoder` using the given `Dominance` map.
+    pub fn new(dominance: D) -> Self {
+        DiploidDecoder { dominance }
+    }
+
+    /// Returns the `Dominance` map.
+    pub fn dominance(&self) -> &D {
+        &self.dominance
+    }
+}
+
+impl<G, D> GenotypeToPhenotype<Diploid<G>, G> for DiploidDecoder<D>
+where
+    G: Genotype,
+    D: Dominance<G>,
+{
+    fn decode(&self, diploid: &Diploid<G>) -> G {
+        diploid.express(&self.dominance)
+    }
+}
+
+/// The `SelfAdaptive` genome holds a genome together with the number of cut
+/// points that is used to breed its children, a strategy parameter as known
+/// from evolution strategies.
+///
+/// The strategy parameter lives in the genome itself, so that it is
+/// selected, inherited and replaced together with the genome it belongs to.
+/// The `recombination::discrete::SelfAdaptiveMultiPointCrossBreeder` reads
+/// the number of cut points of the parents, passes it on to the children and
+/// mutates it. The `mutation::adaptive::SelfAdaptiveMutator` mutates the
+/// genome with another `MutationOp` and keeps the number of cut points.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct SelfAdaptive<G> {
+    /// The genome that encodes the solution.
+    genome: G,
+    /// The number of cut points used to breed the children of the genome.
+    num_cut_points: usize,
+}
+
+impl<G> SelfAdaptive<G> {
+    /// Constructs a new `SelfAdaptive` genome of the given genome and the
+    /// given number of cut points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cut points is 0.
+    pub fn new(genome: G, num_cut_points: usize) -> Self {
+        assert!(
+            num_cut_points > 0,
+            "the number of cut points must be greater than 0"
+        );
+        SelfAdaptive {
+            genome,
+            num_cut_points,
+        }
+    }
+
+    /// Returns the genome that encodes the solution.
+    pub fn genome(&self) -> &G {
+        &self.genome
+    }
+
+    /// Returns the number of cut points used to breed the children of the
+    /// genome.
+    pub fn num_cut_points(&self) -> usize {
+        self.num_cut_points
+    }
+
+    /// Returns the genome and the number of cut points.
+    pub fn into_inner(self) -> (G, usize) {
+        (self.genome, self.num_cut_points)
+    }
+}
+
+impl<G> Genotype for SelfAdaptive<G>
+where
+    G: Genotype,
+{
+    type Dna = G::Dna;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use galvanic_assert::matchers::*;
+
+    fn pattern(len: usize) -> Vec<bool> {
+        (0..len).map(|i| i % 3 == 0 || i % 7 == 0).collect()
+    }
+
+    #[test]
+    fn binary_genome_can_be_created_from_and_converted_to_bools() {
+        let bits = pattern(150);
+
+        let genome = BinaryGenome::from(&bits[..]);
+
+        expect_that!(&genome.len(), eq(150));
+        expect_that!(&genome.num_words(), eq(3));
+        expect_that!(&genome.to_vec(), eq(bits.clone()));
+        expect_that!(
+            &genome.count_ones(),
+            eq(bits.iter().filter(|bit| **bit).count())
+        );
+    }
+
+    #[test]
+    fn set_and_flip_change_single_bits_across_word_boundaries() {
+        let mut genome = BinaryGenome::new(130);
+
+        for index in &[0, 63, 64, 127, 128, 129] {
+            genome.set(*index, true);
+        }
+        genome.flip(64);
+        genome.flip(65);
+        genome.set(128, false);
+
+        let ones: Vec<usize> = genome
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| *bit)
+            .map(|(index, _)| index)
+            .collect();
+        expect_that!(&ones, eq(vec![0, 63, 65, 127, 129]));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 10 is out of bounds for a genome of length 10")]
+    fn get_panics_if_the_index_is_out_of_bounds() {
+        BinaryGenome::new(10).get(10);
+    }
+
+    #[test]
+    fn copy_bits_from_copies_ranges_across_word_boundaries() {
+        let len = 200;
+        let other = BinaryGenome::from(&vec![true; len][..]);
+
+        for &(start, end) in &[
+            (0, 0),
+            (0, 200),
+            (3, 61),
+            (60, 70),
+            (63, 129),
+            (64, 128),
+            (130, 200),
+        ] {
+            let mut genome = BinaryGenome::new(len);
+            genome.copy_bits_from(&other, start, end);
+
+            let expected: Vec<bool> = (0..len).map(|i| i >= start && i < end).collect();
+            expect_that!(&genome.to_vec(), eq(expected));
+        }
+    }
+
+    #[test]
+    fn hamming_distance_of_binary_genomes_counts_differing_bits() {
+        let genome1 = BinaryGenome::from(&pattern(150)[..]);
+        let mut genome2 = genome1.clone();
+        for index in &[0, 63, 64, 149] {
+            genome2.flip(*index);
+        }
+        let shorter = BinaryGenome::from(&pattern(140)[..]);
+
+        expect_that!(&HammingDistance.distance(&genome1, &genome1), eq(0.));
+        expect_that!(&HammingDistance.distance(&genome1, &genome2), eq(4.));
+        expect_that!(&HammingDistance.distance(&genome2, &shorter), eq(13.));
+    }
+
+    #[test]
+    fn binary_genomes_with_the_same_bits_are_equal() {
+        let mut genome1 = BinaryGenome::new(70);
+        let mut genome2 = BinaryGenome::from(&vec![true; 70][..]);
+
+        genome1.set(69, true);
+        for index in 0..69 {
+            genome2.set(index, false);
+        }
+
+        expect_that!(&genome1, eq(genome2));
+    }
+}
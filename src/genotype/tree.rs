@@ -0,0 +1,362 @@
+//! The `tree` module provides the `ExpressionTree`, a tree encoded
+//! `genetic::Genotype` for genetic programming, and the `PrimitiveSet` of
+//! functions and terminals that random trees are grown from.
+//!
+//! The nodes of an `ExpressionTree` are addressed by their index in
+//! pre-order, i.e. the root has the index 0 and the subtree of a node
+//! follows the node itself. The depth of a tree is the number of edges on
+//! the longest path from the root to a leaf, so a single leaf has the depth
+//! 0.
+
+use crate::{
+    encoding::TreeEncoded,
+    genetic::Genotype,
+    random::{random_index, Rng},
+};
+use std::fmt::Debug;
+
+/// The `ExpressionTree` is a tree encoded `genetic::Genotype` as evolved by
+/// genetic programming.
+///
+/// Each `Node` holds a function and its arguments, the children. Each `Leaf`
+/// holds a terminal, e.g. a variable or a constant. The meaning of the
+/// values is up to the application, which typically evaluates the tree
+/// recursively in its `genetic::FitnessFunction`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub enum ExpressionTree<T> {
+    /// A terminal without children.
+    Leaf(T),
+    /// A function with its arguments as children.
+    Node(T, Vec<ExpressionTree<T>>),
+}
+
+impl<T> ExpressionTree<T> {
+    /// Returns the value of the root of this tree.
+    pub fn value(&self) -> &T {
+        match self {
+            ExpressionTree::Leaf(value) | ExpressionTree::Node(value, _) => value,
+        }
+    }
+
+    /// Returns the children of the root of this tree, which are empty for a
+    /// leaf.
+    pub fn children(&self) -> &[ExpressionTree<T>] {
+        match self {
+            ExpressionTree::Leaf(_) => &[],
+            ExpressionTree::Node(_, children) => children,
+        }
+    }
+
+    /// Returns whether the root of this tree is a leaf.
+    pub fn is_leaf(&self) -> bool {
+        matches!(self, ExpressionTree::Leaf(_))
+    }
+
+    /// Returns the depth of this tree, which is 0 for a single leaf.
+    pub fn depth(&self) -> usize {
+        self.children()
+            .iter()
+            .map(|child| child.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of nodes and leaves of this tree.
+    pub fn size(&self) -> usize {
+        1 + self.children().iter().map(Self::size).sum::<usize>()
+    }
+
+    /// Returns the subtree rooted at the node with the given pre-order index
+    /// or `None` if the index is out of bounds.
+    pub fn subtree(&self, index: usize) -> Option<&ExpressionTree<T>> {
+        self.subtree_with_level(index).map(|(subtree, _)| subtree)
+    }
+
+    /// Returns the level of the node with the given pre-order index, i.e.
+    /// the number of edges between the root and the node, or `None` if the
+    /// index is out of bounds.
+    pub fn level_of(&self, index: usize) -> Option<usize> {
+        self.subtree_with_level(index).map(|(_, level)| level)
+    }
+
+    /// Replaces the subtree rooted at the node with the given pre-order index
+    /// by the given subtree and returns the replaced subtree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn replace_subtree(
+        &mut self,
+        index: usize,
+        subtree: ExpressionTree<T>,
+    ) -> ExpressionTree<T> {
+        let size = self.size();
+        match self.subtree_mut(index) {
+            Some(target) => std::mem::replace(target, subtree),
+            None => panic!(
+                "the index {} is out of bounds of a tree of size {}",
+                index, size
+            ),
+        }
+    }
+
+    fn subtree_with_level(&self, index: usize) -> Option<(&ExpressionTree<T>, usize)> {
+        if index == 0 {
+            return Some((self, 0));
+        }
+        let mut remaining = index - 1;
+        for child in self.children() {
+            let child_size = child.size();
+            if remaining < child_size {
+                return child
+                    .subtree_with_level(remaining)
+                    .map(|(subtree, level)| (subtree, level + 1));
+            }
+            remaining -= child_size;
+        }
+        None
+    }
+
+    fn subtree_mut(&mut self, index: usize) -> Option<&mut ExpressionTree<T>> {
+        if index == 0 {
+            return Some(self);
+        }
+        let mut remaining = index - 1;
+        if let ExpressionTree::Node(_, children) = self {
+            for child in children.iter_mut() {
+                let child_size = child.size();
+                if remaining < child_size {
+                    return child.subtree_mut(remaining);
+                }
+                remaining -= child_size;
+            }
+        }
+        None
+    }
+}
+
+impl<T> Genotype for ExpressionTree<T>
+where
+    T: Clone + Debug + PartialEq + Send + Sync,
+{
+    type Dna = T;
+}
+
+impl<T> TreeEncoded for ExpressionTree<T> where T: Clone + Debug + PartialEq + Send + Sync {}
+
+/// The `GrowthMethod` defines how random `ExpressionTree`s are grown up to a
+/// maximum depth.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GrowthMethod {
+    /// Every path from the root to a leaf has the maximum depth. Only
+    /// functions are chosen above the maximum depth.
+    Full,
+    /// The paths from the root to the leaves may be shorter than the maximum
+    /// depth. Functions and terminals are chosen with the same probability
+    /// per primitive above the maximum depth.
+    Grow,
+    /// The trees of a population are grown with depths ramped from a
+    /// minimum depth to the maximum depth, one half of them by `Full` and
+    /// the other half by `Grow`.
+    RampedHalfAndHalf,
+}
+
+/// The `PrimitiveSet` holds the functions with their arity and the terminals
+/// an `ExpressionTree` is built from.
+///
+/// As the children of each node are grown for the arity of its function,
+/// the trees grown from a `PrimitiveSet` are always valid expressions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrimitiveSet<T> {
+    /// The functions and their number of arguments.
+    functions: Vec<(T, usize)>,
+    /// The terminals.
+    terminals: Vec<T>,
+}
+
+impl<T> PrimitiveSet<T>
+where
+    T: Clone,
+{
+    /// Constructs a new `PrimitiveSet` of the given functions, each with its
+    /// number of arguments, and the given terminals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no terminals or if a function takes no arguments.
+    pub fn new(functions: Vec<(T, usize)>, terminals: Vec<T>) -> Self {
+        assert!(
+            !terminals.is_empty(),
+            "the primitive set must contain at least one terminal"
+        );
+        assert!(
+            functions.iter().all(|(_, arity)| *arity > 0),
+            "each function must take at least one argument"
+        );
+        PrimitiveSet {
+            functions,
+            terminals,
+        }
+    }
+
+    /// Returns the functions and their number of arguments.
+    pub fn functions(&self) -> &[(T, usize)] {
+        &self.functions
+    }
+
+    /// Returns the terminals.
+    pub fn terminals(&self) -> &[T] {
+        &self.terminals
+    }
+
+    /// Grows a random `ExpressionTree` with the given `GrowthMethod` whose
+    /// depth is at most `max_depth`.
+    ///
+    /// A single tree grown by `RampedHalfAndHalf` gets a random depth
+    /// between 0 and `max_depth` (inclusive) and is grown by `Full` or `Grow`
+    /// with the same probability.
+    pub fn grow_tree<R>(
+        &self,
+        method: GrowthMethod,
+        max_depth: usize,
+        rng: &mut R,
+    ) -> ExpressionTree<T>
+    where
+        R: Rng + Sized,
+    {
+        match method {
+            GrowthMethod::Full => self.grow(true, max_depth, rng),
+            GrowthMethod::Grow => self.grow(false, max_depth, rng),
+            GrowthMethod::RampedHalfAndHalf => {
+                let depth = random_index(rng, max_depth + 1);
+                let full = rng.gen::<bool>();
+                self.grow(full, depth, rng)
+            },
+        }
+    }
+
+    fn grow<R>(&self, full: bool, depth: usize, rng: &mut R) -> ExpressionTree<T>
+    where
+        R: Rng + Sized,
+    {
+        let num_functions = if depth == 0 { 0 } else { self.functions.len() };
+        let num_primitives = if full && num_functions > 0 {
+            num_functions
+        } else {
+            num_functions + self.terminals.len()
+        };
+        let chosen = random_index(rng, num_primitives);
+        if chosen < num_functions {
+            let (function, arity) = &self.functions[chosen];
+            ExpressionTree::Node(
+                function.clone(),
+                (0..*arity)
+                    .map(|_| self.grow(full, depth - 1, rng))
+                    .collect(),
+            )
+        } else {
+            ExpressionTree::Leaf(self.terminals[chosen - num_functions].clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+
+    fn sample_tree() -> ExpressionTree<char> {
+        // (x + 1) * x
+        ExpressionTree::Node(
+            '*',
+            vec![
+                ExpressionTree::Node(
+                    '+',
+                    vec![ExpressionTree::Leaf('x'), ExpressionTree::Leaf('1')],
+                ),
+                ExpressionTree::Leaf('x'),
+            ],
+        )
+    }
+
+    fn primitives() -> PrimitiveSet<char> {
+        PrimitiveSet::new(vec![('+', 2), ('*', 2), ('-', 1)], vec!['x', '1'])
+    }
+
+    #[test]
+    fn nodes_are_addressed_in_pre_order() {
+        let tree = sample_tree();
+
+        expect_that!(&tree.size(), eq(5));
+        expect_that!(&tree.depth(), eq(2));
+        let values: Vec<char> = (0..5)
+            .map(|index| *tree.subtree(index).unwrap().value())
+            .collect();
+        expect_that!(&values, eq(vec!['*', '+', 'x', '1', 'x']));
+        let levels: Vec<usize> = (0..5).map(|index| tree.level_of(index).unwrap()).collect();
+        expect_that!(&levels, eq(vec![0, 1, 2, 2, 1]));
+        expect_that!(&tree.subtree(5), eq(None));
+    }
+
+    #[test]
+    fn a_replaced_subtree_is_returned() {
+        let mut tree = sample_tree();
+
+        let replaced = tree.replace_subtree(1, ExpressionTree::Leaf('1'));
+
+        expect_that!(&replaced.size(), eq(3));
+        expect_that!(
+            &tree,
+            eq(ExpressionTree::Node(
+                '*',
+                vec![ExpressionTree::Leaf('1'), ExpressionTree::Leaf('x')]
+            ))
+        );
+    }
+
+    #[test]
+    fn full_trees_have_the_maximum_depth_on_every_path() {
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..50 {
+            let tree = primitives().grow_tree(GrowthMethod::Full, 4, &mut rng);
+
+            expect_that!(&tree.depth(), eq(4));
+            for index in 0..tree.size() {
+                let subtree = tree.subtree(index).unwrap();
+                if subtree.is_leaf() {
+                    expect_that!(&tree.level_of(index), eq(Some(4)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn grown_trees_are_valid_expressions_within_the_maximum_depth() {
+        let primitives = primitives();
+        let mut rng = get_rng([42; 32]);
+
+        for method in &[GrowthMethod::Grow, GrowthMethod::RampedHalfAndHalf] {
+            for _ in 0..50 {
+                let tree = primitives.grow_tree(*method, 5, &mut rng);
+
+                expect_that!(&tree.depth(), leq(5));
+                for index in 0..tree.size() {
+                    let subtree = tree.subtree(index).unwrap();
+                    let arity = primitives
+                        .functions()
+                        .iter()
+                        .find(|(function, _)| function == subtree.value())
+                        .map_or(0, |(_, arity)| *arity);
+                    expect_that!(&subtree.children().len(), eq(arity));
+                }
+            }
+        }
+    }
+}
@@ -103,6 +103,8 @@ pub mod prelude;
 
 pub mod genetic;
 
+pub mod pareto;
+
 pub mod algorithm;
 
 pub mod ga;
@@ -111,6 +113,8 @@ pub mod population;
 
 pub mod encoding;
 
+pub mod genotype;
+
 pub mod operator;
 
 pub mod simulation;
@@ -130,3 +134,6 @@ pub mod random;
 pub mod statistic;
 
 pub mod types;
+
+#[cfg(test)]
+mod fixtures;
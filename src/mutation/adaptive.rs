@@ -0,0 +1,578 @@
+//! The `adaptive` module provides a `operator::MutationOp` that adjusts the
+//! mutation rate of another `operator::MutationOp` during the simulation.
+//!
+//! A high mutation rate helps to explore the search space while a low
+//! mutation rate helps to exploit promising regions of the search space. The
+//! `AdaptiveRateMutator` changes the mutation rate once per generation as
+//! determined by a `RateController`. The `SelfAdaptiveMutator` mutates
+//! `genotype::SelfAdaptive` genomes, whose strategy parameter adapts itself.
+//!
+//! The provided `RateController` implementations are:
+//! * `LinearSchedule` - changes the rate linearly over the generations.
+//! * `StagnationController` - raises the rate while the best fitness
+//!   stagnates and lowers it while the best fitness improves.
+//! * `DiversityController` - raises the rate while the diversity of the
+//!   population is low and lowers it while the diversity is high.
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genetic::Genotype,
+    genotype::SelfAdaptive,
+    operator::{GenerationSignal, GeneticOperator, MutationOp, MutationRate},
+    random::Rng,
+    termination::convergence::DiversityMetric,
+};
+use std::fmt::Debug;
+
+/// A `RateController` determines the mutation rate of an
+/// `AdaptiveRateMutator` for the next generation.
+pub trait RateController<G>: Clone + Debug
+where
+    G: Genotype,
+{
+    /// Returns the mutation rate for the generation described by the given
+    /// `GenerationSignal` given the mutation rate of the previous generation.
+    fn next_rate(&mut self, rate: f64, signal: &GenerationSignal<G>) -> f64;
+
+    /// Resets the state of this `RateController` when the
+    /// `AdaptiveRateMutator` is reset. The default implementation does
+    /// nothing.
+    fn reset(&mut self) {}
+}
+
+/// The `AdaptiveRateMutator` wraps another `MutationOp` and adjusts its
+/// mutation rate in every generation as determined by a `RateController`.
+///
+/// The mutation itself is done by the wrapped `MutationOp`, which must
+/// implement the `MutationRate` trait, so that its mutation rate can be
+/// changed. The initial mutation rate is the mutation rate of the wrapped
+/// `MutationOp`. It is kept as the base rate, which the mutation rate is
+/// set back to when the `AdaptiveRateMutator` is reset.
+///
+/// The adapted mutation rate is not part of a `ga::GeneticAlgorithmState`.
+/// To resume a simulation from a checkpoint, save the `AdaptiveRateMutator`
+/// along with it or restore its `mutation_rate` by `set_mutation_rate`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct AdaptiveRateMutator<M, P> {
+    /// The wrapped `MutationOp`.
+    mutator: M,
+    /// The `RateController` that determines the mutation rate.
+    controller: P,
+    /// The mutation rate of the wrapped `MutationOp` before it was adapted.
+    base_rate: f64,
+}
+
+impl<M, P> AdaptiveRateMutator<M, P>
+where
+    M: MutationRate,
+{
+    /// Constructs a new `AdaptiveRateMutator` wrapping the given `MutationOp`
+    /// with its mutation rate adjusted by the given `RateController`.
+    pub fn new(mutator: M, controller: P) -> Self {
+        let base_rate = mutator.mutation_rate();
+        AdaptiveRateMutator {
+            mutator,
+            controller,
+            base_rate,
+        }
+    }
+
+    /// Returns the wrapped `MutationOp`.
+    pub fn mutator(&self) -> &M {
+        &self.mutator
+    }
+
+    /// Returns the `RateController`.
+    pub fn controller(&self) -> &P {
+        &self.controller
+    }
+
+    /// Returns the mutation rate of the wrapped `MutationOp` before it was
+    /// adapted.
+    pub fn base_rate(&self) -> f64 {
+        self.base_rate
+    }
+}
+
+impl<M, P> MutationRate for AdaptiveRateMutator<M, P>
+where
+    M: MutationRate,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutator.mutation_rate()
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutator.set_mutation_rate(value);
+    }
+}
+
+impl<M, P> GeneticOperator for AdaptiveRateMutator<M, P>
+where
+    M: GeneticOperator,
+    P: Clone,
+{
+    fn name() -> String {
+        format!("Adaptive-Rate-{}", M::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        self.mutator.validate()
+    }
+}
+
+impl<G, M, P> MutationOp<G> for AdaptiveRateMutator<M, P>
+where
+    G: Genotype,
+    M: MutationOp<G> + MutationRate,
+    P: RateController<G>,
+{
+    fn mutate<R>(&self, genome: G, rng: &mut R) -> G
+    where
+        R: Rng + Sized,
+    {
+        self.mutator.mutate(genome, rng)
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.mutator.adapt(signal);
+        let rate = self
+            .controller
+            .next_rate(self.mutator.mutation_rate(), signal);
+        self.mutator.set_mutation_rate(rate);
+    }
+
+    fn reset(&mut self) {
+        self.mutator.reset();
+        self.controller.reset();
+        self.mutator.set_mutation_rate(self.base_rate);
+    }
+}
+
+/// The `SelfAdaptiveMutator` is a `MutationOp` for `genotype::SelfAdaptive`
+/// genomes that mutates the genome with another `MutationOp`.
+///
+/// The number of cut points of the `SelfAdaptive` genome is kept as it is.
+/// It is inherited and mutated by the
+/// `recombination::discrete::SelfAdaptiveMultiPointCrossBreeder`. The
+/// wrapped `MutationOp` is adapted with a `GenerationSignal` whose
+/// individuals are the genomes without their numbers of cut points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelfAdaptiveMutator<M> {
+    /// The `MutationOp` that mutates the genomes.
+    mutator: M,
+}
+
+impl<M> SelfAdaptiveMutator<M> {
+    /// Constructs a new `SelfAdaptiveMutator` that mutates the genomes with
+    /// the given `MutationOp`.
+    pub fn new(mutator: M) -> Self {
+        SelfAdaptiveMutator { mutator }
+    }
+
+    /// Returns the `MutationOp` that mutates the genomes.
+    pub fn mutator(&self) -> &M {
+        &self.mutator
+    }
+}
+
+impl<M> MutationRate for SelfAdaptiveMutator<M>
+where
+    M: MutationRate,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutator.mutation_rate()
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutator.set_mutation_rate(value);
+    }
+}
+
+impl<M> GeneticOperator for SelfAdaptiveMutator<M>
+where
+    M: GeneticOperator,
+{
+    fn name() -> String {
+        format!("Self-Adaptive-{}", M::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        self.mutator.validate()
+    }
+}
+
+impl<G, M> MutationOp<SelfAdaptive<G>> for SelfAdaptiveMutator<M>
+where
+    G: Genotype,
+    M: MutationOp<G>,
+{
+    fn mutate<R>(&self, genome: SelfAdaptive<G>, rng: &mut R) -> SelfAdaptive<G>
+    where
+        R: Rng + Sized,
+    {
+        let (genome, num_cut_points) = genome.into_inner();
+        SelfAdaptive::new(self.mutator.mutate(genome, rng), num_cut_points)
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<SelfAdaptive<G>>) {
+        let genomes: Vec<G> = signal
+            .individuals
+            .iter()
+            .map(|individual| individual.genome().clone())
+            .collect();
+        self.mutator.adapt(&GenerationSignal {
+            generation: signal.generation,
+            individuals: &genomes,
+            improved: signal.improved,
+        });
+    }
+
+    fn reset(&mut self) {
+        self.mutator.reset();
+    }
+}
+
+/// The `LinearSchedule` changes the mutation rate linearly from `start_rate`
+/// in the first generation to `end_rate` in generation `num_generations`.
+/// After that the mutation rate stays at `end_rate`.
+///
+/// Usually `start_rate` is higher than `end_rate` in order to explore the
+/// search space at the beginning and to exploit the best regions later on.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct LinearSchedule {
+    /// The mutation rate in the first generation.
+    start_rate: f64,
+    /// The mutation rate from generation `num_generations` on.
+    end_rate: f64,
+    /// The generation in which the mutation rate reaches `end_rate`.
+    num_generations: u64,
+}
+
+impl LinearSchedule {
+    /// Constructs a new `LinearSchedule`.
+    pub fn new(start_rate: f64, end_rate: f64, num_generations: u64) -> Self {
+        LinearSchedule {
+            start_rate,
+            end_rate,
+            num_generations,
+        }
+    }
+
+    /// Returns the mutation rate in the first generation.
+    pub fn start_rate(&self) -> f64 {
+        self.start_rate
+    }
+
+    /// Returns the mutation rate from generation `num_generations` on.
+    pub fn end_rate(&self) -> f64 {
+        self.end_rate
+    }
+
+    /// Returns the generation in which the mutation rate reaches `end_rate`.
+    pub fn num_generations(&self) -> u64 {
+        self.num_generations
+    }
+}
+
+impl<G> RateController<G> for LinearSchedule
+where
+    G: Genotype,
+{
+    fn next_rate(&mut self, _: f64, signal: &GenerationSignal<G>) -> f64 {
+        if signal.generation >= self.num_generations || self.num_generations <= 1 {
+            return self.end_rate;
+        }
+        let progress =
+            signal.generation.saturating_sub(1) as f64 / (self.num_generations - 1) as f64;
+        self.start_rate + (self.end_rate - self.start_rate) * progress
+    }
+}
+
+/// The `StagnationController` multiplies the mutation rate by `factor` in
+/// each generation in which the best fitness does not improve and divides it
+/// by `factor` in each generation in which the best fitness improves. The
+/// mutation rate is kept between `min_rate` and `max_rate`.
+///
+/// The `factor` should be greater than 1.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct StagnationController {
+    /// The lowest mutation rate.
+    min_rate: f64,
+    /// The highest mutation rate.
+    max_rate: f64,
+    /// The factor by which the mutation rate is changed.
+    factor: f64,
+}
+
+impl StagnationController {
+    /// Constructs a new `StagnationController`.
+    pub fn new(min_rate: f64, max_rate: f64, factor: f64) -> Self {
+        StagnationController {
+            min_rate,
+            max_rate,
+            factor,
+        }
+    }
+
+    /// Returns the lowest mutation rate.
+    pub fn min_rate(&self) -> f64 {
+        self.min_rate
+    }
+
+    /// Returns the highest mutation rate.
+    pub fn max_rate(&self) -> f64 {
+        self.max_rate
+    }
+
+    /// Returns the factor by which the mutation rate is changed.
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+}
+
+impl<G> RateController<G> for StagnationController
+where
+    G: Genotype,
+{
+    fn next_rate(&mut self, rate: f64, signal: &GenerationSignal<G>) -> f64 {
+        let rate = if signal.improved {
+            rate / self.factor
+        } else {
+            rate * self.factor
+        };
+        rate.max(self.min_rate).min(self.max_rate)
+    }
+}
+
+/// The `DiversityController` multiplies the mutation rate by `factor` in
+/// each generation in which the diversity of the population is below
+/// `target_diversity` and divides it by `factor` otherwise. The mutation
+/// rate is kept between `min_rate` and `max_rate`.
+///
+/// The diversity of the population is measured by the given
+/// `termination::convergence::DiversityMetric`. The `factor` should be
+/// greater than 1.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct DiversityController<D> {
+    /// The `DiversityMetric` used to measure the diversity of the population.
+    metric: D,
+    /// The diversity below which the mutation rate is raised.
+    target_diversity: f64,
+    /// The lowest mutation rate.
+    min_rate: f64,
+    /// The highest mutation rate.
+    max_rate: f64,
+    /// The factor by which the mutation rate is changed.
+    factor: f64,
+}
+
+impl<D> DiversityController<D> {
+    /// Constructs a new `DiversityController`.
+    pub fn new(
+        metric: D,
+        target_diversity: f64,
+        min_rate: f64,
+        max_rate: f64,
+        factor: f64,
+    ) -> Self {
+        DiversityController {
+            metric,
+            target_diversity,
+            min_rate,
+            max_rate,
+            factor,
+        }
+    }
+
+    /// Returns the `DiversityMetric` used to measure the diversity of the
+    /// population.
+    pub fn metric(&self) -> &D {
+        &self.metric
+    }
+
+    /// Returns the diversity below which the mutation rate is raised.
+    pub fn target_diversity(&self) -> f64 {
+        self.target_diversity
+    }
+
+    /// Returns the lowest mutation rate.
+    pub fn min_rate(&self) -> f64 {
+        self.min_rate
+    }
+
+    /// Returns the highest mutation rate.
+    pub fn max_rate(&self) -> f64 {
+        self.max_rate
+    }
+
+    /// Returns the factor by which the mutation rate is changed.
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+}
+
+impl<G, D> RateController<G> for DiversityController<D>
+where
+    G: Genotype,
+    D: DiversityMetric<G> + Clone + Debug,
+{
+    fn next_rate(&mut self, rate: f64, signal: &GenerationSignal<G>) -> f64 {
+        let rate = if self.metric.diversity(signal.individuals) < self.target_diversity {
+            rate * self.factor
+        } else {
+            rate / self.factor
+        };
+        rate.max(self.min_rate).min(self.max_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mutation::binary::BitFlipMutator, random::get_rng,
+        termination::convergence::HammingDistance,
+    };
+    use galvanic_assert::matchers::*;
+
+    fn signal(
+        generation: u64,
+        individuals: &[Vec<bool>],
+        improved: bool,
+    ) -> GenerationSignal<'_, Vec<bool>> {
+        GenerationSignal {
+            generation,
+            individuals,
+            improved,
+        }
+    }
+
+    #[test]
+    fn stagnation_controller_raises_rate_during_stagnation() {
+        let mut mutator = AdaptiveRateMutator::new(
+            BitFlipMutator::new(0.01),
+            StagnationController::new(0.001, 0.2, 2.),
+        );
+        let individuals = vec![vec![true, false]];
+
+        mutator.adapt(&signal(1, &individuals, true));
+        let mut rates = vec![mutator.mutation_rate()];
+        // the best fitness stagnates for 5 generations
+        for generation in 2..7 {
+            mutator.adapt(&signal(generation, &individuals, false));
+            rates.push(mutator.mutation_rate());
+        }
+
+        expect_that!(&rates, eq(vec![0.005, 0.01, 0.02, 0.04, 0.08, 0.16]));
+        expect_that!(&mutator.mutator().mutation_rate(), eq(0.16));
+        // the rate does not exceed the maximum rate
+        mutator.adapt(&signal(7, &individuals, false));
+        expect_that!(&mutator.mutation_rate(), eq(0.2));
+        // and decreases again when the best fitness improves
+        mutator.adapt(&signal(8, &individuals, true));
+        expect_that!(&mutator.mutation_rate(), eq(0.1));
+    }
+
+    #[test]
+    fn reset_restores_the_base_rate() {
+        let mut mutator = AdaptiveRateMutator::new(
+            BitFlipMutator::new(0.01),
+            StagnationController::new(0.001, 0.2, 2.),
+        );
+        let individuals = vec![vec![true, false]];
+        for generation in 1..5 {
+            mutator.adapt(&signal(generation, &individuals, false));
+        }
+
+        MutationOp::<Vec<bool>>::reset(&mut mutator);
+
+        expect_that!(&mutator.base_rate(), eq(0.01));
+        expect_that!(&mutator.mutation_rate(), eq(0.01));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn stagnation_controller_can_be_serialized() {
+        let controller = StagnationController::new(0.001, 0.2, 2.);
+
+        let serialized = serde_json::to_string(&controller).unwrap();
+
+        expect_that!(
+            &serde_json::from_str::<StagnationController>(&serialized).unwrap(),
+            eq(controller)
+        );
+    }
+
+    #[test]
+    fn linear_schedule_changes_rate_from_start_to_end_rate() {
+        let mut mutator =
+            AdaptiveRateMutator::new(BitFlipMutator::new(0.5), LinearSchedule::new(0.1, 0.02, 5));
+        let individuals = vec![vec![true]];
+
+        let rates: Vec<f64> = (1..8)
+            .map(|generation| {
+                mutator.adapt(&signal(generation, &individuals, true));
+                mutator.mutation_rate()
+            })
+            .collect();
+
+        let expected = [0.1, 0.08, 0.06, 0.04, 0.02, 0.02, 0.02];
+        for (rate, expected) in rates.iter().zip(expected.iter()) {
+            expect_that!(&(rate - expected).abs(), less_than(1e-12));
+        }
+    }
+
+    #[test]
+    fn diversity_controller_raises_rate_when_diversity_is_low() {
+        let mut mutator = AdaptiveRateMutator::new(
+            BitFlipMutator::new(0.04),
+            DiversityController::new(HammingDistance, 1., 0.01, 0.5, 2.),
+        );
+        let converged = vec![vec![true, true, true, true]; 4];
+        let diverse = vec![
+            vec![true, true, true, true],
+            vec![false, false, false, false],
+            vec![true, false, true, false],
+            vec![false, true, false, true],
+        ];
+
+        mutator.adapt(&signal(1, &converged, false));
+        expect_that!(&mutator.mutation_rate(), eq(0.08));
+        mutator.adapt(&signal(2, &diverse, false));
+        expect_that!(&mutator.mutation_rate(), eq(0.04));
+    }
+
+    #[test]
+    fn self_adaptive_mutator_mutates_the_genome_and_keeps_the_number_of_cut_points() {
+        let mutator = SelfAdaptiveMutator::new(BitFlipMutator::new(1.0));
+        let genome = SelfAdaptive::new(vec![false; 20], 3);
+
+        let mutated = mutator.mutate(genome, &mut get_rng([42; 32]));
+
+        expect_that!(&mutated.genome().contains(&true), is(eq(true)));
+        expect_that!(&mutated.num_cut_points(), eq(3));
+    }
+}
@@ -0,0 +1,180 @@
+//! The `binary` module provides `operator::MutationOp`s for binary encoded
+//! `genetic::Genotype`s.
+//!
+//! The provided `MutationOp` implementations are:
+//! * `BitFlipMutator` for `Vec<bool>`, `genotype::BinaryGenome` and
+//!   `fixedbitset::FixedBitSet`
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genotype::BinaryGenome,
+    operator::{validate_ratio, GeneticOperator, MutationOp, MutationRate},
+    random::{random_probability, Rng},
+};
+
+/// The `BitFlipMutator` mutates binary encoded genomes by flipping each bit
+/// independently with a probability of `mutation_rate`.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitFlipMutator {
+    mutation_rate: f64,
+}
+
+impl BitFlipMutator {
+    /// Constructs a new `BitFlipMutator` that flips each bit with the given
+    /// probability.
+    pub fn new(mutation_rate: f64) -> Self {
+        BitFlipMutator { mutation_rate }
+    }
+
+    /// Constructs a new `BitFlipMutator` that flips on average
+    /// `expected_flips` bits of a genome of the given length.
+    pub fn with_expected_flips(expected_flips: f64, genome_length: usize) -> Self {
+        BitFlipMutator::new(expected_flips / genome_length as f64)
+    }
+
+    pub fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    pub fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl GeneticOperator for BitFlipMutator {
+    fn name() -> String {
+        "Bit-Flip-Mutation".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl MutationRate for BitFlipMutator {
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl MutationOp<Vec<bool>> for BitFlipMutator {
+    fn mutate<R>(&self, genome: Vec<bool>, rng: &mut R) -> Vec<bool>
+    where
+        R: Rng + Sized,
+    {
+        genome
+            .into_iter()
+            .map(|bit| {
+                if random_probability(rng) < self.mutation_rate {
+                    !bit
+                } else {
+                    bit
+                }
+            })
+            .collect()
+    }
+}
+
+impl<const N: usize> MutationOp<[bool; N]> for BitFlipMutator {
+    fn mutate<R>(&self, genome: [bool; N], rng: &mut R) -> [bool; N]
+    where
+        R: Rng + Sized,
+    {
+        let mut mutated = genome;
+        for bit in mutated.iter_mut() {
+            if random_probability(rng) < self.mutation_rate {
+                *bit = !*bit;
+            }
+        }
+        mutated
+    }
+}
+
+impl MutationOp<BinaryGenome> for BitFlipMutator {
+    fn mutate<R>(&self, genome: BinaryGenome, rng: &mut R) -> BinaryGenome
+    where
+        R: Rng + Sized,
+    {
+        let mut mutated = genome;
+        for word in 0..mutated.num_words() {
+            // collect the bits to be flipped and flip them all at once
+            let mut flips = 0;
+            for bit in 0..mutated.bits_in_word(word) {
+                if random_probability(rng) < self.mutation_rate {
+                    flips |= 1 << bit;
+                }
+            }
+            if flips != 0 {
+                let value = mutated.word(word) ^ flips;
+                mutated.set_word(word, value);
+            }
+        }
+        mutated
+    }
+}
+
+#[cfg(feature = "fixedbitset")]
+mod fixedbitset_bit_flip_mutator {
+    use super::BitFlipMutator;
+    use crate::{operator::MutationOp, random::random_probability};
+    use fixedbitset::FixedBitSet;
+    use rand::Rng;
+
+    impl MutationOp<FixedBitSet> for BitFlipMutator {
+        fn mutate<R>(&self, genome: FixedBitSet, rng: &mut R) -> FixedBitSet
+        where
+            R: Rng + Sized,
+        {
+            let mut mutated = genome;
+            for bit in 0..mutated.len() {
+                if random_probability(rng) < self.mutation_rate {
+                    mutated.toggle(bit);
+                }
+            }
+            mutated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn bit_flip_mutator_with_rate_1_flips_every_bit() {
+        let mutator = BitFlipMutator::new(1.0);
+        let mut rng = get_rng([42; 32]);
+        let genome = vec![true, false, false, true, true, false, true, false];
+
+        let mutated = mutator.mutate(genome.clone(), &mut rng);
+
+        let flipped: Vec<bool> = genome.iter().map(|bit| !bit).collect();
+        expect_that!(&mutated, eq(flipped));
+    }
+
+    #[test]
+    fn bit_flip_mutator_with_rate_0_does_not_change_the_genome() {
+        let mutator = BitFlipMutator::new(0.0);
+        let mut rng = get_rng([42; 32]);
+        let genome = vec![true, false, false, true, true, false, true, false];
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&mutated, eq(genome.clone()));
+        }
+    }
+
+    #[test]
+    fn bit_flip_mutator_with_expected_flips_sets_rate_relative_to_genome_length() {
+        let mutator = BitFlipMutator::with_expected_flips(2., 40);
+
+        expect_that!(&(mutator.mutation_rate() - 0.05).abs(), less_than(1e-12));
+    }
+}
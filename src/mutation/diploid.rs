@@ -0,0 +1,113 @@
+//! The `diploid` module provides an `operator::MutationOp` for
+//! `genotype::Diploid` genomes.
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genetic::Genotype,
+    genotype::Diploid,
+    operator::{GenerationSignal, GeneticOperator, MutationOp, MutationRate},
+    random::Rng,
+};
+
+/// The `DiploidMutator` is a `MutationOp` for `Diploid` genomes that mutates
+/// each of the two homologous genomes with another `MutationOp`.
+///
+/// The first homologous genome is mutated before the second one. The
+/// wrapped `MutationOp` is adapted with a `GenerationSignal` whose
+/// individuals are the homologous genomes of the population, the first and
+/// the second homologous genome of each individual one after the other.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiploidMutator<M> {
+    /// The `MutationOp` that mutates the homologous genomes.
+    mutator: M,
+}
+
+impl<M> DiploidMutator<M> {
+    /// Constructs a new `DiploidMutator` that mutates the homologous genomes
+    /// with the given `MutationOp`.
+    pub fn new(mutator: M) -> Self {
+        DiploidMutator { mutator }
+    }
+
+    /// Returns the `MutationOp` that mutates the homologous genomes.
+    pub fn mutator(&self) -> &M {
+        &self.mutator
+    }
+}
+
+impl<M> MutationRate for DiploidMutator<M>
+where
+    M: MutationRate,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutator.mutation_rate()
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutator.set_mutation_rate(value);
+    }
+}
+
+impl<M> GeneticOperator for DiploidMutator<M>
+where
+    M: GeneticOperator,
+{
+    fn name() -> String {
+        format!("Diploid-{}", M::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        self.mutator.validate()
+    }
+}
+
+impl<G, M> MutationOp<Diploid<G>> for DiploidMutator<M>
+where
+    G: Genotype,
+    M: MutationOp<G>,
+{
+    fn mutate<R>(&self, genome: Diploid<G>, rng: &mut R) -> Diploid<G>
+    where
+        R: Rng + Sized,
+    {
+        let (first, second) = genome.into_inner();
+        let first = self.mutator.mutate(first, rng);
+        let second = self.mutator.mutate(second, rng);
+        Diploid::new(first, second)
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<Diploid<G>>) {
+        let homologous_genomes: Vec<G> = signal
+            .individuals
+            .iter()
+            .flat_map(|diploid| vec![diploid.first().clone(), diploid.second().clone()])
+            .collect();
+        self.mutator.adapt(&GenerationSignal {
+            generation: signal.generation,
+            individuals: &homologous_genomes,
+            improved: signal.improved,
+        });
+    }
+
+    fn reset(&mut self) {
+        self.mutator.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mutation::binary::BitFlipMutator, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn both_homologous_genomes_are_mutated() {
+        let mutator = DiploidMutator::new(BitFlipMutator::new(1.0));
+        let diploid = Diploid::new(vec![false; 20], vec![false; 20]);
+
+        let mutated = mutator.mutate(diploid, &mut get_rng([42; 32]));
+
+        expect_that!(&mutated.first().contains(&true), is(eq(true)));
+        expect_that!(&mutated.second().contains(&true), is(eq(true)));
+    }
+}
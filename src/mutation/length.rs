@@ -0,0 +1,236 @@
+//! The `length` module provides `operator::MutationOp`s that change the
+//! length of variable-length genomes.
+//!
+//! The provided `operator::MutationOp`s are:
+//! * `InsertDeleteMutator` for `Vec` of any type that implements
+//!   `mutation::value::RandomValueMutation`.
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    mutation::value::RandomValueMutation,
+    operator::{validate_count, validate_ratio, GeneticOperator, MutationOp},
+    random::{random_index, random_index_from_range, Rng},
+};
+use std::fmt::Debug;
+
+/// The `InsertDeleteMutator` grows and shrinks genomes by inserting random
+/// genes and deleting genes at random positions.
+///
+/// Each gene is deleted with the probability `deletion_rate`, but no more
+/// genes than the genome has beyond `min_length`. Then a random gene between
+/// `min_value` and `max_value` (both inclusive) is inserted into each of the
+/// `len + 1` gaps between and around the remaining genes with the probability
+/// `insertion_rate`, but only until the genome has `max_length` genes. A
+/// genome that is already longer than `max_length` does not grow any
+/// further. As for the `value::RandomValueMutator` the rates determine the
+/// expected number of mutations, which are applied at random positions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsertDeleteMutator<V> {
+    /// The probability of an insertion per gap.
+    insertion_rate: f64,
+    /// The probability of a deletion per gene.
+    deletion_rate: f64,
+    /// The minimum number of genes of a mutated genome.
+    min_length: usize,
+    /// The maximum number of genes of a mutated genome.
+    max_length: usize,
+    /// The lower bound of inserted genes.
+    min_value: V,
+    /// The upper bound of inserted genes.
+    max_value: V,
+}
+
+impl<V> InsertDeleteMutator<V>
+where
+    V: PartialOrd + Debug,
+{
+    /// Constructs a new instance of the `InsertDeleteMutator` which keeps the
+    /// length of the genomes between `min_length` and `max_length` and
+    /// inserts genes between `min_value` and `max_value` (both inclusive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_length` is greater than `max_length`, if `max_length`
+    /// is 0 or if `min_value` is greater than `max_value`.
+    pub fn new(
+        insertion_rate: f64,
+        deletion_rate: f64,
+        min_length: usize,
+        max_length: usize,
+        min_value: V,
+        max_value: V,
+    ) -> Self {
+        assert!(max_length > 0, "the maximum length must be greater than 0");
+        assert!(
+            min_length <= max_length,
+            "min_length {} must not be greater than max_length {}",
+            min_length,
+            max_length
+        );
+        assert!(
+            min_value <= max_value,
+            "min_value {:?} must not be greater than max_value {:?}",
+            min_value,
+            max_value
+        );
+        InsertDeleteMutator {
+            insertion_rate,
+            deletion_rate,
+            min_length,
+            max_length,
+            min_value,
+            max_value,
+        }
+    }
+}
+
+impl<V> InsertDeleteMutator<V> {
+    /// Returns the probability of an insertion per gap between the genes.
+    pub fn insertion_rate(&self) -> f64 {
+        self.insertion_rate
+    }
+
+    /// Sets the probability of an insertion per gap between the genes. The
+    /// value must be between 0 and 1 (inclusive).
+    pub fn set_insertion_rate(&mut self, value: f64) {
+        self.insertion_rate = value;
+    }
+
+    /// Returns the probability of a deletion per gene.
+    pub fn deletion_rate(&self) -> f64 {
+        self.deletion_rate
+    }
+
+    /// Sets the probability of a deletion per gene. The value must be between
+    /// 0 and 1 (inclusive).
+    pub fn set_deletion_rate(&mut self, value: f64) {
+        self.deletion_rate = value;
+    }
+
+    /// Returns the minimum number of genes of a mutated genome.
+    pub fn min_length(&self) -> usize {
+        self.min_length
+    }
+
+    /// Returns the maximum number of genes of a mutated genome.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Returns the lower bound of inserted genes.
+    pub fn min_value(&self) -> &V {
+        &self.min_value
+    }
+
+    /// Returns the upper bound of inserted genes.
+    pub fn max_value(&self) -> &V {
+        &self.max_value
+    }
+}
+
+impl<V> GeneticOperator for InsertDeleteMutator<V>
+where
+    V: Clone,
+{
+    fn name() -> String {
+        "Insert-Delete-Mutator".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("insertion rate", self.insertion_rate)?;
+        validate_ratio::<Self>("deletion rate", self.deletion_rate)?;
+        validate_count::<Self>("maximum length", self.max_length)
+    }
+}
+
+impl<V> MutationOp<Vec<V>> for InsertDeleteMutator<V>
+where
+    V: RandomValueMutation + Clone + Debug + PartialEq + Send + Sync,
+{
+    fn mutate<R>(&self, genome: Vec<V>, rng: &mut R) -> Vec<V>
+    where
+        R: Rng + Sized,
+    {
+        let mut mutated = genome;
+        let num_deletions = num_mutations(mutated.len(), self.deletion_rate, rng)
+            .min(mutated.len().saturating_sub(self.min_length));
+        for _ in 0..num_deletions {
+            let index = random_index(rng, mutated.len());
+            mutated.remove(index);
+        }
+        let num_insertions = num_mutations(mutated.len() + 1, self.insertion_rate, rng)
+            .min(self.max_length.saturating_sub(mutated.len()));
+        for _ in 0..num_insertions {
+            let index = random_index_from_range(rng, 0, mutated.len() + 1);
+            let gene = RandomValueMutation::random_mutated(
+                self.min_value.clone(),
+                &self.min_value,
+                &self.max_value,
+                rng,
+            );
+            mutated.insert(index, gene);
+        }
+        mutated
+    }
+}
+
+/// Returns the number of mutations of `num_positions` positions that are
+/// each mutated with the given probability, rounded stochastically.
+fn num_mutations<R>(num_positions: usize, rate: f64, rng: &mut R) -> usize
+where
+    R: Rng + Sized,
+{
+    if rate <= 0. {
+        return 0;
+    }
+    ((num_positions as f64 * rate) + rng.gen::<f64>()).floor() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn genomes_grow_until_the_maximum_length() {
+        let mutator = InsertDeleteMutator::new(0.5, 0., 0, 20, 1u8, 9u8);
+        let mut rng = get_rng([42; 32]);
+        let mut genome = vec![0u8; 3];
+
+        for _ in 0..100 {
+            genome = mutator.mutate(genome, &mut rng);
+            expect_that!(&genome.len(), leq(20));
+        }
+
+        expect_that!(&genome.len(), eq(20));
+        // the original genes are kept and the inserted ones are in range
+        expect_that!(&genome.iter().filter(|gene| **gene == 0).count(), eq(3));
+        expect_that!(&genome.iter().all(|gene| *gene <= 9), is(eq(true)));
+    }
+
+    #[test]
+    fn genomes_shrink_until_the_minimum_length() {
+        let mutator = InsertDeleteMutator::new(0., 0.5, 2, 20, 1u8, 9u8);
+        let mut rng = get_rng([42; 32]);
+        let mut genome: Vec<u8> = (0..10).collect();
+
+        for _ in 0..100 {
+            genome = mutator.mutate(genome, &mut rng);
+            expect_that!(&genome.len(), geq(2));
+        }
+
+        expect_that!(&genome.len(), eq(2));
+        // the remaining genes keep their order
+        expect_that!(&(genome[0] < genome[1]), is(eq(true)));
+    }
+
+    #[test]
+    fn rates_of_0_do_not_change_the_genome() {
+        let mutator = InsertDeleteMutator::new(0., 0., 0, 20, 1u8, 9u8);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<u8> = (0..10).collect();
+
+        expect_that!(&mutator.mutate(genome.clone(), &mut rng), eq(genome));
+    }
+}
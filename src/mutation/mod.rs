@@ -1,7 +1,18 @@
 //! The `mutation` module provides `operator::MutationOp`s implementation
 //! of various mutation schemes for binary encoded, value encoded, permutation
-//! encoded and tree encoded `genetic::Genotype`s.
+//! encoded, tree encoded and variable-length `genetic::Genotype`s as well as
+//! for `genotype::Diploid` genomes.
+
+pub mod adaptive;
+
+pub mod binary;
+
+pub mod diploid;
+
+pub mod length;
 
 pub mod value;
 
 pub mod order;
+
+pub mod tree;
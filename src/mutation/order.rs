@@ -2,8 +2,9 @@
 //! `genetic::Genotype`s.
 
 use crate::{
-    operator::{GeneticOperator, MutationOp},
-    random::{random_cut_points, Rng},
+    ga::GeneticAlgorithmError,
+    operator::{validate_count, validate_ratio, GeneticOperator, MutationOp, MutationRate},
+    random::{random_cut_points, random_index, random_probability, Rng, SliceRandom},
 };
 use std::fmt::Debug;
 
@@ -31,6 +32,20 @@ impl GeneticOperator for InsertOrderMutator {
     fn name() -> String {
         "Order-Insert-Mutation".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl MutationRate for InsertOrderMutator {
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
 }
 
 impl<V> MutationOp<Vec<V>> for InsertOrderMutator
@@ -78,6 +93,20 @@ impl GeneticOperator for SwapOrderMutator {
     fn name() -> String {
         "Order-Swap-Mutation".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl MutationRate for SwapOrderMutator {
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
 }
 
 impl<V> MutationOp<Vec<V>> for SwapOrderMutator
@@ -99,3 +128,409 @@ where
         mutated
     }
 }
+
+/// The `SwapMutator` mutates a permutation encoded genome by swapping the
+/// values at two randomly chosen loci.
+///
+/// Each genome is mutated with a probability of `mutation_rate`. A mutated
+/// genome gets `num_swaps` swaps applied. In contrast to the
+/// `SwapOrderMutator`, where the number of swaps depends on the length of the
+/// genome, the `mutation_rate` of the `SwapMutator` is the probability that a
+/// genome is mutated at all.
+///
+/// As only values are swapped, the multiset of values of the genome is
+/// unchanged by the mutation.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapMutator {
+    mutation_rate: f64,
+    num_swaps: usize,
+}
+
+impl SwapMutator {
+    pub fn new(mutation_rate: f64, num_swaps: usize) -> Self {
+        SwapMutator {
+            mutation_rate,
+            num_swaps,
+        }
+    }
+
+    pub fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    pub fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+
+    pub fn num_swaps(&self) -> usize {
+        self.num_swaps
+    }
+
+    pub fn set_num_swaps(&mut self, value: usize) {
+        self.num_swaps = value;
+    }
+}
+
+impl GeneticOperator for SwapMutator {
+    fn name() -> String {
+        "Swap-Mutation".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)?;
+        validate_count::<Self>("number of swaps", self.num_swaps)
+    }
+}
+
+impl MutationRate for SwapMutator {
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl<V> MutationOp<Vec<V>> for SwapMutator
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn mutate<R>(&self, genome: Vec<V>, rng: &mut R) -> Vec<V>
+    where
+        R: Rng + Sized,
+    {
+        let genome_length = genome.len();
+        if genome_length < 2 || random_probability(rng) >= self.mutation_rate {
+            return genome;
+        }
+        let mut mutated = genome;
+        for _ in 0..self.num_swaps {
+            let locus1 = random_index(rng, genome_length);
+            // pick a second locus that is different from the first one
+            let locus2 = (locus1 + 1 + random_index(rng, genome_length - 1)) % genome_length;
+            mutated.swap(locus1, locus2);
+        }
+        mutated
+    }
+}
+
+/// The `InversionMutator` mutates a permutation encoded genome by reversing
+/// the order of the values within a randomly chosen contiguous slice of the
+/// genome.
+///
+/// Each genome is mutated with a probability of `mutation_rate`. For problems
+/// like the traveling salesman problem an inversion changes only two edges of
+/// a tour while keeping the rest of the tour intact.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InversionMutator {
+    mutation_rate: f64,
+}
+
+impl InversionMutator {
+    pub fn new(mutation_rate: f64) -> Self {
+        InversionMutator { mutation_rate }
+    }
+
+    pub fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    pub fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl GeneticOperator for InversionMutator {
+    fn name() -> String {
+        "Inversion-Mutation".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl MutationRate for InversionMutator {
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl<V> MutationOp<Vec<V>> for InversionMutator
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn mutate<R>(&self, genome: Vec<V>, rng: &mut R) -> Vec<V>
+    where
+        R: Rng + Sized,
+    {
+        if random_probability(rng) >= self.mutation_rate {
+            return genome;
+        }
+        let (start, end) = random_slice(rng, genome.len(), genome.len());
+        let mut mutated = genome;
+        // slices of length 0 or 1 are left unchanged by reversing
+        mutated[start..end].reverse();
+        mutated
+    }
+}
+
+/// The `ScrambleMutator` mutates a permutation encoded genome by shuffling
+/// the values within a randomly chosen contiguous range of the genome.
+///
+/// Each genome is mutated with a probability of `mutation_rate`. The length
+/// of the shuffled range can be limited by setting `max_range_length`. By
+/// default the range is not limited.
+///
+/// The scramble mutation is more disruptive than the swap and the inversion
+/// mutation, which can help to escape local optima.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrambleMutator {
+    mutation_rate: f64,
+    max_range_length: usize,
+}
+
+impl ScrambleMutator {
+    pub fn new(mutation_rate: f64) -> Self {
+        ScrambleMutator {
+            mutation_rate,
+            max_range_length: usize::MAX,
+        }
+    }
+
+    pub fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    pub fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+
+    pub fn max_range_length(&self) -> usize {
+        self.max_range_length
+    }
+
+    pub fn set_max_range_length(&mut self, value: usize) {
+        self.max_range_length = value;
+    }
+}
+
+impl GeneticOperator for ScrambleMutator {
+    fn name() -> String {
+        "Scramble-Mutation".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)?;
+        validate_count::<Self>("maximum range length", self.max_range_length)
+    }
+}
+
+impl MutationRate for ScrambleMutator {
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl<V> MutationOp<Vec<V>> for ScrambleMutator
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn mutate<R>(&self, genome: Vec<V>, rng: &mut R) -> Vec<V>
+    where
+        R: Rng + Sized,
+    {
+        if random_probability(rng) >= self.mutation_rate {
+            return genome;
+        }
+        let (start, end) = random_slice(rng, genome.len(), self.max_range_length);
+        let mut mutated = genome;
+        if end - start > 1 {
+            mutated[start..end].shuffle(rng);
+        }
+        mutated
+    }
+}
+
+/// Chooses the bounds `start..end` of a random slice of a genome of the given
+/// length. The length of the slice is at most `max_length`.
+fn random_slice<R>(rng: &mut R, genome_length: usize, max_length: usize) -> (usize, usize)
+where
+    R: Rng + Sized,
+{
+    let locus1 = random_index(rng, genome_length + 1);
+    let locus2 = random_index(rng, genome_length + 1);
+    let (start, end) = if locus1 < locus2 {
+        (locus1, locus2)
+    } else {
+        (locus2, locus1)
+    };
+    (start, end.min(start.saturating_add(max_length)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+    use proptest::prelude::*;
+
+    fn sorted<V: Clone + Ord>(genome: &[V]) -> Vec<V> {
+        let mut sorted = genome.to_vec();
+        sorted.sort();
+        sorted
+    }
+
+    #[test]
+    fn swap_mutator_keeps_the_values_of_the_genome() {
+        let mutator = SwapMutator::new(1.0, 3);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<usize> = vec![4, 2, 7, 1, 1, 9, 0, 3];
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&sorted(&mutated), eq(sorted(&genome)));
+        }
+    }
+
+    #[test]
+    fn swap_mutator_with_rate_1_changes_the_genome() {
+        let mutator = SwapMutator::new(1.0, 1);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<usize> = (0..10).collect();
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&mutated, not(eq(genome.clone())));
+        }
+    }
+
+    #[test]
+    fn swap_mutator_with_rate_0_does_not_change_the_genome() {
+        let mutator = SwapMutator::new(0.0, 5);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<usize> = (0..10).collect();
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&mutated, eq(genome.clone()));
+        }
+    }
+
+    #[test]
+    fn swap_mutator_does_not_panic_on_short_genomes() {
+        let mutator = SwapMutator::new(1.0, 1);
+        let mut rng = get_rng([42; 32]);
+
+        expect_that!(&mutator.mutate(Vec::<usize>::new(), &mut rng), eq(vec![]));
+        expect_that!(&mutator.mutate(vec![1], &mut rng), eq(vec![1]));
+        expect_that!(&mutator.mutate(vec![1, 2], &mut rng), eq(vec![2, 1]));
+    }
+
+    #[test]
+    fn inversion_mutator_keeps_the_values_of_the_genome() {
+        let mutator = InversionMutator::new(1.0);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<usize> = vec![4, 2, 7, 1, 1, 9, 0, 3];
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&sorted(&mutated), eq(sorted(&genome)));
+        }
+    }
+
+    #[test]
+    fn inversion_mutator_reverses_a_contiguous_slice() {
+        let mutator = InversionMutator::new(1.0);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<usize> = (0..10).collect();
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            let start = (0..10).find(|i| mutated[*i] != *i).unwrap_or(0);
+            let end = (0..10).rev().find(|i| mutated[*i] != *i).unwrap_or(0);
+            for (i, value) in mutated.iter().enumerate().take(end + 1).skip(start) {
+                expect_that!(value, eq(start + end - i));
+            }
+        }
+    }
+
+    #[test]
+    fn inversion_mutator_with_rate_0_does_not_change_the_genome() {
+        let mutator = InversionMutator::new(0.0);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<usize> = (0..10).collect();
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&mutated, eq(genome.clone()));
+        }
+    }
+
+    #[test]
+    fn inversion_mutator_does_not_panic_on_short_genomes() {
+        let mutator = InversionMutator::new(1.0);
+        let mut rng = get_rng([42; 32]);
+
+        expect_that!(&mutator.mutate(Vec::<usize>::new(), &mut rng), eq(vec![]));
+        expect_that!(&mutator.mutate(vec![1], &mut rng), eq(vec![1]));
+    }
+
+    #[test]
+    fn scramble_mutator_shuffles_only_within_the_max_range_length() {
+        let mut mutator = ScrambleMutator::new(1.0);
+        mutator.set_max_range_length(3);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<usize> = (0..20).collect();
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            let changed: Vec<usize> = (0..20).filter(|i| mutated[*i] != *i).collect();
+            if let (Some(first), Some(last)) = (changed.first(), changed.last()) {
+                expect_that!(&(last - first), less_than(3));
+            }
+        }
+    }
+
+    #[test]
+    fn scramble_mutator_with_rate_0_does_not_change_the_genome() {
+        let mutator = ScrambleMutator::new(0.0);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<usize> = (0..10).collect();
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&mutated, eq(genome.clone()));
+        }
+    }
+
+    proptest! {
+
+        #[test]
+        fn scramble_mutator_keeps_the_values_of_the_genome(
+            genome in prop::collection::vec(0..100usize, 0..50),
+            max_range_length in 0..60usize,
+            seed in any::<[u8; 32]>(),
+        ) {
+            let mut mutator = ScrambleMutator::new(1.0);
+            mutator.set_max_range_length(max_range_length);
+            let mut rng = get_rng(seed);
+
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+
+            prop_assert_eq!(sorted(&mutated), sorted(&genome));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! The `tree` module provides an `operator::MutationOp` for
+//! `genotype::tree::ExpressionTree` genomes.
+//!
+//! The provided `operator::MutationOp`s are:
+//! * `SubtreeMutator` for `ExpressionTree`s of any type.
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genotype::tree::{ExpressionTree, GrowthMethod, PrimitiveSet},
+    operator::{validate_ratio, GeneticOperator, MutationOp, MutationRate},
+    random::{random_index, random_probability, Rng},
+};
+use std::fmt::Debug;
+
+/// The `SubtreeMutator` replaces a random subtree of a genome by a random
+/// tree grown from a `PrimitiveSet`.
+///
+/// Each genome is mutated with the probability `mutation_rate`. The new
+/// subtree is grown by `GrowthMethod::Grow` with a depth of at most
+/// `max_depth` minus the level of the mutated node, so that the mutated
+/// genome stays within `max_depth`. A node below `max_depth`, which only
+/// exists in a genome that is already deeper than `max_depth`, is replaced
+/// by a terminal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubtreeMutator<T> {
+    /// The probability of a genome to be mutated.
+    mutation_rate: f64,
+    /// The primitives the new subtrees are grown from.
+    primitives: PrimitiveSet<T>,
+    /// The maximum depth of a mutated genome.
+    max_depth: usize,
+}
+
+impl<T> SubtreeMutator<T> {
+    /// Constructs a new instance of the `SubtreeMutator` which grows the new
+    /// subtrees from the given primitives and keeps the mutated genomes
+    /// within `max_depth`.
+    pub fn new(mutation_rate: f64, primitives: PrimitiveSet<T>, max_depth: usize) -> Self {
+        SubtreeMutator {
+            mutation_rate,
+            primitives,
+            max_depth,
+        }
+    }
+
+    /// Returns the primitives the new subtrees are grown from.
+    pub fn primitives(&self) -> &PrimitiveSet<T> {
+        &self.primitives
+    }
+
+    /// Returns the maximum depth of a mutated genome.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Sets the maximum depth of a mutated genome.
+    pub fn set_max_depth(&mut self, value: usize) {
+        self.max_depth = value;
+    }
+}
+
+impl<T> MutationRate for SubtreeMutator<T> {
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl<T> GeneticOperator for SubtreeMutator<T>
+where
+    T: Clone,
+{
+    fn name() -> String {
+        "Subtree-Mutator".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl<T> MutationOp<ExpressionTree<T>> for SubtreeMutator<T>
+where
+    T: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn mutate<R>(&self, genome: ExpressionTree<T>, rng: &mut R) -> ExpressionTree<T>
+    where
+        R: Rng + Sized,
+    {
+        if random_probability(rng) >= self.mutation_rate {
+            return genome;
+        }
+        let mut mutated = genome;
+        let index = random_index(rng, mutated.size());
+        let level = mutated
+            .level_of(index)
+            .expect("the mutated node is a node of the genome");
+        let subtree = self.primitives.grow_tree(
+            GrowthMethod::Grow,
+            self.max_depth.saturating_sub(level),
+            rng,
+        );
+        mutated.replace_subtree(index, subtree);
+        mutated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+
+    fn primitives() -> PrimitiveSet<char> {
+        PrimitiveSet::new(vec![('+', 2), ('*', 2)], vec!['x', '1'])
+    }
+
+    #[test]
+    fn mutated_genomes_stay_within_the_maximum_depth() {
+        let mutator = SubtreeMutator::new(1.0, primitives(), 4);
+        let mut rng = get_rng([42; 32]);
+        let mut genome = ExpressionTree::Leaf('x');
+        let mut changed = false;
+
+        for _ in 0..200 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&mutated.depth(), leq(4));
+            changed |= mutated != genome;
+            genome = mutated;
+        }
+
+        expect_that!(&changed, is(eq(true)));
+    }
+
+    #[test]
+    fn a_mutation_rate_of_0_does_not_change_the_genome() {
+        let mutator = SubtreeMutator::new(0.0, primitives(), 4);
+        let genome = ExpressionTree::Node(
+            '+',
+            vec![ExpressionTree::Leaf('x'), ExpressionTree::Leaf('1')],
+        );
+
+        expect_that!(
+            &mutator.mutate(genome.clone(), &mut get_rng([42; 32])),
+            eq(genome)
+        );
+    }
+}
@@ -1,17 +1,28 @@
 use crate::{
+    ga::GeneticAlgorithmError,
     genetic::Genotype,
-    operator::{GeneticOperator, MutationOp},
-    random::{random_index, Rng},
+    operator::{validate_ratio, GenerationSignal, GeneticOperator, MutationOp, MutationRate},
+    random::{random_index, random_probability, random_standard_normal, Rng},
 };
 use rand::seq::SliceRandom;
 use std::fmt::Debug;
 
+/// The `RandomValueMutator` replaces genes with random values between
+/// `min_value` and `max_value`. Both bounds are inclusive, e.g. a
+/// `RandomValueMutator` with the bounds `32u8` and `126u8` generates all
+/// printable ASCII characters including `'~'` (126).
+///
+/// By default all loci are mutated with the same `mutation_rate`. A
+/// `RandomValueMutator` constructed by `with_locus_rates` mutates each locus
+/// `i` with its own probability `locus_rates[i]` instead, e.g. to protect
+/// structurally important genes with a rate of 0.
 #[derive(Clone, Debug, PartialEq)]
 pub struct RandomValueMutator<G>
 where
     G: Genotype + RandomGenomeMutation,
 {
     mutation_rate: f64,
+    locus_rates: Option<Vec<f64>>,
     min_value: <G as Genotype>::Dna,
     max_value: <G as Genotype>::Dna,
 }
@@ -20,17 +31,78 @@ impl<G> RandomValueMutator<G>
 where
     G: Genotype + RandomGenomeMutation,
 {
+    /// Constructs a new instance of the `RandomValueMutator` which generates
+    /// values between `min_value` and `max_value` (both inclusive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_value` is greater than `max_value`.
     pub fn new(
         mutation_rate: f64,
         min_value: <G as Genotype>::Dna,
         max_value: <G as Genotype>::Dna,
-    ) -> Self {
+    ) -> Self
+    where
+        <G as Genotype>::Dna: PartialOrd,
+    {
+        assert!(
+            min_value <= max_value,
+            "min_value {:?} must not be greater than max_value {:?}",
+            min_value,
+            max_value
+        );
         RandomValueMutator {
             mutation_rate,
+            locus_rates: None,
             min_value,
             max_value,
         }
     }
+
+    /// Constructs a new instance of the `RandomValueMutator` which mutates
+    /// the locus `i` of a genome with the probability `locus_rates[i]` and
+    /// generates values between `min_value` and `max_value` (both
+    /// inclusive).
+    ///
+    /// The number of rates must match the length of the mutated genomes,
+    /// otherwise mutating a genome panics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_value` is greater than `max_value`, if there are no
+    /// rates or if any rate is not between 0 and 1 (inclusive).
+    pub fn with_locus_rates(
+        locus_rates: Vec<f64>,
+        min_value: <G as Genotype>::Dna,
+        max_value: <G as Genotype>::Dna,
+    ) -> Self
+    where
+        <G as Genotype>::Dna: PartialOrd,
+    {
+        assert!(
+            !locus_rates.is_empty(),
+            "at least one mutation rate is required"
+        );
+        assert!(
+            locus_rates.iter().all(|rate| (0. ..=1.).contains(rate)),
+            "the mutation rates must be between 0 and 1"
+        );
+        let mut mutator = RandomValueMutator::new(0., min_value, max_value);
+        mutator.mutation_rate = mean(&locus_rates);
+        mutator.locus_rates = Some(locus_rates);
+        mutator
+    }
+
+    /// Returns the mutation rate of each locus, if the loci are mutated with
+    /// their own rates.
+    pub fn locus_rates(&self) -> Option<&[f64]> {
+        self.locus_rates.as_deref()
+    }
+}
+
+/// Returns the arithmetic mean of the given rates.
+fn mean(rates: &[f64]) -> f64 {
+    rates.iter().sum::<f64>() / rates.len() as f64
 }
 
 impl<G> GeneticOperator for RandomValueMutator<G>
@@ -40,6 +112,38 @@ where
     fn name() -> String {
         "Random-Value-Mutator".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+/// If the loci are mutated with their own rates, the mutation rate is the
+/// mean of the locus rates. Setting the mutation rate scales all locus rates
+/// proportionally, so that loci with a rate of 0 stay protected. The scaled
+/// rates are capped at 1. If all locus rates are 0 they are not changed.
+impl<G> MutationRate for RandomValueMutator<G>
+where
+    G: Genotype + RandomGenomeMutation,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        match self.locus_rates {
+            Some(ref mut locus_rates) => {
+                if self.mutation_rate > 0. {
+                    let factor = value / self.mutation_rate;
+                    for rate in locus_rates.iter_mut() {
+                        *rate = (*rate * factor).min(1.);
+                    }
+                    self.mutation_rate = mean(locus_rates);
+                }
+            },
+            None => self.mutation_rate = value,
+        }
+    }
 }
 
 impl<G> MutationOp<G> for RandomValueMutator<G>
@@ -50,13 +154,22 @@ where
     where
         R: Rng + Sized,
     {
-        RandomGenomeMutation::mutate_genome(
-            genome,
-            self.mutation_rate,
-            &self.min_value,
-            &self.max_value,
-            rng,
-        )
+        match &self.locus_rates {
+            Some(locus_rates) => RandomGenomeMutation::mutate_loci(
+                genome,
+                locus_rates,
+                &self.min_value,
+                &self.max_value,
+                rng,
+            ),
+            None => RandomGenomeMutation::mutate_genome(
+                genome,
+                self.mutation_rate,
+                &self.min_value,
+                &self.max_value,
+                rng,
+            ),
+        }
     }
 }
 
@@ -72,6 +185,51 @@ pub trait RandomGenomeMutation: Genotype {
     ) -> Self
     where
         R: Rng + Sized;
+
+    /// Mutates the locus `i` of the genome with the probability
+    /// `locus_rates[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of rates does not match the length of the genome.
+    fn mutate_loci<R>(
+        genome: Self,
+        locus_rates: &[f64],
+        min_value: &<Self as Genotype>::Dna,
+        max_value: &<Self as Genotype>::Dna,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized;
+}
+
+/// Asserts that there is one mutation rate for each locus of the genome.
+fn assert_num_locus_rates(num_rates: usize, genome_length: usize) {
+    assert!(
+        num_rates == genome_length,
+        "the number of mutation rates {} does not match the genome length {}",
+        num_rates,
+        genome_length
+    );
+}
+
+/// Mutates the value at index `i` with the probability `locus_rates[i]`.
+fn mutate_values_per_locus<V, R>(
+    values: &mut [V],
+    locus_rates: &[f64],
+    min_value: &V,
+    max_value: &V,
+    rng: &mut R,
+) where
+    V: Clone + RandomValueMutation,
+    R: Rng + Sized,
+{
+    assert_num_locus_rates(locus_rates.len(), values.len());
+    for (value, rate) in values.iter_mut().zip(locus_rates) {
+        if rng.gen::<f64>() < *rate {
+            *value = RandomValueMutation::random_mutated(value.clone(), min_value, max_value, rng);
+        }
+    }
 }
 
 impl<V> RandomGenomeMutation for Vec<V>
@@ -105,11 +263,72 @@ where
         }
         mutated
     }
+
+    fn mutate_loci<R>(
+        genome: Self,
+        locus_rates: &[f64],
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let mut mutated = genome;
+        mutate_values_per_locus(&mut mutated, locus_rates, min_value, max_value, rng);
+        mutated
+    }
+}
+
+impl<V, const N: usize> RandomGenomeMutation for [V; N]
+where
+    V: Clone + Debug + PartialEq + Send + Sync + RandomValueMutation,
+{
+    type Dna = V;
+
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let num_mutations = ((N as f64 * mutation_rate) + rng.gen::<f64>()).floor() as usize;
+        let mut mutated = genome;
+        for _ in 0..num_mutations {
+            let index = random_index(rng, N);
+            mutated[index] = RandomValueMutation::random_mutated(
+                mutated[index].clone(),
+                min_value,
+                max_value,
+                rng,
+            );
+        }
+        mutated
+    }
+
+    fn mutate_loci<R>(
+        genome: Self,
+        locus_rates: &[f64],
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let mut mutated = genome;
+        mutate_values_per_locus(&mut mutated, locus_rates, min_value, max_value, rng);
+        mutated
+    }
 }
 
 #[cfg(feature = "fixedbitset")]
 mod fixedbitset_random_genome_mutation {
-    use super::{random_index, RandomGenomeMutation};
+    use super::{assert_num_locus_rates, random_index, RandomGenomeMutation};
     use crate::genetic::Genotype;
     use fixedbitset::FixedBitSet;
     use rand::Rng;
@@ -138,12 +357,33 @@ mod fixedbitset_random_genome_mutation {
             }
             mutated
         }
+
+        fn mutate_loci<R>(
+            genome: Self,
+            locus_rates: &[f64],
+            _: &<Self as Genotype>::Dna,
+            _: &<Self as Genotype>::Dna,
+            rng: &mut R,
+        ) -> Self
+        where
+            R: Rng + Sized,
+        {
+            assert_num_locus_rates(locus_rates.len(), genome.len());
+            let mut mutated = genome;
+            for (bit, rate) in locus_rates.iter().enumerate() {
+                if rng.gen::<f64>() < *rate {
+                    let value = rng.gen();
+                    mutated.set(bit, value);
+                }
+            }
+            mutated
+        }
     }
 }
 
 #[cfg(feature = "smallvec")]
 mod smallvec_random_genome_mutation {
-    use super::{random_index, RandomGenomeMutation, RandomValueMutation};
+    use super::{mutate_values_per_locus, random_index, RandomGenomeMutation, RandomValueMutation};
     use rand::Rng;
     use smallvec::{Array, SmallVec};
     use std::fmt::Debug;
@@ -180,9 +420,26 @@ mod smallvec_random_genome_mutation {
             }
             mutated
         }
+
+        fn mutate_loci<R>(
+            genome: Self,
+            locus_rates: &[f64],
+            min_value: &V,
+            max_value: &V,
+            rng: &mut R,
+        ) -> Self
+        where
+            R: Rng + Sized,
+        {
+            let mut mutated = genome;
+            mutate_values_per_locus(&mut mutated, locus_rates, min_value, max_value, rng);
+            mutated
+        }
     }
 }
 
+/// A `RandomValueMutation` generates a random value between `min_value` and
+/// `max_value` (both inclusive).
 pub trait RandomValueMutation {
     fn random_mutated<R>(value: Self, min_value: &Self, max_value: &Self, rng: &mut R) -> Self
     where
@@ -197,7 +454,7 @@ macro_rules! impl_random_value_mutation {
                 fn random_mutated<R>(_: $t, min_value: &$t, max_value: &$t, rng: &mut R) -> $t
                     where R: Rng + Sized
                 {
-                    rng.gen_range(*min_value..*max_value)
+                    rng.gen_range(*min_value..=*max_value)
                 }
             }
         )*
@@ -256,6 +513,23 @@ where
     fn name() -> String {
         "Breeder-Value-Mutator".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl<G> MutationRate for BreederValueMutator<G>
+where
+    G: Genotype + BreederGenomeMutation,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
 }
 
 impl<G> MutationOp<G> for BreederValueMutator<G>
@@ -350,6 +624,60 @@ where
     }
 }
 
+impl<V, const N: usize> BreederGenomeMutation for [V; N]
+where
+    V: Clone
+        + Debug
+        + PartialEq
+        + PartialOrd
+        + Send
+        + Sync
+        + BreederValueMutation
+        + RandomValueMutation,
+{
+    type Dna = V;
+
+    fn mutate_genome<R>(
+        genome: [V; N],
+        mutation_rate: f64,
+        range: &<Self as Genotype>::Dna,
+        precision: u8,
+        min_value: &<Self as Genotype>::Dna,
+        max_value: &<Self as Genotype>::Dna,
+        rng: &mut R,
+    ) -> [V; N]
+    where
+        R: Rng + Sized,
+    {
+        let num_mutations = ((N as f64 * mutation_rate) + rng.gen::<f64>()).floor() as usize;
+        let mut mutated = genome;
+        for _ in 0..num_mutations {
+            let index = random_index(rng, N);
+            let sign = *[-1, 1].choose(rng).unwrap();
+            let adjustment = if *[true, false].choose(rng).unwrap() {
+                1. / (1i64 << precision) as f64
+            } else {
+                1.
+            };
+            let value_mut = BreederValueMutation::breeder_mutated(
+                mutated[index].clone(),
+                range,
+                adjustment,
+                sign,
+            );
+            if value_mut < *min_value {
+                mutated[index] =
+                    RandomValueMutation::random_mutated(value_mut, min_value, max_value, rng)
+            } else if value_mut > *max_value {
+                mutated[index] = max_value.clone();
+            } else {
+                mutated[index] = value_mut;
+            }
+        }
+        mutated
+    }
+}
+
 pub trait BreederValueMutation {
     fn breeder_mutated(value: Self, range: &Self, adjustment: f64, sign: i8) -> Self;
 }
@@ -369,3 +697,919 @@ macro_rules! impl_breeder_mutation {
 }
 
 impl_breeder_mutation!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+/// The `GaussianMutator` mutates real valued genes by adding a random value
+/// drawn from a normal distribution with a mean of 0 and a standard deviation
+/// of `sigma`. Mutated values that fall outside the range between `min_value`
+/// and `max_value` are clamped back into that range.
+///
+/// The genes to be mutated are chosen randomly so that on average a fraction
+/// of `mutation_rate` of the genes of a genome is mutated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussianMutator<G>
+where
+    G: Genotype + GaussianGenomeMutation,
+{
+    mutation_rate: f64,
+    sigma: f64,
+    min_value: <G as Genotype>::Dna,
+    max_value: <G as Genotype>::Dna,
+}
+
+impl<G> GaussianMutator<G>
+where
+    G: Genotype + GaussianGenomeMutation,
+{
+    /// Constructs a new `GaussianMutator` with an absolute standard deviation
+    /// `sigma`.
+    pub fn new(
+        mutation_rate: f64,
+        sigma: f64,
+        min_value: <G as Genotype>::Dna,
+        max_value: <G as Genotype>::Dna,
+    ) -> Self {
+        GaussianMutator {
+            mutation_rate,
+            sigma,
+            min_value,
+            max_value,
+        }
+    }
+
+    /// Constructs a new `GaussianMutator` with a standard deviation that is
+    /// the given fraction of the range between `min_value` and `max_value`.
+    pub fn with_relative_sigma(
+        mutation_rate: f64,
+        sigma_fraction: f64,
+        min_value: <G as Genotype>::Dna,
+        max_value: <G as Genotype>::Dna,
+    ) -> Self {
+        let sigma = sigma_fraction * G::range_width(&min_value, &max_value);
+        GaussianMutator::new(mutation_rate, sigma, min_value, max_value)
+    }
+
+    /// Returns the standard deviation of the added random values.
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+}
+
+impl<G> GeneticOperator for GaussianMutator<G>
+where
+    G: Genotype + GaussianGenomeMutation,
+{
+    fn name() -> String {
+        "Gaussian-Mutator".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl<G> MutationRate for GaussianMutator<G>
+where
+    G: Genotype + GaussianGenomeMutation,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl<G> MutationOp<G> for GaussianMutator<G>
+where
+    G: Genotype + GaussianGenomeMutation,
+{
+    fn mutate<R>(&self, genome: G, rng: &mut R) -> G
+    where
+        R: Rng + Sized,
+    {
+        GaussianGenomeMutation::mutate_genome(
+            genome,
+            self.mutation_rate,
+            self.sigma,
+            &self.min_value,
+            &self.max_value,
+            rng,
+        )
+    }
+}
+
+pub trait GaussianGenomeMutation: Genotype {
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        sigma: f64,
+        min_value: &<Self as Genotype>::Dna,
+        max_value: &<Self as Genotype>::Dna,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized;
+
+    fn range_width(min_value: &<Self as Genotype>::Dna, max_value: &<Self as Genotype>::Dna)
+        -> f64;
+}
+
+impl<V> GaussianGenomeMutation for Vec<V>
+where
+    V: Clone + Debug + PartialEq + Send + Sync + GaussianValueMutation,
+{
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        sigma: f64,
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let genome_length = genome.len();
+        let num_mutations =
+            ((genome_length as f64 * mutation_rate) + rng.gen::<f64>()).floor() as usize;
+        let mut mutated = genome;
+        for _ in 0..num_mutations {
+            let index = random_index(rng, genome_length);
+            mutated[index] = GaussianValueMutation::gaussian_mutated(
+                mutated[index].clone(),
+                sigma,
+                min_value,
+                max_value,
+                rng,
+            );
+        }
+        mutated
+    }
+
+    fn range_width(min_value: &V, max_value: &V) -> f64 {
+        GaussianValueMutation::range_width(min_value, max_value)
+    }
+}
+
+impl<V, const N: usize> GaussianGenomeMutation for [V; N]
+where
+    V: Clone + Debug + PartialEq + Send + Sync + GaussianValueMutation,
+{
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        sigma: f64,
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let num_mutations = ((N as f64 * mutation_rate) + rng.gen::<f64>()).floor() as usize;
+        let mut mutated = genome;
+        for _ in 0..num_mutations {
+            let index = random_index(rng, N);
+            mutated[index] = GaussianValueMutation::gaussian_mutated(
+                mutated[index].clone(),
+                sigma,
+                min_value,
+                max_value,
+                rng,
+            );
+        }
+        mutated
+    }
+
+    fn range_width(min_value: &V, max_value: &V) -> f64 {
+        GaussianValueMutation::range_width(min_value, max_value)
+    }
+}
+
+pub trait GaussianValueMutation {
+    fn gaussian_mutated<R>(
+        value: Self,
+        sigma: f64,
+        min_value: &Self,
+        max_value: &Self,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized;
+
+    fn range_width(min_value: &Self, max_value: &Self) -> f64;
+}
+
+macro_rules! impl_gaussian_value_mutation {
+    ($($t:ty),*) => {
+        $(
+            #[allow(trivial_numeric_casts)]
+            impl GaussianValueMutation for $t {
+                #[inline]
+                fn gaussian_mutated<R>(
+                    value: $t,
+                    sigma: f64,
+                    min_value: &$t,
+                    max_value: &$t,
+                    rng: &mut R,
+                ) -> $t
+                    where R: Rng + Sized
+                {
+                    let mutated = value as f64 + sigma * random_standard_normal(rng);
+                    (mutated as $t).max(*min_value).min(*max_value)
+                }
+
+                #[inline]
+                fn range_width(min_value: &$t, max_value: &$t) -> f64 {
+                    *max_value as f64 - *min_value as f64
+                }
+            }
+        )*
+    }
+}
+
+impl_gaussian_value_mutation!(f32, f64);
+
+/// The `NonUniformMutator` implements the non-uniform mutation by
+/// Michalewicz for real valued genes. The magnitude of the mutation decreases
+/// over the generations, so that the search space is explored uniformly in
+/// the first generations and the solutions are fine tuned in the later ones.
+///
+/// A mutated gene `v` is moved towards `max_value` by `delta(t, max_value - v)`
+/// or towards `min_value` by `delta(t, v - min_value)` with equal
+/// probability, where
+///
+/// `delta(t, y) = y * (1 - r^((1 - t / T)^b))`
+///
+/// with `r` a random number between 0 and 1, `t` the current generation, `T`
+/// the number of generations and `b` the shape parameter that controls how
+/// fast the magnitude decreases. In generation `T` and later the genes are not
+/// changed anymore.
+///
+/// The genes to be mutated are chosen randomly so that on average a fraction
+/// of `mutation_rate` of the genes of a genome is mutated.
+///
+/// The current generation is provided by the `ga::GeneticAlgorithm` through
+/// `MutationOp::adapt`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NonUniformMutator<G>
+where
+    G: Genotype + NonUniformGenomeMutation,
+{
+    mutation_rate: f64,
+    min_value: <G as Genotype>::Dna,
+    max_value: <G as Genotype>::Dna,
+    shape: f64,
+    num_generations: u64,
+    generation: u64,
+}
+
+impl<G> NonUniformMutator<G>
+where
+    G: Genotype + NonUniformGenomeMutation,
+{
+    /// Constructs a new `NonUniformMutator` whose mutation magnitude
+    /// decreases to zero within the given number of generations.
+    pub fn new(
+        mutation_rate: f64,
+        min_value: <G as Genotype>::Dna,
+        max_value: <G as Genotype>::Dna,
+        shape: f64,
+        num_generations: u64,
+    ) -> Self {
+        assert!(
+            num_generations > 0,
+            "the number of generations must be positive"
+        );
+        NonUniformMutator {
+            mutation_rate,
+            min_value,
+            max_value,
+            shape,
+            num_generations,
+            generation: 1,
+        }
+    }
+
+    /// Returns the shape parameter `b`.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the number of generations after which the genes are not
+    /// changed anymore.
+    pub fn num_generations(&self) -> u64 {
+        self.num_generations
+    }
+
+    /// Returns the current generation.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Sets the current generation. The generation is counted from 1.
+    pub fn set_generation(&mut self, value: u64) {
+        self.generation = value;
+    }
+
+    /// Returns the exponent `(1 - t / T)^b` of the current generation.
+    fn decay(&self) -> f64 {
+        let progress = (self.generation as f64 / self.num_generations as f64).min(1.);
+        (1. - progress).powf(self.shape)
+    }
+}
+
+impl<G> GeneticOperator for NonUniformMutator<G>
+where
+    G: Genotype + NonUniformGenomeMutation,
+{
+    fn name() -> String {
+        "NonUniform-Mutator".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl<G> MutationRate for NonUniformMutator<G>
+where
+    G: Genotype + NonUniformGenomeMutation,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl<G> MutationOp<G> for NonUniformMutator<G>
+where
+    G: Genotype + NonUniformGenomeMutation,
+{
+    fn mutate<R>(&self, genome: G, rng: &mut R) -> G
+    where
+        R: Rng + Sized,
+    {
+        NonUniformGenomeMutation::mutate_genome(
+            genome,
+            self.mutation_rate,
+            self.decay(),
+            &self.min_value,
+            &self.max_value,
+            rng,
+        )
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.generation = signal.generation;
+    }
+
+    fn reset(&mut self) {
+        self.generation = 1;
+    }
+}
+
+pub trait NonUniformGenomeMutation: Genotype {
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        decay: f64,
+        min_value: &<Self as Genotype>::Dna,
+        max_value: &<Self as Genotype>::Dna,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized;
+}
+
+impl<V> NonUniformGenomeMutation for Vec<V>
+where
+    V: Clone + Debug + PartialEq + Send + Sync + NonUniformValueMutation,
+{
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        decay: f64,
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let genome_length = genome.len();
+        let num_mutations =
+            ((genome_length as f64 * mutation_rate) + rng.gen::<f64>()).floor() as usize;
+        let mut mutated = genome;
+        for _ in 0..num_mutations {
+            let index = random_index(rng, genome_length);
+            mutated[index] = NonUniformValueMutation::non_uniform_mutated(
+                mutated[index].clone(),
+                decay,
+                min_value,
+                max_value,
+                rng,
+            );
+        }
+        mutated
+    }
+}
+
+impl<V, const N: usize> NonUniformGenomeMutation for [V; N]
+where
+    V: Clone + Debug + PartialEq + Send + Sync + NonUniformValueMutation,
+{
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        decay: f64,
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let num_mutations = ((N as f64 * mutation_rate) + rng.gen::<f64>()).floor() as usize;
+        let mut mutated = genome;
+        for _ in 0..num_mutations {
+            let index = random_index(rng, N);
+            mutated[index] = NonUniformValueMutation::non_uniform_mutated(
+                mutated[index].clone(),
+                decay,
+                min_value,
+                max_value,
+                rng,
+            );
+        }
+        mutated
+    }
+}
+
+pub trait NonUniformValueMutation {
+    fn non_uniform_mutated<R>(
+        value: Self,
+        decay: f64,
+        min_value: &Self,
+        max_value: &Self,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized;
+}
+
+macro_rules! impl_non_uniform_value_mutation {
+    ($($t:ty),*) => {
+        $(
+            #[allow(trivial_numeric_casts)]
+            impl NonUniformValueMutation for $t {
+                #[inline]
+                fn non_uniform_mutated<R>(
+                    value: $t,
+                    decay: f64,
+                    min_value: &$t,
+                    max_value: &$t,
+                    rng: &mut R,
+                ) -> $t
+                    where R: Rng + Sized
+                {
+                    let value = value as f64;
+                    let factor = 1. - rng.gen::<f64>().powf(decay);
+                    let mutated = if rng.gen::<bool>() {
+                        value + (*max_value as f64 - value) * factor
+                    } else {
+                        value - (value - *min_value as f64) * factor
+                    };
+                    (mutated as $t).max(*min_value).min(*max_value)
+                }
+            }
+        )*
+    }
+}
+
+impl_non_uniform_value_mutation!(f32, f64);
+
+/// The `BoundaryMutator` sets genes exactly to one of their bounds, e.g. to
+/// explore the edges of the feasible region of box-constrained problems.
+///
+/// Each gene is mutated with the probability `mutation_rate`. A mutated gene
+/// is set to either `min_value` or `max_value` with equal probability. All
+/// other genes are left unchanged. With a `mutation_rate` of 0 the genome is
+/// returned as it is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundaryMutator<G>
+where
+    G: Genotype + BoundaryGenomeMutation,
+{
+    mutation_rate: f64,
+    min_value: <G as Genotype>::Dna,
+    max_value: <G as Genotype>::Dna,
+}
+
+impl<G> BoundaryMutator<G>
+where
+    G: Genotype + BoundaryGenomeMutation,
+{
+    /// Constructs a new `BoundaryMutator` which sets mutated genes to either
+    /// `min_value` or `max_value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_value` is greater than `max_value`.
+    pub fn new(
+        mutation_rate: f64,
+        min_value: <G as Genotype>::Dna,
+        max_value: <G as Genotype>::Dna,
+    ) -> Self
+    where
+        <G as Genotype>::Dna: PartialOrd,
+    {
+        assert!(
+            min_value <= max_value,
+            "min_value {:?} must not be greater than max_value {:?}",
+            min_value,
+            max_value
+        );
+        BoundaryMutator {
+            mutation_rate,
+            min_value,
+            max_value,
+        }
+    }
+
+    /// Returns the lower bound of the genes.
+    pub fn min_value(&self) -> &<G as Genotype>::Dna {
+        &self.min_value
+    }
+
+    /// Returns the upper bound of the genes.
+    pub fn max_value(&self) -> &<G as Genotype>::Dna {
+        &self.max_value
+    }
+}
+
+impl<G> GeneticOperator for BoundaryMutator<G>
+where
+    G: Genotype + BoundaryGenomeMutation,
+{
+    fn name() -> String {
+        "Boundary-Mutator".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mutation rate", self.mutation_rate)
+    }
+}
+
+impl<G> MutationRate for BoundaryMutator<G>
+where
+    G: Genotype + BoundaryGenomeMutation,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutation_rate
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutation_rate = value;
+    }
+}
+
+impl<G> MutationOp<G> for BoundaryMutator<G>
+where
+    G: Genotype + BoundaryGenomeMutation,
+{
+    fn mutate<R>(&self, genome: G, rng: &mut R) -> G
+    where
+        R: Rng + Sized,
+    {
+        if self.mutation_rate <= 0. {
+            return genome;
+        }
+        BoundaryGenomeMutation::mutate_genome(
+            genome,
+            self.mutation_rate,
+            &self.min_value,
+            &self.max_value,
+            rng,
+        )
+    }
+}
+
+pub trait BoundaryGenomeMutation: Genotype {
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        min_value: &<Self as Genotype>::Dna,
+        max_value: &<Self as Genotype>::Dna,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized;
+}
+
+/// Sets each of the given values to one of the bounds with the probability
+/// `mutation_rate`.
+fn set_values_to_bounds<V, R>(
+    values: &mut [V],
+    mutation_rate: f64,
+    min_value: &V,
+    max_value: &V,
+    rng: &mut R,
+) where
+    V: Clone,
+    R: Rng + Sized,
+{
+    for value in values.iter_mut() {
+        if random_probability(rng) < mutation_rate {
+            *value = if rng.gen::<bool>() {
+                max_value.clone()
+            } else {
+                min_value.clone()
+            };
+        }
+    }
+}
+
+impl<V> BoundaryGenomeMutation for Vec<V>
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let mut mutated = genome;
+        set_values_to_bounds(&mut mutated, mutation_rate, min_value, max_value, rng);
+        mutated
+    }
+}
+
+impl<V, const N: usize> BoundaryGenomeMutation for [V; N]
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn mutate_genome<R>(
+        genome: Self,
+        mutation_rate: f64,
+        min_value: &V,
+        max_value: &V,
+        rng: &mut R,
+    ) -> Self
+    where
+        R: Rng + Sized,
+    {
+        let mut mutated = genome;
+        set_values_to_bounds(&mut mutated, mutation_rate, min_value, max_value, rng);
+        mutated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn random_value_mutator_generates_values_including_both_bounds() {
+        let mutator = RandomValueMutator::new(1.0, 32u8, 126u8);
+        let mut rng = get_rng([42; 32]);
+        let mut generated = vec![false; 256];
+
+        for _ in 0..1000 {
+            for value in mutator.mutate(vec![32u8; 10], &mut rng) {
+                generated[value as usize] = true;
+            }
+        }
+
+        let expected: Vec<bool> = (0..256).map(|value| (32..=126).contains(&value)).collect();
+        expect_that!(&generated, eq(expected));
+    }
+
+    #[test]
+    fn random_value_mutator_with_equal_bounds_generates_only_this_value() {
+        let mutator = RandomValueMutator::new(1.0, 7i32, 7i32);
+        let mut rng = get_rng([42; 32]);
+
+        let mutated = mutator.mutate(vec![1i32; 10], &mut rng);
+
+        expect_that!(&mutated.contains(&7), is(eq(true)));
+        expect_that!(
+            &mutated.iter().all(|value| *value == 1 || *value == 7),
+            is(eq(true))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "min_value 126 must not be greater than max_value 32")]
+    fn random_value_mutator_panics_if_the_bounds_are_inverted() {
+        RandomValueMutator::<Vec<u8>>::new(0.1, 126, 32);
+    }
+
+    #[test]
+    fn random_value_mutator_with_locus_rates_only_changes_the_intended_loci() {
+        let mutator =
+            RandomValueMutator::with_locus_rates(vec![0.0, 1.0, 0.0, 1.0, 1.0, 0.0], 10u8, 20u8);
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(vec![0u8; 6], &mut rng);
+
+            for index in [0, 2, 5] {
+                expect_that!(&mutated[index], eq(0));
+            }
+            for index in [1, 3, 4] {
+                expect_that!(&mutated[index], is(greater_than_or_equal(10)));
+            }
+        }
+        expect_that!(&mutator.mutation_rate(), eq(0.5));
+    }
+
+    #[test]
+    fn random_value_mutator_scales_the_locus_rates_when_setting_the_mutation_rate() {
+        let mut mutator =
+            RandomValueMutator::<Vec<u8>>::with_locus_rates(vec![0.0, 0.2, 0.4, 0.6], 0, 1);
+
+        mutator.set_mutation_rate(0.6);
+
+        // the rate of the last locus is capped at 1
+        expect_that!(&mutator.mutation_rate(), is(close_to(0.55, 1e-12)));
+        let locus_rates = mutator.locus_rates().unwrap();
+        expect_that!(&locus_rates[0], eq(0.0));
+        expect_that!(&locus_rates[1], is(close_to(0.4, 1e-12)));
+        expect_that!(&locus_rates[2], is(close_to(0.8, 1e-12)));
+        expect_that!(&locus_rates[3], eq(1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "the number of mutation rates 3 does not match the genome length 4")]
+    fn random_value_mutator_panics_if_the_locus_rates_do_not_match_the_genome_length() {
+        let mutator = RandomValueMutator::with_locus_rates(vec![0.5; 3], 0u8, 10u8);
+
+        mutator.mutate(vec![1u8; 4], &mut get_rng([42; 32]));
+    }
+
+    #[test]
+    fn gaussian_mutator_keeps_values_within_bounds() {
+        let mutator = GaussianMutator::new(1.0, 5.0, -1.0, 1.0);
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..100 {
+            let genome: Vec<f64> = vec![-1.0, -0.5, 0.0, 0.5, 1.0];
+            let mutated = mutator.mutate(genome, &mut rng);
+            for value in mutated {
+                expect_that!(&value, is(greater_than_or_equal(-1.0)));
+                expect_that!(&value, is(less_than_or_equal(1.0)));
+            }
+        }
+    }
+
+    #[test]
+    fn gaussian_mutator_with_rate_0_does_not_change_genes() {
+        let mutator = GaussianMutator::new(0.0, 1.0, -10.0f32, 10.0);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<f32> = vec![-3.5, 0.0, 1.25, 7.0, 9.5];
+
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            expect_that!(&mutated, eq(genome.clone()));
+        }
+    }
+
+    #[test]
+    fn gaussian_mutator_with_relative_sigma_scales_sigma_by_range() {
+        let mutator: GaussianMutator<Vec<f64>> =
+            GaussianMutator::with_relative_sigma(0.1, 0.05, -2.0, 6.0);
+
+        expect_that!(&(mutator.sigma() - 0.4).abs(), less_than(1e-12));
+    }
+
+    fn mean_mutation_magnitude(mutator: &NonUniformMutator<Vec<f64>>) -> f64 {
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<f64> = vec![0.0; 10];
+        let mut sum = 0.;
+        for _ in 0..100 {
+            let mutated = mutator.mutate(genome.clone(), &mut rng);
+            sum += mutated.iter().map(|value| value.abs()).sum::<f64>();
+        }
+        sum / 1000.
+    }
+
+    #[test]
+    fn non_uniform_mutator_decreases_the_mutation_magnitude_over_time() {
+        let mut mutator = NonUniformMutator::new(1.0, -10.0, 10.0, 2.0, 100);
+
+        let magnitudes: Vec<f64> = [1, 25, 50, 75, 99]
+            .iter()
+            .map(|generation| {
+                let genome = vec![0.0; 10];
+                mutator.adapt(&GenerationSignal {
+                    generation: *generation,
+                    individuals: &[genome],
+                    improved: false,
+                });
+                mean_mutation_magnitude(&mutator)
+            })
+            .collect();
+
+        for pair in magnitudes.windows(2) {
+            expect_that!(&pair[1], is(less_than(pair[0])));
+        }
+        expect_that!(&magnitudes[4], is(less_than(0.01)));
+    }
+
+    #[test]
+    fn non_uniform_mutator_does_not_change_genes_in_the_final_generation() {
+        let mut mutator = NonUniformMutator::new(1.0, -10.0, 10.0, 1.0, 100);
+        let mut rng = get_rng([42; 32]);
+        let genome: Vec<f64> = vec![-9.5, -1.0, 0.0, 2.5, 9.0];
+
+        for generation in &[100, 150] {
+            mutator.set_generation(*generation);
+            for _ in 0..100 {
+                let mutated = mutator.mutate(genome.clone(), &mut rng);
+                expect_that!(&mutated, eq(genome.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn non_uniform_mutator_keeps_values_within_bounds() {
+        let mutator = NonUniformMutator::new(1.0, -1.0f32, 1.0, 0.5, 100);
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..100 {
+            let genome: [f32; 5] = [-1.0, -0.5, 0.0, 0.5, 1.0];
+            let mutated = mutator.mutate(genome, &mut rng);
+            for value in &mutated {
+                expect_that!(value, is(greater_than_or_equal(-1.0)));
+                expect_that!(value, is(less_than_or_equal(1.0)));
+            }
+        }
+    }
+
+    #[test]
+    fn boundary_mutator_sets_mutated_genes_exactly_to_a_bound() {
+        let mutator = BoundaryMutator::new(0.3, -1.0f64, 2.0);
+        let mut rng = get_rng([42; 32]);
+        let mut num_at_min = 0;
+        let mut num_at_max = 0;
+        let mut num_preserved = 0;
+
+        for _ in 0..100 {
+            for value in mutator.mutate(vec![0.5f64; 20], &mut rng) {
+                if value == -1.0 {
+                    num_at_min += 1;
+                } else if value == 2.0 {
+                    num_at_max += 1;
+                } else {
+                    expect_that!(&value, eq(0.5));
+                    num_preserved += 1;
+                }
+            }
+        }
+
+        expect_that!(&num_at_min, is(greater_than(0)));
+        expect_that!(&num_at_max, is(greater_than(0)));
+        expect_that!(&num_preserved, is(greater_than(num_at_min + num_at_max)));
+    }
+
+    #[test]
+    fn boundary_mutator_with_a_rate_of_0_does_not_change_the_genome() {
+        let mutator = BoundaryMutator::new(0.0, -1.0f32, 1.0);
+        let mut rng = get_rng([42; 32]);
+        let genome: [f32; 5] = [-0.9, -0.5, 0.0, 0.5, 0.9];
+
+        for _ in 0..100 {
+            expect_that!(&mutator.mutate(genome, &mut rng), eq(genome));
+        }
+    }
+
+    #[test]
+    fn boundary_mutator_composes_with_other_mutators() {
+        let gaussian = GaussianMutator::new(1.0, 0.1, -1.0f64, 1.0);
+        let boundary = BoundaryMutator::new(0.5, -1.0f64, 1.0);
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..100 {
+            let perturbed = gaussian.mutate(vec![0.0f64; 10], &mut rng);
+            let mutated = boundary.mutate(perturbed.clone(), &mut rng);
+            for (value, perturbed) in mutated.iter().zip(&perturbed) {
+                expect_that!(
+                    &(*value == -1.0 || *value == 1.0 || value == perturbed),
+                    eq(true)
+                );
+            }
+        }
+    }
+}
@@ -9,8 +9,11 @@
 
 pub mod prelude;
 
+pub mod schedule;
+
 use crate::{
     algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
     genetic::{Children, Fitness, Genotype, Offspring, Parents},
     random::Rng,
 };
@@ -40,6 +43,76 @@ pub trait GeneticOperator: Clone {
     /// this operator (first part) with some name for the kind of operator
     /// (second part), e.g. "Flip-Bit-Mutation" or "Roulette-Wheel-Selection".
     fn name() -> String;
+
+    /// Validates the parameters of this operator.
+    ///
+    /// The `ga::GeneticAlgorithmBuilder` calls this function when the
+    /// algorithm is built, so that a misconfigured operator fails fast
+    /// instead of producing useless results. It returns a
+    /// `GeneticAlgorithmError::InvalidRatio` for a ratio or probability that
+    /// is not between 0 and 1, a `GeneticAlgorithmError::InvalidCount` for a
+    /// count that is not positive and a
+    /// `GeneticAlgorithmError::InvalidParameter` for any other parameter that
+    /// is out of its valid range. The default implementation accepts any
+    /// parameters.
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        Ok(())
+    }
+}
+
+/// Validates that the parameter of the operator `O` is a ratio or a
+/// probability between 0 and 1 (inclusive).
+pub(crate) fn validate_ratio<O>(parameter: &str, value: f64) -> Result<(), GeneticAlgorithmError>
+where
+    O: GeneticOperator,
+{
+    if (0. ..=1.).contains(&value) {
+        Ok(())
+    } else {
+        Err(GeneticAlgorithmError::InvalidRatio(format!(
+            "The {} of the operator {} must be between 0 and 1, but is {}.",
+            parameter,
+            O::name(),
+            value
+        )))
+    }
+}
+
+/// Validates that the parameter of the operator `O` is a ratio greater than 0
+/// and at most 1.
+pub(crate) fn validate_positive_ratio<O>(
+    parameter: &str,
+    value: f64,
+) -> Result<(), GeneticAlgorithmError>
+where
+    O: GeneticOperator,
+{
+    if value > 0. && value <= 1. {
+        Ok(())
+    } else {
+        Err(GeneticAlgorithmError::InvalidRatio(format!(
+            "The {} of the operator {} must be greater than 0 and at most 1, but is {}.",
+            parameter,
+            O::name(),
+            value
+        )))
+    }
+}
+
+/// Validates that the count parameter of the operator `O` is positive.
+pub(crate) fn validate_count<O>(parameter: &str, value: usize) -> Result<(), GeneticAlgorithmError>
+where
+    O: GeneticOperator,
+{
+    if value > 0 {
+        Ok(())
+    } else {
+        Err(GeneticAlgorithmError::InvalidCount(format!(
+            "The {} of the operator {} must be greater than 0.",
+            parameter,
+            O::name()
+        )))
+    }
 }
 
 /// A `SelectionOp` defines the function of how to select solutions for being
@@ -58,6 +131,55 @@ where
     ) -> Vec<Parents<G>>
     where
         R: Rng + Sized;
+
+    /// Returns the number of individuals per `genetic::Parents` that this
+    /// `SelectionOp` selects, or `None` if the number is not known in
+    /// advance.
+    ///
+    /// It is used to validate that the selected parents can be processed by
+    /// the `CrossoverOp`. The default implementation returns `None`.
+    fn parents_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the minimum number of individuals of the population that this
+    /// `SelectionOp` needs to select from, or `None` if it can select from a
+    /// population of any size.
+    ///
+    /// The `ga::GeneticAlgorithmBuilder` rejects an initial population that
+    /// is smaller. The default implementation returns `None`.
+    fn required_population_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns whether this `SelectionOp` considers lower fitness values to
+    /// be better than higher ones.
+    ///
+    /// The `ga::GeneticAlgorithm` takes the direction of the optimization
+    /// from its `SelectionOp`. If it minimizes, the best solution is the one
+    /// with the lowest fitness value and the `algorithm::EvaluatedPopulation`
+    /// passed to the operators is minimized, so that the reinsertion and the
+    /// termination conditions prefer lower fitness values as well. The
+    /// default implementation returns `false`.
+    fn is_minimize(&self) -> bool {
+        false
+    }
+
+    /// Adapts this `SelectionOp` to the progress of the simulation.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function once per generation
+    /// after the fitness of the population has been evaluated and before the
+    /// parents of this generation are selected. The default implementation
+    /// does nothing.
+    fn adapt(&mut self, _signal: &GenerationSignal<G>) {}
+
+    /// Resets the state that this `SelectionOp` has adapted during the simulation
+    /// to its initial state.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function when it is reset, so
+    /// that a reset simulation runs like a new one. The default
+    /// implementation does nothing.
+    fn reset(&mut self) {}
 }
 
 /// A `CrossoverOp` defines a function of how to crossover two
@@ -74,6 +196,33 @@ where
     fn crossover<R>(&self, parents: Parents<G>, rng: &mut R) -> Children<G>
     where
         R: Rng + Sized;
+
+    /// Returns the number of individuals per `genetic::Parents` that this
+    /// `CrossoverOp` requires, or `None` if it can process parents of any
+    /// size.
+    ///
+    /// The `ga::GeneticAlgorithmBuilder` rejects a `SelectionOp` that selects
+    /// parents of a different size. The default implementation returns
+    /// `None`.
+    fn required_parents_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Adapts this `CrossoverOp` to the progress of the simulation.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function once per generation
+    /// after the fitness of the population has been evaluated and before the
+    /// offspring of this generation is bred. The default implementation does
+    /// nothing.
+    fn adapt(&mut self, _signal: &GenerationSignal<G>) {}
+
+    /// Resets the state that this `CrossoverOp` has adapted during the simulation
+    /// to its initial state.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function when it is reset, so
+    /// that a reset simulation runs like a new one. The default
+    /// implementation does nothing.
+    fn reset(&mut self) {}
 }
 
 /// A `MutationOp` defines a function of how a `genetic::Genotype` mutates. It
@@ -93,6 +242,62 @@ where
     fn mutate<R>(&self, genome: G, rng: &mut R) -> G
     where
         R: Rng + Sized;
+
+    /// Adapts this `MutationOp` to the progress of the simulation.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function once per generation
+    /// after the fitness of the population has been evaluated and before the
+    /// offspring of this generation is mutated. The default implementation
+    /// does nothing.
+    fn adapt(&mut self, _signal: &GenerationSignal<G>) {}
+
+    /// Resets the state that this `MutationOp` has adapted during the simulation
+    /// to its initial state.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function when it is reset, so
+    /// that a reset simulation runs like a new one. The default
+    /// implementation does nothing.
+    fn reset(&mut self) {}
+}
+
+/// A `MutationRate` is implemented by `MutationOp`s whose mutation rate can
+/// be read and changed, e.g. to adjust the mutation rate during the
+/// simulation.
+pub trait MutationRate {
+    /// Returns the mutation rate.
+    fn mutation_rate(&self) -> f64;
+
+    /// Sets the mutation rate to the given value.
+    fn set_mutation_rate(&mut self, value: f64);
+}
+
+/// A `CrossoverRate` is implemented by `CrossoverOp`s whose crossover rate
+/// can be read and changed, e.g. to adjust the crossover rate during the
+/// simulation.
+pub trait CrossoverRate {
+    /// Returns the crossover rate.
+    fn crossover_rate(&self) -> f64;
+
+    /// Sets the crossover rate to the given value.
+    fn set_crossover_rate(&mut self, value: f64);
+}
+
+/// The `GenerationSignal` describes the progress of the simulation in one
+/// generation as passed to `SelectionOp::adapt`, `CrossoverOp::adapt`,
+/// `MutationOp::adapt` and `ReinsertionOp::adapt`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenerationSignal<'a, G>
+where
+    G: Genotype,
+{
+    /// The number of the generation, counted from 1.
+    pub generation: u64,
+    /// The individuals of the population of this generation.
+    pub individuals: &'a [G],
+    /// Whether the best fitness of this generation is better than the best
+    /// fitness of all previous generations. This is always true for the
+    /// first generation.
+    pub improved: bool,
 }
 
 /// A `ReinsertionOp` defines a function that combines the offspring with the
@@ -135,4 +340,32 @@ where
     ) -> Vec<G>
     where
         R: Rng + Sized;
+
+    /// Returns the number of individuals the offspring must contain to be
+    /// combined with a population of `population_size` individuals.
+    ///
+    /// The `ga::GeneticAlgorithm` checks the size of the offspring before it
+    /// calls `combine` and fails with a
+    /// `GeneticAlgorithmError::IncompatibleOperators` if the offspring has a
+    /// different size. The default implementation returns `None`, which means
+    /// the offspring can have any size.
+    fn required_offspring_size(&self, _population_size: usize) -> Option<usize> {
+        None
+    }
+
+    /// Adapts this `ReinsertionOp` to the progress of the simulation.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function once per generation
+    /// after the fitness of the population has been evaluated and before the
+    /// new population of this generation is combined. The default
+    /// implementation does nothing.
+    fn adapt(&mut self, _signal: &GenerationSignal<G>) {}
+
+    /// Resets the state that this `ReinsertionOp` has adapted during the simulation
+    /// to its initial state.
+    ///
+    /// The `ga::GeneticAlgorithm` calls this function when it is reset, so
+    /// that a reset simulation runs like a new one. The default
+    /// implementation does nothing.
+    fn reset(&mut self) {}
 }
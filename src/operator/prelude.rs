@@ -1,7 +1,20 @@
-pub use crate::selection::{proportionate::*, ranking::*, tournament::*, truncation::*, *};
+pub use crate::selection::{
+    boltzmann::*, nsga2::*, pairing::*, proportionate::*, ranking::*, scaling::*, sharing::*,
+    tournament::*, truncation::*, *,
+};
 
-pub use crate::recombination::{discrete::*, order::*, *};
+// the module names `order` of `recombination` and `mutation` clash
+#[allow(ambiguous_glob_reexports)]
+pub use crate::recombination::{
+    arithmetic::*, diploid::*, discrete::*, order::*, probabilistic::*, splice::*, tree::*, *,
+};
 
-pub use crate::mutation::{order::*, value::*, *};
+pub use crate::mutation::{
+    adaptive::*, binary::*, diploid::*, length::*, order::*, tree::*, value::*, *,
+};
 
-pub use crate::reinsertion::{elitist::*, random::*, *};
+pub use crate::reinsertion::{
+    crowding::*, elitist::*, generational::*, random::*, restart::*, steady_state::*, unique::*, *,
+};
+
+pub use crate::operator::schedule::*;
@@ -0,0 +1,504 @@
+//! The `schedule` module provides `Schedule`s that change a parameter of an
+//! operator over the course of a simulation, e.g. to anneal the mutation
+//! rate, and the wrappers that apply them to the operators.
+//!
+//! A `Schedule` determines the value of the parameter from the number of the
+//! current generation and the number of generations the schedule spans. The
+//! `ScheduledMutator` sets the mutation rate of a `MutationOp` and the
+//! `ScheduledCrossover` sets the crossover rate of a `CrossoverOp` once per
+//! generation. Thus the same schedule can be reused for different operators.
+//!
+//! The provided `Schedule` implementations are:
+//! * `ConstantSchedule` - keeps the value constant.
+//! * `LinearDecay` - changes the value linearly over the generations.
+//! * `ExponentialDecay` - changes the value by a constant factor per
+//!   generation.
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genetic::{Children, Genotype, Parents},
+    operator::{
+        validate_ratio, CrossoverOp, CrossoverRate, GenerationSignal, GeneticOperator, MutationOp,
+        MutationRate,
+    },
+    random::Rng,
+};
+use std::fmt::Debug;
+
+/// A `Schedule` determines the value of an operator parameter in each
+/// generation.
+pub trait Schedule: Clone + Debug {
+    /// Returns the value for the given generation, counted from 1, of a
+    /// schedule that spans `max_generation` generations.
+    fn value(&self, generation: u64, max_generation: u64) -> f64;
+}
+
+/// Returns the progress of the given generation through a schedule that
+/// spans `max_generation` generations, from 0 in the first generation to 1
+/// in generation `max_generation` and after.
+fn progress(generation: u64, max_generation: u64) -> f64 {
+    if generation >= max_generation || max_generation <= 1 {
+        return 1.;
+    }
+    generation.saturating_sub(1) as f64 / (max_generation - 1) as f64
+}
+
+/// The `ConstantSchedule` returns the same value in every generation.
+///
+/// Wrapping an operator in a schedule with a `ConstantSchedule` of its own
+/// rate does not change the behavior of the operator.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstantSchedule {
+    /// The value in every generation.
+    value: f64,
+}
+
+impl ConstantSchedule {
+    /// Constructs a new `ConstantSchedule` of the given value.
+    pub fn new(value: f64) -> Self {
+        ConstantSchedule { value }
+    }
+}
+
+impl Schedule for ConstantSchedule {
+    fn value(&self, _: u64, _: u64) -> f64 {
+        self.value
+    }
+}
+
+/// The `LinearDecay` changes the value linearly from `start` in the first
+/// generation to `end` in generation `max_generation`. After that the value
+/// stays at `end`.
+///
+/// Usually `start` is higher than `end` in order to explore the search space
+/// at the beginning and to exploit the best regions later on, but the value
+/// may also increase.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearDecay {
+    /// The value in the first generation.
+    start: f64,
+    /// The value from generation `max_generation` on.
+    end: f64,
+}
+
+impl LinearDecay {
+    /// Constructs a new `LinearDecay` from `start` to `end`.
+    pub fn new(start: f64, end: f64) -> Self {
+        LinearDecay { start, end }
+    }
+
+    /// Returns the value in the first generation.
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Returns the value from generation `max_generation` on.
+    pub fn end(&self) -> f64 {
+        self.end
+    }
+}
+
+impl Schedule for LinearDecay {
+    fn value(&self, generation: u64, max_generation: u64) -> f64 {
+        self.start + (self.end - self.start) * progress(generation, max_generation)
+    }
+}
+
+/// The `ExponentialDecay` changes the value from `start` in the first
+/// generation to `end` in generation `max_generation` by multiplying it with
+/// the same factor in each generation. After that the value stays at `end`.
+///
+/// In contrast to the `LinearDecay` the value decreases fast in the first
+/// generations and slowly in the later ones.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExponentialDecay {
+    /// The value in the first generation.
+    start: f64,
+    /// The value from generation `max_generation` on.
+    end: f64,
+}
+
+impl ExponentialDecay {
+    /// Constructs a new `ExponentialDecay` from `start` to `end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` or `end` is not greater than 0.
+    pub fn new(start: f64, end: f64) -> Self {
+        assert!(
+            start > 0. && end > 0.,
+            "the start and the end value must be greater than 0"
+        );
+        ExponentialDecay { start, end }
+    }
+
+    /// Returns the value in the first generation.
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Returns the value from generation `max_generation` on.
+    pub fn end(&self) -> f64 {
+        self.end
+    }
+}
+
+impl Schedule for ExponentialDecay {
+    fn value(&self, generation: u64, max_generation: u64) -> f64 {
+        self.start * (self.end / self.start).powf(progress(generation, max_generation))
+    }
+}
+
+/// The `ScheduledMutator` wraps another `MutationOp` and sets its mutation
+/// rate in every generation to the value of a `Schedule`.
+///
+/// The mutation itself is done by the wrapped `MutationOp`, which must
+/// implement the `MutationRate` trait, so that its mutation rate can be
+/// changed. The mutation rate is set before the offspring of a generation is
+/// mutated, thus from the first generation on the mutation rate is the one
+/// of the `Schedule`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledMutator<M, S> {
+    /// The wrapped `MutationOp`.
+    mutator: M,
+    /// The `Schedule` that determines the mutation rate.
+    schedule: S,
+    /// The number of generations the `Schedule` spans.
+    max_generation: u64,
+    /// The current generation, 0 before the first generation.
+    generation: u64,
+}
+
+impl<M, S> ScheduledMutator<M, S>
+where
+    M: MutationRate,
+    S: Schedule,
+{
+    /// Constructs a new `ScheduledMutator` wrapping the given `MutationOp`
+    /// with its mutation rate set by the given `Schedule`, which spans
+    /// `max_generation` generations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_generation` is 0.
+    pub fn new(mutator: M, schedule: S, max_generation: u64) -> Self {
+        assert!(
+            max_generation > 0,
+            "the number of generations must be greater than 0"
+        );
+        ScheduledMutator {
+            mutator,
+            schedule,
+            max_generation,
+            generation: 0,
+        }
+    }
+
+    /// Returns the wrapped `MutationOp`.
+    pub fn mutator(&self) -> &M {
+        &self.mutator
+    }
+
+    /// Returns the `Schedule` that determines the mutation rate.
+    pub fn schedule(&self) -> &S {
+        &self.schedule
+    }
+
+    /// Returns the number of generations the `Schedule` spans.
+    pub fn max_generation(&self) -> u64 {
+        self.max_generation
+    }
+
+    /// Returns the current generation, which is 0 before the first
+    /// generation.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<M, S> MutationRate for ScheduledMutator<M, S>
+where
+    M: MutationRate,
+{
+    fn mutation_rate(&self) -> f64 {
+        self.mutator.mutation_rate()
+    }
+
+    fn set_mutation_rate(&mut self, value: f64) {
+        self.mutator.set_mutation_rate(value);
+    }
+}
+
+impl<M, S> GeneticOperator for ScheduledMutator<M, S>
+where
+    M: GeneticOperator,
+    S: Schedule,
+{
+    fn name() -> String {
+        format!("Scheduled-{}", M::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>(
+            "initial mutation rate",
+            self.schedule.value(1, self.max_generation),
+        )?;
+        validate_ratio::<Self>(
+            "final mutation rate",
+            self.schedule
+                .value(self.max_generation, self.max_generation),
+        )?;
+        self.mutator.validate()
+    }
+}
+
+impl<G, M, S> MutationOp<G> for ScheduledMutator<M, S>
+where
+    G: Genotype,
+    M: MutationOp<G> + MutationRate,
+    S: Schedule,
+{
+    fn mutate<R>(&self, genome: G, rng: &mut R) -> G
+    where
+        R: Rng + Sized,
+    {
+        self.mutator.mutate(genome, rng)
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.generation = signal.generation;
+        let rate = self.schedule.value(signal.generation, self.max_generation);
+        self.mutator.set_mutation_rate(rate);
+        self.mutator.adapt(signal);
+    }
+
+    fn reset(&mut self) {
+        self.generation = 0;
+        self.mutator.reset();
+    }
+}
+
+/// The `ScheduledCrossover` wraps another `CrossoverOp` and sets its
+/// crossover rate in every generation to the value of a `Schedule`.
+///
+/// The crossover itself is done by the wrapped `CrossoverOp`, which must
+/// implement the `CrossoverRate` trait, e.g. the
+/// `recombination::probabilistic::ProbabilisticCrossover`. The crossover rate
+/// is set before the offspring of a generation is bred.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledCrossover<C, S> {
+    /// The wrapped `CrossoverOp`.
+    breeder: C,
+    /// The `Schedule` that determines the crossover rate.
+    schedule: S,
+    /// The number of generations the `Schedule` spans.
+    max_generation: u64,
+    /// The current generation, 0 before the first generation.
+    generation: u64,
+}
+
+impl<C, S> ScheduledCrossover<C, S>
+where
+    C: CrossoverRate,
+    S: Schedule,
+{
+    /// Constructs a new `ScheduledCrossover` wrapping the given `CrossoverOp`
+    /// with its crossover rate set by the given `Schedule`, which spans
+    /// `max_generation` generations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_generation` is 0.
+    pub fn new(breeder: C, schedule: S, max_generation: u64) -> Self {
+        assert!(
+            max_generation > 0,
+            "the number of generations must be greater than 0"
+        );
+        ScheduledCrossover {
+            breeder,
+            schedule,
+            max_generation,
+            generation: 0,
+        }
+    }
+
+    /// Returns the wrapped `CrossoverOp`.
+    pub fn breeder(&self) -> &C {
+        &self.breeder
+    }
+
+    /// Returns the `Schedule` that determines the crossover rate.
+    pub fn schedule(&self) -> &S {
+        &self.schedule
+    }
+
+    /// Returns the number of generations the `Schedule` spans.
+    pub fn max_generation(&self) -> u64 {
+        self.max_generation
+    }
+
+    /// Returns the current generation, which is 0 before the first
+    /// generation.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<C, S> CrossoverRate for ScheduledCrossover<C, S>
+where
+    C: CrossoverRate,
+{
+    fn crossover_rate(&self) -> f64 {
+        self.breeder.crossover_rate()
+    }
+
+    fn set_crossover_rate(&mut self, value: f64) {
+        self.breeder.set_crossover_rate(value);
+    }
+}
+
+impl<C, S> GeneticOperator for ScheduledCrossover<C, S>
+where
+    C: GeneticOperator,
+    S: Schedule,
+{
+    fn name() -> String {
+        format!("Scheduled-{}", C::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>(
+            "initial crossover rate",
+            self.schedule.value(1, self.max_generation),
+        )?;
+        validate_ratio::<Self>(
+            "final crossover rate",
+            self.schedule
+                .value(self.max_generation, self.max_generation),
+        )?;
+        self.breeder.validate()
+    }
+}
+
+impl<G, C, S> CrossoverOp<G> for ScheduledCrossover<C, S>
+where
+    G: Genotype,
+    C: CrossoverOp<G> + CrossoverRate,
+    S: Schedule,
+{
+    fn crossover<R>(&self, parents: Parents<G>, rng: &mut R) -> Children<G>
+    where
+        R: Rng + Sized,
+    {
+        self.breeder.crossover(parents, rng)
+    }
+
+    fn required_parents_size(&self) -> Option<usize> {
+        self.breeder.required_parents_size()
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.generation = signal.generation;
+        let rate = self.schedule.value(signal.generation, self.max_generation);
+        self.breeder.set_crossover_rate(rate);
+        self.breeder.adapt(signal);
+    }
+
+    fn reset(&mut self) {
+        self.generation = 0;
+        self.breeder.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mutation::value::RandomValueMutator,
+        recombination::{discrete::UniformCrossBreeder, probabilistic::ProbabilisticCrossover},
+    };
+    use galvanic_assert::matchers::*;
+
+    fn signal(generation: u64, individuals: &[Vec<u8>]) -> GenerationSignal<'_, Vec<u8>> {
+        GenerationSignal {
+            generation,
+            individuals,
+            improved: false,
+        }
+    }
+
+    fn expect_close(actual: f64, expected: f64) {
+        expect_that!(&(actual - expected).abs(), less_than(1e-9));
+    }
+
+    #[test]
+    fn linear_decay_yields_the_expected_rates() {
+        let schedule = LinearDecay::new(0.5, 0.1);
+
+        expect_close(schedule.value(1, 5), 0.5);
+        expect_close(schedule.value(2, 5), 0.4);
+        expect_close(schedule.value(3, 5), 0.3);
+        expect_close(schedule.value(5, 5), 0.1);
+        expect_close(schedule.value(8, 5), 0.1);
+    }
+
+    #[test]
+    fn exponential_decay_yields_the_expected_rates() {
+        let schedule = ExponentialDecay::new(0.8, 0.1);
+
+        expect_close(schedule.value(1, 4), 0.8);
+        expect_close(schedule.value(2, 4), 0.4);
+        expect_close(schedule.value(3, 4), 0.2);
+        expect_close(schedule.value(4, 4), 0.1);
+        expect_close(schedule.value(6, 4), 0.1);
+    }
+
+    #[test]
+    fn scheduled_mutator_sets_the_mutation_rate_in_each_generation() {
+        let mut mutator = ScheduledMutator::new(
+            RandomValueMutator::<Vec<u8>>::new(0.9, 0, 9),
+            LinearDecay::new(0.5, 0.1),
+            5,
+        );
+        let individuals = vec![vec![0u8; 4]];
+
+        expect_that!(&mutator.generation(), eq(0));
+        for (generation, expected) in [(1, 0.5), (2, 0.4), (4, 0.2), (7, 0.1)].iter() {
+            MutationOp::<Vec<u8>>::adapt(&mut mutator, &signal(*generation, &individuals));
+
+            expect_that!(&mutator.generation(), eq(*generation));
+            expect_close(mutator.mutation_rate(), *expected);
+        }
+    }
+
+    #[test]
+    fn scheduled_crossover_sets_the_crossover_rate_in_each_generation() {
+        let mut breeder = ScheduledCrossover::new(
+            ProbabilisticCrossover::new(UniformCrossBreeder::new(), 1.0),
+            LinearDecay::new(0.9, 0.6),
+            4,
+        );
+        let individuals = vec![vec![0u8; 4]];
+
+        for (generation, expected) in [(1, 0.9), (2, 0.8), (3, 0.7), (4, 0.6)].iter() {
+            CrossoverOp::<Vec<u8>>::adapt(&mut breeder, &signal(*generation, &individuals));
+
+            expect_that!(&breeder.generation(), eq(*generation));
+            expect_close(breeder.crossover_rate(), *expected);
+        }
+    }
+
+    #[test]
+    fn schedules_out_of_the_valid_range_are_rejected() {
+        let mutator = ScheduledMutator::new(
+            RandomValueMutator::<Vec<u8>>::new(0.1, 0, 9),
+            LinearDecay::new(1.5, 0.1),
+            5,
+        );
+
+        expect_that!(&mutator.validate().is_err(), eq(true));
+    }
+}
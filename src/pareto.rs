@@ -0,0 +1,351 @@
+//! The `pareto` module provides the building blocks for multi-objective
+//! optimization based on Pareto dominance.
+//!
+//! In multi-objective optimization a solution is evaluated by several
+//! objective values, e.g. the cost and the weight of a construction. The
+//! objective values of a solution are provided by the
+//! `genetic::MultiObjectiveFitness` trait. One solution dominates another
+//! solution if it is at least as good in all objectives and better in at least
+//! one objective. The `ParetoDominance` determines the dominance between two
+//! solutions and the `non_dominated_sort` function sorts solutions into
+//! Pareto fronts as used by NSGA-style algorithms.
+
+use crate::genetic::MultiObjectiveFitness;
+use std::cmp::Ordering;
+
+/// The `Direction` defines whether an objective is to be maximized or to be
+/// minimized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Higher objective values are better.
+    Maximize,
+    /// Lower objective values are better.
+    Minimize,
+}
+
+/// The `Dominance` is the relation between two solutions in terms of Pareto
+/// dominance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dominance {
+    /// The first solution dominates the second one.
+    Dominates,
+    /// The first solution is dominated by the second one.
+    IsDominated,
+    /// None of the solutions dominates the other one.
+    NonDominated,
+}
+
+/// The `ParetoDominance` determines the dominance between two solutions by
+/// comparing their objective values.
+///
+/// The direction of each objective is defined by a `Direction`. Objectives
+/// without a defined direction are maximized, which is consistent with the
+/// single-objective `genetic::Fitness` where higher values are better.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParetoDominance {
+    /// The directions of the objectives in the order of the objective values.
+    directions: Vec<Direction>,
+}
+
+impl ParetoDominance {
+    /// Constructs a new `ParetoDominance` with the given directions of the
+    /// objectives.
+    pub fn new(directions: Vec<Direction>) -> Self {
+        ParetoDominance { directions }
+    }
+
+    /// Constructs a new `ParetoDominance` for which all objectives are
+    /// maximized.
+    pub fn maximizing() -> Self {
+        ParetoDominance::default()
+    }
+
+    /// Returns the directions of the objectives.
+    pub fn directions(&self) -> &[Direction] {
+        &self.directions
+    }
+
+    /// Determines the dominance of the first solution over the second one
+    /// given their objective values.
+    ///
+    /// Both solutions must have the same number of objective values.
+    pub fn compare(&self, objectives1: &[f64], objectives2: &[f64]) -> Dominance {
+        assert_eq!(
+            objectives1.len(),
+            objectives2.len(),
+            "solutions must have the same number of objectives"
+        );
+        let mut first_is_better = false;
+        let mut second_is_better = false;
+        for (index, (value1, value2)) in objectives1.iter().zip(objectives2).enumerate() {
+            let ordering = match self.directions.get(index) {
+                Some(Direction::Minimize) => value2.partial_cmp(value1),
+                _ => value1.partial_cmp(value2),
+            };
+            match ordering {
+                Some(Ordering::Greater) => first_is_better = true,
+                Some(Ordering::Less) => second_is_better = true,
+                // incomparable values (NaN) are treated as equal
+                Some(Ordering::Equal) | None => {},
+            }
+        }
+        match (first_is_better, second_is_better) {
+            (true, false) => Dominance::Dominates,
+            (false, true) => Dominance::IsDominated,
+            _ => Dominance::NonDominated,
+        }
+    }
+
+    /// Returns true if the first solution dominates the second one.
+    pub fn dominates<T>(&self, solution1: &T, solution2: &T) -> bool
+    where
+        T: MultiObjectiveFitness,
+    {
+        self.compare(&solution1.objectives(), &solution2.objectives()) == Dominance::Dominates
+    }
+}
+
+/// Sorts the given solutions into Pareto fronts.
+///
+/// The first front contains the indices of all solutions that are not
+/// dominated by any other solution. The second front contains the indices of
+/// all solutions that are only dominated by solutions of the first front and
+/// so on. The indices within a front are in ascending order.
+///
+/// This is the fast non-dominated sorting algorithm as used by NSGA-II. Its
+/// complexity is O(m * n^2) for n solutions with m objectives.
+pub fn non_dominated_sort<T>(solutions: &[T], dominance: &ParetoDominance) -> Vec<Vec<usize>>
+where
+    T: MultiObjectiveFitness,
+{
+    let objectives: Vec<Vec<f64>> = solutions.iter().map(|s| s.objectives()).collect();
+    let num_solutions = objectives.len();
+    // for each solution the indices of the solutions that it dominates
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); num_solutions];
+    // for each solution the number of solutions that dominate it
+    let mut num_dominating = vec![0usize; num_solutions];
+    for i in 0..num_solutions {
+        for j in (i + 1)..num_solutions {
+            match dominance.compare(&objectives[i], &objectives[j]) {
+                Dominance::Dominates => {
+                    dominated[i].push(j);
+                    num_dominating[j] += 1;
+                },
+                Dominance::IsDominated => {
+                    dominated[j].push(i);
+                    num_dominating[i] += 1;
+                },
+                Dominance::NonDominated => {},
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..num_solutions)
+        .filter(|index| num_dominating[*index] == 0)
+        .collect();
+    while !front.is_empty() {
+        let mut next_front = Vec::new();
+        for index in &front {
+            for dominated_index in &dominated[*index] {
+                num_dominating[*dominated_index] -= 1;
+                if num_dominating[*dominated_index] == 0 {
+                    next_front.push(*dominated_index);
+                }
+            }
+        }
+        next_front.sort_unstable();
+        fronts.push(front);
+        front = next_front;
+    }
+    fronts
+}
+
+/// Calculates the crowding distance of each solution within one Pareto front.
+///
+/// The crowding distance of a solution is the sum over all objectives of the
+/// distance between its two neighbors in that objective, normalized by the
+/// range of the objective values in the front. The solutions with the lowest
+/// and the highest value of an objective are boundary solutions and get an
+/// infinite distance. A greater crowding distance means that the solution is
+/// located in a less crowded region of the front.
+///
+/// Objectives for which all solutions have the same value do not contribute
+/// to the distance of any solution. If the front contains at most
+/// two solutions all of them are boundary solutions.
+pub fn crowding_distance(front: &[Vec<f64>]) -> Vec<f64> {
+    let num_solutions = front.len();
+    if num_solutions <= 2 {
+        return vec![f64::INFINITY; num_solutions];
+    }
+    let mut distances = vec![0.; num_solutions];
+    let num_objectives = front[0].len();
+    let mut sorted: Vec<usize> = (0..num_solutions).collect();
+    for objective in 0..num_objectives {
+        let values: Vec<f64> = front.iter().map(|solution| solution[objective]).collect();
+        // stable sort so that solutions with equal values keep their order
+        sorted.sort_by(|x, y| values[*x].total_cmp(&values[*y]));
+        let range = values[sorted[num_solutions - 1]] - values[sorted[0]];
+        if range <= 0. || !range.is_finite() {
+            continue;
+        }
+        distances[sorted[0]] = f64::INFINITY;
+        distances[sorted[num_solutions - 1]] = f64::INFINITY;
+        for position in 1..(num_solutions - 1) {
+            let lower = values[sorted[position - 1]];
+            let upper = values[sorted[position + 1]];
+            distances[sorted[position]] += (upper - lower) / range;
+        }
+    }
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn compare_detects_dominance_of_maximized_objectives() {
+        let dominance = ParetoDominance::maximizing();
+
+        expect_that!(
+            &dominance.compare(&[2., 3.], &[1., 3.]),
+            eq(Dominance::Dominates)
+        );
+        expect_that!(
+            &dominance.compare(&[1., 3.], &[2., 3.]),
+            eq(Dominance::IsDominated)
+        );
+        expect_that!(
+            &dominance.compare(&[1., 4.], &[2., 3.]),
+            eq(Dominance::NonDominated)
+        );
+        expect_that!(
+            &dominance.compare(&[2., 3.], &[2., 3.]),
+            eq(Dominance::NonDominated)
+        );
+    }
+
+    #[test]
+    fn compare_respects_the_direction_of_the_objectives() {
+        // minimize cost, maximize quality
+        let dominance = ParetoDominance::new(vec![Direction::Minimize, Direction::Maximize]);
+
+        expect_that!(
+            &dominance.compare(&[10., 5.], &[20., 5.]),
+            eq(Dominance::Dominates)
+        );
+        expect_that!(
+            &dominance.compare(&[10., 4.], &[10., 5.]),
+            eq(Dominance::IsDominated)
+        );
+        expect_that!(
+            &dominance.compare(&[10., 4.], &[20., 5.]),
+            eq(Dominance::NonDominated)
+        );
+    }
+
+    #[test]
+    fn dominates_compares_multi_objective_fitness_values() {
+        let dominance = ParetoDominance::maximizing();
+
+        expect_that!(
+            &dominance.dominates(&vec![3., 3.], &vec![2., 3.]),
+            is(eq(true))
+        );
+        expect_that!(
+            &dominance.dominates(&vec![2., 3.], &vec![3., 3.]),
+            is(eq(false))
+        );
+    }
+
+    #[test]
+    fn non_dominated_sort_finds_hand_crafted_fronts() {
+        // minimize both objectives
+        let dominance = ParetoDominance::new(vec![Direction::Minimize, Direction::Minimize]);
+        let solutions: Vec<Vec<f64>> = vec![
+            vec![3., 3.], // front 2
+            vec![1., 5.], // front 1
+            vec![5., 1.], // front 1
+            vec![2., 2.], // front 1
+            vec![4., 4.], // front 3
+            vec![2., 6.], // front 2
+            vec![6., 6.], // front 4
+        ];
+
+        let fronts = non_dominated_sort(&solutions, &dominance);
+
+        expect_that!(
+            &fronts,
+            eq(vec![vec![1, 2, 3], vec![0, 5], vec![4], vec![6]])
+        );
+    }
+
+    #[test]
+    fn non_dominated_sort_puts_mutually_non_dominated_solutions_into_one_front() {
+        let solutions: Vec<Vec<f64>> = vec![vec![1., 4.], vec![2., 3.], vec![3., 2.], vec![4., 1.]];
+
+        let fronts = non_dominated_sort(&solutions, &ParetoDominance::maximizing());
+
+        expect_that!(&fronts, eq(vec![vec![0, 1, 2, 3]]));
+    }
+
+    #[test]
+    fn non_dominated_sort_of_no_solutions_has_no_fronts() {
+        let solutions: Vec<Vec<f64>> = Vec::new();
+
+        let fronts = non_dominated_sort(&solutions, &ParetoDominance::maximizing());
+
+        expect_that!(&fronts.is_empty(), is(eq(true)));
+    }
+
+    #[test]
+    fn crowding_distance_of_boundary_solutions_is_infinite() {
+        let front = vec![vec![0., 4.], vec![1., 3.], vec![3., 1.], vec![4., 0.]];
+
+        let distances = crowding_distance(&front);
+
+        expect_that!(&distances[0], eq(f64::INFINITY));
+        expect_that!(&distances[3], eq(f64::INFINITY));
+        // (3 - 0) / 4 + (4 - 1) / 4
+        expect_that!(&distances[1], eq(1.5));
+        // (4 - 1) / 4 + (3 - 0) / 4
+        expect_that!(&distances[2], eq(1.5));
+    }
+
+    #[test]
+    fn crowding_distance_prefers_less_crowded_solutions() {
+        let front = vec![
+            vec![0., 10.],
+            vec![1., 9.],
+            vec![2., 8.],
+            vec![8., 2.],
+            vec![10., 0.],
+        ];
+
+        let distances = crowding_distance(&front);
+
+        expect_that!(&(distances[3] > distances[1]), is(eq(true)));
+        expect_that!(&(distances[2] > distances[1]), is(eq(true)));
+    }
+
+    #[test]
+    fn crowding_distance_of_small_fronts_is_infinite() {
+        expect_that!(&crowding_distance(&[]).is_empty(), is(eq(true)));
+        expect_that!(&crowding_distance(&[vec![1., 2.]]), eq(vec![f64::INFINITY]));
+        expect_that!(
+            &crowding_distance(&[vec![1., 2.], vec![2., 1.]]),
+            eq(vec![f64::INFINITY, f64::INFINITY])
+        );
+    }
+
+    #[test]
+    fn crowding_distance_ignores_objectives_with_equal_values() {
+        let front = vec![vec![0., 5.], vec![1., 5.], vec![4., 5.]];
+
+        let distances = crowding_distance(&front);
+
+        expect_that!(&distances[1], eq(1.));
+    }
+}
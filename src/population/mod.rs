@@ -8,7 +8,9 @@
 //!
 //! Default implementations of `GenomeBuilder` are provided for the binary
 //! encoded types `fixedbitset::FixedBitSet` and `Vec<bool>` and for the
-//! value encoded type `Vec<T>`.
+//! value encoded type `Vec<T>`. The `IntGenomeSpec` builds integer genomes
+//! and the matching `mutation::value::RandomValueMutator`, so that generated
+//! and mutated values are in the same range.
 //!
 //! ## Examples
 //!
@@ -48,7 +50,7 @@
 //!
 //! fn main() {
 //!     let population: Population<Vec<i64>> = build_population()
-//!         .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -200, 201))
+//!         .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -200, 200))
 //!         .of_size(200)
 //!         .uniform_at_random();
 //!
@@ -99,12 +101,25 @@
 
 use crate::{
     genetic::Genotype,
+    genotype::{
+        tree::{ExpressionTree, GrowthMethod, PrimitiveSet},
+        BinaryGenome,
+    },
+    mutation::value::{RandomGenomeMutation, RandomValueMutation, RandomValueMutator},
     random::{get_rng, random_seed, Prng, Rng, Seed},
 };
 use rand::distributions::uniform::SampleUniform;
-#[cfg(not(target_arch = "wasm32"))]
-use rayon;
-use std::{fmt::Debug, marker::PhantomData};
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use rayon::{
+    self,
+    iter::{IndexedParallelIterator, ParallelIterator},
+    prelude::IntoParallelIterator,
+};
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    sync::Arc,
+};
 
 /// The `Population` defines a set of possible solutions to the optimization
 /// or search problem.
@@ -149,7 +164,7 @@ where
 #[derive(Clone, Debug, PartialEq)]
 pub struct PopulationBuilder;
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
 impl PopulationBuilder {
     fn build_population<B, G>(genome_builder: &B, size: usize, mut rng: Prng) -> Population<G>
     where
@@ -181,7 +196,7 @@ impl PopulationBuilder {
     }
 }
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
 impl PopulationBuilder {
     fn build_population<B, G>(genome_builder: &B, size: usize, mut rng: Prng) -> Population<G>
     where
@@ -209,6 +224,17 @@ where
     fn build_genome<R>(&self, index: usize, rng: &mut R) -> G
     where
         R: Rng + Sized;
+
+    /// Returns whether the given genome is a valid genome for this builder,
+    /// e.g. whether its length is equal to the length of the genomes built by
+    /// this builder.
+    ///
+    /// This function is used to validate the individuals that are put into a
+    /// population in addition to the built ones. The default implementation
+    /// accepts every genome.
+    fn is_valid_genome(&self, _genome: &G) -> bool {
+        true
+    }
 }
 
 #[allow(missing_copy_implementations)]
@@ -257,6 +283,7 @@ where
             _g: self._g,
             genome_builder: self.genome_builder,
             population_size,
+            initial_individuals: Vec::new(),
         }
     }
 }
@@ -270,6 +297,7 @@ where
     _g: PhantomData<G>,
     genome_builder: B,
     population_size: usize,
+    initial_individuals: Vec<G>,
 }
 
 impl<B, G> PopulationWithGenomeBuilderAndSizeBuilder<B, G>
@@ -277,20 +305,69 @@ where
     B: GenomeBuilder<G>,
     G: Genotype,
 {
+    /// Puts the given individuals into the population in addition to the
+    /// built genomes, e.g. good candidate solutions found by a heuristic.
+    ///
+    /// The given individuals become the first members of the population in
+    /// the given order. The remaining members up to the size of the
+    /// population are built by the `GenomeBuilder`. The individuals may
+    /// contain duplicates. Calling this function several times adds all the
+    /// given individuals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the given individuals is not a valid genome for the
+    /// `GenomeBuilder` as determined by `GenomeBuilder::is_valid_genome`.
+    pub fn with_initial_individuals(mut self, individuals: Vec<G>) -> Self {
+        for (index, individual) in individuals.iter().enumerate() {
+            assert!(
+                self.genome_builder.is_valid_genome(individual),
+                "the initial individual at index {} is not a valid genome: {:?}",
+                self.initial_individuals.len() + index,
+                individual
+            );
+        }
+        self.initial_individuals.extend(individuals);
+        self
+    }
+
+    /// Builds the population with genomes generated uniformly at random.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more initial individuals than the size of the
+    /// population.
     pub fn uniform_at_random(self) -> Population<G> {
-        PopulationBuilder::build_population(
-            &self.genome_builder,
-            self.population_size,
-            get_rng(random_seed()),
-        )
+        self.build_with_rng(get_rng(random_seed()))
     }
 
+    /// Builds the population with genomes generated by a `Prng` initialized
+    /// with the given seed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more initial individuals than the size of the
+    /// population.
     pub fn using_seed(self, seed: Seed) -> Population<G> {
-        PopulationBuilder::build_population(
+        self.build_with_rng(get_rng(seed))
+    }
+
+    fn build_with_rng(self, rng: Prng) -> Population<G> {
+        let num_initial = self.initial_individuals.len();
+        assert!(
+            num_initial <= self.population_size,
+            "the number of initial individuals {} is greater than the size of the population {}",
+            num_initial,
+            self.population_size
+        );
+        let built = PopulationBuilder::build_population(
             &self.genome_builder,
-            self.population_size,
-            get_rng(seed),
-        )
+            self.population_size - num_initial,
+            rng,
+        );
+        let mut individuals = self.initial_individuals;
+        individuals.extend(built.individuals);
+        Population { individuals }
     }
 }
 
@@ -300,6 +377,159 @@ pub fn build_population() -> EmptyPopulationBuilder {
     }
 }
 
+/// Builds the genome at the given index with the given `Prng`.
+type GenomeBuild<G> = Arc<dyn Fn(usize, &mut Prng) -> G + Send + Sync>;
+
+/// The `PopulationGenerator` generates populations of a fixed size with a
+/// `GenomeBuilder`.
+///
+/// In contrast to a `Population`, which is built once, the
+/// `PopulationGenerator` keeps the `GenomeBuilder` and the size of the
+/// population, so that a fresh population can be generated whenever it is
+/// needed, e.g. by the `ga::GeneticAlgorithm` each time the simulation is
+/// started or reset. The genomes are built by a `Prng` whose seed is drawn
+/// from the given random number generator. Thus the generated population is
+/// determined by the seed of the simulation.
+///
+/// Besides `generate` the `PopulationGenerator` provides `generate_seeded`
+/// and its parallel counterpart `par_generate`. Both build each genome with
+/// its own `Prng` whose seed is derived from one master seed drawn from the
+/// given random number generator. Thus they generate exactly the same
+/// population for the same master seed, regardless of the number of threads
+/// used by `par_generate`.
+///
+/// Two `PopulationGenerator`s are equal if they are clones of each other and
+/// generate populations of the same size.
+#[derive(Clone)]
+pub struct PopulationGenerator<G>
+where
+    G: Genotype,
+{
+    /// Builds a population of the given size with the given `Prng`.
+    build: Arc<dyn Fn(usize, Prng) -> Population<G> + Send + Sync>,
+    /// Builds the genome at the given index with the given `Prng`.
+    build_genome: GenomeBuild<G>,
+    /// The number of individuals of the generated populations.
+    population_size: usize,
+}
+
+impl<G> PopulationGenerator<G>
+where
+    G: Genotype,
+{
+    /// Constructs a new `PopulationGenerator` that generates populations of
+    /// `population_size` individuals built by the given `GenomeBuilder`.
+    pub fn new<B>(genome_builder: B, population_size: usize) -> Self
+    where
+        B: GenomeBuilder<G> + Send + 'static,
+    {
+        let genome_builder = Arc::new(genome_builder);
+        let population_builder = genome_builder.clone();
+        PopulationGenerator {
+            build: Arc::new(move |size, rng| {
+                PopulationBuilder::build_population(&*population_builder, size, rng)
+            }),
+            build_genome: Arc::new(move |index, rng| genome_builder.build_genome(index, rng)),
+            population_size,
+        }
+    }
+
+    /// Returns the number of individuals of the generated populations.
+    pub fn population_size(&self) -> usize {
+        self.population_size
+    }
+
+    /// Generates a new population.
+    pub fn generate<R>(&self, rng: &mut R) -> Population<G>
+    where
+        R: Rng + Sized,
+    {
+        let mut seed = Seed::default();
+        rng.fill(&mut seed);
+        (self.build)(self.population_size, get_rng(seed))
+    }
+
+    /// Generates a new population one genome after the other, building each
+    /// genome with its own `Prng`.
+    ///
+    /// The seeds of the genomes are derived from a master seed drawn from the
+    /// given random number generator. The generated population is the same
+    /// as the one generated by `par_generate` with the same random number
+    /// generator.
+    pub fn generate_seeded<R>(&self, rng: &mut R) -> Population<G>
+    where
+        R: Rng + Sized,
+    {
+        Population {
+            individuals: self
+                .genome_seeds(rng)
+                .into_iter()
+                .enumerate()
+                .map(|(index, seed)| (self.build_genome)(index, &mut get_rng(seed)))
+                .collect(),
+        }
+    }
+
+    /// Generates a new population building the genomes in parallel.
+    ///
+    /// Each genome is built with its own `Prng` seeded like in
+    /// `generate_seeded`, so the generated population only depends on the
+    /// master seed drawn from the given random number generator and not on
+    /// the number of threads.
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    pub fn par_generate<R>(&self, rng: &mut R) -> Population<G>
+    where
+        R: Rng + Sized,
+    {
+        Population {
+            individuals: self
+                .genome_seeds(rng)
+                .into_par_iter()
+                .enumerate()
+                .map(|(index, seed)| (self.build_genome)(index, &mut get_rng(seed)))
+                .collect(),
+        }
+    }
+
+    /// Derives the seeds of the genomes from a master seed drawn from the
+    /// given random number generator.
+    fn genome_seeds<R>(&self, rng: &mut R) -> Vec<Seed>
+    where
+        R: Rng + Sized,
+    {
+        let mut master_seed = Seed::default();
+        rng.fill(&mut master_seed);
+        let mut master_rng = get_rng(master_seed);
+        (0..self.population_size)
+            .map(|_| {
+                let mut seed = Seed::default();
+                master_rng.fill(&mut seed);
+                seed
+            })
+            .collect()
+    }
+}
+
+impl<G> Debug for PopulationGenerator<G>
+where
+    G: Genotype,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PopulationGenerator")
+            .field("population_size", &self.population_size)
+            .finish()
+    }
+}
+
+impl<G> PartialEq for PopulationGenerator<G>
+where
+    G: Genotype,
+{
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.build, &other.build) && self.population_size == other.population_size
+    }
+}
+
 /// A `GenomeBuilder` that builds binary encoded `genetic::Genotype`s.
 ///
 /// The default implementation can build `fixedbitset::FixedBitSet` genomes
@@ -325,13 +555,18 @@ impl GenomeBuilder<Vec<bool>> for BinaryEncodedGenomeBuilder {
     {
         (0..self.genome_length).map(|_| rng.gen()).collect()
     }
+
+    fn is_valid_genome(&self, genome: &Vec<bool>) -> bool {
+        genome.len() == self.genome_length
+    }
 }
 
 /// A `GenomeBuilder` that builds value encoded `genetic::Genotype`s.
 ///
 /// The default implementation can build `Vec<T>` genomes. The values of
 /// `T` are generated randomly in the range between a min value and a max
-/// value.
+/// value. Both bounds are inclusive like the bounds of the
+/// `mutation::value::RandomValueMutator`.
 #[derive(Clone, Debug, PartialEq)]
 pub struct ValueEncodedGenomeBuilder<V> {
     genome_length: usize,
@@ -344,7 +579,7 @@ impl<V> ValueEncodedGenomeBuilder<V> {
     /// value encoded genomes of length specified by the given `genome_length`.
     ///
     /// The values of the generated genomes are in the range between the given
-    /// `min_value` (inclusive) and `max_value` (exclusive).
+    /// `min_value` and `max_value` (both inclusive).
     pub fn new(genome_length: usize, min_value: V, max_value: V) -> Self {
         ValueEncodedGenomeBuilder {
             genome_length,
@@ -363,9 +598,282 @@ where
         R: Rng + Sized,
     {
         (0..self.genome_length)
-            .map(|_| rng.gen_range(self.min_value.clone()..self.max_value.clone()))
+            .map(|_| rng.gen_range(self.min_value.clone()..=self.max_value.clone()))
             .collect()
     }
+
+    fn is_valid_genome(&self, genome: &Vec<V>) -> bool {
+        genome.len() == self.genome_length
+    }
+}
+
+/// A `GenomeBuilder` that grows random `genotype::tree::ExpressionTree`s
+/// from a `PrimitiveSet` of functions and terminals.
+///
+/// The trees are grown by the `GrowthMethod` up to `max_depth`. With
+/// `RampedHalfAndHalf` the depths of the trees are ramped by their index in
+/// the population from `min_depth` to `max_depth`: the trees at the indices
+/// `2 * k` and `2 * k + 1` get the depth `min_depth + k % (max_depth -
+/// min_depth + 1)`, the former grown by `Full` and the latter by `Grow`. The
+/// other methods ignore `min_depth`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpressionTreeBuilder<T> {
+    primitives: PrimitiveSet<T>,
+    min_depth: usize,
+    max_depth: usize,
+    method: GrowthMethod,
+}
+
+impl<T> ExpressionTreeBuilder<T> {
+    /// Returns a new instance of the `ExpressionTreeBuilder` that grows
+    /// trees from the given primitives by the given `GrowthMethod` with
+    /// depths between `min_depth` and `max_depth` (both inclusive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_depth` is greater than `max_depth`.
+    pub fn new(
+        primitives: PrimitiveSet<T>,
+        min_depth: usize,
+        max_depth: usize,
+        method: GrowthMethod,
+    ) -> Self {
+        assert!(
+            min_depth <= max_depth,
+            "min_depth {} must not be greater than max_depth {}",
+            min_depth,
+            max_depth
+        );
+        ExpressionTreeBuilder {
+            primitives,
+            min_depth,
+            max_depth,
+            method,
+        }
+    }
+
+    /// Returns the primitives the trees are grown from.
+    pub fn primitives(&self) -> &PrimitiveSet<T> {
+        &self.primitives
+    }
+
+    /// Returns the minimum depth of the trees grown by `RampedHalfAndHalf`.
+    pub fn min_depth(&self) -> usize {
+        self.min_depth
+    }
+
+    /// Returns the maximum depth of the trees.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Returns the method the trees are grown by.
+    pub fn method(&self) -> GrowthMethod {
+        self.method
+    }
+}
+
+impl<T> GenomeBuilder<ExpressionTree<T>> for ExpressionTreeBuilder<T>
+where
+    T: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn build_genome<R>(&self, index: usize, rng: &mut R) -> ExpressionTree<T>
+    where
+        R: Rng + Sized,
+    {
+        match self.method {
+            GrowthMethod::RampedHalfAndHalf => {
+                let num_depths = self.max_depth - self.min_depth + 1;
+                let depth = self.min_depth + (index / 2) % num_depths;
+                let method = if index % 2 == 0 {
+                    GrowthMethod::Full
+                } else {
+                    GrowthMethod::Grow
+                };
+                self.primitives.grow_tree(method, depth, rng)
+            },
+            method => self.primitives.grow_tree(method, self.max_depth, rng),
+        }
+    }
+
+    fn is_valid_genome(&self, genome: &ExpressionTree<T>) -> bool {
+        genome.depth() <= self.max_depth
+    }
+}
+
+/// The integer types whose values can be generated by the `IntGenomeSpec`.
+pub trait IntegerValue:
+    Copy + Debug + PartialOrd + SampleUniform + RandomValueMutation + Send + Sync
+{
+}
+
+macro_rules! impl_integer_value {
+    ($($t:ty),*) => {
+        $(
+            impl IntegerValue for $t {}
+        )*
+    }
+}
+
+impl_integer_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// The `IntGenomeSpec` specifies integer genomes of a fixed length whose
+/// values are in the range between `min_value` and `max_value`, both
+/// inclusive.
+///
+/// The spec is a `GenomeBuilder` for the initial population and builds the
+/// `mutation::value::RandomValueMutator` by `mutator`. Thus the generated
+/// values and the mutated values are always in the same range. This is in
+/// contrast to the `ValueEncodedGenomeBuilder`, which excludes the max value,
+/// while the `RandomValueMutator` includes it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntGenomeSpec<V> {
+    genome_length: usize,
+    min_value: V,
+    max_value: V,
+}
+
+impl<V> IntGenomeSpec<V>
+where
+    V: IntegerValue,
+{
+    /// Returns a new instance of the `IntGenomeSpec` for genomes of the given
+    /// `genome_length` with values between `min_value` and `max_value` (both
+    /// inclusive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_value` is not less than `max_value`.
+    pub fn new(genome_length: usize, min_value: V, max_value: V) -> Self {
+        assert!(
+            min_value < max_value,
+            "min_value {:?} must be less than max_value {:?}",
+            min_value,
+            max_value
+        );
+        IntGenomeSpec {
+            genome_length,
+            min_value,
+            max_value,
+        }
+    }
+
+    /// Returns the length of the genomes.
+    pub fn genome_length(&self) -> usize {
+        self.genome_length
+    }
+
+    /// Returns the smallest value of a gene.
+    pub fn min_value(&self) -> V {
+        self.min_value
+    }
+
+    /// Returns the greatest value of a gene.
+    pub fn max_value(&self) -> V {
+        self.max_value
+    }
+
+    /// Returns whether the given value is within the range of this spec.
+    pub fn contains(&self, value: &V) -> bool {
+        self.min_value <= *value && *value <= self.max_value
+    }
+
+    /// Returns a `RandomValueMutator` with the given mutation rate that
+    /// mutates the genes within the range of this spec.
+    pub fn mutator<G>(&self, mutation_rate: f64) -> RandomValueMutator<G>
+    where
+        G: Genotype<Dna = V> + RandomGenomeMutation,
+    {
+        RandomValueMutator::new(mutation_rate, self.min_value, self.max_value)
+    }
+}
+
+impl<V> GenomeBuilder<Vec<V>> for IntGenomeSpec<V>
+where
+    V: IntegerValue,
+{
+    fn build_genome<R>(&self, _: usize, rng: &mut R) -> Vec<V>
+    where
+        R: Rng + Sized,
+    {
+        (0..self.genome_length)
+            .map(|_| rng.gen_range(self.min_value..=self.max_value))
+            .collect()
+    }
+
+    fn is_valid_genome(&self, genome: &Vec<V>) -> bool {
+        genome.len() == self.genome_length && genome.iter().all(|value| self.contains(value))
+    }
+}
+
+impl<V, const N: usize> GenomeBuilder<[V; N]> for IntGenomeSpec<V>
+where
+    V: IntegerValue,
+{
+    fn build_genome<R>(&self, _: usize, rng: &mut R) -> [V; N]
+    where
+        R: Rng + Sized,
+    {
+        assert_eq!(
+            self.genome_length, N,
+            "the genome length must be equal to the length of the array"
+        );
+        std::array::from_fn(|_| rng.gen_range(self.min_value..=self.max_value))
+    }
+
+    fn is_valid_genome(&self, genome: &[V; N]) -> bool {
+        genome.len() == self.genome_length && genome.iter().all(|value| self.contains(value))
+    }
+}
+
+/// Implementation of the `GenomeBuilder` for `BinaryGenome`s, which pack
+/// the random bits of the genome into words.
+impl GenomeBuilder<BinaryGenome> for BinaryEncodedGenomeBuilder {
+    fn build_genome<R>(&self, _index: usize, rng: &mut R) -> BinaryGenome
+    where
+        R: Rng + Sized,
+    {
+        (0..self.genome_length).map(|_| rng.gen::<bool>()).collect()
+    }
+
+    fn is_valid_genome(&self, genome: &BinaryGenome) -> bool {
+        genome.len() == self.genome_length
+    }
+}
+
+/// Implementation of the `GenomeBuilder` for binary encoded genomes of
+/// fixed-size arrays. The genome length of the builder must be equal to the
+/// length of the array.
+impl<const N: usize> GenomeBuilder<[bool; N]> for BinaryEncodedGenomeBuilder {
+    fn build_genome<R>(&self, _index: usize, rng: &mut R) -> [bool; N]
+    where
+        R: Rng + Sized,
+    {
+        assert_eq!(
+            self.genome_length, N,
+            "the genome length must be equal to the length of the array"
+        );
+        std::array::from_fn(|_| rng.gen())
+    }
+}
+
+/// Implementation of the `GenomeBuilder` for value encoded genomes of
+/// fixed-size arrays. The genome length of the builder must be equal to the
+/// length of the array.
+impl<V, const N: usize> GenomeBuilder<[V; N]> for ValueEncodedGenomeBuilder<V>
+where
+    V: Clone + Debug + PartialEq + PartialOrd + SampleUniform + Send + Sync,
+{
+    fn build_genome<R>(&self, _: usize, rng: &mut R) -> [V; N]
+    where
+        R: Rng + Sized,
+    {
+        assert_eq!(
+            self.genome_length, N,
+            "the genome length must be equal to the length of the array"
+        );
+        std::array::from_fn(|_| rng.gen_range(self.min_value.clone()..=self.max_value.clone()))
+    }
 }
 
 #[cfg(feature = "fixedbitset")]
@@ -385,6 +893,10 @@ mod fixedbitset_genome_builder {
             }
             genome
         }
+
+        fn is_valid_genome(&self, genome: &FixedBitSet) -> bool {
+            genome.len() == self.genome_length
+        }
     }
 }
 
@@ -405,6 +917,10 @@ mod smallvec_genome_builder {
         {
             (0..self.genome_length).map(|_| rng.gen()).collect()
         }
+
+        fn is_valid_genome(&self, genome: &SmallVec<A>) -> bool {
+            genome.len() == self.genome_length
+        }
     }
 
     impl<A, V> GenomeBuilder<SmallVec<A>> for ValueEncodedGenomeBuilder<V>
@@ -417,9 +933,13 @@ mod smallvec_genome_builder {
             R: Rng + Sized,
         {
             (0..self.genome_length)
-                .map(|_| rng.gen_range(self.min_value.clone()..self.max_value.clone()))
+                .map(|_| rng.gen_range(self.min_value.clone()..=self.max_value.clone()))
                 .collect()
         }
+
+        fn is_valid_genome(&self, genome: &SmallVec<A>) -> bool {
+            genome.len() == self.genome_length
+        }
     }
 }
 
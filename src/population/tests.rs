@@ -62,3 +62,102 @@ mod population_builder {
         }
     }
 }
+
+mod value_encoded_genome_builder {
+    use super::*;
+    use crate::random::get_rng;
+
+    #[test]
+    fn generates_values_between_both_bounds_inclusive() {
+        let builder = ValueEncodedGenomeBuilder::new(100, -3i16, 3);
+        let values: Vec<i16> = builder.build_genome(0, &mut get_rng([42; 32]));
+
+        for value in -3..=3 {
+            assert!(values.contains(&value));
+        }
+        assert!(values.iter().all(|value| (-3..=3).contains(value)));
+    }
+}
+
+mod int_genome_spec {
+    use super::*;
+    use crate::{operator::MutationOp, random::get_rng};
+
+    #[test]
+    fn generated_and_mutated_genes_are_within_the_bounds() {
+        let spec = IntGenomeSpec::new(20, -3i16, 3);
+        let mutator = spec.mutator(0.5);
+        let mut rng = get_rng([42; 32]);
+        let mut generated = vec![false; 7];
+        let mut mutated = vec![false; 7];
+
+        for index in 0..200 {
+            let genome: Vec<i16> = spec.build_genome(index, &mut rng);
+            assert!(spec.is_valid_genome(&genome));
+            for value in &genome {
+                generated[(value + 3) as usize] = true;
+            }
+            let genome = mutator.mutate(genome, &mut rng);
+            assert!(spec.is_valid_genome(&genome));
+            for value in &genome {
+                mutated[(value + 3) as usize] = true;
+            }
+        }
+
+        // both bounds are included in the generated and the mutated values
+        assert_eq!(generated, vec![true; 7]);
+        assert_eq!(mutated, vec![true; 7]);
+    }
+
+    #[test]
+    fn builds_array_genomes_within_the_bounds() {
+        let spec = IntGenomeSpec::new(8, 32u8, 126);
+        let mutator = spec.mutator::<[u8; 8]>(1.0);
+        let mut rng = get_rng([42; 32]);
+
+        for index in 0..100 {
+            let genome: [u8; 8] = spec.build_genome(index, &mut rng);
+            assert!(spec.is_valid_genome(&genome));
+            assert!(spec.is_valid_genome(&mutator.mutate(genome, &mut rng)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "min_value 5 must be less than max_value 5")]
+    fn panics_if_min_value_is_not_less_than_max_value() {
+        IntGenomeSpec::new(4, 5u32, 5);
+    }
+}
+
+mod expression_tree_builder {
+    use super::*;
+    use crate::random::get_rng;
+
+    fn primitives() -> PrimitiveSet<char> {
+        PrimitiveSet::new(vec![('+', 2), ('*', 2)], vec!['x', '1'])
+    }
+
+    #[test]
+    fn ramped_half_and_half_ramps_the_depths_of_full_trees() {
+        let builder =
+            ExpressionTreeBuilder::new(primitives(), 2, 4, GrowthMethod::RampedHalfAndHalf);
+        let mut rng = get_rng([42; 32]);
+
+        for index in 0..60 {
+            let tree = builder.build_genome(index, &mut rng);
+            let depth = 2 + (index / 2) % 3;
+            assert!(builder.is_valid_genome(&tree));
+            if index % 2 == 0 {
+                assert_eq!(tree.depth(), depth);
+            } else {
+                assert!(tree.depth() <= depth);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "min_depth 5 must not be greater than max_depth 4")]
+    fn panics_if_min_depth_is_greater_than_max_depth() {
+        ExpressionTreeBuilder::new(primitives(), 5, 4, GrowthMethod::Grow);
+    }
+}
@@ -1,6 +1,9 @@
 // Genetic
 //
-pub use crate::genetic::{Fitness, FitnessFunction, Genotype, Phenotype};
+pub use crate::genetic::{
+    Fitness, FitnessFunction, GenomeDistance, Genotype, GenotypeToPhenotype, MultiObjectiveFitness,
+    Phenotype,
+};
 
 // Algorithm
 //
@@ -16,11 +19,16 @@ pub use crate::population::{build_population, GenomeBuilder, Population};
 
 // Simulation
 //
-pub use crate::simulation::{simulator::simulate, SimResult, Simulation, SimulationBuilder};
+pub use crate::simulation::{
+    observer::SimulationObserver, simulator::simulate, SimResult, Simulation, SimulationBuilder,
+};
 
 // Termination
 //
 pub use crate::termination::{
     combinator::{and, or, And, Or},
+    convergence::*,
+    custom::*,
     limit::*,
+    StopReason,
 };
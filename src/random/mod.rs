@@ -8,6 +8,7 @@ pub use rand::{
 };
 
 use crate::genetic::AsScalar;
+use rand::rngs::{SmallRng, StdRng};
 use rand_xoshiro::Xoshiro256Plus;
 
 /// The `Prng` is the pseudo random number generator used through out this
@@ -23,6 +24,39 @@ pub fn random_seed() -> Seed {
     rng.gen()
 }
 
+/// A `SplittableRng` is a random number generator that can be split into
+/// independent generators, e.g. to generate random numbers on several threads.
+///
+/// The `simulation::Simulator` can be built with any random number generator
+/// that implements this trait. It is implemented for the `Prng` and for the
+/// `StdRng` and `SmallRng` of `rand`.
+pub trait SplittableRng: Rng + Clone + Send {
+    /// Advances this generator and returns a new generator whose sequence of
+    /// random numbers does not overlap with the sequence of this generator.
+    fn split(&mut self) -> Self;
+}
+
+impl SplittableRng for Prng {
+    fn split(&mut self) -> Self {
+        self.jump();
+        self.clone()
+    }
+}
+
+/// The new generator is seeded from the random numbers of this generator.
+impl SplittableRng for StdRng {
+    fn split(&mut self) -> Self {
+        StdRng::from_rng(self).expect("a StdRng never fails to generate a seed")
+    }
+}
+
+/// The new generator is seeded from the random numbers of this generator.
+impl SplittableRng for SmallRng {
+    fn split(&mut self) -> Self {
+        SmallRng::from_rng(self).expect("a SmallRng never fails to generate a seed")
+    }
+}
+
 /// Returns a new `Prng` initialized with the given seed.
 pub fn get_rng(seed: Seed) -> Prng {
     Prng::from_seed(seed)
@@ -81,23 +115,47 @@ where
 }
 
 /// Generates `n` cut points for a slice of given length using the given `Prng`.
-/// The returned cut points are ordered in ascending order.
+/// The returned cut points are distinct and ordered in ascending order.
+///
+/// Each cut point is between 1 and `length - 1`, so that each cut point
+/// splits the slice into two non-empty parts. Thus `n` cut points always
+/// split the slice into `n + 1` non-empty slices.
 pub fn random_n_cut_points<R>(rng: &mut R, n: usize, length: usize) -> Vec<usize>
 where
     R: Rng + Sized,
+{
+    let mut cut_points = Vec::with_capacity(n);
+    random_n_cut_points_into(rng, n, length, &mut cut_points);
+    cut_points
+}
+
+/// Generates `n` cut points for a slice of given length using the given `Prng`
+/// like `random_n_cut_points`, but stores them in the given `Vec`, so that
+/// its buffer can be reused. The previous content of the `Vec` is discarded.
+pub fn random_n_cut_points_into<R>(
+    rng: &mut R,
+    n: usize,
+    length: usize,
+    cut_points: &mut Vec<usize>,
+) where
+    R: Rng + Sized,
 {
     assert!(n > 0);
     assert!(length >= 2 * n);
-    let mut cutpoints = Vec::with_capacity(n);
+    cut_points.clear();
     match n {
         1 => {
-            cutpoints.push(random_index(rng, length));
-        }
-        2 => {
-            let (cp1, cp2) = random_cut_points(rng, length);
-            cutpoints.push(cp1);
-            cutpoints.push(cp2);
-        }
+            cut_points.push(random_index_from_range(rng, 1, length));
+        },
+        2 => loop {
+            let cp1 = random_index_from_range(rng, 1, length);
+            let cp2 = random_index_from_range(rng, 1, length);
+            if cp1 != cp2 {
+                cut_points.push(cp1.min(cp2));
+                cut_points.push(cp1.max(cp2));
+                break;
+            }
+        },
         _ => {
             let slice_len = length / n;
             let mut start = 0;
@@ -108,7 +166,7 @@ where
                 if cutpoint == 0 || cutpoint == length {
                     continue;
                 }
-                cutpoints.push(cutpoint);
+                cut_points.push(cutpoint);
                 count += 1;
                 if count > n {
                     break;
@@ -120,9 +178,8 @@ where
                     end += slice_len;
                 }
             }
-        }
+        },
     }
-    cutpoints
 }
 
 /// Generates a random probability between 0 and 1 using the given `Prng`.
@@ -136,12 +193,28 @@ where
     rng.sample(Open01)
 }
 
+/// Generates a random value from the standard normal distribution, which is
+/// the normal distribution with a mean of 0 and a standard deviation of 1.
+///
+/// The value is generated using the Box-Muller transform.
+pub fn random_standard_normal<R>(rng: &mut R) -> f64
+where
+    R: Rng + Sized,
+{
+    let u1 = random_probability(rng);
+    let u2 = random_probability(rng);
+    (-2. * u1.ln()).sqrt() * (2. * std::f64::consts::PI * u2).cos()
+}
+
 /// The `WeightedDistribution` is used to select values proportional to their
 /// weighted values.
 ///
 /// The values in a `WeightedDistribution` must have a scalar representation.
 /// Thus their types must implement the `genetic::AsScalar` trait. The weights
 /// of the values are calculated from their scalar representation.
+///
+/// If the weights of all values are zero, all values are weighted equally,
+/// so that the distribution degrades to a uniform distribution.
 #[derive(Clone, Debug, PartialEq)]
 pub struct WeightedDistribution<'a, T>
 where
@@ -149,7 +222,9 @@ where
 {
     values: &'a [T],
     sum: f64,
-    weights: Vec<f64>,
+    /// The running sums of the weights, so that an index is selected by a
+    /// binary search instead of a scan over all weights.
+    cumulative_weights: Vec<f64>,
 }
 
 impl<'a, T> WeightedDistribution<'a, T>
@@ -162,7 +237,7 @@ where
         let (weights, weight_sum) = calc_weights_and_sum(values);
         WeightedDistribution {
             values,
-            weights,
+            cumulative_weights: cumulate(weights),
             sum: weight_sum,
         }
     }
@@ -173,7 +248,7 @@ where
     /// values. Usually the pointer is chosen uniformly at random.
     pub fn select(&self, pointer: f64) -> usize {
         assert!(pointer >= 0. && pointer <= self.sum);
-        weighted_select(pointer, &self.weights)
+        weighted_select(pointer, &self.cumulative_weights)
     }
 
     /// Returns the sum of the weights of all values in this
@@ -203,20 +278,32 @@ where
         weight_sum += scalar;
         weights.push(scalar);
     }
+    if weight_sum == 0. {
+        // no value is preferred over any other, so weight them equally
+        weights.iter_mut().for_each(|weight| *weight = 1.);
+        weight_sum = weights.len() as f64;
+    }
     (weights, weight_sum)
 }
 
-/// Selects one index proportional to their weights.
-fn weighted_select(pointer: f64, weights: &[f64]) -> usize {
-    let mut delta = pointer;
-    for (i, weight) in weights.iter().enumerate() {
-        delta -= *weight;
-        if delta <= 0. {
-            return i;
-        }
+/// Replaces each of the given weights by the sum of all weights up to and
+/// including it.
+fn cumulate(mut weights: Vec<f64>) -> Vec<f64> {
+    let mut running_sum = 0.;
+    for weight in weights.iter_mut() {
+        running_sum += *weight;
+        *weight = running_sum;
     }
+    weights
+}
+
+/// Selects one index proportional to their weights, which are given as
+/// running sums, by a binary search for the first running sum that reaches
+/// the pointer.
+fn weighted_select(pointer: f64, cumulative_weights: &[f64]) -> usize {
+    let index = cumulative_weights.partition_point(|running_sum| *running_sum < pointer);
     // when rounding errors occur, return the last item's index
-    weights.len() - 1
+    index.min(cumulative_weights.len() - 1)
 }
 
 #[cfg(test)]
@@ -109,6 +109,13 @@ mod random_n_cut_points {
         ) {
             let cutpoints = random_n_cut_points(&mut get_rng(random_seed()), n, length);
 
+            prop_assert_eq!(cutpoints.len(), n);
+            prop_assert!(cutpoints[0] > 0, "cut point 0 is 0");
+            prop_assert!(
+                cutpoints[n - 1] < length,
+                "cut point {}:{} is not less than the length {}",
+                n - 1, cutpoints[n - 1], length,
+            );
             for i in 0..cutpoints.len() - 1 {
                 if cutpoints[i] == cutpoints[i + 1] {
                     prop_assert!(
@@ -129,9 +136,29 @@ mod random_n_cut_points {
     }
 }
 
+mod random_standard_normal {
+    use super::*;
+
+    #[test]
+    fn random_standard_normal_has_mean_0_and_standard_deviation_1() {
+        let mut rng = get_rng([42; 32]);
+        let samples: Vec<f64> = (0..10_000)
+            .map(|_| random_standard_normal(&mut rng))
+            .collect();
+
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let variance =
+            samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / samples.len() as f64;
+
+        expect_that!(&mean.abs(), less_than(0.05));
+        expect_that!(&(variance.sqrt() - 1.).abs(), less_than(0.05));
+    }
+}
+
 mod weighted_distribution {
 
     use super::*;
+    use crate::random::Rng;
 
     #[test]
     fn weighted_distribution_select() {
@@ -142,7 +169,7 @@ mod weighted_distribution {
 
         let weighted_distribution = WeightedDistribution::from_scalar_values(&weights);
 
-        let mut counter = vec![0, 0, 0, 0];
+        let mut counter = [0, 0, 0, 0];
         for _ in 0..n_sum {
             let random = rng.gen::<f64>() * weighted_distribution.sum();
             let index = weighted_distribution.select(random);
@@ -158,4 +185,27 @@ mod weighted_distribution {
         expect_that!(&counter[3], is(greater_than(40)));
         expect_that!(&counter[3], is(less_than(60)));
     }
+
+    #[test]
+    fn weighted_distribution_of_zero_weights_is_uniform() {
+        let mut rng = Prng::from_seed([42; 32]);
+
+        let weights = vec![0, 0, 0, 0];
+        let n_sum = 1_000;
+
+        let weighted_distribution = WeightedDistribution::from_scalar_values(&weights);
+        expect_that!(&weighted_distribution.sum(), eq(4.));
+
+        let mut counter = [0, 0, 0, 0];
+        for _ in 0..n_sum {
+            let random = rng.gen::<f64>() * weighted_distribution.sum();
+            let index = weighted_distribution.select(random);
+            counter[index] += 1;
+        }
+
+        for count in &counter {
+            expect_that!(count, is(greater_than(200)));
+            expect_that!(count, is(less_than(300)));
+        }
+    }
 }
@@ -0,0 +1,348 @@
+//! The `arithmetic` module provides `operator::CrossoverOp`s that recombine
+//! real valued `genetic::Genotype`s by arithmetic operations on the values of
+//! the parent individuals.
+//!
+//! The provided `operator::CrossoverOp`s are:
+//! * `ArithmeticCrossBreeder` for `Vec` of `f32` or `f64`
+//! * `BlendCrossBreeder` for `Vec` of `f32` or `f64`
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genetic::{Children, Parents},
+    operator::{validate_ratio, CrossoverOp, GeneticOperator},
+    random::{random_probability, Rng},
+};
+use std::fmt::Debug;
+
+/// The `ArithmeticCrossBreeder` operator combines real valued
+/// `genetic::Genotype`s by calculating the weighted average of the values of
+/// two parents.
+///
+/// The parents are recombined pairwise. For each pair of parents `a` and `b`
+/// two complementary children are created where the values at each locus are
+/// `alpha * a + (1 - alpha) * b` and `(1 - alpha) * a + alpha * b`. If the
+/// number of parents is odd the last parent is paired with the first parent
+/// to create one more child.
+///
+/// The `alpha` is either fixed for all loci (whole arithmetic crossover) or
+/// drawn randomly for each locus (local arithmetic crossover).
+///
+/// This crossover operator always creates as many child individuals as there
+/// are individuals in the given `genetic::Parents` parameter.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArithmeticCrossBreeder {
+    /// The fixed weight or `None` if the weight is drawn for each locus.
+    alpha: Option<f64>,
+}
+
+impl ArithmeticCrossBreeder {
+    /// Constructs a new `ArithmeticCrossBreeder` for whole arithmetic
+    /// crossover with the given fixed `alpha`.
+    pub fn new(alpha: f64) -> Self {
+        ArithmeticCrossBreeder { alpha: Some(alpha) }
+    }
+
+    /// Constructs a new `ArithmeticCrossBreeder` for local arithmetic
+    /// crossover which draws a random `alpha` in the range between 0 and 1
+    /// for each locus.
+    pub fn local() -> Self {
+        ArithmeticCrossBreeder { alpha: None }
+    }
+
+    /// Returns the fixed `alpha` or `None` if `alpha` is drawn for each locus.
+    pub fn alpha(&self) -> Option<f64> {
+        self.alpha
+    }
+
+    pub fn set_alpha(&mut self, value: Option<f64>) {
+        self.alpha = value;
+    }
+}
+
+impl GeneticOperator for ArithmeticCrossBreeder {
+    fn name() -> String {
+        "Arithmetic-Cross-Breeder".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        match self.alpha {
+            Some(alpha) => validate_ratio::<Self>("alpha", alpha),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<V> CrossoverOp<Vec<V>> for ArithmeticCrossBreeder
+where
+    V: Clone + Debug + PartialEq + Send + Sync + ArithmeticValue,
+{
+    fn crossover<R>(&self, parents: Parents<Vec<V>>, rng: &mut R) -> Children<Vec<V>>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = parents.len();
+        let mut offspring: Vec<Vec<V>> = Vec::with_capacity(num_parents);
+        let mut index = 0;
+        while offspring.len() < num_parents {
+            let parent1 = &parents[index];
+            let parent2 = &parents[(index + 1) % num_parents];
+            let genome_length = parent1.len().min(parent2.len());
+            let mut child1 = Vec::with_capacity(genome_length);
+            let mut child2 = Vec::with_capacity(genome_length);
+            for (value1, value2) in parent1.iter().zip(parent2.iter()) {
+                let alpha = self.alpha.unwrap_or_else(|| random_probability(rng));
+                let (value1, value2) = (value1.to_f64(), value2.to_f64());
+                child1.push(V::from_f64(alpha * value1 + (1. - alpha) * value2));
+                child2.push(V::from_f64((1. - alpha) * value1 + alpha * value2));
+            }
+            offspring.push(child1);
+            if offspring.len() < num_parents {
+                offspring.push(child2);
+            }
+            index += 2;
+        }
+        offspring
+    }
+}
+
+/// The `BlendCrossBreeder` operator combines real valued `genetic::Genotype`s
+/// according to the blend crossover scheme (BLX-alpha).
+///
+/// For each locus the value of a child is drawn uniformly from the interval
+/// `[min - alpha * d, max + alpha * d]`, where `min` and `max` are the smaller
+/// and the greater of the values of two parents and `d = max - min`. Thus the
+/// children can explore values beyond the interval spanned by the parents. If
+/// bounds are given the values of the children are clamped into the range
+/// between the lower and the upper bound.
+///
+/// Each child is created from the parent at the same index and the next
+/// parent in the given `genetic::Parents`. This crossover operator always
+/// creates as many child individuals as there are individuals in the given
+/// `genetic::Parents` parameter.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlendCrossBreeder {
+    alpha: f64,
+    bounds: Option<(f64, f64)>,
+}
+
+impl BlendCrossBreeder {
+    /// Constructs a new `BlendCrossBreeder` with the given `alpha` and
+    /// without bounds.
+    pub fn new(alpha: f64) -> Self {
+        BlendCrossBreeder {
+            alpha,
+            bounds: None,
+        }
+    }
+
+    /// Constructs a new `BlendCrossBreeder` with the given `alpha` which
+    /// clamps the values of the children into the range between `min_value`
+    /// and `max_value`.
+    pub fn with_bounds(alpha: f64, min_value: f64, max_value: f64) -> Self {
+        BlendCrossBreeder {
+            alpha,
+            bounds: Some((min_value, max_value)),
+        }
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    pub fn set_alpha(&mut self, value: f64) {
+        self.alpha = value;
+    }
+
+    /// Returns the lower and upper bound of the values, if any.
+    pub fn bounds(&self) -> Option<(f64, f64)> {
+        self.bounds
+    }
+
+    pub fn set_bounds(&mut self, value: Option<(f64, f64)>) {
+        self.bounds = value;
+    }
+}
+
+impl GeneticOperator for BlendCrossBreeder {
+    fn name() -> String {
+        "Blend-Cross-Breeder".to_string()
+    }
+}
+
+impl<V> CrossoverOp<Vec<V>> for BlendCrossBreeder
+where
+    V: Clone + Debug + PartialEq + Send + Sync + ArithmeticValue,
+{
+    fn crossover<R>(&self, parents: Parents<Vec<V>>, rng: &mut R) -> Children<Vec<V>>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = parents.len();
+        let mut offspring: Vec<Vec<V>> = Vec::with_capacity(num_parents);
+        for index in 0..num_parents {
+            let parent1 = &parents[index];
+            let parent2 = &parents[(index + 1) % num_parents];
+            let genome = parent1
+                .iter()
+                .zip(parent2.iter())
+                .map(|(value1, value2)| {
+                    let (value1, value2) = (value1.to_f64(), value2.to_f64());
+                    let distance = (value1 - value2).abs();
+                    let lower = value1.min(value2) - self.alpha * distance;
+                    let upper = value1.max(value2) + self.alpha * distance;
+                    let mut value = lower + rng.gen::<f64>() * (upper - lower);
+                    if let Some((min_value, max_value)) = self.bounds {
+                        value = value.max(min_value).min(max_value);
+                    }
+                    V::from_f64(value)
+                })
+                .collect();
+            offspring.push(genome);
+        }
+        offspring
+    }
+}
+
+/// The `ArithmeticValue` trait defines the conversions of real values needed
+/// for arithmetic recombination.
+pub trait ArithmeticValue: Sized {
+    /// Converts this value into an `f64`.
+    fn to_f64(&self) -> f64;
+
+    /// Converts the given `f64` into a value of this type.
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_arithmetic_value {
+    ($($t:ty),*) => {
+        $(
+            #[allow(trivial_numeric_casts)]
+            impl ArithmeticValue for $t {
+                #[inline]
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+
+                #[inline]
+                fn from_f64(value: f64) -> $t {
+                    value as $t
+                }
+            }
+        )*
+    }
+}
+
+impl_arithmetic_value!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+
+    fn between(value: f64, bound1: f64, bound2: f64) -> bool {
+        value >= bound1.min(bound2) - 1e-9 && value <= bound1.max(bound2) + 1e-9
+    }
+
+    #[test]
+    fn arithmetic_cross_breeder_creates_values_between_the_parent_values() {
+        let mut rng = get_rng([42; 32]);
+        let parents = vec![vec![0.0, -2.5, 10.0, 3.0], vec![1.0, 2.5, -10.0, 3.0]];
+
+        for breeder in &[
+            ArithmeticCrossBreeder::new(0.3),
+            ArithmeticCrossBreeder::local(),
+        ] {
+            for _ in 0..100 {
+                let children = breeder.crossover(parents.clone(), &mut rng);
+                expect_that!(&children.len(), eq(2));
+                for child in children {
+                    for (locus, value) in child.iter().enumerate() {
+                        expect_that!(&between(*value, parents[0][locus], parents[1][locus]));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn arithmetic_cross_breeder_creates_complementary_children() {
+        let breeder = ArithmeticCrossBreeder::local();
+        let mut rng = get_rng([42; 32]);
+        let parents: Vec<Vec<f32>> = vec![vec![0.0, -2.5, 10.0], vec![1.0, 2.5, -10.0]];
+
+        let children = breeder.crossover(parents.clone(), &mut rng);
+
+        for locus in 0..3 {
+            let sum = children[0][locus] + children[1][locus];
+            expect_that!(
+                &(sum - parents[0][locus] - parents[1][locus]).abs(),
+                less_than(1e-5)
+            );
+        }
+    }
+
+    #[test]
+    fn arithmetic_cross_breeder_creates_as_many_children_as_parents() {
+        let breeder = ArithmeticCrossBreeder::new(0.5);
+        let mut rng = get_rng([42; 32]);
+        let parents = vec![vec![0.0, 1.0], vec![2.0, 3.0], vec![4.0, 5.0]];
+
+        let children = breeder.crossover(parents, &mut rng);
+
+        expect_that!(
+            &children,
+            eq(vec![vec![1.0, 2.0], vec![1.0, 2.0], vec![2.0, 3.0]])
+        );
+    }
+
+    #[test]
+    fn blend_cross_breeder_creates_values_within_the_expanded_interval() {
+        let breeder = BlendCrossBreeder::new(0.5);
+        let mut rng = get_rng([42; 32]);
+        let parents: Vec<Vec<f64>> = vec![vec![0.0, -2.5, 10.0], vec![1.0, 2.5, -10.0]];
+
+        for _ in 0..100 {
+            let children = breeder.crossover(parents.clone(), &mut rng);
+            expect_that!(&children.len(), eq(2));
+            for child in children {
+                for (locus, value) in child.iter().enumerate() {
+                    let (value1, value2) = (parents[0][locus], parents[1][locus]);
+                    let distance = (value1 - value2).abs();
+                    let lower = value1.min(value2) - 0.5 * distance;
+                    let upper = value1.max(value2) + 0.5 * distance;
+                    expect_that!(&between(*value, lower, upper));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blend_cross_breeder_keeps_equal_values() {
+        let breeder = BlendCrossBreeder::new(0.5);
+        let mut rng = get_rng([42; 32]);
+        let parents: Vec<Vec<f32>> = vec![vec![1.5, -3.0], vec![1.5, -3.0]];
+
+        let children = breeder.crossover(parents.clone(), &mut rng);
+
+        expect_that!(&children, eq(parents));
+    }
+
+    #[test]
+    fn blend_cross_breeder_clamps_values_into_bounds() {
+        let breeder = BlendCrossBreeder::with_bounds(1.0, -1.0, 1.0);
+        let mut rng = get_rng([42; 32]);
+        let parents = vec![vec![-1.0, 0.9, -0.9], vec![1.0, 1.0, -1.0]];
+
+        for _ in 0..100 {
+            let children = breeder.crossover(parents.clone(), &mut rng);
+            for child in children {
+                for value in child {
+                    expect_that!(&between(value, -1.0, 1.0));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! The `diploid` module provides an `operator::CrossoverOp` for
+//! `genotype::Diploid` genomes.
+//!
+//! The crossover of diploid genomes resembles the meiosis of diploid
+//! organisms. Each parent recombines its two homologous genomes into a
+//! gamete, and the gametes of two parents form the homologous genomes of a
+//! child.
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genetic::{Children, Genotype, Parents},
+    genotype::Diploid,
+    operator::{CrossoverOp, GeneticOperator},
+    random::{random_index, Rng},
+};
+
+/// The `DiploidCrossBreeder` is a `CrossoverOp` for `Diploid` genomes that
+/// recombines the homologous genomes with another `CrossoverOp`.
+///
+/// A gamete of a parent is formed by the crossover of its two homologous
+/// genomes with the wrapped `CrossoverOp`, which gets the first and the
+/// second homologous genome as parents. One of the resulting children is
+/// chosen at random as the gamete.
+///
+/// The `DiploidCrossBreeder` breeds one child per parent. The child at
+/// index `i` gets a gamete of the parent at index `i` as its first
+/// homologous genome and a gamete of the next parent as its second
+/// homologous genome. The last child gets the second gamete from the first
+/// parent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiploidCrossBreeder<C> {
+    /// The `CrossoverOp` that recombines the homologous genomes.
+    breeder: C,
+}
+
+impl<C> DiploidCrossBreeder<C> {
+    /// Constructs a new `DiploidCrossBreeder` that forms the gametes with
+    /// the given `CrossoverOp`.
+    pub fn new(breeder: C) -> Self {
+        DiploidCrossBreeder { breeder }
+    }
+
+    /// Returns the `CrossoverOp` that recombines the homologous genomes.
+    pub fn breeder(&self) -> &C {
+        &self.breeder
+    }
+
+    /// Forms a gamete of the given parent by the crossover of its homologous
+    /// genomes.
+    fn gamete<G, R>(&self, parent: &Diploid<G>, rng: &mut R) -> G
+    where
+        G: Genotype,
+        C: CrossoverOp<G>,
+        R: Rng + Sized,
+    {
+        let mut children = self
+            .breeder
+            .crossover(vec![parent.first().clone(), parent.second().clone()], rng);
+        assert!(
+            !children.is_empty(),
+            "the crossover of the homologous genomes must breed at least one child"
+        );
+        let chosen = random_index(rng, children.len());
+        children.swap_remove(chosen)
+    }
+}
+
+impl<C> GeneticOperator for DiploidCrossBreeder<C>
+where
+    C: GeneticOperator,
+{
+    fn name() -> String {
+        format!("Diploid-{}", C::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        self.breeder.validate()
+    }
+}
+
+impl<G, C> CrossoverOp<Diploid<G>> for DiploidCrossBreeder<C>
+where
+    G: Genotype,
+    C: CrossoverOp<G>,
+{
+    fn crossover<R>(&self, parents: Parents<Diploid<G>>, rng: &mut R) -> Children<Diploid<G>>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = parents.len();
+        let mut offspring: Children<Diploid<G>> = Vec::with_capacity(num_parents);
+        for index in 0..num_parents {
+            let first = self.gamete(&parents[index], rng);
+            let second = self.gamete(&parents[(index + 1) % num_parents], rng);
+            offspring.push(Diploid::new(first, second));
+        }
+        offspring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{random::get_rng, recombination::discrete::UniformCrossBreeder};
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn children_are_bred_from_the_gametes_of_neighbouring_parents() {
+        let parents = vec![
+            Diploid::new(vec![1, 1, 1, 1], vec![2, 2, 2, 2]),
+            Diploid::new(vec![3, 3, 3, 3], vec![4, 4, 4, 4]),
+            Diploid::new(vec![5, 5, 5, 5], vec![6, 6, 6, 6]),
+        ];
+        let breeder = DiploidCrossBreeder::new(UniformCrossBreeder::new());
+
+        let children = breeder.crossover(parents, &mut get_rng([42; 32]));
+
+        expect_that!(&children.len(), eq(3));
+        let alleles_of = |genome: &Vec<i32>, alleles: &[i32]| -> bool {
+            genome.iter().all(|allele| alleles.contains(allele))
+        };
+        for (index, child) in children.iter().enumerate() {
+            let first_parent = index as i32 * 2 + 1;
+            let second_parent = (index as i32 + 1) % 3 * 2 + 1;
+            expect_that!(
+                &alleles_of(child.first(), &[first_parent, first_parent + 1]),
+                is(eq(true))
+            );
+            expect_that!(
+                &alleles_of(child.second(), &[second_parent, second_parent + 1]),
+                is(eq(true))
+            );
+        }
+    }
+}
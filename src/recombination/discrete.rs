@@ -4,34 +4,84 @@
 //! genotypes and value encoded genotypes.
 //!
 //! The provided `operator::CrossoverOp`s are:
-//! * `UniformCrossBreeder` for `fixedbitset::FixedBitSet`,
-//!   `smallvec::SmallVec` and `Vec` of any type.
-//! * `SinglePointCrossBreeder` for `fixedbitset::FixedBitSet`,
-//!   `smallvec::SmallVec` and `Vec` of any type.
-//! * `MultiPointCrossBreeder` for `fixedbitset::FixedBitSet`,
-//!   `smallvec::SmallVec` and `Vec` of any type.
+//! * `UniformCrossBreeder` for `genotype::BinaryGenome`,
+//!   `fixedbitset::FixedBitSet`, `smallvec::SmallVec` and `Vec` of any type.
+//! * `SinglePointCrossBreeder` for `genotype::BinaryGenome`,
+//!   `fixedbitset::FixedBitSet`, `smallvec::SmallVec` and `Vec` of any type.
+//! * `MultiPointCrossBreeder` for `genotype::BinaryGenome`,
+//!   `fixedbitset::FixedBitSet`, `smallvec::SmallVec` and `Vec` of any type.
+//! * `SelfAdaptiveMultiPointCrossBreeder` for `genotype::SelfAdaptive`
+//!   genomes of any of the types above.
 
 use crate::{
+    ga::GeneticAlgorithmError,
     genetic::{Children, Genotype, Parents},
-    operator::{CrossoverOp, GeneticOperator},
-    random::{random_n_cut_points, Rng},
+    genotype::{BinaryGenome, SelfAdaptive},
+    operator::{validate_count, validate_ratio, CrossoverOp, GeneticOperator},
+    random::{random_index, random_n_cut_points_into, random_probability, Rng},
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, mem};
 
 /// The `UniformCrossBreeder` operator combines binary encoded or value encoded
 /// `genetic::Genotype`s by walking through the bits/values of the parents one
 /// by one and randomly selecting the bit/value of one partner that is copied to
 /// the resulting child.
 ///
+/// If there are two individuals in the given `genetic::Parents` parameter, the
+/// `mixing_ratio` is the probability that a bit/value of a child is copied
+/// from the parent that corresponds to the child. Otherwise the bit/value is
+/// copied from the other parent. A `mixing_ratio` of 0.5, which is the
+/// default, means that both parents contribute equally to the children. If
+/// there are more than two individuals in the given `genetic::Parents`
+/// parameter, each bit/value is copied from a uniformly chosen parent.
+///
 /// This crossover operator always creates as many child individuals as there
 /// are individuals in the given `genetic::Parents` parameter.
 #[allow(missing_copy_implementations)]
-#[derive(Default, Clone, Debug, PartialEq)]
-pub struct UniformCrossBreeder {}
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniformCrossBreeder {
+    mixing_ratio: f64,
+}
 
 impl UniformCrossBreeder {
     pub fn new() -> Self {
-        UniformCrossBreeder {}
+        UniformCrossBreeder { mixing_ratio: 0.5 }
+    }
+
+    /// Constructs a new `UniformCrossBreeder` with the given mixing ratio.
+    pub fn with_mixing_ratio(mixing_ratio: f64) -> Self {
+        UniformCrossBreeder { mixing_ratio }
+    }
+
+    pub fn mixing_ratio(&self) -> f64 {
+        self.mixing_ratio
+    }
+
+    pub fn set_mixing_ratio(&mut self, value: f64) {
+        self.mixing_ratio = value;
+    }
+
+    /// Picks the index of the parent from which the value at the next locus of
+    /// the child with the given index is copied.
+    fn pick_parent<R>(&self, child: usize, num_parents: usize, rng: &mut R) -> usize
+    where
+        R: Rng + Sized,
+    {
+        if num_parents == 2 {
+            if random_probability(rng) < self.mixing_ratio {
+                child
+            } else {
+                1 - child
+            }
+        } else {
+            random_index(rng, num_parents)
+        }
+    }
+}
+
+impl Default for UniformCrossBreeder {
+    fn default() -> Self {
+        UniformCrossBreeder::new()
     }
 }
 
@@ -39,6 +89,10 @@ impl GeneticOperator for UniformCrossBreeder {
     fn name() -> String {
         "Uniform-Cross-Breeder".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("mixing ratio", self.mixing_ratio)
+    }
 }
 
 impl<V> CrossoverOp<Vec<V>> for UniformCrossBreeder
@@ -51,16 +105,84 @@ where
     {
         let genome_length = parents[0].len();
         let num_parents = parents.len();
+        let mut parents = parents;
         // breed one child for each partner in parents
         let mut offspring: Vec<Vec<V>> = Vec::with_capacity(num_parents);
         while num_parents > offspring.len() {
-            let mut genome = Vec::with_capacity(genome_length);
+            let child = offspring.len();
+            let genome = if child + 1 == num_parents {
+                // the parents are not needed after breeding the last child, so
+                // the last child reuses the genome of its own parent
+                let mut genome = mem::take(&mut parents[child]);
+                for (locus, value) in genome.iter_mut().enumerate() {
+                    // pick the value of a randomly chosen parent
+                    let parent = self.pick_parent(child, num_parents, rng);
+                    if parent != child {
+                        *value = parents[parent][locus].clone();
+                    }
+                }
+                genome
+            } else {
+                // for each value in the genotype
+                (0..genome_length)
+                    .map(|locus| {
+                        // pick the value of a randomly chosen parent
+                        let parent = self.pick_parent(child, num_parents, rng);
+                        parents[parent][locus].clone()
+                    })
+                    .collect()
+            };
+            offspring.push(genome);
+        }
+        offspring
+    }
+}
+
+impl<V, const N: usize> CrossoverOp<[V; N]> for UniformCrossBreeder
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn crossover<R>(&self, parents: Parents<[V; N]>, rng: &mut R) -> Children<[V; N]>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = parents.len();
+        // breed one child for each partner in parents
+        let mut offspring: Vec<[V; N]> = Vec::with_capacity(num_parents);
+        while num_parents > offspring.len() {
+            let child = offspring.len();
             // for each value in the genotype
-            for locus in 0..genome_length {
+            let genome = std::array::from_fn(|locus| {
                 // pick the value of a randomly chosen parent
-                let random = rng.gen_range(0..num_parents);
-                let value = parents[random][locus].clone();
-                genome.push(value);
+                let parent = self.pick_parent(child, num_parents, rng);
+                parents[parent][locus].clone()
+            });
+            offspring.push(genome);
+        }
+        offspring
+    }
+}
+
+impl CrossoverOp<BinaryGenome> for UniformCrossBreeder {
+    fn crossover<R>(&self, parents: Parents<BinaryGenome>, rng: &mut R) -> Children<BinaryGenome>
+    where
+        R: Rng + Sized,
+    {
+        let genome_length = parents[0].len();
+        let num_parents = parents.len();
+        // breed one child for each partner in parents
+        let mut offspring: Vec<BinaryGenome> = Vec::with_capacity(num_parents);
+        while num_parents > offspring.len() {
+            let child = offspring.len();
+            let mut genome = BinaryGenome::new(genome_length);
+            // assemble each word from the bits of randomly chosen parents
+            for word in 0..genome.num_words() {
+                let mut value = 0;
+                for bit in 0..genome.bits_in_word(word) {
+                    let parent = self.pick_parent(child, num_parents, rng);
+                    value |= parents[parent].word(word) & (1 << bit);
+                }
+                genome.set_word(word, value);
             }
             offspring.push(genome);
         }
@@ -88,14 +210,14 @@ mod fixedbitset_uniform_cross_breeder {
             // breed one child for each partner in parents
             let mut offspring: Vec<FixedBitSet> = Vec::with_capacity(num_parents);
             while num_parents > offspring.len() {
+                let child = offspring.len();
                 let mut genome = FixedBitSet::with_capacity(genome_length);
-                // for each value in the genotype
-                for locus in 0..genome_length {
-                    // pick the value of a randomly chosen parent
-                    let random = rng.gen_range(0..num_parents);
-                    let value = parents[random][locus];
-                    genome.set(locus, value);
-                }
+                // for each value in the genotype set the bits that are set in
+                // a randomly chosen parent
+                genome.extend((0..genome_length).filter(|locus| {
+                    let parent = self.pick_parent(child, num_parents, rng);
+                    parents[parent][*locus]
+                }));
                 offspring.push(genome);
             }
             offspring
@@ -125,14 +247,15 @@ mod smallvec_uniform_cross_breeder {
             // breed one child for each partner in parents
             let mut offspring: Vec<SmallVec<A>> = Vec::with_capacity(num_parents);
             while num_parents > offspring.len() {
-                let mut genome = SmallVec::with_capacity(genome_length);
+                let child = offspring.len();
                 // for each value in the genotype
-                for locus in 0..genome_length {
-                    // pick the value of a randomly chosen parent
-                    let random = rng.gen_range(0..num_parents);
-                    let value = parents[random][locus].clone();
-                    genome.push(value);
-                }
+                let genome = (0..genome_length)
+                    .map(|locus| {
+                        // pick the value of a randomly chosen parent
+                        let parent = self.pick_parent(child, num_parents, rng);
+                        parents[parent][locus].clone()
+                    })
+                    .collect();
                 offspring.push(genome);
             }
             offspring
@@ -180,6 +303,12 @@ where
 /// slices and combining the slices from randomly picked parents into the new
 /// `genetic:Genotype`.
 ///
+/// For each child `num_cut_points` distinct cut points are chosen at random,
+/// which split the genome into `num_cut_points + 1` non-empty slices. Two
+/// consecutive slices are always taken from different parents. Thus each cut
+/// point is a boundary at which the parent is swapped. The genomes must have
+/// a length of at least `2 * num_cut_points`.
+///
 /// This crossover operator always creates as many child individuals as there
 /// are individuals in the given `genetic::Parents` parameter.
 #[allow(missing_copy_implementations)]
@@ -190,16 +319,33 @@ pub struct MultiPointCrossBreeder {
 }
 
 impl MultiPointCrossBreeder {
+    /// Constructs a new instance of the `MultiPointCrossBreeder` that uses
+    /// the given number of cut points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cut points is 0.
     pub fn new(num_cut_points: usize) -> Self {
+        assert!(
+            num_cut_points > 0,
+            "the number of cut points must be greater than 0"
+        );
         MultiPointCrossBreeder { num_cut_points }
     }
 
+    /// Constructs a new instance of the `MultiPointCrossBreeder` that uses
+    /// one cut point, which is the classic single-point crossover.
+    pub fn single_point() -> Self {
+        MultiPointCrossBreeder::new(1)
+    }
+
     /// Returns the number of cut points used by this operator.
     pub fn num_cut_points(&self) -> usize {
         self.num_cut_points
     }
 
     /// Sets the number of cut points used by this operator to the given value.
+    /// The value must be greater than 0.
     pub fn set_num_cut_points(&mut self, value: usize) {
         self.num_cut_points = value;
     }
@@ -209,6 +355,10 @@ impl GeneticOperator for MultiPointCrossBreeder {
     fn name() -> String {
         "Multi-Point-Cross-Breeder".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_count::<Self>("number of cut points", self.num_cut_points)
+    }
 }
 
 impl<G> CrossoverOp<G> for MultiPointCrossBreeder
@@ -223,6 +373,129 @@ where
     }
 }
 
+/// The `SelfAdaptiveMultiPointCrossBreeder` is a multi-point crossover for
+/// `genotype::SelfAdaptive` genomes whose number of cut points is not a fixed
+/// parameter of the operator but evolves alongside the solutions.
+///
+/// The number of cut points is a strategy parameter that lives in each
+/// `SelfAdaptive` genome. The parents are recombined like by the
+/// `MultiPointCrossBreeder` with the number of cut points they have
+/// inherited: the mean of their numbers of cut points, rounded
+/// stochastically. Each child inherits this number, which is then mutated
+/// by one up or down with the probability `strategy_mutation_rate` and kept
+/// between 1 and `max_cut_points`. Thus genomes whose number of cut points
+/// breeds good children spread it in the population.
+///
+/// The genomes must have a length of at least `2 * max_cut_points`.
+///
+/// This crossover operator always creates as many child individuals as there
+/// are individuals in the given `genetic::Parents` parameter.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelfAdaptiveMultiPointCrossBreeder {
+    /// The maximum number of cut points of a genome.
+    max_cut_points: usize,
+    /// The probability of the number of cut points of a child to be mutated.
+    strategy_mutation_rate: f64,
+}
+
+impl SelfAdaptiveMultiPointCrossBreeder {
+    /// Constructs a new instance of the `SelfAdaptiveMultiPointCrossBreeder`
+    /// that keeps the number of cut points between 1 and `max_cut_points` and
+    /// mutates it with the given probability.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the maximum number of cut points is 0.
+    pub fn new(max_cut_points: usize, strategy_mutation_rate: f64) -> Self {
+        assert!(
+            max_cut_points > 0,
+            "the maximum number of cut points must be greater than 0"
+        );
+        SelfAdaptiveMultiPointCrossBreeder {
+            max_cut_points,
+            strategy_mutation_rate,
+        }
+    }
+
+    /// Returns the maximum number of cut points of a genome.
+    pub fn max_cut_points(&self) -> usize {
+        self.max_cut_points
+    }
+
+    /// Sets the maximum number of cut points of a genome. The value must be
+    /// greater than 0.
+    pub fn set_max_cut_points(&mut self, value: usize) {
+        self.max_cut_points = value;
+    }
+
+    /// Returns the probability of the number of cut points of a child to be
+    /// mutated.
+    pub fn strategy_mutation_rate(&self) -> f64 {
+        self.strategy_mutation_rate
+    }
+
+    /// Sets the probability of the number of cut points of a child to be
+    /// mutated. The value must be between 0 and 1 (inclusive).
+    pub fn set_strategy_mutation_rate(&mut self, value: f64) {
+        self.strategy_mutation_rate = value;
+    }
+
+    /// Mutates the inherited number of cut points of a child.
+    fn mutate_num_cut_points<R>(&self, num_cut_points: usize, rng: &mut R) -> usize
+    where
+        R: Rng + Sized,
+    {
+        if random_probability(rng) >= self.strategy_mutation_rate {
+            num_cut_points
+        } else if rng.gen::<bool>() {
+            (num_cut_points + 1).min(self.max_cut_points)
+        } else {
+            (num_cut_points - 1).max(1)
+        }
+    }
+}
+
+impl GeneticOperator for SelfAdaptiveMultiPointCrossBreeder {
+    fn name() -> String {
+        "Self-Adaptive-Multi-Point-Cross-Breeder".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_count::<Self>("maximum number of cut points", self.max_cut_points)?;
+        validate_ratio::<Self>("strategy mutation rate", self.strategy_mutation_rate)
+    }
+}
+
+impl<G> CrossoverOp<SelfAdaptive<G>> for SelfAdaptiveMultiPointCrossBreeder
+where
+    G: Genotype + MultiPointCrossover,
+{
+    fn crossover<R>(
+        &self,
+        parents: Parents<SelfAdaptive<G>>,
+        rng: &mut R,
+    ) -> Children<SelfAdaptive<G>>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = parents.len();
+        let mut genomes = Vec::with_capacity(num_parents);
+        let mut sum_cut_points = 0;
+        for parent in parents {
+            let (genome, num_cut_points) = parent.into_inner();
+            genomes.push(genome);
+            sum_cut_points += num_cut_points;
+        }
+        let mean = sum_cut_points as f64 / num_parents as f64;
+        let inherited = ((mean + rng.gen::<f64>()).floor() as usize).clamp(1, self.max_cut_points);
+        MultiPointCrossover::crossover(genomes, inherited, rng)
+            .into_iter()
+            .map(|child| SelfAdaptive::new(child, self.mutate_num_cut_points(inherited, rng)))
+            .collect()
+    }
+}
+
 pub trait MultiPointCrossover: Genotype {
     type Dna;
 
@@ -243,32 +516,110 @@ where
     {
         let genome_length = parents[0].len();
         let num_parents = parents.len();
+        let mut parents = parents;
+        // the buffer of the cut points is reused for all children
+        let mut slice_ends = Vec::with_capacity(num_cut_points + 1);
         // breed one child for each partner in parents
         let mut offspring: Vec<Vec<V>> = Vec::with_capacity(num_parents);
         while num_parents > offspring.len() {
-            let mut genome = Vec::with_capacity(genome_length);
-            let mut cutpoints = random_n_cut_points(rng, num_cut_points, genome_length);
-            cutpoints.push(genome_length);
-            let mut start = 0;
-            let mut end = cutpoints.remove(0);
-            let mut p_index = num_parents;
-            loop {
-                loop {
-                    let index = rng.gen_range(0..num_parents);
-                    if index != p_index {
-                        p_index = index;
-                        break;
+            random_slice_ends(rng, num_cut_points, genome_length, &mut slice_ends);
+            let first = random_partner(rng, num_parents, num_parents);
+            let genome = if offspring.len() + 1 == num_parents {
+                // the parents are not needed after breeding the last child, so
+                // the last child reuses the genome of its first partner
+                let mut genome = mem::take(&mut parents[first]);
+                let mut p_index = first;
+                for window in slice_ends.windows(2) {
+                    p_index = random_partner(rng, num_parents, p_index);
+                    if p_index != first {
+                        let (start, end) = (window[0], window[1]);
+                        genome[start..end].clone_from_slice(&parents[p_index][start..end]);
                     }
                 }
-                let partner = &parents[p_index];
-                for partner in partner.iter().take(end).skip(start) {
-                    genome.push(partner.clone())
+                genome
+            } else {
+                let mut genome = Vec::with_capacity(genome_length);
+                genome.extend_from_slice(&parents[first][..slice_ends[0]]);
+                let mut p_index = first;
+                for window in slice_ends.windows(2) {
+                    p_index = random_partner(rng, num_parents, p_index);
+                    genome.extend_from_slice(&parents[p_index][window[0]..window[1]]);
                 }
-                if cutpoints.is_empty() {
-                    break;
+                genome
+            };
+            offspring.push(genome);
+        }
+        offspring
+    }
+}
+
+impl<V, const N: usize> MultiPointCrossover for [V; N]
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    type Dna = V;
+
+    fn crossover<R>(parents: Parents<Self>, num_cut_points: usize, rng: &mut R) -> Children<Self>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = parents.len();
+        // the buffer of the cut points is reused for all children
+        let mut slice_ends = Vec::with_capacity(num_cut_points + 1);
+        // breed one child for each partner in parents
+        let mut offspring: Vec<[V; N]> = Vec::with_capacity(num_parents);
+        while num_parents > offspring.len() {
+            random_slice_ends(rng, num_cut_points, N, &mut slice_ends);
+            // the first slice is already in place, all other slices are
+            // overwritten by the chosen partner
+            let first = random_partner(rng, num_parents, num_parents);
+            let mut genome = parents[first].clone();
+            let mut p_index = first;
+            for window in slice_ends.windows(2) {
+                p_index = random_partner(rng, num_parents, p_index);
+                if p_index != first {
+                    let (start, end) = (window[0], window[1]);
+                    genome[start..end].clone_from_slice(&parents[p_index][start..end]);
+                }
+            }
+            offspring.push(genome);
+        }
+        offspring
+    }
+}
+
+impl MultiPointCrossover for BinaryGenome {
+    type Dna = bool;
+
+    fn crossover<R>(parents: Parents<Self>, num_cut_points: usize, rng: &mut R) -> Children<Self>
+    where
+        R: Rng + Sized,
+    {
+        let genome_length = parents[0].len();
+        let num_parents = parents.len();
+        let mut parents = parents;
+        // the buffer of the cut points is reused for all children
+        let mut slice_ends = Vec::with_capacity(num_cut_points + 1);
+        // breed one child for each partner in parents
+        let mut offspring: Vec<BinaryGenome> = Vec::with_capacity(num_parents);
+        while num_parents > offspring.len() {
+            random_slice_ends(rng, num_cut_points, genome_length, &mut slice_ends);
+            let first = random_partner(rng, num_parents, num_parents);
+            // the first slice is already in place, all other slices are
+            // overwritten by the chosen partner word by word
+            let mut genome = if offspring.len() + 1 == num_parents {
+                // the parents are not needed after breeding the last child, so
+                // the last child reuses the genome of its first partner
+                mem::take(&mut parents[first])
+            } else {
+                parents[first].clone()
+            };
+            let mut p_index = first;
+            for window in slice_ends.windows(2) {
+                p_index = random_partner(rng, num_parents, p_index);
+                if p_index != first {
+                    genome.copy_bits_from(&parents[p_index], window[0], window[1]);
                 }
-                start = end;
-                end = cutpoints.remove(0);
             }
             offspring.push(genome);
         }
@@ -276,10 +627,41 @@ where
     }
 }
 
+/// Generates the cut points of one child of a multi-point crossover followed
+/// by the genome length, so that each value is the end of one slice.
+fn random_slice_ends<R>(
+    rng: &mut R,
+    num_cut_points: usize,
+    genome_length: usize,
+    slice_ends: &mut Vec<usize>,
+) where
+    R: Rng + Sized,
+{
+    random_n_cut_points_into(rng, num_cut_points, genome_length, slice_ends);
+    slice_ends.push(genome_length);
+}
+
+/// Picks the parent that provides the next slice of a multi-point crossover.
+/// Two consecutive slices are never provided by the same parent.
+fn random_partner<R>(rng: &mut R, num_parents: usize, previous: usize) -> usize
+where
+    R: Rng + Sized,
+{
+    loop {
+        let index = rng.gen_range(0..num_parents);
+        if index != previous {
+            return index;
+        }
+    }
+}
+
 #[cfg(feature = "smallvec")]
 mod smallvec_multipoint_crossover {
-    use super::{random_n_cut_points, MultiPointCrossover};
-    use crate::genetic::{Children, Parents};
+    use super::MultiPointCrossover;
+    use crate::{
+        genetic::{Children, Parents},
+        random::random_n_cut_points,
+    };
     use rand::Rng;
     use smallvec::{Array, SmallVec};
     use std::fmt::Debug;
@@ -337,8 +719,11 @@ mod smallvec_multipoint_crossover {
 
 #[cfg(feature = "fixedbitset")]
 mod fixedbitset_multipoint_crossover {
-    use super::{random_n_cut_points, MultiPointCrossover};
-    use crate::genetic::{Children, Parents};
+    use super::MultiPointCrossover;
+    use crate::{
+        genetic::{Children, Parents},
+        random::random_n_cut_points,
+    };
     use fixedbitset::FixedBitSet;
     use rand::Rng;
 
@@ -388,3 +773,169 @@ mod fixedbitset_multipoint_crossover {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn uniform_cross_breeder_copies_values_from_parents_at_the_same_locus() {
+        let breeder = UniformCrossBreeder::new();
+        let mut rng = get_rng([42; 32]);
+        let parents: Vec<Vec<usize>> =
+            vec![(0..10).collect(), (10..20).collect(), (20..30).collect()];
+
+        for _ in 0..100 {
+            let children = breeder.crossover(parents.clone(), &mut rng);
+            expect_that!(&children.len(), eq(3));
+            for child in children {
+                for (locus, value) in child.iter().enumerate() {
+                    expect_that!(&(value % 10), eq(locus));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn uniform_cross_breeder_with_mixing_ratio_1_copies_the_parents() {
+        let breeder = UniformCrossBreeder::with_mixing_ratio(1.0);
+        let mut rng = get_rng([42; 32]);
+        let parents: Vec<Vec<bool>> = vec![vec![true; 8], vec![false; 8]];
+
+        let children = breeder.crossover(parents.clone(), &mut rng);
+
+        expect_that!(&children, eq(parents));
+    }
+
+    #[test]
+    fn uniform_cross_breeder_with_mixing_ratio_0_swaps_the_parents() {
+        let breeder = UniformCrossBreeder::with_mixing_ratio(0.0);
+        let mut rng = get_rng([42; 32]);
+        let parents: Vec<Vec<bool>> = vec![vec![true; 8], vec![false; 8]];
+
+        let children = breeder.crossover(parents, &mut rng);
+
+        expect_that!(&children, eq(vec![vec![false; 8], vec![true; 8]]));
+    }
+
+    #[test]
+    fn uniform_cross_breeder_breeds_array_genomes_like_vec_genomes() {
+        let breeder = UniformCrossBreeder::new();
+        let vec_parents: Vec<Vec<usize>> = vec![(0..10).collect(), (10..20).collect()];
+        let array_parents: Vec<[usize; 10]> = vec![
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            [10, 11, 12, 13, 14, 15, 16, 17, 18, 19],
+        ];
+
+        let vec_children = breeder.crossover(vec_parents, &mut get_rng([42; 32]));
+        let array_children = breeder.crossover(array_parents, &mut get_rng([42; 32]));
+
+        let array_children: Vec<Vec<usize>> =
+            array_children.iter().map(|child| child.to_vec()).collect();
+        expect_that!(&array_children, eq(vec_children));
+    }
+
+    #[test]
+    fn multi_point_cross_breeder_breeds_array_genomes_like_vec_genomes() {
+        let breeder = MultiPointCrossBreeder::new(3);
+        let vec_parents: Vec<Vec<usize>> = vec![(0..10).collect(), (10..20).collect()];
+        let array_parents: Vec<[usize; 10]> = vec![
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            [10, 11, 12, 13, 14, 15, 16, 17, 18, 19],
+        ];
+
+        for seed in 0..20 {
+            let vec_children = breeder.crossover(vec_parents.clone(), &mut get_rng([seed; 32]));
+            let array_children = breeder.crossover(array_parents.clone(), &mut get_rng([seed; 32]));
+
+            let array_children: Vec<Vec<usize>> =
+                array_children.iter().map(|child| child.to_vec()).collect();
+            expect_that!(&array_children, eq(vec_children));
+        }
+    }
+
+    /// Counts the loci of the child at which the parent that provided the
+    /// value differs from the parent of the previous locus. The first parent
+    /// provides the values below 100, the second one the others.
+    fn num_swap_boundaries(child: &[usize]) -> usize {
+        child
+            .windows(2)
+            .filter(|pair| (pair[0] < 100) != (pair[1] < 100))
+            .count()
+    }
+
+    #[test]
+    fn multi_point_cross_breeder_swaps_the_parent_at_each_of_the_distinct_cut_points() {
+        let parents: Vec<Vec<usize>> = vec![(0..12).collect(), (100..112).collect()];
+
+        for num_cut_points in 1..=6 {
+            let breeder = MultiPointCrossBreeder::new(num_cut_points);
+            for seed in 0..50 {
+                let children = breeder.crossover(parents.clone(), &mut get_rng([seed; 32]));
+
+                for child in children {
+                    expect_that!(&num_swap_boundaries(&child), eq(num_cut_points));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn multi_point_cross_breeder_with_a_single_point_swaps_the_parent_exactly_once() {
+        let breeder = MultiPointCrossBreeder::single_point();
+        let parents: Vec<Vec<usize>> = vec![(0..2).collect(), (100..102).collect()];
+
+        expect_that!(&breeder.num_cut_points(), eq(1));
+        for seed in 0..50 {
+            let children = breeder.crossover(parents.clone(), &mut get_rng([seed; 32]));
+
+            for child in children {
+                expect_that!(&num_swap_boundaries(&child), eq(1));
+            }
+        }
+    }
+
+    #[test]
+    fn self_adaptive_multi_point_cross_breeder_uses_the_inherited_number_of_cut_points() {
+        let breeder = SelfAdaptiveMultiPointCrossBreeder::new(6, 0.);
+        let parents = vec![
+            SelfAdaptive::new((0..12).collect::<Vec<usize>>(), 2),
+            SelfAdaptive::new((100..112).collect::<Vec<usize>>(), 4),
+        ];
+
+        for seed in 0..50 {
+            let children = breeder.crossover(parents.clone(), &mut get_rng([seed; 32]));
+
+            expect_that!(&children.len(), eq(2));
+            for child in children {
+                expect_that!(&child.num_cut_points(), eq(3));
+                expect_that!(&num_swap_boundaries(child.genome()), eq(3));
+            }
+        }
+    }
+
+    #[test]
+    fn self_adaptive_multi_point_cross_breeder_mutates_the_number_of_cut_points_within_bounds() {
+        let breeder = SelfAdaptiveMultiPointCrossBreeder::new(2, 1.);
+        let parents = vec![
+            SelfAdaptive::new((0..12).collect::<Vec<usize>>(), 2),
+            SelfAdaptive::new((100..112).collect::<Vec<usize>>(), 2),
+        ];
+        let mut num_cut_points = Vec::new();
+
+        for seed in 0..50 {
+            let children = breeder.crossover(parents.clone(), &mut get_rng([seed; 32]));
+
+            for child in children {
+                expect_that!(&num_swap_boundaries(child.genome()), eq(2));
+                num_cut_points.push(child.num_cut_points());
+            }
+        }
+
+        // a number above the maximum is kept at the maximum
+        expect_that!(&num_cut_points.contains(&1), is(eq(true)));
+        expect_that!(&num_cut_points.iter().all(|num| *num <= 2), is(eq(true)));
+    }
+}
@@ -2,9 +2,24 @@
 //! `operator::CrossoverOp`. The provided crossover operators are organized
 //! in the categories:
 //! * `discrete` - crossover schemes working on discrete values of a bitset or
-//!                or list of values.
+//!   or list of values.
 //! * `order` - crossover schemes for permutation encoded values.
+//! * `arithmetic` - crossover schemes for real values.
+//! * `diploid` - crossover schemes for diploid genomes.
+//! * `probabilistic` - applies another crossover scheme with a crossover rate.
+//! * `splice` - crossover schemes for variable-length genomes.
+//! * `tree` - crossover schemes for expression trees.
+
+pub mod arithmetic;
+
+pub mod diploid;
 
 pub mod discrete;
 
 pub mod order;
+
+pub mod probabilistic;
+
+pub mod splice;
+
+pub mod tree;
@@ -4,20 +4,30 @@
 //! values.
 //!
 //! The provided `operator::CrossoverOp`s for permutation encoded values are:
-//! * `OrderOneCrossover` (OX1)
-//! * `PartiallyMappedCrossover` (PMX)
+//! * `OrderOneCrossover` (OX1) for `Vec` of any type
+//! * `PartiallyMappedCrossover` (PMX) for `Vec` of any type that implements
+//!   `Eq` and `Hash`
+//! * `CycleCrossover` (CX) for `Vec` of any type that implements `Eq`
+//! * `EdgeRecombinationCrossover` (ERX) for `Vec` of any type that implements
+//!   `Eq` and `Hash`
 
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
 
 use crate::{
     genetic::{Children, Parents, ParentsSlice},
     operator::{CrossoverOp, GeneticOperator},
-    random::{random_cut_points, Rng},
+    random::{random_cut_points, random_index, Rng},
 };
 
 /// The `OrderOneCrossover` operator combines permutation encoded
 /// `genetic::Genotype`s according the order one crossover scheme (OX1).
 ///
+/// A random slice of one parent is copied into the child. The remaining
+/// positions are filled with the values that are not contained in the slice
+/// in the order they appear in the other parent. Thus every child is a valid
+/// permutation of the values of its parents and the relative order of the
+/// values of the other parent is preserved.
+///
 /// This crossover operator always creates as many child individuals as there
 /// are individuals in the given `genetic::Parents` parameter.
 #[allow(missing_copy_implementations)]
@@ -36,8 +46,11 @@ impl GeneticOperator for OrderOneCrossover {
     }
 }
 
-impl CrossoverOp<Vec<usize>> for OrderOneCrossover {
-    fn crossover<R>(&self, parents: Parents<Vec<usize>>, rng: &mut R) -> Children<Vec<usize>>
+impl<V> CrossoverOp<Vec<V>> for OrderOneCrossover
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn crossover<R>(&self, parents: Parents<Vec<V>>, rng: &mut R) -> Children<Vec<V>>
     where
         R: Rng + Sized,
     {
@@ -45,9 +58,21 @@ impl CrossoverOp<Vec<usize>> for OrderOneCrossover {
     }
 }
 
+/// The `OrderCrossBreeder` is an alias of the `OrderOneCrossover` following
+/// the naming of the cross breeders in the `discrete` module.
+pub type OrderCrossBreeder = OrderOneCrossover;
+
 /// The `PartiallyMappedCrossover` operator combines permutation encoded
 /// `genetic::Genotype`s according the partially mapped crossover scheme (PMX).
 ///
+/// The segment between two random cut points is copied from one parent. The
+/// remaining positions are filled with the values of the other parent, where
+/// values that are already contained in the copied segment are replaced
+/// according to the mapping established by the segment. Thus every child is
+/// a valid permutation of the values of its parents.
+///
+/// The values of the genome must implement `Eq` and `Hash`.
+///
 /// This crossover operator always creates as many child individuals as there
 /// are individuals in the given `genetic::Parents` parameter.
 #[allow(missing_copy_implementations)]
@@ -66,8 +91,11 @@ impl GeneticOperator for PartiallyMappedCrossover {
     }
 }
 
-impl CrossoverOp<Vec<usize>> for PartiallyMappedCrossover {
-    fn crossover<R>(&self, parents: Parents<Vec<usize>>, rng: &mut R) -> Children<Vec<usize>>
+impl<V> CrossoverOp<Vec<V>> for PartiallyMappedCrossover
+where
+    V: Clone + Debug + Eq + Hash + Send + Sync,
+{
+    fn crossover<R>(&self, parents: Parents<Vec<V>>, rng: &mut R) -> Children<Vec<V>>
     where
         R: Rng + Sized,
     {
@@ -75,19 +103,138 @@ impl CrossoverOp<Vec<usize>> for PartiallyMappedCrossover {
     }
 }
 
-fn multi_parents_cyclic_crossover<'a, FN, R>(
-    parents: ParentsSlice<'a, Vec<usize>>,
+/// The `PartiallyMappedCrossBreeder` is an alias of the
+/// `PartiallyMappedCrossover` following the naming of the cross breeders in
+/// the `discrete` module.
+pub type PartiallyMappedCrossBreeder = PartiallyMappedCrossover;
+
+/// The `CycleCrossover` operator combines permutation encoded
+/// `genetic::Genotype`s according the cycle crossover scheme (CX).
+///
+/// The positions of the genome are divided into cycles. Starting at a
+/// position of one parent the cycle continues at the position where this
+/// parent holds the value of the other parent at the current position, until
+/// it returns to the start. The values of the first, third, fifth, ... cycle
+/// are copied from one parent and the values of the other cycles from the
+/// other parent. Thus every child is a valid permutation of the values of its
+/// parents and every value of a child is located at the same position as in
+/// one of its parents. If the whole genome forms a single cycle the child is
+/// a copy of the first parent.
+///
+/// The values of the genome must implement `Eq`. The cycle crossover does not
+/// use any random numbers.
+///
+/// This crossover operator always creates as many child individuals as there
+/// are individuals in the given `genetic::Parents` parameter.
+#[allow(missing_copy_implementations)]
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct CycleCrossover {}
+
+impl CycleCrossover {
+    pub fn new() -> Self {
+        CycleCrossover {}
+    }
+}
+
+impl GeneticOperator for CycleCrossover {
+    fn name() -> String {
+        "Cycle-Crossover".to_string()
+    }
+}
+
+impl<V> CrossoverOp<Vec<V>> for CycleCrossover
+where
+    V: Clone + Debug + Eq + Send + Sync,
+{
+    fn crossover<R>(&self, parents: Parents<Vec<V>>, _: &mut R) -> Children<Vec<V>>
+    where
+        R: Rng + Sized,
+    {
+        let parents_size = parents.len();
+        (0..parents_size)
+            .map(|p1_index| {
+                let p2_index = (p1_index + 1) % parents_size;
+                cycle_crossover(&parents[p1_index], &parents[p2_index])
+            })
+            .collect()
+    }
+}
+
+/// The `CycleCrossBreeder` is an alias of the `CycleCrossover` following the
+/// naming of the cross breeders in the `discrete` module.
+pub type CycleCrossBreeder = CycleCrossover;
+
+/// The `EdgeRecombinationCrossover` operator combines permutation encoded
+/// `genetic::Genotype`s according the edge recombination crossover scheme
+/// (ERX). It is suited best for problems where the adjacency of the values
+/// matters, like the tours of the traveling salesman problem.
+///
+/// The genomes are treated as closed tours. An edge map holds the neighbors
+/// of each value in both parents. The child starts with the first value of
+/// one parent. Then the child is extended by the neighbor of the last value
+/// in the edge map that has the fewest remaining neighbors itself. Values
+/// that are already part of the child are removed from the edge map. Ties are
+/// broken at random. If the last value has no remaining neighbors the child is
+/// extended by a random value that is not yet part of the child. Thus every
+/// child is a valid permutation of the values of its parents and most of its
+/// edges are inherited from one of the parents.
+///
+/// The values of the genome must implement `Eq` and `Hash`.
+///
+/// This crossover operator always creates as many child individuals as there
+/// are individuals in the given `genetic::Parents` parameter.
+#[allow(missing_copy_implementations)]
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct EdgeRecombinationCrossover {}
+
+impl EdgeRecombinationCrossover {
+    pub fn new() -> Self {
+        EdgeRecombinationCrossover {}
+    }
+}
+
+impl GeneticOperator for EdgeRecombinationCrossover {
+    fn name() -> String {
+        "Edge-Recombination-Crossover".to_string()
+    }
+}
+
+impl<V> CrossoverOp<Vec<V>> for EdgeRecombinationCrossover
+where
+    V: Clone + Debug + Eq + Hash + Send + Sync,
+{
+    fn crossover<R>(&self, parents: Parents<Vec<V>>, rng: &mut R) -> Children<Vec<V>>
+    where
+        R: Rng + Sized,
+    {
+        let parents_size = parents.len();
+        (0..parents_size)
+            .map(|p1_index| {
+                let p2_index = (p1_index + 1) % parents_size;
+                edge_recombination_crossover(&parents[p1_index], &parents[p2_index], rng)
+            })
+            .collect()
+    }
+}
+
+/// The `EdgeRecombinationCrossBreeder` is an alias of the
+/// `EdgeRecombinationCrossover` following the naming of the cross breeders in
+/// the `discrete` module.
+pub type EdgeRecombinationCrossBreeder = EdgeRecombinationCrossover;
+
+fn multi_parents_cyclic_crossover<'a, V, FN, R>(
+    parents: ParentsSlice<'a, Vec<V>>,
     crossover: FN,
     rng: &mut R,
-) -> Children<Vec<usize>>
+) -> Children<Vec<V>>
 where
-    FN: Fn(&'a [usize], &'a [usize], usize, usize) -> Vec<usize>,
+    FN: Fn(&'a [V], &'a [V], usize, usize) -> Vec<V>,
     R: Rng + Sized,
 {
     let parents_size = parents.len();
     let genome_length = parents[0].len();
     // breed one child for each partner in parents
-    let mut offspring: Vec<Vec<usize>> = Vec::with_capacity(parents_size);
+    let mut offspring: Vec<Vec<V>> = Vec::with_capacity(parents_size);
     let mut p1_index = 0;
     let mut p2_index = 1;
     while p1_index < parents_size {
@@ -103,16 +250,19 @@ where
     offspring
 }
 
-fn order_one_crossover(
-    parent1: &[usize],
-    parent2: &[usize],
+fn order_one_crossover<V>(
+    parent1: &[V],
+    parent2: &[V],
     cutpoint1: usize,
     cutpoint2: usize,
-) -> Vec<usize> {
+) -> Vec<V>
+where
+    V: Clone + PartialEq,
+{
     let genome_length = parent1.len();
-    let mut genome: Vec<usize> = Vec::with_capacity(genome_length);
+    let mut genome: Vec<V> = Vec::with_capacity(genome_length);
     // collect genes of parent1 located at cutpoint1 to cutpoint2
-    let mut p1_slice: Vec<usize> = if cutpoint1 == 0 {
+    let mut p1_slice: Vec<V> = if cutpoint1 == 0 {
         parent1
             .iter()
             .take(cutpoint2 + 1)
@@ -127,12 +277,12 @@ fn order_one_crossover(
             .collect()
     };
     // collect genes from parent2 which are not in cut slice
-    let mut p2_slice: Vec<usize> = Vec::with_capacity(genome_length);
+    let mut p2_slice: Vec<V> = Vec::with_capacity(genome_length);
     let mut p2_index = (cutpoint2 + 1) % genome_length;
     for _ in 0..genome_length {
-        let p2_genome = parent2[p2_index];
-        if p1_slice.iter().all(|g| p2_genome != *g) {
-            p2_slice.push(p2_genome);
+        let p2_genome = &parent2[p2_index];
+        if p1_slice.iter().all(|g| p2_genome != g) {
+            p2_slice.push(p2_genome.clone());
         }
         p2_index += 1;
         if p2_index >= genome_length {
@@ -154,21 +304,21 @@ fn order_one_crossover(
     genome
 }
 
-fn partial_mapped_crossover(
-    parent1: &[usize],
-    parent2: &[usize],
+fn partial_mapped_crossover<V>(
+    parent1: &[V],
+    parent2: &[V],
     cutpoint1: usize,
     cutpoint2: usize,
-) -> Vec<usize> {
+) -> Vec<V>
+where
+    V: Clone + Eq + Hash,
+{
     let genome_length = parent1.len();
-    let mut genome: Vec<usize> = Vec::with_capacity(genome_length);
-    // using HashMap as indexed array of variable length
-    let mut result: HashMap<usize, usize> = HashMap::with_capacity(genome_length);
+    let mut genome: Vec<V> = parent2.to_vec();
     // mapping of value to index
-    let mut inverse: HashMap<usize, usize> = HashMap::with_capacity(genome_length);
+    let mut inverse: HashMap<V, usize> = HashMap::with_capacity(genome_length);
     for (i, v2) in parent2.iter().enumerate() {
-        result.insert(i, *v2);
-        inverse.insert(*v2, i);
+        inverse.insert(v2.clone(), i);
     }
     for (j, v1) in parent1
         .iter()
@@ -176,14 +326,103 @@ fn partial_mapped_crossover(
         .take(cutpoint2 + 1)
         .skip(cutpoint1)
     {
-        let orig = result[&j];
-        result.insert(j, *v1);
+        let orig = genome[j].clone();
+        genome[j] = v1.clone();
         let k = inverse[v1];
-        result.insert(k, orig);
+        genome[k] = orig.clone();
         inverse.insert(orig, k);
     }
-    for i in 0..genome_length {
-        genome.push(result[&i])
+    genome
+}
+
+fn cycle_crossover<V>(parent1: &[V], parent2: &[V]) -> Vec<V>
+where
+    V: Clone + Eq,
+{
+    let genome_length = parent1.len();
+    let mut genome: Vec<V> = parent2.to_vec();
+    let mut visited = vec![false; genome_length];
+    let mut from_parent1 = true;
+    for start in 0..genome_length {
+        if visited[start] {
+            continue;
+        }
+        let mut locus = start;
+        while !visited[locus] {
+            visited[locus] = true;
+            if from_parent1 {
+                genome[locus] = parent1[locus].clone();
+            }
+            locus = parent1
+                .iter()
+                .position(|v1| *v1 == parent2[locus])
+                .expect("parents must be permutations of the same values");
+        }
+        from_parent1 = !from_parent1;
+    }
+    genome
+}
+
+fn edge_recombination_crossover<V, R>(parent1: &[V], parent2: &[V], rng: &mut R) -> Vec<V>
+where
+    V: Clone + Eq + Hash,
+    R: Rng + Sized,
+{
+    let genome_length = parent1.len();
+    if genome_length == 0 {
+        return Vec::new();
+    }
+    // the values are identified by their position in parent1
+    let mut index_of: HashMap<&V, usize> = HashMap::with_capacity(genome_length);
+    for (i, v1) in parent1.iter().enumerate() {
+        index_of.insert(v1, i);
+    }
+    // edge map of the closed tours of both parents
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::with_capacity(4); genome_length];
+    let parent2_indices: Vec<usize> = parent2.iter().map(|v2| index_of[v2]).collect();
+    for tour in &[(0..genome_length).collect(), parent2_indices] {
+        for (position, &value) in tour.iter().enumerate() {
+            let next = tour[(position + 1) % genome_length];
+            if value != next && !neighbors[value].contains(&next) {
+                neighbors[value].push(next);
+                neighbors[next].push(value);
+            }
+        }
+    }
+    let mut remaining: Vec<usize> = (0..genome_length).collect();
+    let mut candidates: Vec<usize> = Vec::with_capacity(4);
+    let mut genome: Vec<V> = Vec::with_capacity(genome_length);
+    let mut current = 0;
+    loop {
+        genome.push(parent1[current].clone());
+        let position = remaining
+            .iter()
+            .position(|&value| value == current)
+            .expect("every value is visited only once");
+        remaining.swap_remove(position);
+        if remaining.is_empty() {
+            break;
+        }
+        for i in 0..neighbors[current].len() {
+            let neighbor = neighbors[current][i];
+            neighbors[neighbor].retain(|&value| value != current);
+        }
+        let fewest_neighbors = neighbors[current]
+            .iter()
+            .map(|&neighbor| neighbors[neighbor].len())
+            .min();
+        current = match fewest_neighbors {
+            Some(fewest) => {
+                candidates.clear();
+                candidates.extend(
+                    neighbors[current]
+                        .iter()
+                        .filter(|&&neighbor| neighbors[neighbor].len() == fewest),
+                );
+                candidates[random_index(rng, candidates.len())]
+            },
+            None => remaining[random_index(rng, remaining.len())],
+        };
     }
     genome
 }
@@ -191,7 +430,15 @@ fn partial_mapped_crossover(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::random::get_rng;
     use galvanic_assert::matchers::*;
+    use proptest::prelude::*;
+
+    fn sorted<V: Clone + Ord>(genome: &[V]) -> Vec<V> {
+        let mut sorted = genome.to_vec();
+        sorted.sort();
+        sorted
+    }
 
     #[test]
     fn order_one_crossover_cutpoints_3_6() {
@@ -336,4 +583,166 @@ mod tests {
         let children = partial_mapped_crossover(&p2, &p1, 1, 7);
         expect_that!(&children, eq(vec![1, 3, 7, 8, 2, 6, 5, 9, 4]));
     }
+
+    #[test]
+    fn partially_mapped_crossover_creates_permutations_of_8_cities() {
+        let crossover = PartiallyMappedCrossover::new();
+        let mut rng = get_rng([42; 32]);
+        let parents = vec![
+            vec!['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'],
+            vec!['d', 'h', 'a', 'f', 'b', 'g', 'e', 'c'],
+            vec!['h', 'g', 'f', 'e', 'd', 'c', 'b', 'a'],
+        ];
+
+        for _ in 0..100 {
+            let children = crossover.crossover(parents.clone(), &mut rng);
+            expect_that!(&children.len(), eq(3));
+            for child in children {
+                expect_that!(&sorted(&child), eq(sorted(&parents[0])));
+            }
+        }
+    }
+
+    #[test]
+    fn order_one_crossover_of_identical_parents_creates_identical_children() {
+        let crossover = OrderOneCrossover::new();
+        let mut rng = get_rng([42; 32]);
+        let parent = vec!["a", "b", "c", "d", "e", "f", "g", "h"];
+
+        for _ in 0..100 {
+            let children = crossover.crossover(vec![parent.clone(), parent.clone()], &mut rng);
+            expect_that!(&children, eq(vec![parent.clone(), parent.clone()]));
+        }
+    }
+
+    #[test]
+    fn cycle_crossover_copies_alternating_cycles_from_the_parents() {
+        let crossover = CycleCrossover::new();
+        let mut rng = get_rng([42; 32]);
+        let parents = vec![vec![1, 2, 3, 4, 5, 6, 7, 8], vec![8, 5, 2, 1, 3, 6, 4, 7]];
+
+        let children = crossover.crossover(parents, &mut rng);
+
+        // cycles: {0, 7, 6, 3}, {1, 4, 2} and {5}
+        expect_that!(
+            &children,
+            eq(vec![
+                vec![1, 5, 2, 4, 3, 6, 7, 8],
+                vec![8, 2, 3, 1, 5, 6, 4, 7],
+            ])
+        );
+    }
+
+    #[test]
+    fn cycle_crossover_of_a_single_cycle_creates_copies_of_the_parents() {
+        let crossover = CycleCrossover::new();
+        let mut rng = get_rng([42; 32]);
+        let parents = vec![vec!['a', 'b', 'c', 'd', 'e'], vec!['b', 'c', 'd', 'e', 'a']];
+
+        let children = crossover.crossover(parents.clone(), &mut rng);
+
+        expect_that!(&children, eq(parents));
+    }
+
+    fn edges<V: Clone + Ord>(tour: &[V]) -> Vec<(V, V)> {
+        (0..tour.len())
+            .map(|i| {
+                let a = tour[i].clone();
+                let b = tour[(i + 1) % tour.len()].clone();
+                if a < b {
+                    (a, b)
+                } else {
+                    (b, a)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn edge_recombination_crossover_creates_tours_of_8_cities_with_inherited_edges() {
+        let crossover = EdgeRecombinationCrossover::new();
+        let mut rng = get_rng([42; 32]);
+        let parents = vec![
+            vec!['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'],
+            vec!['d', 'h', 'a', 'f', 'b', 'g', 'e', 'c'],
+        ];
+        let parent_edges: Vec<(char, char)> = parents.iter().flat_map(|p| edges(p)).collect();
+
+        let mut num_edges = 0;
+        let mut num_inherited_edges = 0;
+        for _ in 0..100 {
+            let children = crossover.crossover(parents.clone(), &mut rng);
+            expect_that!(&children.len(), eq(2));
+            for child in children {
+                expect_that!(&sorted(&child), eq(sorted(&parents[0])));
+                for edge in edges(&child) {
+                    num_edges += 1;
+                    if parent_edges.contains(&edge) {
+                        num_inherited_edges += 1;
+                    }
+                }
+            }
+        }
+
+        // a random tour would inherit only 16 of 28 possible edges
+        let inherited_fraction = num_inherited_edges as f64 / num_edges as f64;
+        expect_that!(&inherited_fraction, is(greater_than(0.9)));
+    }
+
+    #[test]
+    fn edge_recombination_crossover_of_identical_parents_inherits_all_edges() {
+        let crossover = EdgeRecombinationCrossover::new();
+        let mut rng = get_rng([42; 32]);
+        let parent = vec![3, 1, 4, 0, 5, 2, 6];
+
+        for _ in 0..100 {
+            let children = crossover.crossover(vec![parent.clone(), parent.clone()], &mut rng);
+            for child in children {
+                expect_that!(&sorted(&edges(&child)), eq(sorted(&edges(&parent))));
+            }
+        }
+    }
+
+    proptest! {
+
+        #[test]
+        fn order_one_crossover_preserves_the_order_of_parent2(
+            parent1 in Just((0..12usize).collect::<Vec<_>>()).prop_shuffle(),
+            parent2 in Just((0..12usize).collect::<Vec<_>>()).prop_shuffle(),
+            cutpoint1 in 0..12usize,
+            length in 0..12usize,
+        ) {
+            let cutpoint2 = (cutpoint1 + length).min(11);
+
+            let child = order_one_crossover(&parent1, &parent2, cutpoint1, cutpoint2);
+
+            prop_assert_eq!(sorted(&child), sorted(&parent1));
+            prop_assert_eq!(&child[cutpoint1..=cutpoint2], &parent1[cutpoint1..=cutpoint2]);
+            // the values outside the slice are in the order of parent2 when
+            // read cyclically starting after the slice
+            let slice = &parent1[cutpoint1..=cutpoint2];
+            let child_order: Vec<usize> = (cutpoint2 + 1..cutpoint2 + 1 + 12)
+                .map(|i| child[i % 12])
+                .filter(|v| !slice.contains(v))
+                .collect();
+            let parent2_order: Vec<usize> = (cutpoint2 + 1..cutpoint2 + 1 + 12)
+                .map(|i| parent2[i % 12])
+                .filter(|v| !slice.contains(v))
+                .collect();
+            prop_assert_eq!(child_order, parent2_order);
+        }
+
+        #[test]
+        fn cycle_crossover_keeps_every_value_at_a_position_of_a_parent(
+            parent1 in Just((0..12usize).collect::<Vec<_>>()).prop_shuffle(),
+            parent2 in Just((0..12usize).collect::<Vec<_>>()).prop_shuffle(),
+        ) {
+            let child = cycle_crossover(&parent1, &parent2);
+
+            prop_assert_eq!(sorted(&child), sorted(&parent1));
+            for (locus, value) in child.iter().enumerate() {
+                prop_assert!(*value == parent1[locus] || *value == parent2[locus]);
+            }
+        }
+    }
 }
@@ -0,0 +1,183 @@
+//! The `probabilistic` module provides an `operator::CrossoverOp` that
+//! recombines the parents only with a given probability.
+//!
+//! Classic genetic algorithms apply the crossover with a crossover rate
+//! `p_c`. With the probability `1 - p_c` the parents are passed on to the
+//! offspring unchanged, which preserves good combinations of genes.
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genetic::{Children, Genotype, Parents},
+    operator::{validate_ratio, CrossoverOp, CrossoverRate, GenerationSignal, GeneticOperator},
+    random::{random_probability, Rng},
+};
+
+/// The `ProbabilisticCrossover` wraps another `CrossoverOp` and applies it
+/// with the probability `crossover_rate`. Otherwise the parents are copied
+/// to the children without recombination, so that there are as many children
+/// as there are parents.
+///
+/// Whether the parents are recombined is decided for each `genetic::Parents`
+/// by a random number drawn from the given `Rng`, so that a simulation with
+/// the same seed always produces the same results. With a `crossover_rate` of
+/// 1.0 or 0.0 no random number is drawn at all. Thus a
+/// `ProbabilisticCrossover` with a `crossover_rate` of 1.0 produces exactly
+/// the same children as the wrapped `CrossoverOp`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProbabilisticCrossover<C> {
+    /// The wrapped `CrossoverOp`.
+    breeder: C,
+    /// The probability that the parents are recombined.
+    crossover_rate: f64,
+}
+
+impl<C> ProbabilisticCrossover<C> {
+    /// Constructs a new instance of the `ProbabilisticCrossover` that applies
+    /// the given `CrossoverOp` with the probability `crossover_rate`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `crossover_rate` is not between 0 and 1 (inclusive).
+    pub fn new(breeder: C, crossover_rate: f64) -> Self {
+        assert!(
+            (0. ..=1.).contains(&crossover_rate),
+            "the crossover rate must be between 0 and 1"
+        );
+        ProbabilisticCrossover {
+            breeder,
+            crossover_rate,
+        }
+    }
+
+    /// Returns the wrapped `CrossoverOp`.
+    pub fn breeder(&self) -> &C {
+        &self.breeder
+    }
+
+    /// Returns the probability that the parents are recombined.
+    pub fn crossover_rate(&self) -> f64 {
+        self.crossover_rate
+    }
+
+    /// Sets the probability that the parents are recombined. The value must
+    /// be between 0 and 1 (inclusive).
+    pub fn set_crossover_rate(&mut self, value: f64) {
+        self.crossover_rate = value;
+    }
+
+    /// Returns whether the next parents are recombined.
+    fn is_recombined<R>(&self, rng: &mut R) -> bool
+    where
+        R: Rng + Sized,
+    {
+        if self.crossover_rate >= 1. {
+            true
+        } else if self.crossover_rate <= 0. {
+            false
+        } else {
+            random_probability(rng) < self.crossover_rate
+        }
+    }
+}
+
+impl<C> CrossoverRate for ProbabilisticCrossover<C> {
+    fn crossover_rate(&self) -> f64 {
+        self.crossover_rate
+    }
+
+    fn set_crossover_rate(&mut self, value: f64) {
+        self.crossover_rate = value;
+    }
+}
+
+impl<C> GeneticOperator for ProbabilisticCrossover<C>
+where
+    C: GeneticOperator,
+{
+    fn name() -> String {
+        format!("Probabilistic-{}", C::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("crossover rate", self.crossover_rate)?;
+        self.breeder.validate()
+    }
+}
+
+impl<G, C> CrossoverOp<G> for ProbabilisticCrossover<C>
+where
+    G: Genotype,
+    C: CrossoverOp<G>,
+{
+    fn crossover<R>(&self, parents: Parents<G>, rng: &mut R) -> Children<G>
+    where
+        R: Rng + Sized,
+    {
+        if self.is_recombined(rng) {
+            self.breeder.crossover(parents, rng)
+        } else {
+            parents
+        }
+    }
+
+    fn required_parents_size(&self) -> Option<usize> {
+        self.breeder.required_parents_size()
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.breeder.adapt(signal);
+    }
+
+    fn reset(&mut self) {
+        self.breeder.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{random::get_rng, recombination::discrete::MultiPointCrossBreeder};
+    use galvanic_assert::matchers::*;
+
+    fn parents() -> Parents<Vec<u8>> {
+        vec![vec![0; 8], vec![1; 8]]
+    }
+
+    #[test]
+    fn a_crossover_rate_of_0_copies_the_parents() {
+        let breeder = ProbabilisticCrossover::new(MultiPointCrossBreeder::new(2), 0.0);
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..100 {
+            expect_that!(&breeder.crossover(parents(), &mut rng), eq(parents()));
+        }
+    }
+
+    #[test]
+    fn a_crossover_rate_of_1_breeds_like_the_wrapped_breeder() {
+        let wrapped = MultiPointCrossBreeder::new(2);
+        let breeder = ProbabilisticCrossover::new(wrapped.clone(), 1.0);
+        let mut rng = get_rng([42; 32]);
+        let mut wrapped_rng = get_rng([42; 32]);
+
+        for _ in 0..100 {
+            expect_that!(
+                &breeder.crossover(parents(), &mut rng),
+                eq(wrapped.crossover(parents(), &mut wrapped_rng))
+            );
+        }
+    }
+
+    #[test]
+    fn the_parents_are_recombined_with_the_crossover_rate() {
+        let breeder = ProbabilisticCrossover::new(MultiPointCrossBreeder::new(2), 0.3);
+        let mut rng = get_rng([42; 32]);
+
+        let num_recombined = (0..10_000)
+            .filter(|_| breeder.crossover(parents(), &mut rng) != parents())
+            .count();
+
+        expect_that!(&num_recombined, is(greater_than(2_800)));
+        expect_that!(&num_recombined, is(less_than(3_200)));
+    }
+}
@@ -0,0 +1,165 @@
+//! The `splice` module provides `operator::CrossoverOp`s for variable-length
+//! genomes, whose children may be shorter or longer than their parents.
+//!
+//! The provided `operator::CrossoverOp`s are:
+//! * `CutAndSpliceCrossBreeder` for `Vec` of any type.
+
+use crate::{
+    ga::GeneticAlgorithmError,
+    genetic::{Children, Parents},
+    operator::{validate_count, CrossoverOp, GeneticOperator},
+    random::{random_index_from_range, Rng},
+};
+use std::fmt::Debug;
+
+/// The `CutAndSpliceCrossBreeder` cuts each parent at its own random cut
+/// point and splices the head of one parent with the tail of the next one.
+///
+/// As the cut points of the parents are chosen independently, the lengths of
+/// the children differ from the lengths of their parents. With the parents
+/// `p1` and `p2` and their cut points `c1` and `c2` the children are
+/// `p1[..c1] + p2[c2..]` and `p2[..c2] + p1[c1..]`. For more than two parents
+/// the `i`-th child is spliced from the `i`-th parent and the next one.
+///
+/// A cut point is chosen from `1..=len`, so that each child starts with at
+/// least one gene of its first parent, unless this parent is empty. Children
+/// that are longer than `max_length` are truncated to `max_length` genes.
+/// Thus the children of parents with at least one gene are never empty and
+/// never longer than the maximum length.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CutAndSpliceCrossBreeder {
+    /// The maximum number of genes of a child.
+    max_length: usize,
+}
+
+impl CutAndSpliceCrossBreeder {
+    /// Constructs a new instance of the `CutAndSpliceCrossBreeder` that
+    /// breeds children of at most `max_length` genes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_length` is 0.
+    pub fn new(max_length: usize) -> Self {
+        assert!(max_length > 0, "the maximum length must be greater than 0");
+        CutAndSpliceCrossBreeder { max_length }
+    }
+
+    /// Returns the maximum number of genes of a child.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Sets the maximum number of genes of a child. The value must be greater
+    /// than 0.
+    pub fn set_max_length(&mut self, value: usize) {
+        self.max_length = value;
+    }
+}
+
+impl GeneticOperator for CutAndSpliceCrossBreeder {
+    fn name() -> String {
+        "Cut-And-Splice-Cross-Breeder".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_count::<Self>("maximum length", self.max_length)
+    }
+}
+
+impl<V> CrossoverOp<Vec<V>> for CutAndSpliceCrossBreeder
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn crossover<R>(&self, parents: Parents<Vec<V>>, rng: &mut R) -> Children<Vec<V>>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = parents.len();
+        let cut_points: Vec<usize> = parents
+            .iter()
+            .map(|parent| {
+                if parent.is_empty() {
+                    0
+                } else {
+                    random_index_from_range(rng, 1, parent.len() + 1)
+                }
+            })
+            .collect();
+        (0..num_parents)
+            .map(|index| {
+                let next = (index + 1) % num_parents;
+                let head = &parents[index][..cut_points[index]];
+                let tail = &parents[next][cut_points[next]..];
+                let length = (head.len() + tail.len()).min(self.max_length);
+                head.iter().chain(tail).take(length).cloned().collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::get_rng;
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn children_are_spliced_from_the_head_and_the_tail_of_the_parents() {
+        let breeder = CutAndSpliceCrossBreeder::new(100);
+        let mut rng = get_rng([42; 32]);
+        let parents: Vec<Vec<u32>> = vec![(0..10).collect(), (100..105).collect()];
+
+        for _ in 0..100 {
+            let children = breeder.crossover(parents.clone(), &mut rng);
+
+            expect_that!(&children.len(), eq(2));
+            let total_length: usize = children.iter().map(Vec::len).sum();
+            expect_that!(&total_length, eq(15));
+            // the head of the first child is the first parent
+            let head_length = children[0].iter().take_while(|gene| **gene < 100).count();
+            expect_that!(&head_length, is(greater_than(0)));
+            expect_that!(
+                &children[0][..head_length].to_vec(),
+                eq(parents[0][..head_length].to_vec())
+            );
+            expect_that!(
+                &children[1][children[1].len() - (10 - head_length)..].to_vec(),
+                eq(parents[0][head_length..].to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn children_of_varying_lengths_stay_within_the_maximum_length() {
+        let breeder = CutAndSpliceCrossBreeder::new(12);
+        let mut rng = get_rng([42; 32]);
+        let mut parents: Vec<Vec<u8>> = vec![vec![1; 12], vec![2; 3]];
+        let mut lengths = std::collections::HashSet::new();
+
+        for _ in 0..1_000 {
+            let children = breeder.crossover(parents, &mut rng);
+            for child in &children {
+                expect_that!(&child.len(), is(greater_than(0)));
+                expect_that!(&child.len(), leq(12));
+                lengths.insert(child.len());
+            }
+            parents = children;
+        }
+
+        expect_that!(&lengths.len(), is(greater_than(5)));
+    }
+
+    #[test]
+    fn children_longer_than_the_maximum_length_are_truncated() {
+        let breeder = CutAndSpliceCrossBreeder::new(4);
+        let mut rng = get_rng([42; 32]);
+        let parents: Vec<Vec<u8>> = vec![vec![1; 4], vec![2; 4]];
+
+        for _ in 0..100 {
+            for child in breeder.crossover(parents.clone(), &mut rng) {
+                expect_that!(&child.len(), leq(4));
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! The `tree` module provides an `operator::CrossoverOp` for
+//! `genotype::tree::ExpressionTree` genomes.
+//!
+//! The provided `operator::CrossoverOp`s are:
+//! * `SubtreeCrossBreeder` for `ExpressionTree`s of any type.
+
+use crate::{
+    genetic::{Children, Parents},
+    genotype::tree::ExpressionTree,
+    operator::{CrossoverOp, GeneticOperator},
+    random::{random_index, Rng},
+};
+use std::fmt::Debug;
+
+/// The `SubtreeCrossBreeder` replaces a random subtree of one parent by a
+/// random subtree of the next parent.
+///
+/// The `SubtreeCrossBreeder` breeds one child per parent. The child at index
+/// `i` is a copy of the parent at index `i` in which the subtree rooted at a
+/// random crossover point is replaced by a copy of a random subtree of the
+/// next parent. The last child gets the subtree from the first parent.
+///
+/// To prevent bloat the subtree of the donating parent is only chosen among
+/// the subtrees that keep the child within `max_depth`. As whole subtrees are
+/// exchanged, each function keeps its number of arguments, so the children
+/// of valid trees are valid trees too. A child whose crossover point cannot
+/// take any subtree within `max_depth`, which only happens if the receiving
+/// parent is already deeper than `max_depth`, is a copy of its parent.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubtreeCrossBreeder {
+    /// The maximum depth of a child.
+    max_depth: usize,
+}
+
+impl SubtreeCrossBreeder {
+    /// Constructs a new instance of the `SubtreeCrossBreeder` that breeds
+    /// children with a depth of at most `max_depth`.
+    pub fn new(max_depth: usize) -> Self {
+        SubtreeCrossBreeder { max_depth }
+    }
+
+    /// Returns the maximum depth of a child.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Sets the maximum depth of a child.
+    pub fn set_max_depth(&mut self, value: usize) {
+        self.max_depth = value;
+    }
+}
+
+impl GeneticOperator for SubtreeCrossBreeder {
+    fn name() -> String {
+        "Subtree-Cross-Breeder".to_string()
+    }
+}
+
+impl<T> CrossoverOp<ExpressionTree<T>> for SubtreeCrossBreeder
+where
+    T: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn crossover<R>(
+        &self,
+        parents: Parents<ExpressionTree<T>>,
+        rng: &mut R,
+    ) -> Children<ExpressionTree<T>>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = parents.len();
+        let mut offspring: Children<ExpressionTree<T>> = Vec::with_capacity(num_parents);
+        for index in 0..num_parents {
+            let mut child = parents[index].clone();
+            let donor = &parents[(index + 1) % num_parents];
+            let crossover_point = random_index(rng, child.size());
+            let level = child
+                .level_of(crossover_point)
+                .expect("the crossover point is a node of the child");
+            let candidates: Vec<usize> = (0..donor.size())
+                .filter(|donor_index| {
+                    donor
+                        .subtree(*donor_index)
+                        .is_some_and(|subtree| level + subtree.depth() <= self.max_depth)
+                })
+                .collect();
+            if !candidates.is_empty() {
+                let donated = candidates[random_index(rng, candidates.len())];
+                let subtree = donor
+                    .subtree(donated)
+                    .expect("the donated subtree is a node of the donor")
+                    .clone();
+                child.replace_subtree(crossover_point, subtree);
+            }
+            offspring.push(child);
+        }
+        offspring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        genotype::tree::{GrowthMethod, PrimitiveSet},
+        random::get_rng,
+    };
+    use galvanic_assert::matchers::*;
+
+    fn primitives() -> PrimitiveSet<char> {
+        PrimitiveSet::new(vec![('+', 2), ('*', 2), ('-', 1)], vec!['x', '1'])
+    }
+
+    fn arity_of(value: char) -> usize {
+        match value {
+            '+' | '*' => 2,
+            '-' => 1,
+            _ => 0,
+        }
+    }
+
+    fn is_valid(tree: &ExpressionTree<char>) -> bool {
+        tree.children().len() == arity_of(*tree.value()) && tree.children().iter().all(is_valid)
+    }
+
+    #[test]
+    fn children_are_valid_trees_within_the_maximum_depth() {
+        let primitives = primitives();
+        let breeder = SubtreeCrossBreeder::new(5);
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..100 {
+            let parents = vec![
+                primitives.grow_tree(GrowthMethod::Full, 5, &mut rng),
+                primitives.grow_tree(GrowthMethod::Grow, 5, &mut rng),
+            ];
+
+            let children = breeder.crossover(parents, &mut rng);
+
+            expect_that!(&children.len(), eq(2));
+            for child in &children {
+                expect_that!(&child.depth(), leq(5));
+                expect_that!(&is_valid(child), is(eq(true)));
+            }
+        }
+    }
+
+    #[test]
+    fn children_get_a_subtree_of_the_next_parent() {
+        let parents = vec![
+            ExpressionTree::Node('-', vec![ExpressionTree::Leaf('x')]),
+            ExpressionTree::Node('-', vec![ExpressionTree::Leaf('1')]),
+        ];
+        let breeder = SubtreeCrossBreeder::new(1);
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..20 {
+            let children = breeder.crossover(parents.clone(), &mut rng);
+
+            // the subtree `-(-(1))` would exceed the maximum depth
+            expect_that!(
+                &children[0],
+                any_of!(
+                    eq(ExpressionTree::Node('-', vec![ExpressionTree::Leaf('1')])),
+                    eq(ExpressionTree::Leaf('1'))
+                )
+            );
+            expect_that!(
+                &children[1],
+                any_of!(
+                    eq(ExpressionTree::Node('-', vec![ExpressionTree::Leaf('x')])),
+                    eq(ExpressionTree::Leaf('x'))
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! The `crowding` module provides `operator::ReinsertionOp` that let the
+//! individuals of the offspring compete only against similar individuals of
+//! the old population. Crowding maintains several niches in a population and
+//! is therefore suited for multimodal fitness landscapes.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    genetic::{Fitness, FitnessFunction, GenomeDistance, Genotype, Offspring},
+    operator::{GeneticOperator, MultiObjective, ReinsertionOp, SingleObjective},
+    random::Rng,
+};
+use std::{cmp::Ordering, marker::PhantomData};
+
+/// The `DeterministicCrowding` reinserter lets each individual of the
+/// offspring compete against the most similar individual of the old
+/// population. The individual from the offspring replaces its competitor if
+/// it has a higher fitness. Otherwise the individual of the old population
+/// survives.
+///
+/// The similarity of two individuals is measured by the given
+/// `genetic::GenomeDistance`, e.g. `genetic::HammingDistance` for discrete
+/// genomes or `genetic::EuclideanDistance` for continuous genomes.
+///
+/// A `ReinsertionOp` does not know which parents an individual of the
+/// offspring has been bred from. Therefore each individual of the offspring is
+/// paired with its nearest neighbor in the population, which usually is one
+/// of its parents. The individuals of the offspring compete one after the
+/// other in the order of the offspring, so that an individual of the
+/// offspring that has replaced an individual of the old population may be
+/// challenged by later individuals of the offspring. The population size is
+/// preserved and the offspring is left empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeterministicCrowding<G, F, E, D>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    D: GenomeDistance<G>,
+{
+    /// The `FitnessFunction` to be used to calculate fitness values of
+    /// individuals of the offspring.
+    fitness_evaluator: Box<E>,
+    /// The `GenomeDistance` to be used to find the most similar individual
+    /// of the old population.
+    distance: D,
+    // phantom types
+    _g: PhantomData<G>,
+    _f: PhantomData<F>,
+}
+
+impl<G, F, E, D> DeterministicCrowding<G, F, E, D>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    D: GenomeDistance<G>,
+{
+    /// Constructs a new instance of the `DeterministicCrowding` reinserter.
+    pub fn new(fitness_evaluator: E, distance: D) -> Self {
+        DeterministicCrowding {
+            fitness_evaluator: Box::new(fitness_evaluator),
+            distance,
+            _g: PhantomData,
+            _f: PhantomData,
+        }
+    }
+
+    /// Returns the `GenomeDistance` used to find the most similar
+    /// individuals.
+    pub fn distance(&self) -> &D {
+        &self.distance
+    }
+}
+
+impl<G, F, E, D> GeneticOperator for DeterministicCrowding<G, F, E, D>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    D: GenomeDistance<G>,
+{
+    fn name() -> String {
+        "Deterministic-Crowding-Reinserter".to_string()
+    }
+}
+
+/// Can be used for single-objective optimization
+impl<G, F, E, D> SingleObjective for DeterministicCrowding<G, F, E, D>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    D: GenomeDistance<G>,
+{
+}
+/// Can be used for multi-objective optimization
+impl<G, F, E, D> MultiObjective for DeterministicCrowding<G, F, E, D>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    D: GenomeDistance<G>,
+{
+}
+
+impl<G, F, E, D> ReinsertionOp<G, F> for DeterministicCrowding<G, F, E, D>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+    D: GenomeDistance<G>,
+{
+    fn combine<R>(
+        &self,
+        offspring: &mut Offspring<G>,
+        evaluated: &EvaluatedPopulation<G, F>,
+        _: &mut R,
+    ) -> Vec<G>
+    where
+        R: Rng + Sized,
+    {
+        let mut new_population = evaluated.clone_individuals();
+        let mut fitness_values = evaluated.fitness_values().to_vec();
+        if new_population.is_empty() {
+            return new_population;
+        }
+
+        for child in offspring.drain(..) {
+            let nearest = (0..new_population.len())
+                .map(|index| {
+                    (
+                        index,
+                        self.distance.distance(&child, &new_population[index]),
+                    )
+                })
+                .min_by(|(_, distance1), (_, distance2)| distance1.total_cmp(distance2))
+                .map(|(index, _)| index)
+                .expect("a non empty population");
+            let fitness = self.fitness_evaluator.fitness_of(&child);
+            if evaluated.compare_fitness(&fitness, &fitness_values[nearest]) == Ordering::Greater {
+                new_population[nearest] = child;
+                fitness_values[nearest] = fitness;
+            }
+        }
+        new_population
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, genetic::EuclideanDistance, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct FirstGene;
+
+    impl FitnessFunction<Vec<f64>, i64> for FirstGene {
+        fn fitness_of(&self, individual: &Vec<f64>) -> i64 {
+            individual[0].round() as i64
+        }
+
+        fn average(&self, fitness_values: &[i64]) -> i64 {
+            fitness_values.iter().sum::<i64>() / fitness_values.len() as i64
+        }
+
+        fn highest_possible_fitness(&self) -> i64 {
+            i64::MAX
+        }
+
+        fn lowest_possible_fitness(&self) -> i64 {
+            i64::MIN
+        }
+    }
+
+    fn evaluated_population() -> EvaluatedPopulation<Vec<f64>, i64> {
+        let individuals = vec![vec![0.], vec![10.], vec![20.], vec![30.]];
+        fixtures::evaluated_population(individuals, vec![0, 10, 20, 30], 15)
+    }
+
+    #[test]
+    fn deterministic_crowding_replaces_nearest_individual_if_fitter() {
+        let reinserter = DeterministicCrowding::new(FirstGene, EuclideanDistance);
+        // the first competes against 10 and wins, the second against 20 and
+        // loses
+        let mut offspring = vec![vec![11.], vec![19.]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![0.], vec![11.], vec![20.], vec![30.]])
+        );
+        expect_that!(&offspring.is_empty(), is(eq(true)));
+    }
+
+    #[test]
+    fn deterministic_crowding_lets_later_offspring_challenge_replaced_individuals() {
+        let reinserter = DeterministicCrowding::new(FirstGene, EuclideanDistance);
+        // both are nearest to 30, the second one wins against the first one
+        let mut offspring = vec![vec![31.], vec![32.], vec![29.]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![0.], vec![10.], vec![20.], vec![32.]])
+        );
+    }
+
+    #[test]
+    fn deterministic_crowding_preserves_population_size() {
+        let reinserter = DeterministicCrowding::new(FirstGene, EuclideanDistance);
+        let mut offspring: Vec<Vec<f64>> = (0..10).map(|i| vec![i as f64 * 7.]).collect();
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(&new_population.len(), eq(4));
+        expect_that!(&offspring.is_empty(), is(eq(true)));
+    }
+}
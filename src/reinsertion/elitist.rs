@@ -4,12 +4,17 @@
 
 use crate::{
     algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
     genetic::{Fitness, FitnessFunction, Genotype, Offspring},
-    operator::{GeneticOperator, MultiObjective, ReinsertionOp, SingleObjective},
+    operator::{
+        validate_count, validate_ratio, GeneticOperator, MultiObjective, ReinsertionOp,
+        SingleObjective,
+    },
     random::Rng,
 };
-use std::marker::PhantomData;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
 use rayon::iter::{ParallelDrainRange, ParallelIterator};
+use std::{cmp::Ordering, marker::PhantomData};
 
 /// The `ElitistReinserter` combines the best individuals from the offspring and
 /// the old population. When there are more individuals in the offspring than
@@ -27,6 +32,13 @@ use rayon::iter::{ParallelDrainRange, ParallelIterator};
 /// individuals then the new population is filled up with individuals from the
 /// old population. If the offspring contains more individuals than the size of
 /// the population then the individuals are chosen uniformly at random.
+///
+/// Alternatively the reinserter can be configured by an elite count, see
+/// `ElitistReinserter::with_elite_count`. Then exactly the `elite_count` best
+/// individuals of the old population are taken over into the new population
+/// and the remaining spots are filled with the best individuals from the
+/// offspring. The replace ratio and whether the offspring has precedence are
+/// ignored if an elite count is set.
 #[derive(Clone, Debug, PartialEq)]
 pub struct ElitistReinserter<G, F, E>
 where
@@ -44,6 +56,10 @@ where
     /// The `replace_ratio` defines the fraction of the population size that
     /// is going to be replaced by individuals from the offspring.
     replace_ratio: f64,
+    /// The `elite_count` defines the number of the best individuals of the
+    /// old population that are taken over into the new population. If set it
+    /// takes precedence over the `replace_ratio`.
+    elite_count: Option<usize>,
     // phantom types
     _g: PhantomData<G>,
     _f: PhantomData<F>,
@@ -62,12 +78,36 @@ where
             fitness_evaluator: Box::new(fitness_evaluator),
             offspring_has_precedence,
             replace_ratio,
+            elite_count: None,
             _g: PhantomData,
             _f: PhantomData,
             is_parallel: false,
         }
     }
 
+    /// Constructs a new instance of the `ElitistReinserter` that takes over
+    /// the `elite_count` best individuals of the old population unchanged
+    /// and fills the remaining spots of the new population with the best
+    /// individuals from the offspring.
+    ///
+    /// This guarantees that the best individuals are never lost regardless
+    /// of the size of the population. If the elite count is greater than the
+    /// size of the population the old population is taken over completely.
+    /// If the offspring does not contain enough individuals the new
+    /// population is filled up with the next best individuals from the old
+    /// population.
+    pub fn with_elite_count(fitness_evaluator: E, elite_count: usize) -> Self {
+        ElitistReinserter {
+            elite_count: Some(elite_count),
+            ..ElitistReinserter::new(fitness_evaluator, false, 1.0)
+        }
+    }
+
+    /// Lets this `ElitistReinserter` calculate the fitness values of the
+    /// offspring in parallel.
+    ///
+    /// Parallel evaluation requires the crate feature `parallel`. Without it
+    /// the fitness values are calculated sequentially.
     pub fn as_parallel(mut self) -> Self {
         self.is_parallel = true;
         self
@@ -95,6 +135,19 @@ where
     pub fn set_replace_ratio(&mut self, value: f64) {
         self.replace_ratio = value;
     }
+
+    /// Returns the `elite_count` of this `ElitistReinserter` if it is
+    /// configured by an elite count.
+    pub fn elite_count(&self) -> Option<usize> {
+        self.elite_count
+    }
+
+    /// Sets the `elite_count` of this `ElitistReinserter` to the given value.
+    /// The elite count takes precedence over the `replace_ratio` and the
+    /// `offspring_has_precedence` flag.
+    pub fn set_elite_count(&mut self, value: usize) {
+        self.elite_count = Some(value);
+    }
 }
 
 impl<G, F, E> GeneticOperator for ElitistReinserter<G, F, E>
@@ -106,6 +159,10 @@ where
     fn name() -> String {
         "Uniform-Reinserter".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("replace ratio", self.replace_ratio)
+    }
 }
 
 /// Can be used for single-objective optimization
@@ -125,6 +182,47 @@ where
 {
 }
 
+impl<G, F, E> ElitistReinserter<G, F, E>
+where
+    G: Genotype + Send + Sync,
+    F: Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Send + Sync,
+{
+    /// Calculates the fitness values of the offspring, which is drained. The
+    /// returned individuals are in the same order as in the offspring.
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    fn evaluate_offspring(&self, offspring: &mut Offspring<G>) -> Vec<(G, F)> {
+        if self.is_parallel {
+            offspring
+                .par_drain(..)
+                .map(|individual| {
+                    let fitness = self.fitness_evaluator.fitness_of(&individual);
+                    (individual, fitness)
+                })
+                .collect()
+        } else {
+            self.seq_evaluate_offspring(offspring)
+        }
+    }
+
+    /// Calculates the fitness values of the offspring, which is drained. The
+    /// returned individuals are in the same order as in the offspring.
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    fn evaluate_offspring(&self, offspring: &mut Offspring<G>) -> Vec<(G, F)> {
+        self.seq_evaluate_offspring(offspring)
+    }
+
+    fn seq_evaluate_offspring(&self, offspring: &mut Offspring<G>) -> Vec<(G, F)> {
+        offspring
+            .drain(..)
+            .map(|individual| {
+                let fitness = self.fitness_evaluator.fitness_of(&individual);
+                (individual, fitness)
+            })
+            .collect()
+    }
+}
+
 impl<G, F, E> ReinsertionOp<G, F> for ElitistReinserter<G, F, E>
 where
     G: Genotype + Send + Sync,
@@ -142,43 +240,29 @@ where
     {
         let old_individuals = evaluated.individuals();
         let old_fitness_values = evaluated.fitness_values();
-        // holds indices to the individuals and fitness_values slices
-        let mut old_population_indices: Vec<usize> = (0..old_fitness_values.len()).collect();
-        // sort fitness indices from best performing to worst performing index
-        old_population_indices.sort_by(|x, y| old_fitness_values[*y].cmp(&old_fitness_values[*x]));
 
         let population_size = old_individuals.len();
         let mut new_population: Vec<G> = Vec::with_capacity(population_size);
 
         // How many individuals should we take from the offspring?
-        let num_offspring = (population_size as f64 * self.replace_ratio + 0.5).floor() as usize;
+        let num_offspring = match self.elite_count {
+            Some(elite_count) => population_size - elite_count.min(population_size),
+            None => (population_size as f64 * self.replace_ratio + 0.5).floor() as usize,
+        };
 
-        if self.offspring_has_precedence {
+        if self.offspring_has_precedence || self.elite_count.is_some() {
             // first pick individuals from offspring
             if num_offspring < offspring.len() {
                 // evaluate fitness of the offspring individuals
-                let mut offspring_fitness = if self.is_parallel {
-                    offspring.par_drain(..)
-                        .map(|individual| {
-                            let fitness = self.fitness_evaluator.fitness_of(&individual);
-                            (individual, fitness)
-                        })
-                        .collect::<Vec<_>>()
-                } else {
-                    let mut offspring_fitness: Vec<(G, F)> = Vec::with_capacity(offspring.len());
-                    while let Some(child) = offspring.pop() {
-                        let fitness = self.fitness_evaluator.fitness_of(&child);
-                        offspring_fitness.push((child, fitness));
-                    };
-                    offspring_fitness
-                };
-
-                // sort offspring from worst to best performing performing
-                offspring_fitness.sort_by(|x, y| x.1.cmp(&y.1));
+                let offspring_fitness = self.evaluate_offspring(offspring);
                 // pick only the best individuals from the offspring
-                for _ in 0..num_offspring {
-                    new_population.push(offspring_fitness.pop().unwrap().0);
-                }
+                let best_offspring = best_of_offspring(
+                    evaluated,
+                    offspring_fitness,
+                    num_offspring,
+                    |_, individual| offspring.push(individual),
+                );
+                new_population.extend(best_offspring.into_iter().map(|(individual, _)| individual));
             } else {
                 // insert all individuals from offspring
                 offspring.truncate(num_offspring);
@@ -186,41 +270,538 @@ where
             }
             // finally fill up new population with individuals from old population
             let num_old_population = population_size - new_population.len();
-            for index_old in old_population_indices.iter().take(num_old_population) {
+            for index_old in best_indices(evaluated, num_old_population) {
                 // pick only the best individuals from old population
-                new_population.push(old_individuals[*index_old].clone());
+                new_population.push(evaluated.clone_individual(index_old));
             }
         } else {
             // evaluate fitness of the offspring individuals
-            let mut offspring_fitness = if self.is_parallel {
-                offspring.par_drain(..)
-                    .map(|individual| {
-                        let fitness = self.fitness_evaluator.fitness_of(&individual);
-                        (individual, fitness)
-                    })
-                    .collect::<Vec<_>>()
-            } else {
-                let mut offspring_fitness: Vec<(G, F)> = Vec::with_capacity(offspring.len());
-                while let Some(child) = offspring.pop() {
-                    let fitness = self.fitness_evaluator.fitness_of(&child);
-                    offspring_fitness.push((child, fitness));
-                };
-                offspring_fitness
-            };
-            // sort offspring from worst to best performing performing
-            offspring_fitness.sort_by(|x, y| x.1.cmp(&y.1));
+            let offspring_fitness = self.evaluate_offspring(offspring);
+            // at most the whole population is taken from either side
+            let mut best_offspring = best_of_offspring(
+                evaluated,
+                offspring_fitness,
+                population_size,
+                |_, individual| offspring.push(individual),
+            )
+            .into_iter()
+            .peekable();
+            let old_population_indices = best_indices(evaluated, population_size);
+            let mut next_old = 0;
             for _ in 0..population_size {
                 // compare fitness of best offspring with best fitness of old population
+                let index_old = old_population_indices[next_old];
+                match best_offspring.peek() {
+                    Some((_, fitness))
+                        if evaluated.compare_fitness(fitness, &old_fitness_values[index_old])
+                            == Ordering::Greater =>
+                    {
+                        let (offspring, _) = best_offspring.next().unwrap();
+                        // insert best from offspring
+                        new_population.push(offspring);
+                    },
+                    _ => {
+                        // insert best from old population
+                        new_population.push(evaluated.clone_individual(index_old));
+                        next_old += 1;
+                    },
+                }
+            }
+            // leave the individuals that are not inserted in the offspring
+            offspring.extend(best_offspring.map(|(individual, _)| individual));
+        }
+        new_population
+    }
+}
+
+/// The `RevaluatingElitistReinserter` is an elitist reinserter for noisy
+/// fitness functions, i.e. `FitnessFunction`s that return different fitness
+/// values for the same genome on each call.
+///
+/// With such a fitness function the best individuals of a population are
+/// often those whose fitness has been over-estimated by chance. Strict
+/// elitism would keep such a lucky individual although its true fitness is
+/// only mediocre. Therefore the `elite_count` best individuals of the old
+/// population are not taken over by their recorded fitness values. Instead
+/// each of them is evaluated `num_reevaluations` times again and its fitness
+/// is the average of these evaluations, as calculated by
+/// `FitnessFunction::average`. The recorded fitness value is not part of the
+/// average, as it is biased by the selection of the elites.
+///
+/// The elites compete with the offspring by their averaged fitness values:
+/// the new population consists of the best individuals of the elites and the
+/// offspring. If these are not enough to fill up the new population, the
+/// next best individuals of the old population are taken over as well.
+///
+/// The re-evaluation is not free. Each call of `combine` calculates up to
+/// `elite_count * num_reevaluations` fitness values in addition to the
+/// fitness values of the offspring, see
+/// `RevaluatingElitistReinserter::num_extra_evaluations`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RevaluatingElitistReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    /// The `FitnessFunction` to be used to calculate fitness values of
+    /// the elites and of the offspring.
+    fitness_evaluator: Box<E>,
+    /// The number of the best individuals of the old population that are
+    /// re-evaluated and compete with the offspring.
+    elite_count: usize,
+    /// The number of evaluations the fitness of each elite is averaged over.
+    num_reevaluations: usize,
+    // phantom types
+    _g: PhantomData<G>,
+    _f: PhantomData<F>,
+}
+
+impl<G, F, E> RevaluatingElitistReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    /// Constructs a new instance of the `RevaluatingElitistReinserter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_reevaluations` is 0.
+    pub fn new(fitness_evaluator: E, elite_count: usize, num_reevaluations: usize) -> Self {
+        assert!(
+            num_reevaluations > 0,
+            "the number of re-evaluations must be greater than 0"
+        );
+        RevaluatingElitistReinserter {
+            fitness_evaluator: Box::new(fitness_evaluator),
+            elite_count,
+            num_reevaluations,
+            _g: PhantomData,
+            _f: PhantomData,
+        }
+    }
+
+    /// Returns the number of the best individuals of the old population that
+    /// are re-evaluated.
+    pub fn elite_count(&self) -> usize {
+        self.elite_count
+    }
+
+    /// Sets the number of the best individuals of the old population that are
+    /// re-evaluated.
+    pub fn set_elite_count(&mut self, value: usize) {
+        self.elite_count = value;
+    }
+
+    /// Returns the number of evaluations the fitness of each elite is
+    /// averaged over.
+    pub fn num_reevaluations(&self) -> usize {
+        self.num_reevaluations
+    }
+
+    /// Sets the number of evaluations the fitness of each elite is averaged
+    /// over. The value must be greater than 0.
+    pub fn set_num_reevaluations(&mut self, value: usize) {
+        self.num_reevaluations = value;
+    }
+
+    /// Returns the maximum number of fitness values that are calculated in
+    /// addition to the fitness values of the offspring by each call of
+    /// `combine`. Fewer fitness values are calculated if the population is
+    /// smaller than the elite count.
+    pub fn num_extra_evaluations(&self) -> usize {
+        self.elite_count * self.num_reevaluations
+    }
+
+    /// Evaluates the given genome `num_reevaluations` times and returns the
+    /// average of the fitness values.
+    pub fn reevaluate(&self, genome: &G) -> F {
+        let fitness_values: Vec<F> = (0..self.num_reevaluations)
+            .map(|_| self.fitness_evaluator.fitness_of(genome))
+            .collect();
+        self.fitness_evaluator.average(&fitness_values)
+    }
+}
+
+impl<G, F, E> GeneticOperator for RevaluatingElitistReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    fn name() -> String {
+        "Revaluating-Elitist-Reinserter".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_count::<Self>("number of re-evaluations", self.num_reevaluations)
+    }
+}
+
+/// Can be used for single-objective optimization
+impl<G, F, E> SingleObjective for RevaluatingElitistReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+}
+/// Can be used for multi-objective optimization
+impl<G, F, E> MultiObjective for RevaluatingElitistReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+}
+
+impl<G, F, E> ReinsertionOp<G, F> for RevaluatingElitistReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    fn combine<R>(
+        &self,
+        offspring: &mut Offspring<G>,
+        evaluated: &EvaluatedPopulation<G, F>,
+        _: &mut R,
+    ) -> Vec<G>
+    where
+        R: Rng + Sized,
+    {
+        let old_individuals = evaluated.individuals();
+        let population_size = old_individuals.len();
+
+        let old_population_indices = best_indices(evaluated, population_size);
+        let num_elites = self.elite_count.min(population_size);
+        // the offspring comes first, so that elites win ties with the offspring
+        let num_offspring = offspring.len();
+        let mut candidates: Vec<(G, F)> = offspring
+            .drain(..)
+            .map(|individual| {
+                let fitness = self.fitness_evaluator.fitness_of(&individual);
+                (individual, fitness)
+            })
+            .collect();
+        candidates.extend(
+            old_population_indices[..num_elites]
+                .iter()
+                .map(|index_old| {
+                    let elite = evaluated.clone_individual(*index_old);
+                    let fitness = self.reevaluate(&elite);
+                    (elite, fitness)
+                }),
+        );
+
+        // leave the individuals of the offspring that are not inserted in the
+        // offspring, the elites come after the offspring
+        let mut new_population: Vec<G> = best_of_offspring(
+            evaluated,
+            candidates,
+            population_size,
+            |index, individual| {
+                if index < num_offspring {
+                    offspring.push(individual);
+                }
+            },
+        )
+        .into_iter()
+        .map(|(individual, _)| individual)
+        .collect();
+        // fill up with the best individuals of the old population that have
+        // not been elites
+        let num_old_population = population_size - new_population.len();
+        new_population.extend(
+            old_population_indices[num_elites..]
+                .iter()
+                .take(num_old_population)
+                .map(|index_old| evaluated.clone_individual(*index_old)),
+        );
+        new_population
+    }
+}
+
+/// Returns the indices of the `count` individuals of the population with the
+/// best fitness values sorted from best to worst. Individuals with equal
+/// fitness values are ordered by their index.
+fn best_indices<G, F>(evaluated: &EvaluatedPopulation<G, F>, count: usize) -> Vec<usize>
+where
+    G: Genotype,
+    F: Fitness,
+{
+    let fitness_values = evaluated.fitness_values();
+    let mut indices: Vec<usize> = (0..fitness_values.len()).collect();
+    partial_sort_by(
+        &mut indices,
+        count,
+        |x, y| {
+            evaluated
+                .compare_fitness(&fitness_values[*y], &fitness_values[*x])
+                .then(x.cmp(y))
+        },
+        drop,
+    );
+    indices
+}
+
+/// Returns the `count` evaluated offspring individuals with the best fitness
+/// values, in the direction of the optimization of the given population,
+/// sorted from best to worst. Of the individuals with equal fitness values
+/// the one that comes last in the offspring is picked first. The individuals
+/// that are not picked are passed to `discard` along with their index in the
+/// offspring.
+fn best_of_offspring<G, F, D>(
+    evaluated: &EvaluatedPopulation<G, F>,
+    offspring_fitness: Vec<(G, F)>,
+    count: usize,
+    mut discard: D,
+) -> Vec<(G, F)>
+where
+    G: Genotype,
+    F: Fitness,
+    D: FnMut(usize, G),
+{
+    let mut ranked: Vec<(usize, (G, F))> = offspring_fitness.into_iter().enumerate().collect();
+    partial_sort_by(
+        &mut ranked,
+        count,
+        |(x, (_, fitness_x)), (y, (_, fitness_y))| {
+            evaluated
+                .compare_fitness(fitness_y, fitness_x)
+                .then(y.cmp(x))
+        },
+        |(index, (individual, _))| discard(index, individual),
+    );
+    ranked.into_iter().map(|(_, evaluated)| evaluated).collect()
+}
+
+/// Keeps the `count` least items according to the given total order and
+/// sorts them. This takes O(n + k log k) time instead of O(n log n) for
+/// sorting all `n` items when only the `k` least ones are needed. The other
+/// items are passed to `discard` in no particular order.
+fn partial_sort_by<T, C, D>(items: &mut Vec<T>, count: usize, mut compare: C, discard: D)
+where
+    C: FnMut(&T, &T) -> Ordering,
+    D: FnMut(T),
+{
+    if count == 0 {
+        items.drain(..).for_each(discard);
+        return;
+    }
+    if count < items.len() {
+        items.select_nth_unstable_by(count - 1, &mut compare);
+        items.drain(count..).for_each(discard);
+    }
+    items.sort_unstable_by(compare);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{self, population_of_fitness},
+        random::get_rng,
+    };
+    use galvanic_assert::matchers::*;
+    use proptest::prelude::*;
+    use std::sync::{
+        atomic::{self, AtomicU64},
+        Arc,
+    };
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct SumOfGenes;
+
+    impl FitnessFunction<Vec<u32>, u32> for SumOfGenes {
+        fn fitness_of(&self, individual: &Vec<u32>) -> u32 {
+            individual.iter().sum()
+        }
+
+        fn average(&self, fitness_values: &[u32]) -> u32 {
+            fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+        }
+
+        fn highest_possible_fitness(&self) -> u32 {
+            u32::MAX
+        }
+
+        fn lowest_possible_fitness(&self) -> u32 {
+            0
+        }
+    }
+
+    fn evaluated_population() -> EvaluatedPopulation<Vec<u32>, u32> {
+        population_of_fitness(vec![5, 1, 7, 3, 9, 2], 4)
+    }
+
+    #[test]
+    fn elite_count_takes_over_the_best_individuals_and_fills_up_with_best_offspring() {
+        let reinserter = ElitistReinserter::with_elite_count(SumOfGenes, 2);
+        let mut offspring = vec![vec![4], vec![0], vec![8], vec![6], vec![2], vec![1]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![8], vec![6], vec![4], vec![2], vec![9], vec![7]])
+        );
+    }
+
+    #[test]
+    fn the_offspring_that_is_not_reinserted_is_left_in_the_offspring() {
+        let reinserter = ElitistReinserter::with_elite_count(SumOfGenes, 2);
+        let mut offspring = vec![vec![4], vec![0], vec![8], vec![6], vec![2], vec![1]];
+
+        reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        offspring.sort();
+        expect_that!(&offspring, eq(vec![vec![0], vec![1]]));
+    }
+
+    #[test]
+    fn elite_count_takes_over_the_lowest_individuals_of_a_minimized_population() {
+        let reinserter = ElitistReinserter::with_elite_count(SumOfGenes, 2);
+        let mut offspring = vec![vec![4], vec![0], vec![8], vec![6], vec![2], vec![1]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population().with_minimize(true),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![0], vec![1], vec![2], vec![4], vec![1], vec![2]])
+        );
+    }
+
+    #[test]
+    fn elite_count_keeps_the_elites_even_if_the_offspring_is_better() {
+        let reinserter = ElitistReinserter::with_elite_count(SumOfGenes, 1);
+        let mut offspring = vec![vec![20]; 6];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(&new_population.len(), eq(6));
+        expect_that!(&new_population.contains(&vec![9]), is(eq(true)));
+        expect_that!(&new_population.contains(&vec![7]), is(eq(false)));
+    }
+
+    #[test]
+    fn elite_count_greater_than_the_population_size_is_clamped() {
+        let reinserter = ElitistReinserter::with_elite_count(SumOfGenes, 10);
+        let mut offspring = vec![vec![20]; 6];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![9], vec![7], vec![5], vec![3], vec![2], vec![1]])
+        );
+    }
+
+    #[test]
+    fn elite_count_fills_up_with_old_individuals_if_the_offspring_is_too_small() {
+        let reinserter = ElitistReinserter::with_elite_count(SumOfGenes, 2);
+        let mut offspring = vec![vec![0], vec![4]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![0], vec![4], vec![9], vec![7], vec![5], vec![3]])
+        );
+    }
+
+    /// The fitness is the first gene only. The second gene distinguishes
+    /// individuals of equal fitness.
+    #[derive(Clone, Debug, PartialEq)]
+    struct FirstGene;
+
+    impl FitnessFunction<Vec<u32>, u32> for FirstGene {
+        fn fitness_of(&self, individual: &Vec<u32>) -> u32 {
+            individual[0]
+        }
+
+        fn average(&self, fitness_values: &[u32]) -> u32 {
+            fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+        }
+
+        fn highest_possible_fitness(&self) -> u32 {
+            u32::MAX
+        }
+
+        fn lowest_possible_fitness(&self) -> u32 {
+            0
+        }
+    }
+
+    /// Combines the offspring and the population by sorting them completely,
+    /// as a reference for the partial sorting done by the `ElitistReinserter`.
+    fn combine_by_sorting(
+        reinserter: &ElitistReinserter<Vec<u32>, u32, FirstGene>,
+        mut offspring: Vec<Vec<u32>>,
+        evaluated: &EvaluatedPopulation<Vec<u32>, u32>,
+    ) -> Vec<Vec<u32>> {
+        let old_individuals = evaluated.individuals();
+        let old_fitness_values = evaluated.fitness_values();
+        let mut old_population_indices: Vec<usize> = (0..old_fitness_values.len()).collect();
+        old_population_indices.sort_by(|x, y| old_fitness_values[*y].cmp(&old_fitness_values[*x]));
+        let population_size = old_individuals.len();
+        let mut new_population = Vec::with_capacity(population_size);
+        let num_offspring = match reinserter.elite_count {
+            Some(elite_count) => population_size - elite_count.min(population_size),
+            None => (population_size as f64 * reinserter.replace_ratio + 0.5).floor() as usize,
+        };
+        let mut offspring_fitness: Vec<(Vec<u32>, u32)> = offspring
+            .iter()
+            .map(|individual| (individual.clone(), FirstGene.fitness_of(individual)))
+            .collect();
+        offspring_fitness.sort_by_key(|x| x.1);
+        if reinserter.offspring_has_precedence || reinserter.elite_count.is_some() {
+            if num_offspring < offspring.len() {
+                for _ in 0..num_offspring {
+                    new_population.push(offspring_fitness.pop().unwrap().0);
+                }
+            } else {
+                offspring.truncate(num_offspring);
+                new_population.append(&mut offspring);
+            }
+            let num_old_population = population_size - new_population.len();
+            for index_old in old_population_indices.iter().take(num_old_population) {
+                new_population.push(old_individuals[*index_old].clone());
+            }
+        } else {
+            for _ in 0..population_size {
                 let index_old = old_population_indices[0];
                 if !offspring_fitness.is_empty()
                     && offspring_fitness[offspring_fitness.len() - 1].1
                         > old_fitness_values[index_old]
                 {
-                    let (offspring, _) = offspring_fitness.pop().unwrap();
-                    // insert best from offspring
-                    new_population.push(offspring);
+                    new_population.push(offspring_fitness.pop().unwrap().0);
                 } else {
-                    // insert best from old population
                     new_population.push(old_individuals[index_old].clone());
                     old_population_indices.remove(0);
                 }
@@ -228,4 +809,168 @@ where
         }
         new_population
     }
+
+    fn population_of(genes: &[u32]) -> EvaluatedPopulation<Vec<u32>, u32> {
+        let individuals: Vec<Vec<u32>> = genes
+            .iter()
+            .enumerate()
+            .map(|(index, gene)| vec![*gene * 100, index as u32])
+            .collect();
+        let fitness_values = individuals.iter().map(|x| x[0]).collect();
+        fixtures::evaluated_population(individuals, fitness_values, 0)
+    }
+
+    /// The fitness is the first gene disturbed by a pseudo-random noise of at
+    /// most `amplitude`. Counts the calls of `fitness_of`.
+    #[derive(Clone, Debug)]
+    struct NoisyFirstGene {
+        amplitude: i64,
+        calls: Arc<AtomicU64>,
+    }
+
+    impl NoisyFirstGene {
+        fn new(amplitude: i64) -> Self {
+            NoisyFirstGene {
+                amplitude,
+                calls: Arc::new(AtomicU64::new(0)),
+            }
+        }
+
+        fn num_calls(&self) -> u64 {
+            self.calls.load(atomic::Ordering::SeqCst)
+        }
+    }
+
+    impl FitnessFunction<Vec<i64>, i64> for NoisyFirstGene {
+        fn fitness_of(&self, individual: &Vec<i64>) -> i64 {
+            // splitmix64 of the number of calls
+            let mut z = self
+                .calls
+                .fetch_add(1, atomic::Ordering::SeqCst)
+                .wrapping_add(0x9e37_79b9_7f4a_7c15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^= z >> 31;
+            let noise = (z % (2 * self.amplitude as u64 + 1)) as i64 - self.amplitude;
+            individual[0] + noise
+        }
+
+        fn average(&self, fitness_values: &[i64]) -> i64 {
+            fitness_values.iter().sum::<i64>() / fitness_values.len() as i64
+        }
+
+        fn highest_possible_fitness(&self) -> i64 {
+            i64::MAX
+        }
+
+        fn lowest_possible_fitness(&self) -> i64 {
+            i64::MIN
+        }
+    }
+
+    fn noisy_population(
+        individuals: Vec<Vec<i64>>,
+        fitness_values: Vec<i64>,
+    ) -> EvaluatedPopulation<Vec<i64>, i64> {
+        fixtures::evaluated_population(individuals, fitness_values, 0)
+    }
+
+    #[test]
+    fn the_reevaluated_fitness_converges_toward_the_true_mean() {
+        let elite = vec![500];
+        let mean_errors: Vec<i64> = [1, 10, 100, 1_000]
+            .iter()
+            .map(|num_reevaluations| {
+                let reinserter = RevaluatingElitistReinserter::new(
+                    NoisyFirstGene::new(100),
+                    1,
+                    *num_reevaluations,
+                );
+                (0..20)
+                    .map(|_| (reinserter.reevaluate(&elite) - 500).abs())
+                    .sum::<i64>()
+                    / 20
+            })
+            .collect();
+
+        for errors in mean_errors.windows(2) {
+            expect_that!(&errors[1], is(less_than(errors[0])));
+        }
+        expect_that!(&mean_errors[3], is(less_than(5)));
+    }
+
+    #[test]
+    fn an_over_estimated_elite_is_replaced_by_better_offspring() {
+        let reinserter = RevaluatingElitistReinserter::new(NoisyFirstGene::new(2), 2, 100);
+        // the first individual has been evaluated far too optimistically
+        let evaluated = noisy_population(
+            vec![vec![50], vec![80], vec![10], vec![20]],
+            vec![1_000, 80, 10, 20],
+        );
+        let mut offspring = vec![vec![60], vec![70], vec![55]];
+
+        let new_population = reinserter.combine(&mut offspring, &evaluated, &mut get_rng([42; 32]));
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![80], vec![70], vec![60], vec![55]])
+        );
+        expect_that!(&offspring.is_empty(), eq(true));
+    }
+
+    #[test]
+    fn the_new_population_is_filled_up_with_the_best_old_individuals() {
+        let reinserter = RevaluatingElitistReinserter::new(NoisyFirstGene::new(0), 1, 3);
+        let evaluated = noisy_population(vec![vec![5], vec![1], vec![7]], vec![5, 1, 7]);
+
+        let new_population = reinserter.combine(&mut vec![], &evaluated, &mut get_rng([42; 32]));
+
+        expect_that!(&new_population, eq(vec![vec![7], vec![5], vec![1]]));
+    }
+
+    #[test]
+    fn each_elite_is_evaluated_num_reevaluations_times() {
+        let fitness_function = NoisyFirstGene::new(10);
+        let reinserter = RevaluatingElitistReinserter::new(fitness_function.clone(), 2, 10);
+        let evaluated = noisy_population(
+            vec![vec![50], vec![80], vec![10], vec![20]],
+            vec![50, 80, 10, 20],
+        );
+        let mut offspring = vec![vec![60], vec![70], vec![55]];
+
+        reinserter.combine(&mut offspring, &evaluated, &mut get_rng([42; 32]));
+
+        expect_that!(&reinserter.num_extra_evaluations(), eq(20));
+        expect_that!(&fitness_function.num_calls(), eq(3 + 20));
+    }
+
+    proptest! {
+
+        #[test]
+        fn partial_sorting_gives_the_same_population_as_sorting_completely(
+            old_genes in prop::collection::vec(0..4u32, 1..40),
+            offspring_genes in prop::collection::vec(0..4u32, 0..60),
+            replace_ratio in 0.0..=1.0f64,
+            offspring_has_precedence in any::<bool>(),
+            elite_count in prop::option::of(0..50usize),
+        ) {
+            let mut reinserter =
+                ElitistReinserter::new(FirstGene, offspring_has_precedence, replace_ratio);
+            if let Some(elite_count) = elite_count {
+                reinserter.set_elite_count(elite_count);
+            }
+            let evaluated = population_of(&old_genes);
+            let offspring: Vec<Vec<u32>> = offspring_genes
+                .iter()
+                .enumerate()
+                .map(|(index, gene)| vec![*gene * 100, 1_000 + index as u32])
+                .collect();
+
+            let expected = combine_by_sorting(&reinserter, offspring.clone(), &evaluated);
+            let new_population =
+                reinserter.combine(&mut offspring.clone(), &evaluated, &mut get_rng([42; 32]));
+
+            prop_assert_eq!(new_population, expected);
+        }
+    }
 }
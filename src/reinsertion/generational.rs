@@ -0,0 +1,194 @@
+//! The `generational` module provides an `operator::ReinsertionOp` that
+//! replaces the whole population with the offspring in each generation.
+//!
+//! This is the reinsertion of the textbook simple genetic algorithm. In
+//! contrast to the `elitist::ElitistReinserter` no individual survives unless
+//! an elitism is configured explicitly, which makes the
+//! `GenerationalReinserter` a baseline to measure the effect of elitism.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{Fitness, Genotype, Offspring},
+    operator::{GeneticOperator, MultiObjective, ReinsertionOp, SingleObjective},
+    random::Rng,
+};
+
+/// The `GenerationalReinserter` replaces the old population entirely with
+/// the offspring. The offspring must contain exactly as many individuals as
+/// the old population, see `ReinsertionOp::required_offspring_size`. The
+/// `ga::GeneticAlgorithm` stops with a
+/// `GeneticAlgorithmError::IncompatibleOperators` error if the selection and
+/// the crossover operator breed a different number of children.
+///
+/// Optionally the `elitism` best individuals of the old population are
+/// carried over into the new population. They take the places of the last
+/// `elitism` individuals of the offspring, which are left in the offspring.
+/// With the default `elitism` of 0 no individual of the old population
+/// survives.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenerationalReinserter {
+    /// The number of the best individuals of the old population that are
+    /// carried over into the new population.
+    elitism: usize,
+}
+
+impl GenerationalReinserter {
+    /// Constructs a new instance of the `GenerationalReinserter` that
+    /// replaces the whole population.
+    pub fn new() -> Self {
+        GenerationalReinserter { elitism: 0 }
+    }
+
+    /// Constructs a new instance of the `GenerationalReinserter` that
+    /// carries the `elitism` best individuals of the old population over
+    /// into the new population.
+    pub fn with_elitism(elitism: usize) -> Self {
+        GenerationalReinserter { elitism }
+    }
+
+    /// Returns the number of the best individuals of the old population that
+    /// are carried over into the new population.
+    pub fn elitism(&self) -> usize {
+        self.elitism
+    }
+
+    /// Sets the number of the best individuals of the old population that
+    /// are carried over into the new population.
+    pub fn set_elitism(&mut self, value: usize) {
+        self.elitism = value;
+    }
+}
+
+impl Default for GenerationalReinserter {
+    fn default() -> Self {
+        GenerationalReinserter::new()
+    }
+}
+
+impl GeneticOperator for GenerationalReinserter {
+    fn name() -> String {
+        "Generational-Reinserter".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        Ok(())
+    }
+}
+
+/// Can be used for single-objective optimization
+impl SingleObjective for GenerationalReinserter {}
+/// Can be used for multi-objective optimization
+impl MultiObjective for GenerationalReinserter {}
+
+impl<G, F> ReinsertionOp<G, F> for GenerationalReinserter
+where
+    G: Genotype,
+    F: Fitness,
+{
+    /// # Panics
+    ///
+    /// Panics if the offspring does not contain exactly as many individuals
+    /// as the old population.
+    fn combine<R>(
+        &self,
+        offspring: &mut Offspring<G>,
+        evaluated: &EvaluatedPopulation<G, F>,
+        _: &mut R,
+    ) -> Vec<G>
+    where
+        R: Rng + Sized,
+    {
+        let old_individuals = evaluated.individuals();
+        let old_fitness_values = evaluated.fitness_values();
+        let population_size = old_individuals.len();
+        assert!(
+            offspring.len() == population_size,
+            "The {} replaces the whole population of {} individuals, but the offspring has {} \
+             individuals. The selection and the crossover operator must breed one child per \
+             individual of the population.",
+            <Self as GeneticOperator>::name(),
+            population_size,
+            offspring.len()
+        );
+
+        let num_elites = self.elitism.min(population_size);
+        // sort indices from best performing to worst performing individual
+        let mut old_population_indices: Vec<usize> = (0..population_size).collect();
+        old_population_indices.sort_by(|x, y| {
+            evaluated.compare_fitness(&old_fitness_values[*y], &old_fitness_values[*x])
+        });
+
+        let mut new_population: Vec<G> = offspring.drain(..population_size - num_elites).collect();
+        for index_old in old_population_indices.into_iter().take(num_elites) {
+            new_population.push(evaluated.clone_individual(index_old));
+        }
+        new_population
+    }
+
+    fn required_offspring_size(&self, population_size: usize) -> Option<usize> {
+        Some(population_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    fn evaluated_population() -> EvaluatedPopulation<Vec<u8>, u32> {
+        let individuals = vec![vec![3], vec![9], vec![1], vec![7]];
+        fixtures::evaluated_population(individuals, vec![3, 9, 1, 7], 5)
+    }
+
+    #[test]
+    fn the_whole_population_is_replaced_without_elitism() {
+        let reinserter = GenerationalReinserter::new();
+        let mut offspring = vec![vec![10], vec![11], vec![12], vec![13]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![10], vec![11], vec![12], vec![13]])
+        );
+        expect_that!(&offspring.is_empty(), eq(true));
+    }
+
+    #[test]
+    fn the_best_individuals_are_carried_over_with_elitism() {
+        let reinserter = GenerationalReinserter::with_elitism(2);
+        let mut offspring = vec![vec![10], vec![11], vec![12], vec![13]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![10], vec![11], vec![9], vec![7]])
+        );
+        expect_that!(&offspring, eq(vec![vec![12], vec![13]]));
+    }
+
+    #[test]
+    #[should_panic(expected = "the offspring has 3 individuals")]
+    fn an_offspring_of_a_different_size_is_rejected() {
+        let reinserter = GenerationalReinserter::new();
+        let mut offspring = vec![vec![10], vec![11], vec![12]];
+
+        reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+    }
+}
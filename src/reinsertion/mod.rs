@@ -4,7 +4,22 @@
 //! The provided implementations of the `operator:ReinsertionOp` are:
 //! * `random::UniformReinserter`
 //! * `elitist::ElitistReinserter`
+//! * `steady_state::SteadyStateReinserter`
+//! * `crowding::DeterministicCrowding`
+//! * `restart::HypermutationReinserter`
+//! * `unique::UniqueReinserter`
+//! * `generational::GenerationalReinserter`
 
 pub mod random;
 
 pub mod elitist;
+
+pub mod steady_state;
+
+pub mod crowding;
+
+pub mod restart;
+
+pub mod unique;
+
+pub mod generational;
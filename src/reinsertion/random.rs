@@ -4,9 +4,10 @@
 
 use crate::{
     algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
     genetic::{Fitness, Genotype, Offspring},
-    operator::{GeneticOperator, MultiObjective, ReinsertionOp, SingleObjective},
-    random::{random_index, Rng},
+    operator::{validate_ratio, GeneticOperator, MultiObjective, ReinsertionOp, SingleObjective},
+    random::{random_index, random_index_from_range, Rng},
 };
 
 /// The `UniformReinserter` takes n individuals from the offspring and
@@ -25,6 +26,12 @@ use crate::{
 /// individuals then the new population is filled up with individuals from the
 /// old population. If the offspring contains more individuals than the size of
 /// the population then the individuals are chosen uniformly at random.
+///
+/// The individuals from the old population are chosen uniformly at random
+/// as well. Each individual of the old population is taken over at most once.
+/// As the fitness of the individuals is not considered the `UniformReinserter`
+/// is a baseline to measure the effect of selection pressure, e.g. of the
+/// `elitist::ElitistReinserter`.
 #[allow(missing_copy_implementations)]
 #[derive(Clone, Debug, PartialEq)]
 pub struct UniformReinserter {
@@ -56,6 +63,10 @@ impl GeneticOperator for UniformReinserter {
     fn name() -> String {
         "Uniform-Reinserter".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("replace ratio", self.replace_ratio)
+    }
 }
 
 /// Can be used for single-objective optimization
@@ -93,19 +104,106 @@ where
             }
         } else {
             // insert all individuals from offspring
-            let mut i = 0;
-            while i < offspring.len() {
-                new_population.push(offspring.remove(i));
-                i += 1;
-            }
+            new_population.append(offspring);
         }
-        // finally fill up new population with individuals from old population
-        // (as many as needed).
-        let num_old_population = population_size - new_population.len();
-        for _ in 0..num_old_population {
-            let index = random_index(rng, old_individuals.len());
-            new_population.push(old_individuals[index].clone());
+        // finally fill up new population with distinct individuals from old
+        // population (as many as needed) by a partial shuffle of the indices.
+        let num_old_population = population_size.saturating_sub(new_population.len());
+        let mut old_population_indices: Vec<usize> = (0..old_individuals.len()).collect();
+        for i in 0..num_old_population {
+            let j = random_index_from_range(rng, i, old_population_indices.len());
+            old_population_indices.swap(i, j);
+            new_population.push(evaluated.clone_individual(old_population_indices[i]));
         }
         new_population
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::indexed_population, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    fn evaluated_population(size: usize) -> EvaluatedPopulation<Vec<usize>, usize> {
+        indexed_population((0..size).collect(), size / 2)
+    }
+
+    fn offspring(size: usize) -> Vec<Vec<usize>> {
+        (0..size).map(|i| vec![100 + i]).collect()
+    }
+
+    #[test]
+    fn uniform_reinserter_preserves_population_size() {
+        let population = evaluated_population(10);
+        let mut rng = get_rng([42; 32]);
+        for replace_ratio in &[0., 0.3, 0.5, 1.] {
+            let reinserter = UniformReinserter::new(*replace_ratio);
+            for num_offspring in &[0, 3, 10, 25] {
+                let mut offspring = offspring(*num_offspring);
+
+                let new_population = reinserter.combine(&mut offspring, &population, &mut rng);
+
+                expect_that!(&new_population.len(), eq(10));
+            }
+        }
+    }
+
+    #[test]
+    fn uniform_reinserter_inserts_all_offspring_if_there_are_not_enough() {
+        let reinserter = UniformReinserter::new(1.);
+        let mut offspring = offspring(4);
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(10),
+            &mut get_rng([42; 32]),
+        );
+
+        let num_from_offspring = new_population.iter().filter(|x| x[0] >= 100).count();
+        expect_that!(&num_from_offspring, eq(4));
+        expect_that!(&offspring.is_empty(), is(eq(true)));
+    }
+
+    #[test]
+    fn uniform_reinserter_takes_individuals_of_old_population_at_most_once() {
+        let reinserter = UniformReinserter::new(0.5);
+        let mut offspring = offspring(20);
+
+        let mut new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(10),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(&offspring.len(), eq(15));
+        new_population.sort();
+        new_population.dedup();
+        expect_that!(&new_population.len(), eq(10));
+    }
+
+    #[test]
+    fn uniform_reinserter_selects_individuals_uniformly_at_random() {
+        let reinserter = UniformReinserter::new(0.5);
+        let population = evaluated_population(10);
+        let mut rng = get_rng([42; 32]);
+        let mut old_counter = [0; 10];
+        let mut offspring_counter = [0; 10];
+        for _ in 0..2_000 {
+            let mut offspring = offspring(10);
+            for individual in reinserter.combine(&mut offspring, &population, &mut rng) {
+                if individual[0] >= 100 {
+                    offspring_counter[individual[0] - 100] += 1;
+                } else {
+                    old_counter[individual[0]] += 1;
+                }
+            }
+        }
+
+        // each individual is expected to be taken over in half of the runs
+        for count in old_counter.iter().chain(offspring_counter.iter()) {
+            expect_that!(count, is(greater_than(900)));
+            expect_that!(count, is(less_than(1_100)));
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! The `restart` module provides an `operator::ReinsertionOp` that partially
+//! restarts the population when the simulation stagnates.
+//!
+//! When the best fitness has not improved for a number of generations the
+//! population has most likely converged to a local optimum. Replacing a part
+//! of the population with freshly generated individuals brings new genetic
+//! material into the population and helps to escape the local optimum.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{Fitness, Genotype, Offspring},
+    operator::{validate_ratio, GenerationSignal, GeneticOperator, ReinsertionOp, SingleObjective},
+    population::GenomeBuilder,
+    random::Rng,
+};
+
+/// The `HypermutationReinserter` wraps another `ReinsertionOp` and replaces
+/// the worst individuals of the population with freshly generated genomes
+/// after the best fitness has stagnated for `stagnation_generations`
+/// generations.
+///
+/// As long as the best fitness improves the new population is combined by
+/// the wrapped `ReinsertionOp`. On a restart the individuals of the old
+/// population are ranked by their fitness. The worst `fraction` of them is
+/// replaced by genomes built by the `GenomeBuilder`, the others are taken
+/// over into the new population. The offspring of that generation is left
+/// untouched. The best individual is always taken over, even if `fraction`
+/// is 1.0, so that a restart never loses the best solution found so far.
+///
+/// The stagnation is tracked through `ReinsertionOp::adapt`, which is called
+/// by the `ga::GeneticAlgorithm` in every generation. After a restart and
+/// after the algorithm is reset the stagnation counter starts again from 0.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HypermutationReinserter<R, B> {
+    /// The wrapped `ReinsertionOp`.
+    reinserter: R,
+    /// The `GenomeBuilder` that generates the genomes of a restart.
+    genome_builder: B,
+    /// The number of generations without improvement of the best fitness
+    /// after which the population is restarted.
+    stagnation_generations: u64,
+    /// The fraction of the population that is replaced on a restart.
+    fraction: f64,
+    /// The number of generations without improvement so far.
+    stagnation: u64,
+    /// Whether the population is restarted in the current generation.
+    is_restart: bool,
+}
+
+impl<R, B> HypermutationReinserter<R, B> {
+    /// Constructs a new instance of the `HypermutationReinserter`.
+    ///
+    /// The number of stagnation generations must be greater than 0 and the
+    /// fraction must be between 0 and 1.0 (inclusive).
+    pub fn new(
+        reinserter: R,
+        genome_builder: B,
+        stagnation_generations: u64,
+        fraction: f64,
+    ) -> Self {
+        assert!(
+            stagnation_generations > 0,
+            "the number of stagnation generations must be greater than 0"
+        );
+        assert!(
+            (0. ..=1.).contains(&fraction),
+            "the fraction must be between 0 and 1"
+        );
+        HypermutationReinserter {
+            reinserter,
+            genome_builder,
+            stagnation_generations,
+            fraction,
+            stagnation: 0,
+            is_restart: false,
+        }
+    }
+
+    /// Returns the wrapped `ReinsertionOp`.
+    pub fn reinserter(&self) -> &R {
+        &self.reinserter
+    }
+
+    /// Returns the `GenomeBuilder` that generates the genomes of a restart.
+    pub fn genome_builder(&self) -> &B {
+        &self.genome_builder
+    }
+
+    /// Returns the number of generations without improvement after which
+    /// the population is restarted.
+    pub fn stagnation_generations(&self) -> u64 {
+        self.stagnation_generations
+    }
+
+    /// Sets the number of generations without improvement after which the
+    /// population is restarted. The value must be greater than 0.
+    pub fn set_stagnation_generations(&mut self, value: u64) {
+        self.stagnation_generations = value;
+    }
+
+    /// Returns the fraction of the population that is replaced on a restart.
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Sets the fraction of the population that is replaced on a restart.
+    /// The value must be between 0 and 1.0 (inclusive).
+    pub fn set_fraction(&mut self, value: f64) {
+        self.fraction = value;
+    }
+
+    /// Returns the number of generations without improvement since the
+    /// last improvement or restart.
+    pub fn stagnation(&self) -> u64 {
+        self.stagnation
+    }
+
+    /// Returns whether the population is restarted in the current
+    /// generation.
+    pub fn is_restart(&self) -> bool {
+        self.is_restart
+    }
+}
+
+impl<R, B> GeneticOperator for HypermutationReinserter<R, B>
+where
+    R: GeneticOperator,
+    B: Clone,
+{
+    fn name() -> String {
+        format!("Hypermutation-{}", R::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("fraction", self.fraction)?;
+        self.reinserter.validate()
+    }
+}
+
+/// Can be used for single-objective optimization
+impl<R, B> SingleObjective for HypermutationReinserter<R, B>
+where
+    R: GeneticOperator,
+    B: Clone,
+{
+}
+
+impl<G, F, R, B> ReinsertionOp<G, F> for HypermutationReinserter<R, B>
+where
+    G: Genotype,
+    F: Fitness,
+    R: ReinsertionOp<G, F>,
+    B: GenomeBuilder<G> + Clone,
+{
+    fn combine<N>(
+        &self,
+        offspring: &mut Offspring<G>,
+        evaluated: &EvaluatedPopulation<G, F>,
+        rng: &mut N,
+    ) -> Vec<G>
+    where
+        N: Rng + Sized,
+    {
+        if !self.is_restart {
+            return self.reinserter.combine(offspring, evaluated, rng);
+        }
+        let old_individuals = evaluated.individuals();
+        let old_fitness_values = evaluated.fitness_values();
+        let population_size = old_individuals.len();
+        // sort indices from best performing to worst performing individual
+        let mut old_population_indices: Vec<usize> = (0..population_size).collect();
+        old_population_indices.sort_by(|x, y| {
+            evaluated.compare_fitness(&old_fitness_values[*y], &old_fitness_values[*x])
+        });
+
+        let num_replaced = ((population_size as f64 * self.fraction + 0.5).floor() as usize)
+            .min(population_size.saturating_sub(1));
+        let num_kept = population_size - num_replaced;
+        let mut new_population: Vec<G> = Vec::with_capacity(population_size);
+        for index_old in old_population_indices.iter().take(num_kept) {
+            new_population.push(evaluated.clone_individual(*index_old));
+        }
+        for index in 0..num_replaced {
+            new_population.push(self.genome_builder.build_genome(index, rng));
+        }
+        new_population
+    }
+
+    fn required_offspring_size(&self, population_size: usize) -> Option<usize> {
+        if self.is_restart {
+            None
+        } else {
+            self.reinserter.required_offspring_size(population_size)
+        }
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.reinserter.adapt(signal);
+        if signal.improved {
+            self.stagnation = 0;
+        } else {
+            self.stagnation += 1;
+        }
+        self.is_restart = self.stagnation >= self.stagnation_generations;
+        if self.is_restart {
+            self.stagnation = 0;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reinserter.reset();
+        self.stagnation = 0;
+        self.is_restart = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::population_of_fitness, random::get_rng, reinsertion::random::UniformReinserter,
+    };
+    use galvanic_assert::matchers::*;
+
+    /// Builds the genomes `[100]`, `[101]`, ... that are easily distinguished
+    /// from the individuals of the population.
+    #[derive(Clone, Debug, PartialEq)]
+    struct FreshGenomeBuilder;
+
+    impl GenomeBuilder<Vec<u32>> for FreshGenomeBuilder {
+        fn build_genome<R>(&self, index: usize, _: &mut R) -> Vec<u32>
+        where
+            R: Rng + Sized,
+        {
+            vec![100 + index as u32]
+        }
+    }
+
+    fn evaluated_population() -> EvaluatedPopulation<Vec<u32>, u32> {
+        population_of_fitness(vec![5, 1, 7, 3, 9, 2], 4)
+    }
+
+    fn adapt(
+        reinserter: &mut HypermutationReinserter<UniformReinserter, FreshGenomeBuilder>,
+        generation: u64,
+        improved: bool,
+    ) {
+        let individuals = evaluated_population().individuals().to_vec();
+        ReinsertionOp::<Vec<u32>, u32>::adapt(
+            reinserter,
+            &GenerationSignal {
+                generation,
+                individuals: &individuals,
+                improved,
+            },
+        );
+    }
+
+    fn combine(
+        reinserter: &HypermutationReinserter<UniformReinserter, FreshGenomeBuilder>,
+        offspring: &mut Vec<Vec<u32>>,
+    ) -> Vec<Vec<u32>> {
+        reinserter.combine(offspring, &evaluated_population(), &mut get_rng([42; 32]))
+    }
+
+    #[test]
+    fn stagnation_partially_refreshes_the_population_and_preserves_the_best() {
+        let mut reinserter =
+            HypermutationReinserter::new(UniformReinserter::new(1.0), FreshGenomeBuilder, 3, 0.5);
+        let mut offspring = vec![vec![0]; 6];
+
+        adapt(&mut reinserter, 1, true);
+        for generation in 2..=4 {
+            adapt(&mut reinserter, generation, false);
+        }
+        expect_that!(&reinserter.is_restart(), is(eq(true)));
+
+        let new_population = combine(&reinserter, &mut offspring);
+
+        expect_that!(
+            &new_population,
+            eq(vec![
+                vec![9],
+                vec![7],
+                vec![5],
+                vec![100],
+                vec![101],
+                vec![102]
+            ])
+        );
+        expect_that!(&offspring.len(), eq(6));
+    }
+
+    #[test]
+    fn the_population_is_combined_by_the_wrapped_reinserter_without_stagnation() {
+        let mut reinserter =
+            HypermutationReinserter::new(UniformReinserter::new(1.0), FreshGenomeBuilder, 3, 0.5);
+        let mut offspring = vec![vec![0]; 6];
+
+        adapt(&mut reinserter, 1, true);
+        adapt(&mut reinserter, 2, false);
+        adapt(&mut reinserter, 3, false);
+        adapt(&mut reinserter, 4, true);
+        adapt(&mut reinserter, 5, false);
+
+        expect_that!(&reinserter.is_restart(), is(eq(false)));
+        expect_that!(&combine(&reinserter, &mut offspring), eq(vec![vec![0]; 6]));
+    }
+
+    #[test]
+    fn the_stagnation_counter_restarts_after_a_restart() {
+        let mut reinserter =
+            HypermutationReinserter::new(UniformReinserter::new(1.0), FreshGenomeBuilder, 2, 0.5);
+
+        let restarts: Vec<bool> = (1..=7)
+            .map(|generation| {
+                adapt(&mut reinserter, generation, generation == 1);
+                reinserter.is_restart()
+            })
+            .collect();
+
+        expect_that!(
+            &restarts,
+            eq(vec![false, false, true, false, true, false, true])
+        );
+    }
+
+    #[test]
+    fn reset_clears_the_stagnation_counter() {
+        let mut reinserter =
+            HypermutationReinserter::new(UniformReinserter::new(1.0), FreshGenomeBuilder, 3, 0.5);
+        adapt(&mut reinserter, 1, false);
+        adapt(&mut reinserter, 2, false);
+
+        ReinsertionOp::<Vec<u32>, u32>::reset(&mut reinserter);
+
+        expect_that!(&reinserter.stagnation(), eq(0));
+        expect_that!(&reinserter.is_restart(), is(eq(false)));
+    }
+
+    #[test]
+    fn a_full_restart_keeps_the_best_individual() {
+        let mut reinserter =
+            HypermutationReinserter::new(UniformReinserter::new(1.0), FreshGenomeBuilder, 1, 1.0);
+        let mut offspring = Vec::new();
+
+        adapt(&mut reinserter, 1, false);
+        let new_population = combine(&reinserter, &mut offspring);
+
+        expect_that!(
+            &new_population,
+            eq(vec![
+                vec![9],
+                vec![100],
+                vec![101],
+                vec![102],
+                vec![103],
+                vec![104]
+            ])
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! The `steady_state` module provides `operator::ReinsertionOp` that replace
+//! only a few individuals of the old population with individuals from the
+//! offspring in each generation.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{Fitness, FitnessFunction, Genotype, Offspring},
+    operator::{validate_count, GeneticOperator, MultiObjective, ReinsertionOp, SingleObjective},
+    random::Rng,
+};
+use std::marker::PhantomData;
+
+/// The `SteadyStateReinserter` replaces the `num_replaced` worst individuals
+/// of the old population with the `num_replaced` best individuals of the
+/// offspring. All other individuals of the old population are taken over
+/// into the new population unchanged and at the same position.
+///
+/// If the offspring contains fewer than `num_replaced` individuals, only as
+/// many individuals as there are in the offspring are replaced. The offspring
+/// replaces the worst individuals regardless of whether it is better or worse
+/// than them. As everywhere in this library individuals with higher fitness
+/// values are considered to be better.
+///
+/// The individuals of the offspring that are not inserted into the new
+/// population are left in the offspring.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SteadyStateReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    /// The `FitnessFunction` to be used to calculate fitness values of
+    /// individuals of the offspring.
+    fitness_evaluator: Box<E>,
+    /// The number of individuals of the old population that are replaced
+    /// by individuals from the offspring.
+    num_replaced: usize,
+    // phantom types
+    _g: PhantomData<G>,
+    _f: PhantomData<F>,
+}
+
+impl<G, F, E> SteadyStateReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    /// Constructs a new instance of the `SteadyStateReinserter`.
+    pub fn new(fitness_evaluator: E, num_replaced: usize) -> Self {
+        SteadyStateReinserter {
+            fitness_evaluator: Box::new(fitness_evaluator),
+            num_replaced,
+            _g: PhantomData,
+            _f: PhantomData,
+        }
+    }
+
+    /// Returns the number of individuals that are replaced in each
+    /// generation.
+    pub fn num_replaced(&self) -> usize {
+        self.num_replaced
+    }
+
+    /// Sets the number of individuals that are replaced in each generation.
+    pub fn set_num_replaced(&mut self, value: usize) {
+        self.num_replaced = value;
+    }
+}
+
+impl<G, F, E> GeneticOperator for SteadyStateReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    fn name() -> String {
+        "Steady-State-Reinserter".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_count::<Self>("number of replaced individuals", self.num_replaced)
+    }
+}
+
+/// Can be used for single-objective optimization
+impl<G, F, E> SingleObjective for SteadyStateReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+}
+/// Can be used for multi-objective optimization
+impl<G, F, E> MultiObjective for SteadyStateReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+}
+
+impl<G, F, E> ReinsertionOp<G, F> for SteadyStateReinserter<G, F, E>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    fn combine<R>(
+        &self,
+        offspring: &mut Offspring<G>,
+        evaluated: &EvaluatedPopulation<G, F>,
+        _: &mut R,
+    ) -> Vec<G>
+    where
+        R: Rng + Sized,
+    {
+        let mut new_population = evaluated.clone_individuals();
+        let old_fitness_values = evaluated.fitness_values();
+        let num_replaced = self
+            .num_replaced
+            .min(offspring.len())
+            .min(new_population.len());
+        if num_replaced == 0 {
+            return new_population;
+        }
+
+        // sort indices of the old population from worst to best performing
+        let mut old_population_indices: Vec<usize> = (0..old_fitness_values.len()).collect();
+        old_population_indices.sort_by(|x, y| {
+            evaluated.compare_fitness(&old_fitness_values[*x], &old_fitness_values[*y])
+        });
+
+        // sort offspring from best to worst performing
+        let mut offspring_fitness: Vec<(G, F)> = offspring
+            .drain(..)
+            .map(|individual| {
+                let fitness = self.fitness_evaluator.fitness_of(&individual);
+                (individual, fitness)
+            })
+            .collect();
+        offspring_fitness.sort_by(|x, y| evaluated.compare_fitness(&y.1, &x.1));
+
+        let mut offspring_fitness = offspring_fitness.into_iter();
+        for index_old in old_population_indices.into_iter().take(num_replaced) {
+            let (individual, _) = offspring_fitness.next().expect("enough offspring");
+            new_population[index_old] = individual;
+        }
+        // leave the individuals that are not inserted in the offspring
+        offspring.extend(offspring_fitness.map(|(individual, _)| individual));
+        new_population
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::population_of_fitness, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct SumOfGenes;
+
+    impl FitnessFunction<Vec<u32>, u32> for SumOfGenes {
+        fn fitness_of(&self, individual: &Vec<u32>) -> u32 {
+            individual.iter().sum()
+        }
+
+        fn average(&self, fitness_values: &[u32]) -> u32 {
+            fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+        }
+
+        fn highest_possible_fitness(&self) -> u32 {
+            u32::MAX
+        }
+
+        fn lowest_possible_fitness(&self) -> u32 {
+            0
+        }
+    }
+
+    fn evaluated_population() -> EvaluatedPopulation<Vec<u32>, u32> {
+        population_of_fitness(vec![5, 1, 7, 3, 9, 2], 4)
+    }
+
+    #[test]
+    fn steady_state_reinserter_replaces_worst_individuals_with_best_offspring() {
+        let reinserter = SteadyStateReinserter::new(SumOfGenes, 2);
+        let mut offspring = vec![vec![4], vec![8], vec![6], vec![0]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![5], vec![8], vec![7], vec![3], vec![9], vec![6]])
+        );
+        expect_that!(&offspring, eq(vec![vec![4], vec![0]]));
+    }
+
+    #[test]
+    fn steady_state_reinserter_preserves_population_size_and_changes_num_replaced_individuals() {
+        let population = evaluated_population();
+        for num_replaced in 0..=3 {
+            let reinserter = SteadyStateReinserter::new(SumOfGenes, num_replaced);
+            let mut offspring = vec![vec![10], vec![11], vec![12]];
+
+            let new_population =
+                reinserter.combine(&mut offspring, &population, &mut get_rng([42; 32]));
+
+            expect_that!(&new_population.len(), eq(population.individuals().len()));
+            let num_changed = new_population
+                .iter()
+                .zip(population.individuals().iter())
+                .filter(|(new, old)| new != old)
+                .count();
+            expect_that!(&num_changed, eq(num_replaced));
+        }
+    }
+
+    #[test]
+    fn steady_state_reinserter_replaces_no_more_individuals_than_offspring() {
+        let reinserter = SteadyStateReinserter::new(SumOfGenes, 4);
+        let mut offspring = vec![vec![0]];
+
+        let new_population = reinserter.combine(
+            &mut offspring,
+            &evaluated_population(),
+            &mut get_rng([42; 32]),
+        );
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![5], vec![0], vec![7], vec![3], vec![9], vec![2]])
+        );
+        expect_that!(&offspring.is_empty(), is(eq(true)));
+    }
+}
@@ -0,0 +1,320 @@
+//! The `unique` module provides an `operator::ReinsertionOp` that keeps the
+//! genomes of the population distinct.
+//!
+//! For discrete problems the population can fill up with copies of the same
+//! genomes. Each copy costs a fitness evaluation without adding any
+//! information and the diversity of the population collapses. Replacing the
+//! duplicates with freshly generated genomes keeps the population genetically
+//! distinct.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{ExactIdentity, Fitness, GenomeIdentity, Genotype, Offspring},
+    operator::{GenerationSignal, GeneticOperator, MultiObjective, ReinsertionOp, SingleObjective},
+    population::GenomeBuilder,
+    random::Rng,
+};
+use std::collections::HashSet;
+
+/// The number of genomes that are generated for one duplicate until a genome
+/// is found that is not part of the population yet.
+const MAX_ATTEMPTS: usize = 10;
+
+/// The `UniqueReinserter` wraps another `ReinsertionOp` and replaces the
+/// duplicate genomes of the new population combined by the wrapped
+/// `ReinsertionOp` with freshly generated genomes.
+///
+/// Genomes are compared by the canonical keys of a `genetic::GenomeIdentity`.
+/// A `UniqueReinserter` created by `new` uses the `genetic::ExactIdentity`,
+/// which compares the genomes by `Eq` and `Hash`. The `with_identity`
+/// constructor takes any other `genetic::GenomeIdentity`, e.g. to treat
+/// phenotypically equivalent genomes as duplicates. The first occurrence of
+/// each genome is kept and only the further copies are replaced. Thus each
+/// distinct genome survives, in particular the best individual taken over by
+/// an elitist `ReinsertionOp` is never replaced.
+///
+/// The replacements are built by the `GenomeBuilder`. If the `GenomeBuilder`
+/// does not build a genome that is not part of the population yet within a
+/// few attempts, e.g. because the search space is small, the last built
+/// genome is used anyway.
+///
+/// The enforcement of uniqueness can be switched off by
+/// `set_enforce_uniqueness`. Then the population combined by the wrapped
+/// `ReinsertionOp` is returned as it is, without hashing any genome.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniqueReinserter<R, B, I = ExactIdentity> {
+    /// The wrapped `ReinsertionOp`.
+    reinserter: R,
+    /// The `GenomeBuilder` that generates the replacements of duplicates.
+    genome_builder: B,
+    /// The `GenomeIdentity` that decides which genomes are duplicates.
+    identity: I,
+    /// Whether duplicate genomes are replaced.
+    enforce_uniqueness: bool,
+}
+
+impl<R, B> UniqueReinserter<R, B> {
+    /// Constructs a new instance of the `UniqueReinserter` that enforces the
+    /// uniqueness of the genomes.
+    pub fn new(reinserter: R, genome_builder: B) -> Self {
+        UniqueReinserter::with_identity(reinserter, genome_builder, ExactIdentity)
+    }
+}
+
+impl<R, B, I> UniqueReinserter<R, B, I> {
+    /// Constructs a new instance of the `UniqueReinserter` that enforces the
+    /// uniqueness of the genomes as defined by the given `GenomeIdentity`.
+    pub fn with_identity(reinserter: R, genome_builder: B, identity: I) -> Self {
+        UniqueReinserter {
+            reinserter,
+            genome_builder,
+            identity,
+            enforce_uniqueness: true,
+        }
+    }
+
+    /// Returns the wrapped `ReinsertionOp`.
+    pub fn reinserter(&self) -> &R {
+        &self.reinserter
+    }
+
+    /// Returns the `GenomeBuilder` that generates the replacements of
+    /// duplicates.
+    pub fn genome_builder(&self) -> &B {
+        &self.genome_builder
+    }
+
+    /// Returns the `GenomeIdentity` that decides which genomes are
+    /// duplicates.
+    pub fn identity(&self) -> &I {
+        &self.identity
+    }
+
+    /// Returns whether duplicate genomes are replaced.
+    pub fn enforce_uniqueness(&self) -> bool {
+        self.enforce_uniqueness
+    }
+
+    /// Sets whether duplicate genomes are replaced.
+    pub fn set_enforce_uniqueness(&mut self, value: bool) {
+        self.enforce_uniqueness = value;
+    }
+}
+
+impl<R, B, I> GeneticOperator for UniqueReinserter<R, B, I>
+where
+    R: GeneticOperator,
+    B: Clone,
+    I: Clone,
+{
+    fn name() -> String {
+        format!("Unique-{}", R::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        self.reinserter.validate()
+    }
+}
+
+/// Can be used for single-objective optimization
+impl<R, B, I> SingleObjective for UniqueReinserter<R, B, I>
+where
+    R: GeneticOperator + SingleObjective,
+    B: Clone,
+    I: Clone,
+{
+}
+
+/// Can be used for multi-objective optimization
+impl<R, B, I> MultiObjective for UniqueReinserter<R, B, I>
+where
+    R: GeneticOperator + MultiObjective,
+    B: Clone,
+    I: Clone,
+{
+}
+
+impl<G, F, R, B, I> ReinsertionOp<G, F> for UniqueReinserter<R, B, I>
+where
+    G: Genotype,
+    F: Fitness,
+    R: ReinsertionOp<G, F>,
+    B: GenomeBuilder<G> + Clone,
+    I: GenomeIdentity<G>,
+{
+    fn combine<N>(
+        &self,
+        offspring: &mut Offspring<G>,
+        evaluated: &EvaluatedPopulation<G, F>,
+        rng: &mut N,
+    ) -> Vec<G>
+    where
+        N: Rng + Sized,
+    {
+        let mut new_population = self.reinserter.combine(offspring, evaluated, rng);
+        if !self.enforce_uniqueness {
+            return new_population;
+        }
+        let mut replacements: Vec<(usize, G)> = Vec::new();
+        {
+            let mut seen = HashSet::with_capacity(new_population.len());
+            let mut duplicates = Vec::new();
+            for (index, genome) in new_population.iter().enumerate() {
+                if !seen.insert(self.identity.canonical_key(genome)) {
+                    duplicates.push(index);
+                }
+            }
+            for index in duplicates {
+                let mut genome = self.genome_builder.build_genome(index, rng);
+                let mut key = self.identity.canonical_key(&genome);
+                for _ in 1..MAX_ATTEMPTS {
+                    if !seen.contains(&key) {
+                        break;
+                    }
+                    genome = self.genome_builder.build_genome(index, rng);
+                    key = self.identity.canonical_key(&genome);
+                }
+                seen.insert(key);
+                replacements.push((index, genome));
+            }
+        }
+        for (index, genome) in replacements {
+            new_population[index] = genome;
+        }
+        new_population
+    }
+
+    fn required_offspring_size(&self, population_size: usize) -> Option<usize> {
+        self.reinserter.required_offspring_size(population_size)
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.reinserter.adapt(signal);
+    }
+
+    fn reset(&mut self) {
+        self.reinserter.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures,
+        genetic::{FitnessFunction, ReversalIdentity},
+        random::get_rng,
+        reinsertion::{elitist::ElitistReinserter, random::UniformReinserter},
+    };
+    use galvanic_assert::matchers::*;
+
+    /// Builds the genomes `[100]`, `[101]`, ... that are easily distinguished
+    /// from the individuals of the population.
+    #[derive(Clone, Debug, PartialEq)]
+    struct FreshGenomeBuilder;
+
+    impl GenomeBuilder<Vec<u32>> for FreshGenomeBuilder {
+        fn build_genome<R>(&self, index: usize, _: &mut R) -> Vec<u32>
+        where
+            R: Rng + Sized,
+        {
+            vec![100 + index as u32]
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct FirstLocus;
+
+    impl FitnessFunction<Vec<u32>, u32> for FirstLocus {
+        fn fitness_of(&self, genome: &Vec<u32>) -> u32 {
+            genome[0]
+        }
+
+        fn average(&self, fitness_values: &[u32]) -> u32 {
+            fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+        }
+
+        fn highest_possible_fitness(&self) -> u32 {
+            u32::MAX
+        }
+
+        fn lowest_possible_fitness(&self) -> u32 {
+            0
+        }
+    }
+
+    fn evaluated_population(individuals: Vec<Vec<u32>>) -> EvaluatedPopulation<Vec<u32>, u32> {
+        let fitness_values = individuals.iter().map(|x| x[0]).collect();
+        fixtures::evaluated_population(individuals, fitness_values, 0)
+    }
+
+    #[test]
+    fn duplicates_are_replaced_by_fresh_genomes() {
+        let reinserter = UniqueReinserter::new(UniformReinserter::new(1.0), FreshGenomeBuilder);
+        let evaluated = evaluated_population(vec![vec![5]; 6]);
+        let mut offspring = vec![vec![5]; 6];
+
+        let new_population = reinserter.combine(&mut offspring, &evaluated, &mut get_rng([42; 32]));
+
+        expect_that!(
+            &new_population,
+            eq(vec![
+                vec![5],
+                vec![101],
+                vec![102],
+                vec![103],
+                vec![104],
+                vec![105]
+            ])
+        );
+    }
+
+    #[test]
+    fn the_best_individual_is_never_replaced() {
+        let reinserter = UniqueReinserter::new(
+            ElitistReinserter::new(FirstLocus, false, 0.5),
+            FreshGenomeBuilder,
+        );
+        let evaluated = evaluated_population(vec![vec![9], vec![9], vec![3], vec![9]]);
+        let mut offspring = vec![vec![9], vec![9], vec![2], vec![2]];
+
+        let new_population = reinserter.combine(&mut offspring, &evaluated, &mut get_rng([42; 32]));
+
+        expect_that!(&new_population.len(), eq(4));
+        let num_best = new_population.iter().filter(|x| **x == vec![9]).count();
+        expect_that!(&num_best, eq(1));
+        let distinct: HashSet<&Vec<u32>> = new_population.iter().collect();
+        expect_that!(&distinct.len(), eq(4));
+    }
+
+    #[test]
+    fn duplicates_are_kept_if_uniqueness_is_not_enforced() {
+        let mut reinserter = UniqueReinserter::new(UniformReinserter::new(1.0), FreshGenomeBuilder);
+        reinserter.set_enforce_uniqueness(false);
+        let evaluated = evaluated_population(vec![vec![5]; 6]);
+        let mut offspring = vec![vec![5]; 6];
+
+        let new_population = reinserter.combine(&mut offspring, &evaluated, &mut get_rng([42; 32]));
+
+        expect_that!(&new_population, eq(vec![vec![5]; 6]));
+    }
+
+    #[test]
+    fn genomes_with_the_same_canonical_key_are_duplicates() {
+        let reinserter = UniqueReinserter::with_identity(
+            UniformReinserter::new(1.0),
+            FreshGenomeBuilder,
+            ReversalIdentity,
+        );
+        let evaluated = evaluated_population(vec![vec![1, 2, 3]; 3]);
+        let mut offspring = vec![vec![1, 2, 3], vec![3, 2, 1], vec![2, 1, 3]];
+
+        let new_population = reinserter.combine(&mut offspring, &evaluated, &mut get_rng([42; 32]));
+
+        expect_that!(
+            &new_population,
+            eq(vec![vec![1, 2, 3], vec![101], vec![2, 1, 3]])
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! The `boltzmann` module provides the `BoltzmannSelector`, which implements
+//! a stochastic selection strategy whose selection pressure increases over
+//! the generations like the cooling schedule of simulated annealing.
+//!
+//! Each individual is selected with a probability proportional to
+//! `exp(f / T)`, where `f` is the scalar fitness value of the individual and
+//! `T` is the temperature of the current generation. At a high temperature
+//! all individuals are selected nearly uniformly at random. The lower the
+//! temperature gets the more the selection prefers the fittest individuals.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{AsScalar, Fitness, Genotype, Parents},
+    operator::{validate_count, GenerationSignal, GeneticOperator, SelectionOp, SingleObjective},
+    random::{random_probability, Rng, WeightedDistribution},
+};
+
+/// The `CoolingSchedule` defines how the temperature of the
+/// `BoltzmannSelector` decreases from the initial temperature to the final
+/// temperature.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoolingSchedule {
+    /// The temperature decreases by the same amount in every generation.
+    Linear,
+    /// The temperature decreases by the same factor in every generation.
+    Exponential,
+}
+
+/// The `BoltzmannSelector` selects individuals with a probability
+/// proportional to `exp(f / T)`, where the temperature `T` decreases from the
+/// initial temperature in the first generation to the final temperature in
+/// the given number of generations according to a `CoolingSchedule`. After
+/// that number of generations the temperature stays at the final
+/// temperature.
+///
+/// The weights are calculated as `exp((f - max) / T)`, where `max` is the
+/// highest scalar fitness value of the population, so that the exponential
+/// function does not overflow.
+///
+/// The current generation is provided by the `ga::GeneticAlgorithm` through
+/// `SelectionOp::adapt`.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoltzmannSelector {
+    /// The number of parents to select.
+    num_parents: usize,
+    /// The number of individuals per parents.
+    num_individuals_per_parents: usize,
+    /// The temperature in the first generation.
+    initial_temperature: f64,
+    /// The temperature that is reached after `num_generations`.
+    final_temperature: f64,
+    /// The number of generations in which the final temperature is reached.
+    num_generations: u64,
+    /// The schedule of decreasing the temperature.
+    schedule: CoolingSchedule,
+    /// The current generation.
+    generation: u64,
+}
+
+impl BoltzmannSelector {
+    /// Constructs a new instance of the `BoltzmannSelector`, which selects
+    /// the given number of parents with 2 individuals per parents.
+    ///
+    /// The temperatures must be positive and the initial temperature must not
+    /// be lower than the final temperature, which is checked by
+    /// `GeneticOperator::validate` when the algorithm is built.
+    pub fn new(
+        num_parents: usize,
+        initial_temperature: f64,
+        final_temperature: f64,
+        num_generations: u64,
+        schedule: CoolingSchedule,
+    ) -> Self {
+        BoltzmannSelector {
+            num_parents,
+            num_individuals_per_parents: 2,
+            initial_temperature,
+            final_temperature,
+            num_generations,
+            schedule,
+            generation: 1,
+        }
+    }
+
+    /// Returns the number of parents that are selected on every call of the
+    /// `select_from` function.
+    pub fn num_parents(&self) -> usize {
+        self.num_parents
+    }
+
+    /// Sets the number of parents that are selected on every call of the
+    /// `select_from` function to a new value.
+    pub fn set_num_parents(&mut self, value: usize) {
+        self.num_parents = value;
+    }
+
+    /// Returns the number of individuals per parents use by this selector.
+    pub fn num_individuals_per_parents(&self) -> usize {
+        self.num_individuals_per_parents
+    }
+
+    /// Sets the number of individuals per parents to the given value.
+    pub fn set_num_individuals_per_parents(&mut self, value: usize) {
+        self.num_individuals_per_parents = value;
+    }
+
+    /// Returns the temperature in the first generation.
+    pub fn initial_temperature(&self) -> f64 {
+        self.initial_temperature
+    }
+
+    /// Returns the temperature that is reached after the number of
+    /// generations of the cooling schedule.
+    pub fn final_temperature(&self) -> f64 {
+        self.final_temperature
+    }
+
+    /// Returns the number of generations in which the final temperature is
+    /// reached.
+    pub fn num_generations(&self) -> u64 {
+        self.num_generations
+    }
+
+    /// Returns the `CoolingSchedule` of this selector.
+    pub fn schedule(&self) -> CoolingSchedule {
+        self.schedule
+    }
+
+    /// Returns the current generation.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Sets the current generation. The generation is counted from 1.
+    pub fn set_generation(&mut self, value: u64) {
+        self.generation = value;
+    }
+
+    /// Returns the temperature of the current generation.
+    pub fn temperature(&self) -> f64 {
+        if self.num_generations <= 1 || self.generation >= self.num_generations {
+            return self.final_temperature;
+        }
+        let progress = self.generation.saturating_sub(1) as f64 / (self.num_generations - 1) as f64;
+        match self.schedule {
+            CoolingSchedule::Linear => {
+                self.initial_temperature
+                    + (self.final_temperature - self.initial_temperature) * progress
+            },
+            CoolingSchedule::Exponential => {
+                self.initial_temperature
+                    * (self.final_temperature / self.initial_temperature).powf(progress)
+            },
+        }
+    }
+}
+
+impl SingleObjective for BoltzmannSelector {}
+
+impl GeneticOperator for BoltzmannSelector {
+    fn name() -> String {
+        "Boltzmann-Selection".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_count::<Self>("number of parents", self.num_parents)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )?;
+        if self.final_temperature > 0. && self.initial_temperature >= self.final_temperature {
+            Ok(())
+        } else {
+            Err(GeneticAlgorithmError::InvalidParameter(format!(
+                "The temperatures of the operator {} must be positive and must not increase, \
+                 but the initial temperature is {} and the final temperature is {}.",
+                Self::name(),
+                self.initial_temperature,
+                self.final_temperature
+            )))
+        }
+    }
+}
+
+impl<G, F> SelectionOp<G, F> for BoltzmannSelector
+where
+    G: Genotype,
+    F: Fitness + AsScalar,
+{
+    fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, rng: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let individuals = evaluated.individuals();
+        if individuals.is_empty() {
+            return Vec::new();
+        }
+        let temperature = self.temperature();
+        let scalar_values: Vec<f64> = evaluated
+            .fitness_values()
+            .iter()
+            .map(AsScalar::as_scalar)
+            .collect();
+        let max_value = scalar_values
+            .iter()
+            .cloned()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = scalar_values
+            .iter()
+            .map(|value| ((value - max_value) / temperature).exp())
+            .collect();
+        let weighted_distribution = WeightedDistribution::from_scalar_values(&weights);
+        let mut parents = Vec::with_capacity(self.num_parents);
+        for _ in 0..self.num_parents {
+            let mut tuple = Vec::with_capacity(self.num_individuals_per_parents);
+            for _ in 0..self.num_individuals_per_parents {
+                let random = random_probability(rng) * weighted_distribution.sum();
+                let selected = weighted_distribution.select(random);
+                tuple.push(evaluated.clone_individual(selected));
+            }
+            parents.push(tuple);
+        }
+        parents
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.generation = signal.generation;
+    }
+
+    fn reset(&mut self) {
+        self.generation = 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::indexed_population, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    fn evaluated_population(fitness_values: Vec<usize>) -> EvaluatedPopulation<Vec<usize>, usize> {
+        let average = fitness_values.iter().sum::<usize>() / fitness_values.len();
+        indexed_population(fitness_values, average)
+    }
+
+    fn count_selections_in_generation(
+        selector: &mut BoltzmannSelector,
+        generation: u64,
+        population: &EvaluatedPopulation<Vec<usize>, usize>,
+    ) -> Vec<usize> {
+        let individuals = population.individuals().to_vec();
+        SelectionOp::<Vec<usize>, usize>::adapt(
+            selector,
+            &GenerationSignal {
+                generation,
+                individuals: &individuals,
+                improved: false,
+            },
+        );
+        let mut rng = get_rng([42; 32]);
+        let mut counter = vec![0; population.fitness_values().len()];
+        for parents in selector.select_from(population, &mut rng) {
+            for genome in parents {
+                counter[genome[0]] += 1;
+            }
+        }
+        counter
+    }
+
+    #[test]
+    fn linear_schedule_decreases_the_temperature_by_the_same_amount() {
+        let mut selector = BoltzmannSelector::new(10, 100., 10., 10, CoolingSchedule::Linear);
+
+        let temperatures: Vec<f64> = (1..=12)
+            .map(|generation| {
+                selector.set_generation(generation);
+                selector.temperature()
+            })
+            .collect();
+
+        expect_that!(
+            &temperatures,
+            eq(vec![
+                100., 90., 80., 70., 60., 50., 40., 30., 20., 10., 10., 10.
+            ])
+        );
+    }
+
+    #[test]
+    fn exponential_schedule_decreases_the_temperature_by_the_same_factor() {
+        let mut selector = BoltzmannSelector::new(10, 1000., 1., 4, CoolingSchedule::Exponential);
+
+        let temperatures: Vec<f64> = (1..=5)
+            .map(|generation| {
+                selector.set_generation(generation);
+                selector.temperature()
+            })
+            .collect();
+
+        let expected = [1000., 100., 10., 1., 1.];
+        for (temperature, expected) in temperatures.iter().zip(expected.iter()) {
+            expect_that!(temperature, is(close_to(*expected, 1e-9)));
+        }
+    }
+
+    #[test]
+    fn early_generations_select_nearly_uniformly_late_generations_greedily() {
+        let population = evaluated_population((0..10).collect());
+        let mut selector =
+            BoltzmannSelector::new(500, 1000., 0.01, 50, CoolingSchedule::Exponential);
+
+        let early = count_selections_in_generation(&mut selector, 1, &population);
+        let late = count_selections_in_generation(&mut selector, 50, &population);
+
+        // 500 parents * 2 individuals = 1000 picks
+        for count in &early {
+            expect_that!(count, is(greater_than(60)));
+            expect_that!(count, is(less_than(140)));
+        }
+        expect_that!(&late[9], eq(1000));
+    }
+
+    #[test]
+    fn large_fitness_values_do_not_overflow() {
+        let population = evaluated_population(vec![1_000_000, 2_000_000, 3_000_000]);
+        let mut selector = BoltzmannSelector::new(50, 1., 1., 1, CoolingSchedule::Linear);
+
+        let counter = count_selections_in_generation(&mut selector, 1, &population);
+
+        expect_that!(&counter, eq(vec![0, 0, 100]));
+    }
+
+    #[test]
+    fn increasing_or_non_positive_temperatures_are_rejected() {
+        let increasing = BoltzmannSelector::new(10, 1., 10., 10, CoolingSchedule::Linear);
+        let non_positive = BoltzmannSelector::new(10, 10., 0., 10, CoolingSchedule::Exponential);
+
+        expect_that!(
+            &increasing.validate(),
+            eq(Err(GeneticAlgorithmError::InvalidParameter(
+                "The temperatures of the operator Boltzmann-Selection must be positive and \
+                 must not increase, but the initial temperature is 1 and the final \
+                 temperature is 10."
+                    .to_string()
+            )))
+        );
+        expect_that!(&non_positive.validate().is_err(), eq(true));
+        expect_that!(
+            &BoltzmannSelector::new(10, 10., 10., 10, CoolingSchedule::Linear).validate(),
+            eq(Ok(()))
+        );
+    }
+}
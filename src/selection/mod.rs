@@ -7,11 +7,30 @@
 //! * `ranking`
 //! * `proportionate`
 //! * `tournament`
+//! * `boltzmann`
+//! * `nsga2`
+//!
+//! The `scaling` module provides fitness scaling methods that can be combined
+//! with the fitness proportionate selection operators. The `sharing` module
+//! provides fitness sharing, which wraps a selection operator in order to
+//! maintain several niches in the population. The `pairing` module provides
+//! strategies that decide which of the selected individuals are recombined
+//! with each other.
+
+pub mod boltzmann;
+
+pub mod nsga2;
+
+pub mod pairing;
 
 pub mod proportionate;
 
 pub mod ranking;
 
+pub mod scaling;
+
+pub mod sharing;
+
 pub mod tournament;
 
 pub mod truncation;
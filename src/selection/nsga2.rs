@@ -0,0 +1,210 @@
+//! The `nsga2` module provides selection operators of the non-dominated
+//! sorting genetic algorithm II (NSGA-II) for multi-objective optimization.
+//!
+//! The provided `SelectionOp` implementations are:
+//! * `Nsga2Selector`
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{Fitness, Genotype, MultiObjectiveFitness, Parents},
+    operator::{validate_count, GeneticOperator, MultiObjective, SelectionOp},
+    pareto::{crowding_distance, non_dominated_sort, ParetoDominance},
+    random::{Rng, SliceRandom},
+};
+
+/// The `Nsga2Selector` selects parents by non-dominated sorting and crowding
+/// distance as done by the NSGA-II algorithm.
+///
+/// The individuals of the population are sorted into Pareto fronts according
+/// to the objective values of their `genetic::MultiObjectiveFitness`. Within
+/// each front the individuals are ordered by their crowding distance, from
+/// the least crowded to the most crowded. The selector picks the individuals
+/// in this order, i.e. front by front, until it has picked enough individuals
+/// for `num_parents` parents. If the population is too small the picking
+/// starts again at the beginning. The picked individuals are shuffled before
+/// they are grouped into parents.
+///
+/// Individuals with equal rank and crowding distance keep their order in the
+/// population, so that the selection of an entirely non-dominated population
+/// is deterministic up to the final shuffle.
+///
+/// By default all objectives are maximized. Use `with_dominance` to define a
+/// `pareto::ParetoDominance` with other directions of the objectives.
+///
+/// This `Nsga2Selector` can only be used for multi-objective fitness values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nsga2Selector {
+    /// The number of parents to select.
+    num_parents: usize,
+    /// The number of individuals per parents.
+    num_individuals_per_parents: usize,
+    /// The `ParetoDominance` used to sort the individuals into fronts.
+    dominance: ParetoDominance,
+}
+
+impl Nsga2Selector {
+    /// Constructs a new instance of the `Nsga2Selector` that selects the
+    /// given number of parents with 2 individuals per parents and maximizes
+    /// all objectives.
+    pub fn new(num_parents: usize) -> Self {
+        Nsga2Selector::with_dominance(num_parents, ParetoDominance::maximizing())
+    }
+
+    /// Constructs a new instance of the `Nsga2Selector` that selects the
+    /// given number of parents with 2 individuals per parents and uses the
+    /// given `ParetoDominance`.
+    pub fn with_dominance(num_parents: usize, dominance: ParetoDominance) -> Self {
+        Nsga2Selector {
+            num_parents,
+            num_individuals_per_parents: 2,
+            dominance,
+        }
+    }
+
+    /// Returns the number of parents that are selected on every call of the
+    /// `select_from` function.
+    pub fn num_parents(&self) -> usize {
+        self.num_parents
+    }
+
+    /// Sets the number of parents that are selected on every call of the
+    /// `select_from` function.
+    pub fn set_num_parents(&mut self, value: usize) {
+        self.num_parents = value;
+    }
+
+    /// Returns the number of individuals per parents use by this selector.
+    pub fn num_individuals_per_parents(&self) -> usize {
+        self.num_individuals_per_parents
+    }
+
+    /// Sets the number of individuals per parents to the given value.
+    pub fn set_num_individuals_per_parents(&mut self, value: usize) {
+        self.num_individuals_per_parents = value;
+    }
+
+    /// Returns the `ParetoDominance` used by this selector.
+    pub fn dominance(&self) -> &ParetoDominance {
+        &self.dominance
+    }
+
+    /// Sets the `ParetoDominance` used by this selector.
+    pub fn set_dominance(&mut self, value: ParetoDominance) {
+        self.dominance = value;
+    }
+}
+
+/// Can be used for multi-objective optimization
+impl MultiObjective for Nsga2Selector {}
+
+impl GeneticOperator for Nsga2Selector {
+    fn name() -> String {
+        "NSGA-II-Selection".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_count::<Self>("number of parents", self.num_parents)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )
+    }
+}
+
+impl<G, F> SelectionOp<G, F> for Nsga2Selector
+where
+    G: Genotype,
+    F: Fitness + MultiObjectiveFitness,
+{
+    fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, rng: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let individuals = evaluated.individuals();
+        if individuals.is_empty() || self.num_individuals_per_parents == 0 {
+            return Vec::new();
+        }
+        let objectives: Vec<Vec<f64>> = evaluated
+            .fitness_values()
+            .iter()
+            .map(|fitness| fitness.objectives())
+            .collect();
+
+        // mating pool holds indices to the individuals slice
+        let mating_pool = crowded_order(&objectives, &self.dominance);
+        let pool_size = mating_pool.len();
+        let num_candidates = self.num_parents * self.num_individuals_per_parents;
+        let mut candidates: Vec<usize> = (0..num_candidates)
+            .map(|index_m| mating_pool[index_m % pool_size])
+            .collect();
+        candidates.shuffle(rng);
+
+        candidates
+            .chunks(self.num_individuals_per_parents)
+            .map(|tuple| {
+                tuple
+                    .iter()
+                    .map(|index_i| evaluated.clone_individual(*index_i))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
+}
+
+/// Returns the indices of the given solutions sorted by the crowded
+/// comparison of NSGA-II, i.e. by ascending rank of their Pareto front and
+/// within a front by descending crowding distance.
+fn crowded_order(objectives: &[Vec<f64>], dominance: &ParetoDominance) -> Vec<usize> {
+    let mut order = Vec::with_capacity(objectives.len());
+    for front in non_dominated_sort(objectives, dominance) {
+        let front_objectives: Vec<Vec<f64>> = front
+            .iter()
+            .map(|index| objectives[*index].clone())
+            .collect();
+        let distances = crowding_distance(&front_objectives);
+        let mut positions: Vec<usize> = (0..front.len()).collect();
+        // stable sort so that ties keep the order of the population
+        positions.sort_by(|x, y| distances[*y].total_cmp(&distances[*x]));
+        order.extend(positions.into_iter().map(|position| front[position]));
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pareto::Direction;
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn crowded_order_sorts_by_front_and_crowding_distance() {
+        // minimize both objectives
+        let dominance = ParetoDominance::new(vec![Direction::Minimize, Direction::Minimize]);
+        let objectives = vec![
+            vec![7., 7.],  // front 2
+            vec![1., 9.],  // front 1, boundary
+            vec![2., 7.],  // front 1, most crowded
+            vec![2.5, 6.], // front 1, crowded
+            vec![6., 2.],  // front 1, not crowded
+            vec![9., 1.],  // front 1, boundary
+        ];
+
+        let order = crowded_order(&objectives, &dominance);
+
+        expect_that!(&order, eq(vec![1, 5, 4, 3, 2, 0]));
+    }
+
+    #[test]
+    fn crowded_order_of_equally_spaced_non_dominated_solutions_is_stable() {
+        let objectives: Vec<Vec<f64>> = (0..6).map(|i| vec![i as f64, 5. - i as f64]).collect();
+
+        let order = crowded_order(&objectives, &ParetoDominance::maximizing());
+
+        expect_that!(&order, eq(vec![0, 5, 1, 2, 3, 4]));
+    }
+}
@@ -0,0 +1,401 @@
+//! The `pairing` module provides strategies that decide which of the selected
+//! individuals are recombined with each other.
+//!
+//! A `SelectionOp` determines which individuals form the mating pool and how
+//! they are grouped into `genetic::Parents`. The `PairedSelector` wraps a
+//! `SelectionOp` and regroups its mating pool by a `PairingStrategy` before
+//! the parents are passed on to the `operator::CrossoverOp`. Thus mating
+//! schemes like assortative mating, which recombines similar individuals, can
+//! be combined with any selection and any crossover operator.
+//!
+//! The provided `PairingStrategy` implementations are:
+//! * `RandomPairing`
+//! * `SequentialPairing`
+//! * `SimilarityPairing`
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{Fitness, GenomeDistance, Genotype, Parents},
+    operator::{GenerationSignal, GeneticOperator, MultiObjective, SelectionOp, SingleObjective},
+    random::{Rng, SliceRandom},
+};
+
+/// A `PairingStrategy` groups the individuals of a mating pool into the
+/// `genetic::Parents` that are recombined by the `operator::CrossoverOp`.
+///
+/// Implementations must return `mating_pool.len() / parents_size` parents of
+/// exactly `parents_size` individuals each. Surplus individuals that do not
+/// form complete parents are dropped.
+pub trait PairingStrategy<G>: Clone
+where
+    G: Genotype,
+{
+    /// Groups the individuals of the given mating pool into parents of
+    /// `parents_size` individuals each.
+    fn pair<R>(&self, mating_pool: Vec<G>, parents_size: usize, rng: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized;
+}
+
+/// The `RandomPairing` groups the individuals of the mating pool at random.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomPairing;
+
+impl<G> PairingStrategy<G> for RandomPairing
+where
+    G: Genotype,
+{
+    fn pair<R>(&self, mating_pool: Vec<G>, parents_size: usize, rng: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let mut mating_pool = mating_pool;
+        mating_pool.shuffle(rng);
+        SequentialPairing.pair(mating_pool, parents_size, rng)
+    }
+}
+
+/// The `SequentialPairing` groups the individuals of the mating pool in the
+/// order they have been selected, i.e. the first `parents_size` individuals
+/// form the first parents and so on.
+///
+/// If the wrapped `SelectionOp` selects parents of `parents_size`
+/// individuals the `SequentialPairing` reproduces the parents as selected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SequentialPairing;
+
+impl<G> PairingStrategy<G> for SequentialPairing
+where
+    G: Genotype,
+{
+    fn pair<R>(&self, mating_pool: Vec<G>, parents_size: usize, _: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = mating_pool.len() / parents_size;
+        let mut individuals = mating_pool.into_iter();
+        (0..num_parents)
+            .map(|_| individuals.by_ref().take(parents_size).collect())
+            .collect()
+    }
+}
+
+/// The `SimilarityPairing` groups each individual with the individuals of
+/// the mating pool that are most similar to it (assortative mating) or most
+/// different from it (disassortative mating), as measured by a
+/// `genetic::GenomeDistance`.
+///
+/// The parents are formed greedily: the first individual of the mating pool
+/// that is not part of any parents yet is grouped with the `parents_size - 1`
+/// remaining individuals that are nearest to it, or farthest from it
+/// respectively. Of equally distant individuals the one selected first is
+/// taken.
+///
+/// The distances are calculated for pairs of individuals, so the cost of the
+/// pairing grows quadratically with the size of the mating pool.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimilarityPairing<D> {
+    /// The `GenomeDistance` that measures the similarity of two individuals.
+    distance: D,
+    /// Whether similar or different individuals are grouped.
+    prefers_similar: bool,
+}
+
+impl<D> SimilarityPairing<D> {
+    /// Constructs a new instance of the `SimilarityPairing` that groups the
+    /// most similar individuals.
+    pub fn new(distance: D) -> Self {
+        SimilarityPairing {
+            distance,
+            prefers_similar: true,
+        }
+    }
+
+    /// Constructs a new instance of the `SimilarityPairing` that groups the
+    /// most different individuals.
+    pub fn dissimilar(distance: D) -> Self {
+        SimilarityPairing {
+            distance,
+            prefers_similar: false,
+        }
+    }
+
+    /// Returns the `GenomeDistance` that measures the similarity of two
+    /// individuals.
+    pub fn distance(&self) -> &D {
+        &self.distance
+    }
+
+    /// Returns whether the most similar individuals are grouped. If `false`
+    /// the most different individuals are grouped.
+    pub fn prefers_similar(&self) -> bool {
+        self.prefers_similar
+    }
+}
+
+impl<G, D> PairingStrategy<G> for SimilarityPairing<D>
+where
+    G: Genotype,
+    D: GenomeDistance<G>,
+{
+    fn pair<R>(&self, mating_pool: Vec<G>, parents_size: usize, _: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let num_parents = mating_pool.len() / parents_size;
+        let mut unpaired: Vec<Option<G>> = mating_pool.into_iter().map(Some).collect();
+        let mut parents = Vec::with_capacity(num_parents);
+        for _ in 0..num_parents {
+            let first = unpaired
+                .iter_mut()
+                .find_map(Option::take)
+                .expect("enough individuals left");
+            let mut mates = Vec::with_capacity(parents_size);
+            mates.push(first);
+            for _ in 1..parents_size {
+                let mut best: Option<(usize, f64)> = None;
+                for (index, candidate) in unpaired.iter().enumerate() {
+                    if let Some(candidate) = candidate {
+                        let distance = self.distance.distance(&mates[0], candidate);
+                        let is_better = match best {
+                            None => true,
+                            Some((_, best_distance)) if self.prefers_similar => {
+                                distance < best_distance
+                            },
+                            Some((_, best_distance)) => distance > best_distance,
+                        };
+                        if is_better {
+                            best = Some((index, distance));
+                        }
+                    }
+                }
+                let (index, _) = best.expect("enough individuals left");
+                mates.push(unpaired[index].take().expect("not paired yet"));
+            }
+            parents.push(mates);
+        }
+        parents
+    }
+}
+
+/// The `PairedSelector` wraps another `SelectionOp` and regroups the
+/// individuals it selects by a `PairingStrategy`.
+///
+/// The individuals of all parents selected by the wrapped `SelectionOp` form
+/// the mating pool, which is grouped into parents of the same size again.
+/// Thus the `PairedSelector` selects the same individuals as the wrapped
+/// `SelectionOp` and the number of individuals per parents still matches the
+/// `operator::CrossoverOp`. Only which individuals are recombined with each
+/// other is decided by the `PairingStrategy`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairedSelector<S, P> {
+    /// The wrapped `SelectionOp`.
+    selector: S,
+    /// The `PairingStrategy` that groups the selected individuals.
+    pairing: P,
+}
+
+impl<S, P> PairedSelector<S, P> {
+    /// Constructs a new instance of the `PairedSelector` that groups the
+    /// individuals selected by the given `SelectionOp` by the given
+    /// `PairingStrategy`.
+    pub fn new(selector: S, pairing: P) -> Self {
+        PairedSelector { selector, pairing }
+    }
+
+    /// Returns the wrapped `SelectionOp`.
+    pub fn selector(&self) -> &S {
+        &self.selector
+    }
+
+    /// Returns the `PairingStrategy` that groups the selected individuals.
+    pub fn pairing(&self) -> &P {
+        &self.pairing
+    }
+}
+
+impl<S, P> GeneticOperator for PairedSelector<S, P>
+where
+    S: GeneticOperator,
+    P: Clone,
+{
+    fn name() -> String {
+        format!("Paired-{}", S::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        self.selector.validate()
+    }
+}
+
+/// Can be used for single-objective optimization
+impl<S, P> SingleObjective for PairedSelector<S, P>
+where
+    S: GeneticOperator + SingleObjective,
+    P: Clone,
+{
+}
+
+/// Can be used for multi-objective optimization
+impl<S, P> MultiObjective for PairedSelector<S, P>
+where
+    S: GeneticOperator + MultiObjective,
+    P: Clone,
+{
+}
+
+impl<G, F, S, P> SelectionOp<G, F> for PairedSelector<S, P>
+where
+    G: Genotype,
+    F: Fitness,
+    S: SelectionOp<G, F>,
+    P: PairingStrategy<G>,
+{
+    fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, rng: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let selected = self.selector.select_from(evaluated, rng);
+        let parents_size = match self.selector.parents_size() {
+            Some(parents_size) => parents_size,
+            None => selected.first().map_or(0, Vec::len),
+        };
+        if parents_size == 0 {
+            return selected;
+        }
+        let mating_pool = selected.into_iter().flatten().collect();
+        self.pairing.pair(mating_pool, parents_size, rng)
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        self.selector.parents_size()
+    }
+
+    fn required_population_size(&self) -> Option<usize> {
+        self.selector.required_population_size()
+    }
+
+    fn is_minimize(&self) -> bool {
+        self.selector.is_minimize()
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.selector.adapt(signal);
+    }
+
+    fn reset(&mut self) {
+        self.selector.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::evaluated_population, genetic::EuclideanDistance, random::get_rng,
+        selection::truncation::MaximizeSelector,
+    };
+    use galvanic_assert::matchers::*;
+
+    fn mating_pool() -> Vec<Vec<f64>> {
+        vec![
+            vec![0.],
+            vec![10.],
+            vec![21.],
+            vec![9.],
+            vec![1.],
+            vec![20.],
+        ]
+    }
+
+    #[test]
+    fn similarity_pairing_pairs_the_nearest_neighbors() {
+        let pairing = SimilarityPairing::new(EuclideanDistance);
+
+        let parents = pairing.pair(mating_pool(), 2, &mut get_rng([42; 32]));
+
+        expect_that!(
+            &parents,
+            eq(vec![
+                vec![vec![0.], vec![1.]],
+                vec![vec![10.], vec![9.]],
+                vec![vec![21.], vec![20.]],
+            ])
+        );
+    }
+
+    #[test]
+    fn similarity_pairing_groups_the_nearest_neighbors_for_more_than_two_parents() {
+        let pairing = SimilarityPairing::new(EuclideanDistance);
+
+        let parents = pairing.pair(mating_pool(), 3, &mut get_rng([42; 32]));
+
+        expect_that!(
+            &parents,
+            eq(vec![
+                vec![vec![0.], vec![1.], vec![9.]],
+                vec![vec![10.], vec![20.], vec![21.]],
+            ])
+        );
+    }
+
+    #[test]
+    fn dissimilar_pairing_pairs_the_farthest_individuals() {
+        let pairing = SimilarityPairing::dissimilar(EuclideanDistance);
+
+        let parents = pairing.pair(mating_pool(), 2, &mut get_rng([42; 32]));
+
+        expect_that!(
+            &parents,
+            eq(vec![
+                vec![vec![0.], vec![21.]],
+                vec![vec![10.], vec![20.]],
+                vec![vec![9.], vec![1.]],
+            ])
+        );
+    }
+
+    #[test]
+    fn sequential_pairing_keeps_the_order_and_drops_surplus_individuals() {
+        let parents = SequentialPairing.pair(mating_pool(), 4, &mut get_rng([42; 32]));
+
+        expect_that!(
+            &parents,
+            eq(vec![vec![vec![0.], vec![10.], vec![21.], vec![9.]]])
+        );
+    }
+
+    #[test]
+    fn random_pairing_groups_each_individual_once() {
+        let parents = RandomPairing.pair(mating_pool(), 2, &mut get_rng([42; 32]));
+
+        expect_that!(&parents.len(), eq(3));
+        let mut individuals: Vec<Vec<f64>> = parents.into_iter().flatten().collect();
+        individuals.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        let mut expected = mating_pool();
+        expected.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        expect_that!(&individuals, eq(expected));
+    }
+
+    #[test]
+    fn paired_selector_regroups_the_selected_individuals() {
+        let individuals = mating_pool();
+        let fitness_values: Vec<u32> = individuals.iter().map(|x| x[0] as u32).collect();
+        let evaluated = evaluated_population(individuals, fitness_values, 10);
+        let wrapped = MaximizeSelector::new(1.0, 2);
+        let selector =
+            PairedSelector::new(wrapped.clone(), SimilarityPairing::new(EuclideanDistance));
+        let mut rng = get_rng([42; 32]);
+
+        let selected = wrapped.select_from(&evaluated, &mut rng);
+        let parents = selector.select_from(&evaluated, &mut rng);
+
+        expect_that!(&parents.len(), eq(selected.len()));
+        for group in &parents {
+            expect_that!(&group.len(), eq(2));
+            let distance = (group[0][0] - group[1][0]).abs();
+            expect_that!(&distance, leq(1.));
+        }
+    }
+}
@@ -13,27 +13,47 @@
 //! The provided **fitness proportionate selection** operators are:
 //! * `RouletteWheelSelector` - no bias - does not guarantee minimal spread.
 //! * `UniversalSamplingSelector` - no bias - minimal spread.
+//!
+//! Both operators can be combined with a `selection::scaling::FitnessScaling`
+//! that transforms the fitness values before the weights are calculated, e.g.
+//! `SigmaScaling` to keep the selection pressure constant while the population
+//! converges.
 
 use crate::{
     algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
     genetic::{AsScalar, Fitness, Genotype, Parents},
-    operator::{GeneticOperator, SelectionOp, SingleObjective},
+    operator::{validate_count, validate_ratio, GeneticOperator, SelectionOp, SingleObjective},
     random::{random_probability, Rng, WeightedDistribution},
+    selection::scaling::{FitnessScaling, NoScaling},
 };
 
 /// The `RouletteWheelSelector` implements stochastic fitness proportionate
 /// selection. Each candidate is picked randomly with a probability of being
 /// picked that is proportional to its fitness value.
 ///
+/// The weight of a candidate is the scalar value of its fitness as returned
+/// by the `genetic::AsScalar` implementation of the `genetic::Fitness` type.
+/// If all candidates have a fitness of zero, they are picked uniformly at
+/// random.
+///
+/// The fitness values can be transformed by a `FitnessScaling` before the
+/// weights are calculated. By default the fitness values are not scaled.
+///
 /// Characteristics: no bias, does not guarantee minimal spread.
 #[allow(missing_copy_implementations)]
 #[derive(Clone, Debug, PartialEq)]
-pub struct RouletteWheelSelector {
+pub struct RouletteWheelSelector<S = NoScaling>
+where
+    S: FitnessScaling,
+{
     /// The fraction of number of parents to select in relation to the
     /// number of individuals in the population.
     selection_ratio: f64,
     /// The number of individuals per parents.
     num_individuals_per_parents: usize,
+    /// The scaling applied to the fitness values.
+    scaling: S,
 }
 
 impl RouletteWheelSelector {
@@ -42,6 +62,26 @@ impl RouletteWheelSelector {
         RouletteWheelSelector {
             selection_ratio,
             num_individuals_per_parents,
+            scaling: NoScaling,
+        }
+    }
+}
+
+impl<S> RouletteWheelSelector<S>
+where
+    S: FitnessScaling,
+{
+    /// Constructs a new instance of `RouletteWheelSelector` that scales the fitness
+    /// values using the given `FitnessScaling`.
+    pub fn with_scaling(
+        selection_ratio: f64,
+        num_individuals_per_parents: usize,
+        scaling: S,
+    ) -> Self {
+        RouletteWheelSelector {
+            selection_ratio,
+            num_individuals_per_parents,
+            scaling,
         }
     }
 
@@ -72,20 +112,42 @@ impl RouletteWheelSelector {
     pub fn set_num_individuals_per_parents(&mut self, value: usize) {
         self.num_individuals_per_parents = value;
     }
+
+    /// Returns the `FitnessScaling` used by this selector.
+    pub fn scaling(&self) -> &S {
+        &self.scaling
+    }
+
+    /// Sets the `FitnessScaling` used by this selector.
+    pub fn set_scaling(&mut self, scaling: S) {
+        self.scaling = scaling;
+    }
 }
 
-impl SingleObjective for RouletteWheelSelector {}
+impl<S> SingleObjective for RouletteWheelSelector<S> where S: FitnessScaling {}
 
-impl GeneticOperator for RouletteWheelSelector {
+impl<S> GeneticOperator for RouletteWheelSelector<S>
+where
+    S: FitnessScaling,
+{
     fn name() -> String {
         "Roulette-Wheel-Selection".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("selection ratio", self.selection_ratio)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )
+    }
 }
 
-impl<G, F> SelectionOp<G, F> for RouletteWheelSelector
+impl<G, F, S> SelectionOp<G, F> for RouletteWheelSelector<S>
 where
     G: Genotype,
     F: Fitness + AsScalar,
+    S: FitnessScaling,
 {
     fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, rng: &mut R) -> Vec<Parents<G>>
     where
@@ -95,34 +157,50 @@ where
         let num_parents_to_select =
             (individuals.len() as f64 * self.selection_ratio + 0.5).floor() as usize;
         let mut parents = Vec::with_capacity(num_parents_to_select);
-        let weighted_distribution =
-            WeightedDistribution::from_scalar_values(evaluated.fitness_values());
+        let weights = scaled_weights(evaluated.fitness_values(), &self.scaling);
+        let weighted_distribution = WeightedDistribution::from_scalar_values(&weights);
         for _ in 0..num_parents_to_select {
             let mut tuple = Vec::with_capacity(self.num_individuals_per_parents);
             for _ in 0..self.num_individuals_per_parents {
                 let random = random_probability(rng) * weighted_distribution.sum();
                 let selected = weighted_distribution.select(random);
-                tuple.push(individuals[selected].clone());
+                tuple.push(evaluated.clone_individual(selected));
             }
             parents.push(tuple);
         }
         parents
     }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
 }
 
 /// The `UniversalSamplingSelector` implements stochastic fitness proportionate
 /// selection. The first candidate is picked randomly. All other candidates are
 /// picked by equidistant jumps.
 ///
+/// The pointer to the first candidate is placed randomly within the distance
+/// of one jump, so that all candidates are picked in one pass over the
+/// cumulative weight distribution.
+///
+/// The fitness values can be transformed by a `FitnessScaling` before the
+/// weights are calculated. By default the fitness values are not scaled.
+///
 /// Characteristics: no bias, minimal spread.
 #[allow(missing_copy_implementations)]
 #[derive(Clone, Debug, PartialEq)]
-pub struct UniversalSamplingSelector {
+pub struct UniversalSamplingSelector<S = NoScaling>
+where
+    S: FitnessScaling,
+{
     /// The fraction of number of parents to select in relation to the
     /// number of individuals in the population.
     selection_ratio: f64,
     /// The number of individuals per parents.
     num_individuals_per_parents: usize,
+    /// The scaling applied to the fitness values.
+    scaling: S,
 }
 
 impl UniversalSamplingSelector {
@@ -131,6 +209,26 @@ impl UniversalSamplingSelector {
         UniversalSamplingSelector {
             selection_ratio,
             num_individuals_per_parents,
+            scaling: NoScaling,
+        }
+    }
+}
+
+impl<S> UniversalSamplingSelector<S>
+where
+    S: FitnessScaling,
+{
+    /// Constructs a new instance of `UniversalSamplingSelector` that scales the fitness
+    /// values using the given `FitnessScaling`.
+    pub fn with_scaling(
+        selection_ratio: f64,
+        num_individuals_per_parents: usize,
+        scaling: S,
+    ) -> Self {
+        UniversalSamplingSelector {
+            selection_ratio,
+            num_individuals_per_parents,
+            scaling,
         }
     }
 
@@ -161,20 +259,42 @@ impl UniversalSamplingSelector {
     pub fn set_num_individuals_per_parents(&mut self, value: usize) {
         self.num_individuals_per_parents = value;
     }
+
+    /// Returns the `FitnessScaling` used by this selector.
+    pub fn scaling(&self) -> &S {
+        &self.scaling
+    }
+
+    /// Sets the `FitnessScaling` used by this selector.
+    pub fn set_scaling(&mut self, scaling: S) {
+        self.scaling = scaling;
+    }
 }
 
-impl SingleObjective for UniversalSamplingSelector {}
+impl<S> SingleObjective for UniversalSamplingSelector<S> where S: FitnessScaling {}
 
-impl GeneticOperator for UniversalSamplingSelector {
+impl<S> GeneticOperator for UniversalSamplingSelector<S>
+where
+    S: FitnessScaling,
+{
     fn name() -> String {
         "Stochastic-Universal-Sampling-Selection".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("selection ratio", self.selection_ratio)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )
+    }
 }
 
-impl<G, F> SelectionOp<G, F> for UniversalSamplingSelector
+impl<G, F, S> SelectionOp<G, F> for UniversalSamplingSelector<S>
 where
     G: Genotype,
     F: Fitness + AsScalar,
+    S: FitnessScaling,
 {
     fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, rng: &mut R) -> Vec<Parents<G>>
     where
@@ -184,20 +304,152 @@ where
         let num_parents_to_select =
             (individuals.len() as f64 * self.selection_ratio + 0.5).floor() as usize;
         let mut parents = Vec::with_capacity(num_parents_to_select);
-        let weighted_distribution =
-            WeightedDistribution::from_scalar_values(evaluated.fitness_values());
+        let weights = scaled_weights(evaluated.fitness_values(), &self.scaling);
+        let weighted_distribution = WeightedDistribution::from_scalar_values(&weights);
         let distance = weighted_distribution.sum()
             / (num_parents_to_select * self.num_individuals_per_parents) as f64;
-        let mut pointer = random_probability(rng) * weighted_distribution.sum();
+        let mut pointer = random_probability(rng) * distance;
         for _ in 0..num_parents_to_select {
             let mut tuple = Vec::with_capacity(self.num_individuals_per_parents);
             for _ in 0..self.num_individuals_per_parents {
                 let selected = weighted_distribution.select(pointer);
-                tuple.push(individuals[selected].clone());
+                tuple.push(evaluated.clone_individual(selected));
                 pointer += distance;
             }
             parents.push(tuple);
         }
         parents
     }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
+}
+
+/// Returns the scaled scalar values of the given fitness values.
+fn scaled_weights<F, S>(fitness_values: &[F], scaling: &S) -> Vec<f64>
+where
+    F: AsScalar,
+    S: FitnessScaling,
+{
+    let scalar_values: Vec<f64> = fitness_values.iter().map(AsScalar::as_scalar).collect();
+    scaling.scale(&scalar_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::indexed_population, random::get_rng, selection::scaling::SigmaScaling};
+    use galvanic_assert::matchers::*;
+
+    fn evaluated_population(fitness_values: Vec<usize>) -> EvaluatedPopulation<Vec<usize>, usize> {
+        let average = fitness_values.iter().sum::<usize>() / fitness_values.len();
+        indexed_population(fitness_values, average)
+    }
+
+    fn count_selections<S>(
+        selector: &S,
+        population: &EvaluatedPopulation<Vec<usize>, usize>,
+    ) -> Vec<usize>
+    where
+        S: SelectionOp<Vec<usize>, usize>,
+    {
+        let mut rng = get_rng([42; 32]);
+        let mut counter = vec![0; population.fitness_values().len()];
+        for _ in 0..100 {
+            for parents in selector.select_from(population, &mut rng) {
+                for genome in parents {
+                    counter[genome[0]] += 1;
+                }
+            }
+        }
+        counter
+    }
+
+    #[test]
+    fn roulette_wheel_selects_proportional_to_fitness() {
+        let population = evaluated_population(vec![10, 0, 30, 60]);
+        let selector = RouletteWheelSelector::new(1.0, 2);
+
+        let counter = count_selections(&selector, &population);
+
+        // 100 runs * 4 parents * 2 individuals = 800 picks
+        expect_that!(&counter[0], is(greater_than(50)));
+        expect_that!(&counter[0], is(less_than(110)));
+        expect_that!(&counter[1], eq(0));
+        expect_that!(&counter[2], is(greater_than(200)));
+        expect_that!(&counter[2], is(less_than(280)));
+        expect_that!(&counter[3], is(greater_than(440)));
+        expect_that!(&counter[3], is(less_than(520)));
+    }
+
+    #[test]
+    fn universal_sampling_selects_proportional_to_fitness() {
+        let population = evaluated_population(vec![10, 0, 30, 60]);
+        let selector = UniversalSamplingSelector::new(1.0, 2);
+
+        let counter = count_selections(&selector, &population);
+
+        // with minimal spread each run picks the individuals in proportion
+        // 10:0:30:60 out of 8 picks
+        expect_that!(&counter[0], is(less_than_or_equal(100)));
+        expect_that!(&counter[1], eq(0));
+        expect_that!(&counter[2], is(greater_than_or_equal(200)));
+        expect_that!(&counter[2], is(less_than_or_equal(300)));
+        expect_that!(&counter[3], is(greater_than_or_equal(400)));
+        expect_that!(&counter[3], is(less_than_or_equal(500)));
+    }
+
+    #[test]
+    fn universal_sampling_selects_all_parents_in_one_pass() {
+        let population = evaluated_population(vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+        let selector = UniversalSamplingSelector::new(0.5, 2);
+
+        let selected = selector.select_from(&population, &mut get_rng([42; 32]));
+
+        // 10 equally weighted individuals and 10 picks select each one once
+        let mut picked: Vec<usize> = selected.iter().flatten().map(|genome| genome[0]).collect();
+        picked.sort_unstable();
+        expect_that!(&picked, eq((0..10).collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn sigma_scaling_keeps_selection_pressure_in_converged_population() {
+        let population = evaluated_population(vec![1000, 1000, 1000, 1010]);
+        let unscaled = RouletteWheelSelector::new(1.0, 2);
+        let scaled = RouletteWheelSelector::with_scaling(1.0, 2, SigmaScaling::new());
+
+        let unscaled_counter = count_selections(&unscaled, &population);
+        let scaled_counter = count_selections(&scaled, &population);
+
+        // the best individual gets about 25% of 800 picks without scaling and
+        // about 47% with sigma scaling
+        expect_that!(&unscaled_counter[3], is(less_than(240)));
+        expect_that!(&scaled_counter[3], is(greater_than(320)));
+    }
+
+    #[test]
+    fn universal_sampling_with_sigma_scaling_selects_equal_fitness_values_uniformly() {
+        let population = evaluated_population(vec![5, 5, 5, 5, 5, 5, 5, 5, 5, 5]);
+        let selector = UniversalSamplingSelector::with_scaling(0.5, 2, SigmaScaling::new());
+
+        let selected = selector.select_from(&population, &mut get_rng([42; 32]));
+
+        let mut picked: Vec<usize> = selected.iter().flatten().map(|genome| genome[0]).collect();
+        picked.sort_unstable();
+        expect_that!(&picked, eq((0..10).collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn roulette_wheel_selects_uniformly_if_all_fitness_values_are_zero() {
+        let population = evaluated_population(vec![0, 0, 0, 0]);
+        let selector = RouletteWheelSelector::new(1.0, 2);
+
+        let counter = count_selections(&selector, &population);
+
+        for count in &counter {
+            expect_that!(count, is(greater_than(160)));
+            expect_that!(count, is(less_than(240)));
+        }
+    }
 }
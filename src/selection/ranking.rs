@@ -1 +1,508 @@
+//! The `ranking` module provides `operator::SelectionOp`s that select
+//! individuals according to their rank within the population rather than
+//! their raw `genetic::Fitness` value. Ranking based selection is insensitive
+//! to the scaling of the fitness values and therefore prevents single
+//! dominating individuals from taking over the population too early.
+//!
+//! The provided `SelectionOp` implementations are:
+//! * `LinearRankingSelector`
+//! * `RankTournamentSelector`
 
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{Fitness, Genotype, Parents},
+    operator::{
+        validate_count, validate_ratio, GeneticOperator, MultiObjective, SelectionOp,
+        SingleObjective,
+    },
+    random::{random_index, random_probability, Rng, SliceRandom},
+};
+
+/// The `LinearRankingSelector` implements linear ranking selection. The
+/// individuals are sorted by their fitness and selected randomly with a
+/// probability that depends linearly on their rank.
+///
+/// The `selective_pressure` controls the spread of the probabilities. It must
+/// be in the range between 1.0 and 2.0 (inclusive). In a population of size
+/// `n` the worst individual is selected with a probability of
+/// `(2 - selective_pressure) / n` and the best individual is selected with a
+/// probability of `selective_pressure / n`. A selective pressure of 1.0 means
+/// that all individuals are selected with the same probability.
+///
+/// By default individuals with higher fitness values are ranked better. If the
+/// `minimize` flag is set individuals with lower fitness values are ranked
+/// better and the `ga::GeneticAlgorithm` minimizes the fitness values, see
+/// `operator::SelectionOp::is_minimize`.
+///
+/// This `LinearRankingSelector` can be used for single-objective fitness
+/// values as well as multi-objective fitness values.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearRankingSelector {
+    /// The fraction of number of parents to select in relation to the
+    /// number of individuals in the population.
+    selection_ratio: f64,
+    /// The number of individuals per parents.
+    num_individuals_per_parents: usize,
+    /// The selective pressure in the range of 1.0 to 2.0 (inclusive).
+    selective_pressure: f64,
+    /// Whether lower fitness values are ranked better than higher ones.
+    minimize: bool,
+}
+
+impl LinearRankingSelector {
+    /// Constructs a new instance of the `LinearRankingSelector`.
+    pub fn new(
+        selection_ratio: f64,
+        num_individuals_per_parents: usize,
+        selective_pressure: f64,
+        minimize: bool,
+    ) -> Self {
+        LinearRankingSelector {
+            selection_ratio,
+            num_individuals_per_parents,
+            selective_pressure,
+            minimize,
+        }
+    }
+
+    /// Returns the selection ratio.
+    ///
+    /// The selection ratio is the fraction of number of parents that are
+    /// selected on every call of the `select_from` function and the number
+    /// of individuals in the population.
+    pub fn selection_ratio(&self) -> f64 {
+        self.selection_ratio
+    }
+
+    /// Sets the selection ratio to a new value.
+    ///
+    /// The selection ratio is the fraction of number of parents that are
+    /// selected on every call of the `select_from` function and the number
+    /// of individuals in the population.
+    pub fn set_selection_ratio(&mut self, value: f64) {
+        self.selection_ratio = value;
+    }
+
+    /// Returns the number of individuals per parents use by this selector.
+    pub fn num_individuals_per_parents(&self) -> usize {
+        self.num_individuals_per_parents
+    }
+
+    /// Sets the number of individuals per parents to the given value.
+    pub fn set_num_individuals_per_parents(&mut self, value: usize) {
+        self.num_individuals_per_parents = value;
+    }
+
+    /// Returns the selective pressure.
+    pub fn selective_pressure(&self) -> f64 {
+        self.selective_pressure
+    }
+
+    /// Sets the selective pressure to the given value. The value must be
+    /// between 1.0 and 2.0 (inclusive).
+    pub fn set_selective_pressure(&mut self, value: f64) {
+        self.selective_pressure = value;
+    }
+
+    /// Returns whether lower fitness values are ranked better.
+    pub fn is_minimize(&self) -> bool {
+        self.minimize
+    }
+
+    /// Sets whether lower fitness values are ranked better.
+    pub fn set_minimize(&mut self, value: bool) {
+        self.minimize = value;
+    }
+}
+
+/// Can be used for single-objective optimization
+impl SingleObjective for LinearRankingSelector {}
+/// Can be used for multi-objective optimization
+impl MultiObjective for LinearRankingSelector {}
+
+impl GeneticOperator for LinearRankingSelector {
+    fn name() -> String {
+        "Linear-Ranking-Selection".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("selection ratio", self.selection_ratio)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )
+    }
+}
+
+impl<G, F> SelectionOp<G, F> for LinearRankingSelector
+where
+    G: Genotype,
+    F: Fitness,
+{
+    fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, rng: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let individuals = evaluated.individuals();
+        let fitness_values = evaluated.fitness_values();
+
+        // ranking holds indices to the individuals sorted from worst to best
+        let mut ranking: Vec<usize> = (0..fitness_values.len()).collect();
+        if self.minimize {
+            ranking.sort_by(|x, y| fitness_values[*y].cmp(&fitness_values[*x]));
+        } else {
+            ranking.sort_by(|x, y| fitness_values[*x].cmp(&fitness_values[*y]));
+        }
+        let ranking = ranking;
+
+        // cumulative distribution of the rank probabilities
+        let mut cumulative = linear_rank_probabilities(ranking.len(), self.selective_pressure);
+        let mut sum = 0.;
+        for probability in cumulative.iter_mut() {
+            sum += *probability;
+            *probability = sum;
+        }
+
+        let num_parents_to_select =
+            (individuals.len() as f64 * self.selection_ratio + 0.5).floor() as usize;
+        let mut selected: Vec<Parents<G>> = Vec::with_capacity(num_parents_to_select);
+        for _ in 0..num_parents_to_select {
+            let mut tuple = Vec::with_capacity(self.num_individuals_per_parents);
+            for _ in 0..self.num_individuals_per_parents {
+                let pointer = random_probability(rng) * sum;
+                // when rounding errors occur, take the best ranked individual
+                let rank = cumulative
+                    .partition_point(|probability| *probability < pointer)
+                    .min(ranking.len() - 1);
+                tuple.push(evaluated.clone_individual(ranking[rank]));
+            }
+            selected.push(tuple);
+        }
+        selected
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
+
+    fn is_minimize(&self) -> bool {
+        self.minimize
+    }
+}
+
+/// The `RankTournamentSelector` runs tournaments on the ranks of the
+/// individuals instead of their raw fitness values.
+///
+/// Before each selection the individuals are ranked by their fitness.
+/// Individuals with equal fitness values get distinct ranks in a random
+/// order, using the supplied random number generator. Thus the ties are
+/// broken the same way for the same seed. Each tournament draws
+/// `tournament_size` ranks at random, with replacement, and the individual
+/// with the best drawn rank wins.
+///
+/// As only the ranks take part in the tournaments, the selective pressure
+/// depends on the tournament size alone and not on the scale of the fitness
+/// values.
+///
+/// This `RankTournamentSelector` can be used for single-objective fitness
+/// values as well as multi-objective fitness values.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankTournamentSelector {
+    /// The number of participants on each tournament.
+    tournament_size: usize,
+    /// The number of parents to select.
+    num_parents: usize,
+    /// The number of individuals per parents.
+    num_individuals_per_parents: usize,
+}
+
+impl RankTournamentSelector {
+    /// Constructs a new instance of the `RankTournamentSelector` that selects
+    /// `num_parents` parents of `num_individuals_per_parents` individuals
+    /// each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `tournament_size` is 0.
+    pub fn new(
+        tournament_size: usize,
+        num_parents: usize,
+        num_individuals_per_parents: usize,
+    ) -> Self {
+        assert!(
+            tournament_size > 0,
+            "the tournament size must be greater than 0"
+        );
+        RankTournamentSelector {
+            tournament_size,
+            num_parents,
+            num_individuals_per_parents,
+        }
+    }
+
+    /// Returns the size of one tournament.
+    pub fn tournament_size(&self) -> usize {
+        self.tournament_size
+    }
+
+    /// Sets the size of one tournament to a given value. The value must be
+    /// a positive integer greater 0.
+    pub fn set_tournament_size(&mut self, value: usize) {
+        self.tournament_size = value;
+    }
+
+    /// Returns the number of parents that are selected on every call of the
+    /// `select_from` function.
+    pub fn num_parents(&self) -> usize {
+        self.num_parents
+    }
+
+    /// Sets the number of parents that are selected on every call of the
+    /// `select_from` function.
+    pub fn set_num_parents(&mut self, value: usize) {
+        self.num_parents = value;
+    }
+
+    /// Returns the number of individuals per parents use by this selector.
+    pub fn num_individuals_per_parents(&self) -> usize {
+        self.num_individuals_per_parents
+    }
+
+    /// Sets the number of individuals per parents to the given value.
+    pub fn set_num_individuals_per_parents(&mut self, value: usize) {
+        self.num_individuals_per_parents = value;
+    }
+}
+
+/// Can be used for single-objective optimization
+impl SingleObjective for RankTournamentSelector {}
+/// Can be used for multi-objective optimization
+impl MultiObjective for RankTournamentSelector {}
+
+impl GeneticOperator for RankTournamentSelector {
+    fn name() -> String {
+        "Rank-Tournament-Selection".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_count::<Self>("tournament size", self.tournament_size)?;
+        validate_count::<Self>("number of parents", self.num_parents)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )
+    }
+}
+
+impl<G, F> SelectionOp<G, F> for RankTournamentSelector
+where
+    G: Genotype,
+    F: Fitness,
+{
+    fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, rng: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let individuals = evaluated.individuals();
+        let fitness_values = evaluated.fitness_values();
+        if individuals.is_empty() {
+            return Vec::new();
+        }
+
+        // ranking holds indices to the individuals sorted from worst to best,
+        // shuffling them before the stable sort breaks ties randomly
+        let mut ranking: Vec<usize> = (0..fitness_values.len()).collect();
+        ranking.shuffle(rng);
+        ranking.sort_by(|x, y| fitness_values[*x].cmp(&fitness_values[*y]));
+        let ranking = ranking;
+
+        let mut selected: Vec<Parents<G>> = Vec::with_capacity(self.num_parents);
+        for _ in 0..self.num_parents {
+            let mut tuple = Vec::with_capacity(self.num_individuals_per_parents);
+            for _ in 0..self.num_individuals_per_parents {
+                let best_rank = (0..self.tournament_size)
+                    .map(|_| random_index(rng, ranking.len()))
+                    .max()
+                    .expect("the tournament size is greater than 0");
+                tuple.push(evaluated.clone_individual(ranking[best_rank]));
+            }
+            selected.push(tuple);
+        }
+        selected
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
+}
+
+/// Calculates the selection probabilities of a population of the given size
+/// for linear ranking with the given selective pressure.
+///
+/// The returned probabilities are ordered from worst rank to best rank.
+fn linear_rank_probabilities(size: usize, selective_pressure: f64) -> Vec<f64> {
+    match size {
+        0 => Vec::new(),
+        1 => vec![1.],
+        _ => {
+            let n = size as f64;
+            (0..size)
+                .map(|rank| {
+                    (2. - selective_pressure
+                        + 2. * (selective_pressure - 1.) * rank as f64 / (n - 1.))
+                        / n
+                })
+                .collect()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::indexed_population, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn linear_rank_probabilities_sum_up_to_1() {
+        for pressure in &[1.0, 1.2, 1.5, 1.9, 2.0] {
+            let probabilities = linear_rank_probabilities(17, *pressure);
+
+            let sum: f64 = probabilities.iter().sum();
+            expect_that!(&(sum - 1.).abs(), less_than(1e-9));
+        }
+    }
+
+    #[test]
+    fn linear_rank_probabilities_range_from_2_minus_pressure_to_pressure() {
+        let probabilities = linear_rank_probabilities(10, 1.8);
+
+        expect_that!(&(probabilities[0] - 0.2 / 10.).abs(), less_than(1e-9));
+        expect_that!(&(probabilities[9] - 1.8 / 10.).abs(), less_than(1e-9));
+        for i in 1..10 {
+            expect_that!(&probabilities[i], greater_than(probabilities[i - 1]));
+        }
+    }
+
+    #[test]
+    fn linear_rank_probabilities_with_pressure_1_are_uniform() {
+        let probabilities = linear_rank_probabilities(8, 1.0);
+
+        for probability in probabilities {
+            expect_that!(&(probability - 1. / 8.).abs(), less_than(1e-9));
+        }
+    }
+
+    fn count_selections(selector: &LinearRankingSelector) -> Vec<usize> {
+        // fitness values are scaled badly to show that only the rank counts
+        let fitness_values: Vec<u64> = vec![1, 1_000, 10, 1_000_000];
+        let population = indexed_population(fitness_values, 250_252);
+        let mut rng = get_rng([42; 32]);
+        let mut counter = vec![0; 4];
+        for _ in 0..1_000 {
+            for parents in selector.select_from(&population, &mut rng) {
+                for genome in parents {
+                    counter[genome[0]] += 1;
+                }
+            }
+        }
+        counter
+    }
+
+    #[test]
+    fn linear_ranking_selects_by_rank() {
+        let selector = LinearRankingSelector::new(1.0, 2, 2.0, false);
+
+        let counter = count_selections(&selector);
+
+        // probabilities by rank are 0, 1/6, 2/6 and 3/6 of 8_000 picks
+        expect_that!(&counter[0], eq(0));
+        expect_that!(&counter[2], is(greater_than(1_200)));
+        expect_that!(&counter[2], is(less_than(1_470)));
+        expect_that!(&counter[1], is(greater_than(2_530)));
+        expect_that!(&counter[1], is(less_than(2_800)));
+        expect_that!(&counter[3], is(greater_than(3_860)));
+        expect_that!(&counter[3], is(less_than(4_140)));
+    }
+
+    #[test]
+    fn linear_ranking_selects_by_inverse_rank_when_minimizing() {
+        let selector = LinearRankingSelector::new(1.0, 2, 2.0, true);
+
+        let counter = count_selections(&selector);
+
+        expect_that!(&counter[3], eq(0));
+        expect_that!(&counter[0], is(greater_than(3_860)));
+        expect_that!(&counter[0], is(less_than(4_140)));
+    }
+
+    #[test]
+    fn linear_ranking_with_pressure_1_selects_uniformly() {
+        let selector = LinearRankingSelector::new(1.0, 2, 1.0, false);
+
+        let counter = count_selections(&selector);
+
+        for count in counter {
+            expect_that!(&count, is(greater_than(1_850)));
+            expect_that!(&count, is(less_than(2_150)));
+        }
+    }
+
+    fn count_rank_tournament_selections(fitness_values: Vec<u64>) -> Vec<usize> {
+        let selector = RankTournamentSelector::new(2, 100, 2);
+        let population = indexed_population(fitness_values, 0);
+        let mut rng = get_rng([42; 32]);
+        let mut counter = vec![0; 4];
+        for _ in 0..20 {
+            for parents in selector.select_from(&population, &mut rng) {
+                for genome in parents {
+                    counter[genome[0]] += 1;
+                }
+            }
+        }
+        counter
+    }
+
+    #[test]
+    fn rank_tournament_selects_by_rank() {
+        let counter = count_rank_tournament_selections(vec![1, 1_000, 10, 1_000_000]);
+
+        // the best of 2 drawn ranks wins with probabilities 1/16, 3/16,
+        // 5/16 and 7/16 of 4_000 picks
+        expect_that!(&counter[0], is(greater_than(170)));
+        expect_that!(&counter[0], is(less_than(330)));
+        expect_that!(&counter[2], is(greater_than(630)));
+        expect_that!(&counter[2], is(less_than(870)));
+        expect_that!(&counter[1], is(greater_than(1_100)));
+        expect_that!(&counter[1], is(less_than(1_400)));
+        expect_that!(&counter[3], is(greater_than(1_600)));
+        expect_that!(&counter[3], is(less_than(1_900)));
+    }
+
+    #[test]
+    fn rank_tournament_selection_does_not_change_when_fitness_values_are_scaled() {
+        let fitness_values: Vec<u64> = vec![3, 1, 4, 1];
+        let scaled: Vec<u64> = fitness_values
+            .iter()
+            .map(|fitness| fitness * 1_000)
+            .collect();
+
+        let counter = count_rank_tournament_selections(fitness_values);
+        let scaled_counter = count_rank_tournament_selections(scaled);
+
+        expect_that!(&scaled_counter, eq(counter));
+    }
+
+    #[test]
+    fn rank_tournament_breaks_ties_randomly() {
+        let counter = count_rank_tournament_selections(vec![5, 5, 5, 5]);
+
+        for count in counter {
+            expect_that!(&count, is(greater_than(850)));
+            expect_that!(&count, is(less_than(1_150)));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! The `scaling` module provides fitness scaling methods that are applied to
+//! the scalar fitness values of a population before fitness proportionate
+//! selection.
+//!
+//! Plain fitness proportionate selection loses selection pressure when the
+//! population converges and the differences between the fitness values get
+//! small compared to their absolute values. Fitness scaling transforms the
+//! fitness values into weights, which keep the selection pressure roughly
+//! constant across generations.
+//!
+//! The provided fitness scaling methods are:
+//! * `NoScaling` - uses the scalar fitness values as they are.
+//! * `SigmaScaling` - scales the fitness values relative to the mean and the
+//!   standard deviation of the population.
+
+use std::fmt::Debug;
+
+/// A `FitnessScaling` transforms the scalar fitness values of a population
+/// into the weights used by fitness proportionate selection.
+pub trait FitnessScaling: Clone + Debug {
+    /// Returns the scaled values for the given scalar fitness values. The
+    /// returned values are non-negative and have the same order as the given
+    /// fitness values.
+    fn scale(&self, values: &[f64]) -> Vec<f64>;
+}
+
+/// The `NoScaling` uses the scalar fitness values unchanged as weights for
+/// the selection.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NoScaling;
+
+impl FitnessScaling for NoScaling {
+    fn scale(&self, values: &[f64]) -> Vec<f64> {
+        values.to_vec()
+    }
+}
+
+/// The `SigmaScaling` scales each fitness value `f` to
+/// `1 + (f - mean) / (c * sigma)`, where `mean` is the mean and `sigma` the
+/// standard deviation of the fitness values of the population. Scaled values
+/// below zero are clamped to zero.
+///
+/// The scaling coefficient `c` is usually 2. If all fitness values are equal
+/// (`sigma` is zero) every value is scaled to 1, so that all individuals are
+/// selected with the same probability.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SigmaScaling {
+    /// The coefficient `c` that the standard deviation is multiplied with.
+    coefficient: f64,
+}
+
+impl SigmaScaling {
+    /// Constructs a new instance of `SigmaScaling` with a scaling coefficient
+    /// of 2.
+    pub fn new() -> Self {
+        SigmaScaling { coefficient: 2. }
+    }
+
+    /// Constructs a new instance of `SigmaScaling` with the given scaling
+    /// coefficient.
+    pub fn with_coefficient(coefficient: f64) -> Self {
+        SigmaScaling { coefficient }
+    }
+
+    /// Returns the scaling coefficient.
+    pub fn coefficient(&self) -> f64 {
+        self.coefficient
+    }
+
+    /// Sets the scaling coefficient to a new value.
+    pub fn set_coefficient(&mut self, value: f64) {
+        self.coefficient = value;
+    }
+}
+
+impl Default for SigmaScaling {
+    fn default() -> Self {
+        SigmaScaling::new()
+    }
+}
+
+impl FitnessScaling for SigmaScaling {
+    fn scale(&self, values: &[f64]) -> Vec<f64> {
+        if values.is_empty() {
+            return Vec::new();
+        }
+        let num_values = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / num_values;
+        let variance = values
+            .iter()
+            .map(|value| (value - mean) * (value - mean))
+            .sum::<f64>()
+            / num_values;
+        let sigma = variance.sqrt();
+        if sigma == 0. || !sigma.is_finite() {
+            // no value is preferred over any other
+            return vec![1.; values.len()];
+        }
+        values
+            .iter()
+            .map(|value| (1. + (value - mean) / (self.coefficient * sigma)).max(0.))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use galvanic_assert::matchers::*;
+
+    #[test]
+    fn sigma_scaling_scales_relative_to_mean_and_standard_deviation() {
+        // mean = 5, sigma = 2
+        let scaled = SigmaScaling::new().scale(&[2., 4., 4., 4., 5., 5., 7., 9.]);
+
+        expect_that!(&scaled, eq(vec![0.25, 0.75, 0.75, 0.75, 1., 1., 1.5, 2.]));
+    }
+
+    #[test]
+    fn sigma_scaled_values_are_non_negative_and_mean_centered() {
+        let values = [1000., 1001., 1002., 1003., 1004., 1010.];
+
+        let scaled = SigmaScaling::new().scale(&values);
+
+        for value in &scaled {
+            expect_that!(value, is(greater_than_or_equal(0.)));
+        }
+        let mean = scaled.iter().sum::<f64>() / scaled.len() as f64;
+        expect_that!(&mean, is(close_to(1., 1e-9)));
+    }
+
+    #[test]
+    fn sigma_scaling_clamps_values_far_below_the_mean_to_zero() {
+        let scaled = SigmaScaling::new().scale(&[0., 100., 100., 100., 100., 100., 100., 100.]);
+
+        expect_that!(&scaled[0], eq(0.));
+        for value in &scaled[1..] {
+            expect_that!(value, is(greater_than(1.)));
+        }
+    }
+
+    #[test]
+    fn sigma_scaling_weights_all_values_equally_if_all_values_are_equal() {
+        let scaled = SigmaScaling::new().scale(&[3., 3., 3., 3.]);
+
+        expect_that!(&scaled, eq(vec![1., 1., 1., 1.]));
+    }
+}
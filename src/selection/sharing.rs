@@ -0,0 +1,300 @@
+//! The `sharing` module provides fitness sharing, a niching method that
+//! derates the fitness of individuals in crowded regions of the search
+//! space.
+//!
+//! The fitness of each individual is divided by its niche count, which
+//! measures how many individuals are located within the sharing radius
+//! around it. Individuals that share a peak with many others get a lower
+//! shared fitness than individuals on a less populated peak. Thus the
+//! population spreads over several optima instead of converging to a single
+//! one.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::{AsScalar, Fitness, GenomeDistance, Genotype, Parents},
+    operator::{GenerationSignal, GeneticOperator, SelectionOp, SingleObjective},
+    random::Rng,
+};
+use std::cmp::Ordering;
+
+/// The `SharedFitness` is the fitness of an individual divided by its niche
+/// count as calculated by the `FitnessSharing`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SharedFitness(f64);
+
+impl SharedFitness {
+    /// Returns the shared fitness value.
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl Eq for SharedFitness {}
+
+impl PartialOrd for SharedFitness {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SharedFitness {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Fitness for SharedFitness {
+    fn zero() -> Self {
+        SharedFitness(0.)
+    }
+
+    fn abs_diff(&self, other: &Self) -> Self {
+        SharedFitness((self.0 - other.0).abs())
+    }
+}
+
+impl AsScalar for SharedFitness {
+    fn as_scalar(&self) -> f64 {
+        self.0
+    }
+}
+
+/// The `FitnessSharing` wraps another `SelectionOp` and lets it select the
+/// parents according to the shared fitness values of the individuals.
+///
+/// The shared fitness of individual `i` is `f_i / m_i`, where `f_i` is the
+/// scalar fitness value and `m_i = sum(sh(d_ij))` is the niche count over all
+/// individuals `j` of the population. The distance `d_ij` is measured by the
+/// `GenomeDistance`. The triangular sharing function
+/// `sh(d) = 1 - d / sigma_share` for `d < sigma_share` and `sh(d) = 0`
+/// otherwise is used. As each individual is at distance 0 to itself the
+/// niche count is at least 1, so that an individual that is alone in its
+/// niche keeps its fitness unchanged.
+///
+/// The shared fitness values are only seen by the wrapped `SelectionOp`,
+/// which is usually a fitness proportionate selector like the
+/// `RouletteWheelSelector`. The fitness values reported by the simulation
+/// are not changed.
+///
+/// The niche counts are calculated from the distances of all pairs of
+/// individuals, so the cost of the selection grows quadratically with the
+/// size of the population.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FitnessSharing<S, D> {
+    /// The wrapped `SelectionOp`.
+    selector: S,
+    /// The `GenomeDistance` that measures the distance of two individuals.
+    distance: D,
+    /// The sharing radius.
+    sigma_share: f64,
+}
+
+impl<S, D> FitnessSharing<S, D> {
+    /// Constructs a new instance of `FitnessSharing` that wraps the given
+    /// `SelectionOp` and shares the fitness within the sharing radius
+    /// `sigma_share` as measured by the given `GenomeDistance`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma_share` is not greater than 0.
+    pub fn new(selector: S, distance: D, sigma_share: f64) -> Self {
+        assert!(
+            sigma_share > 0.,
+            "the sharing radius must be greater than 0"
+        );
+        FitnessSharing {
+            selector,
+            distance,
+            sigma_share,
+        }
+    }
+
+    /// Returns the wrapped `SelectionOp`.
+    pub fn selector(&self) -> &S {
+        &self.selector
+    }
+
+    /// Returns the `GenomeDistance` that measures the distance of two
+    /// individuals.
+    pub fn distance(&self) -> &D {
+        &self.distance
+    }
+
+    /// Returns the sharing radius.
+    pub fn sigma_share(&self) -> f64 {
+        self.sigma_share
+    }
+
+    /// Sets the sharing radius. The value must be greater than 0.
+    pub fn set_sigma_share(&mut self, value: f64) {
+        self.sigma_share = value;
+    }
+
+    /// Returns the niche counts of the given individuals.
+    pub fn niche_counts<G>(&self, individuals: &[G]) -> Vec<f64>
+    where
+        G: Genotype,
+        D: GenomeDistance<G>,
+    {
+        let mut niche_counts = vec![1.; individuals.len()];
+        for i in 0..individuals.len() {
+            for j in (i + 1)..individuals.len() {
+                let distance = self.distance.distance(&individuals[i], &individuals[j]);
+                if distance < self.sigma_share {
+                    let sharing = 1. - distance / self.sigma_share;
+                    niche_counts[i] += sharing;
+                    niche_counts[j] += sharing;
+                }
+            }
+        }
+        niche_counts
+    }
+}
+
+impl<S, D> GeneticOperator for FitnessSharing<S, D>
+where
+    S: GeneticOperator,
+    D: Clone,
+{
+    fn name() -> String {
+        format!("Fitness-Sharing-{}", S::name())
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        self.selector.validate()
+    }
+}
+
+/// Can be used for single-objective optimization
+impl<S, D> SingleObjective for FitnessSharing<S, D>
+where
+    S: GeneticOperator,
+    D: Clone,
+{
+}
+
+impl<G, F, S, D> SelectionOp<G, F> for FitnessSharing<S, D>
+where
+    G: Genotype,
+    F: Fitness + AsScalar,
+    S: SelectionOp<G, SharedFitness>,
+    D: GenomeDistance<G>,
+{
+    fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, rng: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let individuals = evaluated.individuals();
+        if individuals.is_empty() {
+            return Vec::new();
+        }
+        let niche_counts = self.niche_counts(&individuals);
+        let shared_values: Vec<SharedFitness> = evaluated
+            .fitness_values()
+            .iter()
+            .zip(niche_counts.iter())
+            .map(|(fitness, niche_count)| SharedFitness(fitness.as_scalar() / niche_count))
+            .collect();
+        let highest = *shared_values.iter().max().expect("not empty");
+        let lowest = *shared_values.iter().min().expect("not empty");
+        let average = SharedFitness(
+            shared_values.iter().map(SharedFitness::value).sum::<f64>()
+                / shared_values.len() as f64,
+        );
+        let mut shared =
+            EvaluatedPopulation::new(individuals, shared_values, highest, lowest, average);
+        if let Some(genome_pool) = evaluated.genome_pool() {
+            shared = shared.with_genome_pool(genome_pool.clone());
+        }
+        self.selector.select_from(&shared, rng)
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        self.selector.parents_size()
+    }
+
+    fn required_population_size(&self) -> Option<usize> {
+        self.selector.required_population_size()
+    }
+
+    fn adapt(&mut self, signal: &GenerationSignal<G>) {
+        self.selector.adapt(signal);
+    }
+
+    fn reset(&mut self) {
+        self.selector.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::evaluated_population, genetic::EuclideanDistance, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    /// Selects every individual once and records the shared fitness values.
+    #[derive(Clone, Debug, PartialEq)]
+    struct SelectAll;
+
+    impl GeneticOperator for SelectAll {
+        fn name() -> String {
+            "Select-All".to_string()
+        }
+    }
+
+    impl SelectionOp<Vec<f64>, SharedFitness> for SelectAll {
+        fn select_from<R>(
+            &self,
+            evaluated: &EvaluatedPopulation<Vec<f64>, SharedFitness>,
+            _: &mut R,
+        ) -> Vec<Parents<Vec<f64>>>
+        where
+            R: Rng + Sized,
+        {
+            // encode the shared fitness into the selected genomes
+            evaluated
+                .fitness_values()
+                .iter()
+                .map(|fitness| vec![vec![fitness.value()]])
+                .collect()
+        }
+    }
+
+    fn shared_values(individuals: Vec<Vec<f64>>, fitness_values: Vec<u32>) -> Vec<f64> {
+        let evaluated = evaluated_population(individuals, fitness_values, 0);
+        let sharing = FitnessSharing::new(SelectAll, EuclideanDistance, 1.);
+        sharing
+            .select_from(&evaluated, &mut get_rng([42; 32]))
+            .into_iter()
+            .map(|parents| parents[0][0])
+            .collect()
+    }
+
+    #[test]
+    fn niche_counts_use_the_triangular_sharing_function() {
+        let sharing = FitnessSharing::new(SelectAll, EuclideanDistance, 1.);
+
+        let niche_counts = sharing.niche_counts(&[vec![0.], vec![0.5], vec![0.75], vec![5.]]);
+
+        expect_that!(&niche_counts, eq(vec![1.75, 2.25, 2., 1.]));
+    }
+
+    #[test]
+    fn fitness_is_divided_by_the_niche_count() {
+        let shared = shared_values(
+            vec![vec![0.], vec![0.], vec![0.5], vec![3.]],
+            vec![10, 10, 30, 8],
+        );
+
+        // niche counts are 2.5, 2.5, 2 and 1
+        expect_that!(&shared, eq(vec![4., 4., 15., 8.]));
+    }
+
+    #[test]
+    fn an_individual_alone_keeps_its_fitness() {
+        let shared = shared_values(vec![vec![1.]], vec![7]);
+
+        expect_that!(&shared, eq(vec![7.]));
+    }
+}
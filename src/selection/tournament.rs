@@ -5,8 +5,12 @@
 
 use crate::{
     algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
     genetic::{Fitness, Genotype, Parents},
-    operator::{GeneticOperator, MultiObjective, SelectionOp, SingleObjective},
+    operator::{
+        validate_count, validate_ratio, GeneticOperator, MultiObjective, SelectionOp,
+        SingleObjective,
+    },
     random::{random_index, random_probability, Rng},
 };
 
@@ -18,17 +22,21 @@ use crate::{
 /// `tournament_size` field. A tournament size of 1 is called 1-way tournament
 /// and is equivalent to random selection.
 ///
-/// The final selection is picked from the best performing participants in each
-/// tournament but with a probability. The probability gives also chances to
-/// the second best, third best and so on. The probability is configurable by
-/// the `probability` field. A probability of 1.0 means the tournament is
-/// deterministic. The best and only the best individual of each tournament is
-/// selected.
+/// Each tournament has exactly one winner, which is picked from the
+/// participants with a probability depending on its rank in the tournament.
+/// The best participant wins with the probability `p`, the second best with
+/// `p(1-p)`, the third best with `p(1-p)^2` and so on. The worst participant
+/// wins with the remaining probability. The probability `p` is configurable
+/// by the `selection_probability` field. A probability of 1.0 means the
+/// tournament is deterministic. The best and only the best individual of each
+/// tournament is selected. Lower probabilities soften the selection pressure,
+/// which can help to avoid premature convergence.
 ///
 /// To avoid that candidates chosen once are selected again they are removed
 /// from the list of candidates. Though this can be configured as well. The
 /// field `remove_selected_individuals` controls whether selected candidates
-/// are removed or not.
+/// are removed or not, i.e. whether the selection is done without or with
+/// replacement.
 ///
 /// This `TournamentSelector` can be used for single-objective fitness values
 /// as well as multi-objective fitness values.
@@ -42,9 +50,9 @@ pub struct TournamentSelector {
     num_individuals_per_parents: usize,
     /// The number of participants on each tournament.
     tournament_size: usize,
-    /// The probability to pick candidates from one tournament.
+    /// The probability that the best participant wins a tournament.
     /// Values must be between 0 and 1.0 (inclusive).
-    probability: f64,
+    selection_probability: f64,
     /// Remove chosen individuals from the list of candidates to avoid that
     /// they can be picked again.
     remove_selected_individuals: bool,
@@ -56,14 +64,14 @@ impl TournamentSelector {
         selection_ratio: f64,
         num_individuals_per_parents: usize,
         tournament_size: usize,
-        probability: f64,
+        selection_probability: f64,
         remove_selected_individuals: bool,
     ) -> Self {
         TournamentSelector {
             selection_ratio,
             num_individuals_per_parents,
             tournament_size,
-            probability,
+            selection_probability,
             remove_selected_individuals,
         }
     }
@@ -110,18 +118,18 @@ impl TournamentSelector {
         self.tournament_size = value;
     }
 
-    /// Returns the probability to pick candidates from one tournament.
-    pub fn probability(&self) -> f64 {
-        self.probability
+    /// Returns the probability that the best participant wins a tournament.
+    pub fn selection_probability(&self) -> f64 {
+        self.selection_probability
     }
 
-    /// Set the probability to pick candidates from one tournament to the given
-    /// value. The value must be between 0 and 1.0 (inclusive).
+    /// Sets the probability that the best participant wins a tournament to
+    /// the given value. The value must be between 0 and 1.0 (inclusive).
     ///
     /// A probability of 1.0 means the tournament is deterministic. The best
     /// and only the best individual of each tournament is selected.
-    pub fn set_probability(&mut self, value: f64) {
-        self.probability = value;
+    pub fn set_selection_probability(&mut self, value: f64) {
+        self.selection_probability = value;
     }
 
     /// Returns whether individuals are removed from the list of candidates
@@ -146,6 +154,16 @@ impl GeneticOperator for TournamentSelector {
     fn name() -> String {
         "Tournament-Selection".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_ratio::<Self>("selection ratio", self.selection_ratio)?;
+        validate_ratio::<Self>("selection probability", self.selection_probability)?;
+        validate_count::<Self>("tournament size", self.tournament_size)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )
+    }
 }
 
 impl<G, F> SelectionOp<G, F> for TournamentSelector
@@ -185,35 +203,127 @@ where
             }
             // sort tournament from best performing to worst performing index
             tournament.sort_by(|x, y| fitness_values[*y].cmp(&fitness_values[*x]));
-            // pick candidates with probability
-            let mut prob = self.probability;
-            let mut prob_redux = 1.;
-            while prob > 0. {
-                if random_probability(rng) <= prob {
-                    let picked = tournament.remove(0);
-                    if self.remove_selected_individuals {
-                        if let Some(position) = mating_pool.iter().position(|x| *x == picked) {
-                            mating_pool.remove(position);
-                        }
-                    }
-                    picked_candidates.push(picked);
-                    count_candidates += 1;
+            // pick the winner with a probability depending on its rank
+            let picked = tournament[winner_rank(tournament.len(), self.selection_probability, rng)];
+            if self.remove_selected_individuals {
+                if let Some(position) = mating_pool.iter().position(|x| *x == picked) {
+                    mating_pool.remove(position);
                 }
-                prob_redux *= 1. - prob;
-                prob *= prob_redux;
             }
+            picked_candidates.push(picked);
+            count_candidates += 1;
         }
+        // only complete tuples of parents can be used for breeding
+        picked_candidates.truncate(
+            picked_candidates.len() - picked_candidates.len() % self.num_individuals_per_parents,
+        );
         // convert selected candidate indices to parents of individuals
-        let mut selected: Vec<Parents<G>> = Vec::with_capacity(num_parents_to_select);
-        while !picked_candidates.is_empty() {
-            let mut tuple = Vec::with_capacity(self.num_individuals_per_parents);
-            for _ in 0..self.num_individuals_per_parents {
-                // index into individuals slice
-                let index_i = picked_candidates.remove(0);
-                tuple.push(individuals[index_i].clone());
+        picked_candidates
+            .chunks(self.num_individuals_per_parents)
+            .map(|tuple| {
+                tuple
+                    .iter()
+                    .map(|index_i| evaluated.clone_individual(*index_i))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
+}
+
+/// Returns the rank of the winner of a tournament with the given number of
+/// participants. The best participant has rank 0. Rank `i` wins with the
+/// probability `p(1-p)^i`; the last rank wins with the remaining probability.
+fn winner_rank<R>(num_participants: usize, probability: f64, rng: &mut R) -> usize
+where
+    R: Rng + Sized,
+{
+    let last_rank = num_participants - 1;
+    (0..last_rank)
+        .find(|_| random_probability(rng) < probability)
+        .unwrap_or(last_rank)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::indexed_population, random::get_rng};
+    use galvanic_assert::matchers::*;
+
+    fn evaluated_population(size: usize) -> EvaluatedPopulation<Vec<usize>, usize> {
+        indexed_population((0..size).collect(), size / 2)
+    }
+
+    #[test]
+    fn deterministic_tournament_selects_the_best_participants() {
+        let population = evaluated_population(20);
+        // a big tournament includes the best individual for sure
+        let selector = TournamentSelector::new(0.5, 2, 200, 1.0, false);
+
+        let selected = selector.select_from(&population, &mut get_rng([42; 32]));
+
+        expect_that!(&selected.len(), eq(10));
+        for parents in selected {
+            expect_that!(&parents, eq(vec![vec![19], vec![19]]));
+        }
+    }
+
+    #[test]
+    fn selected_individuals_are_not_picked_again_if_removed() {
+        let population = evaluated_population(20);
+        let selector = TournamentSelector::new(0.5, 2, 20, 1.0, true);
+
+        let selected = selector.select_from(&population, &mut get_rng([42; 32]));
+
+        let mut picked: Vec<usize> = selected.iter().flatten().map(|genome| genome[0]).collect();
+        picked.sort_unstable();
+        picked.dedup();
+        expect_that!(&picked.len(), eq(20));
+    }
+
+    #[test]
+    fn non_deterministic_tournament_selects_only_complete_parents() {
+        let population = evaluated_population(30);
+        let selector = TournamentSelector::new(0.7, 3, 2, 0.5, true);
+
+        for seed in 0..100 {
+            let selected = selector.select_from(&population, &mut get_rng([seed; 32]));
+
+            expect_that!(&selected.len(), leq(21));
+            for parents in selected {
+                expect_that!(&parents.len(), eq(3));
             }
-            selected.push(tuple);
         }
-        selected
+    }
+
+    #[test]
+    fn winner_rank_is_always_the_best_for_probability_one() {
+        let mut rng = get_rng([42; 32]);
+
+        for _ in 0..1000 {
+            expect_that!(&winner_rank(5, 1.0, &mut rng), eq(0));
+        }
+    }
+
+    #[test]
+    fn winner_ranks_are_distributed_geometrically() {
+        let num_participants = 4;
+        let probability = 0.6;
+        let num_tournaments = 100_000;
+        let mut rng = get_rng([7; 32]);
+
+        let mut counts = vec![0usize; num_participants];
+        for _ in 0..num_tournaments {
+            counts[winner_rank(num_participants, probability, &mut rng)] += 1;
+        }
+
+        let expected = [0.6, 0.6 * 0.4, 0.6 * 0.4 * 0.4, 0.4 * 0.4 * 0.4];
+        for (count, expected) in counts.iter().zip(expected.iter()) {
+            let frequency = *count as f64 / num_tournaments as f64;
+            expect_that!(&frequency, close_to(*expected, 0.01));
+        }
     }
 }
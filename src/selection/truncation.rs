@@ -4,17 +4,28 @@
 //!
 //! The provided `SelectionOp` implementations are:
 //! * `MaximizeSelector`
+//! * `MinimizeSelector`
 
 use crate::{
     algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
     genetic::{Fitness, Genotype, Parents},
-    operator::{GeneticOperator, MultiObjective, SelectionOp, SingleObjective},
+    operator::{
+        validate_count, validate_positive_ratio, GeneticOperator, MultiObjective, SelectionOp,
+        SingleObjective,
+    },
     random::Rng,
 };
+use std::cmp::Ordering;
 
 /// The `MaximizeSelector` selects the best performing `genetic::Genotype`s
 /// from the population.
 ///
+/// The number of selected parents is either determined by a selection ratio
+/// (see `new`) or by an exact count of the best individuals that form the
+/// mating pool (see `with_count`), e.g. the number μ of survivors of a
+/// (μ, λ) or (μ + λ) evolution strategy.
+///
 /// This `MaximizeSelector` can be used for single-objective fitness values
 /// as well as multi-objective fitness values.
 #[allow(missing_copy_implementations)]
@@ -26,7 +37,9 @@ pub struct MaximizeSelector {
     selection_ratio: f64,
     /// The number of individuals per parents.
     num_individuals_per_parents: usize,
-    // phantom types
+    /// The number of the best individuals that form the mating pool. If it
+    /// is set, the selection ratio is ignored.
+    count: Option<usize>,
 }
 
 impl MaximizeSelector {
@@ -35,6 +48,31 @@ impl MaximizeSelector {
         MaximizeSelector {
             selection_ratio,
             num_individuals_per_parents,
+            count: None,
+        }
+    }
+
+    /// Constructs a new instance of the `MaximizeSelector` whose mating pool
+    /// consists of exactly the `count` best individuals of the population.
+    ///
+    /// On every call of the `selection` function `count` parents are
+    /// selected from the mating pool, so that each individual of the mating
+    /// pool is part of `num_individuals_per_parents` parents.
+    ///
+    /// The population must have at least `count` individuals. This is
+    /// validated by the `ga::GeneticAlgorithmBuilder` for the initial
+    /// population.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is 0. Selecting from a population with less than
+    /// `count` individuals panics as well.
+    pub fn with_count(count: usize, num_individuals_per_parents: usize) -> Self {
+        assert!(count > 0, "the count must be greater than 0");
+        MaximizeSelector {
+            selection_ratio: 1.,
+            num_individuals_per_parents,
+            count: Some(count),
         }
     }
 
@@ -65,6 +103,20 @@ impl MaximizeSelector {
     pub fn set_num_individuals_per_parents(&mut self, value: usize) {
         self.num_individuals_per_parents = value;
     }
+
+    /// Returns the number of the best individuals that form the mating
+    /// pool, or `None` if the number of parents is determined by the
+    /// selection ratio.
+    pub fn count(&self) -> Option<usize> {
+        self.count
+    }
+
+    /// Sets the number of the best individuals that form the mating pool.
+    /// If it is `None` the number of parents is determined by the selection
+    /// ratio.
+    pub fn set_count(&mut self, value: Option<usize>) {
+        self.count = value;
+    }
 }
 
 /// Can be used for single-objective optimization
@@ -76,6 +128,18 @@ impl GeneticOperator for MaximizeSelector {
     fn name() -> String {
         "Maximizing-Truncation-Selection".to_string()
     }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_positive_ratio::<Self>("selection ratio", self.selection_ratio)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )?;
+        if let Some(count) = self.count {
+            validate_count::<Self>("count", count)?;
+        }
+        Ok(())
+    }
 }
 
 impl<G, F> SelectionOp<G, F> for MaximizeSelector
@@ -87,33 +151,272 @@ where
     where
         R: Rng + Sized,
     {
-        let individuals = evaluated.individuals();
-        let fitness_values = evaluated.fitness_values();
-
-        // mating pool holds indices to the individuals and fitness_values slices
-        let mut mating_pool: Vec<usize> = (0..fitness_values.len()).collect();
-        // sort mating pool from best performing to worst performing index
-        mating_pool.sort_by(|x, y| fitness_values[*y].cmp(&fitness_values[*x]));
-        let mating_pool = mating_pool;
-
-        let num_parents_to_select =
-            (individuals.len() as f64 * self.selection_ratio + 0.5).floor() as usize;
-        let pool_size = mating_pool.len();
-        let mut selected: Vec<Parents<G>> = Vec::with_capacity(num_parents_to_select);
-
-        let mut index_m = 0;
-        for _ in 0..num_parents_to_select {
-            let mut tuple = Vec::with_capacity(self.num_individuals_per_parents);
-            for _ in 0..self.num_individuals_per_parents {
-                // index into mating pool
-                index_m %= pool_size;
-                // index into individuals slice
-                let index_i = mating_pool[index_m];
-                tuple.push(individuals[index_i].clone());
-                index_m += 1;
-            }
-            selected.push(tuple);
+        let population_size = evaluated.individuals().len();
+        let (num_parents, pool_size) = match self.count {
+            Some(count) => {
+                assert!(
+                    count <= population_size,
+                    "the count {} is greater than the size of the population {}",
+                    count,
+                    population_size
+                );
+                (count, count)
+            },
+            None => (
+                num_parents_for_ratio(population_size, self.selection_ratio),
+                population_size,
+            ),
+        };
+        // sort from best performing (highest) to worst performing (lowest)
+        select_truncated(
+            evaluated,
+            num_parents,
+            pool_size,
+            self.num_individuals_per_parents,
+            |x, y| y.cmp(x),
+        )
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
+
+    fn required_population_size(&self) -> Option<usize> {
+        self.count
+    }
+}
+
+/// The `MinimizeSelector` selects the `genetic::Genotype`s with the lowest
+/// fitness values from the population.
+///
+/// This is the counterpart of the `MaximizeSelector` for minimization
+/// problems, e.g. minimizing the length of a path. A `ga::GeneticAlgorithm`
+/// with a `MinimizeSelector` minimizes the fitness values: the best solution
+/// of a generation is the individual with the lowest fitness value and the
+/// reinsertion and the termination conditions prefer lower fitness values as
+/// well, see `operator::SelectionOp::is_minimize`.
+///
+/// This `MinimizeSelector` can be used for single-objective fitness values
+/// as well as multi-objective fitness values.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimizeSelector {
+    /// The truncation threshold is the ratio between the number of parents
+    /// to be selected and the size of the population:
+    /// threshold = number of parents / size of population
+    selection_ratio: f64,
+    /// The number of individuals per parents.
+    num_individuals_per_parents: usize,
+}
+
+impl MinimizeSelector {
+    /// Constructs a new instance of the `MinimizeSelector`.
+    pub fn new(selection_ratio: f64, num_individuals_per_parents: usize) -> Self {
+        MinimizeSelector {
+            selection_ratio,
+            num_individuals_per_parents,
+        }
+    }
+
+    /// Returns the selection ratio.
+    ///
+    /// The selection ratio is the fraction of number of parents that are
+    /// selected on every call of the `selection` function and the number
+    /// of individuals in the population.
+    pub fn selection_ratio(&self) -> f64 {
+        self.selection_ratio
+    }
+
+    /// Sets the selection ratio to a new value.
+    ///
+    /// The selection ratio is the fraction of number of parents that are
+    /// selected on every call of the `selection` function and the number
+    /// of individuals in the population.
+    pub fn set_selection_ratio(&mut self, value: f64) {
+        self.selection_ratio = value;
+    }
+
+    /// Returns the number of individuals per parents use by this selector.
+    pub fn num_individuals_per_parents(&self) -> usize {
+        self.num_individuals_per_parents
+    }
+
+    /// Sets the number of individuals per parents to the given value.
+    pub fn set_num_individuals_per_parents(&mut self, value: usize) {
+        self.num_individuals_per_parents = value;
+    }
+}
+
+/// Can be used for single-objective optimization
+impl SingleObjective for MinimizeSelector {}
+/// Can be used for multi-objective optimization
+impl MultiObjective for MinimizeSelector {}
+
+impl GeneticOperator for MinimizeSelector {
+    fn name() -> String {
+        "Minimizing-Truncation-Selection".to_string()
+    }
+
+    fn validate(&self) -> Result<(), GeneticAlgorithmError> {
+        validate_positive_ratio::<Self>("selection ratio", self.selection_ratio)?;
+        validate_count::<Self>(
+            "number of individuals per parents",
+            self.num_individuals_per_parents,
+        )
+    }
+}
+
+impl<G, F> SelectionOp<G, F> for MinimizeSelector
+where
+    G: Genotype,
+    F: Fitness,
+{
+    fn select_from<R>(&self, evaluated: &EvaluatedPopulation<G, F>, _: &mut R) -> Vec<Parents<G>>
+    where
+        R: Rng + Sized,
+    {
+        let population_size = evaluated.individuals().len();
+        // sort from best performing (lowest) to worst performing (highest)
+        select_truncated(
+            evaluated,
+            num_parents_for_ratio(population_size, self.selection_ratio),
+            population_size,
+            self.num_individuals_per_parents,
+            |x, y| x.cmp(y),
+        )
+    }
+
+    fn parents_size(&self) -> Option<usize> {
+        Some(self.num_individuals_per_parents)
+    }
+
+    fn is_minimize(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the number of parents to select for the given selection ratio.
+fn num_parents_for_ratio(population_size: usize, selection_ratio: f64) -> usize {
+    (population_size as f64 * selection_ratio + 0.5).floor() as usize
+}
+
+/// Selects parents from the individuals of the given population, that are
+/// ranked best according to the given ordering of their fitness values.
+///
+/// The mating pool consists of the `pool_size` best individuals. The
+/// `compare` function must order the fitness values from best performing
+/// to worst performing.
+fn select_truncated<G, F, C>(
+    evaluated: &EvaluatedPopulation<G, F>,
+    num_parents_to_select: usize,
+    pool_size: usize,
+    num_individuals_per_parents: usize,
+    compare: C,
+) -> Vec<Parents<G>>
+where
+    G: Genotype,
+    F: Fitness,
+    C: Fn(&F, &F) -> Ordering,
+{
+    let fitness_values = evaluated.fitness_values();
+
+    // mating pool holds indices to the individuals and fitness_values slices
+    let mut mating_pool: Vec<usize> = (0..fitness_values.len()).collect();
+    // sort mating pool from best performing to worst performing index
+    mating_pool.sort_by(|x, y| compare(&fitness_values[*x], &fitness_values[*y]));
+    mating_pool.truncate(pool_size);
+    let mating_pool = mating_pool;
+
+    let mut selected: Vec<Parents<G>> = Vec::with_capacity(num_parents_to_select);
+
+    let mut index_m = 0;
+    for _ in 0..num_parents_to_select {
+        let mut tuple = Vec::with_capacity(num_individuals_per_parents);
+        for _ in 0..num_individuals_per_parents {
+            // index into mating pool
+            index_m %= pool_size;
+            // index into individuals slice
+            let index_i = mating_pool[index_m];
+            tuple.push(evaluated.clone_individual(index_i));
+            index_m += 1;
         }
-        selected
+        selected.push(tuple);
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::population_of_fitness,
+        random::{get_rng, random_seed},
+    };
+    use galvanic_assert::matchers::*;
+
+    fn evaluated_population() -> EvaluatedPopulation<Vec<usize>, usize> {
+        population_of_fitness(vec![5, 2, 9, 7, 1, 3], 4)
+    }
+
+    #[test]
+    fn maximize_selector_selects_individuals_with_highest_fitness() {
+        let selector = MaximizeSelector::new(0.5, 2);
+
+        let selected = selector.select_from(&evaluated_population(), &mut get_rng(random_seed()));
+
+        expect_that!(
+            &selected,
+            eq(vec![
+                vec![vec![9], vec![7]],
+                vec![vec![5], vec![3]],
+                vec![vec![2], vec![1]]
+            ])
+        );
+    }
+
+    #[test]
+    fn maximize_selector_with_count_selects_exactly_count_distinct_parents() {
+        let selector = MaximizeSelector::with_count(4, 2);
+
+        let selected = selector.select_from(&evaluated_population(), &mut get_rng(random_seed()));
+
+        expect_that!(&selected.len(), eq(4));
+        let mut mating_pool: Vec<Vec<usize>> = selected.into_iter().flatten().collect();
+        mating_pool.sort();
+        mating_pool.dedup();
+        expect_that!(&mating_pool, eq(vec![vec![3], vec![5], vec![7], vec![9]]));
+    }
+
+    #[test]
+    #[should_panic(expected = "the count 7 is greater than the size of the population 6")]
+    fn maximize_selector_with_count_panics_if_the_population_is_too_small() {
+        let selector = MaximizeSelector::with_count(7, 2);
+
+        selector.select_from(&evaluated_population(), &mut get_rng(random_seed()));
+    }
+
+    #[test]
+    fn minimize_selector_selects_individuals_with_lowest_fitness() {
+        let selector = MinimizeSelector::new(0.5, 2);
+
+        let selected = selector.select_from(&evaluated_population(), &mut get_rng(random_seed()));
+
+        expect_that!(
+            &selected,
+            eq(vec![
+                vec![vec![1], vec![2]],
+                vec![vec![3], vec![5]],
+                vec![vec![7], vec![9]]
+            ])
+        );
+    }
+
+    #[test]
+    fn minimize_selector_with_small_ratio_selects_only_the_best() {
+        let selector = MinimizeSelector::new(0.34, 1);
+
+        let selected = selector.select_from(&evaluated_population(), &mut get_rng(random_seed()));
+
+        expect_that!(&selected, eq(vec![vec![vec![1]], vec![vec![2]]]));
     }
 }
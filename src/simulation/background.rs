@@ -0,0 +1,265 @@
+//! The `background` module provides the `AsyncSimulator` which steps a
+//! simulation on its own thread.
+//!
+//! Interactive applications, like a GUI that shows the progress of the
+//! evolution, can start the simulation in the background, poll the results of
+//! the generations from a channel and pause, resume or stop the simulation at
+//! any time.
+
+use crate::{
+    algorithm::Algorithm,
+    simulation::{SimResult, Simulation},
+};
+use std::{
+    fmt::{self, Debug},
+    sync::{
+        mpsc::{self, Receiver, SendError, Sender, SyncSender},
+        Arc, Condvar, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// A result of the simulation as sent by the `AsyncSimulator`.
+pub type AsyncSimResult<A, E> = Result<SimResult<A>, E>;
+
+/// The `AsyncSimulator` steps a `Simulation` on a background thread and
+/// sends the result of each step to a channel.
+///
+/// Each step of the simulation yields one `SimResult::Intermediate` on the
+/// channel. The last result is either a `SimResult::Final` or an error. After
+/// the last result the stepping thread finishes and the channel is
+/// disconnected.
+///
+/// The simulation is built by a factory closure on the background thread.
+/// Thus the simulation itself, including its `SimulationObserver`s, does not
+/// need to be `Send`. Only the results sent over the channel must be `Send`.
+///
+/// By default the channel is unbounded. If the consumer is slower than the
+/// simulation the results pile up in the channel. A simulation spawned by
+/// `spawn_bounded` uses a bounded channel instead. The stepping thread blocks
+/// when the channel is full, until the consumer receives the next result.
+///
+/// Dropping the `AsyncSimulator` stops the simulation and waits for the
+/// stepping thread to finish, like `stop` does.
+pub struct AsyncSimulator<A, E>
+where
+    A: Algorithm,
+{
+    /// The pause and stop flags shared with the stepping thread.
+    control: Arc<Control>,
+    /// The channel on which the stepping thread sends the results.
+    results: Receiver<AsyncSimResult<A, E>>,
+    /// The stepping thread. It is `None` after the thread has been joined.
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<A, E> AsyncSimulator<A, E>
+where
+    A: Algorithm + 'static,
+    <A as Algorithm>::Output: Send,
+    E: Send + 'static,
+{
+    /// Spawns a new thread that builds the simulation by calling the given
+    /// factory closure and steps it until it is finished. The results are
+    /// sent to an unbounded channel.
+    pub fn spawn<S, B>(factory: B) -> Self
+    where
+        S: Simulation<A, Error = E>,
+        B: FnOnce() -> S + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        Self::spawn_with_sender(ResultSender::Unbounded(sender), receiver, factory)
+    }
+
+    /// Spawns a new thread that builds the simulation by calling the given
+    /// factory closure and steps it until it is finished. The results are
+    /// sent to a bounded channel which buffers at most `capacity` results.
+    ///
+    /// With a `capacity` of 0 no results are buffered at all. The stepping
+    /// thread then waits for the consumer to receive each result before it
+    /// processes the next step.
+    pub fn spawn_bounded<S, B>(capacity: usize, factory: B) -> Self
+    where
+        S: Simulation<A, Error = E>,
+        B: FnOnce() -> S + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        Self::spawn_with_sender(ResultSender::Bounded(sender), receiver, factory)
+    }
+
+    fn spawn_with_sender<S, B>(
+        sender: ResultSender<AsyncSimResult<A, E>>,
+        receiver: Receiver<AsyncSimResult<A, E>>,
+        factory: B,
+    ) -> Self
+    where
+        S: Simulation<A, Error = E>,
+        B: FnOnce() -> S + Send + 'static,
+    {
+        let control = Arc::new(Control::default());
+        let thread_control = control.clone();
+        let thread = thread::spawn(move || {
+            let mut simulation = factory();
+            while thread_control.wait_while_paused() {
+                let result = simulation.step();
+                let finished = !matches!(result, Ok(SimResult::Intermediate(_)));
+                if sender.send(result).is_err() || finished {
+                    break;
+                }
+            }
+        });
+        AsyncSimulator {
+            control,
+            results: receiver,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl<A, E> AsyncSimulator<A, E>
+where
+    A: Algorithm,
+{
+    /// Returns the channel on which the results of the simulation are
+    /// received.
+    ///
+    /// Receiving from the channel fails after the last result has been
+    /// received, i.e. after the simulation is finished.
+    pub fn results(&self) -> &Receiver<AsyncSimResult<A, E>> {
+        &self.results
+    }
+
+    /// Pauses the simulation after the current step is finished.
+    pub fn pause(&self) {
+        self.control.set_paused(true);
+    }
+
+    /// Resumes a paused simulation.
+    pub fn resume(&self) {
+        self.control.set_paused(false);
+    }
+
+    /// Returns whether the simulation is paused.
+    pub fn is_paused(&self) -> bool {
+        self.control.state().paused
+    }
+
+    /// Stops the simulation after the current step is finished and waits for
+    /// the stepping thread to finish.
+    ///
+    /// Returns the results that have been sent but not received yet. These
+    /// are at most the capacity of a bounded channel plus the result of the
+    /// step that has been processed while stopping.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stepping thread has panicked, e.g. because the
+    /// simulation panicked, with the payload of that panic.
+    pub fn stop(mut self) -> Vec<AsyncSimResult<A, E>> {
+        let (remaining, outcome) = self.shutdown();
+        if let Err(payload) = outcome {
+            std::panic::resume_unwind(payload);
+        }
+        remaining
+    }
+
+    /// Sets the stop flag, receives all pending results and joins the
+    /// stepping thread.
+    ///
+    /// Receiving the pending results unblocks a stepping thread that waits
+    /// on a full bounded channel, so that it can notice the stop flag.
+    fn shutdown(&mut self) -> (Vec<AsyncSimResult<A, E>>, thread::Result<()>) {
+        self.control.stop();
+        let remaining = self.results.iter().collect();
+        let outcome = match self.thread.take() {
+            Some(thread) => thread.join(),
+            None => Ok(()),
+        };
+        (remaining, outcome)
+    }
+}
+
+impl<A, E> Drop for AsyncSimulator<A, E>
+where
+    A: Algorithm,
+{
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            // a panic of the stepping thread is not propagated while dropping
+            let _ = self.shutdown();
+        }
+    }
+}
+
+impl<A, E> Debug for AsyncSimulator<A, E>
+where
+    A: Algorithm,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.control.state();
+        f.debug_struct("AsyncSimulator")
+            .field("paused", &state.paused)
+            .field("stopped", &state.stopped)
+            .field("running", &self.thread.is_some())
+            .finish()
+    }
+}
+
+/// The sending half of either an unbounded or a bounded channel.
+enum ResultSender<T> {
+    Unbounded(Sender<T>),
+    Bounded(SyncSender<T>),
+}
+
+impl<T> ResultSender<T> {
+    fn send(&self, value: T) -> Result<(), SendError<T>> {
+        match self {
+            ResultSender::Unbounded(sender) => sender.send(value),
+            ResultSender::Bounded(sender) => sender.send(value),
+        }
+    }
+}
+
+/// The flags by which the `AsyncSimulator` controls the stepping thread.
+#[derive(Clone, Copy, Debug, Default)]
+struct ControlState {
+    paused: bool,
+    stopped: bool,
+}
+
+#[derive(Debug, Default)]
+struct Control {
+    state: Mutex<ControlState>,
+    changed: Condvar,
+}
+
+impl Control {
+    fn state(&self) -> ControlState {
+        *self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn update(&self, update: impl FnOnce(&mut ControlState)) {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        update(&mut state);
+        self.changed.notify_all();
+    }
+
+    fn set_paused(&self, paused: bool) {
+        self.update(|state| state.paused = paused);
+    }
+
+    fn stop(&self) {
+        self.update(|state| state.stopped = true);
+    }
+
+    /// Blocks while the simulation is paused. Returns whether the simulation
+    /// shall continue, i.e. `false` if it has been stopped.
+    fn wait_while_paused(&self) -> bool {
+        let state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        let state = self
+            .changed
+            .wait_while(state, |state| state.paused && !state.stopped)
+            .unwrap_or_else(|error| error.into_inner());
+        !state.stopped
+    }
+}
@@ -0,0 +1,235 @@
+//! The `ensemble` module provides the `MultiStartRunner` which runs several
+//! independent simulations of the same problem and keeps the best result.
+//!
+//! Genetic algorithms are stochastic. Restarting the simulation several times
+//! with different random seeds and keeping the best result is a simple way to
+//! make the outcome more reliable.
+
+use crate::{
+    algorithm::{Algorithm, BestFitness},
+    random::{get_rng, Rng, Seed},
+    simulation::{
+        simulator::{SimError, Simulator, SimulatorBuilder},
+        SimResult, Simulation, SimulationBuilder,
+    },
+    statistic::{ProcessingTime, TrackProcessingTime},
+    termination::{StopReason, Termination},
+};
+use chrono::Duration;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use rayon::prelude::*;
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+/// The `MultiStartRunner` runs a number of independent simulations and
+/// returns the best result of all runs.
+///
+/// The simulations are built by a factory closure, which is called with the
+/// index of the run, counted from 0. Each simulation is built with its own
+/// `Seed`. The seeds of the runs are derived from the master seed, so that
+/// running the same ensemble with the same master seed again gives the same
+/// results.
+#[derive(Clone, Debug)]
+pub struct MultiStartRunner<B> {
+    /// The factory closure that creates the `SimulatorBuilder` of a run.
+    factory: B,
+    /// The number of runs.
+    num_runs: usize,
+    /// The seed from which the seeds of the runs are derived.
+    master_seed: Seed,
+}
+
+impl<B> MultiStartRunner<B> {
+    /// Constructs a new `MultiStartRunner` that runs the simulations created
+    /// by the given factory closure `num_runs` times.
+    pub fn new(num_runs: usize, master_seed: Seed, factory: B) -> Self {
+        MultiStartRunner {
+            factory,
+            num_runs,
+            master_seed,
+        }
+    }
+
+    /// Returns the number of runs.
+    pub fn num_runs(&self) -> usize {
+        self.num_runs
+    }
+
+    /// Returns the seed from which the seeds of the runs are derived.
+    pub fn master_seed(&self) -> Seed {
+        self.master_seed
+    }
+
+    /// Returns the seeds of all runs. The seed at index `i` is used to build
+    /// the simulation of run `i`.
+    pub fn seeds(&self) -> Vec<Seed> {
+        let mut rng = get_rng(self.master_seed);
+        (0..self.num_runs)
+            .map(|_| {
+                let mut seed = Seed::default();
+                rng.fill(&mut seed);
+                seed
+            })
+            .collect()
+    }
+
+    /// Runs the simulations one after the other and returns the best result.
+    ///
+    /// If one of the simulations fails the error is returned immediately and
+    /// the remaining simulations are not run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of runs is zero.
+    pub fn run<A, T>(&self) -> Result<EnsembleResult<A>, SimError<A>>
+    where
+        B: Fn(usize) -> SimulatorBuilder<A, T>,
+        A: BestFitness + TrackProcessingTime + Debug,
+        <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+        T: Termination<A>,
+    {
+        let mut results = Vec::with_capacity(self.num_runs);
+        for (run, seed) in self.seeds().into_iter().enumerate() {
+            results.push((seed, self.run_one(run, seed)?));
+        }
+        Ok(EnsembleResult::from_runs(results))
+    }
+
+    /// Runs the simulations in parallel and returns the best result.
+    ///
+    /// The result is the same as the result of `run`. If several simulations
+    /// fail the error of the run with the lowest index is returned.
+    ///
+    /// As the simulations are run on different threads the outputs of the
+    /// algorithm must be `Send`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of runs is zero.
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    pub fn run_parallel<A, T>(&self) -> Result<EnsembleResult<A>, SimError<A>>
+    where
+        B: Fn(usize) -> SimulatorBuilder<A, T> + Sync,
+        A: BestFitness + TrackProcessingTime + Debug,
+        <A as Algorithm>::Output: Send,
+        <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+        T: Termination<A>,
+    {
+        let results: Vec<_> = self
+            .seeds()
+            .into_par_iter()
+            .enumerate()
+            .map(|(run, seed)| self.run_one(run, seed).map(|result| (seed, result)))
+            .collect();
+        let results = results.into_iter().collect::<Result<Vec<_>, _>>()?;
+        Ok(EnsembleResult::from_runs(results))
+    }
+
+    /// Builds and runs the simulation of the given run.
+    fn run_one<A, T>(&self, run: usize, seed: Seed) -> Result<SimResult<A>, SimError<A>>
+    where
+        B: Fn(usize) -> SimulatorBuilder<A, T>,
+        A: BestFitness + TrackProcessingTime + Debug,
+        <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+        T: Termination<A>,
+    {
+        let mut simulation: Simulator<A, T> = (self.factory)(run).build_with_seed(seed);
+        simulation.run()
+    }
+}
+
+/// The `RunSummary` describes the outcome of one run of a
+/// `MultiStartRunner`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunSummary<F> {
+    /// The index of the run, counted from 0.
+    pub run: usize,
+    /// The seed the simulation of this run has been built with.
+    pub seed: Seed,
+    /// The number of the last iteration of this run.
+    pub iterations: u64,
+    /// The fitness of the best solution of the last iteration.
+    pub best_fitness: F,
+    /// The total processing time of this run.
+    pub processing_time: ProcessingTime,
+    /// The total duration of this run.
+    pub duration: Duration,
+    /// The reason why this run stopped.
+    pub stop_reason: StopReason,
+}
+
+/// The `EnsembleResult` is the result of running a `MultiStartRunner`.
+#[derive(Debug, PartialEq)]
+pub struct EnsembleResult<A>
+where
+    A: BestFitness,
+{
+    /// The index of the run with the best result. If several runs found a
+    /// solution with the same fitness the run with the lowest index wins.
+    pub best_run: usize,
+    /// The `SimResult::Final` of the best run.
+    pub best_result: SimResult<A>,
+    /// The summaries of all runs in the order of the runs.
+    pub runs: Vec<RunSummary<<A as BestFitness>::Fitness>>,
+    /// The sum of the total processing times of all runs.
+    pub processing_time: ProcessingTime,
+    /// The sum of the total durations of all runs. If the runs are executed
+    /// in parallel the elapsed time is shorter.
+    pub duration: Duration,
+}
+
+impl<A> EnsembleResult<A>
+where
+    A: BestFitness,
+{
+    /// Aggregates the final results of the runs in the order of the runs.
+    fn from_runs(results: Vec<(Seed, SimResult<A>)>) -> Self {
+        assert!(!results.is_empty(), "a multi start needs at least one run");
+        let mut runs: Vec<RunSummary<<A as BestFitness>::Fitness>> =
+            Vec::with_capacity(results.len());
+        let mut processing_time = ProcessingTime::zero();
+        let mut duration = Duration::zero();
+        let mut best: Option<(usize, SimResult<A>)> = None;
+        for (run, (seed, result)) in results.into_iter().enumerate() {
+            let summary = match &result {
+                SimResult::Final(state, run_processing_time, run_duration, stop_reason) => {
+                    RunSummary {
+                        run,
+                        seed,
+                        iterations: state.iteration,
+                        best_fitness: A::best_fitness_of(&state.result),
+                        processing_time: *run_processing_time,
+                        duration: *run_duration,
+                        stop_reason: stop_reason.clone(),
+                    }
+                },
+                SimResult::Intermediate(_) => {
+                    panic!("a simulation run must return a final result")
+                },
+            };
+            processing_time += summary.processing_time;
+            duration += summary.duration;
+            let is_better = match (&best, &result) {
+                (Some((_, SimResult::Final(best_state, ..))), SimResult::Final(state, ..)) => {
+                    A::compare_best_fitness(&state.result, &best_state.result) == Ordering::Greater
+                },
+                _ => true,
+            };
+            runs.push(summary);
+            if is_better {
+                best = Some((run, result));
+            }
+        }
+        let (best_run, best_result) = best.expect("at least one run has been processed");
+        EnsembleResult {
+            best_run,
+            best_result,
+            runs,
+            processing_time,
+            duration,
+        }
+    }
+}
@@ -0,0 +1,544 @@
+//! The `islands` module provides the `IslandSimulator` which implements the
+//! island model of genetic algorithms.
+//!
+//! In the island model several populations, the islands, evolve independently
+//! of each other. Periodically individuals of each island migrate to other
+//! islands according to a `MigrationTopology`. Which individuals emigrate and
+//! which individuals they replace on the destination islands is defined by a
+//! `MigrationPolicy`. Compared to one large population the island model
+//! better maintains the diversity of the individuals.
+
+use crate::{
+    algorithm::{Algorithm, BestFitness},
+    ga::GeneticAlgorithm,
+    genetic::{Fitness, FitnessFunction, Genotype, GenotypeToPhenotype},
+    operator::{CrossoverOp, MutationOp, ReinsertionOp, SelectionOp},
+    random::{random_index_from_range, Prng},
+    simulation::{
+        simulator::{IterationProcessor, SimError, Simulator},
+        SimResult, Simulation, State,
+    },
+    statistic::{ProcessingTime, TrackProcessingTime},
+    termination::{StopReason, Termination},
+};
+use chrono::Duration;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+use rayon::prelude::*;
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+/// The `MigrationTopology` defines to which islands the migrants of an island
+/// emigrate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MigrationTopology {
+    /// The islands are arranged in a ring. The migrants of each island
+    /// emigrate to the next island in the ring.
+    Ring,
+    /// The migrants of each island emigrate to all other islands.
+    FullyConnected,
+}
+
+/// An `Algorithm` that can exchange individuals with other instances of the
+/// same algorithm.
+///
+/// The islands are compared by the fitness of their best solution as compared
+/// by `algorithm::BestFitness::compare_best_fitness`. The individuals that are
+/// exchanged are chosen by a `MigrationPolicy`.
+pub trait Migration: BestFitness {
+    /// The type of the individuals that migrate.
+    type Genome: Genotype;
+
+    /// Returns the individuals of the current population.
+    fn individuals(&self) -> &[Self::Genome];
+
+    /// Returns the indices of the individuals of the current population
+    /// sorted from the best to the worst performing individual.
+    ///
+    /// A policy may rank the same population several times, e.g. the
+    /// `BestReplaceWorst` policy ranks each island when selecting its
+    /// emigrants and again when integrating its immigrants. Thus the fitness
+    /// of the current population should be evaluated only once until the
+    /// population is replaced.
+    fn ranking(&mut self) -> Vec<usize>;
+
+    /// Replaces individuals of the current population. Each replacement
+    /// consists of the index of the individual to be replaced and the
+    /// individual that takes its place. Thus the size of the population never
+    /// changes.
+    fn replace_individuals(&mut self, replacements: Vec<(usize, Self::Genome)>);
+}
+
+/// A `MigrationPolicy` defines which individuals of an island emigrate and how
+/// the immigrants are integrated into the population of the destination
+/// island.
+pub trait MigrationPolicy: Clone + Debug {
+    /// Selects up to `num_emigrants` distinct individuals of the given island
+    /// that emigrate to other islands.
+    fn select_emigrants<A>(
+        &self,
+        island: &mut A,
+        num_emigrants: usize,
+        rng: &mut Prng,
+    ) -> Vec<A::Genome>
+    where
+        A: Migration;
+
+    /// Integrates the immigrants into the population of the given island.
+    /// Each immigrant replaces a distinct individual of the population. If
+    /// there are more immigrants than individuals the surplus immigrants are
+    /// discarded.
+    fn integrate_immigrants<A>(&self, island: &mut A, immigrants: Vec<A::Genome>, rng: &mut Prng)
+    where
+        A: Migration;
+}
+
+/// The `BestReplaceWorst` policy sends the best individuals of an island to
+/// other islands where they replace the worst individuals.
+///
+/// This is the classic migration policy of the island model. It spreads good
+/// solutions quickly between the islands at the cost of diversity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BestReplaceWorst;
+
+impl MigrationPolicy for BestReplaceWorst {
+    fn select_emigrants<A>(
+        &self,
+        island: &mut A,
+        num_emigrants: usize,
+        _: &mut Prng,
+    ) -> Vec<A::Genome>
+    where
+        A: Migration,
+    {
+        let ranking = island.ranking();
+        let individuals = island.individuals();
+        ranking
+            .into_iter()
+            .take(num_emigrants)
+            .map(|index| individuals[index].clone())
+            .collect()
+    }
+
+    fn integrate_immigrants<A>(&self, island: &mut A, immigrants: Vec<A::Genome>, _: &mut Prng)
+    where
+        A: Migration,
+    {
+        let targets = island.ranking().into_iter().rev();
+        island.replace_individuals(targets.zip(immigrants).collect());
+    }
+}
+
+/// The `RandomReplaceRandom` policy sends randomly chosen individuals of an
+/// island to other islands where they replace randomly chosen individuals.
+///
+/// As the migrants are not chosen by their fitness, good solutions spread
+/// slower than with the `BestReplaceWorst` policy, which preserves the
+/// diversity of the islands. No fitness is evaluated for the migration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RandomReplaceRandom;
+
+impl MigrationPolicy for RandomReplaceRandom {
+    fn select_emigrants<A>(
+        &self,
+        island: &mut A,
+        num_emigrants: usize,
+        rng: &mut Prng,
+    ) -> Vec<A::Genome>
+    where
+        A: Migration,
+    {
+        let individuals = island.individuals();
+        random_distinct_indices(individuals.len(), num_emigrants, rng)
+            .into_iter()
+            .map(|index| individuals[index].clone())
+            .collect()
+    }
+
+    fn integrate_immigrants<A>(&self, island: &mut A, immigrants: Vec<A::Genome>, rng: &mut Prng)
+    where
+        A: Migration,
+    {
+        let targets = random_distinct_indices(island.individuals().len(), immigrants.len(), rng);
+        island.replace_individuals(targets.into_iter().zip(immigrants).collect());
+    }
+}
+
+/// Chooses up to `num_indices` distinct indices out of `0..len` by a partial
+/// shuffle of the indices.
+fn random_distinct_indices(len: usize, num_indices: usize, rng: &mut Prng) -> Vec<usize> {
+    let num_indices = num_indices.min(len);
+    let mut indices: Vec<usize> = (0..len).collect();
+    for i in 0..num_indices {
+        let j = random_index_from_range(rng, i, len);
+        indices.swap(i, j);
+    }
+    indices.truncate(num_indices);
+    indices
+}
+
+impl<G, F, E, S, C, M, R, P, D> Migration for GeneticAlgorithm<G, F, E, S, C, M, R, P, D>
+where
+    G: Genotype,
+    F: Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Sync,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G> + Sync,
+    M: MutationOp<G> + Sync,
+    R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
+{
+    type Genome = G;
+
+    fn individuals(&self) -> &[G] {
+        self.population()
+    }
+
+    /// The fitness of the current population is evaluated when it is ranked
+    /// for the first time. The ranking is kept until the population is
+    /// replaced, e.g. by the next generation or by the immigrants.
+    fn ranking(&mut self) -> Vec<usize> {
+        GeneticAlgorithm::ranking(self)
+    }
+
+    fn replace_individuals(&mut self, replacements: Vec<(usize, G)>) {
+        let mut population = self.population().to_vec();
+        for (index, individual) in replacements {
+            population[index] = individual;
+        }
+        self.set_population(population);
+    }
+}
+
+/// The `IslandSimulator` runs several `Simulator`s, the islands, side by side
+/// and migrates individuals between them.
+///
+/// Each call of `step` processes one generation on every island. After every
+/// `migration_interval` generations `num_migrants` individuals of each island
+/// emigrate to each of its destinations according to the `MigrationTopology`.
+/// The emigrants and the individuals they replace are chosen by the
+/// `MigrationPolicy`, which is `BestReplaceWorst` by default.
+///
+/// The emigrants of an island for all of its destinations are selected at
+/// once and dealt out to the destinations one after the other. Thus no
+/// individual is copied to several destinations as long as the island has
+/// enough individuals, e.g. with the `FullyConnected` topology each
+/// destination receives another one of the best individuals of an island.
+/// The immigrants only replace individuals of the destination, so that the
+/// size of each population stays the same.
+///
+/// The `SimResult` returned by `step` and `run` holds the `State` of the
+/// island with the best solution of the current generation. The simulation
+/// is finished as soon as the termination criteria of one of the islands are
+/// met.
+///
+/// The generations of the islands are processed in parallel if the crate
+/// feature `parallel` is enabled. A simulation run is reproducible if each
+/// island is built with a seed, e.g. by using
+/// `SimulationBuilder::build_with_seed`.
+#[derive(Clone, Debug)]
+pub struct IslandSimulator<A, T, P = BestReplaceWorst>
+where
+    A: Algorithm,
+    T: Termination<A>,
+{
+    islands: Vec<Simulator<A, T>>,
+    migration_interval: u64,
+    num_migrants: usize,
+    topology: MigrationTopology,
+    policy: P,
+    iteration: u64,
+    processing_time: ProcessingTime,
+    duration: Duration,
+}
+
+impl<A, T> IslandSimulator<A, T>
+where
+    A: Algorithm + TrackProcessingTime + Debug + Migration,
+    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+    T: Termination<A>,
+{
+    /// Constructs a new `IslandSimulator` for the given islands that migrates
+    /// individuals by the `BestReplaceWorst` policy.
+    ///
+    /// Returns `SimError::InvalidSimulation` if there are no islands.
+    pub fn new(
+        islands: Vec<Simulator<A, T>>,
+        migration_interval: u64,
+        num_migrants: usize,
+        topology: MigrationTopology,
+    ) -> Result<Self, SimError<A>> {
+        if islands.is_empty() {
+            return Err(SimError::InvalidSimulation(
+                "an island simulation needs at least one island".to_string(),
+            ));
+        }
+        Ok(IslandSimulator {
+            islands,
+            migration_interval,
+            num_migrants,
+            topology,
+            policy: BestReplaceWorst,
+            iteration: 0,
+            processing_time: ProcessingTime::zero(),
+            duration: Duration::zero(),
+        })
+    }
+}
+
+impl<A, T, P> IslandSimulator<A, T, P>
+where
+    A: Algorithm + TrackProcessingTime + Debug + Migration,
+    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+    T: Termination<A>,
+    P: MigrationPolicy,
+{
+    /// Replaces the `MigrationPolicy` of this simulation by the given one.
+    pub fn with_migration_policy<Q>(self, policy: Q) -> IslandSimulator<A, T, Q>
+    where
+        Q: MigrationPolicy,
+    {
+        IslandSimulator {
+            islands: self.islands,
+            migration_interval: self.migration_interval,
+            num_migrants: self.num_migrants,
+            topology: self.topology,
+            policy,
+            iteration: self.iteration,
+            processing_time: self.processing_time,
+            duration: self.duration,
+        }
+    }
+
+    /// Returns the islands of this simulation.
+    pub fn islands(&self) -> &[Simulator<A, T>] {
+        &self.islands
+    }
+
+    pub fn migration_interval(&self) -> u64 {
+        self.migration_interval
+    }
+
+    pub fn set_migration_interval(&mut self, value: u64) {
+        self.migration_interval = value;
+    }
+
+    pub fn num_migrants(&self) -> usize {
+        self.num_migrants
+    }
+
+    pub fn set_num_migrants(&mut self, value: usize) {
+        self.num_migrants = value;
+    }
+
+    pub fn topology(&self) -> MigrationTopology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, value: MigrationTopology) {
+        self.topology = value;
+    }
+
+    /// Returns the `MigrationPolicy` of this simulation.
+    pub fn migration_policy(&self) -> &P {
+        &self.policy
+    }
+
+    /// Migrates individuals of each island to other islands.
+    fn migrate(&mut self) {
+        let num_islands = self.islands.len();
+        if num_islands < 2 || self.num_migrants == 0 {
+            return;
+        }
+        let mut immigrants: Vec<Vec<<A as Migration>::Genome>> = vec![Vec::new(); num_islands];
+        for (source, island) in self.islands.iter_mut().enumerate() {
+            let destinations: Vec<usize> = match self.topology {
+                MigrationTopology::Ring => vec![(source + 1) % num_islands],
+                MigrationTopology::FullyConnected => {
+                    (0..num_islands).filter(|index| *index != source).collect()
+                },
+            };
+            let (algorithm, rng) = island.algorithm_and_rng_mut();
+            let emigrants = self.policy.select_emigrants(
+                algorithm,
+                self.num_migrants * destinations.len(),
+                rng,
+            );
+            // deal out the emigrants, so that each one leaves for exactly one
+            // destination
+            for (index, emigrant) in emigrants.into_iter().enumerate() {
+                immigrants[destinations[index % destinations.len()]].push(emigrant);
+            }
+        }
+        for (island, immigrants) in self.islands.iter_mut().zip(immigrants) {
+            let (algorithm, rng) = island.algorithm_and_rng_mut();
+            self.policy.integrate_immigrants(algorithm, immigrants, rng);
+        }
+    }
+}
+
+/// The islands are stepped in parallel if the `parallel` feature is enabled.
+/// Thus the algorithm and its outputs must be `Send`. The results do not
+/// depend on whether the islands are stepped in parallel, as each island
+/// uses its own random number generator.
+impl<A, T, P> Simulation<A> for IslandSimulator<A, T, P>
+where
+    A: Algorithm + TrackProcessingTime + Debug + Migration + Send,
+    <A as Algorithm>::Output: Send,
+    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+    T: Termination<A>,
+    P: MigrationPolicy,
+{
+    type Error = <Simulator<A, T> as Simulation<A>>::Error;
+
+    fn run(&mut self) -> Result<SimResult<A>, Self::Error> {
+        loop {
+            if let SimResult::Final(state, processing_time, duration, reason) = self.step()? {
+                return Ok(SimResult::Final(state, processing_time, duration, reason));
+            }
+        }
+    }
+
+    fn step(&mut self) -> Result<SimResult<A>, Self::Error> {
+        self.iteration += 1;
+        let mut best_state: Option<(usize, bool, State<A>)> = None;
+        let mut stop_reason = None;
+        for (index, result) in step_islands(&mut self.islands)?.into_iter().enumerate() {
+            let (is_final, state) = match result {
+                SimResult::Intermediate(state) => (false, state),
+                SimResult::Final(state, _, _, reason) => {
+                    if stop_reason.is_none() {
+                        stop_reason = Some(StopReason::Island(index, Box::new(reason)));
+                    }
+                    (true, state)
+                },
+            };
+            self.processing_time += state.processing_time;
+            self.duration += state.duration;
+            let is_better = match best_state {
+                Some((_, _, ref best)) => {
+                    A::compare_best_fitness(&state.result, &best.result) == Ordering::Greater
+                },
+                None => true,
+            };
+            if is_better {
+                best_state = Some((index, is_final, state));
+            }
+        }
+        let (best_island, is_final, mut state) =
+            best_state.expect("the constructor ensures at least one island");
+        match stop_reason {
+            Some(reason) => {
+                // the state of an island that has not finished itself
+                if !is_final {
+                    let (algorithm, _) = self.islands[best_island].algorithm_and_rng_mut();
+                    algorithm.finish(&mut state.result);
+                }
+                self.stop()?;
+                Ok(SimResult::Final(
+                    state,
+                    self.processing_time,
+                    self.duration,
+                    reason,
+                ))
+            },
+            None => {
+                if self.migration_interval > 0 && self.iteration % self.migration_interval == 0 {
+                    self.migrate();
+                }
+                Ok(SimResult::Intermediate(state))
+            },
+        }
+    }
+
+    fn stop(&mut self) -> Result<bool, Self::Error> {
+        let mut stopped = false;
+        for island in &mut self.islands {
+            stopped |= island.stop()?;
+        }
+        Ok(stopped)
+    }
+
+    fn reset(&mut self) -> Result<bool, Self::Error> {
+        let mut reset = true;
+        for island in &mut self.islands {
+            reset &= island.reset()?;
+        }
+        self.iteration = 0;
+        self.processing_time = ProcessingTime::zero();
+        self.duration = Duration::zero();
+        Ok(reset)
+    }
+}
+
+/// Steps each of the given islands like `Simulation::step` and returns their
+/// results in the order of the islands. If stepping an island fails the error
+/// of the island with the lowest index is returned.
+///
+/// The generations of the islands are processed in parallel, while the
+/// observers and the termination of each island are evaluated one island
+/// after the other.
+fn step_islands<A, T>(islands: &mut [Simulator<A, T>]) -> Result<Vec<SimResult<A>>, SimError<A>>
+where
+    A: Algorithm + TrackProcessingTime + Debug + Send,
+    <A as Algorithm>::Output: Send,
+    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+    T: Termination<A>,
+{
+    for island in islands.iter_mut() {
+        island.start_step_mode()?;
+    }
+    let mut results: Vec<Option<SimResult<A>>> = islands.iter().map(|_| None).collect();
+    // an island processes several generations per step if it does not report
+    // every generation
+    while results.iter().any(Option::is_none) {
+        let pending: Vec<usize> = (0..islands.len())
+            .filter(|index| results[*index].is_none())
+            .collect();
+        let mut processors: Vec<IterationProcessor<A, Prng>> = islands
+            .iter_mut()
+            .zip(&results)
+            .filter(|(_, result)| result.is_none())
+            .map(|(island, _)| island.iteration_processor())
+            .collect();
+        let states = process_iterations(&mut processors);
+        for (index, state) in pending.into_iter().zip(states) {
+            results[index] = islands[index].complete_step(state?);
+        }
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Processes the next generation of each island in parallel.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn process_iterations<A>(
+    processors: &mut [IterationProcessor<A, Prng>],
+) -> Vec<Result<State<A>, SimError<A>>>
+where
+    A: Algorithm + TrackProcessingTime + Debug + Send,
+    <A as Algorithm>::Output: Send,
+    <A as Algorithm>::Error: Eq + Hash + Send + Sync,
+{
+    processors
+        .par_iter_mut()
+        .map(IterationProcessor::process)
+        .collect()
+}
+
+/// Processes the next generation of each island one after the other.
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+fn process_iterations<A>(
+    processors: &mut [IterationProcessor<A, Prng>],
+) -> Vec<Result<State<A>, SimError<A>>>
+where
+    A: Algorithm + TrackProcessingTime + Debug,
+    <A as Algorithm>::Error: Eq + Hash,
+{
+    processors
+        .iter_mut()
+        .map(IterationProcessor::process)
+        .collect()
+}
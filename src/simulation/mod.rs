@@ -1,11 +1,30 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub mod background;
+pub mod ensemble;
+pub mod islands;
+pub mod observer;
 pub mod simulator;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::background::AsyncSimulator;
+
 use crate::{
-    algorithm::Algorithm, random::Seed, statistic::ProcessingTime, termination::StopReason,
+    algorithm::Algorithm,
+    random::{Prng, Seed},
+    statistic::ProcessingTime,
+    termination::StopReason,
 };
-use chrono::{DateTime, Duration, Local};
+use chrono::{DateTime, Duration, Utc};
 
 /// A `Simulation` is the execution of an algorithm.
+///
+/// A simulation is either run completely by calling `run` or step by step by
+/// calling `step` repeatedly. Both return the same `SimResult::Final` for the
+/// same seed, including the `StopReason`. The two modes can not be
+/// interleaved freely: `run` fails with an error while a simulation started
+/// by `step` has not finished yet. A simulation that has finished or that has
+/// been stopped by calling `stop` may be continued in either mode. It
+/// continues with the next iteration unless it is `reset` before.
 pub trait Simulation<A>
 where
     A: Algorithm,
@@ -13,11 +32,14 @@ where
     type Error;
 
     /// Runs this simulation completely. The simulation ends when the
-    /// termination criteria are met.
+    /// termination criteria are met. The returned result is always a
+    /// `SimResult::Final`. Any error is returned immediately.
     fn run(&mut self) -> Result<SimResult<A>, Self::Error>;
 
     /// Makes one step in this simulation. One step in the simulation performs
-    /// one time the complete loop of the genetic algorithm.
+    /// one time the complete loop of the genetic algorithm. The returned
+    /// result is a `SimResult::Final` when the termination criteria are met
+    /// and a `SimResult::Intermediate` otherwise.
     fn step(&mut self) -> Result<SimResult<A>, Self::Error>;
 
     /// Stops the simulation after the current loop is finished.
@@ -54,7 +76,7 @@ where
     A: Algorithm,
 {
     /// The local time when this simulation started.
-    pub started_at: DateTime<chrono::Utc>,
+    pub started_at: DateTime<Utc>,
     /// The number of the iteration that this state represents. Iterations
     /// are counted from 1 and increased by 1 on each iteration of the
     /// simulation loop.
@@ -71,6 +93,27 @@ where
     pub result: <A as Algorithm>::Output,
 }
 
+/// The `SimState` holds the state of a `simulator::Simulator` that is needed
+/// to resume it later on at the point where it was saved. The state of the
+/// termination is not part of it, see `simulator::Simulator::restore`.
+///
+/// With the crate feature `serde` enabled the `SimState` can be serialized
+/// in order to store it as a checkpoint of a long running simulation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_::Serialize, serde_::Deserialize),
+    serde(crate = "serde_")
+)]
+pub struct SimState<S, R = Prng> {
+    /// The number of the last processed iteration.
+    pub iteration: u64,
+    /// The state of the random number generator.
+    pub rng: R,
+    /// The saved state of the algorithm.
+    pub algorithm: S,
+}
+
 /// The result of running a step in the `Simulation`.
 #[derive(Debug, PartialEq)]
 pub enum SimResult<A>
@@ -0,0 +1,133 @@
+//! The `observer` module provides the `SimulationObserver` trait to get
+//! notified about the progress of a simulation without owning the loop that
+//! steps the simulation.
+//!
+//! Observers are registered on the `simulator::SimulatorBuilder` by calling
+//! `add_observer`. They can be used for logging, live plotting or to stop the
+//! simulation early.
+//!
+//! Provided observers are:
+//! * `BestFitnessHistory` - records the best fitness of every generation.
+
+use crate::{
+    algorithm::Algorithm,
+    ga::GeneticAlgorithm,
+    genetic::{Fitness, FitnessFunction, Genotype, GenotypeToPhenotype},
+    operator::{CrossoverOp, MutationOp, ReinsertionOp, SelectionOp},
+    simulation::{SimResult, State},
+    termination::StopFlag,
+};
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+/// A `SimulationObserver` gets notified by the `simulator::Simulator` after
+/// each generation and when the simulation is finished.
+///
+/// If several observers are registered they are notified in the order in
+/// which they have been registered.
+pub trait SimulationObserver<A>: Debug + CloneObserver<A>
+where
+    A: Algorithm,
+{
+    /// Is called after each generation with the `State` of this generation.
+    ///
+    /// An observer requests to stop the simulation by returning
+    /// `StopFlag::StopNow`. All observers are notified about the generation
+    /// even if an observer registered before them requests to stop. The
+    /// reason of the first observer requesting to stop becomes the
+    /// `StopReason` of the simulation. The termination condition of the
+    /// simulation is only evaluated if no observer requests to stop.
+    fn on_generation(&mut self, state: &State<A>) -> StopFlag;
+
+    /// Is called once when the simulation is finished with the resulting
+    /// `SimResult::Final`. It is not called if the simulation fails with an
+    /// error or if it is stopped by calling `Simulation::stop`.
+    ///
+    /// The default implementation does nothing.
+    fn on_finish(&mut self, _result: &SimResult<A>) {}
+}
+
+/// The `CloneObserver` trait enables cloning of boxed `SimulationObserver`s.
+/// It is implemented for all `SimulationObserver`s that implement `Clone`.
+pub trait CloneObserver<A>
+where
+    A: Algorithm,
+{
+    /// Clones this observer into a new box.
+    fn clone_observer(&self) -> Box<dyn SimulationObserver<A>>;
+}
+
+impl<A, O> CloneObserver<A> for O
+where
+    A: Algorithm,
+    O: 'static + SimulationObserver<A> + Clone,
+{
+    fn clone_observer(&self) -> Box<dyn SimulationObserver<A>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<A> Clone for Box<dyn SimulationObserver<A>>
+where
+    A: Algorithm,
+{
+    fn clone(&self) -> Self {
+        self.clone_observer()
+    }
+}
+
+/// The `BestFitnessHistory` records the fitness of the best solution of
+/// every generation of a `ga::GeneticAlgorithm`.
+///
+/// Clones of a `BestFitnessHistory` share the recorded history. Keep a clone
+/// of the observer that is registered on the simulation to read the history
+/// while or after the simulation is running.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BestFitnessHistory<F>
+where
+    F: Fitness,
+{
+    /// The best fitness of each generation shared by all clones.
+    history: Rc<RefCell<Vec<F>>>,
+}
+
+impl<F> BestFitnessHistory<F>
+where
+    F: Fitness,
+{
+    /// Creates a new `BestFitnessHistory` with an empty history.
+    pub fn new() -> Self {
+        BestFitnessHistory {
+            history: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Returns the best fitness values recorded so far. The first value is
+    /// the best fitness of the first generation.
+    pub fn history(&self) -> Vec<F> {
+        self.history.borrow().clone()
+    }
+}
+
+impl<G, F, E, S, C, M, R, P, D> SimulationObserver<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>
+    for BestFitnessHistory<F>
+where
+    G: Genotype,
+    F: 'static + Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Sync,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G> + Sync,
+    M: MutationOp<G> + Sync,
+    R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
+{
+    fn on_generation(
+        &mut self,
+        state: &State<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>,
+    ) -> StopFlag {
+        self.history
+            .borrow_mut()
+            .push(state.result.best_solution.solution.fitness.clone());
+        StopFlag::Continue
+    }
+}
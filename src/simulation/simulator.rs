@@ -1,15 +1,18 @@
 use crate::{
-    algorithm::Algorithm,
-    random::{get_rng, random_seed, Prng, Seed},
-    simulation::{SimResult, Simulation, SimulationBuilder, State},
-    statistic::{ProcessingTime, TrackProcessingTime},
-    termination::{StopFlag, Termination},
+    algorithm::{Algorithm, Restartable, Resumable},
+    random::{get_rng, random_seed, Prng, Seed, SeedableRng, SplittableRng},
+    simulation::{
+        observer::SimulationObserver, SimResult, SimState, Simulation, SimulationBuilder, State,
+    },
+    statistic::{now, ProcessingTime, TrackProcessingTime},
+    termination::{StopFlag, StopReason, Termination},
 };
-use chrono::{DateTime, Local, Utc, Duration};
+use chrono::{DateTime, Utc};
 use std::{
     error::Error,
     fmt::{self, Debug, Display},
     hash::Hash,
+    ops::ControlFlow,
 };
 
 /// The `simulate` function creates a new `Simulator` for the given
@@ -21,7 +24,7 @@ where
     SimulatorBuilderWithAlgorithm { algorithm }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct SimulatorBuilder<A, T>
 where
     A: Algorithm,
@@ -29,24 +32,76 @@ where
 {
     algorithm: A,
     termination: T,
+    rng_seed: Option<u64>,
+    report_every: u64,
+    observers: Vec<Box<dyn SimulationObserver<A>>>,
 }
 
-impl<A, T> SimulationBuilder<Simulator<A, T>, A> for SimulatorBuilder<A, T>
+impl<A, T> SimulatorBuilder<A, T>
 where
-    A: Algorithm + TrackProcessingTime + Debug,
-    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+    A: Algorithm,
     T: Termination<A>,
 {
-    fn build(self) -> Simulator<A, T> {
-        self.build_with_seed(random_seed())
+    /// Sets the seed from which the `Prng` of the simulation is initialized.
+    ///
+    /// All randomness of selection, crossover, mutation and reinsertion is
+    /// drawn from this `Prng`. Thus building the same algorithm twice with the
+    /// same seed results in identical simulation runs. When building the
+    /// simulation with `build_with_seed` the given `Seed` takes precedence.
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
     }
 
-    fn build_with_seed(self, seed: Seed) -> Simulator<A, T> {
+    /// Sets the number of generations after which the simulation reports its
+    /// progress when it is run in step mode.
+    ///
+    /// With a value of `n` each call of `Simulation::step` processes
+    /// generations until the number of the generation is a multiple of `n`
+    /// and returns a `SimResult::Intermediate` for this generation. The
+    /// `SimResult::Final` is always returned as soon as the termination
+    /// condition is met, regardless of this setting. The `SimulationObserver`s
+    /// are still notified after every generation. The default is to report
+    /// every generation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn report_every(mut self, n: u64) -> Self {
+        assert!(
+            n > 0,
+            "the number of generations to report must be greater than 0"
+        );
+        self.report_every = n;
+        self
+    }
+
+    /// Registers the given `SimulationObserver` to be notified about the
+    /// progress of the simulation.
+    ///
+    /// Observers are notified in the order in which they are registered.
+    pub fn add_observer(mut self, observer: Box<dyn SimulationObserver<A>>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Finally build the simulation using the given random number generator.
+    ///
+    /// All randomness of the simulation is drawn from the given generator
+    /// instead of the `Prng`, e.g. to use a generator that is available on a
+    /// specific platform or to control the random numbers in tests. The seed
+    /// set by `with_rng_seed` is ignored.
+    pub fn build_with_rng<R>(self, rng: R) -> Simulator<A, T, R>
+    where
+        R: SplittableRng,
+    {
         Simulator {
             algorithm: self.algorithm,
             termination: self.termination,
+            observers: self.observers,
             run_mode: RunMode::NotRunning,
-            rng: get_rng(seed),
+            report_every: self.report_every,
+            rng,
             started_at: DateTime::default(),
             iteration: 0,
             processing_time: ProcessingTime::zero(),
@@ -54,6 +109,24 @@ where
     }
 }
 
+impl<A, T> SimulationBuilder<Simulator<A, T>, A> for SimulatorBuilder<A, T>
+where
+    A: Algorithm + TrackProcessingTime + Debug,
+    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+    T: Termination<A>,
+{
+    fn build(self) -> Simulator<A, T> {
+        match self.rng_seed {
+            Some(seed) => self.build_with_rng(Prng::seed_from_u64(seed)),
+            None => self.build_with_seed(random_seed()),
+        }
+    }
+
+    fn build_with_seed(self, seed: Seed) -> Simulator<A, T> {
+        self.build_with_rng(get_rng(seed))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct SimulatorBuilderWithAlgorithm<A>
 where
@@ -73,6 +146,9 @@ where
         SimulatorBuilder {
             algorithm: self.algorithm,
             termination,
+            rng_seed: None,
+            report_every: 1,
+            observers: Vec::new(),
         }
     }
 }
@@ -99,6 +175,7 @@ where
 {
     AlgorithmError(<A as Algorithm>::Error),
     SimulationAlreadyRunning(String),
+    InvalidSimulation(String),
 }
 
 impl<A> Display for SimError<A>
@@ -111,7 +188,10 @@ where
             SimError::AlgorithmError(ref error) => write!(f, "algorithm error: {}", error),
             SimError::SimulationAlreadyRunning(ref message) => {
                 write!(f, "simulation already running {}", message)
-            }
+            },
+            SimError::InvalidSimulation(ref message) => {
+                write!(f, "invalid simulation: {}", message)
+            },
         }
     }
 }
@@ -125,131 +205,384 @@ where
         match *self {
             SimError::AlgorithmError(ref error) => Some(error),
             SimError::SimulationAlreadyRunning(_) => None,
+            SimError::InvalidSimulation(_) => None,
         }
     }
 }
 
+/// The `IntoStopFlag` trait converts the value returned by the callback of
+/// `Simulator::run_with` into a `StopFlag`.
+///
+/// It is implemented for `()`, which always continues the simulation, for
+/// `StopFlag` itself and for `ControlFlow<StopReason>`, where
+/// `ControlFlow::Break` stops the simulation with the given reason.
+pub trait IntoStopFlag {
+    /// Converts this value into a `StopFlag`.
+    fn into_stop_flag(self) -> StopFlag;
+}
+
+impl IntoStopFlag for () {
+    fn into_stop_flag(self) -> StopFlag {
+        StopFlag::Continue
+    }
+}
+
+impl IntoStopFlag for StopFlag {
+    fn into_stop_flag(self) -> StopFlag {
+        self
+    }
+}
+
+impl IntoStopFlag for ControlFlow<StopReason> {
+    fn into_stop_flag(self) -> StopFlag {
+        match self {
+            ControlFlow::Continue(()) => StopFlag::Continue,
+            ControlFlow::Break(reason) => StopFlag::StopNow(reason),
+        }
+    }
+}
+
+/// The `GenerationCounter` defines how the number of the iteration proceeds
+/// when a simulation is restarted by `Simulator::restart_preserving_best`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationCounter {
+    /// The iterations are counted on from the last processed iteration, as
+    /// if the restart is just another generation of the same run. The
+    /// termination condition keeps its state.
+    Continue,
+    /// The iterations are counted from 1 again, as if the simulation has
+    /// just been started. The termination condition and the processing time
+    /// are reset too.
+    Reset,
+}
+
 #[derive(Clone, Debug)]
-pub struct Simulator<A, T>
+pub struct Simulator<A, T, R = Prng>
 where
     A: Algorithm,
     T: Termination<A>,
+    R: SplittableRng,
 {
     algorithm: A,
     termination: T,
+    observers: Vec<Box<dyn SimulationObserver<A>>>,
     run_mode: RunMode,
-    rng: Prng,
+    report_every: u64,
+    rng: R,
     started_at: DateTime<Utc>,
     iteration: u64,
     processing_time: ProcessingTime,
 }
 
-impl<A, T> Simulator<A, T>
+impl<A, T, R> Simulator<A, T, R>
 where
     A: Algorithm + TrackProcessingTime + Debug,
     <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
     T: Termination<A>,
+    R: SplittableRng,
 {
     pub fn termination(&self) -> &T {
         &self.termination
     }
 
-    /// Processes one iteration of the algorithm used in this simulation.
-    fn process_one_iteration(&mut self) -> Result<State<A>, <Self as Simulation<A>>::Error> {
+    pub fn algorithm(&self) -> &A {
+        &self.algorithm
+    }
 
-        self.iteration += 1;
-        let result = self.algorithm.next(self.iteration, &mut self.rng);
-        self.processing_time += self.algorithm.processing_time();
+    /// Returns mutable references to the algorithm and the random number
+    /// generator of this simulation.
+    pub(crate) fn algorithm_and_rng_mut(&mut self) -> (&mut A, &mut R) {
+        (&mut self.algorithm, &mut self.rng)
+    }
 
-        match result {
-            Ok(result) => Ok(State {
-                started_at: self.started_at,
-                iteration: self.iteration,
-                duration: Duration::seconds(0),
-                processing_time: self.algorithm.processing_time(),
-                result,
-            }),
-            Err(error) => Err(SimError::AlgorithmError(error)),
+    /// Saves the state of this simulation, so that it can be resumed later on
+    /// by calling `Simulator::restore`.
+    pub fn save_state(&self) -> SimState<<A as Resumable>::SavedState, R>
+    where
+        A: Resumable,
+    {
+        SimState {
+            iteration: self.iteration,
+            rng: self.rng.clone(),
+            algorithm: self.algorithm.save_state(),
         }
     }
-}
 
-impl<A, T> Simulation<A> for Simulator<A, T>
-where
-    A: Algorithm + TrackProcessingTime + Debug,
-    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
-    T: Termination<A>,
-{
-    type Error = SimError<A>;
+    /// Builds a new `Simulator` from the given builder and restores the given
+    /// saved state.
+    ///
+    /// Restored are the number of the last processed iteration, the state of
+    /// the random number generator and the saved state of the algorithm as
+    /// defined by its implementation of `algorithm::Resumable`. Not restored
+    /// are:
+    /// * the state of the termination, e.g. the number of generations without
+    ///   improvement of a `termination::convergence::StagnationLimit` or
+    ///   the state captured by the closure of a
+    ///   `termination::custom::FnTermination`, which is taken over from the
+    ///   termination of the builder,
+    /// * the state of the algorithm that is not part of its saved state, e.g.
+    ///   the mutation rate of a `mutation::adaptive::AdaptiveRateMutator`,
+    ///   which is taken over from the algorithm of the builder,
+    /// * the observers, which are the ones registered on the builder,
+    /// * the processing time, which starts from zero.
+    ///
+    /// Thus stepping the restored simulation produces the same results as if
+    /// the simulation that saved the state has never been interrupted only if
+    /// the termination and the algorithm of the builder are in the same state
+    /// as when the state was saved, e.g. for a
+    /// `termination::limit::GenerationLimit` and operators that do not adapt
+    /// during the simulation.
+    pub fn restore(
+        builder: SimulatorBuilder<A, T>,
+        state: SimState<<A as Resumable>::SavedState, R>,
+    ) -> Self
+    where
+        A: Resumable,
+    {
+        let mut simulator = builder.build_with_rng(state.rng);
+        simulator.algorithm.restore_state(state.algorithm);
+        simulator.iteration = state.iteration;
+        simulator
+    }
 
-    fn run(&mut self) -> Result<SimResult<A>, Self::Error> {
+    /// Restarts the algorithm of this simulation with a freshly generated
+    /// population, into which up to `num_best` of the best solutions found
+    /// so far are injected, e.g. when the population has converged
+    /// prematurely. The fresh population is generated from the random number
+    /// generator of this simulation.
+    ///
+    /// The `GenerationCounter` defines whether the iterations are counted on
+    /// or from 1 again after the restart. Unlike a reinsertion operator that
+    /// replaces parts of the population, e.g. the
+    /// `reinsertion::restart::HypermutationReinserter`, the whole population
+    /// is rebuilt.
+    ///
+    /// Returns whether the population has been replaced, see
+    /// `algorithm::Restartable` for the conditions of an algorithm.
+    pub fn restart_preserving_best(
+        &mut self,
+        num_best: usize,
+        counter: GenerationCounter,
+    ) -> Result<bool, SimError<A>>
+    where
+        A: Restartable,
+    {
+        let restarted = self
+            .algorithm
+            .restart_preserving_best(num_best, &mut self.rng)
+            .map_err(SimError::AlgorithmError)?;
+        if restarted && counter == GenerationCounter::Reset {
+            self.iteration = 0;
+            self.processing_time = ProcessingTime::zero();
+            self.termination.reset();
+        }
+        Ok(restarted)
+    }
+
+    /// Runs this simulation completely like `Simulation::run` and calls the
+    /// given closure with the `State` of every generation, e.g. to report the
+    /// progress of the simulation.
+    ///
+    /// The closure is called after the generation has been processed and
+    /// before the `SimulationObserver`s are notified and the termination
+    /// condition is evaluated. Thus it is also called for the last generation.
+    /// If processing a generation fails the error is returned immediately
+    /// without calling the closure.
+    ///
+    /// The closure may stop the simulation early, e.g. when the user cancels
+    /// it, by returning `StopFlag::StopNow` or `ControlFlow::Break` with a
+    /// `StopReason`. Then the observers are still notified about the
+    /// generation, but the termination condition is not evaluated. The
+    /// simulation finishes with a `SimResult::Final` with the returned
+    /// `StopReason`. A closure that returns `()` never stops the simulation.
+    pub fn run_with<C, Q>(&mut self, mut callback: C) -> Result<SimResult<A>, SimError<A>>
+    where
+        C: FnMut(&State<A>) -> Q,
+        Q: IntoStopFlag,
+    {
         match self.run_mode {
             RunMode::Loop => {
                 return Err(SimError::SimulationAlreadyRunning(format!(
                     "in loop mode since {}",
                     &self.started_at
                 )))
-            }
+            },
             RunMode::Step => {
                 return Err(SimError::SimulationAlreadyRunning(format!(
                     "in step mode since {}",
                     &self.started_at
                 )))
-            }
+            },
             RunMode::NotRunning => {
                 self.run_mode = RunMode::Loop;
-                self.started_at = DateTime::default();
-            }
+                self.started_at = now();
+            },
         }
         let result = loop {
             match self.process_one_iteration() {
                 Ok(state) => {
-                    // Stage 5: Be aware of the termination:
-                    match self.termination.evaluate(&state) {
-                        StopFlag::Continue => {}
-                        StopFlag::StopNow(reason) => {
-                            let processing_time = self.processing_time;
-                            let duration = DateTime::<chrono::Utc>::default().signed_duration_since(self.started_at);
-                            break Ok(SimResult::Final(state, processing_time, duration, reason));
-                        }
+                    let stop_reason = match callback(&state).into_stop_flag() {
+                        StopFlag::StopNow(reason) => Some(reason),
+                        StopFlag::Continue => None,
+                    };
+                    match self.complete_iteration(state, stop_reason) {
+                        SimResult::Intermediate(_) => {},
+                        result => break Ok(result),
                     }
-                }
+                },
                 Err(error) => {
                     break Err(error);
-                }
+                },
             }
         };
         self.run_mode = RunMode::NotRunning;
         result
     }
 
-    fn step(&mut self) -> Result<SimResult<A>, Self::Error> {
+    /// Processes one iteration of the algorithm used in this simulation.
+    fn process_one_iteration(&mut self) -> Result<State<A>, <Self as Simulation<A>>::Error> {
+        self.iteration_processor().process()
+    }
+
+    /// Returns the parts of this simulation that process the iterations of
+    /// its algorithm.
+    pub(crate) fn iteration_processor(&mut self) -> IterationProcessor<'_, A, R> {
+        IterationProcessor {
+            algorithm: &mut self.algorithm,
+            rng: &mut self.rng,
+            iteration: &mut self.iteration,
+            processing_time: &mut self.processing_time,
+            started_at: self.started_at,
+        }
+    }
+
+    /// Switches this simulation into step mode, unless it is already running
+    /// in step mode.
+    pub(crate) fn start_step_mode(&mut self) -> Result<(), SimError<A>> {
         match self.run_mode {
-            RunMode::Loop => {
-                return Err(SimError::SimulationAlreadyRunning(format!(
-                    "in loop mode since {}",
-                    &self.started_at
-                )))
-            }
-            RunMode::Step => (),
+            RunMode::Loop => Err(SimError::SimulationAlreadyRunning(format!(
+                "in loop mode since {}",
+                &self.started_at
+            ))),
+            RunMode::Step => Ok(()),
             RunMode::NotRunning => {
                 self.run_mode = RunMode::Step;
-                self.started_at = DateTime::default();
-            }
+                self.started_at = now();
+                Ok(())
+            },
         }
-        self.process_one_iteration().and_then(|state|
-            // Stage 5: Be aware of the termination:
-            Ok(match self.termination.evaluate(&state) {
-                StopFlag::Continue => {
-                    SimResult::Intermediate(state)
-                },
-                StopFlag::StopNow(reason) => {
-                    let processing_time = self.processing_time;
-                    let duration = DateTime::<Utc>::default().signed_duration_since(self.started_at);
+    }
+
+    /// Completes an iteration that has been processed in step mode. Returns
+    /// the `SimResult` if the iteration is reported, i.e. if it is the last
+    /// iteration or its number is a multiple of `report_every`.
+    pub(crate) fn complete_step(&mut self, state: State<A>) -> Option<SimResult<A>> {
+        match self.complete_iteration(state, None) {
+            SimResult::Intermediate(_) if self.iteration % self.report_every != 0 => None,
+            result => {
+                if let SimResult::Final(..) = result {
                     self.run_mode = RunMode::NotRunning;
-                    SimResult::Final(state, processing_time, duration, reason)
-                },
-            }))
+                }
+                Some(result)
+            },
+        }
+    }
+
+    /// Notifies the observers about the processed iteration and evaluates the
+    /// termination condition unless a `StopReason` is already given.
+    fn complete_iteration(
+        &mut self,
+        mut state: State<A>,
+        mut stop_reason: Option<StopReason>,
+    ) -> SimResult<A> {
+        for observer in &mut self.observers {
+            if let StopFlag::StopNow(reason) = observer.on_generation(&state) {
+                stop_reason.get_or_insert(reason);
+            }
+        }
+        // Stage 5: Be aware of the termination:
+        let stop_reason = match stop_reason {
+            Some(reason) => Some(reason),
+            None => match self.termination.evaluate(&state) {
+                StopFlag::Continue => None,
+                StopFlag::StopNow(reason) => Some(reason),
+            },
+        };
+        match stop_reason {
+            None => SimResult::Intermediate(state),
+            Some(reason) => {
+                self.algorithm.finish(&mut state.result);
+                let processing_time = self.processing_time;
+                let duration = now().signed_duration_since(self.started_at);
+                let result = SimResult::Final(state, processing_time, duration, reason);
+                for observer in &mut self.observers {
+                    observer.on_finish(&result);
+                }
+                result
+            },
+        }
+    }
+}
+
+/// The `IterationProcessor` borrows the parts of a `Simulator` that process
+/// one iteration of its algorithm. Unlike the `Simulator` it does not borrow
+/// the observers, which need not be `Send`. Thus the iterations of several
+/// simulations can be processed on different threads.
+pub(crate) struct IterationProcessor<'a, A, R> {
+    algorithm: &'a mut A,
+    rng: &'a mut R,
+    iteration: &'a mut u64,
+    processing_time: &'a mut ProcessingTime,
+    started_at: DateTime<Utc>,
+}
+
+impl<'a, A, R> IterationProcessor<'a, A, R>
+where
+    A: Algorithm + TrackProcessingTime + Debug,
+    <A as Algorithm>::Error: Eq + Hash,
+    R: SplittableRng,
+{
+    /// Processes the next iteration of the algorithm.
+    pub(crate) fn process(&mut self) -> Result<State<A>, SimError<A>> {
+        let loop_started_at = now();
+        *self.iteration += 1;
+        let result = self.algorithm.next(*self.iteration, self.rng);
+        *self.processing_time += self.algorithm.processing_time();
+
+        match result {
+            Ok(result) => Ok(State {
+                started_at: self.started_at,
+                iteration: *self.iteration,
+                duration: now().signed_duration_since(loop_started_at),
+                processing_time: self.algorithm.processing_time(),
+                result,
+            }),
+            Err(error) => Err(SimError::AlgorithmError(error)),
+        }
+    }
+}
+
+impl<A, T, R> Simulation<A> for Simulator<A, T, R>
+where
+    A: Algorithm + TrackProcessingTime + Debug,
+    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+    T: Termination<A>,
+    R: SplittableRng,
+{
+    type Error = SimError<A>;
+
+    fn run(&mut self) -> Result<SimResult<A>, Self::Error> {
+        self.run_with(|_| {})
+    }
+
+    fn step(&mut self) -> Result<SimResult<A>, Self::Error> {
+        self.start_step_mode()?;
+        loop {
+            let state = self.process_one_iteration()?;
+            if let Some(result) = self.complete_step(state) {
+                return Ok(result);
+            }
+        }
     }
 
     fn stop(&mut self) -> Result<bool, Self::Error> {
@@ -257,7 +590,7 @@ where
             RunMode::Loop | RunMode::Step => {
                 self.run_mode = RunMode::NotRunning;
                 Ok(true)
-            }
+            },
             RunMode::NotRunning => Ok(false),
         }
     }
@@ -270,19 +603,20 @@ where
                      simulation to finish or stop it before resetting it.",
                     &self.started_at
                 )))
-            }
+            },
             RunMode::Step => {
                 return Err(SimError::SimulationAlreadyRunning(format!(
                     "Simulation still running in step mode since {}. Wait for the \
                      simulation to finish or stop it before resetting it.",
                     &self.started_at
                 )))
-            }
+            },
             RunMode::NotRunning => (),
         }
         self.run_mode = RunMode::NotRunning;
         self.processing_time = ProcessingTime::zero();
         self.iteration = 0;
+        self.termination.reset();
         self.algorithm.reset().map_err(SimError::AlgorithmError)
     }
 }
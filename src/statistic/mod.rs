@@ -1,8 +1,12 @@
 //! The `statistic` module provides functionality to collect and display
 //! statistic about a genetic algorithm application and its execution.
 
-use crate::types::fmt::Display;
-use chrono::{Duration, Local};
+use crate::{
+    algorithm::EvaluatedPopulation,
+    genetic::{AsScalar, Fitness, FitnessFunction, Genotype},
+    types::fmt::Display,
+};
+use chrono::{DateTime, Duration, Utc};
 use std::{
     convert::From,
     fmt,
@@ -73,6 +77,33 @@ pub struct TimedResult<U> {
     pub time: ProcessingTime,
 }
 
+/// Returns the current time as used to measure the processing time and the
+/// duration of a simulation.
+///
+/// On `wasm32-unknown-unknown` the system clock is only available through
+/// JavaScript, which requires the `wasm-bindgen` feature. Without this
+/// feature the clock stands still at the UNIX epoch instead of panicking.
+/// Thus all measured processing times and durations are zero and a
+/// `termination::limit::TimeLimit` is never reached.
+pub fn now() -> DateTime<Utc> {
+    #[cfg(not(all(
+        target_arch = "wasm32",
+        not(any(target_os = "emscripten", target_os = "wasi")),
+        not(feature = "wasm-bindgen")
+    )))]
+    {
+        Utc::now()
+    }
+    #[cfg(all(
+        target_arch = "wasm32",
+        not(any(target_os = "emscripten", target_os = "wasi")),
+        not(feature = "wasm-bindgen")
+    ))]
+    {
+        DateTime::<Utc>::from(std::time::UNIX_EPOCH)
+    }
+}
+
 pub fn timed<F, U>(op: F) -> TimedFn<F, U>
 where
     F: FnOnce() -> U,
@@ -93,13 +124,224 @@ where
     F: FnOnce() -> U,
 {
     pub fn run(self) -> TimedResult<U> {
+        let started_at = now();
         let result = (self.function)();
         TimedResult {
             result,
-            time: ProcessingTime::from(Duration::seconds(0)),
+            time: ProcessingTime::from(now().signed_duration_since(started_at)),
         }
     }
 }
 
+/// The `PhaseTimings` hold the processing time of each phase of one
+/// generation of the genetic algorithm.
+///
+/// In case of parallel processing each value is the accumulated time spent by
+/// each thread. The sum of all phases is the processing time of the
+/// generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhaseTimings {
+    /// The time spent on calculating the fitness values of the population.
+    pub evaluation: ProcessingTime,
+    /// The time spent on determining the best solution and on calculating the
+    /// `GenerationStats`.
+    pub statistics: ProcessingTime,
+    /// The time spent on selecting the parents.
+    pub selection: ProcessingTime,
+    /// The time spent on breeding the offspring from the selected parents.
+    pub crossover: ProcessingTime,
+    /// The time spent on mutating the offspring.
+    pub mutation: ProcessingTime,
+    /// The time spent on combining the offspring and the population into the
+    /// next generation.
+    pub reinsertion: ProcessingTime,
+}
+
+impl PhaseTimings {
+    /// Returns the sum of the processing time of all phases.
+    pub fn total(&self) -> ProcessingTime {
+        self.evaluation
+            + self.statistics
+            + self.selection
+            + self.crossover
+            + self.mutation
+            + self.reinsertion
+    }
+}
+
+/// The `GenerationStats` hold statistics about the `genetic::Fitness` values
+/// of the individuals of one generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenerationStats<F>
+where
+    F: Fitness,
+{
+    /// The lowest fitness value of the generation.
+    pub min: F,
+    /// The highest fitness value of the generation.
+    pub max: F,
+    /// The median of the fitness values of the generation. For an even number
+    /// of individuals this is the lower one of the two middle values.
+    pub median: F,
+    /// The average fitness value as calculated by the
+    /// `genetic::FitnessFunction`.
+    pub average: F,
+    /// The standard deviation of the fitness values. It is calculated from
+    /// the scalar representation of the fitness values as given by
+    /// `genetic::AsScalar`, if the `ga::GeneticAlgorithm` is built
+    /// `with_scalar_fitness`. Otherwise it is `None`.
+    pub std_dev: Option<f64>,
+}
+
+impl<F> GenerationStats<F>
+where
+    F: Fitness,
+{
+    /// Calculates the statistics of the given evaluated population without
+    /// the standard deviation, which needs the scalar representation of the
+    /// fitness values, see `from_scalar_evaluated`.
+    ///
+    /// The fitness values are not calculated again. They are taken from the
+    /// evaluated population.
+    pub fn from_evaluated<G>(evaluated: &EvaluatedPopulation<G, F>) -> Self
+    where
+        G: Genotype,
+    {
+        GenerationStats::calculate(evaluated, None)
+    }
+
+    /// Calculates the statistics of the given evaluated population including
+    /// the standard deviation of the scalar representation of the fitness
+    /// values.
+    ///
+    /// The fitness values are not calculated again. They are taken from the
+    /// evaluated population. The mean and the standard deviation of the
+    /// scalar fitness values are calculated in a single pass.
+    pub fn from_scalar_evaluated<G>(evaluated: &EvaluatedPopulation<G, F>) -> Self
+    where
+        G: Genotype,
+        F: AsScalar,
+    {
+        GenerationStats::calculate(evaluated, Some(F::as_scalar))
+    }
+
+    /// Calculates the statistics of the given evaluated population. The
+    /// standard deviation is only calculated if the function that converts
+    /// the fitness values into their scalar representation is given.
+    pub(crate) fn calculate<G>(
+        evaluated: &EvaluatedPopulation<G, F>,
+        as_scalar: Option<fn(&F) -> f64>,
+    ) -> Self
+    where
+        G: Genotype,
+    {
+        let fitness_values = evaluated.fitness_values();
+        let mut sorted = fitness_values.to_vec();
+        let median = if sorted.is_empty() {
+            F::zero()
+        } else {
+            let middle = (sorted.len() - 1) / 2;
+            sorted.select_nth_unstable(middle).1.clone()
+        };
+        GenerationStats {
+            min: evaluated.lowest_fitness().clone(),
+            max: evaluated.highest_fitness().clone(),
+            median,
+            average: evaluated.average_fitness().clone(),
+            std_dev: as_scalar.and_then(|as_scalar| std_dev(fitness_values, as_scalar)),
+        }
+    }
+}
+
+/// Calculates the population standard deviation of the given fitness values
+/// using Welford's algorithm.
+fn std_dev<F>(fitness_values: &[F], as_scalar: fn(&F) -> f64) -> Option<f64> {
+    if fitness_values.is_empty() {
+        return None;
+    }
+    let mut mean = 0.;
+    let mut sum_of_squares = 0.;
+    for (count, fitness) in fitness_values.iter().enumerate() {
+        let value = as_scalar(fitness);
+        let delta = value - mean;
+        mean += delta / (count + 1) as f64;
+        sum_of_squares += delta * (value - mean);
+    }
+    Some((sum_of_squares / fitness_values.len() as f64).sqrt())
+}
+
+/// Counts the fitness values of the given evaluated population in
+/// `num_buckets` buckets of equal width.
+///
+/// The buckets span the range from the scalar value of the
+/// `lowest_possible_fitness` to the scalar value of the
+/// `highest_possible_fitness` of the `genetic::FitnessFunction`. If one of
+/// these limits is not finite, e.g. because the fitness is unbounded, the
+/// range spans the lowest and the highest fitness value of the population
+/// instead. Fitness values outside of the range are counted in the first or
+/// the last bucket respectively. If the range is empty, i.e. the lower and
+/// the upper limit are equal, all fitness values are counted in the first
+/// bucket. Thus the counts always add up to the size of the population.
+///
+/// The buckets are calculated from the scalar representation of the fitness
+/// values. Thus the `genetic::Fitness` type must implement `genetic::AsScalar`.
+///
+/// # Panics
+///
+/// Panics if `num_buckets` is 0.
+pub fn fitness_histogram<G, F, E>(
+    evaluated: &EvaluatedPopulation<G, F>,
+    evaluator: &E,
+    num_buckets: usize,
+) -> Vec<usize>
+where
+    G: Genotype,
+    F: Fitness + AsScalar,
+    E: FitnessFunction<G, F>,
+{
+    scalar_fitness_histogram(evaluated, evaluator, num_buckets, F::as_scalar)
+}
+
+/// Counts the fitness values of the given evaluated population like
+/// `fitness_histogram` using the given function to convert the fitness values
+/// into their scalar representation.
+pub(crate) fn scalar_fitness_histogram<G, F, E>(
+    evaluated: &EvaluatedPopulation<G, F>,
+    evaluator: &E,
+    num_buckets: usize,
+    as_scalar: fn(&F) -> f64,
+) -> Vec<usize>
+where
+    G: Genotype,
+    F: Fitness,
+    E: FitnessFunction<G, F>,
+{
+    assert!(
+        num_buckets > 0,
+        "the number of buckets must be greater than 0"
+    );
+    let values: Vec<f64> = evaluated.fitness_values().iter().map(as_scalar).collect();
+    let lowest = as_scalar(&evaluator.lowest_possible_fitness());
+    let highest = as_scalar(&evaluator.highest_possible_fitness());
+    // for minimization problems the lowest possible fitness has the greater
+    // scalar value
+    let (mut lower, mut upper) = (lowest.min(highest), lowest.max(highest));
+    if !lower.is_finite() || !upper.is_finite() {
+        lower = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        upper = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    }
+    let width = upper - lower;
+    let mut counts = vec![0; num_buckets];
+    for value in values {
+        let bucket = if width > 0. {
+            ((value - lower) / width * num_buckets as f64).max(0.) as usize
+        } else {
+            0
+        };
+        counts[bucket.min(num_buckets - 1)] += 1;
+    }
+    counts
+}
+
 #[cfg(test)]
 mod tests;
@@ -8,7 +8,6 @@ mod timed_fn {
     use std::{thread, time::Duration};
 
     #[test]
-    #[ignore]
     fn timed_function_calls_return_a_time_greater_0() {
         let result = timed(|| {
             thread::sleep(Duration::from_millis(141));
@@ -22,7 +21,6 @@ mod timed_fn {
     }
 
     #[test]
-    #[ignore]
     fn timed_function_calls_measure_time_in_nanoseconds() {
         let result = timed(|| {
             thread::sleep(Duration::from_nanos(141));
@@ -35,3 +33,119 @@ mod timed_fn {
         );
     }
 }
+
+mod generation_stats {
+
+    use super::*;
+    use crate::{
+        algorithm::EvaluatedPopulation, fixtures::population_of_fitness, statistic::GenerationStats,
+    };
+
+    fn evaluated_population(fitness_values: Vec<u32>) -> EvaluatedPopulation<Vec<u32>, u32> {
+        let average = fitness_values.iter().sum::<u32>() / fitness_values.len() as u32;
+        population_of_fitness(fitness_values, average)
+    }
+
+    #[test]
+    fn generation_stats_of_odd_number_of_individuals() {
+        let evaluated = evaluated_population(vec![9, 2, 4, 4, 5, 4, 7]);
+
+        let stats = GenerationStats::from_scalar_evaluated(&evaluated);
+
+        expect_that!(&stats.min, eq(2));
+        expect_that!(&stats.max, eq(9));
+        expect_that!(&stats.median, eq(4));
+        expect_that!(&stats.average, eq(5));
+        let std_dev = stats.std_dev.unwrap();
+        expect_that!(&(std_dev - (32f64 / 7.).sqrt()).abs(), less_than(1e-9));
+    }
+
+    #[test]
+    fn generation_stats_median_of_even_number_of_individuals_is_the_lower_middle() {
+        let evaluated = evaluated_population(vec![8, 2, 6, 4]);
+
+        let stats = GenerationStats::from_scalar_evaluated(&evaluated);
+
+        expect_that!(&stats.median, eq(4));
+        let std_dev = stats.std_dev.unwrap();
+        expect_that!(&(std_dev - 5f64.sqrt()).abs(), less_than(1e-9));
+    }
+
+    #[test]
+    fn generation_stats_without_scalar_fitness_have_no_std_dev() {
+        let evaluated = evaluated_population(vec![1, 2, 3]);
+
+        let stats = GenerationStats::from_evaluated(&evaluated);
+
+        expect_that!(&stats.median, eq(2));
+        expect_that!(&stats.std_dev, eq(None));
+    }
+}
+
+mod fitness_histogram {
+
+    use super::*;
+    use crate::{
+        algorithm::EvaluatedPopulation, fixtures::population_of_fitness, genetic::FitnessFunction,
+        statistic::fitness_histogram,
+    };
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Evaluator {
+        lowest: i32,
+        highest: i32,
+    }
+
+    impl FitnessFunction<Vec<i32>, i32> for Evaluator {
+        fn fitness_of(&self, individual: &Vec<i32>) -> i32 {
+            individual[0]
+        }
+
+        fn average(&self, fitness_values: &[i32]) -> i32 {
+            fitness_values.iter().sum::<i32>() / fitness_values.len() as i32
+        }
+
+        fn highest_possible_fitness(&self) -> i32 {
+            self.highest
+        }
+
+        fn lowest_possible_fitness(&self) -> i32 {
+            self.lowest
+        }
+    }
+
+    fn evaluator(lowest: i32, highest: i32) -> Evaluator {
+        Evaluator { lowest, highest }
+    }
+
+    fn evaluated_population(fitness_values: Vec<i32>) -> EvaluatedPopulation<Vec<i32>, i32> {
+        population_of_fitness(fitness_values, 0)
+    }
+
+    #[test]
+    fn fitness_values_are_counted_in_buckets_of_equal_width() {
+        let evaluated = evaluated_population(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 10, 5, 5]);
+
+        let histogram = fitness_histogram(&evaluated, &evaluator(0, 10), 4);
+
+        expect_that!(&histogram, eq(vec![3, 2, 5, 4]));
+    }
+
+    #[test]
+    fn fitness_values_outside_of_the_range_are_counted_in_the_outer_buckets() {
+        let evaluated = evaluated_population(vec![-5, 0, 3, 10, 15]);
+
+        let histogram = fitness_histogram(&evaluated, &evaluator(0, 10), 2);
+
+        expect_that!(&histogram, eq(vec![3, 2]));
+    }
+
+    #[test]
+    fn all_fitness_values_are_counted_in_the_first_bucket_if_the_range_is_empty() {
+        let evaluated = evaluated_population(vec![4, 4, 4]);
+
+        let histogram = fitness_histogram(&evaluated, &evaluator(4, 4), 3);
+
+        expect_that!(&histogram, eq(vec![3, 0, 0]));
+    }
+}
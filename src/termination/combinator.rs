@@ -1,11 +1,16 @@
 use crate::{
     algorithm::Algorithm,
     simulation::State,
-    termination::{StopFlag, Termination},
+    termination::{StopFlag, StopReason, Termination},
 };
 use std::marker::PhantomData;
 
-// TODO add doc comments
+/// Combines the two given `Termination` conditions with a logical and.
+///
+/// The returned `And` combinator stops the simulation only when both
+/// conditions stop it in the same generation. Combinators can be nested to
+/// combine more than two conditions, e.g.
+/// `and(GenerationLimit::new(500), and(condition2, condition3))`.
 pub fn and<T1, T2, A>(condition1: T1, condition2: T2) -> And<T1, T2, A>
 where
     T1: Termination<A>,
@@ -15,7 +20,14 @@ where
     And::new(condition1, condition2)
 }
 
-// TODO add doc comments
+/// The `And` combinator stops the simulation when both of its `Termination`
+/// conditions stop the simulation.
+///
+/// Both conditions are evaluated in every generation, even if the first one
+/// does not stop the simulation. Thus stateful conditions, like the
+/// `convergence::StagnationLimit`, keep track of every generation. The
+/// `StopReason` of the `And` combinator is a `StopReason::Combined` of the
+/// reasons of both conditions.
 #[derive(Clone, Debug, PartialEq)]
 pub struct And<T1, T2, A>
 where
@@ -69,12 +81,21 @@ where
         }
         match reasons.len() {
             0 | 1 => StopFlag::Continue,
-            _ => StopFlag::StopNow(reasons.join(" and ")), /* TODO how combine the two `StopReason`s preserving combinator semantics? */
+            _ => StopFlag::StopNow(StopReason::combined(reasons)),
         }
     }
+
+    fn reset(&mut self) {
+        self.condition1.reset();
+        self.condition2.reset();
+    }
 }
 
-// TODO add doc comments
+/// Combines the two given `Termination` conditions with a logical or.
+///
+/// The returned `Or` combinator stops the simulation as soon as one of the
+/// conditions stops it. Combinators can be nested to combine more than two
+/// conditions, e.g. `or(GenerationLimit::new(500), or(condition2, condition3))`.
 pub fn or<T1, T2, A>(condition1: T1, condition2: T2) -> Or<T1, T2, A>
 where
     T1: Termination<A>,
@@ -84,7 +105,14 @@ where
     Or::new(condition1, condition2)
 }
 
-// TODO add doc comments
+/// The `Or` combinator stops the simulation when at least one of its
+/// `Termination` conditions stops the simulation.
+///
+/// Both conditions are evaluated in every generation, even if the first one
+/// already stops the simulation. The `StopReason` of the `Or` combinator is
+/// the reason of the condition that stopped the simulation, or a
+/// `StopReason::Combined` of both reasons if both conditions stopped the
+/// simulation in the same generation.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Or<T1, T2, A>
 where
@@ -136,10 +164,269 @@ where
             StopFlag::StopNow(reason) => reasons.push(reason),
             StopFlag::Continue => (),
         }
-        match reasons.len() {
-            0 => StopFlag::Continue,
-            1 => StopFlag::StopNow(reasons[0].clone()),
-            _ => StopFlag::StopNow(reasons.join(" and ")), /* TODO how combine the two `StopReason`s preserving combinator semantics? */
+        if reasons.is_empty() {
+            StopFlag::Continue
+        } else {
+            StopFlag::StopNow(StopReason::combined(reasons))
+        }
+    }
+
+    fn reset(&mut self) {
+        self.condition1.reset();
+        self.condition2.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithm::Algorithm, random::SplittableRng, statistic::ProcessingTime,
+        termination::limit::GenerationLimit,
+    };
+    use chrono::{DateTime, Duration};
+    use galvanic_assert::matchers::*;
+    use std::{error::Error, fmt};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestError;
+
+    impl fmt::Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("test error")
+        }
+    }
+
+    impl Error for TestError {}
+
+    /// An `Algorithm` whose output is the fitness of its best solution.
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestAlgorithm;
+
+    impl Algorithm for TestAlgorithm {
+        type Output = u32;
+        type Error = TestError;
+
+        fn next<R>(&mut self, _: u64, _: &mut R) -> Result<u32, TestError>
+        where
+            R: SplittableRng,
+        {
+            Ok(0)
+        }
+
+        fn reset(&mut self) -> Result<bool, TestError> {
+            Ok(true)
+        }
+    }
+
+    /// Stops the simulation when the output reaches the target.
+    #[derive(Clone, Debug, PartialEq)]
+    struct OutputLimit(u32);
+
+    impl Termination<TestAlgorithm> for OutputLimit {
+        fn evaluate(&mut self, state: &State<TestAlgorithm>) -> StopFlag {
+            if state.result >= self.0 {
+                StopFlag::StopNow(StopReason::Custom(format!("output reached {}", self.0)))
+            } else {
+                StopFlag::Continue
+            }
+        }
+    }
+
+    /// Stops the simulation after it has been evaluated the given number of
+    /// times.
+    #[derive(Clone, Debug, PartialEq)]
+    struct EvaluationCounter {
+        max_evaluations: u64,
+        evaluations: u64,
+    }
+
+    impl Termination<TestAlgorithm> for EvaluationCounter {
+        fn evaluate(&mut self, _: &State<TestAlgorithm>) -> StopFlag {
+            self.evaluations += 1;
+            if self.evaluations >= self.max_evaluations {
+                StopFlag::StopNow(StopReason::Custom(format!(
+                    "evaluated {} times",
+                    self.evaluations
+                )))
+            } else {
+                StopFlag::Continue
+            }
+        }
+
+        fn reset(&mut self) {
+            self.evaluations = 0;
+        }
+    }
+
+    fn state(iteration: u64, output: u32) -> State<TestAlgorithm> {
+        State {
+            started_at: DateTime::default(),
+            iteration,
+            duration: Duration::zero(),
+            processing_time: ProcessingTime::zero(),
+            result: output,
+        }
+    }
+
+    /// Returns the iteration and the reason when the condition stops the
+    /// simulation for the given sequence of outputs.
+    fn stopped_at<T>(condition: &mut T, outputs: &[u32]) -> Option<(u64, StopReason)>
+    where
+        T: Termination<TestAlgorithm>,
+    {
+        outputs.iter().enumerate().find_map(|(index, output)| {
+            let iteration = index as u64 + 1;
+            match condition.evaluate(&state(iteration, *output)) {
+                StopFlag::StopNow(reason) => Some((iteration, reason)),
+                StopFlag::Continue => None,
+            }
+        })
+    }
+
+    #[test]
+    fn and_stops_when_the_later_condition_fires_after_the_earlier_one() {
+        let mut condition = and(GenerationLimit::new(2), OutputLimit(95));
+
+        let (iteration, reason) = stopped_at(&mut condition, &[50, 60, 80, 95, 97])
+            .expect("simulation should be stopped");
+
+        expect_that!(&iteration, eq(4));
+        let reasons = reason.reasons();
+        expect_that!(&reasons.len(), eq(2));
+        expect_that!(
+            &matches!(reasons[0], StopReason::GenerationLimitReached(_)),
+            is(eq(true))
+        );
+        expect_that!(
+            &reasons[1],
+            eq(StopReason::Custom("output reached 95".to_string()))
+        );
+        expect_that!(
+            &reason.to_string().ends_with(" and output reached 95"),
+            is(eq(true))
+        );
+    }
+
+    #[test]
+    fn and_stops_when_the_earlier_condition_fires_after_the_later_one() {
+        let mut condition = and(GenerationLimit::new(4), OutputLimit(95));
+
+        let stopped = stopped_at(&mut condition, &[95, 96, 97, 98, 99]);
+
+        expect_that!(&stopped.map(|(iteration, _)| iteration), eq(Some(4)));
+    }
+
+    #[test]
+    fn and_continues_if_only_one_condition_fires() {
+        let mut condition = and(GenerationLimit::new(1), OutputLimit(95));
+
+        let stopped = stopped_at(&mut condition, &[10, 20, 30]);
+
+        expect_that!(&stopped, eq(None));
+    }
+
+    #[test]
+    fn and_can_be_nested() {
+        let mut condition = and(
+            GenerationLimit::new(2),
+            and(OutputLimit(90), GenerationLimit::new(3)),
+        );
+
+        let stopped = stopped_at(&mut condition, &[90, 91, 92, 93]);
+
+        expect_that!(&stopped.map(|(iteration, _)| iteration), eq(Some(3)));
+    }
+
+    #[test]
+    fn and_evaluates_stateful_conditions_in_every_generation() {
+        let counter = EvaluationCounter {
+            max_evaluations: 3,
+            evaluations: 0,
+        };
+        let mut condition = and(OutputLimit(95), counter);
+
+        let stopped = stopped_at(&mut condition, &[95, 10, 20, 95]);
+
+        // the counter fires in its 3rd and 4th evaluation
+        expect_that!(
+            &stopped,
+            eq(Some((
+                4,
+                StopReason::Combined(vec![
+                    StopReason::Custom("output reached 95".to_string()),
+                    StopReason::Custom("evaluated 4 times".to_string()),
+                ])
+            )))
+        );
+    }
+
+    #[test]
+    fn and_resets_both_conditions() {
+        let counter = EvaluationCounter {
+            max_evaluations: 2,
+            evaluations: 0,
+        };
+        let mut condition = and(OutputLimit(95), counter);
+        stopped_at(&mut condition, &[10, 10, 10]);
+
+        Termination::reset(&mut condition);
+
+        expect_that!(&condition.condition2().evaluations, eq(0));
+    }
+
+    #[test]
+    fn and_flattens_the_reasons_of_nested_conditions() {
+        let mut condition = and(
+            OutputLimit(90),
+            and(OutputLimit(91), GenerationLimit::new(1)),
+        );
+
+        let (_, reason) = stopped_at(&mut condition, &[95]).expect("simulation should be stopped");
+
+        let reasons = reason.reasons();
+        expect_that!(&reasons.len(), eq(3));
+        expect_that!(
+            &matches!(reasons[2], StopReason::GenerationLimitReached(_)),
+            is(eq(true))
+        );
+    }
+
+    #[test]
+    fn or_reports_the_reason_of_the_condition_that_fired() {
+        let mut condition = or(GenerationLimit::new(10), OutputLimit(95));
+
+        let stopped = stopped_at(&mut condition, &[50, 60, 95]);
+
+        expect_that!(
+            &stopped,
+            eq(Some((
+                3,
+                StopReason::Custom("output reached 95".to_string())
+            )))
+        );
+    }
+
+    #[test]
+    fn or_reports_the_reasons_of_both_conditions_if_both_fire() {
+        let mut condition = or(GenerationLimit::new(3), OutputLimit(95));
+
+        let (iteration, reason) =
+            stopped_at(&mut condition, &[50, 60, 95]).expect("simulation should be stopped");
+
+        expect_that!(&iteration, eq(3));
+        match reason {
+            StopReason::Combined(reasons) => {
+                expect_that!(
+                    &matches!(reasons[0], StopReason::GenerationLimitReached(_)),
+                    is(eq(true))
+                );
+                expect_that!(
+                    &reasons[1],
+                    eq(StopReason::Custom("output reached 95".to_string()))
+                );
+            },
+            _ => panic!("both reasons should be reported"),
         }
     }
 }
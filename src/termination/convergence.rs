@@ -0,0 +1,513 @@
+//! The `convergence` package provides `Termination` functions that stop the
+//! simulation when the `genetic::Fitness` values do not improve anymore.
+//!
+//! Provided conditions are:
+//! * `StagnationLimit` - stops the simulation after the best fitness has not
+//!   improved for a number of consecutive generations.
+//! * `DiversityLimit` - stops the simulation after the diversity of the
+//!   population dropped below a threshold.
+//!
+//! The diversity of a population is measured by a `DiversityMetric`. Provided
+//! metrics are:
+//! * `HammingDistance` - the average number of differing genes between two
+//!   individuals.
+//! * `EuclideanDistance` - the average euclidean distance between two
+//!   individuals.
+//! * `UniqueFraction` - the fraction of distinct individuals.
+//! * `UniqueKeyFraction` - the fraction of distinct individuals as defined by
+//!   a `GenomeIdentity`.
+
+use crate::{
+    ga::GeneticAlgorithm,
+    genetic::{
+        Fitness, FitnessFunction, GenomeDistance, GenomeIdentity, Genotype, GenotypeToPhenotype,
+    },
+    operator::{CrossoverOp, MutationOp, ReinsertionOp, SelectionOp},
+    simulation::State,
+    termination::{StopFlag, StopReason, Termination},
+};
+use std::{collections::HashSet, fmt::Debug, marker::PhantomData};
+
+pub use crate::genetic::{EuclideanDistance, HammingDistance};
+
+/// The `StagnationLimit` condition stops the simulation after the best
+/// fitness has not improved for a number of consecutive generations.
+///
+/// The best fitness has improved if the fitness of the best solution of a
+/// generation is better than the best fitness found so far by more than the
+/// `epsilon` value. Smaller improvements are not considered progress. By
+/// default the `epsilon` is zero, so that any improvement counts.
+///
+/// By default higher fitness values are better. If the `operator::SelectionOp`
+/// of the `GeneticAlgorithm` minimizes lower fitness values are better.
+///
+/// The `StagnationLimit` keeps track of the best fitness of previous
+/// generations. Its state is cleared when the simulation is reset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StagnationLimit<G, F>
+where
+    G: Genotype,
+    F: Fitness,
+{
+    _g: PhantomData<G>,
+    /// The number of consecutive generations without improvement after
+    /// which the simulation is stopped.
+    num_generations: u64,
+    /// The minimum improvement of the best fitness that counts as progress.
+    epsilon: F,
+    /// The best fitness found so far.
+    best_fitness: Option<F>,
+    /// The number of consecutive generations without improvement so far.
+    stagnant_generations: u64,
+}
+
+impl<G, F> StagnationLimit<G, F>
+where
+    G: Genotype,
+    F: Fitness,
+{
+    /// Creates a new instance of `StagnationLimit` that stops the simulation
+    /// after the best fitness has not improved for the given number of
+    /// generations.
+    pub fn new(num_generations: u64) -> Self {
+        StagnationLimit::with_epsilon(num_generations, F::zero())
+    }
+
+    /// Creates a new instance of `StagnationLimit` that stops the simulation
+    /// after the best fitness has not improved by more than `epsilon` for the
+    /// given number of generations.
+    pub fn with_epsilon(num_generations: u64, epsilon: F) -> Self {
+        StagnationLimit {
+            _g: PhantomData,
+            num_generations,
+            epsilon,
+            best_fitness: None,
+            stagnant_generations: 0,
+        }
+    }
+
+    /// Returns the number of consecutive generations without improvement
+    /// after which the simulation is stopped.
+    pub fn num_generations(&self) -> u64 {
+        self.num_generations
+    }
+
+    /// Returns the minimum improvement of the best fitness that counts as
+    /// progress.
+    pub fn epsilon(&self) -> &F {
+        &self.epsilon
+    }
+
+    /// Returns the best fitness found so far.
+    pub fn best_fitness(&self) -> Option<&F> {
+        self.best_fitness.as_ref()
+    }
+
+    /// Returns the number of consecutive generations without improvement so
+    /// far.
+    pub fn stagnant_generations(&self) -> u64 {
+        self.stagnant_generations
+    }
+
+    /// Evaluates the best fitness of one generation. If `minimize` is set
+    /// lower fitness values are better.
+    fn evaluate_fitness(&mut self, fitness: &F, minimize: bool) -> StopFlag {
+        let improved = match self.best_fitness {
+            Some(ref best) => {
+                let is_better = if minimize {
+                    fitness < best
+                } else {
+                    fitness > best
+                };
+                is_better && fitness.abs_diff(best) > self.epsilon
+            },
+            None => true,
+        };
+        if improved {
+            self.best_fitness = Some(fitness.clone());
+            self.stagnant_generations = 0;
+            return StopFlag::Continue;
+        }
+        self.stagnant_generations += 1;
+        if self.stagnant_generations >= self.num_generations {
+            StopFlag::StopNow(StopReason::Stagnation(format!(
+                "Simulation stopped after the best fitness of {:?} has not \
+                 improved by more than {:?} for {} generations.",
+                self.best_fitness.as_ref().expect("best fitness is set"),
+                &self.epsilon,
+                self.stagnant_generations
+            )))
+        } else {
+            StopFlag::Continue
+        }
+    }
+}
+
+impl<G, F, E, S, C, M, R, P, D> Termination<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>
+    for StagnationLimit<G, F>
+where
+    G: Genotype,
+    F: Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Sync,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G> + Sync,
+    M: MutationOp<G> + Sync,
+    R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
+{
+    fn evaluate(&mut self, state: &State<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>) -> StopFlag {
+        self.evaluate_fitness(
+            &state.result.best_solution.solution.fitness,
+            state.result.evaluated_population.is_minimize(),
+        )
+    }
+
+    fn reset(&mut self) {
+        self.best_fitness = None;
+        self.stagnant_generations = 0;
+    }
+}
+
+/// A `DiversityMetric` measures how different the individuals of a
+/// population are.
+///
+/// The returned diversity is 0 if all individuals are identical and grows the
+/// more the individuals differ from each other.
+pub trait DiversityMetric<G>
+where
+    G: Genotype,
+{
+    /// Calculates the diversity of the given individuals.
+    fn diversity(&self, individuals: &[G]) -> f64;
+}
+
+impl<V> DiversityMetric<Vec<V>> for HammingDistance
+where
+    V: Clone + Debug + PartialEq + Send + Sync,
+{
+    fn diversity(&self, individuals: &[Vec<V>]) -> f64 {
+        average_pairwise_distance(individuals, |genome1, genome2| {
+            self.distance(genome1, genome2)
+        })
+    }
+}
+
+impl<V> DiversityMetric<Vec<V>> for EuclideanDistance
+where
+    V: Clone + Copy + Debug + PartialEq + Send + Sync + Into<f64>,
+{
+    fn diversity(&self, individuals: &[Vec<V>]) -> f64 {
+        average_pairwise_distance(individuals, |genome1, genome2| {
+            self.distance(genome1, genome2)
+        })
+    }
+}
+
+/// The `UniqueFraction` measures the diversity of a population as the number
+/// of distinct individuals divided by the number of individuals.
+///
+/// The metric can be used for any kind of genome. In contrast to the distance
+/// based metrics it is 1 / n for a population of size n which consists of
+/// identical individuals only.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniqueFraction;
+
+impl<G> DiversityMetric<G> for UniqueFraction
+where
+    G: Genotype,
+{
+    fn diversity(&self, individuals: &[G]) -> f64 {
+        if individuals.is_empty() {
+            return 0.;
+        }
+        let mut unique: Vec<&G> = Vec::with_capacity(individuals.len());
+        for individual in individuals {
+            if !unique.contains(&individual) {
+                unique.push(individual);
+            }
+        }
+        unique.len() as f64 / individuals.len() as f64
+    }
+}
+
+/// The `UniqueKeyFraction` measures the diversity of a population as the
+/// number of distinct canonical keys of the individuals divided by the number
+/// of individuals. The keys are calculated by a `GenomeIdentity`, so that
+/// equivalent individuals are counted once, e.g. a permutation and its
+/// reversal compared by the `genetic::ReversalIdentity`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniqueKeyFraction<I> {
+    /// The `GenomeIdentity` that calculates the canonical keys.
+    identity: I,
+}
+
+impl<I> UniqueKeyFraction<I> {
+    /// Constructs a new `UniqueKeyFraction` that compares the individuals by
+    /// the canonical keys of the given `GenomeIdentity`.
+    pub fn new(identity: I) -> Self {
+        UniqueKeyFraction { identity }
+    }
+
+    /// Returns the `GenomeIdentity` that calculates the canonical keys.
+    pub fn identity(&self) -> &I {
+        &self.identity
+    }
+}
+
+impl<G, I> DiversityMetric<G> for UniqueKeyFraction<I>
+where
+    G: Genotype,
+    I: GenomeIdentity<G>,
+{
+    fn diversity(&self, individuals: &[G]) -> f64 {
+        if individuals.is_empty() {
+            return 0.;
+        }
+        let unique: HashSet<I::Key> = individuals
+            .iter()
+            .map(|individual| self.identity.canonical_key(individual))
+            .collect();
+        unique.len() as f64 / individuals.len() as f64
+    }
+}
+
+/// Calculates the average distance of all pairs of the given individuals.
+fn average_pairwise_distance<G, D>(individuals: &[G], distance: D) -> f64
+where
+    D: Fn(&G, &G) -> f64,
+{
+    let num_individuals = individuals.len();
+    if num_individuals < 2 {
+        return 0.;
+    }
+    let mut sum = 0.;
+    for (index, individual) in individuals.iter().enumerate() {
+        for other in &individuals[index + 1..] {
+            sum += distance(individual, other);
+        }
+    }
+    sum / (num_individuals * (num_individuals - 1) / 2) as f64
+}
+
+/// The `DiversityLimit` condition stops the simulation after the diversity of
+/// the population dropped below a threshold.
+///
+/// The diversity is measured by the given `DiversityMetric` on the evaluated
+/// population of each generation. Low diversity means that the population
+/// converged, so that further generations are unlikely to find better
+/// solutions.
+///
+/// Note that the distance based metrics compare all pairs of individuals,
+/// thus their cost grows quadratically with the population size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiversityLimit<D> {
+    /// The metric used to measure the diversity of the population.
+    metric: D,
+    /// The diversity below which the simulation is stopped.
+    threshold: f64,
+}
+
+impl<D> DiversityLimit<D> {
+    /// Creates a new instance of `DiversityLimit` that stops the simulation
+    /// when the diversity measured by the given metric drops below the
+    /// given threshold.
+    pub fn new(metric: D, threshold: f64) -> Self {
+        DiversityLimit { metric, threshold }
+    }
+
+    /// Returns the metric used to measure the diversity of the population.
+    pub fn metric(&self) -> &D {
+        &self.metric
+    }
+
+    /// Returns the diversity below which the simulation is stopped.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Sets the diversity below which the simulation is stopped.
+    pub fn set_threshold(&mut self, value: f64) {
+        self.threshold = value;
+    }
+
+    /// Evaluates the diversity of the individuals of one generation.
+    fn evaluate_individuals<G>(&self, individuals: &[G]) -> StopFlag
+    where
+        G: Genotype,
+        D: DiversityMetric<G>,
+    {
+        let diversity = self.metric.diversity(individuals);
+        if diversity < self.threshold {
+            StopFlag::StopNow(StopReason::DiversityLimitReached(format!(
+                "Simulation stopped after the diversity of the population \
+                 dropped to {} which is below the threshold of {}.",
+                diversity, self.threshold
+            )))
+        } else {
+            StopFlag::Continue
+        }
+    }
+}
+
+impl<G, F, E, S, C, M, R, P, D, T> Termination<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>
+    for DiversityLimit<T>
+where
+    G: Genotype,
+    F: Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Sync,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G> + Sync,
+    M: MutationOp<G> + Sync,
+    R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
+    T: DiversityMetric<G>,
+{
+    fn evaluate(&mut self, state: &State<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>) -> StopFlag {
+        self.evaluate_individuals(&state.result.evaluated_population.individuals())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genetic::ReversalIdentity;
+    use galvanic_assert::matchers::*;
+
+    /// Returns the generation in which the given limit stops the simulation.
+    fn stopped_at(
+        limit: &mut StagnationLimit<Vec<u8>, i32>,
+        fitness_values: &[i32],
+        minimize: bool,
+    ) -> Option<u64> {
+        fitness_values
+            .iter()
+            .position(|fitness| limit.evaluate_fitness(fitness, minimize) != StopFlag::Continue)
+            .map(|index| index as u64 + 1)
+    }
+
+    #[test]
+    fn stagnation_limit_stops_flat_fitness_sequence() {
+        let mut limit = StagnationLimit::new(5);
+
+        let generation = stopped_at(&mut limit, &[7; 20], false);
+
+        // the first generation sets the best fitness, the next 5 stagnate
+        expect_that!(&generation, eq(Some(6)));
+        expect_that!(&limit.stagnant_generations(), eq(5));
+    }
+
+    #[test]
+    fn stagnation_limit_restarts_counting_on_improvement() {
+        let mut limit = StagnationLimit::new(3);
+
+        let generation = stopped_at(&mut limit, &[1, 1, 1, 2, 2, 1, 2, 2], false);
+
+        expect_that!(&generation, eq(Some(7)));
+        expect_that!(&limit.best_fitness(), eq(Some(&2)));
+    }
+
+    #[test]
+    fn stagnation_limit_ignores_improvements_not_greater_than_epsilon() {
+        let mut limit = StagnationLimit::with_epsilon(3, 2);
+
+        let generation = stopped_at(&mut limit, &[10, 11, 12, 11, 20], false);
+
+        expect_that!(&generation, eq(Some(4)));
+        expect_that!(&limit.best_fitness(), eq(Some(&10)));
+    }
+
+    #[test]
+    fn stagnation_limit_treats_lower_fitness_as_improvement_when_minimizing() {
+        let mut limit = StagnationLimit::new(2);
+
+        let generation = stopped_at(&mut limit, &[10, 9, 8, 12, 15, 7, 7, 7], true);
+
+        expect_that!(&generation, eq(Some(5)));
+        expect_that!(&limit.best_fitness(), eq(Some(&8)));
+    }
+
+    #[test]
+    fn hamming_distance_averages_differing_loci_over_all_pairs() {
+        let individuals: Vec<Vec<u8>> = vec![vec![1, 2, 3], vec![1, 2, 4], vec![5, 2, 4]];
+
+        // pairs differ in 1, 2 and 1 loci
+        let diversity = HammingDistance.diversity(&individuals);
+
+        expect_that!(&(diversity - 4. / 3.).abs(), less_than(1e-9));
+    }
+
+    #[test]
+    fn euclidean_distance_averages_distances_over_all_pairs() {
+        let individuals: Vec<Vec<f64>> = vec![vec![0., 0.], vec![3., 4.], vec![3., 0.]];
+
+        // pairs have distances of 5, 3 and 4
+        let diversity = EuclideanDistance.diversity(&individuals);
+
+        expect_that!(&(diversity - 4.).abs(), less_than(1e-9));
+    }
+
+    #[test]
+    fn unique_fraction_counts_distinct_individuals() {
+        let individuals: Vec<Vec<u8>> = vec![vec![1, 2], vec![2, 1], vec![1, 2], vec![1, 2]];
+
+        let diversity = UniqueFraction.diversity(&individuals);
+
+        expect_that!(&diversity, eq(0.5));
+    }
+
+    #[test]
+    fn unique_key_fraction_counts_equivalent_individuals_once() {
+        let individuals: Vec<Vec<u8>> = vec![vec![1, 2], vec![2, 1], vec![1, 2], vec![3, 4]];
+
+        let diversity = UniqueKeyFraction::new(ReversalIdentity).diversity(&individuals);
+
+        expect_that!(&diversity, eq(0.5));
+    }
+
+    #[test]
+    fn diversity_limit_stops_population_of_identical_individuals() {
+        let collapsed_values: Vec<Vec<u8>> = vec![vec![3, 1, 4, 1]; 10];
+        let collapsed_bits: Vec<Vec<bool>> = vec![vec![true, false, true]; 10];
+        let collapsed_reals: Vec<Vec<f64>> = vec![vec![0.5, 1.5, -2.]; 10];
+
+        let hamming = DiversityLimit::new(HammingDistance, 0.1);
+        // 10 identical individuals have a unique fraction of 0.1
+        let unique_fraction = DiversityLimit::new(UniqueFraction, 0.2);
+        let euclidean = DiversityLimit::new(EuclideanDistance, 0.1);
+
+        expect_that!(
+            &hamming.evaluate_individuals(&collapsed_values),
+            not(eq(StopFlag::Continue))
+        );
+        expect_that!(
+            &hamming.evaluate_individuals(&collapsed_bits),
+            not(eq(StopFlag::Continue))
+        );
+        expect_that!(
+            &unique_fraction.evaluate_individuals(&collapsed_values),
+            not(eq(StopFlag::Continue))
+        );
+        expect_that!(
+            &unique_fraction.evaluate_individuals(&collapsed_bits),
+            not(eq(StopFlag::Continue))
+        );
+        expect_that!(
+            &euclidean.evaluate_individuals(&collapsed_reals),
+            not(eq(StopFlag::Continue))
+        );
+    }
+
+    #[test]
+    fn diversity_limit_continues_for_diverse_population() {
+        let individuals: Vec<Vec<u8>> = (0..10).map(|i| vec![i, i + 1, i + 2]).collect();
+
+        let limit = DiversityLimit::new(HammingDistance, 0.1);
+
+        expect_that!(
+            &limit.evaluate_individuals(&individuals),
+            eq(StopFlag::Continue)
+        );
+    }
+}
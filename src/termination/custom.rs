@@ -0,0 +1,114 @@
+//! The `custom` package provides `Termination` functions that stop the
+//! simulation based on user defined rules.
+//!
+//! Provided conditions are:
+//! * `FnTermination` - stops the simulation when a closure returns true.
+
+use crate::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithm,
+    genetic::{Fitness, FitnessFunction, Genotype, GenotypeToPhenotype},
+    operator::{CrossoverOp, MutationOp, ReinsertionOp, SelectionOp},
+    simulation::State,
+    termination::{StopFlag, StopReason, Termination},
+};
+use std::fmt;
+
+/// The `FnTermination` condition stops the simulation when the given closure
+/// returns true.
+///
+/// The closure is called once per generation after the generation has been
+/// processed. It is called with two arguments:
+/// 1. the `EvaluatedPopulation` of the current generation, i.e. the
+///    individuals and their fitness values as evaluated in this generation.
+/// 2. the number of the current generation. Generations are counted from 1
+///    and the number equals the `iteration` of the simulation `State`.
+///
+/// The closure may mutate its captured state, e.g. for counting or tracking
+/// values across generations. The description is included in the
+/// `StopReason`. It should explain the condition implemented by the closure.
+///
+/// The types of the closure arguments usually have to be annotated, e.g.
+///
+/// ```ignore
+/// FnTermination::with_description(
+///     |population: &EvaluatedPopulation<MyGenome, u32>, generation| {
+///         *population.highest_fitness() >= 95 && generation > 100
+///     },
+///     "the best fitness is at least 95 after 100 generations",
+/// )
+/// ```
+#[derive(Clone)]
+pub struct FnTermination<P> {
+    /// The closure that decides whether to stop the simulation.
+    predicate: P,
+    /// The description of the condition implemented by the closure.
+    description: String,
+}
+
+impl<P> FnTermination<P> {
+    /// Creates a new instance of `FnTermination` for the given closure with
+    /// a generic description.
+    pub fn new<G, F>(predicate: P) -> Self
+    where
+        G: Genotype,
+        F: Fitness,
+        P: FnMut(&EvaluatedPopulation<G, F>, u64) -> bool,
+    {
+        FnTermination::with_description(predicate, "the custom condition is met")
+    }
+
+    /// Creates a new instance of `FnTermination` for the given closure and
+    /// description of the condition implemented by the closure.
+    pub fn with_description<G, F, D>(predicate: P, description: D) -> Self
+    where
+        G: Genotype,
+        F: Fitness,
+        P: FnMut(&EvaluatedPopulation<G, F>, u64) -> bool,
+        D: Into<String>,
+    {
+        FnTermination {
+            predicate,
+            description: description.into(),
+        }
+    }
+
+    /// Returns the description of the condition implemented by the closure.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl<P> fmt::Debug for FnTermination<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FnTermination")
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+impl<G, F, E, S, C, M, R, P, D, T> Termination<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>
+    for FnTermination<T>
+where
+    G: Genotype,
+    F: Fitness + Send + Sync,
+    E: FitnessFunction<G, F> + Sync,
+    S: SelectionOp<G, F>,
+    C: CrossoverOp<G> + Sync,
+    M: MutationOp<G> + Sync,
+    R: ReinsertionOp<G, F>,
+    P: Clone + fmt::Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
+    T: FnMut(&EvaluatedPopulation<G, F>, u64) -> bool,
+{
+    fn evaluate(&mut self, state: &State<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>) -> StopFlag {
+        if (self.predicate)(&state.result.evaluated_population, state.iteration) {
+            StopFlag::StopNow(StopReason::Custom(format!(
+                "Simulation stopped in generation {} because {}.",
+                state.iteration, self.description
+            )))
+        } else {
+            StopFlag::Continue
+        }
+    }
+}
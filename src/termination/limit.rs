@@ -12,16 +12,20 @@
 use crate::{
     algorithm::Algorithm,
     ga::GeneticAlgorithm,
-    genetic::{Fitness, FitnessFunction, Genotype},
+    genetic::{Fitness, FitnessFunction, Genotype, GenotypeToPhenotype},
     operator::{CrossoverOp, MutationOp, ReinsertionOp, SelectionOp},
     simulation::State,
-    termination::{StopFlag, Termination},
+    statistic::now,
+    termination::{StopFlag, StopReason, Termination},
 };
-use chrono::{Duration, Local, DateTime};
-use std::marker::PhantomData;
+use std::{cmp::Ordering, fmt::Debug, marker::PhantomData, time::Duration};
 
 /// The `FitnessLimit` condition stops the simulation after a solution with
 /// a certain fitness has been found.
+///
+/// The fitness target is reached by a fitness greater than or equal to the
+/// target. If the `operator::SelectionOp` of the `GeneticAlgorithm` minimizes
+/// it is reached by a fitness lower than or equal to the target.
 #[derive(Clone, Debug, PartialEq)]
 pub struct FitnessLimit<G, F>
 where
@@ -53,7 +57,8 @@ where
     }
 }
 
-impl<G, F, E, S, C, M, R> Termination<GeneticAlgorithm<G, F, E, S, C, M, R>> for FitnessLimit<G, F>
+impl<G, F, E, S, C, M, R, P, D> Termination<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>
+    for FitnessLimit<G, F>
 where
     G: Genotype,
     F: Fitness + Send + Sync,
@@ -63,15 +68,22 @@ where
     C: CrossoverOp<G> + Sync,
     M: MutationOp<G> + Sync,
     R: ReinsertionOp<G, F>,
+    P: Clone + Debug + PartialEq,
+    D: GenotypeToPhenotype<G, P>,
 {
-    fn evaluate(&mut self, state: &State<GeneticAlgorithm<G, F, E, S, C, M, R>>) -> StopFlag {
-        let highest_fitness = &state.result.best_solution.solution.fitness;
-        if *highest_fitness >= self.fitness_target {
-            StopFlag::StopNow(format!(
+    fn evaluate(&mut self, state: &State<GeneticAlgorithm<G, F, E, S, C, M, R, P, D>>) -> StopFlag {
+        let best_fitness = &state.result.best_solution.solution.fitness;
+        if state
+            .result
+            .evaluated_population
+            .compare_fitness(best_fitness, &self.fitness_target)
+            != Ordering::Less
+        {
+            StopFlag::StopNow(StopReason::FitnessLimitReached(format!(
                 "Simulation stopped after a solution with a fitness of {:?} \
                  has been found.",
-                highest_fitness
-            ))
+                best_fitness
+            )))
         } else {
             StopFlag::Continue
         }
@@ -105,11 +117,11 @@ where
 {
     fn evaluate(&mut self, state: &State<A>) -> StopFlag {
         if state.iteration >= self.max_generations {
-            StopFlag::StopNow(format!(
+            StopFlag::StopNow(StopReason::GenerationLimitReached(format!(
                 "Simulation stopped after the limit of {} generations have \
                  been processed.",
                 &state.iteration
-            ))
+            )))
         } else {
             StopFlag::Continue
         }
@@ -119,6 +131,17 @@ where
 /// The `TimeLimit` condition stops the simulation after the specified time
 /// limit has been reached, i.e. the simulation is already running for the
 /// specified amount of time.
+///
+/// The time is measured as wall-clock time since the simulation has been
+/// started. In contrast to the accumulated `ProcessingTime` of the simulation
+/// the wall-clock time does not depend on the number of threads used for
+/// parallel processing. The condition is evaluated after each generation,
+/// thus the simulation may run longer than the time limit by the time needed
+/// to process one generation.
+///
+/// On `wasm32-unknown-unknown` the time can only be measured with the
+/// `wasm-bindgen` feature enabled, see `statistic::now`. Without it the time
+/// limit is never reached.
 #[allow(missing_copy_implementations)]
 #[derive(Clone, Debug, PartialEq)]
 pub struct TimeLimit {
@@ -144,13 +167,16 @@ where
     A: Algorithm,
 {
     fn evaluate(&mut self, state: &State<A>) -> StopFlag {
-        let duration = DateTime::<chrono::Utc>::default().signed_duration_since(state.started_at);
+        let duration = now()
+            .signed_duration_since(state.started_at)
+            .to_std()
+            .unwrap_or_default();
         if duration >= self.max_time {
-            StopFlag::StopNow(format!(
-                "Simulation stopped after running for {} which exceeds the \
-                 maximal runtime of {}.",
+            StopFlag::StopNow(StopReason::TimeLimit(format!(
+                "Simulation stopped after running for {:?} which exceeds the \
+                 maximal runtime of {:?}.",
                 &duration, &self.max_time
-            ))
+            )))
         } else {
             StopFlag::Continue
         }
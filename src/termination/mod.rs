@@ -23,9 +23,14 @@
 pub mod combinator;
 pub use self::combinator::{and, or};
 
+pub mod convergence;
+
+pub mod custom;
+
 pub mod limit;
 
 use crate::{algorithm::Algorithm, simulation::State};
+use std::fmt::{self, Display};
 
 /// The `StopFlag` is the result of the `Termination` function. It tells
 /// the simulation whether it shall stop or if it can continue.
@@ -40,13 +45,110 @@ pub enum StopFlag {
     Continue,
 }
 
-/// A `StopReason` should explain to the user of the simulation why the
-/// simulation has been stopped. Examples:
+/// A `StopReason` tells why the simulation has been stopped. The variant
+/// identifies the kind of condition that stopped the simulation, so that the
+/// caller can act on it. The message of the variant explains the reason to
+/// the user of the simulation. Examples:
 /// * "Simulation stopped after the maximum of 100 generations have been
 ///   processed"
 /// * "Simulation stopped after a solution with a fitness value of 81 has
 ///   been found which is above the target fitness of 80.
-pub type StopReason = String;
+///
+/// The `Display` implementation prints the message.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StopReason {
+    /// A solution has been found whose fitness reached the target fitness,
+    /// see `limit::FitnessLimit`.
+    FitnessLimitReached(String),
+    /// The maximum number of generations has been processed, see
+    /// `limit::GenerationLimit`.
+    GenerationLimitReached(String),
+    /// The simulation has been running for the maximum amount of time, see
+    /// `limit::TimeLimit`.
+    TimeLimit(String),
+    /// The best fitness has not improved for a number of generations, see
+    /// `convergence::StagnationLimit`.
+    Stagnation(String),
+    /// The diversity of the population dropped below a threshold, see
+    /// `convergence::DiversityLimit`.
+    DiversityLimitReached(String),
+    /// One of the islands of an island model has been stopped by its
+    /// `Termination` condition. Holds the index of the island and its
+    /// `StopReason`.
+    Island(usize, Box<StopReason>),
+    /// Several conditions stopped the simulation in the same generation,
+    /// e.g. both conditions of a `combinator::And`. Holds the reasons of the
+    /// conditions in the order in which the conditions have been combined.
+    Combined(Vec<StopReason>),
+    /// Any other condition, e.g. a `custom::FnTermination` or a condition
+    /// implemented by the user of the simulation.
+    Custom(String),
+}
+
+impl StopReason {
+    /// Combines the reasons of several conditions that stopped the
+    /// simulation in the same generation. Nested `StopReason::Combined`
+    /// reasons are flattened and a single reason is returned as it is.
+    pub fn combined(reasons: Vec<StopReason>) -> StopReason {
+        let mut flattened = Vec::with_capacity(reasons.len());
+        for reason in reasons {
+            match reason {
+                StopReason::Combined(inner) => flattened.extend(inner),
+                _ => flattened.push(reason),
+            }
+        }
+        if flattened.len() == 1 {
+            flattened.remove(0)
+        } else {
+            StopReason::Combined(flattened)
+        }
+    }
+
+    /// Returns the reasons of the single conditions that stopped the
+    /// simulation, i.e. the reasons held by a `StopReason::Combined` or
+    /// otherwise this reason only.
+    pub fn reasons(&self) -> &[StopReason] {
+        match self {
+            StopReason::Combined(reasons) => reasons,
+            _ => std::slice::from_ref(self),
+        }
+    }
+}
+
+impl Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StopReason::FitnessLimitReached(message)
+            | StopReason::GenerationLimitReached(message)
+            | StopReason::TimeLimit(message)
+            | StopReason::Stagnation(message)
+            | StopReason::DiversityLimitReached(message)
+            | StopReason::Custom(message) => f.write_str(message),
+            StopReason::Island(index, reason) => write!(f, "island {}: {}", index, reason),
+            StopReason::Combined(reasons) => {
+                for (index, reason) in reasons.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(" and ")?;
+                    }
+                    write!(f, "{}", reason)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl From<String> for StopReason {
+    fn from(message: String) -> Self {
+        StopReason::Custom(message)
+    }
+}
+
+impl<'a> From<&'a str> for StopReason {
+    fn from(message: &'a str) -> Self {
+        StopReason::Custom(message.to_string())
+    }
+}
 
 /// A `Termination` defines a condition when the `Simulation` shall stop.
 ///
@@ -19,7 +19,7 @@
 
 pub mod fmt;
 
-use crate::genetic::{AsScalar, Fitness};
+use crate::genetic::{AsScalar, Fitness, Penalizable};
 
 macro_rules! implement_fitness_for_signed_integer {
     ( $($t:ty),* ) => {
@@ -41,6 +41,13 @@ macro_rules! implement_fitness_for_signed_integer {
                     *self as f64
                 }
             }
+
+            impl Penalizable for $t {
+                fn penalized(&self, penalty: f64) -> $t {
+                    // round up so that any positive penalty lowers the value
+                    self.saturating_sub(penalty.max(0.).ceil() as $t)
+                }
+            }
         )*
     }
 }
@@ -70,8 +77,22 @@ macro_rules! implement_fitness_for_unsigned_integer {
                     *self as f64
                 }
             }
+
+            impl Penalizable for $t {
+                fn penalized(&self, penalty: f64) -> $t {
+                    // round up so that any positive penalty lowers the value
+                    self.saturating_sub(penalty.max(0.).ceil() as $t)
+                }
+            }
         )*
     }
 }
 
-implement_fitness_for_unsigned_integer!(u8, u16, u32, u64, usize);
\ No newline at end of file
+implement_fitness_for_unsigned_integer!(u8, u16, u32, u64, usize);
+
+impl AsScalar for f64 {
+    #[inline]
+    fn as_scalar(&self) -> f64 {
+        *self
+    }
+}
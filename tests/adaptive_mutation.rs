@@ -0,0 +1,66 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    operator::{prelude::*, MutationRate},
+    population::BinaryEncodedGenomeBuilder,
+    prelude::*,
+    random::get_rng,
+};
+
+type MyGenome = Vec<bool>;
+
+/// A fitness function that never improves, so that the simulation stagnates
+/// from the second generation on.
+#[derive(Clone, Debug, PartialEq)]
+struct ConstantFitness;
+
+impl FitnessFunction<MyGenome, u32> for ConstantFitness {
+    fn fitness_of(&self, _: &MyGenome) -> u32 {
+        1
+    }
+
+    fn average(&self, _: &[u32]) -> u32 {
+        1
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        2
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+#[test]
+fn genetic_algorithm_raises_mutation_rate_of_adaptive_mutator_during_stagnation() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(BinaryEncodedGenomeBuilder::new(8))
+        .of_size(20)
+        .uniform_at_random();
+    let mut algorithm = genetic_algorithm()
+        .with_evaluation(ConstantFitness)
+        .with_selection(MaximizeSelector::new(0.5, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(AdaptiveRateMutator::new(
+            BitFlipMutator::new(0.01),
+            StagnationController::new(0.001, 0.5, 2.),
+        ))
+        .with_reinsertion(ElitistReinserter::new(ConstantFitness, false, 0.5))
+        .with_initial_population(initial_population)
+        .build();
+    let mut rng = get_rng([42; 32]);
+
+    let mut rates = Vec::new();
+    for iteration in 1..=4 {
+        algorithm.next(iteration, &mut rng).unwrap();
+        rates.push(algorithm.mutator().mutation_rate());
+    }
+
+    // improved in the first generation, stagnated afterwards
+    assert_that!(&rates, eq(vec![0.005, 0.01, 0.02, 0.04]));
+}
@@ -4,49 +4,290 @@ extern crate galvanic_assert;
 
 use galvanic_assert::matchers::*;
 
-use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+use genevo::{
+    ga::GeneticAlgorithmError,
+    genetic::{Children, Parents},
+    operator::{prelude::*, CrossoverOp, GeneticOperator, MutationRate},
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    random::Rng,
+};
 
-#[test]
-fn create_new_genetic_algorithm_application() {
-    type MyGenome = Vec<f64>;
+type MyGenome = Vec<f64>;
 
-    #[derive(Clone, Debug, PartialEq)]
-    struct MyFitnessEvaluator;
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
 
-    impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
-        fn fitness_of(&self, individual: &MyGenome) -> u32 {
-            (individual.iter().sum::<f64>() * 10000. + 0.5).floor() as u32
-        }
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().sum::<f64>() * 10000. + 0.5).floor() as u32
+    }
 
-        fn average(&self, fitness_values: &[u32]) -> u32 {
-            (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
-                as u32
-        }
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
 
-        fn highest_possible_fitness(&self) -> u32 {
-            10000
-        }
+    fn highest_possible_fitness(&self) -> u32 {
+        10000
+    }
 
-        fn lowest_possible_fitness(&self) -> u32 {
-            0
-        }
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
     }
+}
 
-    let initial_population: Population<Vec<f64>> = build_population()
+fn initial_population() -> Population<MyGenome> {
+    build_population()
         .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
         .of_size(200)
-        .uniform_at_random();
+        .uniform_at_random()
+}
+
+/// A crossover that can only process exactly two parents.
+#[derive(Clone, Debug, PartialEq)]
+struct TwoParentsCrossover;
+
+impl GeneticOperator for TwoParentsCrossover {
+    fn name() -> String {
+        "Two-Parents-Crossover".to_string()
+    }
+}
+
+impl CrossoverOp<MyGenome> for TwoParentsCrossover {
+    fn crossover<R>(&self, parents: Parents<MyGenome>, _: &mut R) -> Children<MyGenome>
+    where
+        R: Rng + Sized,
+    {
+        let child1 = parents[0].clone();
+        let child2 = parents[1].clone();
+        vec![child1, child2]
+    }
 
+    fn required_parents_size(&self) -> Option<usize> {
+        Some(2)
+    }
+}
+
+#[test]
+fn create_new_genetic_algorithm_application() {
     let algorithm = genetic_algorithm()
         .with_evaluation(MyFitnessEvaluator)
         .with_selection(RouletteWheelSelector::new(0.7, 2))
         .with_crossover(MultiPointCrossBreeder::new(3))
         .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
         .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
-        .with_initial_population(initial_population)
+        .with_initial_population(initial_population())
         .build();
 
     assert_that!(&algorithm.selector().selection_ratio(), eq(0.7));
     assert_that!(&algorithm.selector().num_individuals_per_parents(), eq(2));
     assert_that!(&algorithm.breeder().num_cut_points(), eq(3));
 }
+
+#[test]
+fn builder_accepts_a_selector_that_selects_the_parents_size_the_crossover_requires() {
+    let result = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.7, 2, 4, 0.9, false))
+        .with_crossover(TwoParentsCrossover)
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .try_build();
+
+    assert_that!(&result.is_ok(), is(eq(true)));
+}
+
+#[test]
+fn builder_rejects_a_selector_that_selects_more_parents_than_the_crossover_requires() {
+    let result = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.7, 3, 4, 0.9, false))
+        .with_crossover(TwoParentsCrossover)
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .try_build();
+
+    match result {
+        Err(error) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::IncompatibleOperators(
+                "The selection operator Tournament-Selection selects parents of 3 individuals, \
+                 but the crossover operator Two-Parents-Crossover requires parents of 2 \
+                 individuals."
+                    .to_string()
+            ))
+        ),
+        Ok(_) => panic!("the builder should reject the operators"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "The selection operator Tournament-Selection selects parents of 3")]
+fn build_panics_if_the_operators_are_incompatible() {
+    genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.7, 3, 4, 0.9, false))
+        .with_crossover(TwoParentsCrossover)
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .build();
+}
+
+#[test]
+fn builder_rejects_a_count_of_the_selector_greater_than_the_population_size() {
+    let result = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(MaximizeSelector::with_count(201, 2))
+        .with_crossover(TwoParentsCrossover)
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .try_build();
+
+    match result {
+        Err(error) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::PopulationTooSmall(
+                "The selection operator Maximizing-Truncation-Selection requires a population \
+                 of at least 201 individuals, but the initial population has a size of 200."
+                    .to_string()
+            ))
+        ),
+        Ok(_) => panic!("the builder should reject the population"),
+    }
+}
+
+#[test]
+fn builder_accepts_a_count_of_the_selector_up_to_the_population_size() {
+    let result = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(MaximizeSelector::with_count(200, 2))
+        .with_crossover(TwoParentsCrossover)
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .try_build();
+
+    assert_that!(&result.is_ok(), is(eq(true)));
+}
+
+#[test]
+fn builder_rejects_a_selection_ratio_greater_than_1() {
+    let mut selector = RouletteWheelSelector::new(0.7, 2);
+    selector.set_selection_ratio(1.5);
+    let result = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(selector)
+        .with_crossover(MultiPointCrossBreeder::new(3))
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .try_build();
+
+    match result {
+        Err(error) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::InvalidRatio(
+                "The selection ratio of the operator Roulette-Wheel-Selection must be between \
+                 0 and 1, but is 1.5."
+                    .to_string()
+            ))
+        ),
+        Ok(_) => panic!("the builder should reject the selection ratio"),
+    }
+}
+
+#[test]
+fn builder_rejects_a_truncation_selection_ratio_of_0() {
+    let result = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(MaximizeSelector::new(0., 2))
+        .with_crossover(MultiPointCrossBreeder::new(3))
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .try_build();
+
+    match result {
+        Err(error) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::InvalidRatio(
+                "The selection ratio of the operator Maximizing-Truncation-Selection must be \
+                 greater than 0 and at most 1, but is 0."
+                    .to_string()
+            ))
+        ),
+        Ok(_) => panic!("the builder should reject the selection ratio"),
+    }
+}
+
+#[test]
+fn builder_rejects_a_negative_mutation_rate() {
+    let mut mutator = RandomValueMutator::new(0.015, -2.0, 2.0);
+    mutator.set_mutation_rate(-0.1);
+    let result = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(RouletteWheelSelector::new(0.7, 2))
+        .with_crossover(MultiPointCrossBreeder::new(3))
+        .with_mutation(mutator)
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .try_build();
+
+    match result {
+        Err(error) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::InvalidRatio(
+                "The mutation rate of the operator Random-Value-Mutator must be between 0 and \
+                 1, but is -0.1."
+                    .to_string()
+            ))
+        ),
+        Ok(_) => panic!("the builder should reject the mutation rate"),
+    }
+}
+
+#[test]
+fn builder_rejects_a_crossover_without_cut_points() {
+    let mut breeder = MultiPointCrossBreeder::new(3);
+    breeder.set_num_cut_points(0);
+    let result = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(RouletteWheelSelector::new(0.7, 2))
+        .with_crossover(breeder)
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .try_build();
+
+    match result {
+        Err(error) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::InvalidCount(
+                "The number of cut points of the operator Multi-Point-Cross-Breeder must be \
+                 greater than 0."
+                    .to_string()
+            ))
+        ),
+        Ok(_) => panic!("the builder should reject the number of cut points"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "The selection ratio of the operator Roulette-Wheel-Selection")]
+fn build_panics_if_a_parameter_of_an_operator_is_invalid() {
+    let mut selector = RouletteWheelSelector::new(0.7, 2);
+    selector.set_selection_ratio(1.5);
+    genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(selector)
+        .with_crossover(MultiPointCrossBreeder::new(3))
+        .with_mutation(RandomValueMutator::new(0.015, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, false, 0.7))
+        .with_initial_population(initial_population())
+        .build();
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    operator::{prelude::*, CrossoverOp, MutationOp},
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    random::get_rng,
+};
+
+const TARGET_TEXT: &str = "See how a genius creates a legend";
+
+type TextGenome = [u8; TARGET_TEXT.len()];
+
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessFunction<TextGenome, usize> for FitnessCalc {
+    fn fitness_of(&self, genome: &TextGenome) -> usize {
+        genome
+            .iter()
+            .zip(TARGET_TEXT.bytes())
+            .filter(|(c, t)| **c == *t)
+            .count()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        TARGET_TEXT.len()
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+#[test]
+fn simulation_evolves_array_genomes() {
+    let initial_population: Population<TextGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(TARGET_TEXT.len(), 32, 126))
+        .of_size(200)
+        .using_seed([11; 32]);
+    let initial_best = initial_population
+        .individuals()
+        .iter()
+        .map(|genome| FitnessCalc.fitness_of(genome))
+        .max()
+        .unwrap();
+
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(FitnessCalc)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(MultiPointCrossBreeder::new(5))
+            .with_mutation(RandomValueMutator::new(0.02, 32, 126))
+            .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+            .with_initial_population(initial_population)
+            .build(),
+    )
+    .until(or(
+        FitnessLimit::new(FitnessCalc.highest_possible_fitness()),
+        GenerationLimit::new(300),
+    ))
+    .build_with_seed([42; 32]);
+
+    let result = simulation.run().expect("simulation failed");
+
+    match result {
+        SimResult::Final(state, _, _, _) => {
+            let best_fitness = state.result.best_solution.solution.fitness;
+            assert_that!(&best_fitness, is(greater_than(initial_best + 10)));
+        },
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    }
+}
+
+#[test]
+fn bit_flip_mutator_and_uniform_cross_breeder_accept_array_genomes() {
+    let mut rng = get_rng([42; 32]);
+    let parents = vec![[true; 16], [false; 16]];
+
+    let children = CrossoverOp::crossover(&UniformCrossBreeder::new(), parents, &mut rng);
+    let mutated = BitFlipMutator::new(1.0).mutate(children[0], &mut rng);
+
+    let flipped: Vec<bool> = children[0].iter().map(|bit| !bit).collect();
+    assert_that!(&mutated.to_vec(), eq(flipped));
+}
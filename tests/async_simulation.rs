@@ -0,0 +1,179 @@
+// the `AsyncSimulator` runs the simulation on a thread, which is not supported on wasm32
+#![cfg(not(target_arch = "wasm32"))]
+
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    simulation::{simulator::SimError, AsyncSimulator},
+};
+use std::{
+    sync::mpsc::{RecvTimeoutError, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+type MyAlgorithm = GeneticAlgorithm<
+    MyGenome,
+    u32,
+    MyFitnessEvaluator,
+    MaximizeSelector,
+    UniformCrossBreeder,
+    GaussianMutator<MyGenome>,
+    ElitistReinserter<MyGenome, u32, MyFitnessEvaluator>,
+>;
+
+fn spawn(
+    capacity: Option<usize>,
+    generation_limit: u64,
+) -> AsyncSimulator<MyAlgorithm, SimError<MyAlgorithm>> {
+    let factory = move || {
+        let initial_population: Population<MyGenome> = build_population()
+            .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+            .of_size(20)
+            .using_seed([7; 32]);
+        simulate(
+            genetic_algorithm()
+                .with_evaluation(MyFitnessEvaluator)
+                .with_selection(MaximizeSelector::new(0.7, 2))
+                .with_crossover(UniformCrossBreeder::new())
+                .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+                .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+                .with_initial_population(initial_population)
+                .build(),
+        )
+        .until(GenerationLimit::new(generation_limit))
+        .build_with_seed([42; 32])
+    };
+    match capacity {
+        Some(capacity) => AsyncSimulator::spawn_bounded(capacity, factory),
+        None => AsyncSimulator::spawn(factory),
+    }
+}
+
+fn iteration(result: &Result<SimResult<MyAlgorithm>, SimError<MyAlgorithm>>) -> u64 {
+    match result {
+        Ok(SimResult::Intermediate(state)) => state.iteration,
+        Ok(SimResult::Final(state, _, _, _)) => state.iteration,
+        Err(error) => panic!("simulation failed: {}", error),
+    }
+}
+
+#[test]
+fn the_simulation_is_started_yields_some_results_and_is_stopped_cleanly() {
+    let simulator = spawn(Some(2), 1_000_000);
+
+    let received: Vec<u64> = (0..5)
+        .map(|_| iteration(&simulator.results().recv().expect("a result is received")))
+        .collect();
+    let remaining = simulator.stop();
+
+    assert_that!(&received, eq(vec![1, 2, 3, 4, 5]));
+    // the bounded channel buffers at most 2 results plus the one processed
+    // while stopping
+    assert_that!(&remaining.len(), is(less_than_or_equal(3)));
+    let remaining: Vec<u64> = remaining.iter().map(iteration).collect();
+    let expected: Vec<u64> = (6..6 + remaining.len() as u64).collect();
+    assert_that!(&remaining, eq(expected));
+}
+
+#[test]
+fn the_simulation_yields_one_result_per_generation_until_it_is_finished() {
+    let simulator = spawn(None, 10);
+
+    let results: Vec<_> = simulator.results().iter().collect();
+
+    let iterations: Vec<u64> = results.iter().map(iteration).collect();
+    assert_that!(&iterations, eq((1..=10).collect::<Vec<u64>>()));
+    for result in &results[..9] {
+        assert_that!(
+            &matches!(result, Ok(SimResult::Intermediate(_))),
+            is(eq(true))
+        );
+    }
+    assert_that!(
+        &matches!(results[9], Ok(SimResult::Final(..))),
+        is(eq(true))
+    );
+    assert_that!(&simulator.stop().is_empty(), is(eq(true)));
+}
+
+#[test]
+fn a_paused_simulation_yields_no_results_until_it_is_resumed() {
+    let simulator = spawn(None, 1_000_000);
+    let first = simulator.results().recv().expect("a result is received");
+    assert_that!(&iteration(&first), eq(1));
+
+    simulator.pause();
+    assert_that!(&simulator.is_paused(), is(eq(true)));
+    // drain the result of the step that was processed while pausing
+    let mut last_iteration = 1;
+    loop {
+        match simulator.results().recv_timeout(Duration::from_millis(100)) {
+            Ok(result) => last_iteration = iteration(&result),
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => panic!("simulation is finished"),
+        }
+    }
+    thread::sleep(Duration::from_millis(50));
+    assert_that!(
+        &simulator.results().try_recv().err(),
+        eq(Some(TryRecvError::Empty))
+    );
+
+    simulator.resume();
+    assert_that!(&simulator.is_paused(), is(eq(false)));
+    let next = simulator.results().recv().expect("a result is received");
+    assert_that!(&iteration(&next), eq(last_iteration + 1));
+    simulator.stop();
+}
+
+#[test]
+fn a_paused_simulation_is_stopped_cleanly() {
+    let simulator = spawn(Some(0), 1_000_000);
+    let first = simulator.results().recv().expect("a result is received");
+    assert_that!(&iteration(&first), eq(1));
+    simulator.pause();
+
+    let remaining = simulator.stop();
+
+    assert_that!(&remaining.len(), is(less_than_or_equal(1)));
+}
+
+#[test]
+fn dropping_the_simulator_stops_the_simulation() {
+    let simulator = spawn(Some(1), 1_000_000);
+    let first = simulator.results().recv().expect("a result is received");
+    assert_that!(&iteration(&first), eq(1));
+
+    drop(simulator);
+}
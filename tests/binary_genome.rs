@@ -0,0 +1,153 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genotype::BinaryGenome,
+    operator::{prelude::*, CrossoverOp, MutationOp},
+    population::{BinaryEncodedGenomeBuilder, GenomeBuilder},
+    prelude::*,
+    random::get_rng,
+};
+use std::fmt::Debug;
+
+const GENOME_LENGTH: usize = 100;
+
+/// The OneMax problem: the fitness is the number of bits set to `true`.
+#[derive(Clone, Debug)]
+struct OneMax;
+
+impl FitnessFunction<Vec<bool>, usize> for OneMax {
+    fn fitness_of(&self, genome: &Vec<bool>) -> usize {
+        genome.iter().filter(|bit| **bit).count()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        GENOME_LENGTH
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+impl FitnessFunction<BinaryGenome, usize> for OneMax {
+    fn fitness_of(&self, genome: &BinaryGenome) -> usize {
+        genome.count_ones()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        GENOME_LENGTH
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+/// Runs OneMax for 30 generations and returns the best fitness and the best
+/// genome of each generation.
+fn evolve<G, C>(breeder: C, to_bits: fn(&G) -> Vec<bool>) -> Vec<(usize, Vec<bool>)>
+where
+    G: Genotype,
+    OneMax: FitnessFunction<G, usize> + Send + Sync,
+    C: CrossoverOp<G> + Debug + Sync,
+    BitFlipMutator: MutationOp<G>,
+    BinaryEncodedGenomeBuilder: GenomeBuilder<G>,
+{
+    let initial_population: Population<G> = build_population()
+        .with_genome_builder(BinaryEncodedGenomeBuilder::new(GENOME_LENGTH))
+        .of_size(40)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(OneMax)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(breeder)
+            .with_mutation(BitFlipMutator::new(0.01))
+            .with_reinsertion(ElitistReinserter::new(OneMax, true, 0.7))
+            .with_initial_population(initial_population)
+            .build(),
+    )
+    .until(GenerationLimit::new(30))
+    .build_with_seed([42; 32]);
+
+    let mut generations = Vec::new();
+    loop {
+        let result = simulation.step().expect("simulation failed");
+        let state = match &result {
+            SimResult::Intermediate(state) => state,
+            SimResult::Final(state, _, _, _) => state,
+        };
+        let best_solution = &state.result.best_solution.solution;
+        generations.push((best_solution.fitness, to_bits(&best_solution.genome)));
+        if let SimResult::Final(..) = result {
+            break;
+        }
+    }
+    generations
+}
+
+#[test]
+fn binary_genome_evolves_like_vec_of_bool_with_uniform_crossover() {
+    let vec_generations = evolve::<Vec<bool>, _>(UniformCrossBreeder::new(), Clone::clone);
+    let binary_generations =
+        evolve::<BinaryGenome, _>(UniformCrossBreeder::new(), BinaryGenome::to_vec);
+
+    assert_that!(&binary_generations.len(), eq(30));
+    assert_that!(&binary_generations, eq(vec_generations.clone()));
+    // the population improves over the generations
+    assert_that!(
+        &binary_generations.last().unwrap().0,
+        is(greater_than(binary_generations[0].0))
+    );
+}
+
+#[test]
+fn binary_genome_evolves_like_vec_of_bool_with_multi_point_crossover() {
+    let vec_generations = evolve::<Vec<bool>, _>(MultiPointCrossBreeder::new(5), Clone::clone);
+    let binary_generations =
+        evolve::<BinaryGenome, _>(MultiPointCrossBreeder::new(5), BinaryGenome::to_vec);
+
+    assert_that!(&binary_generations, eq(vec_generations));
+}
+
+#[test]
+fn binary_genome_is_crossed_over_like_vec_of_bool_across_word_boundaries() {
+    let vec_parents: Vec<Vec<bool>> = vec![vec![true; 200], vec![false; 200], vec![true; 200]];
+    let binary_parents: Vec<BinaryGenome> = vec_parents
+        .iter()
+        .map(|bits| BinaryGenome::from(&bits[..]))
+        .collect();
+    let breeder = MultiPointCrossBreeder::new(7);
+
+    for seed in 0..20 {
+        let vec_children = breeder.crossover(vec_parents.clone(), &mut get_rng([seed; 32]));
+        let binary_children = breeder.crossover(binary_parents.clone(), &mut get_rng([seed; 32]));
+
+        let binary_children: Vec<Vec<bool>> =
+            binary_children.iter().map(BinaryGenome::to_vec).collect();
+        assert_that!(&binary_children, eq(vec_children));
+    }
+}
+
+#[test]
+fn bit_flip_mutator_flips_bits_of_binary_genome_like_vec_of_bool() {
+    let mutator = BitFlipMutator::new(0.3);
+    let bits: Vec<bool> = (0..150).map(|i| i % 5 == 0).collect();
+
+    let vec_mutated = mutator.mutate(bits.clone(), &mut get_rng([42; 32]));
+    let binary_mutated = mutator.mutate(BinaryGenome::from(&bits[..]), &mut get_rng([42; 32]));
+
+    assert_that!(&binary_mutated.to_vec(), eq(vec_mutated));
+}
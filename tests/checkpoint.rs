@@ -0,0 +1,104 @@
+#![cfg(feature = "serde")]
+
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    ga::GeneticAlgorithmState, operator::prelude::*, population::ValueEncodedGenomeBuilder,
+    prelude::*, simulation::SimState,
+};
+
+const TARGET_TEXT: &str = "See how a genius creates a legend";
+
+type TextGenome = Vec<u8>;
+
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessFunction<TextGenome, usize> for FitnessCalc {
+    fn fitness_of(&self, genome: &TextGenome) -> usize {
+        genome
+            .iter()
+            .zip(TARGET_TEXT.bytes())
+            .filter(|(c, t)| **c == *t)
+            .count()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        TARGET_TEXT.len()
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+macro_rules! simulator_builder {
+    () => {{
+        let initial_population: Population<TextGenome> = build_population()
+            .with_genome_builder(ValueEncodedGenomeBuilder::new(TARGET_TEXT.len(), 32, 126))
+            .of_size(100)
+            .using_seed([3; 32]);
+        simulate(
+            genetic_algorithm()
+                .with_evaluation(FitnessCalc)
+                .with_selection(TournamentSelector::new(0.7, 2, 3, 0.9, false))
+                .with_crossover(MultiPointCrossBreeder::new(4))
+                .with_mutation(RandomValueMutator::new(0.01, 32, 126))
+                .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+                .with_initial_population(initial_population)
+                .build(),
+        )
+        .until(GenerationLimit::new(200))
+    }};
+}
+
+macro_rules! next_best_solutions {
+    ($simulation:expr, $num_steps:expr) => {{
+        let mut best_solutions = Vec::new();
+        for _ in 0..$num_steps {
+            match $simulation.step().expect("simulation step failed") {
+                SimResult::Intermediate(state) => {
+                    best_solutions.push((state.iteration, state.result.best_solution))
+                },
+                SimResult::Final(..) => panic!("simulation finished unexpectedly"),
+            }
+        }
+        best_solutions
+    }};
+}
+
+#[test]
+fn restored_simulation_continues_like_an_uninterrupted_simulation() {
+    let mut uninterrupted = simulator_builder!().build_with_seed([42; 32]);
+    let mut interrupted = simulator_builder!().build_with_seed([42; 32]);
+
+    next_best_solutions!(uninterrupted, 50);
+    next_best_solutions!(interrupted, 50);
+
+    let saved = serde_json::to_string(&interrupted.save_state()).unwrap();
+    drop(interrupted);
+    let state: SimState<GeneticAlgorithmState<TextGenome, usize>> =
+        serde_json::from_str(&saved).unwrap();
+    let mut restored =
+        genevo::simulation::simulator::Simulator::restore(simulator_builder!(), state);
+
+    assert_that!(
+        &serde_json::to_string(&restored.save_state()).unwrap(),
+        eq(saved)
+    );
+    assert_that!(
+        &next_best_solutions!(restored, 10),
+        eq(next_best_solutions!(uninterrupted, 10))
+    );
+    assert_that!(
+        &restored.algorithm().best_solution(),
+        eq(uninterrupted.algorithm().best_solution())
+    );
+}
@@ -0,0 +1,90 @@
+//! Fitness functions and helpers that are shared by the integration tests.
+//!
+//! Each integration test is compiled as its own crate, which uses only some
+//! of the items in this module.
+#![allow(dead_code)]
+
+use genevo::{
+    genotype::SelfAdaptive,
+    prelude::*,
+    simulation::{simulator::Simulator, State},
+    statistic::TrackProcessingTime,
+    termination::Termination,
+};
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+/// The fitness is the sum of the loci, which is between 0 and the sum of the
+/// highest values of all loci.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SumOfLoci {
+    highest_possible_fitness: u32,
+}
+
+impl SumOfLoci {
+    /// Constructs a new instance of `SumOfLoci` for genomes of `num_loci`
+    /// loci with values up to `max_value`.
+    pub fn new(num_loci: u32, max_value: u32) -> Self {
+        SumOfLoci {
+            highest_possible_fitness: num_loci * max_value,
+        }
+    }
+}
+
+impl FitnessFunction<Vec<u8>, u32> for SumOfLoci {
+    fn fitness_of(&self, genome: &Vec<u8>) -> u32 {
+        genome.iter().map(|locus| *locus as u32).sum()
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        self.highest_possible_fitness
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+impl FitnessFunction<SelfAdaptive<Vec<u8>>, u32> for SumOfLoci {
+    fn fitness_of(&self, genome: &SelfAdaptive<Vec<u8>>) -> u32 {
+        self.fitness_of(genome.genome())
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        FitnessFunction::<Vec<u8>, u32>::average(self, fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        self.highest_possible_fitness
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Runs the simulation until it is finished by `Simulator::run_with` and
+/// returns what `record` takes from the `State` of each generation.
+///
+/// The `State` of a generation is dropped before the next generation is
+/// processed, so that recording only parts of it does not keep its genomes
+/// alive.
+pub fn run_generations<A, T, X, C>(simulation: &mut Simulator<A, T>, mut record: C) -> Vec<X>
+where
+    A: Algorithm + TrackProcessingTime + Debug,
+    <A as Algorithm>::Error: Eq + Hash + Display + Send + Sync,
+    T: Termination<A>,
+    C: FnMut(&State<A>) -> X,
+{
+    let mut generations = Vec::new();
+    simulation
+        .run_with(|state| generations.push(record(state)))
+        .expect("simulation failed");
+    generations
+}
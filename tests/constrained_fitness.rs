@@ -0,0 +1,60 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genetic::{ConstrainedFitness, PenaltyMethod},
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+};
+
+type MyGenome = Vec<u8>;
+
+#[test]
+fn the_genetic_algorithm_advances_the_generation_of_the_penalty() {
+    let evaluator = ConstrainedFitness::new(
+        SumOfLoci::new(4, 10),
+        PenaltyMethod::GenerationScaled {
+            coefficient: 1.,
+            exponent: 1.,
+        },
+    )
+    .with_constraint(|genome: &MyGenome| {
+        genome.iter().map(|locus| *locus as f64).sum::<f64>() - 20.
+    });
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, 0, 10))
+        .of_size(20)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(evaluator.clone())
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+            .with_reinsertion(UniformReinserter::new(1.0))
+            .with_initial_population(initial_population)
+            .build(),
+    )
+    .until(GenerationLimit::new(3))
+    .build_with_seed([42; 32]);
+
+    let generations = run_generations(&mut simulation, |state| {
+        assert_that!(&evaluator.generation(), eq(state.iteration));
+        let evaluated = &state.result.evaluated_population;
+        let expected: Vec<u32> = evaluated
+            .individuals()
+            .iter()
+            .map(|genome| evaluator.fitness_of(genome))
+            .collect();
+        assert_that!(&evaluated.fitness_values().to_vec(), eq(expected));
+        state.iteration
+    });
+    assert_that!(&generations, eq(vec![1, 2, 3]));
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+
+type MyGenome = Vec<f64>;
+
+/// A fitness function with two peaks of equal height at -1 and 1.
+#[derive(Clone, Debug, PartialEq)]
+struct TwoPeaks;
+
+impl FitnessFunction<MyGenome, u32> for TwoPeaks {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        let distance_to_peak = individual[0].abs() - 1.;
+        ((1. - distance_to_peak * distance_to_peak).max(0.) * 1000. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1000
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+#[test]
+fn deterministic_crowding_maintains_both_peaks_of_a_two_peak_function() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(1, -2., 2.))
+        .of_size(40)
+        .using_seed([7; 32]);
+
+    let algorithm = genetic_algorithm()
+        .with_evaluation(TwoPeaks)
+        .with_selection(RouletteWheelSelector::new(1.0, 2))
+        .with_crossover(BlendCrossBreeder::with_bounds(0.5, -2., 2.))
+        .with_mutation(GaussianMutator::new(0.2, 0.05, -2., 2.))
+        .with_reinsertion(DeterministicCrowding::new(TwoPeaks, EuclideanDistance))
+        .with_initial_population(initial_population)
+        .build();
+
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(100))
+        .with_rng_seed(42)
+        .build();
+
+    let population = loop {
+        if let SimResult::Final(state, _, _, _) = simulation.step().expect("simulation failed") {
+            break state.result.evaluated_population.individuals();
+        }
+    };
+
+    let near_left_peak = population
+        .iter()
+        .filter(|genome| (genome[0] + 1.).abs() < 0.2)
+        .count();
+    let near_right_peak = population
+        .iter()
+        .filter(|genome| (genome[0] - 1.).abs() < 0.2)
+        .count();
+    assert_that!(&population.len(), eq(40));
+    assert_that!(&near_left_peak, geq(10));
+    assert_that!(&near_right_peak, geq(10));
+}
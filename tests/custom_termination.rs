@@ -0,0 +1,94 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    algorithm::EvaluatedPopulation, operator::prelude::*, population::ValueEncodedGenomeBuilder,
+    prelude::*,
+};
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+#[test]
+fn fn_termination_stops_the_simulation_when_the_closure_returns_true() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(100)
+        .using_seed([7; 32]);
+
+    let algorithm = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.8, 2, 4, 0.9, false))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+        .with_initial_population(initial_population)
+        .build();
+
+    let mut generations_seen = Vec::new();
+    let mut simulation = simulate(algorithm)
+        .until(or(
+            GenerationLimit::new(200),
+            FnTermination::with_description(
+                |population: &EvaluatedPopulation<MyGenome, u32>, generation| {
+                    generations_seen.push(generation);
+                    *population.highest_fitness() >= 1200 && generation > 10
+                },
+                "the best fitness is at least 1200 after 10 generations",
+            ),
+        ))
+        .with_rng_seed(42)
+        .build();
+
+    let final_generation = match simulation.run().expect("simulation failed") {
+        SimResult::Final(state, _, _, stop_reason) => {
+            assert_that!(&state.iteration, gt(10));
+            assert_that!(&state.iteration, lt(200));
+            assert_that!(
+                &state.result.evaluated_population.highest_fitness(),
+                geq(&1200)
+            );
+            assert_that!(
+                &stop_reason,
+                eq(StopReason::Custom(format!(
+                    "Simulation stopped in generation {} because the best fitness is at \
+                     least 1200 after 10 generations.",
+                    state.iteration
+                )))
+            );
+            state.iteration
+        },
+        SimResult::Intermediate(_) => panic!("simulation did not finish"),
+    };
+    drop(simulation);
+
+    // the closure is called once for every generation
+    assert_that!(
+        &generations_seen,
+        eq((1..=final_generation).collect::<Vec<_>>())
+    );
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genotype::{Diploid, DiploidDecoder, Dominance, ExpressedFitness},
+    operator::prelude::*,
+    prelude::*,
+};
+
+const GENOME_LENGTH: usize = 20;
+
+/// The OneMax problem: the fitness is the number of bits set to `true`.
+#[derive(Clone, Debug)]
+struct OneMax;
+
+impl FitnessFunction<Vec<bool>, usize> for OneMax {
+    fn fitness_of(&self, genome: &Vec<bool>) -> usize {
+        genome.iter().filter(|bit| **bit).count()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        GENOME_LENGTH
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+/// The allele `true` is dominant, the deleterious allele `false` is only
+/// expressed if both homologous genomes carry it.
+#[derive(Clone, Debug)]
+struct TrueIsDominant;
+
+impl Dominance<Vec<bool>> for TrueIsDominant {
+    fn express(&self, first: &Vec<bool>, second: &Vec<bool>) -> Vec<bool> {
+        first.iter().zip(second).map(|(a, b)| *a || *b).collect()
+    }
+}
+
+#[test]
+fn a_recessive_deleterious_allele_persists_while_the_expressed_fitness_stays_high() {
+    // every individual is heterozygous at every locus
+    let initial_population = Population::with_individuals(
+        (0..30)
+            .map(|_| Diploid::new(vec![true; GENOME_LENGTH], vec![false; GENOME_LENGTH]))
+            .collect(),
+    );
+    let fitness = ExpressedFitness::new(OneMax, TrueIsDominant);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(fitness.clone())
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(DiploidCrossBreeder::new(UniformCrossBreeder::new()))
+            .with_mutation(DiploidMutator::new(BitFlipMutator::new(0.0)))
+            .with_reinsertion(ElitistReinserter::new(fitness, true, 0.7))
+            .with_initial_population(initial_population)
+            .with_decoder(DiploidDecoder::new(TrueIsDominant))
+            .build(),
+    )
+    .until(GenerationLimit::new(20))
+    .build_with_seed([42; 32]);
+
+    let (best_solution, population) = loop {
+        match simulation.step().expect("simulation failed") {
+            SimResult::Intermediate(_) => {},
+            SimResult::Final(state, _, _, _) => {
+                break (
+                    state.result.best_solution,
+                    state.result.evaluated_population,
+                )
+            },
+        }
+    };
+
+    assert_that!(&best_solution.solution.fitness, eq(GENOME_LENGTH));
+    assert_that!(&best_solution.phenotype, eq(vec![true; GENOME_LENGTH]));
+    let recessive_alleles: usize = population
+        .individuals()
+        .iter()
+        .map(|diploid| {
+            diploid
+                .first()
+                .iter()
+                .chain(diploid.second())
+                .filter(|bit| !**bit)
+                .count()
+        })
+        .sum();
+    // the deleterious allele is still carried by many individuals
+    assert_that!(&recessive_alleles, is(greater_than(50)));
+    let expressed_fitness: Vec<usize> = population
+        .individuals()
+        .iter()
+        .map(|diploid| OneMax.fitness_of(&diploid.express(&TrueIsDominant)))
+        .collect();
+    assert_that!(
+        &(expressed_fitness.iter().sum::<usize>() / expressed_fitness.len()),
+        is(greater_than(GENOME_LENGTH * 9 / 10))
+    );
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    algorithm::EvaluatedPopulation, operator::prelude::*, population::ValueEncodedGenomeBuilder,
+    prelude::*,
+};
+use std::cmp::Reverse;
+
+type MyGenome = Vec<f64>;
+
+const ELITE_COUNT: usize = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Returns the `ELITE_COUNT` best individuals of the given generation.
+fn elites(population: &EvaluatedPopulation<MyGenome, u32>) -> Vec<MyGenome> {
+    let mut evaluated: Vec<(u32, MyGenome)> = population
+        .fitness_values()
+        .iter()
+        .cloned()
+        .zip(population.individuals().iter().cloned())
+        .collect();
+    evaluated.sort_by_key(|(fitness, _)| Reverse(*fitness));
+    evaluated
+        .into_iter()
+        .take(ELITE_COUNT)
+        .map(|(_, genome)| genome)
+        .collect()
+}
+
+#[test]
+fn the_elites_survive_intact_in_every_generation() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(20)
+        .using_seed([7; 32]);
+
+    let algorithm = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(1.0, 2, 4, 0.9, false))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(GaussianMutator::new(0.5, 0.5, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::with_elite_count(
+            MyFitnessEvaluator,
+            ELITE_COUNT,
+        ))
+        .with_initial_population(initial_population)
+        .build();
+
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(30))
+        .build_with_seed([42; 32]);
+
+    let mut previous_elites: Option<Vec<MyGenome>> = None;
+    let mut num_generations = 0;
+    loop {
+        let (state, is_final) = match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => (state, false),
+            SimResult::Final(state, _, _, _) => (state, true),
+        };
+        let population = &state.result.evaluated_population;
+        if let Some(previous_elites) = previous_elites {
+            for elite in &previous_elites {
+                assert_that!(&population.individuals().contains(elite), is(eq(true)));
+            }
+        }
+        previous_elites = Some(elites(population));
+        num_generations += 1;
+        if is_final {
+            break;
+        }
+    }
+    assert_that!(&num_generations, eq(30));
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genotype::tree::{ExpressionTree, GrowthMethod, PrimitiveSet},
+    operator::prelude::*,
+    population::ExpressionTreeBuilder,
+    prelude::*,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Primitive {
+    Add,
+    Sub,
+    Mul,
+    X,
+    One,
+}
+
+type MyGenome = ExpressionTree<Primitive>;
+
+const MAX_DEPTH: usize = 6;
+
+fn primitives() -> PrimitiveSet<Primitive> {
+    PrimitiveSet::new(
+        vec![
+            (Primitive::Add, 2),
+            (Primitive::Sub, 2),
+            (Primitive::Mul, 2),
+        ],
+        vec![Primitive::X, Primitive::One],
+    )
+}
+
+fn evaluate(tree: &MyGenome, x: f64) -> f64 {
+    let argument = |index: usize| evaluate(&tree.children()[index], x);
+    match tree.value() {
+        Primitive::Add => argument(0) + argument(1),
+        Primitive::Sub => argument(0) - argument(1),
+        Primitive::Mul => argument(0) * argument(1),
+        Primitive::X => x,
+        Primitive::One => 1.,
+    }
+}
+
+/// Rewards trees that approximate `x^4 + x^3 + x^2 + x` at some sample points.
+#[derive(Clone, Debug, PartialEq)]
+struct SymbolicRegression;
+
+impl FitnessFunction<MyGenome, usize> for SymbolicRegression {
+    fn fitness_of(&self, genome: &MyGenome) -> usize {
+        let error: f64 = (-4..=4)
+            .map(|step| {
+                let x = step as f64 / 2.;
+                (evaluate(genome, x) - (x.powi(4) + x.powi(3) + x * x + x)).abs()
+            })
+            .sum();
+        (1000. / (1. + error)) as usize
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        1000
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+#[test]
+fn expression_trees_evolve_towards_the_target_within_the_depth_limit() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SymbolicRegression)
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(SubtreeCrossBreeder::new(MAX_DEPTH))
+        .with_mutation(SubtreeMutator::new(0.2, primitives(), MAX_DEPTH))
+        .with_reinsertion(ElitistReinserter::new(SymbolicRegression, false, 0.7))
+        .with_population_generator(ExpressionTreeBuilder::new(
+            primitives(),
+            2,
+            4,
+            GrowthMethod::RampedHalfAndHalf,
+        ))
+        .with_population_size(100)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(or(FitnessLimit::new(1000), GenerationLimit::new(100)))
+        .build_with_seed([42; 32]);
+
+    let mut depths = Vec::new();
+    let mut best_fitness = Vec::new();
+    loop {
+        let (state, done) = match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => (state, false),
+            SimResult::Final(state, _, _, _) => (state, true),
+        };
+        let individuals = state.result.evaluated_population.individuals();
+        depths.extend(individuals.iter().map(ExpressionTree::depth));
+        best_fitness.push(state.result.best_solution.solution.fitness);
+        if done {
+            break;
+        }
+    }
+
+    for depth in &depths {
+        assert_that!(depth, leq(MAX_DEPTH));
+    }
+    assert_that!(
+        best_fitness.last().unwrap(),
+        is(greater_than(best_fitness[0]))
+    );
+}
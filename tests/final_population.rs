@@ -0,0 +1,107 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{ga::State, operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+
+const POPULATION_SIZE: usize = 40;
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Runs the simulation for 10 generations and returns the final state.
+fn final_state(full_final_population: bool) -> State<MyGenome, u32> {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(POPULATION_SIZE)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(MyFitnessEvaluator)
+            .with_selection(TournamentSelector::new(0.8, 2, 4, 0.9, false))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+            .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+            .with_initial_population(initial_population)
+            .with_full_final_population(full_final_population)
+            .build(),
+    )
+    .until(GenerationLimit::new(10))
+    .build_with_seed([42; 32]);
+
+    loop {
+        match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => {
+                assert_that!(&state.result.final_population, eq(None));
+            },
+            SimResult::Final(state, _, _, _) => break state.result,
+        }
+    }
+}
+
+#[test]
+fn final_result_contains_the_full_population_with_the_computed_fitness_values() {
+    let state = final_state(true);
+
+    let final_population = state
+        .final_population
+        .expect("the final population is reported");
+    assert_that!(&final_population.len(), eq(POPULATION_SIZE));
+    let individuals: Vec<MyGenome> = final_population
+        .iter()
+        .map(|(genome, _)| genome.clone())
+        .collect();
+    let fitness_values: Vec<u32> = final_population
+        .iter()
+        .map(|(_, fitness)| *fitness)
+        .collect();
+    assert_that!(
+        &individuals,
+        eq(state.evaluated_population.individuals().to_vec())
+    );
+    assert_that!(
+        &fitness_values,
+        eq(state.evaluated_population.fitness_values().to_vec())
+    );
+    for (genome, fitness) in &final_population {
+        assert_that!(fitness, eq(MyFitnessEvaluator.fitness_of(genome)));
+    }
+    assert_that!(
+        final_population
+            .iter()
+            .map(|(_, fitness)| fitness)
+            .max()
+            .unwrap(),
+        eq(state.best_solution.solution.fitness)
+    );
+}
+
+#[test]
+fn final_population_is_not_reported_by_default() {
+    let state = final_state(false);
+
+    assert_that!(&state.final_population, eq(None));
+}
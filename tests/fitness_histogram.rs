@@ -0,0 +1,104 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{ga::State, operator::prelude::*, prelude::*};
+
+type MyGenome = Vec<u8>;
+
+/// Runs the simulation and returns the state of each generation.
+fn generations_with_histogram(
+    fitness_histogram: Option<usize>,
+    scalar_fitness: bool,
+) -> Vec<State<MyGenome, u32>> {
+    // the fitness values are 0, 2, 5, 9, 10, 18, 25, 31, 39 and 40
+    let initial_population = Population::with_individuals(vec![
+        vec![0, 0, 0, 0],
+        vec![1, 1, 0, 0],
+        vec![5, 0, 0, 0],
+        vec![3, 3, 3, 0],
+        vec![10, 0, 0, 0],
+        vec![9, 9, 0, 0],
+        vec![10, 10, 5, 0],
+        vec![10, 10, 10, 1],
+        vec![10, 10, 10, 9],
+        vec![10, 10, 10, 10],
+    ]);
+    let builder = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(4, 10))
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(4, 10), false, 0.7))
+        .with_initial_population(initial_population);
+    let builder = match fitness_histogram {
+        Some(buckets) => builder.with_fitness_histogram(buckets),
+        None => builder,
+    };
+    let builder = if scalar_fitness {
+        builder.with_scalar_fitness()
+    } else {
+        builder
+    };
+    let mut simulation = simulate(builder.build())
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    run_generations(&mut simulation, |state| state.result.clone())
+}
+
+#[test]
+fn the_histogram_counts_the_individuals_per_fitness_bucket() {
+    let states = generations_with_histogram(Some(4), false);
+
+    assert_that!(&states[0].fitness_histogram, eq(Some(vec![4, 2, 1, 3])));
+    for state in &states {
+        let histogram = state.fitness_histogram.as_ref().unwrap();
+        assert_that!(&histogram.len(), eq(4));
+        assert_that!(
+            &histogram.iter().sum::<usize>(),
+            eq(state.evaluated_population.individuals().len())
+        );
+    }
+}
+
+#[test]
+fn there_is_no_histogram_by_default() {
+    for state in generations_with_histogram(None, false) {
+        assert_that!(&state.fitness_histogram, eq(None));
+    }
+}
+
+#[test]
+fn the_histogram_does_not_change_the_results() {
+    let with_histogram = generations_with_histogram(Some(7), false);
+    let without_histogram = generations_with_histogram(None, false);
+
+    for (state, other) in with_histogram.iter().zip(without_histogram.iter()) {
+        assert_that!(&state.best_solution, eq(other.best_solution.clone()));
+        assert_that!(&state.stats, eq(other.stats.clone()));
+    }
+}
+
+#[test]
+fn the_standard_deviation_is_only_reported_with_scalar_fitness() {
+    let with_scalar_fitness = generations_with_histogram(None, true);
+    let without_scalar_fitness = generations_with_histogram(Some(4), false);
+
+    // the fitness values of the initial population have a mean of 17.9
+    let std_dev = with_scalar_fitness[0].stats.std_dev.unwrap();
+    assert_that!(&(std_dev - 14.272).abs(), less_than(1e-6));
+    for (state, other) in with_scalar_fitness
+        .iter()
+        .zip(without_scalar_fitness.iter())
+    {
+        assert_that!(&state.stats.std_dev.is_some(), eq(true));
+        assert_that!(&other.stats.std_dev, eq(None));
+        assert_that!(&state.best_solution, eq(other.best_solution.clone()));
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genetic::{pipeline, FitnessTransform},
+    operator::prelude::*,
+    population::IntGenomeSpec,
+    prelude::*,
+};
+
+type MyGenome = Vec<u8>;
+
+/// Doubles the fitness of each genome.
+#[derive(Clone, Debug, PartialEq)]
+struct Double;
+
+impl FitnessTransform<MyGenome, u32> for Double {
+    fn transform(&self, _: &MyGenome, fitness: u32) -> u32 {
+        fitness * 2
+    }
+
+    fn transform_limit(&self, limit: u32) -> u32 {
+        limit * 2
+    }
+}
+
+/// Subtracts the lowest fitness of the population from all values.
+#[derive(Clone, Debug, PartialEq)]
+struct SubtractLowest;
+
+impl FitnessTransform<MyGenome, u32> for SubtractLowest {
+    fn transform_population(&self, _: &[MyGenome], fitness_values: &mut [u32]) -> bool {
+        let lowest = fitness_values.iter().min().cloned().unwrap_or(0);
+        for fitness in fitness_values.iter_mut() {
+            *fitness -= lowest;
+        }
+        lowest > 0
+    }
+}
+
+#[test]
+fn the_genetic_algorithm_evaluates_the_population_by_the_pipeline() {
+    let genome_spec = IntGenomeSpec::new(4, 1u8, 100);
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(genome_spec.clone())
+        .of_size(20)
+        .using_seed([7; 32]);
+    let evaluator = pipeline(SumOfLoci::new(4, 100))
+        .then(Double)
+        .then(SubtractLowest);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(evaluator)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(genome_spec.mutator(0.2))
+            .with_reinsertion(UniformReinserter::new(1.0))
+            .with_initial_population(initial_population.clone())
+            .build(),
+    )
+    .until(GenerationLimit::new(1))
+    .build_with_seed([42; 32]);
+
+    let evaluated_populations = run_generations(&mut simulation, |state| {
+        state.result.evaluated_population.clone()
+    });
+
+    let raw: Vec<u32> = initial_population
+        .individuals()
+        .iter()
+        .map(|genome| SumOfLoci::new(4, 100).fitness_of(genome) * 2)
+        .collect();
+    let raw_lowest = *raw.iter().min().unwrap();
+    let expected: Vec<u32> = raw.iter().map(|fitness| fitness - raw_lowest).collect();
+    let evaluated = &evaluated_populations[0];
+    assert_that!(&evaluated.fitness_values().to_vec(), eq(expected.clone()));
+    assert_that!(&evaluated.lowest_fitness(), eq(0));
+    assert_that!(
+        &evaluated.highest_fitness(),
+        eq(*expected.iter().max().unwrap())
+    );
+}
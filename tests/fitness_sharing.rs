@@ -0,0 +1,100 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genetic::EuclideanDistance,
+    operator::{prelude::*, SelectionOp},
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+};
+use std::fmt::Debug;
+
+type MyGenome = Vec<f64>;
+
+/// A fitness function with a peak of height 1000 at 1 and a lower peak of
+/// height 800 at -1.
+#[derive(Clone, Debug, PartialEq)]
+struct TwoPeaks;
+
+impl FitnessFunction<MyGenome, u32> for TwoPeaks {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        let x = individual[0];
+        let height = if x < 0. { 800. } else { 1000. };
+        let distance_to_peak = x.abs() - 1.;
+        ((1. - distance_to_peak * distance_to_peak).max(0.) * height + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1000
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Runs the simulation and returns the number of individuals near the left
+/// and near the right peak.
+fn individuals_near_peaks<S>(selector: S) -> (usize, usize)
+where
+    S: SelectionOp<MyGenome, u32> + Debug,
+{
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(1, -2., 2.))
+        .of_size(60)
+        .using_seed([7; 32]);
+
+    let algorithm = genetic_algorithm()
+        .with_evaluation(TwoPeaks)
+        .with_selection(selector)
+        .with_crossover(BlendCrossBreeder::with_bounds(0.1, -2., 2.))
+        .with_mutation(GaussianMutator::new(0.2, 0.05, -2., 2.))
+        .with_reinsertion(UniformReinserter::new(1.0))
+        .with_initial_population(initial_population)
+        .build();
+
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(200))
+        .with_rng_seed(42)
+        .build();
+
+    let population = loop {
+        if let SimResult::Final(state, _, _, _) = simulation.step().expect("simulation failed") {
+            break state.result.evaluated_population.individuals();
+        }
+    };
+
+    let near_left_peak = population
+        .iter()
+        .filter(|genome| (genome[0] + 1.).abs() < 0.3)
+        .count();
+    let near_right_peak = population
+        .iter()
+        .filter(|genome| (genome[0] - 1.).abs() < 0.3)
+        .count();
+    (near_left_peak, near_right_peak)
+}
+
+#[test]
+fn fitness_sharing_maintains_both_peaks_of_a_two_peak_function() {
+    let plain = individuals_near_peaks(UniversalSamplingSelector::new(1.0, 2));
+    let shared = individuals_near_peaks(FitnessSharing::new(
+        UniversalSamplingSelector::new(1.0, 2),
+        EuclideanDistance,
+        1.0,
+    ));
+
+    // without sharing the population converges to one of the peaks
+    assert_that!(&plain.0.min(plain.1), less_than(5));
+    // with sharing both peaks keep a considerable part of the population
+    assert_that!(&shared.0, geq(10));
+    assert_that!(&shared.1, geq(10));
+}
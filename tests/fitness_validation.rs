@@ -0,0 +1,150 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    ga::GeneticAlgorithmError, genetic::ScalarFitness, operator::prelude::*, prelude::*,
+    simulation::simulator::SimError,
+};
+
+type MyGenome = Vec<u8>;
+
+/// Declares a highest possible fitness of 20, but returns up to 40 for the
+/// sum of the loci.
+#[derive(Clone, Debug, PartialEq)]
+struct TooHighSum;
+
+impl FitnessFunction<MyGenome, u32> for TooHighSum {
+    fn fitness_of(&self, genome: &MyGenome) -> u32 {
+        genome.iter().map(|locus| *locus as u32).sum()
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        20
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Returns NaN for genomes with a first locus of 7.
+#[derive(Clone, Debug, PartialEq)]
+struct NanForSeven;
+
+impl FitnessFunction<MyGenome, ScalarFitness> for NanForSeven {
+    fn fitness_of(&self, genome: &MyGenome) -> ScalarFitness {
+        if genome[0] == 7 {
+            return ScalarFitness(f64::NAN);
+        }
+        ScalarFitness(genome.iter().map(|locus| *locus as f64).sum())
+    }
+
+    fn average(&self, fitness_values: &[ScalarFitness]) -> ScalarFitness {
+        ScalarFitness(
+            fitness_values.iter().map(ScalarFitness::value).sum::<f64>()
+                / fitness_values.len() as f64,
+        )
+    }
+
+    fn highest_possible_fitness(&self) -> ScalarFitness {
+        ScalarFitness(40.)
+    }
+
+    fn lowest_possible_fitness(&self) -> ScalarFitness {
+        ScalarFitness(f64::NEG_INFINITY)
+    }
+}
+
+fn initial_population() -> Population<MyGenome> {
+    Population::with_individuals(vec![
+        vec![1, 2, 3, 4],
+        vec![2, 2, 2, 2],
+        vec![7, 1, 0, 0],
+        vec![10, 10, 10, 10],
+        vec![0, 0, 0, 0],
+        vec![3, 3, 3, 3],
+    ])
+}
+
+#[test]
+fn a_fitness_above_the_highest_possible_fitness_is_an_error() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(TooHighSum)
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(TooHighSum, false, 0.7))
+        .with_initial_population(initial_population())
+        .with_fitness_validation(true)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    match simulation.step() {
+        Err(SimError::AlgorithmError(error)) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::InvalidFitness(
+                "The fitness function returned the fitness 40, which is outside of the range \
+                 from the lowest possible fitness 0 to the highest possible fitness 20, for the \
+                 individual 3 of generation 1: [10, 10, 10, 10]"
+                    .to_string()
+            ))
+        ),
+        _ => panic!("the simulation should fail"),
+    }
+}
+
+#[test]
+fn a_nan_fitness_is_an_error() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(NanForSeven)
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(NanForSeven, false, 0.7))
+        .with_initial_population(initial_population())
+        .with_fitness_validation(true)
+        .with_scalar_fitness()
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    match simulation.step() {
+        Err(SimError::AlgorithmError(error)) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::InvalidFitness(
+                "The fitness function returned the fitness ScalarFitness(NaN), which is not \
+                 finite, for the individual 2 of generation 1: [7, 1, 0, 0]"
+                    .to_string()
+            ))
+        ),
+        _ => panic!("the simulation should fail"),
+    }
+}
+
+#[test]
+fn the_fitness_is_not_validated_by_default() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(TooHighSum)
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(TooHighSum, false, 0.7))
+        .with_initial_population(initial_population())
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    assert_that!(&simulation.algorithm().fitness_validation(), eq(false));
+    assert_that!(&simulation.run().is_ok(), eq(true));
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genetic::ScalarFitness, operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*,
+};
+
+type MyGenome = Vec<f64>;
+
+/// The fitness is the negated squared distance of the genome to the point
+/// `[1, 1, 1, 1]`. The objective is undefined, i.e. NaN, for genomes with a
+/// first locus below -1.
+#[derive(Clone, Debug, PartialEq)]
+struct DistanceToOnes;
+
+impl FitnessFunction<MyGenome, ScalarFitness> for DistanceToOnes {
+    fn fitness_of(&self, genome: &MyGenome) -> ScalarFitness {
+        if genome[0] < -1. {
+            return ScalarFitness(f64::NAN);
+        }
+        ScalarFitness(-genome.iter().map(|x| (x - 1.) * (x - 1.)).sum::<f64>())
+    }
+
+    fn average(&self, fitness_values: &[ScalarFitness]) -> ScalarFitness {
+        let defined: Vec<f64> = fitness_values
+            .iter()
+            .map(ScalarFitness::value)
+            .filter(|value| !value.is_nan())
+            .collect();
+        ScalarFitness(defined.iter().sum::<f64>() / defined.len().max(1) as f64)
+    }
+
+    fn highest_possible_fitness(&self) -> ScalarFitness {
+        ScalarFitness(0.)
+    }
+
+    fn lowest_possible_fitness(&self) -> ScalarFitness {
+        ScalarFitness(f64::NAN)
+    }
+}
+
+#[test]
+fn a_float_fitness_is_maximized_while_nan_fitness_is_the_worst() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(50)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(DistanceToOnes)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(GaussianMutator::new(0.2, 0.2, -2.0, 2.0))
+            .with_reinsertion(ElitistReinserter::new(DistanceToOnes, false, 0.7))
+            .with_initial_population(initial_population)
+            .build(),
+    )
+    .until(GenerationLimit::new(100))
+    .build_with_seed([42; 32]);
+
+    let mut best_fitness = Vec::new();
+    let best_solution = loop {
+        match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => {
+                best_fitness.push(state.result.best_solution.solution.fitness)
+            },
+            SimResult::Final(state, _, _, _) => {
+                best_fitness.push(state.result.best_solution.solution.fitness);
+                break state.result.best_solution.solution;
+            },
+        }
+    };
+
+    for fitness in &best_fitness {
+        assert_that!(&fitness.value().is_nan(), eq(false));
+    }
+    for window in best_fitness.windows(2) {
+        assert_that!(&window[1], is(greater_than_or_equal(window[0])));
+    }
+    assert_that!(&best_solution.fitness.value(), is(greater_than(-0.1)));
+    for locus in &best_solution.genome {
+        assert_that!(locus, is(close_to(1., 0.3)));
+    }
+}
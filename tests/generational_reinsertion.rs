@@ -0,0 +1,74 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    ga::GeneticAlgorithmError, operator::prelude::*, population::ValueEncodedGenomeBuilder,
+    prelude::*, simulation::simulator::SimError,
+};
+
+type MyGenome = Vec<u8>;
+
+fn initial_population() -> Population<MyGenome> {
+    build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, 0, 10))
+        .of_size(10)
+        .using_seed([3; 32])
+}
+
+#[test]
+fn an_elitism_of_1_carries_the_best_individual_into_the_next_generation() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(4, 10))
+        .with_selection(MaximizeSelector::new(0.5, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.5, 0, 10))
+        .with_reinsertion(GenerationalReinserter::with_elitism(1))
+        .with_initial_population(initial_population())
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(20))
+        .build_with_seed([42; 32]);
+
+    let best_fitness_values = run_generations(&mut simulation, |state| {
+        state.result.best_solution.solution.fitness
+    });
+    assert_that!(&best_fitness_values.len(), eq(20));
+    for pair in best_fitness_values.windows(2) {
+        assert_that!(&pair[1], geq(pair[0]));
+    }
+}
+
+#[test]
+fn an_offspring_of_a_different_size_than_the_population_is_an_error() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(4, 10))
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(GenerationalReinserter::new())
+        .with_initial_population(initial_population())
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    match simulation.step() {
+        Err(SimError::AlgorithmError(error)) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::IncompatibleOperators(
+                "Offspring of generation 1 has a size of 14 but the reinsertion operator \
+                 Generational-Reinserter requires 10 individuals for a population of 10. \
+                 Configure the selection operator Maximizing-Truncation-Selection and the \
+                 crossover operator Uniform-Cross-Breeder to breed 10 children."
+                    .to_string()
+            ))
+        ),
+        _ => panic!("the simulation should fail"),
+    }
+}
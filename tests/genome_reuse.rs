@@ -0,0 +1,52 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+
+type MyGenome = Vec<u8>;
+
+/// Runs the simulation for 20 generations and returns the population and the
+/// best fitness of each generation. The output of each generation is dropped
+/// before the next generation is processed, so that its genomes can be
+/// reused.
+fn generations_with_genome_reuse(genome_reuse: bool) -> Vec<(Vec<MyGenome>, u32)> {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .of_size(20)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(SumOfLoci::new(8, 10))
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(MultiPointCrossBreeder::new(2))
+            .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+            .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), true, 0.7))
+            .with_initial_population(initial_population)
+            .with_genome_reuse(genome_reuse)
+            .build(),
+    )
+    .until(GenerationLimit::new(20))
+    .build_with_seed([42; 32]);
+
+    run_generations(&mut simulation, |state| {
+        (
+            state.result.evaluated_population.individuals().to_vec(),
+            state.result.best_solution.solution.fitness,
+        )
+    })
+}
+
+#[test]
+fn reusing_genomes_gives_the_same_results_as_cloning_them() {
+    let reusing = generations_with_genome_reuse(true);
+    let cloning = generations_with_genome_reuse(false);
+
+    assert_that!(&reusing.len(), eq(20));
+    assert_that!(&reusing, eq(cloning));
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+
+const TARGET_TEXT: &str = "Hello genevo";
+
+type TextGenome = Vec<u8>;
+
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessFunction<TextGenome, usize> for FitnessCalc {
+    fn fitness_of(&self, genome: &TextGenome) -> usize {
+        genome
+            .iter()
+            .zip(TARGET_TEXT.bytes())
+            .filter(|(c, t)| **c == *t)
+            .count()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        TARGET_TEXT.len()
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+/// Decodes a `TextGenome` into the text it spells.
+#[derive(Clone, Debug, PartialEq)]
+struct TextDecoder;
+
+impl GenotypeToPhenotype<TextGenome, String> for TextDecoder {
+    fn decode(&self, genome: &TextGenome) -> String {
+        String::from_utf8(genome.clone()).unwrap()
+    }
+}
+
+fn initial_population() -> Population<TextGenome> {
+    build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(TARGET_TEXT.len(), 32, 126))
+        .of_size(40)
+        .using_seed([3; 32])
+}
+
+#[test]
+fn decoded_phenotype_of_the_best_solution_matches_a_manual_decode() {
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(FitnessCalc)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(MultiPointCrossBreeder::new(2))
+            .with_mutation(RandomValueMutator::new(0.05, 32, 126))
+            .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+            .with_initial_population(initial_population())
+            .with_decoder(TextDecoder)
+            .build(),
+    )
+    .until(GenerationLimit::new(20))
+    .build_with_seed([42; 32]);
+
+    let mut num_generations = 0;
+    loop {
+        match simulation.step().expect("simulation failed") {
+            SimResult::Intermediate(state) => {
+                let best_solution = state.result.best_solution;
+                assert_that!(
+                    &best_solution.phenotype,
+                    eq(TextDecoder.decode(&best_solution.solution.genome))
+                );
+                num_generations += 1;
+            },
+            SimResult::Final(state, _, _, _) => {
+                let best_solution = state.result.best_solution;
+                assert_that!(
+                    &best_solution.phenotype,
+                    eq(String::from_utf8(best_solution.solution.genome).unwrap())
+                );
+                break;
+            },
+        }
+    }
+    assert_that!(&num_generations, eq(19));
+}
+
+#[test]
+fn phenotype_is_the_genome_if_no_decoder_is_registered() {
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(FitnessCalc)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(MultiPointCrossBreeder::new(2))
+            .with_mutation(RandomValueMutator::new(0.05, 32, 126))
+            .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+            .with_initial_population(initial_population())
+            .build(),
+    )
+    .until(GenerationLimit::new(5))
+    .build_with_seed([42; 32]);
+
+    let result = simulation.run().expect("simulation failed");
+
+    match result {
+        SimResult::Final(state, _, _, _) => {
+            let best_solution = state.result.best_solution;
+            assert_that!(&best_solution.phenotype, eq(best_solution.solution.genome));
+        },
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{ga::State, genetic::Fitness, operator::prelude::*, prelude::*};
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Arc,
+    },
+};
+
+type MyGenome = Vec<u8>;
+
+/// A cost that is minimized, i.e. a lower cost compares as a greater value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Cost(u32);
+
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cost {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl Fitness for Cost {
+    fn zero() -> Self {
+        Cost(0)
+    }
+
+    fn abs_diff(&self, other: &Self) -> Self {
+        Cost(self.0.max(other.0) - self.0.min(other.0))
+    }
+}
+
+/// Assigns the fitness value of the current generation of the given
+/// trajectory to all genomes.
+#[derive(Clone, Debug)]
+struct Trajectory<F> {
+    fitness_values: Vec<F>,
+    lowest: F,
+    highest: F,
+    generation: Arc<AtomicUsize>,
+}
+
+impl<F> FitnessFunction<MyGenome, F> for Trajectory<F>
+where
+    F: Fitness + Send + Sync,
+{
+    fn fitness_of(&self, _: &MyGenome) -> F {
+        self.fitness_values[self.generation.load(AtomicOrdering::SeqCst)].clone()
+    }
+
+    fn average(&self, fitness_values: &[F]) -> F {
+        fitness_values[0].clone()
+    }
+
+    fn highest_possible_fitness(&self) -> F {
+        self.highest.clone()
+    }
+
+    fn lowest_possible_fitness(&self) -> F {
+        self.lowest.clone()
+    }
+}
+
+/// Runs one generation for each value of the trajectory and returns the
+/// state of each generation.
+fn run_trajectory<F>(fitness_values: Vec<F>, lowest: F, highest: F) -> Vec<State<MyGenome, F>>
+where
+    F: Fitness + Send + Sync + 'static,
+{
+    let generation = Arc::new(AtomicUsize::new(0));
+    let num_generations = fitness_values.len();
+    let evaluator = Trajectory {
+        fitness_values,
+        lowest,
+        highest,
+        generation: generation.clone(),
+    };
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(evaluator)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(RandomValueMutator::new(0.2, 0, 9))
+            .with_reinsertion(UniformReinserter::new(1.0))
+            .with_initial_population(Population::with_individuals(vec![vec![1, 2, 3]; 6]))
+            .build(),
+    )
+    .until(GenerationLimit::new(num_generations as u64))
+    .build_with_seed([42; 32]);
+
+    (0..num_generations)
+        .map(|index| {
+            generation.store(index, AtomicOrdering::SeqCst);
+            match simulation.step().expect("simulation step failed") {
+                SimResult::Intermediate(state) | SimResult::Final(state, _, _, _) => state.result,
+            }
+        })
+        .collect()
+}
+
+fn progress<F>(states: &[State<MyGenome, F>]) -> (Vec<F>, Vec<u64>)
+where
+    F: Fitness + Debug,
+{
+    (
+        states
+            .iter()
+            .map(|state| state.best_fitness_delta.clone())
+            .collect(),
+        states
+            .iter()
+            .map(|state| state.generations_since_improvement)
+            .collect(),
+    )
+}
+
+#[test]
+fn the_improvement_is_tracked_for_a_maximization_problem() {
+    let states = run_trajectory(vec![5u32, 8, 8, 7, 12, 12, 13], 0, 100);
+
+    let (deltas, since_improvement) = progress(&states);
+
+    assert_that!(&deltas, eq(vec![0, 3, 0, 0, 4, 0, 1]));
+    assert_that!(&since_improvement, eq(vec![0, 0, 1, 2, 0, 1, 0]));
+}
+
+#[test]
+fn the_improvement_is_tracked_for_a_minimization_problem() {
+    let costs = vec![10, 7, 7, 9, 3, 5].into_iter().map(Cost).collect();
+    let states = run_trajectory(costs, Cost(u32::MAX), Cost(0));
+
+    let (deltas, since_improvement) = progress(&states);
+
+    let expected: Vec<Cost> = vec![0, 3, 0, 0, 4, 0].into_iter().map(Cost).collect();
+    assert_that!(&deltas, eq(expected));
+    assert_that!(&since_improvement, eq(vec![0, 0, 1, 2, 0, 1]));
+}
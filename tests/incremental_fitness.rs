@@ -0,0 +1,176 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genetic::{IncrementalFitness, IncrementalFitnessEvaluation},
+    operator::{prelude::*, CrossoverOp, MutationOp},
+    population::IntGenomeSpec,
+    prelude::*,
+    random::get_rng,
+};
+
+const TARGET_TEXT: &str = "Be not afraid of greatness! Some are great, some achieve greatness, \
+                           and some have greatness thrust upon 'em.";
+
+type TextGenome = [u8; TARGET_TEXT.len()];
+
+/// The fitness function of the `monkeys` example.
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessCalc {
+    /// Returns the fitness of a genome with `score` matching characters.
+    fn fitness_of_score(score: usize) -> usize {
+        let fraction = score as f32 / TARGET_TEXT.len() as f32;
+        (fraction * fraction * 10_000. + 0.5).floor() as usize
+    }
+
+    /// Returns the number of matching characters of a genome with the given
+    /// fitness.
+    fn score_of_fitness(fitness: usize) -> usize {
+        let estimate = ((fitness as f64 / 10_000.).sqrt() * TARGET_TEXT.len() as f64).round();
+        let estimate = estimate as usize;
+        (estimate.saturating_sub(1)..=estimate + 1)
+            .find(|score| FitnessCalc::fitness_of_score(*score) == fitness)
+            .expect("not a fitness value of the fitness function")
+    }
+}
+
+impl FitnessFunction<TextGenome, usize> for FitnessCalc {
+    fn fitness_of(&self, genome: &TextGenome) -> usize {
+        let score = genome
+            .iter()
+            .zip(TARGET_TEXT.bytes())
+            .filter(|(c, t)| **c == *t)
+            .count();
+        FitnessCalc::fitness_of_score(score)
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        10_000
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+impl IncrementalFitness<TextGenome, usize> for FitnessCalc {
+    fn fitness_delta(
+        &self,
+        parent_fitness: usize,
+        parent: &TextGenome,
+        child: &TextGenome,
+    ) -> usize {
+        let mut score = FitnessCalc::score_of_fitness(parent_fitness);
+        for ((p, c), t) in parent.iter().zip(child.iter()).zip(TARGET_TEXT.bytes()) {
+            if p != c {
+                if *p == t {
+                    score -= 1;
+                } else if *c == t {
+                    score += 1;
+                }
+            }
+        }
+        FitnessCalc::fitness_of_score(score)
+    }
+}
+
+fn genome_spec() -> IntGenomeSpec<u8> {
+    // the printable ASCII characters from ' ' (32) to '~' (126)
+    IntGenomeSpec::new(TARGET_TEXT.len(), 32, 126)
+}
+
+#[test]
+fn fitness_delta_equals_the_full_evaluation_for_thousands_of_children() {
+    let mut rng = get_rng([42; 32]);
+    let breeder = MultiPointCrossBreeder::new(TARGET_TEXT.len() / 6);
+    let mutator = genome_spec().mutator(0.05);
+    let population: Population<TextGenome> = build_population()
+        .with_genome_builder(genome_spec())
+        .of_size(100)
+        .using_seed([7; 32]);
+    let individuals = population.individuals();
+
+    let mut num_children = 0;
+    for pair in individuals.chunks(2).cycle().take(2_500) {
+        let parents = pair.to_vec();
+        for (index, child) in breeder
+            .crossover(parents.clone(), &mut rng)
+            .into_iter()
+            .enumerate()
+        {
+            let child = mutator.mutate(child, &mut rng);
+            let parent = &parents[index];
+            let parent_fitness = FitnessCalc.fitness_of(parent);
+
+            assert_that!(
+                &FitnessCalc.fitness_delta(parent_fitness, parent, &child),
+                eq(FitnessCalc.fitness_of(&child))
+            );
+            num_children += 1;
+        }
+    }
+    assert_that!(&num_children, eq(5_000));
+}
+
+/// Returns the best solution of each generation.
+fn run<E>(evaluation: E) -> Vec<(u64, usize, TextGenome)>
+where
+    E: FitnessFunction<TextGenome, usize> + std::fmt::Debug + Send + Sync,
+{
+    let initial_population: Population<TextGenome> = build_population()
+        .with_genome_builder(genome_spec())
+        .of_size(200)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(evaluation)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(MultiPointCrossBreeder::new(TARGET_TEXT.len() / 6))
+            .with_mutation(genome_spec().mutator(0.01))
+            .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+            .with_initial_population(initial_population)
+            .build(),
+    )
+    .until(GenerationLimit::new(100))
+    .build_with_seed([42; 32]);
+
+    let mut best_solutions = Vec::new();
+    loop {
+        match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => {
+                let best = state.result.best_solution;
+                best_solutions.push((best.generation, best.solution.fitness, best.solution.genome));
+            },
+            SimResult::Final(state, _, _, _) => {
+                let best = state.result.best_solution;
+                best_solutions.push((best.generation, best.solution.fitness, best.solution.genome));
+                break best_solutions;
+            },
+        }
+    }
+}
+
+#[test]
+fn incremental_evaluation_gives_the_same_results_as_the_full_evaluation() {
+    let incremental = IncrementalFitnessEvaluation::new(FitnessCalc);
+
+    let expected = run(FitnessCalc);
+    let best_solutions = run(incremental.clone());
+
+    assert_that!(&best_solutions, eq(expected));
+    // only the genomes of the initial population are evaluated from scratch
+    assert_that!(&incremental.num_full_evaluations(), leq(200));
+    assert_that!(
+        &incremental.num_incremental_evaluations(),
+        is(greater_than(10_000))
+    );
+}
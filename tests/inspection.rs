@@ -0,0 +1,87 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{ga::State, operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+
+type MyGenome = Vec<u8>;
+
+const POPULATION_SIZE: usize = 20;
+const SELECTION_RATIO: f64 = 0.7;
+const NUM_INDIVIDUALS_PER_PARENTS: usize = 2;
+
+/// Runs the simulation for 5 generations and returns the state of each
+/// generation.
+fn generations_with_inspection(inspection: bool) -> Vec<State<MyGenome, u32>> {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, 0, 10))
+        .of_size(POPULATION_SIZE)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(SumOfLoci::new(4, 10))
+            .with_selection(MaximizeSelector::new(
+                SELECTION_RATIO,
+                NUM_INDIVIDUALS_PER_PARENTS,
+            ))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+            .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(4, 10), false, 0.7))
+            .with_initial_population(initial_population)
+            .with_inspection(inspection)
+            .build(),
+    )
+    .until(GenerationLimit::new(5))
+    .build_with_seed([42; 32]);
+
+    run_generations(&mut simulation, |state| state.result.clone())
+}
+
+#[test]
+fn the_mating_pool_and_the_offspring_match_the_configured_ratios() {
+    let states = generations_with_inspection(true);
+
+    let num_parents = (POPULATION_SIZE as f64 * SELECTION_RATIO).round() as usize;
+    assert_that!(&states.len(), eq(5));
+    for state in states {
+        let inspection = state.inspection.expect("the inspection is reported");
+        assert_that!(&inspection.parents.len(), eq(num_parents));
+        for parents in &inspection.parents {
+            assert_that!(&parents.len(), eq(NUM_INDIVIDUALS_PER_PARENTS));
+        }
+        let num_children = num_parents * NUM_INDIVIDUALS_PER_PARENTS;
+        assert_that!(&inspection.children.len(), eq(num_children));
+        assert_that!(&inspection.offspring.len(), eq(num_children));
+    }
+}
+
+#[test]
+fn the_inspection_does_not_change_the_results() {
+    let inspected = generations_with_inspection(true);
+    let not_inspected = generations_with_inspection(false);
+
+    for (inspected, not_inspected) in inspected.iter().zip(not_inspected.iter()) {
+        assert_that!(
+            &inspected.evaluated_population,
+            eq(not_inspected.evaluated_population.clone())
+        );
+        assert_that!(
+            &inspected.best_solution,
+            eq(not_inspected.best_solution.clone())
+        );
+    }
+}
+
+#[test]
+fn the_inspection_is_not_reported_by_default() {
+    let states = generations_with_inspection(false);
+
+    for state in states {
+        assert_that!(&state.inspection, eq(None));
+    }
+}
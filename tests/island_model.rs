@@ -0,0 +1,270 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use genevo::{
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    random::get_rng,
+    simulation::islands::{
+        BestReplaceWorst, IslandSimulator, Migration, MigrationPolicy, MigrationTopology,
+        RandomReplaceRandom,
+    },
+    simulation::simulator::SimError,
+};
+
+const TARGET_TEXT: &str = "See how a genius creates a legend";
+
+type TextGenome = Vec<u8>;
+
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessFunction<TextGenome, usize> for FitnessCalc {
+    fn fitness_of(&self, genome: &TextGenome) -> usize {
+        let score = genome
+            .iter()
+            .zip(TARGET_TEXT.bytes())
+            .filter(|(c, t)| **c == *t)
+            .count();
+        let fraction = score as f32 / TARGET_TEXT.len() as f32;
+        (fraction * fraction * 10_000. + 0.5).floor() as usize
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        10_000
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+const POPULATION_SIZE: usize = 60;
+
+/// The outcome of an island simulation.
+struct IslandRun {
+    /// The best fitness of each generation.
+    best_fitness: Vec<usize>,
+    /// The population sizes of the islands after each generation.
+    population_sizes: Vec<Vec<usize>>,
+}
+
+/// Runs an island simulation of the monkeys problem.
+fn run_islands<P>(topology: MigrationTopology, policy: P, num_migrants: usize) -> IslandRun
+where
+    P: MigrationPolicy,
+{
+    let islands = (0..4u8)
+        .map(|island| {
+            let initial_population: Population<TextGenome> = build_population()
+                .with_genome_builder(ValueEncodedGenomeBuilder::new(TARGET_TEXT.len(), 32, 126))
+                .of_size(POPULATION_SIZE)
+                .using_seed([island; 32]);
+            let algorithm = genetic_algorithm()
+                .with_evaluation(FitnessCalc)
+                .with_selection(MaximizeSelector::new(0.7, 2))
+                .with_crossover(MultiPointCrossBreeder::new(TARGET_TEXT.len() / 6))
+                .with_mutation(RandomValueMutator::new(0.02, 32, 126))
+                .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+                .with_initial_population(initial_population)
+                .build();
+            simulate(algorithm)
+                .until(or(
+                    FitnessLimit::new(FitnessCalc.highest_possible_fitness()),
+                    GenerationLimit::new(1_000),
+                ))
+                .build_with_seed([island + 100; 32])
+        })
+        .collect();
+
+    let mut simulation = IslandSimulator::new(islands, 10, num_migrants, topology)
+        .expect("the simulation has islands")
+        .with_migration_policy(policy);
+
+    let mut best_fitness = Vec::new();
+    let mut population_sizes = Vec::new();
+    loop {
+        let result = simulation.step().expect("simulation step failed");
+        population_sizes.push(
+            simulation
+                .islands()
+                .iter()
+                .map(|island| island.algorithm().population().len())
+                .collect(),
+        );
+        match result {
+            SimResult::Intermediate(state) => {
+                best_fitness.push(state.result.best_solution.solution.fitness);
+            },
+            SimResult::Final(state, _, _, _) => {
+                best_fitness.push(state.result.best_solution.solution.fitness);
+                break;
+            },
+        }
+    }
+    IslandRun {
+        best_fitness,
+        population_sizes,
+    }
+}
+
+#[test]
+fn island_simulator_needs_at_least_one_island() {
+    let mut islands = vec![simulate(
+        genetic_algorithm()
+            .with_evaluation(FitnessCalc)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(MultiPointCrossBreeder::new(1))
+            .with_mutation(RandomValueMutator::new(0.02, 32, 126))
+            .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+            .with_initial_population(Population::with_individuals(vec![vec![32; 3]; 4]))
+            .build(),
+    )
+    .until(GenerationLimit::new(10))
+    .build_with_seed([42; 32])];
+    islands.clear();
+
+    let result = IslandSimulator::new(islands, 10, 2, MigrationTopology::Ring);
+
+    assert_that!(
+        &matches!(result, Err(SimError::InvalidSimulation(_))),
+        is(eq(true))
+    );
+}
+
+#[test]
+fn island_model_with_ring_topology_converges() {
+    let best_fitness = run_islands(MigrationTopology::Ring, BestReplaceWorst, 2).best_fitness;
+
+    assert_that!(&best_fitness[best_fitness.len() - 1], eq(10_000));
+    assert_that!(&best_fitness.len(), lt(1_000));
+}
+
+#[test]
+fn island_model_with_fully_connected_topology_converges() {
+    let best_fitness =
+        run_islands(MigrationTopology::FullyConnected, RandomReplaceRandom, 2).best_fitness;
+
+    assert_that!(&best_fitness[best_fitness.len() - 1], eq(10_000));
+    assert_that!(&best_fitness.len(), lt(1_000));
+}
+
+#[test]
+fn island_model_with_seeded_islands_is_reproducible() {
+    let first_run = run_islands(MigrationTopology::Ring, RandomReplaceRandom, 2).best_fitness;
+    let second_run = run_islands(MigrationTopology::Ring, RandomReplaceRandom, 2).best_fitness;
+
+    assert_that!(&first_run, eq(second_run));
+}
+
+#[test]
+fn migration_keeps_the_size_of_each_island() {
+    // more immigrants than individuals arrive at each island
+    let runs = vec![
+        run_islands(MigrationTopology::Ring, BestReplaceWorst, 50),
+        run_islands(MigrationTopology::FullyConnected, BestReplaceWorst, 50),
+        run_islands(MigrationTopology::Ring, RandomReplaceRandom, 50),
+        run_islands(MigrationTopology::FullyConnected, RandomReplaceRandom, 50),
+    ];
+
+    for run in runs {
+        assert_that!(&run.population_sizes.len(), is(greater_than(10)));
+        for sizes in run.population_sizes {
+            assert_that!(&sizes, eq(vec![POPULATION_SIZE; 4]));
+        }
+    }
+}
+
+#[test]
+fn each_emigrant_is_a_distinct_individual() {
+    let individuals: Vec<TextGenome> = (0..POPULATION_SIZE as u8).map(|i| vec![i; 3]).collect();
+    let mut island = genetic_algorithm()
+        .with_evaluation(FitnessCalc)
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(MultiPointCrossBreeder::new(1))
+        .with_mutation(RandomValueMutator::new(0.02, 32, 126))
+        .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+        .with_initial_population(Population::with_individuals(individuals))
+        .build();
+    let mut rng = get_rng([42; 32]);
+
+    let random = RandomReplaceRandom.select_emigrants(&mut island, 30, &mut rng);
+    let best = BestReplaceWorst.select_emigrants(&mut island, 100, &mut rng);
+
+    assert_that!(&random.iter().collect::<HashSet<_>>().len(), eq(30));
+    assert_that!(
+        &best.iter().collect::<HashSet<_>>().len(),
+        eq(POPULATION_SIZE)
+    );
+}
+
+/// Counts how often the fitness of a genome is evaluated.
+#[derive(Clone, Debug, Default)]
+struct CountingFitnessCalc {
+    evaluations: Arc<AtomicUsize>,
+}
+
+impl FitnessFunction<TextGenome, usize> for CountingFitnessCalc {
+    fn fitness_of(&self, genome: &TextGenome) -> usize {
+        self.evaluations.fetch_add(1, Ordering::SeqCst);
+        FitnessCalc.fitness_of(genome)
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        FitnessCalc.average(fitness_values)
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        FitnessCalc.highest_possible_fitness()
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        FitnessCalc.lowest_possible_fitness()
+    }
+}
+
+#[test]
+fn best_replace_worst_evaluates_each_population_once_per_migration() {
+    let fitness = CountingFitnessCalc::default();
+    let individuals: Vec<TextGenome> = (0..POPULATION_SIZE as u8).map(|i| vec![i; 3]).collect();
+    let mut island = genetic_algorithm()
+        .with_evaluation(fitness.clone())
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(MultiPointCrossBreeder::new(1))
+        .with_mutation(RandomValueMutator::new(0.02, 32, 126))
+        .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+        .with_initial_population(Population::with_individuals(individuals))
+        .build();
+    let mut rng = get_rng([42; 32]);
+
+    let emigrants = BestReplaceWorst.select_emigrants(&mut island, 5, &mut rng);
+    let ranking = island.ranking();
+    BestReplaceWorst.integrate_immigrants(&mut island, emigrants, &mut rng);
+
+    assert_that!(&ranking.len(), eq(POPULATION_SIZE));
+    assert_that!(
+        &fitness.evaluations.load(Ordering::SeqCst),
+        eq(POPULATION_SIZE)
+    );
+    // the immigrants replace the population, so that it is ranked again
+    island.ranking();
+    assert_that!(
+        &fitness.evaluations.load(Ordering::SeqCst),
+        eq(2 * POPULATION_SIZE)
+    );
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::SumOfLoci;
+use galvanic_assert::matchers::*;
+
+use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+
+type MyGenome = Vec<u8>;
+
+#[test]
+fn a_minimize_selector_minimizes_the_fitness_values() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .of_size(20)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(SumOfLoci::new(8, 10))
+            .with_selection(MinimizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+            .with_reinsertion(ElitistReinserter::with_elite_count(
+                SumOfLoci::new(8, 10),
+                2,
+            ))
+            .with_initial_population(initial_population)
+            .build(),
+    )
+    .until(or(FitnessLimit::new(5), GenerationLimit::new(200)))
+    .build_with_seed([42; 32]);
+
+    // the fitness of a genome is its cost, which is to be minimized
+    let mut best_costs = Vec::new();
+    let result = simulation
+        .run_with(|state| {
+            let lowest_cost = *state
+                .result
+                .evaluated_population
+                .fitness_values()
+                .iter()
+                .min()
+                .unwrap();
+            // the best solution is the one with the lowest cost
+            assert_that!(
+                &state.result.best_solution.solution.fitness,
+                eq(lowest_cost)
+            );
+            best_costs.push(lowest_cost);
+        })
+        .expect("simulation failed");
+    let reason = match result {
+        SimResult::Final(_, _, _, reason) => reason,
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    };
+
+    // the elitist reinsertion never loses the cheapest solution
+    assert_that!(
+        &best_costs.windows(2).all(|costs| costs[1] <= costs[0]),
+        is(eq(true))
+    );
+    assert_that!(&best_costs.last().copied(), leq(Some(5)));
+    assert_that!(
+        &matches!(reason, StopReason::FitnessLimitReached(_)),
+        is(eq(true))
+    );
+}
@@ -0,0 +1,220 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    simulation::{
+        ensemble::{EnsembleResult, MultiStartRunner},
+        simulator::SimulatorBuilder,
+    },
+    statistic::ProcessingTime,
+};
+
+const TARGET_TEXT: &str = "See how a genius creates a legend";
+
+type TextGenome = Vec<u8>;
+
+#[derive(Clone, Debug)]
+struct FitnessCalc;
+
+impl FitnessFunction<TextGenome, usize> for FitnessCalc {
+    fn fitness_of(&self, genome: &TextGenome) -> usize {
+        let score = genome
+            .iter()
+            .zip(TARGET_TEXT.bytes())
+            .filter(|(c, t)| **c == *t)
+            .count();
+        let fraction = score as f32 / TARGET_TEXT.len() as f32;
+        (fraction * fraction * 10_000. + 0.5).floor() as usize
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        10_000
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+type MyAlgorithm = GeneticAlgorithm<
+    TextGenome,
+    usize,
+    FitnessCalc,
+    MaximizeSelector,
+    MultiPointCrossBreeder,
+    RandomValueMutator<TextGenome>,
+    ElitistReinserter<TextGenome, usize, FitnessCalc>,
+>;
+
+type MyTermination = Or<FitnessLimit<TextGenome, usize>, GenerationLimit, MyAlgorithm>;
+
+fn monkeys(run: usize) -> SimulatorBuilder<MyAlgorithm, MyTermination> {
+    let initial_population: Population<TextGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(TARGET_TEXT.len(), 32, 126))
+        .of_size(30)
+        .using_seed([run as u8; 32]);
+    simulate(
+        genetic_algorithm()
+            .with_evaluation(FitnessCalc)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(MultiPointCrossBreeder::new(TARGET_TEXT.len() / 6))
+            .with_mutation(RandomValueMutator::new(0.02, 32, 126))
+            .with_reinsertion(ElitistReinserter::new(FitnessCalc, true, 0.7))
+            .with_initial_population(initial_population)
+            .build(),
+    )
+    .until(or(
+        FitnessLimit::new(FitnessCalc.highest_possible_fitness()),
+        GenerationLimit::new(40),
+    ))
+}
+
+#[test]
+fn multi_start_returns_the_result_with_the_highest_fitness() {
+    let runner = MultiStartRunner::new(4, [42; 32], monkeys);
+
+    let result = runner.run().expect("simulation failed");
+
+    assert_that!(&result.runs.len(), eq(4));
+    let highest = result
+        .runs
+        .iter()
+        .map(|run| run.best_fitness)
+        .max()
+        .unwrap();
+    assert_that!(&result.runs[result.best_run].best_fitness, eq(highest));
+    match result.best_result {
+        SimResult::Final(state, processing_time, _, _) => {
+            assert_that!(&state.result.best_solution.solution.fitness, eq(highest));
+            assert_that!(
+                &processing_time,
+                eq(result.runs[result.best_run].processing_time)
+            );
+        },
+        SimResult::Intermediate(_) => panic!("the best result should be final"),
+    }
+}
+
+#[test]
+fn multi_start_sums_up_the_processing_time_of_all_runs() {
+    let runner = MultiStartRunner::new(4, [42; 32], monkeys);
+
+    let result = runner.run().expect("simulation failed");
+
+    let mut processing_time = ProcessingTime::zero();
+    for run in &result.runs {
+        processing_time += run.processing_time;
+    }
+    assert_that!(&result.processing_time, eq(processing_time));
+}
+
+#[test]
+fn multi_start_with_the_same_master_seed_is_reproducible() {
+    let runner = MultiStartRunner::new(4, [42; 32], monkeys);
+
+    let first = runner.run().expect("simulation failed");
+    let second = runner.run().expect("simulation failed");
+
+    let seeds: Vec<_> = first.runs.iter().map(|run| run.seed).collect();
+    assert_that!(&seeds, eq(runner.seeds()));
+    for (index, seed) in seeds.iter().enumerate() {
+        assert_that!(&seeds[index + 1..].contains(seed), is(eq(false)));
+    }
+    let fitness = |result: &EnsembleResult<MyAlgorithm>| {
+        result
+            .runs
+            .iter()
+            .map(|run| (run.iterations, run.best_fitness))
+            .collect::<Vec<_>>()
+    };
+    assert_that!(&fitness(&first), eq(fitness(&second)));
+    assert_that!(&first.best_run, eq(second.best_run));
+}
+
+/// Draws a random number in each iteration and keeps the highest number drawn
+/// so far. Its output is `Send`, so that it can be run in parallel.
+#[cfg(feature = "parallel")]
+#[derive(Clone, Debug, PartialEq)]
+struct RandomSearch {
+    best: u32,
+}
+
+#[cfg(feature = "parallel")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct NoError;
+
+#[cfg(feature = "parallel")]
+impl std::fmt::Display for NoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "no error")
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl std::error::Error for NoError {}
+
+#[cfg(feature = "parallel")]
+impl genevo::algorithm::Algorithm for RandomSearch {
+    type Output = u32;
+    type Error = NoError;
+
+    fn next<R>(&mut self, _: u64, rng: &mut R) -> Result<u32, NoError>
+    where
+        R: genevo::random::SplittableRng,
+    {
+        self.best = self.best.max(rng.gen_range(0..1_000_000));
+        Ok(self.best)
+    }
+
+    fn reset(&mut self) -> Result<bool, NoError> {
+        self.best = 0;
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl genevo::algorithm::BestFitness for RandomSearch {
+    type Fitness = u32;
+
+    fn best_fitness_of(output: &u32) -> u32 {
+        *output
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl genevo::statistic::TrackProcessingTime for RandomSearch {
+    fn processing_time(&self) -> ProcessingTime {
+        ProcessingTime::zero()
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn multi_start_in_parallel_gives_the_same_result_as_sequential_runs() {
+    let runner = MultiStartRunner::new(8, [7; 32], |_| {
+        simulate(RandomSearch { best: 0 }).until(GenerationLimit::new(5))
+    });
+
+    let sequential = runner.run().expect("simulation failed");
+    let parallel = runner.run_parallel().expect("simulation failed");
+
+    assert_that!(&parallel.best_run, eq(sequential.best_run));
+    let fitness = |result: &EnsembleResult<RandomSearch>| {
+        result
+            .runs
+            .iter()
+            .map(|run| (run.seed, run.best_fitness))
+            .collect::<Vec<_>>()
+    };
+    assert_that!(&fitness(&parallel), eq(fitness(&sequential)));
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    algorithm::EvaluatedPopulation,
+    operator::{prelude::*, SelectionOp},
+    pareto::{Direction, ParetoDominance},
+    prelude::*,
+    random::{get_rng, Rng},
+};
+use std::{cmp::Ordering, sync::Arc};
+
+type MyGenome = Vec<f64>;
+
+const NUM_GENES: usize = 10;
+
+/// The objective values of the ZDT1 problem. Both objectives are minimized.
+#[derive(Clone, Debug, PartialEq)]
+struct Zdt1Fitness {
+    f1: f64,
+    f2: f64,
+}
+
+impl Eq for Zdt1Fitness {}
+
+impl PartialOrd for Zdt1Fitness {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Zdt1Fitness {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.f1
+            .total_cmp(&other.f1)
+            .then_with(|| self.f2.total_cmp(&other.f2))
+    }
+}
+
+impl Fitness for Zdt1Fitness {
+    fn zero() -> Self {
+        Zdt1Fitness { f1: 0., f2: 0. }
+    }
+
+    fn abs_diff(&self, other: &Self) -> Self {
+        Zdt1Fitness {
+            f1: (self.f1 - other.f1).abs(),
+            f2: (self.f2 - other.f2).abs(),
+        }
+    }
+}
+
+impl MultiObjectiveFitness for Zdt1Fitness {
+    fn objectives(&self) -> Vec<f64> {
+        vec![self.f1, self.f2]
+    }
+}
+
+fn zdt1(genome: &MyGenome) -> Zdt1Fitness {
+    let f1 = genome[0];
+    let g = 1. + 9. * genome[1..].iter().sum::<f64>() / (genome.len() - 1) as f64;
+    let f2 = g * (1. - (f1 / g).sqrt());
+    Zdt1Fitness { f1, f2 }
+}
+
+fn is_pareto_optimal(genome: &MyGenome) -> bool {
+    genome[1..].iter().all(|x| *x == 0.)
+}
+
+/// Builds a population of which one half is on the Pareto front of ZDT1 and
+/// the other half is dominated by the Pareto front. The population contains
+/// both ends of the Pareto front.
+fn zdt1_population(rng: &mut impl Rng) -> EvaluatedPopulation<MyGenome, Zdt1Fitness> {
+    let mut individuals = vec![vec![0.; NUM_GENES], vec![0.; NUM_GENES]];
+    individuals[1][0] = 1.;
+    for i in 0..198 {
+        let mut genome = vec![0.; NUM_GENES];
+        genome[0] = rng.gen_range(0.0..=1.0);
+        if i % 2 == 1 {
+            for gene in genome[1..].iter_mut() {
+                *gene = rng.gen_range(0.05..=1.0);
+            }
+        }
+        individuals.push(genome);
+    }
+    let fitness_values: Vec<Zdt1Fitness> = individuals.iter().map(zdt1).collect();
+    let highest = fitness_values.iter().max().cloned().unwrap();
+    let lowest = fitness_values.iter().min().cloned().unwrap();
+    EvaluatedPopulation::new(
+        Arc::new(individuals),
+        fitness_values,
+        highest,
+        lowest,
+        Zdt1Fitness::zero(),
+    )
+}
+
+#[test]
+fn nsga2_selector_selects_a_well_spread_approximation_of_the_zdt1_pareto_front() {
+    let dominance = ParetoDominance::new(vec![Direction::Minimize, Direction::Minimize]);
+    let selector = Nsga2Selector::with_dominance(20, dominance);
+
+    for seed in 0..10u8 {
+        let mut rng = get_rng([seed; 32]);
+        let population = zdt1_population(&mut rng);
+
+        let parents = selector.select_from(&population, &mut rng);
+
+        assert_that!(&parents.len(), eq(20));
+        let selected: Vec<&MyGenome> = parents.iter().flatten().collect();
+        assert_that!(&selected.len(), eq(40));
+        assert_that!(
+            &selected.iter().all(|genome| is_pareto_optimal(genome)),
+            is(eq(true))
+        );
+
+        // the boundary solutions of the front are selected, no large gaps are
+        // left between the selected solutions and all parts of the front are
+        // covered
+        let mut f1_values: Vec<f64> = selected.iter().map(|genome| genome[0]).collect();
+        f1_values.sort_by(f64::total_cmp);
+        assert_that!(&f1_values[0], eq(0.));
+        assert_that!(&f1_values[f1_values.len() - 1], eq(1.));
+        let max_gap = f1_values
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .fold(0., f64::max);
+        let mut quarters = [0; 4];
+        for f1 in &f1_values {
+            quarters[((f1 * 4.) as usize).min(3)] += 1;
+        }
+        assert_that!(&(max_gap < 0.2), is(eq(true)));
+        assert_that!(&quarters.iter().all(|count| *count >= 4), is(eq(true)));
+    }
+}
+
+#[test]
+fn nsga2_selector_selects_no_individual_twice_if_the_population_is_large_enough() {
+    let selector = Nsga2Selector::with_dominance(
+        10,
+        ParetoDominance::new(vec![Direction::Minimize, Direction::Minimize]),
+    );
+    let mut rng = get_rng([7; 32]);
+    let population = zdt1_population(&mut rng);
+
+    let parents = selector.select_from(&population, &mut rng);
+
+    let mut selected: Vec<&MyGenome> = parents.iter().flatten().collect();
+    selected.sort_by(|x, y| x[0].total_cmp(&y[0]));
+    selected.dedup();
+    assert_that!(&selected.len(), eq(20));
+}
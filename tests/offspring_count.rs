@@ -0,0 +1,142 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    algorithm::EvaluatedPopulation,
+    ga::GeneticAlgorithmError,
+    genetic::Parents,
+    operator::{prelude::*, GeneticOperator, SelectionOp},
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    random::Rng,
+    simulation::simulator::SimError,
+};
+
+type MyGenome = Vec<u8>;
+
+const POPULATION_SIZE: usize = 20;
+
+/// A selection operator that never selects any parents.
+#[derive(Clone, Debug, PartialEq)]
+struct NoParentsSelector;
+
+impl GeneticOperator for NoParentsSelector {
+    fn name() -> String {
+        "No-Parents-Selection".to_string()
+    }
+}
+
+impl SelectionOp<MyGenome, u32> for NoParentsSelector {
+    fn select_from<R>(
+        &self,
+        _: &EvaluatedPopulation<MyGenome, u32>,
+        _: &mut R,
+    ) -> Vec<Parents<MyGenome>>
+    where
+        R: Rng + Sized,
+    {
+        Vec::new()
+    }
+}
+
+fn initial_population() -> Population<MyGenome> {
+    build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .of_size(POPULATION_SIZE)
+        .using_seed([3; 32])
+}
+
+#[test]
+fn an_offspring_of_three_times_the_population_is_reduced_to_the_population_size() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(8, 10))
+        .with_selection(MaximizeSelector::new(0.3, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), true, 1.0))
+        .with_initial_population(initial_population())
+        .with_offspring_count(3 * POPULATION_SIZE)
+        .with_inspection(true)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(30))
+        .build_with_seed([42; 32]);
+
+    let best_fitness_values = run_generations(&mut simulation, |state| {
+        let inspection = state.result.inspection.as_ref().expect("inspection");
+        assert_that!(&inspection.offspring.len(), eq(3 * POPULATION_SIZE));
+        assert_that!(&inspection.children.len(), eq(3 * POPULATION_SIZE));
+        assert_that!(
+            &state.result.evaluated_population.individuals().len(),
+            eq(POPULATION_SIZE)
+        );
+        state.result.best_solution.solution.fitness
+    });
+    assert_that!(&best_fitness_values.len(), eq(30));
+    // the whole population is replaced by the best of the offspring as in a
+    // (mu, lambda) evolution strategy, which still makes progress
+    assert_that!(&best_fitness_values[29], gt(best_fitness_values[0]));
+}
+
+#[test]
+fn the_offspring_count_matches_the_offspring_required_by_the_generational_reinserter() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(8, 10))
+        .with_selection(MaximizeSelector::new(0.2, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(GenerationalReinserter::new())
+        .with_initial_population(initial_population())
+        .with_offspring_count(POPULATION_SIZE)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    let result = simulation.run().expect("simulation failed");
+
+    match result {
+        SimResult::Final(state, _, _, _) => {
+            assert_that!(&state.iteration, eq(10));
+            assert_that!(
+                &state.result.evaluated_population.individuals().len(),
+                eq(POPULATION_SIZE)
+            );
+        },
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    }
+}
+
+#[test]
+fn an_offspring_count_with_a_selection_without_parents_is_rejected() {
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(8, 10))
+        .with_selection(NoParentsSelector)
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), true, 1.0))
+        .with_initial_population(initial_population())
+        .with_offspring_count(3 * POPULATION_SIZE)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    match simulation.step() {
+        Err(SimError::AlgorithmError(error)) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::PopulationTooSmall(
+                "The selection operator No-Parents-Selection selected no parents from the \
+                 population of generation 1 with a size of 20."
+                    .to_string()
+            ))
+        ),
+        _ => panic!("the simulation should fail with an algorithm error"),
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    operator::{prelude::*, CrossoverOp, MutationOp},
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+};
+
+use std::fmt::Debug;
+
+type MyGenome = Vec<u8>;
+
+fn initial_population() -> Population<MyGenome> {
+    build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .of_size(20)
+        .using_seed([3; 32])
+}
+
+/// Runs the simulation with the given mutation and crossover operators and
+/// returns the best genome of each generation.
+fn run<M, C>(mutator: M, breeder: C) -> Vec<MyGenome>
+where
+    M: MutationOp<MyGenome> + Debug + PartialEq + Send + Sync,
+    C: CrossoverOp<MyGenome> + Debug + PartialEq + Send + Sync,
+{
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(8, 10))
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(breeder)
+        .with_mutation(mutator)
+        .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), false, 0.7))
+        .with_initial_population(initial_population())
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(20))
+        .build_with_seed([7; 32]);
+
+    run_generations(&mut simulation, |state| {
+        state.result.best_solution.solution.genome.clone()
+    })
+}
+
+#[test]
+fn a_constant_schedule_does_not_change_the_simulation() {
+    let unscheduled = run(
+        RandomValueMutator::new(0.2, 0, 10),
+        ProbabilisticCrossover::new(UniformCrossBreeder::new(), 0.8),
+    );
+    let scheduled = run(
+        ScheduledMutator::new(
+            RandomValueMutator::new(0.2, 0, 10),
+            ConstantSchedule::new(0.2),
+            20,
+        ),
+        ScheduledCrossover::new(
+            ProbabilisticCrossover::new(UniformCrossBreeder::new(), 0.8),
+            ConstantSchedule::new(0.8),
+            20,
+        ),
+    );
+
+    assert_that!(&scheduled, eq(unscheduled));
+}
+
+#[test]
+fn a_decaying_schedule_changes_the_simulation() {
+    let unscheduled = run(
+        RandomValueMutator::new(0.2, 0, 10),
+        UniformCrossBreeder::new(),
+    );
+    let scheduled = run(
+        ScheduledMutator::new(
+            RandomValueMutator::new(0.2, 0, 10),
+            LinearDecay::new(0.5, 0.01),
+            20,
+        ),
+        UniformCrossBreeder::new(),
+    );
+
+    assert_that!(&scheduled, not(eq(unscheduled)));
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator {
+    parallel: bool,
+}
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        self.parallel
+    }
+}
+
+fn run_simulation(parallel: bool) -> Vec<(u64, MyGenome, u32)> {
+    let evaluator = MyFitnessEvaluator { parallel };
+    let reinserter = ElitistReinserter::new(evaluator.clone(), true, 0.8);
+    let reinserter = if parallel {
+        reinserter.as_parallel()
+    } else {
+        reinserter
+    };
+
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(200)
+        .using_seed([7; 32]);
+
+    let algorithm = genetic_algorithm()
+        .with_evaluation(evaluator)
+        .with_selection(TournamentSelector::new(0.8, 2, 4, 0.9, false))
+        .with_crossover(MultiPointCrossBreeder::new(2))
+        .with_mutation(RandomValueMutator::new(0.1, -2.0, 2.0))
+        .with_reinsertion(reinserter)
+        .with_initial_population(initial_population)
+        .build();
+
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(20))
+        .build_with_seed([42; 32]);
+
+    let mut best_solutions = Vec::new();
+    while let SimResult::Intermediate(state) = simulation.step().expect("simulation step failed") {
+        let best = state.result.best_solution;
+        best_solutions.push((best.generation, best.solution.genome, best.solution.fitness));
+    }
+    best_solutions
+}
+
+#[test]
+fn parallel_and_sequential_evaluation_produce_the_same_results() {
+    let parallel = run_simulation(true);
+    let sequential = run_simulation(false);
+
+    assert_that!(&parallel.len(), eq(19));
+    assert_that!(&parallel, eq(sequential));
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::SumOfLoci;
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    algorithm::EvaluatedPopulation,
+    genetic::Parents,
+    operator::{prelude::*, CrossoverOp, GeneticOperator, SelectionOp},
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    random::Rng,
+};
+use std::sync::{Arc, Mutex};
+
+type MyGenome = Vec<u8>;
+
+/// The random numbers drawn by the operators.
+type Draws = Arc<Mutex<Vec<u64>>>;
+
+/// Wraps an operator and records a random number drawn before each call of
+/// the wrapped operator.
+#[derive(Clone, Debug)]
+struct Recording<O> {
+    operator: O,
+    draws: Draws,
+}
+
+impl<O> Recording<O> {
+    fn record<R>(&self, rng: &mut R)
+    where
+        R: Rng + Sized,
+    {
+        self.draws.lock().unwrap().push(rng.gen());
+    }
+}
+
+impl<O> GeneticOperator for Recording<O>
+where
+    O: GeneticOperator,
+{
+    fn name() -> String {
+        format!("Recording-{}", O::name())
+    }
+}
+
+impl<O> SelectionOp<MyGenome, u32> for Recording<O>
+where
+    O: SelectionOp<MyGenome, u32>,
+{
+    fn select_from<R>(
+        &self,
+        population: &EvaluatedPopulation<MyGenome, u32>,
+        rng: &mut R,
+    ) -> Vec<Parents<MyGenome>>
+    where
+        R: Rng + Sized,
+    {
+        self.record(rng);
+        self.operator.select_from(population, rng)
+    }
+}
+
+impl<O> CrossoverOp<MyGenome> for Recording<O>
+where
+    O: CrossoverOp<MyGenome>,
+{
+    fn crossover<R>(&self, parents: Parents<MyGenome>, rng: &mut R) -> Vec<MyGenome>
+    where
+        R: Rng + Sized,
+    {
+        self.record(rng);
+        self.operator.crossover(parents, rng)
+    }
+}
+
+/// Runs the simulation for 10 generations with the given mutation rate and
+/// returns the random numbers drawn by the selection and by the crossover
+/// operator.
+fn record_draws(mutation_rate: f64, phase_rngs: bool) -> (Vec<u64>, Vec<u64>) {
+    let selection_draws = Draws::default();
+    let crossover_draws = Draws::default();
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .of_size(20)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(SumOfLoci::new(8, 10))
+            .with_selection(Recording {
+                operator: TournamentSelector::new(0.7, 2, 3, 0.9, false),
+                draws: selection_draws.clone(),
+            })
+            .with_crossover(Recording {
+                operator: UniformCrossBreeder::new(),
+                draws: crossover_draws.clone(),
+            })
+            .with_mutation(RandomValueMutator::new(mutation_rate, 0, 10))
+            .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), false, 0.7))
+            .with_initial_population(initial_population)
+            .with_phase_rngs(phase_rngs)
+            .build(),
+    )
+    .until(GenerationLimit::new(10))
+    .build_with_seed([42; 32]);
+
+    simulation.run().expect("simulation failed");
+    let selection_draws = selection_draws.lock().unwrap().clone();
+    let crossover_draws = crossover_draws.lock().unwrap().clone();
+    (selection_draws, crossover_draws)
+}
+
+#[test]
+fn changing_the_mutation_does_not_change_the_draws_of_selection_and_crossover() {
+    let (selection_draws, crossover_draws) = record_draws(0.1, true);
+    let (other_selection_draws, other_crossover_draws) = record_draws(0.6, true);
+
+    assert_that!(&selection_draws.len(), eq(10));
+    assert_that!(&selection_draws, eq(other_selection_draws));
+    assert_that!(&crossover_draws.len(), eq(10 * 14));
+    assert_that!(&crossover_draws, eq(other_crossover_draws));
+}
+
+#[test]
+fn a_shared_rng_lets_the_mutation_change_the_draws_of_other_operators() {
+    let (selection_draws, crossover_draws) = record_draws(0.1, false);
+    let (other_selection_draws, other_crossover_draws) = record_draws(0.6, false);
+
+    assert_that!(&selection_draws, not(eq(other_selection_draws)));
+    assert_that!(&crossover_draws, not(eq(other_crossover_draws)));
+}
+
+#[test]
+fn phase_rngs_are_deterministic_for_a_given_seed() {
+    assert_that!(&record_draws(0.3, true), eq(record_draws(0.3, true)));
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    ga::State, operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*,
+    statistic::PhaseTimings,
+};
+use std::{thread, time::Duration};
+
+type MyGenome = Vec<f64>;
+
+/// A fitness function that takes at least one millisecond per individual.
+#[derive(Clone, Debug, PartialEq)]
+struct SlowFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for SlowFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        thread::sleep(Duration::from_millis(1));
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Runs the simulation for 5 generations and returns the state of each
+/// generation.
+fn run_generations(timings: bool) -> Vec<State<MyGenome, u32>> {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(20)
+        .using_seed([7; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(SlowFitnessEvaluator)
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+            .with_reinsertion(UniformReinserter::new(1.0))
+            .with_initial_population(initial_population)
+            .with_timings(timings)
+            .build(),
+    )
+    .until(GenerationLimit::new(5))
+    .build_with_seed([42; 32]);
+
+    let mut states = Vec::new();
+    loop {
+        match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => states.push(state.result),
+            SimResult::Final(state, _, _, _) => {
+                states.push(state.result);
+                break states;
+            },
+        }
+    }
+}
+
+#[test]
+fn the_evaluation_phase_dominates_with_a_slow_fitness_function() {
+    let states = run_generations(true);
+
+    assert_that!(&states.len(), eq(5));
+    for state in states {
+        let timings: PhaseTimings = state.phase_timings.expect("the timings are reported");
+        let evaluation = timings.evaluation.duration();
+        let others = timings.statistics
+            + timings.selection
+            + timings.crossover
+            + timings.mutation
+            + timings.reinsertion;
+        assert_that!(&evaluation.num_milliseconds(), is(greater_than_or_equal(1)));
+        assert_that!(&evaluation, is(greater_than(others.duration() * 10)));
+        assert_that!(&timings.total(), eq(state.processing_time));
+    }
+}
+
+#[test]
+fn the_timings_are_not_reported_by_default() {
+    let states = run_generations(false);
+
+    for state in states {
+        assert_that!(&state.phase_timings, eq(None));
+    }
+}
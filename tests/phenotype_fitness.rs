@@ -0,0 +1,107 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    genetic::{PhenotypeFitness, PhenotypeFitnessFunction},
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+const TARGET_TEXT: &str = "Hello genevo";
+
+type TextGenome = Vec<u8>;
+
+/// Evaluates the text spelled by a `TextGenome`.
+#[derive(Clone, Debug)]
+struct TextFitness;
+
+impl PhenotypeFitnessFunction<String, usize> for TextFitness {
+    fn fitness_of(&self, text: &String) -> usize {
+        text.chars()
+            .zip(TARGET_TEXT.chars())
+            .filter(|(c, t)| c == t)
+            .count()
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        TARGET_TEXT.len()
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+/// Decodes a `TextGenome` into the text it spells and records every decoded
+/// genome.
+#[derive(Clone, Debug, Default)]
+struct CountingDecoder {
+    decoded: Arc<Mutex<Vec<TextGenome>>>,
+}
+
+impl CountingDecoder {
+    fn take_decoded(&self) -> Vec<TextGenome> {
+        std::mem::take(&mut *self.decoded.lock().unwrap())
+    }
+}
+
+impl GenotypeToPhenotype<TextGenome, String> for CountingDecoder {
+    fn decode(&self, genome: &TextGenome) -> String {
+        self.decoded.lock().unwrap().push(genome.clone());
+        String::from_utf8(genome.clone()).unwrap()
+    }
+}
+
+#[test]
+fn each_individual_is_decoded_once_per_generation() {
+    let decoder = CountingDecoder::default();
+    let evaluator = PhenotypeFitness::new(decoder.clone(), TextFitness);
+    let population_size = 40;
+    let initial_population = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(TARGET_TEXT.len(), 32, 126))
+        .of_size(population_size)
+        .using_seed([3; 32]);
+
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(evaluator.clone())
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(MultiPointCrossBreeder::new(2))
+            .with_mutation(RandomValueMutator::new(0.05, 32, 126))
+            .with_reinsertion(ElitistReinserter::new(evaluator.clone(), true, 0.7))
+            .with_initial_population(initial_population)
+            .with_decoder(evaluator)
+            .build(),
+    )
+    .until(GenerationLimit::new(10))
+    .build_with_seed([42; 32]);
+
+    loop {
+        let result = simulation.step().expect("simulation failed");
+        let decoded = decoder.take_decoded();
+        let distinct: HashSet<&TextGenome> = decoded.iter().collect();
+        assert_that!(&distinct.len(), eq(decoded.len()));
+        match result {
+            SimResult::Intermediate(state) => {
+                let best_solution = state.result.best_solution;
+                assert_that!(
+                    &best_solution.phenotype,
+                    eq(String::from_utf8(best_solution.solution.genome).unwrap())
+                );
+            },
+            SimResult::Final(..) => break,
+        }
+    }
+}
@@ -64,6 +64,66 @@ fn create_population_of_custom_genotype_uniform_at_random() {
     assert_that!(&population.size(), eq(200));
 }
 
+#[test]
+fn create_population_with_initial_individuals() {
+    let heuristic_solution = vec![0.5, -0.5, 1.0, -1.0];
+    let other_solution = vec![0., 0., 0., 0.];
+    let initial_individuals = vec![
+        heuristic_solution.clone(),
+        other_solution.clone(),
+        heuristic_solution.clone(),
+    ];
+
+    let population: Population<Vec<f64>> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(200)
+        .with_initial_individuals(initial_individuals.clone())
+        .using_seed([42; 32]);
+
+    assert_that!(&population.size(), eq(200));
+    assert_that!(
+        &population.individuals()[..3].to_vec(),
+        eq(initial_individuals)
+    );
+    for individual in &population.individuals()[3..] {
+        assert_that!(&individual.len(), eq(4));
+    }
+}
+
+#[test]
+fn create_population_consisting_of_initial_individuals_only() {
+    let initial_individuals = vec![vec![true, false], vec![false, true]];
+
+    let population: Population<Vec<bool>> = build_population()
+        .with_genome_builder(BinaryEncodedGenomeBuilder::new(2))
+        .of_size(2)
+        .with_initial_individuals(initial_individuals.clone())
+        .uniform_at_random();
+
+    assert_that!(&population.individuals().to_vec(), eq(initial_individuals));
+}
+
+#[test]
+#[should_panic(expected = "the initial individual at index 1 is not a valid genome: [true]")]
+fn create_population_with_an_initial_individual_of_wrong_length_panics() {
+    build_population()
+        .with_genome_builder(BinaryEncodedGenomeBuilder::new(2))
+        .of_size(10)
+        .with_initial_individuals(vec![vec![true, true], vec![true]]);
+}
+
+#[test]
+#[should_panic(
+    expected = "the number of initial individuals 3 is greater than the size of the population 2"
+)]
+fn create_population_with_more_initial_individuals_than_its_size_panics() {
+    build_population()
+        .with_genome_builder(BinaryEncodedGenomeBuilder::new(1))
+        .of_size(2)
+        .with_initial_individuals(vec![vec![true]; 3])
+        .uniform_at_random();
+}
+
 #[cfg(feature = "fixedbitset")]
 mod fixedbitset_population_builder {
 
@@ -0,0 +1,161 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::SumOfLoci;
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    ga::State,
+    operator::prelude::*,
+    population::{PopulationGenerator, ValueEncodedGenomeBuilder},
+    prelude::*,
+    random::get_rng,
+    simulation::simulator::Simulator,
+};
+
+type MyGenome = Vec<u8>;
+
+type MyAlgorithm = GeneticAlgorithm<
+    MyGenome,
+    u32,
+    SumOfLoci,
+    MaximizeSelector,
+    UniformCrossBreeder,
+    RandomValueMutator<MyGenome>,
+    ElitistReinserter<MyGenome, u32, SumOfLoci>,
+>;
+
+fn generated_algorithm(population_size: usize) -> MyAlgorithm {
+    genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(8, 10))
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), false, 0.7))
+        .with_population_generator(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .with_population_size(population_size)
+        .build()
+}
+
+fn simulation(seed: [u8; 32]) -> Simulator<MyAlgorithm, GenerationLimit> {
+    simulate(generated_algorithm(30))
+        .until(GenerationLimit::new(5))
+        .build_with_seed(seed)
+}
+
+fn first_generation(
+    simulation: &mut Simulator<MyAlgorithm, GenerationLimit>,
+) -> State<MyGenome, u32> {
+    match simulation.step().expect("simulation step failed") {
+        SimResult::Intermediate(state) => state.result,
+        SimResult::Final(state, _, _, _) => state.result,
+    }
+}
+
+#[test]
+fn the_initial_population_is_generated_with_the_requested_size() {
+    let mut simulation = simulation([42; 32]);
+
+    let population_generator = simulation
+        .algorithm()
+        .population_generator()
+        .expect("the algorithm keeps the population generator");
+    assert_that!(&population_generator.population_size(), eq(30));
+    assert_that!(&simulation.algorithm().population().is_empty(), eq(true));
+
+    let state = first_generation(&mut simulation);
+
+    assert_that!(&state.evaluated_population.individuals().len(), eq(30));
+    for genome in state.evaluated_population.individuals().iter() {
+        assert_that!(&genome.len(), eq(8));
+    }
+}
+
+#[test]
+fn the_initial_population_is_determined_by_the_seed_of_the_simulation() {
+    let first = first_generation(&mut simulation([42; 32]));
+    let same_seed = first_generation(&mut simulation([42; 32]));
+    let other_seed = first_generation(&mut simulation([7; 32]));
+
+    assert_that!(
+        &same_seed.evaluated_population.individuals(),
+        eq(first.evaluated_population.individuals())
+    );
+    assert_that!(
+        &other_seed.evaluated_population.individuals(),
+        not(eq(first.evaluated_population.individuals()))
+    );
+}
+
+#[test]
+fn a_fresh_population_is_generated_after_a_reset() {
+    let mut simulation = simulation([42; 32]);
+    let first = first_generation(&mut simulation);
+
+    simulation.stop().expect("stop failed");
+    simulation.reset().expect("reset failed");
+    let restarted = first_generation(&mut simulation);
+
+    assert_that!(&restarted.evaluated_population.individuals().len(), eq(30));
+    assert_that!(
+        &restarted.evaluated_population.individuals(),
+        not(eq(first.evaluated_population.individuals()))
+    );
+}
+
+#[test]
+fn a_pre_built_initial_population_is_still_supported() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .of_size(20)
+        .using_seed([3; 32]);
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(8, 10))
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), false, 0.7))
+        .with_initial_population(initial_population.clone())
+        .build();
+
+    assert_that!(&algorithm.population_generator().is_none(), eq(true));
+    assert_that!(
+        &algorithm.population(),
+        eq(initial_population.individuals())
+    );
+}
+
+#[test]
+fn a_seeded_population_is_determined_by_the_master_seed() {
+    let generator: PopulationGenerator<MyGenome> =
+        PopulationGenerator::new(ValueEncodedGenomeBuilder::new(8, 0, 10), 120);
+
+    let first = generator.generate_seeded(&mut get_rng([42; 32]));
+    let same_seed = generator.generate_seeded(&mut get_rng([42; 32]));
+    let other_seed = generator.generate_seeded(&mut get_rng([7; 32]));
+
+    assert_that!(&first.size(), eq(120));
+    assert_that!(&same_seed.individuals(), eq(first.individuals()));
+    assert_that!(&other_seed.individuals(), not(eq(first.individuals())));
+}
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+#[test]
+fn the_parallel_generated_population_equals_the_sequential_one_for_any_number_of_threads() {
+    let generator: PopulationGenerator<MyGenome> =
+        PopulationGenerator::new(ValueEncodedGenomeBuilder::new(8, 0, 10), 500);
+    let sequential = generator.generate_seeded(&mut get_rng([42; 32]));
+
+    for num_threads in &[1, 2, 3, 8] {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(*num_threads)
+            .build()
+            .expect("thread pool could not be built");
+        let parallel = pool.install(|| generator.par_generate(&mut get_rng([42; 32])));
+
+        assert_that!(&parallel.individuals(), eq(sequential.individuals()));
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::SumOfLoci;
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    simulation::simulator::{GenerationCounter, Simulator},
+};
+use std::sync::{Arc, Mutex};
+
+type MyGenome = Vec<u8>;
+
+const POPULATION_SIZE: usize = 20;
+
+/// Builds random genomes and records each built genome.
+#[derive(Clone, Debug, Default)]
+struct RecordingBuilder {
+    built: Arc<Mutex<Vec<MyGenome>>>,
+}
+
+impl RecordingBuilder {
+    fn take_built(&self) -> Vec<MyGenome> {
+        std::mem::take(&mut *self.built.lock().unwrap())
+    }
+}
+
+impl GenomeBuilder<MyGenome> for RecordingBuilder {
+    fn build_genome<R>(&self, index: usize, rng: &mut R) -> MyGenome
+    where
+        R: Rng + Sized,
+    {
+        let genome: MyGenome = ValueEncodedGenomeBuilder::new(8, 0, 10).build_genome(index, rng);
+        self.built.lock().unwrap().push(genome.clone());
+        genome
+    }
+}
+
+type MyAlgorithm = GeneticAlgorithm<
+    MyGenome,
+    u32,
+    SumOfLoci,
+    MaximizeSelector,
+    UniformCrossBreeder,
+    RandomValueMutator<MyGenome>,
+    ElitistReinserter<MyGenome, u32, SumOfLoci>,
+>;
+
+fn simulation(builder: RecordingBuilder) -> Simulator<MyAlgorithm, GenerationLimit> {
+    simulate(
+        genetic_algorithm()
+            .with_evaluation(SumOfLoci::new(8, 10))
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+            .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), false, 0.7))
+            .with_population_generator(builder)
+            .with_population_size(POPULATION_SIZE)
+            .with_top_solutions(3)
+            .build(),
+    )
+    .until(GenerationLimit::new(100))
+    .build_with_seed([42; 32])
+}
+
+fn step(simulation: &mut Simulator<MyAlgorithm, GenerationLimit>) -> u64 {
+    match simulation.step().expect("simulation step failed") {
+        SimResult::Intermediate(state) => state.iteration,
+        SimResult::Final(state, _, _, _) => state.iteration,
+    }
+}
+
+#[test]
+fn a_restart_injects_the_best_solutions_into_a_freshly_generated_population() {
+    let builder = RecordingBuilder::default();
+    let mut simulation = simulation(builder.clone());
+    for _ in 0..10 {
+        step(&mut simulation);
+    }
+    let top_genomes: Vec<MyGenome> = simulation
+        .algorithm()
+        .top_solutions()
+        .iter()
+        .map(|top| top.solution.genome.clone())
+        .collect();
+    assert_that!(&top_genomes.len(), eq(3));
+    builder.take_built();
+
+    let restarted = simulation
+        .restart_preserving_best(3, GenerationCounter::Continue)
+        .expect("restart failed");
+
+    let built = builder.take_built();
+    let population = simulation.algorithm().population();
+    assert_that!(&restarted, eq(true));
+    assert_that!(&built.len(), eq(POPULATION_SIZE));
+    assert_that!(&population.len(), eq(POPULATION_SIZE));
+    assert_that!(&population[..3].to_vec(), eq(top_genomes));
+    for individual in &population[3..] {
+        assert_that!(&built.contains(individual), eq(true));
+    }
+}
+
+#[test]
+fn the_generation_counter_continues_after_a_restart() {
+    let mut simulation = simulation(RecordingBuilder::default());
+    for _ in 0..10 {
+        step(&mut simulation);
+    }
+
+    simulation
+        .restart_preserving_best(1, GenerationCounter::Continue)
+        .expect("restart failed");
+
+    assert_that!(&step(&mut simulation), eq(11));
+}
+
+#[test]
+fn the_generation_counter_starts_from_1_again_if_it_is_reset_by_a_restart() {
+    let mut simulation = simulation(RecordingBuilder::default());
+    let mut best_fitness = 0;
+    for _ in 0..10 {
+        if let SimResult::Intermediate(state) = simulation.step().expect("step failed") {
+            best_fitness = state.result.best_solution.solution.fitness;
+        }
+    }
+
+    simulation
+        .restart_preserving_best(1, GenerationCounter::Reset)
+        .expect("restart failed");
+
+    match simulation.step().expect("simulation step failed") {
+        SimResult::Intermediate(state) => {
+            assert_that!(&state.iteration, eq(1));
+            // the injected best solution is part of the fresh population
+            assert_that!(
+                &state.result.best_solution.solution.fitness,
+                geq(best_fitness)
+            );
+        },
+        SimResult::Final(..) => panic!("the simulation should not be finished"),
+    }
+}
+
+#[test]
+fn an_algorithm_built_with_an_initial_population_is_not_restarted() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .of_size(POPULATION_SIZE)
+        .using_seed([3; 32]);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(SumOfLoci::new(8, 10))
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+            .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), false, 0.7))
+            .with_initial_population(initial_population)
+            .build(),
+    )
+    .until(GenerationLimit::new(100))
+    .build_with_seed([42; 32]);
+    simulation.step().expect("simulation step failed");
+    let population = simulation.algorithm().population().to_vec();
+
+    let restarted = simulation
+        .restart_preserving_best(1, GenerationCounter::Continue)
+        .expect("restart failed");
+
+    assert_that!(&restarted, eq(false));
+    assert_that!(
+        &simulation.algorithm().population().to_vec(),
+        eq(population)
+    );
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    random::{SeedableRng, SplittableRng},
+};
+use rand::rngs::{SmallRng, StdRng};
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+type MyAlgorithm = GeneticAlgorithm<
+    MyGenome,
+    u32,
+    MyFitnessEvaluator,
+    TournamentSelector,
+    UniformCrossBreeder,
+    GaussianMutator<MyGenome>,
+    ElitistReinserter<MyGenome, u32, MyFitnessEvaluator>,
+>;
+
+fn algorithm() -> MyAlgorithm {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(100)
+        .using_seed([7; 32]);
+
+    genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.8, 2, 4, 0.9, false))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+        .with_initial_population(initial_population)
+        .build()
+}
+
+fn best_solutions<S>(mut simulation: S) -> Vec<(u64, MyGenome, u32)>
+where
+    S: Simulation<MyAlgorithm>,
+    S::Error: std::fmt::Debug,
+{
+    let mut best_solutions = Vec::new();
+    while let SimResult::Intermediate(state) = simulation.step().expect("simulation step failed") {
+        let best = state.result.best_solution;
+        best_solutions.push((best.generation, best.solution.genome, best.solution.fitness));
+    }
+    best_solutions
+}
+
+fn run_simulation(seed: u64) -> Vec<(u64, MyGenome, u32)> {
+    let simulation = simulate(algorithm())
+        .until(GenerationLimit::new(30))
+        .with_rng_seed(seed)
+        .build();
+    best_solutions(simulation)
+}
+
+fn run_simulation_with_rng<R>(rng: R) -> Vec<(u64, MyGenome, u32)>
+where
+    R: SplittableRng,
+{
+    let simulation = simulate(algorithm())
+        .until(GenerationLimit::new(30))
+        .build_with_rng(rng);
+    best_solutions(simulation)
+}
+
+#[test]
+fn simulations_with_the_same_rng_seed_produce_the_same_results() {
+    let first = run_simulation(42);
+    let second = run_simulation(42);
+
+    assert_that!(&first.len(), eq(29));
+    assert_that!(&first, eq(second));
+}
+
+#[test]
+fn simulations_with_different_rng_seeds_produce_different_results() {
+    let first = run_simulation(42);
+    let second = run_simulation(43);
+
+    assert_that!(&first, not(eq(second)));
+}
+
+#[test]
+fn simulation_can_be_built_with_a_std_rng() {
+    let first = run_simulation_with_rng(StdRng::seed_from_u64(42));
+    let second = run_simulation_with_rng(StdRng::seed_from_u64(42));
+    let third = run_simulation_with_rng(StdRng::seed_from_u64(43));
+
+    assert_that!(&first.len(), eq(29));
+    assert_that!(&first, eq(second));
+    assert_that!(&first, not(eq(third)));
+}
+
+#[test]
+fn simulation_can_be_built_with_a_small_rng() {
+    let first = run_simulation_with_rng(SmallRng::seed_from_u64(42));
+    let second = run_simulation_with_rng(SmallRng::seed_from_u64(42));
+
+    assert_that!(&first.len(), eq(29));
+    assert_that!(&first, eq(second));
+}
+
+#[test]
+fn building_with_a_seeded_prng_is_the_same_as_building_with_the_rng_seed() {
+    let simulation = simulate(algorithm())
+        .until(GenerationLimit::new(30))
+        .build_with_rng(Prng::seed_from_u64(42));
+
+    assert_that!(&best_solutions(simulation), eq(run_simulation(42)));
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{genotype::SelfAdaptive, operator::prelude::*, prelude::*};
+use std::collections::HashSet;
+
+type MyGenome = SelfAdaptive<Vec<u8>>;
+
+#[test]
+fn the_number_of_cut_points_varies_across_the_population_over_the_generations() {
+    let initial_population = Population::with_individuals(
+        (0..40)
+            .map(|index| SelfAdaptive::new(vec![(index % 5) as u8; 32], 1))
+            .collect::<Vec<MyGenome>>(),
+    );
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(32, 9))
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(SelfAdaptiveMultiPointCrossBreeder::new(8, 0.3))
+        .with_mutation(SelfAdaptiveMutator::new(RandomValueMutator::new(
+            0.05, 0, 9,
+        )))
+        .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(32, 9), false, 0.7))
+        .with_initial_population(initial_population)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(30))
+        .build_with_seed([42; 32]);
+
+    let mut all_cut_points = HashSet::new();
+    let num_distinct = run_generations(&mut simulation, |state| {
+        let cut_points: HashSet<usize> = state
+            .result
+            .evaluated_population
+            .individuals()
+            .iter()
+            .map(SelfAdaptive::num_cut_points)
+            .collect();
+        let num_distinct = cut_points.len();
+        all_cut_points.extend(cut_points);
+        num_distinct
+    });
+
+    // the number of cut points differs between the individuals
+    assert_that!(num_distinct.last().unwrap(), is(greater_than(1)));
+    // and evolves beyond the initial single cut point over the generations
+    assert_that!(&all_cut_points.len(), is(greater_than(2)));
+    for num_cut_points in &all_cut_points {
+        assert_that!(num_cut_points, geq(1));
+        assert_that!(num_cut_points, leq(8));
+    }
+}
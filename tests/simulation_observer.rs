@@ -0,0 +1,199 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    simulation::{observer::BestFitnessHistory, State},
+    termination::{StopFlag, StopReason},
+};
+use std::{cell::RefCell, rc::Rc};
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+type MyAlgorithm = GeneticAlgorithm<
+    MyGenome,
+    u32,
+    MyFitnessEvaluator,
+    TournamentSelector,
+    UniformCrossBreeder,
+    GaussianMutator<MyGenome>,
+    ElitistReinserter<MyGenome, u32, MyFitnessEvaluator>,
+>;
+
+fn algorithm() -> MyAlgorithm {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(50)
+        .using_seed([7; 32]);
+
+    genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.8, 2, 4, 0.9, false))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+        .with_initial_population(initial_population)
+        .build()
+}
+
+/// Records its notifications in a log shared with other observers and
+/// requests to stop the simulation in the given generation.
+#[derive(Clone, Debug)]
+struct RecordingObserver {
+    name: &'static str,
+    log: Rc<RefCell<Vec<String>>>,
+    stop_in_generation: Option<u64>,
+}
+
+impl SimulationObserver<MyAlgorithm> for RecordingObserver {
+    fn on_generation(&mut self, state: &State<MyAlgorithm>) -> StopFlag {
+        self.log
+            .borrow_mut()
+            .push(format!("{} generation {}", self.name, state.iteration));
+        if self.stop_in_generation == Some(state.iteration) {
+            StopFlag::StopNow(StopReason::Custom(format!(
+                "{} requested to stop",
+                self.name
+            )))
+        } else {
+            StopFlag::Continue
+        }
+    }
+
+    fn on_finish(&mut self, _: &SimResult<MyAlgorithm>) {
+        self.log
+            .borrow_mut()
+            .push(format!("{} finished", self.name));
+    }
+}
+
+fn recording_observer(
+    name: &'static str,
+    log: &Rc<RefCell<Vec<String>>>,
+    stop_in_generation: Option<u64>,
+) -> Box<RecordingObserver> {
+    Box::new(RecordingObserver {
+        name,
+        log: log.clone(),
+        stop_in_generation,
+    })
+}
+
+#[test]
+fn observers_are_notified_in_registration_order() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut simulation = simulate(algorithm())
+        .until(GenerationLimit::new(2))
+        .add_observer(recording_observer("first", &log, None))
+        .add_observer(recording_observer("second", &log, None))
+        .build_with_seed([42; 32]);
+
+    simulation.run().expect("simulation failed");
+
+    assert_that!(
+        &*log.borrow(),
+        eq(vec![
+            "first generation 1".to_string(),
+            "second generation 1".to_string(),
+            "first generation 2".to_string(),
+            "second generation 2".to_string(),
+            "first finished".to_string(),
+            "second finished".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn observer_can_stop_the_simulation_early() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut simulation = simulate(algorithm())
+        .until(GenerationLimit::new(100))
+        .add_observer(recording_observer("first", &log, None))
+        .add_observer(recording_observer("second", &log, Some(3)))
+        .build_with_seed([42; 32]);
+
+    let result = simulation.run().expect("simulation failed");
+
+    match result {
+        SimResult::Final(state, _, _, reason) => {
+            assert_that!(&state.iteration, eq(3));
+            assert_that!(
+                &reason,
+                eq(StopReason::Custom("second requested to stop".to_string()))
+            );
+        },
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    }
+    // the first observer has been notified before the simulation stopped
+    assert_that!(
+        &log.borrow().contains(&"first generation 3".to_string()),
+        is(eq(true))
+    );
+    assert_that!(
+        &log.borrow().last().cloned(),
+        eq(Some("second finished".to_string()))
+    );
+}
+
+#[test]
+fn observer_can_stop_a_simulation_run_in_step_mode() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut simulation = simulate(algorithm())
+        .until(GenerationLimit::new(100))
+        .add_observer(recording_observer("first", &log, Some(2)))
+        .build_with_seed([42; 32]);
+
+    let mut num_steps = 0;
+    while let SimResult::Intermediate(_) = simulation.step().expect("simulation step failed") {
+        num_steps += 1;
+    }
+
+    assert_that!(&num_steps, eq(1));
+}
+
+#[test]
+fn best_fitness_history_records_the_best_fitness_of_every_generation() {
+    let history = BestFitnessHistory::new();
+    let mut simulation = simulate(algorithm())
+        .until(GenerationLimit::new(10))
+        .add_observer(Box::new(history.clone()))
+        .build_with_seed([42; 32]);
+
+    let result = simulation.run().expect("simulation failed");
+
+    let recorded = history.history();
+    assert_that!(&recorded.len(), eq(10));
+    if let SimResult::Final(state, _, _, _) = result {
+        assert_that!(
+            &recorded.last().cloned(),
+            eq(Some(state.result.best_solution.solution.fitness))
+        );
+    }
+}
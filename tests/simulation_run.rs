@@ -0,0 +1,217 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    algorithm::BestSolution,
+    operator::prelude::*,
+    population::ValueEncodedGenomeBuilder,
+    prelude::*,
+    simulation::simulator::{SimError, Simulator},
+};
+use std::ops::ControlFlow;
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+type MyAlgorithm = GeneticAlgorithm<
+    MyGenome,
+    u32,
+    MyFitnessEvaluator,
+    TournamentSelector,
+    UniformCrossBreeder,
+    GaussianMutator<MyGenome>,
+    ElitistReinserter<MyGenome, u32, MyFitnessEvaluator>,
+>;
+
+fn algorithm(min_population_size: usize) -> MyAlgorithm {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(50)
+        .using_seed([7; 32]);
+
+    genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.8, 2, 4, 0.9, false))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+        .with_initial_population(initial_population)
+        .with_min_population_size(min_population_size)
+        .build()
+}
+
+fn simulation(min_population_size: usize) -> Simulator<MyAlgorithm, GenerationLimit> {
+    simulate(algorithm(min_population_size))
+        .until(GenerationLimit::new(20))
+        .build_with_seed([42; 32])
+}
+
+#[test]
+fn run_with_returns_the_same_result_as_stepping_manually() {
+    let mut stepped = simulation(2);
+    let expected = loop {
+        match stepped.step().expect("simulation step failed") {
+            SimResult::Intermediate(_) => {},
+            SimResult::Final(state, _, _, reason) => {
+                break (state.iteration, state.result.best_solution, reason)
+            },
+        }
+    };
+
+    let mut iterations = Vec::new();
+    let result = simulation(2)
+        .run_with(|state| iterations.push(state.iteration))
+        .expect("simulation failed");
+
+    match result {
+        SimResult::Final(state, _, _, reason) => {
+            assert_that!(&state.iteration, eq(expected.0));
+            assert_that!(&state.result.best_solution, eq(expected.1));
+            assert_that!(&reason, eq(expected.2));
+        },
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    }
+    assert_that!(&iterations, eq((1..=20).collect::<Vec<u64>>()));
+}
+
+#[test]
+fn run_reports_that_the_generation_limit_has_been_reached() {
+    let result = simulation(2).run().expect("simulation failed");
+
+    match result {
+        SimResult::Final(state, _, _, StopReason::GenerationLimitReached(message)) => {
+            assert_that!(&state.iteration, eq(20));
+            assert_that!(
+                &message,
+                eq(
+                    "Simulation stopped after the limit of 20 generations have been processed."
+                        .to_string()
+                )
+            );
+        },
+        SimResult::Final(_, _, _, reason) => panic!("unexpected stop reason: {:?}", reason),
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    }
+}
+
+#[test]
+fn run_with_returns_an_error_immediately() {
+    let mut num_callbacks = 0;
+    let result = simulation(100).run_with(|_| num_callbacks += 1);
+
+    match result {
+        Err(SimError::AlgorithmError(_)) => {},
+        _ => panic!("the simulation should fail with an algorithm error"),
+    }
+    assert_that!(&num_callbacks, eq(0));
+}
+
+#[test]
+fn run_with_stops_when_the_callback_breaks() {
+    let mut iterations = Vec::new();
+    let result = simulation(2)
+        .run_with(|state| {
+            iterations.push(state.iteration);
+            if state.iteration == 5 {
+                ControlFlow::Break(StopReason::Custom("Stopped by the callback".to_string()))
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .expect("simulation failed");
+
+    match result {
+        SimResult::Final(state, processing_time, _, reason) => {
+            assert_that!(&state.iteration, eq(5));
+            assert_that!(
+                &reason,
+                eq(StopReason::Custom("Stopped by the callback".to_string()))
+            );
+            assert_that!(
+                &processing_time.duration().num_nanoseconds(),
+                is(greater_than(Some(0)))
+            );
+        },
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    }
+    assert_that!(&iterations, eq((1..=5).collect::<Vec<u64>>()));
+}
+
+#[test]
+fn run_is_rejected_while_stepping() {
+    let mut simulation = simulation(2);
+    simulation.step().expect("simulation step failed");
+
+    match simulation.run() {
+        Err(SimError::SimulationAlreadyRunning(_)) => {},
+        _ => panic!("run should be rejected while the simulation is in step mode"),
+    }
+}
+
+/// Steps the simulation until it is finished and returns the iterations of
+/// the intermediate results and the final result.
+fn step_until_finished(
+    mut simulation: Simulator<MyAlgorithm, GenerationLimit>,
+) -> (Vec<u64>, u64, BestSolution<MyGenome, u32>) {
+    let mut intermediates = Vec::new();
+    loop {
+        match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => intermediates.push(state.iteration),
+            SimResult::Final(state, _, _, _) => {
+                break (intermediates, state.iteration, state.result.best_solution)
+            },
+        }
+    }
+}
+
+#[test]
+fn report_every_emits_one_intermediate_result_per_n_generations() {
+    let simulation = simulate(algorithm(2))
+        .until(GenerationLimit::new(35))
+        .report_every(10)
+        .build_with_seed([42; 32]);
+
+    let (intermediates, final_iteration, _) = step_until_finished(simulation);
+
+    assert_that!(&intermediates, eq(vec![10, 20, 30]));
+    assert_that!(&final_iteration, eq(35));
+}
+
+#[test]
+fn report_every_does_not_change_the_final_result() {
+    let throttled = simulate(algorithm(2))
+        .until(GenerationLimit::new(20))
+        .report_every(10)
+        .build_with_seed([42; 32]);
+
+    let (intermediates, final_iteration, best_solution) = step_until_finished(throttled);
+    let (_, expected_iteration, expected_best_solution) = step_until_finished(simulation(2));
+
+    assert_that!(&intermediates, eq(vec![10]));
+    assert_that!(&final_iteration, eq(expected_iteration));
+    assert_that!(&best_solution, eq(expected_best_solution));
+}
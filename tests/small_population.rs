@@ -0,0 +1,174 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    ga::GeneticAlgorithmError, operator::prelude::*, population::ValueEncodedGenomeBuilder,
+    prelude::*, simulation::simulator::SimError,
+};
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct MyFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for MyFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        (individual.iter().map(|x| 4. - x * x).sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        1600
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Runs the simulation of a population of the given size with parents of 3
+/// individuals and returns the error of the first generation.
+fn first_generation_error(
+    population_size: usize,
+    min_population_size: usize,
+) -> GeneticAlgorithmError {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(population_size)
+        .using_seed([7; 32]);
+    let algorithm = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.8, 3, 2, 0.9, false))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+        .with_initial_population(initial_population)
+        .with_min_population_size(min_population_size)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    match simulation.run() {
+        Err(SimError::AlgorithmError(error)) => error,
+        _ => panic!("the simulation should fail with an algorithm error"),
+    }
+}
+
+#[test]
+fn an_empty_population_is_rejected() {
+    let error = first_generation_error(0, 0);
+
+    assert_that!(
+        &error,
+        eq(GeneticAlgorithmError::EmptyPopulation(
+            "Population of generation 1 is empty. The required minimum size for populations \
+             is 0."
+                .to_string()
+        ))
+    );
+}
+
+#[test]
+fn a_population_of_one_individual_is_rejected() {
+    let error = first_generation_error(1, 1);
+
+    assert_that!(
+        &error,
+        eq(GeneticAlgorithmError::PopulationTooSmall(
+            "Population of generation 1 has a size of 1 which is smaller than the 3 \
+             individuals per parents selected by the selection operator Tournament-Selection."
+                .to_string()
+        ))
+    );
+}
+
+#[test]
+fn a_population_one_below_the_number_of_individuals_per_parents_is_rejected() {
+    let error = first_generation_error(2, 1);
+
+    assert_that!(
+        &error,
+        eq(GeneticAlgorithmError::PopulationTooSmall(
+            "Population of generation 1 has a size of 2 which is smaller than the 3 \
+             individuals per parents selected by the selection operator Tournament-Selection."
+                .to_string()
+        ))
+    );
+}
+
+#[test]
+fn a_population_below_the_minimum_size_is_rejected() {
+    let error = first_generation_error(1, 6);
+
+    assert_that!(
+        &error,
+        eq(GeneticAlgorithmError::PopulationTooSmall(
+            "Population of generation 1 has a size of 1 which is smaller than the required \
+             minimum size of 6"
+                .to_string()
+        ))
+    );
+}
+
+#[test]
+fn a_population_of_the_number_of_individuals_per_parents_is_processed() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(3)
+        .using_seed([7; 32]);
+    let algorithm = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.8, 3, 2, 0.9, false))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+        .with_initial_population(initial_population)
+        .with_min_population_size(1)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    let result = simulation.run();
+
+    assert_that!(&result.is_ok(), is(eq(true)));
+}
+
+#[test]
+fn a_selection_without_parents_is_rejected() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, -2., 2.))
+        .of_size(20)
+        .using_seed([7; 32]);
+    // 1% of a population of 20 individuals rounds to 0 parents
+    let algorithm = genetic_algorithm()
+        .with_evaluation(MyFitnessEvaluator)
+        .with_selection(MaximizeSelector::new(0.01, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(GaussianMutator::new(0.2, 0.1, -2.0, 2.0))
+        .with_reinsertion(ElitistReinserter::new(MyFitnessEvaluator, true, 0.8))
+        .with_initial_population(initial_population)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(10))
+        .build_with_seed([42; 32]);
+
+    match simulation.run() {
+        Err(SimError::AlgorithmError(error)) => assert_that!(
+            &error,
+            eq(GeneticAlgorithmError::PopulationTooSmall(
+                "The selection operator Maximizing-Truncation-Selection selected no parents \
+                 from the population of generation 1 with a size of 20."
+                    .to_string()
+            ))
+        ),
+        _ => panic!("the simulation should fail with an algorithm error"),
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{
+    ga::{State, TieBreak},
+    operator::prelude::*,
+    prelude::*,
+};
+use std::cmp::Ordering;
+
+type MyGenome = Vec<u8>;
+
+/// The fitness is the sum of the loci capped at 10, so that many individuals
+/// share the highest fitness value.
+#[derive(Clone, Debug, PartialEq)]
+struct CappedSum;
+
+impl FitnessFunction<MyGenome, u32> for CappedSum {
+    fn fitness_of(&self, genome: &MyGenome) -> u32 {
+        genome
+            .iter()
+            .map(|locus| *locus as u32)
+            .sum::<u32>()
+            .min(10)
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        10
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Prefers genomes with fewer loci that are not 0.
+fn fewer_non_zero_loci(genome: &MyGenome, other: &MyGenome) -> Ordering {
+    let count = |genome: &MyGenome| genome.iter().filter(|locus| **locus != 0).count();
+    count(genome).cmp(&count(other))
+}
+
+/// Runs the simulation and returns the state of each generation.
+fn run_generations(tie_break: Option<TieBreak<MyGenome>>) -> Vec<State<MyGenome, u32>> {
+    let initial_population = Population::with_individuals(vec![
+        vec![5, 5, 0],
+        vec![1, 0, 0],
+        vec![9, 0, 1],
+        vec![10, 0, 0],
+        vec![0, 0, 2],
+        vec![0, 3, 0],
+    ]);
+    let builder = genetic_algorithm()
+        .with_evaluation(CappedSum)
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(CappedSum, false, 0.7))
+        .with_initial_population(initial_population);
+    let builder = match tie_break {
+        Some(tie_break) => builder.with_tie_break(tie_break),
+        None => builder,
+    };
+    let mut simulation = simulate(builder.build())
+        .until(GenerationLimit::new(5))
+        .build_with_seed([42; 32]);
+
+    let mut states = Vec::new();
+    loop {
+        match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => states.push(state.result),
+            SimResult::Final(state, _, _, _) => {
+                states.push(state.result);
+                break states;
+            },
+        }
+    }
+}
+
+#[test]
+fn first_seen_reports_the_first_of_the_equally_fit_individuals() {
+    let states = run_generations(Some(TieBreak::FirstSeen));
+
+    assert_that!(&states[0].best_solution.solution.genome, eq(vec![5, 5, 0]));
+    for (index, state) in states.iter().enumerate() {
+        let best = &state.best_solution;
+        assert_that!(&best.generation, eq(index as u64 + 1));
+        assert_that!(&best.solution.fitness, eq(10));
+        let evaluated = &state.evaluated_population;
+        let first_index = evaluated
+            .fitness_values()
+            .iter()
+            .position(|fitness| *fitness == 10)
+            .unwrap();
+        assert_that!(
+            &best.solution.genome,
+            eq(evaluated.individuals()[first_index].clone())
+        );
+    }
+}
+
+#[test]
+fn first_seen_is_the_default_policy() {
+    let default: Vec<_> = run_generations(None)
+        .into_iter()
+        .map(|state| state.best_solution)
+        .collect();
+    let first_seen: Vec<_> = run_generations(Some(TieBreak::FirstSeen))
+        .into_iter()
+        .map(|state| state.best_solution)
+        .collect();
+
+    assert_that!(&default, eq(first_seen));
+}
+
+#[test]
+fn lowest_generation_keeps_reporting_the_solution_found_first() {
+    let states = run_generations(Some(TieBreak::LowestGeneration));
+
+    for state in &states {
+        let best = &state.best_solution;
+        assert_that!(&best.generation, eq(1));
+        assert_that!(&best.solution.genome, eq(vec![5, 5, 0]));
+        assert_that!(&best.solution.fitness, eq(10));
+    }
+}
+
+#[test]
+fn comparator_reports_the_least_of_the_equally_fit_individuals() {
+    let states = run_generations(Some(TieBreak::Comparator(fewer_non_zero_loci)));
+
+    assert_that!(&states[0].best_solution.solution.genome, eq(vec![10, 0, 0]));
+    for state in &states {
+        let best = &state.best_solution;
+        assert_that!(&best.solution.fitness, eq(10));
+        let evaluated = &state.evaluated_population;
+        for (genome, fitness) in evaluated
+            .individuals()
+            .iter()
+            .zip(evaluated.fitness_values())
+        {
+            if *fitness == 10 {
+                assert_that!(
+                    &fewer_non_zero_loci(&best.solution.genome, genome),
+                    not(eq(Ordering::Greater))
+                );
+            }
+        }
+    }
+}
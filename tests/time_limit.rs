@@ -0,0 +1,83 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+use std::{thread, time::Duration};
+
+type MyGenome = Vec<f64>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct SlowFitnessEvaluator;
+
+impl FitnessFunction<MyGenome, u32> for SlowFitnessEvaluator {
+    fn fitness_of(&self, individual: &MyGenome) -> u32 {
+        thread::sleep(Duration::from_millis(1));
+        (individual.iter().sum::<f64>() * 100. + 0.5).floor() as u32
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        (fitness_values.iter().sum::<u32>() as f64 / fitness_values.len() as f64 + 0.5).floor()
+            as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        400
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+
+    fn use_parallel_ga(&self) -> bool {
+        false
+    }
+}
+
+#[test]
+fn time_limit_stops_the_simulation_after_the_time_budget_is_spent() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(4, 0., 1.))
+        .of_size(10)
+        .using_seed([7; 32]);
+
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SlowFitnessEvaluator)
+        .with_selection(TournamentSelector::new(0.8, 2, 2, 0.9, false))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.1, 0., 1.))
+        .with_reinsertion(ElitistReinserter::new(SlowFitnessEvaluator, false, 0.8))
+        .with_initial_population(initial_population)
+        .build();
+
+    // the fitness limit is not reachable
+    let mut simulation = simulate(algorithm)
+        .until(or(
+            FitnessLimit::new(1_000),
+            TimeLimit::new(Duration::from_millis(100)),
+        ))
+        .with_rng_seed(42)
+        .build();
+
+    match simulation.run().expect("simulation failed") {
+        SimResult::Final(_, _, duration, stop_reason) => {
+            assert_that!(
+                &duration,
+                greater_than_or_equal(chrono::Duration::milliseconds(100))
+            );
+            assert_that!(
+                &matches!(stop_reason, StopReason::TimeLimit(_)),
+                is(eq(true))
+            );
+            assert_that!(
+                &stop_reason
+                    .to_string()
+                    .contains("exceeds the maximal runtime of 100ms"),
+                is(eq(true))
+            );
+        },
+        SimResult::Intermediate(_) => panic!("simulation did not finish"),
+    }
+}
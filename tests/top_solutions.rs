@@ -0,0 +1,181 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{ga::State, genetic::ReversalIdentity, operator::prelude::*, prelude::*};
+
+type MyGenome = Vec<u8>;
+
+/// A fitness landscape with a broad peak at `[2, 2]` and a narrow peak at
+/// `[8, 8]`, which is lower than the broad peak, but higher than its
+/// neighbors.
+#[derive(Clone, Debug, PartialEq)]
+struct TwoPeaks;
+
+impl FitnessFunction<MyGenome, u32> for TwoPeaks {
+    fn fitness_of(&self, genome: &MyGenome) -> u32 {
+        if genome == &vec![8, 8] {
+            90
+        } else {
+            let distance: u32 = genome
+                .iter()
+                .map(|locus| (*locus as i32 - 2).unsigned_abs())
+                .sum();
+            100u32.saturating_sub(15 * distance)
+        }
+    }
+
+    fn average(&self, fitness_values: &[u32]) -> u32 {
+        fitness_values.iter().sum::<u32>() / fitness_values.len() as u32
+    }
+
+    fn highest_possible_fitness(&self) -> u32 {
+        100
+    }
+
+    fn lowest_possible_fitness(&self) -> u32 {
+        0
+    }
+}
+
+/// Runs the simulation and returns the state of each generation.
+fn run_generations(top_solutions: Option<usize>) -> Vec<State<MyGenome, u32>> {
+    run_generations_with_identity(top_solutions, false)
+}
+
+/// Runs the simulation, optionally comparing the genomes by the
+/// `ReversalIdentity`, and returns the state of each generation.
+fn run_generations_with_identity(
+    top_solutions: Option<usize>,
+    reversal_identity: bool,
+) -> Vec<State<MyGenome, u32>> {
+    let initial_population = Population::with_individuals(vec![
+        vec![8, 8],
+        vec![9, 9],
+        vec![6, 7],
+        vec![5, 5],
+        vec![9, 4],
+        vec![7, 9],
+        vec![6, 6],
+        vec![5, 8],
+    ]);
+    let builder = genetic_algorithm()
+        .with_evaluation(TwoPeaks)
+        .with_selection(MaximizeSelector::new(0.5, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.3, 0, 9))
+        .with_reinsertion(UniformReinserter::new(1.0))
+        .with_initial_population(initial_population);
+    let builder = match top_solutions {
+        Some(count) => builder.with_top_solutions(count),
+        None => builder,
+    };
+    let builder = if reversal_identity {
+        builder.with_genome_identity(ReversalIdentity)
+    } else {
+        builder
+    };
+    let mut simulation = simulate(builder.build())
+        .until(GenerationLimit::new(30))
+        .build_with_seed([42; 32]);
+
+    let mut states = Vec::new();
+    loop {
+        match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => states.push(state.result),
+            SimResult::Final(state, _, _, _) => {
+                states.push(state.result);
+                break states;
+            },
+        }
+    }
+}
+
+#[test]
+fn both_peaks_are_among_the_top_solutions() {
+    let states = run_generations(Some(2));
+
+    let top_solutions = states.last().unwrap().top_solutions.clone().unwrap();
+    let genomes: Vec<MyGenome> = top_solutions
+        .iter()
+        .map(|top| top.solution.genome.clone())
+        .collect();
+    assert_that!(&genomes, eq(vec![vec![2, 2], vec![8, 8]]));
+    assert_that!(&top_solutions[1].solution.fitness, eq(90));
+    assert_that!(&top_solutions[1].generation, eq(1));
+}
+
+#[test]
+fn solutions_lost_from_the_population_stay_in_the_top_solutions() {
+    let states = run_generations(Some(2));
+
+    let narrow_peak = vec![8, 8];
+    let lost_in = states
+        .iter()
+        .position(|state| {
+            !state
+                .evaluated_population
+                .individuals()
+                .contains(&narrow_peak)
+        })
+        .expect("the narrow peak is lost from the population");
+    for state in &states[lost_in..] {
+        let top_solutions = state.top_solutions.as_ref().unwrap();
+        assert_that!(
+            &top_solutions
+                .iter()
+                .any(|top| top.solution.genome == narrow_peak),
+            eq(true)
+        );
+    }
+}
+
+#[test]
+fn top_solutions_are_distinct_and_ordered_by_fitness() {
+    for state in run_generations(Some(5)) {
+        let top_solutions = state.top_solutions.unwrap();
+        assert_that!(&top_solutions.len(), eq(5));
+        for (index, top) in top_solutions.iter().enumerate() {
+            for other in &top_solutions[index + 1..] {
+                assert_that!(&top.solution.genome, not(eq(other.solution.genome.clone())));
+                assert_that!(&top.solution.fitness, geq(other.solution.fitness));
+            }
+        }
+    }
+}
+
+#[test]
+fn top_solutions_contain_one_of_the_genomes_with_the_same_canonical_key() {
+    let mut found_reversed_pair = false;
+    for state in run_generations(Some(5)) {
+        let top_solutions = state.top_solutions.unwrap();
+        found_reversed_pair |= top_solutions.iter().any(|top| {
+            let reversed: MyGenome = top.solution.genome.iter().rev().cloned().collect();
+            reversed != top.solution.genome
+                && top_solutions
+                    .iter()
+                    .any(|other| other.solution.genome == reversed)
+        });
+    }
+    assert_that!(&found_reversed_pair, eq(true));
+
+    for state in run_generations_with_identity(Some(5), true) {
+        let top_solutions = state.top_solutions.unwrap();
+        for (index, top) in top_solutions.iter().enumerate() {
+            let reversed: MyGenome = top.solution.genome.iter().rev().cloned().collect();
+            for other in &top_solutions[index + 1..] {
+                assert_that!(&other.solution.genome, not(eq(reversed.clone())));
+                assert_that!(&other.solution.genome, not(eq(top.solution.genome.clone())));
+            }
+        }
+    }
+}
+
+#[test]
+fn top_solutions_are_not_tracked_by_default() {
+    for state in run_generations(None) {
+        assert_that!(&state.top_solutions, eq(None));
+    }
+}
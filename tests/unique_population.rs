@@ -0,0 +1,59 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::{run_generations, SumOfLoci};
+use galvanic_assert::matchers::*;
+
+use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+use std::collections::HashSet;
+
+type MyGenome = Vec<u8>;
+
+/// Runs one generation on a population of identical genomes and returns the
+/// individuals of the next generation.
+fn next_generation(enforce_uniqueness: bool) -> Vec<MyGenome> {
+    let genome_builder = ValueEncodedGenomeBuilder::new(8, 0u8, 255);
+    let mut reinserter = UniqueReinserter::new(
+        ElitistReinserter::new(SumOfLoci::new(8, 255), false, 0.5),
+        genome_builder,
+    );
+    reinserter.set_enforce_uniqueness(enforce_uniqueness);
+    let mut simulation = simulate(
+        genetic_algorithm()
+            .with_evaluation(SumOfLoci::new(8, 255))
+            .with_selection(MaximizeSelector::new(0.7, 2))
+            .with_crossover(UniformCrossBreeder::new())
+            .with_mutation(RandomValueMutator::new(0.0, 0, 255))
+            .with_reinsertion(reinserter)
+            .with_initial_population(Population::with_individuals(vec![vec![7; 8]; 20]))
+            .build(),
+    )
+    .until(GenerationLimit::new(2))
+    .build_with_seed([42; 32]);
+
+    run_generations(&mut simulation, |state| {
+        state.result.evaluated_population.individuals().to_vec()
+    })
+    .pop()
+    .expect("the simulation runs two generations")
+}
+
+#[test]
+fn a_population_of_identical_genomes_diversifies_after_one_generation() {
+    let individuals = next_generation(true);
+
+    let distinct: HashSet<&MyGenome> = individuals.iter().collect();
+    assert_that!(&individuals.len(), eq(20));
+    assert_that!(&distinct.len(), eq(20));
+    assert_that!(&individuals.contains(&vec![7; 8]), is(eq(true)));
+}
+
+#[test]
+fn the_population_stays_identical_if_uniqueness_is_not_enforced() {
+    let individuals = next_generation(false);
+
+    assert_that!(&individuals, eq(vec![vec![7; 8]; 20]));
+}
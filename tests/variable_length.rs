@@ -0,0 +1,91 @@
+#[cfg(test)]
+#[macro_use]
+extern crate galvanic_assert;
+
+use galvanic_assert::matchers::*;
+
+use genevo::{operator::prelude::*, prelude::*};
+
+type MyGenome = Vec<u8>;
+
+const MAX_LENGTH: usize = 16;
+
+/// Rewards genomes that spell out the target, whatever their length.
+#[derive(Clone, Debug, PartialEq)]
+struct MatchTarget {
+    target: Vec<u8>,
+}
+
+impl FitnessFunction<MyGenome, usize> for MatchTarget {
+    fn fitness_of(&self, genome: &MyGenome) -> usize {
+        let matches = genome
+            .iter()
+            .zip(self.target.iter())
+            .filter(|(gene, target)| gene == target)
+            .count();
+        let length_penalty = (genome.len() as isize - self.target.len() as isize).unsigned_abs();
+        (matches * 2).saturating_sub(length_penalty)
+    }
+
+    fn average(&self, fitness_values: &[usize]) -> usize {
+        fitness_values.iter().sum::<usize>() / fitness_values.len()
+    }
+
+    fn highest_possible_fitness(&self) -> usize {
+        self.target.len() * 2
+    }
+
+    fn lowest_possible_fitness(&self) -> usize {
+        0
+    }
+}
+
+#[test]
+fn genomes_of_varying_length_evolve_within_the_length_bounds() {
+    let fitness = MatchTarget {
+        target: vec![1, 2, 3, 4, 5, 6, 7, 8],
+    };
+    let initial_population = Population::with_individuals(
+        (0..40)
+            .map(|index| vec![(index % 9) as u8; 1 + index % 6])
+            .collect::<Vec<MyGenome>>(),
+    );
+    let algorithm = genetic_algorithm()
+        .with_evaluation(fitness.clone())
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(CutAndSpliceCrossBreeder::new(MAX_LENGTH))
+        .with_mutation(InsertDeleteMutator::new(0.1, 0.1, 1, MAX_LENGTH, 0, 9))
+        .with_reinsertion(ElitistReinserter::new(fitness, false, 0.7))
+        .with_initial_population(initial_population)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(100))
+        .build_with_seed([42; 32]);
+
+    let mut lengths = Vec::new();
+    let mut best_fitness = Vec::new();
+    loop {
+        let (state, done) = match simulation.step().expect("simulation step failed") {
+            SimResult::Intermediate(state) => (state, false),
+            SimResult::Final(state, _, _, _) => (state, true),
+        };
+        let individuals = state.result.evaluated_population.individuals();
+        lengths.extend(individuals.iter().map(Vec::len));
+        best_fitness.push(state.result.best_solution.solution.fitness);
+        if done {
+            break;
+        }
+    }
+
+    for length in &lengths {
+        assert_that!(length, is(greater_than(0)));
+        assert_that!(length, leq(MAX_LENGTH));
+    }
+    let min_length = *lengths.iter().min().unwrap();
+    let max_length = *lengths.iter().max().unwrap();
+    assert_that!(&min_length, is(less_than(max_length)));
+    assert_that!(
+        best_fitness.last().unwrap(),
+        is(greater_than(best_fitness[0]))
+    );
+}
@@ -0,0 +1,57 @@
+//! Runs a simulation on `wasm32-unknown-unknown`, e.g. by
+//! `wasm-pack test --node -- --no-default-features --test wasm`.
+#![cfg(target_arch = "wasm32")]
+
+#[macro_use]
+extern crate galvanic_assert;
+
+mod common;
+
+use common::SumOfLoci;
+use galvanic_assert::matchers::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use genevo::{operator::prelude::*, population::ValueEncodedGenomeBuilder, prelude::*};
+
+type MyGenome = Vec<u8>;
+
+#[wasm_bindgen_test]
+fn a_seeded_simulation_runs_a_few_generations() {
+    let initial_population: Population<MyGenome> = build_population()
+        .with_genome_builder(ValueEncodedGenomeBuilder::new(8, 0, 10))
+        .of_size(20)
+        .using_seed([42; 32]);
+    let algorithm = genetic_algorithm()
+        .with_evaluation(SumOfLoci::new(8, 10))
+        .with_selection(MaximizeSelector::new(0.7, 2))
+        .with_crossover(UniformCrossBreeder::new())
+        .with_mutation(RandomValueMutator::new(0.2, 0, 10))
+        .with_reinsertion(ElitistReinserter::new(SumOfLoci::new(8, 10), false, 0.7))
+        .with_initial_population(initial_population)
+        .build();
+    let mut simulation = simulate(algorithm)
+        .until(GenerationLimit::new(5))
+        .build_with_seed([42; 32]);
+
+    let mut generations = 0;
+    match simulation
+        .run_with(|_| generations += 1)
+        .expect("simulation failed")
+    {
+        SimResult::Final(state, _, _, stop_reason) => {
+            assert_that!(&state.result.best_solution.solution.fitness, leq(80));
+            assert_that!(
+                &matches!(stop_reason, StopReason::GenerationLimitReached(_)),
+                eq(true)
+            );
+            // without the `wasm-bindgen` feature there is no clock
+            #[cfg(not(feature = "wasm-bindgen"))]
+            assert_that!(
+                &state.processing_time,
+                eq(genevo::statistic::ProcessingTime::zero())
+            );
+        },
+        SimResult::Intermediate(_) => panic!("the simulation should be finished"),
+    }
+    assert_that!(&generations, eq(5));
+}